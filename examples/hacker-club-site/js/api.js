@@ -48,12 +48,14 @@ const CoterieAPI = {
      * Fetch public announcements
      * @param {Object} options - Query options
      * @param {number} options.limit - Maximum number of announcements to return
-     * @param {string} options.type - Filter by type (news, alert, update, etc.)
+     * @param {number} options.offset - Number of announcements to skip
+     * @param {string} options.type - Filter by type (News, Achievement, Meeting, CTFResult, General)
      * @returns {Promise<Array>} List of announcements
      */
-    async getAnnouncements({ limit = 10, type = null } = {}) {
+    async getAnnouncements({ limit = 10, offset = 0, type = null } = {}) {
         const params = new URLSearchParams();
         if (limit) params.set('limit', limit);
+        if (offset) params.set('offset', offset);
         if (type) params.set('type', type);
 
         const url = `${COTERIE_API_URL}/public/announcements?${params}`;
@@ -69,7 +71,9 @@ const CoterieAPI = {
                 throw new Error(`HTTP ${response.status}: ${response.statusText}`);
             }
 
-            return await response.json();
+            // Paged response: { items, total, limit, offset }
+            const page = await response.json();
+            return page.items;
         } catch (error) {
             console.error('Failed to fetch announcements:', error);
             throw error;
@@ -0,0 +1,37 @@
+-- Record of every outbound integration attempt (Discord role sync,
+-- channel posts, admin-alert email, ...). Operators debugging "why
+-- didn't this member get their role" need to see what we tried, when,
+-- and what came back — the tracing output is gone by the time anyone
+-- asks.
+--
+-- `event_payload` is the serialized IntegrationEvent so a failed entry
+-- can be replayed against the same integration. `replay_of` points a
+-- replay attempt at the row it retried; `replayed_at` marks the
+-- original so the admin UI can hide the button once it's been used.
+--
+-- Strings are redacted before insert (bot tokens, API keys); the
+-- payload is domain data only and never carries credentials.
+
+CREATE TABLE IF NOT EXISTS integration_log (
+    id TEXT PRIMARY KEY NOT NULL,
+    integration TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    target TEXT NOT NULL,
+    request_summary TEXT NOT NULL,
+    response_status TEXT NOT NULL CHECK (response_status IN ('ok', 'error')),
+    error TEXT,
+    event_payload TEXT NOT NULL,
+    replay_of TEXT REFERENCES integration_log(id) ON DELETE SET NULL,
+    replayed_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_log_created_at ON integration_log(created_at);
+CREATE INDEX IF NOT EXISTS idx_integration_log_integration ON integration_log(integration, response_status);
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('integrations.log_retention_days', '30', 'number', 'integrations',
+     'Days to keep entries in the outbound integration log before automatic deletion.',
+     0);
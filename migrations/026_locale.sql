@@ -0,0 +1,18 @@
+-- Display locale for dates, times, and amounts. `org.locale` is the
+-- default for every page and outgoing email; `members.locale` lets a
+-- member override it from their profile. NULL means "use the org
+-- default" so changing the org setting moves everyone who hasn't
+-- picked explicitly.
+--
+-- Values are BCP 47 tags understood by `domain::Locale` (en-US, en-GB,
+-- de-DE, fr-FR). Unknown values fall back to en-US at read time rather
+-- than failing the page.
+
+ALTER TABLE members ADD COLUMN locale TEXT;
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('org.locale', 'en-US', 'string', 'organization',
+     'Default display locale for dates and amounts (en-US, en-GB, de-DE, fr-FR). Members can override it on their profile.',
+     0);
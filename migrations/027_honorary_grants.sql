@@ -0,0 +1,19 @@
+-- Honorary / complimentary membership grants. Until now the only way
+-- to make a speaker or sponsor honorary was to flip `status` by hand
+-- and remember to tick bypass_dues; nothing recorded why, and a
+-- one-year comp had no way to end on its own.
+--
+-- `honorary_until` is NULL for a permanent grant. When set, the hourly
+-- sweep reverts the member once it passes: status goes back to Active
+-- or Expired depending on `dues_paid_until`, and bypass_dues is
+-- restored from `honorary_prev_bypass_dues` so an account that was
+-- already dues-exempt before the grant stays that way.
+--
+-- The reason and grantor live in the audit log, not here.
+
+ALTER TABLE members ADD COLUMN honorary_until DATETIME;
+ALTER TABLE members ADD COLUMN honorary_prev_bypass_dues INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_members_honorary_until
+    ON members(honorary_until)
+    WHERE honorary_until IS NOT NULL;
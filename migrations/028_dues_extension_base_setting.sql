@@ -0,0 +1,19 @@
+-- Which date a manual "extend dues by N months" counts from.
+--
+--   later  — later of today and the current expiry (the historical
+--            behaviour: current members stack, lapsed members restart
+--            from today)
+--   now    — always today; no stacking
+--   expiry — always the current expiry, even when lapsed, so the
+--            extension covers the gap
+--
+-- The admin member page can override this for a single extension.
+-- Filed under 'membership' rather than 'billing' so it shows on the
+-- settings page; the billing category holds runner internals.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('billing.dues_extension_base', 'later', 'string', 'membership',
+     'Base date for manual dues extensions: later (of today or current expiry), now, or expiry.',
+     0);
@@ -0,0 +1,21 @@
+-- Scheduled in-app database backups. Off by default: deployments that
+-- already run deploy/backup.sh from a systemd timer don't need a
+-- second copy. When enabled, the app takes a `VACUUM INTO` snapshot
+-- into `{data_dir}/backups/app` whenever the newest one is older than
+-- `backup.interval_hours`, keeping the newest `backup.keep` files.
+--
+-- On-demand backups from /portal/admin/backup work regardless and are
+-- rotated by the same `backup.keep` the next time the job runs.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('backup.scheduled_enabled', 'false', 'boolean', 'backup',
+     'Take database backups automatically on a schedule.',
+     0),
+    ('backup.interval_hours', '24', 'number', 'backup',
+     'Hours between scheduled database backups.',
+     0),
+    ('backup.keep', '7', 'number', 'backup',
+     'Number of in-app backups to keep; older ones are deleted after each scheduled backup.',
+     0);
@@ -0,0 +1,15 @@
+-- Member-proposed events. An Active member can submit an event from
+-- the portal; it lands as `Proposed` and stays out of every public and
+-- member listing until an admin approves it (→ `Published`) or rejects
+-- it with feedback (→ `Rejected`). Existing rows are all admin-created
+-- and therefore already published.
+--
+-- The proposer is the row's `created_by`. `review_feedback` carries the
+-- admin's note on rejection (or an optional note on approval) so the
+-- proposer can see it later; who decided and when is in the audit log.
+
+ALTER TABLE events ADD COLUMN status TEXT NOT NULL DEFAULT 'Published'
+    CHECK (status IN ('Published', 'Proposed', 'Rejected'));
+ALTER TABLE events ADD COLUMN review_feedback TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_events_status ON events(status);
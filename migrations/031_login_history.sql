@@ -0,0 +1,40 @@
+-- One row per successful member login, shown to the member on
+-- /portal/profile/sessions so they can spot sign-ins they don't
+-- recognize.
+--
+-- Kept deliberately thin: the raw IP and user-agent are what the
+-- member needs to recognize their own devices, `location` is a coarse
+-- "City, Country" only when a GeoIP database is configured, and rows
+-- are pruned hourly after `auth.login_history_retention_days`.
+--
+-- `device_fingerprint` is a hash of the user-agent plus the network
+-- prefix (IPv4 /24, IPv6 /48), not the exact address, so a phone
+-- hopping between DHCP leases on the same network doesn't read as a
+-- new device. A login whose fingerprint the member has never used
+-- before sets `new_device` and triggers the alert email.
+
+CREATE TABLE IF NOT EXISTS login_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    ip_address TEXT NOT NULL,
+    user_agent TEXT,
+    location TEXT,
+    device_fingerprint TEXT NOT NULL,
+    method TEXT NOT NULL CHECK (method IN ('password', 'totp', 'recovery_code', 'api')),
+    new_device INTEGER NOT NULL DEFAULT 0,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_history_member ON login_history(member_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_login_history_fingerprint ON login_history(member_id, device_fingerprint);
+CREATE INDEX IF NOT EXISTS idx_login_history_created_at ON login_history(created_at);
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('auth.login_history_retention_days', '90', 'number', 'auth',
+     'Days to keep member login history before automatic deletion.',
+     0),
+    ('auth.new_device_alerts', 'true', 'boolean', 'auth',
+     'Email members when their account is signed in from a device or network they have not used before.',
+     0);
@@ -0,0 +1,50 @@
+-- Guest RSVPs for open events. An admin can flag a Public event as
+-- accepting RSVPs from non-members; the public site then posts a name
+-- and email to `/public/events/rsvp` and a lightweight guest row lands
+-- in `event_attendance` alongside the member RSVPs — no account, no
+-- password, nothing in `members`.
+--
+-- `event_attendance` keyed on (event_id, member_id) with member_id
+-- NOT NULL, so a guest row needs the table-rewrite recipe (same shape
+-- as migrations 016 and 021):
+--   * `member_id` becomes nullable. NULL means a guest, and then
+--     `guest_name` / `guest_email` must both be present.
+--   * The primary key moves to the implicit rowid; a UNIQUE on
+--     (event_id, member_id) keeps "one row per member per event" and
+--     the `ON CONFLICT (event_id, member_id)` upsert working. SQLite
+--     treats NULLs as distinct there, so guests need their own partial
+--     unique index on (event_id, guest_email). Emails are stored
+--     lowercased so that index catches "Ann@x" vs "ann@x".
+--
+-- Guests count toward `max_attendees` like anyone else. They get no
+-- reminder emails: the reminder runner joins `members`.
+
+ALTER TABLE events ADD COLUMN allow_guest_rsvp BOOLEAN NOT NULL DEFAULT 0;
+
+PRAGMA defer_foreign_keys = ON;
+
+CREATE TABLE event_attendance_new (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    member_id TEXT REFERENCES members(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK(status IN ('Registered', 'Waitlisted', 'Cancelled')),
+    registered_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    attended BOOLEAN NOT NULL DEFAULT 0,
+    -- Carried forward from migration 022
+    reminder_sent_at DATETIME,
+    guest_name TEXT,
+    guest_email TEXT,
+    UNIQUE (event_id, member_id),
+    CHECK (member_id IS NOT NULL OR (guest_name IS NOT NULL AND guest_email IS NOT NULL))
+);
+
+INSERT INTO event_attendance_new (
+    event_id, member_id, status, registered_at, attended, reminder_sent_at
+)
+SELECT event_id, member_id, status, registered_at, attended, reminder_sent_at
+FROM event_attendance;
+
+DROP TABLE event_attendance;
+ALTER TABLE event_attendance_new RENAME TO event_attendance;
+
+CREATE UNIQUE INDEX idx_event_attendance_guest
+    ON event_attendance(event_id, guest_email) WHERE member_id IS NULL;
@@ -0,0 +1,10 @@
+-- Optional expiry for time-sensitive announcements ("volunteers needed
+-- by Saturday"). Once `expires_at` has passed the announcement drops
+-- out of the public list, the RSS feed, the members-only count and the
+-- member portal, with no job needed: those queries filter on the
+-- current time. Admins still see and edit expired rows.
+--
+-- NULL means the announcement never expires, which is every existing
+-- row.
+
+ALTER TABLE announcements ADD COLUMN expires_at DATETIME;
@@ -0,0 +1,10 @@
+-- Contact visibility, separate from directory visibility. A member who
+-- opts into the directory (`show_in_directory`) is listed to other
+-- members with their name and membership details; their email is shown
+-- only if they also set `show_contact`. Otherwise other members get a
+-- "contact via admin" link instead.
+--
+-- Defaults to hidden, so nobody's email becomes visible by this
+-- migration alone.
+
+ALTER TABLE member_profiles ADD COLUMN show_contact BOOLEAN NOT NULL DEFAULT 0;
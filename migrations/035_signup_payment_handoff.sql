@@ -0,0 +1,21 @@
+-- Post-signup payment handoff.
+--
+-- `membership.require_payment_for_activation` has been seeded since
+-- 001 but nothing read it. It now gates the handoff: when 'true' and
+-- the chosen membership type has a fee, /public/signup either returns
+-- a Stripe Checkout URL (member activates when the webhook reports the
+-- session paid) or, without Stripe, these offline instructions plus an
+-- admin alert. When 'false', signup is unchanged and admins activate
+-- by hand.
+--
+-- INSERT OR IGNORE so re-applying against a hand-edited DB stays
+-- idempotent.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('membership.offline_payment_instructions',
+     'Please pay your first dues by cash or bank transfer. An administrator will activate your membership once payment is received.',
+     'string', 'membership',
+     'Shown to new members after signup when online payment is not available.',
+     0);
@@ -0,0 +1,42 @@
+-- Composite indexes for the hottest read paths.
+--
+-- The single-column indexes from 001 already cover lookups on
+-- members.email/username/status, payments.member_id and the *_types
+-- slugs (email, username, slug and stripe_payment_id are also UNIQUE,
+-- so SQLite keeps an autoindex on each). What they don't cover is the
+-- common "filter, then order by time" shape: with only idx_events_status
+-- to pick from, the upcoming-events query walks every Published event
+-- and sorts them in a temp B-tree. Each index below leads with the
+-- equality columns and ends with the ordering column so the planner
+-- can range-scan and read rows back already sorted.
+--
+-- tests/query_plan_test.rs pins the plans for the member-by-email and
+-- upcoming-events lookups.
+
+-- Upcoming events (portal dashboard, /public/events, iCal feed).
+CREATE INDEX IF NOT EXISTS idx_events_status_start
+    ON events(status, start_time);
+
+-- Upcoming events filtered by visibility (public listing, members-only
+-- count on the marketing site).
+CREATE INDEX IF NOT EXISTS idx_events_status_visibility_start
+    ON events(status, visibility, start_time);
+
+-- Public / members-only announcement lists, newest first.
+CREATE INDEX IF NOT EXISTS idx_announcements_public_published
+    ON announcements(is_public, published_at);
+
+-- Featured announcements are a handful of rows; a partial index keeps
+-- them cheap to pick out without indexing the rest.
+CREATE INDEX IF NOT EXISTS idx_announcements_featured
+    ON announcements(published_at)
+    WHERE featured = 1;
+
+-- A member's payment history, newest first.
+CREATE INDEX IF NOT EXISTS idx_payments_member_created
+    ON payments(member_id, created_at);
+
+-- A member's RSVPs. UNIQUE (event_id, member_id) only serves lookups
+-- that start from the event.
+CREATE INDEX IF NOT EXISTS idx_event_attendance_member
+    ON event_attendance(member_id);
@@ -0,0 +1,13 @@
+-- Calendar invites for member RSVPs.
+--
+-- Each RSVP confirmation, event update and cancellation email carries
+-- an .ics invite for the same UID (the event id). Calendar apps only
+-- replace an entry when the new invite's SEQUENCE is higher than the
+-- one they already have, so we track the last sequence sent per
+-- attendance row. NULL means no invite has gone out yet; the first
+-- one is sent as 0.
+--
+-- Kept across cancel/re-RSVP: the row is upserted rather than
+-- deleted, so a fresh REQUEST after a CANCEL still outranks it.
+
+ALTER TABLE event_attendance ADD COLUMN invite_sequence INTEGER;
@@ -0,0 +1,25 @@
+-- Admin-configurable signup fields.
+--
+-- `membership.signup_fields` is a JSON array of extra fields for the
+-- public signup form (see `domain::signup_field`). Each entry has a
+-- key, a label, an optional `required` flag and an optional `kind`
+-- (text, textarea, url). Keys naming a member_profiles column are
+-- stored there; anything else is a custom field stored below.
+--
+-- Empty by default, so signup asks for exactly what it did before.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('membership.signup_fields', '[]', 'json', 'membership',
+     'Extra signup form fields as JSON, e.g. [{"key": "pronouns", "label": "Pronouns", "required": true}]. Keys bio, blog_url and github_username fill in the member profile.',
+     0);
+
+CREATE TABLE IF NOT EXISTS member_custom_fields (
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    field_key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (member_id, field_key)
+);
@@ -0,0 +1,29 @@
+-- Member comment threads on announcements.
+--
+-- Off unless an admin turns on `comments_enabled` for an announcement.
+-- Comments are readable by whoever can read the announcement itself:
+-- anyone for public ones, signed-in members for members-only ones.
+--
+-- `body` is the Markdown the member typed; it's escaped and rendered
+-- to a small safe subset on display, never stored as HTML. Admins can
+-- hide a comment (reversible, `hidden_at`/`hidden_by`) or delete it
+-- (`deleted_at`, soft so the audit trail still points at something).
+
+ALTER TABLE announcements ADD COLUMN comments_enabled INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS comments (
+    id TEXT PRIMARY KEY NOT NULL,
+    announcement_id TEXT NOT NULL REFERENCES announcements(id) ON DELETE CASCADE,
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    hidden_at DATETIME,
+    hidden_by TEXT REFERENCES members(id) ON DELETE SET NULL,
+    deleted_at DATETIME,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_comments_announcement
+    ON comments(announcement_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_comments_member
+    ON comments(member_id, created_at);
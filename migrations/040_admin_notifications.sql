@@ -0,0 +1,46 @@
+-- Admin notification routing.
+--
+-- Some events (a new signup, a failed payment, a submitted event
+-- proposal) are addressed to the admins themselves rather than the
+-- org-wide alert channels. `notifications.admin_events` picks which
+-- of those notify, `notifications.admin_recipients` narrows them to
+-- a subset of admins, and `notifications.admin_digest` holds them
+-- for one daily email/DM instead of sending each as it happens.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('notifications.admin_events', 'new_signup,payment_failed,proposal_submitted', 'string', 'notifications',
+     'Events that notify admins directly, comma-separated: new_signup, payment_failed, proposal_submitted. Leave empty to turn admin notifications off.',
+     0),
+    ('notifications.admin_recipients', '', 'string', 'notifications',
+     'Admins to notify, as comma-separated usernames or emails. Leave empty to notify every admin.',
+     0),
+    ('notifications.admin_digest', 'false', 'boolean', 'notifications',
+     'Batch admin notifications into one daily digest per admin instead of sending each as it happens.',
+     0);
+
+-- Each admin's delivery channel. No row means email.
+CREATE TABLE IF NOT EXISTS admin_notification_preferences (
+    member_id TEXT PRIMARY KEY REFERENCES members(id) ON DELETE CASCADE,
+    channel TEXT NOT NULL DEFAULT 'email' CHECK (channel IN ('email', 'discord', 'none')),
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- One row per (admin, event). The unique key is what dedupes a
+-- redelivered webhook or a retried job; `sent_at` stays NULL while
+-- the row waits for the next digest.
+CREATE TABLE IF NOT EXISTS admin_notifications (
+    id TEXT PRIMARY KEY,
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    dedupe_key TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    sent_at DATETIME,
+    UNIQUE (member_id, dedupe_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_notifications_pending
+    ON admin_notifications(member_id, sent_at);
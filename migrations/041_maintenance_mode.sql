@@ -0,0 +1,20 @@
+-- Maintenance mode.
+--
+-- While `maintenance.enabled` is on, every route except login, health
+-- checks, static assets and the Stripe webhook answers with a 503
+-- maintenance page showing `maintenance.message`. Logged-in admins and
+-- requests from `maintenance.allowed_ips` pass through so operators
+-- can check the upgrade and switch the flag back off.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('maintenance.enabled', 'false', 'boolean', 'maintenance',
+     'Show the maintenance page instead of the site. Admins who are logged in (or log in at /login) still get through.',
+     0),
+    ('maintenance.message', 'We''re carrying out scheduled maintenance and will be back shortly.', 'string', 'maintenance',
+     'Message shown on the maintenance page.',
+     0),
+    ('maintenance.allowed_ips', '', 'string', 'maintenance',
+     'Client IPs that bypass maintenance mode, comma-separated. Behind a reverse proxy this needs server.trust_forwarded_for.',
+     0);
@@ -0,0 +1,12 @@
+-- Registration groups.
+--
+-- Events that share a `registration_group` key allow a member one
+-- active (non-cancelled) RSVP across the whole group — one CTF team
+-- slot, one of several parallel workshops. NULL means the event is
+-- independent, which is every event that existed before this.
+
+ALTER TABLE events ADD COLUMN registration_group TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_events_registration_group
+    ON events(registration_group)
+    WHERE registration_group IS NOT NULL;
@@ -0,0 +1,12 @@
+-- Default image for link previews.
+--
+-- The public share pages (/events/:id, /announcements/:id) emit Open
+-- Graph and Twitter Card tags. Items with their own image use it;
+-- everything else falls back to this one. Empty means no image tag.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('org.share_image_url', '', 'string', 'organization',
+     'Default image for link previews of shared events and announcements (absolute URL or /uploads/... path).',
+     0);
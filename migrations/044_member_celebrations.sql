@@ -0,0 +1,32 @@
+-- Birthday and membership-anniversary shout-outs.
+--
+-- Members can add a birthday (month and day only; no year, so no age)
+-- on their profile and opt out of shout-outs altogether. Anniversaries
+-- come from `members.joined_at`. An hourly sweep posts to Discord
+-- and/or emails the member on the day, per the toggles below.
+
+ALTER TABLE member_profiles ADD COLUMN birthday TEXT; -- 'MM-DD'
+ALTER TABLE member_profiles ADD COLUMN celebrate BOOLEAN NOT NULL DEFAULT 1;
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('celebrations.discord_enabled', 'false', 'boolean', 'celebrations',
+     'Post birthday and membership-anniversary shout-outs to Discord.',
+     0),
+    ('celebrations.discord_channel_id', '', 'string', 'celebrations',
+     'Discord channel for shout-outs. Leave empty to use the announcements channel.',
+     0),
+    ('celebrations.email_enabled', 'false', 'boolean', 'celebrations',
+     'Email members on their birthday and membership anniversary.',
+     0);
+
+-- One row per shout-out sent. The key is what stops the hourly sweep
+-- from celebrating anyone twice in a year.
+CREATE TABLE IF NOT EXISTS member_celebrations (
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('birthday', 'anniversary')),
+    year INTEGER NOT NULL,
+    sent_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (member_id, kind, year)
+);
@@ -0,0 +1,13 @@
+-- Whether public signup says which field is already taken.
+--
+-- Off (the default), a signup that collides with an existing email or
+-- username gets a generic 409, so the endpoint can't be used to check
+-- who has an account. On, the 409 names the field like the other
+-- signup field errors, which is friendlier for orgs that don't mind.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('membership.signup_duplicate_field_errors', 'false', 'boolean', 'membership',
+     'Tell people signing up whether their email or username is already taken. Off gives a generic error that does not reveal who has an account.',
+     0);
@@ -0,0 +1,21 @@
+-- Where members land after signing in.
+--
+-- One club-wide default for members and one for admins (admins often
+-- want the admin member list, some clubs want everyone on Events), plus
+-- an optional per-member override on the profile. A login that carries
+-- a `redirect` still goes there instead. Values are `LandingPage`
+-- strings; admin-only pages are refused for the member default and
+-- fall back to the dashboard for a non-admin override.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('auth.member_landing_page', 'dashboard', 'string', 'auth',
+     'Page members land on after signing in: dashboard, events, announcements or directory.',
+     0),
+    ('auth.admin_landing_page', 'dashboard', 'string', 'auth',
+     'Page admins land on after signing in: dashboard, events, announcements, directory, admin_members or admin_billing.',
+     0);
+
+-- NULL follows the club default.
+ALTER TABLE member_profiles ADD COLUMN landing_page TEXT;
@@ -0,0 +1,8 @@
+-- RSVP deadlines.
+--
+-- When set, members (and public guests) can RSVP only until this
+-- instant, which is never later than the event's start. Admins can
+-- still register a member afterwards from the event page. NULL keeps
+-- the old behavior: RSVPs stay open until the event starts.
+
+ALTER TABLE events ADD COLUMN rsvp_deadline DATETIME;
@@ -0,0 +1,40 @@
+-- "Contact the admins" form.
+--
+-- Anyone without an account (a prospective member, or someone locked
+-- out) can send the admins a message from /contact, linked from the
+-- login page, or from the public site via POST /public/contact. Each
+-- message lands in `contact_submissions`, the admin contact inbox,
+-- and raises a `contact_submitted` admin notification.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('features.contact_form_enabled', 'true', 'boolean', 'features',
+     'Let visitors without an account message the admins from the login page and the public site.',
+     0);
+
+-- Installs still on the stock event list pick up the new kind; a list
+-- an admin has already edited is left as they set it.
+UPDATE app_settings
+SET value = 'new_signup,payment_failed,proposal_submitted,contact_submitted'
+WHERE key = 'notifications.admin_events'
+  AND value = 'new_signup,payment_failed,proposal_submitted';
+
+UPDATE app_settings
+SET description = 'Events that notify admins directly, comma-separated: new_signup, payment_failed, proposal_submitted, contact_submitted. Leave empty to turn admin notifications off.'
+WHERE key = 'notifications.admin_events';
+
+CREATE TABLE IF NOT EXISTS contact_submissions (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL,
+    category TEXT NOT NULL CHECK (category IN ('general', 'membership', 'cant_log_in')),
+    message TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Set when an admin marks the message dealt with.
+    handled_at DATETIME,
+    handled_by TEXT REFERENCES members(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_submissions_open
+    ON contact_submissions(handled_at, created_at);
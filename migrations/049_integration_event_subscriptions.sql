@@ -0,0 +1,24 @@
+-- Per-integration event subscriptions.
+--
+-- Each integration is sent every event kind it handles unless an
+-- admin narrows it here: a comma-separated list of member_activated,
+-- member_expired, member_updated, event_published,
+-- announcement_published, admin_alert, admin_notification,
+-- member_celebration. Empty keeps the integration's default (all).
+-- Read on every dispatch, so changes apply without a restart.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('integrations.discord.events', '', 'string', 'integrations',
+     'Event kinds sent to Discord, comma-separated (e.g. member_activated,member_expired,member_updated). Empty sends all.',
+     0),
+    ('integrations.admin_alert_email.events', '', 'string', 'integrations',
+     'Event kinds sent to the admin alert email, comma-separated. Empty sends all.',
+     0),
+    ('integrations.admin_notifications.events', '', 'string', 'integrations',
+     'Event kinds routed to per-admin notifications, comma-separated. Empty sends all.',
+     0),
+    ('integrations.unifi.events', '', 'string', 'integrations',
+     'Event kinds sent to Unifi access control, comma-separated. Empty sends all.',
+     0);
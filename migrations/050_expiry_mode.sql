@@ -0,0 +1,19 @@
+-- How the daily sweep expires members whose dues lapsed past the
+-- grace period.
+--
+--   stored  — flip `status` to 'Expired' (the historical behaviour)
+--   derived — leave `status` as 'Active' and treat the member as
+--             Expired wherever status matters (access, the portal,
+--             integration roles), computed from dues on read. Keeps
+--             the record of who was active when their dues ran out.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('membership.expiry_mode', 'stored', 'string', 'membership',
+     'What happens when dues lapse past the grace period: stored (set status to Expired) or derived (keep status Active, treat as Expired).',
+     0);
+
+-- Derived mode: the paid-through date the sweep last sent a
+-- MemberExpired for, so each lapse is dispatched once.
+ALTER TABLE members ADD COLUMN expiry_dispatched_for DATETIME;
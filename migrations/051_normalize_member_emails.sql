@@ -0,0 +1,20 @@
+-- Emails are stored trimmed and lowercased from now on (see
+-- `domain::normalize_email`), and every lookup normalizes its input.
+-- Bring existing rows into that form.
+--
+-- Two accounts that differ only by case can't both be normalized
+-- without breaking UNIQUE(email). The oldest account in such a group
+-- takes the normalized address, unless another row already holds it
+-- verbatim; the rest keep their address as entered for an admin to
+-- merge or change. Those members can still sign in by username.
+UPDATE members
+SET email = lower(trim(email))
+WHERE email != lower(trim(email))
+  AND NOT EXISTS (
+      SELECT 1 FROM members other
+      WHERE other.id != members.id
+        AND lower(trim(other.email)) = lower(trim(members.email))
+        AND (other.email = lower(trim(members.email))
+             OR other.created_at < members.created_at
+             OR (other.created_at = members.created_at AND other.id < members.id))
+  );
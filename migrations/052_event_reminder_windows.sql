@@ -0,0 +1,33 @@
+-- Several reminder lead times per event, e.g. a week before and a day
+-- before. `events.reminder_lead_hours` becomes a comma-separated list
+-- of hours; an existing single value is already a one-entry list.
+
+UPDATE app_settings
+SET value_type = 'string',
+    description = 'Hours before an event to send RSVP reminder emails, comma-separated (e.g. 168,24 for a week and a day before). Each attendee gets one reminder per lead time.'
+WHERE key = 'events.reminder_lead_hours';
+
+-- One row per reminder sent, keyed by lead time. Replaces the single
+-- `event_attendance.reminder_sent_at` stamp, which can only say
+-- "reminded once". The runner claims a row here before sending, so
+-- two ticks can't double-send within a window.
+CREATE TABLE IF NOT EXISTS event_reminders_sent (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    lead_hours INTEGER NOT NULL,
+    sent_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (event_id, member_id, lead_hours)
+);
+
+-- Reminders already sent count against the lead time that was
+-- configured when they went out.
+INSERT OR IGNORE INTO event_reminders_sent (event_id, member_id, lead_hours, sent_at)
+SELECT ea.event_id, ea.member_id,
+       COALESCE((SELECT CAST(value AS INTEGER) FROM app_settings
+                 WHERE key = 'events.reminder_lead_hours'), 24),
+       ea.reminder_sent_at
+FROM event_attendance ea
+WHERE ea.member_id IS NOT NULL AND ea.reminder_sent_at IS NOT NULL;
+
+-- Members can turn event reminder emails off from their profile.
+ALTER TABLE member_profiles ADD COLUMN event_reminders BOOLEAN NOT NULL DEFAULT 1;
@@ -0,0 +1,18 @@
+-- Quiet publishes.
+--
+-- `announcements.notify_members` is the admin's choice for whether
+-- publishing dispatches `AnnouncementPublished` (the Discord post and
+-- anything else subscribed to it). NULL follows the announcement
+-- type's `notify_members` default, which is on so existing types keep
+-- broadcasting.
+--
+-- `announcements.notified_at` records the broadcast. Unpublishing and
+-- publishing again doesn't re-notify unless the admin asks for it.
+-- Rows already published are treated as notified.
+
+ALTER TABLE announcement_types ADD COLUMN notify_members INTEGER NOT NULL DEFAULT 1;
+
+ALTER TABLE announcements ADD COLUMN notify_members INTEGER;
+ALTER TABLE announcements ADD COLUMN notified_at DATETIME;
+
+UPDATE announcements SET notified_at = published_at WHERE published_at IS NOT NULL;
@@ -0,0 +1,15 @@
+-- Who created and last edited each configurable type.
+--
+-- Set from the signed-in admin by the admin types pages. Rows created
+-- by migrations, `seed_defaults` or first-run setup have no actor and
+-- stay NULL, as do rows that predate this migration. Deleting the
+-- admin clears the reference rather than the type.
+
+ALTER TABLE event_types ADD COLUMN created_by TEXT REFERENCES members(id) ON DELETE SET NULL;
+ALTER TABLE event_types ADD COLUMN updated_by TEXT REFERENCES members(id) ON DELETE SET NULL;
+
+ALTER TABLE announcement_types ADD COLUMN created_by TEXT REFERENCES members(id) ON DELETE SET NULL;
+ALTER TABLE announcement_types ADD COLUMN updated_by TEXT REFERENCES members(id) ON DELETE SET NULL;
+
+ALTER TABLE membership_types ADD COLUMN created_by TEXT REFERENCES members(id) ON DELETE SET NULL;
+ALTER TABLE membership_types ADD COLUMN updated_by TEXT REFERENCES members(id) ON DELETE SET NULL;
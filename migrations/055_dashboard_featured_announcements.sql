@@ -0,0 +1,9 @@
+-- How many featured announcements the member dashboard shows, newest
+-- first. 0 hides the card.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('features.dashboard_featured_announcements', '3', 'number', 'features',
+     'Featured announcements shown on the member dashboard (0 hides the card, at most 10).',
+     0);
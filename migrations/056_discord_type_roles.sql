@@ -0,0 +1,10 @@
+-- Per-membership-type Discord roles, for clubs that want e.g. a
+-- Corporate role on top of the base member role. One `slug=role_id`
+-- pair per line; types without a line only get the base role.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('discord.type_role_ids', '', 'string', 'discord',
+     'Extra role per membership type, one slug=role_id per line. Applied alongside the member role while the member is Active or Honorary.',
+     0);
@@ -0,0 +1,18 @@
+-- Per-club defaults for the admin events and announcements lists,
+-- used when the URL carries no sort/order/time params. Defaults match
+-- the previously hardcoded behaviour. Allowed values are enforced in
+-- `domain::list_defaults`.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('events.default_sort', 'start_time', 'string', 'events',
+     'Default sort for the admin events list: start_time, title, type or visibility.', 0),
+    ('events.default_order', 'asc', 'string', 'events',
+     'Default sort order for the admin events list: asc or desc.', 0),
+    ('events.default_time_filter', 'upcoming', 'string', 'events',
+     'Which events the admin events list shows by default: upcoming, past or all.', 0),
+    ('announcements.default_sort', 'created_at', 'string', 'announcements',
+     'Default sort for the admin announcements list: created_at, published_at, title or type.', 0),
+    ('announcements.default_order', 'desc', 'string', 'announcements',
+     'Default sort order for the admin announcements list: asc or desc.', 0);
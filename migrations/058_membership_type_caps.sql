@@ -0,0 +1,5 @@
+-- Optional cap on how many members a membership type can hold. NULL
+-- means uncapped, which is every existing type. Public signup into a
+-- type that has reached its cap is refused.
+
+ALTER TABLE membership_types ADD COLUMN max_members INTEGER CHECK (max_members IS NULL OR max_members >= 1);
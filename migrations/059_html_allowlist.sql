@@ -0,0 +1,17 @@
+-- Raw HTML members may use in announcement and comment Markdown.
+-- Comma-separated lists; defaults match `domain::html_policy`.
+-- `script`, `on*` handlers, `srcdoc` and javascript:/data: URLs are
+-- refused whatever these say.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('announcements.html_allowed_tags',
+     'a, b, i, em, strong, code, pre, blockquote, ul, ol, li, br', 'string', 'announcements',
+     'HTML tags allowed in announcement and comment Markdown, comma-separated.', 0),
+    ('announcements.html_allowed_attributes', 'a.href, a.title', 'string', 'announcements',
+     'Attributes allowed on those tags, as tag.attribute (e.g. iframe.src), comma-separated.', 0),
+    ('announcements.html_allowed_url_schemes', 'https, http, mailto', 'string', 'announcements',
+     'URL schemes allowed in link and embed attributes, comma-separated.', 0),
+    ('announcements.html_allowed_iframe_hosts', '', 'string', 'announcements',
+     'Hosts iframes may embed from (subdomains included), e.g. youtube-nocookie.com. Only used when iframe is an allowed tag.', 0);
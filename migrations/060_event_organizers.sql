@@ -0,0 +1,13 @@
+-- Co-hosts: members other than the creator who can edit an event from
+-- the portal. The creator is `events.created_by` and never has a row
+-- here. Only the creator or an admin adds and removes co-hosts.
+
+CREATE TABLE IF NOT EXISTS event_organizers (
+    event_id TEXT NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    added_by TEXT REFERENCES members(id) ON DELETE SET NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (event_id, member_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_event_organizers_member ON event_organizers(member_id);
@@ -0,0 +1,12 @@
+-- What the public announcements RSS feed carries. The item limit is
+-- the default when the request has no `?limit=`; either way the feed
+-- never exceeds 100 items. Full content is the announcement rendered
+-- as HTML; otherwise each item gets a short plain-text summary.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('announcements.feed_item_limit', '20', 'number', 'announcements',
+     'How many announcements the RSS feed includes (at most 100).', 0),
+    ('announcements.feed_full_content', 'true', 'boolean', 'announcements',
+     'Include each announcement in full in the RSS feed. When off, items carry a short summary.', 0);
@@ -0,0 +1,17 @@
+-- Members land on their own summary (/portal/me) after signing in.
+-- Clubs that never changed the member landing page move to it; one
+-- that chose a page keeps it. Admins still default to the dashboard.
+
+UPDATE app_settings
+SET value = 'me'
+WHERE key = 'auth.member_landing_page'
+  AND value = 'dashboard'
+  AND updated_by IS NULL;
+
+UPDATE app_settings
+SET description = 'Page members land on after signing in: me, dashboard, events, announcements or directory.'
+WHERE key = 'auth.member_landing_page';
+
+UPDATE app_settings
+SET description = 'Page admins land on after signing in: dashboard, me, events, announcements, directory, admin_members or admin_billing.'
+WHERE key = 'auth.admin_landing_page';
@@ -0,0 +1,17 @@
+-- Emergency contact and medical notes, for clubs running in-person
+-- events. All optional; the member fills them in on their profile.
+--
+-- Kept out of `member_profiles` on purpose: the directory, the member
+-- export and everything else that reads profiles never sees these.
+-- Admins see them on the member page and can opt them into an event's
+-- attendee export for check-in.
+
+CREATE TABLE IF NOT EXISTS member_emergency_contacts (
+    member_id TEXT PRIMARY KEY REFERENCES members(id) ON DELETE CASCADE,
+    contact_name TEXT,
+    contact_phone TEXT,
+    contact_relationship TEXT,
+    -- Allergies, conditions, anything organizers should know.
+    medical_notes TEXT,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
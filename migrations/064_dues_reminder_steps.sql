@@ -0,0 +1,41 @@
+-- Dues reminders escalate: a friendly nudge, a firmer one closer to
+-- the date, and a "your membership has lapsed" note afterwards.
+-- `membership.reminder_sequence` lists the steps as days:tone, with
+-- negative days counting after expiry. It starts as the single
+-- friendly reminder clubs already had.
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('membership.reminder_sequence',
+     COALESCE((SELECT value FROM app_settings
+               WHERE key = 'membership.reminder_days_before'), '7') || ':friendly',
+     'string', 'membership',
+     'Dues reminder emails as days:tone, comma-separated (e.g. 14:friendly,3:firm,-3:lapsed). Tones are friendly, firm and lapsed; negative days are after dues run out. Each step is sent once per dues period.',
+     0);
+
+UPDATE app_settings
+SET description = 'Days before expiration for the single reminder used when membership.reminder_sequence is unreadable.'
+WHERE key = 'membership.reminder_days_before';
+
+-- One row per reminder step sent, keyed by the dues date it was about.
+-- A payment moves dues_paid_until, which starts a fresh sequence and
+-- leaves the rest of the old one unsent. Replaces the single
+-- `members.dues_reminder_sent_at` stamp, which can only say "reminded
+-- once".
+CREATE TABLE IF NOT EXISTS dues_reminders_sent (
+    member_id TEXT NOT NULL REFERENCES members(id) ON DELETE CASCADE,
+    dues_paid_until DATETIME NOT NULL,
+    step_days INTEGER NOT NULL,
+    sent_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (member_id, dues_paid_until, step_days)
+);
+
+-- Reminders already sent count as the friendly step.
+INSERT OR IGNORE INTO dues_reminders_sent (member_id, dues_paid_until, step_days, sent_at)
+SELECT id, dues_paid_until,
+       COALESCE((SELECT CAST(value AS INTEGER) FROM app_settings
+                 WHERE key = 'membership.reminder_days_before'), 7),
+       dues_reminder_sent_at
+FROM members
+WHERE dues_reminder_sent_at IS NOT NULL AND dues_paid_until IS NOT NULL;
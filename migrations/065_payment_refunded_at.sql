@@ -0,0 +1,9 @@
+-- When a payment was refunded. Stamped when the row flips to Refunded,
+-- whether by the admin Refund button or Stripe's charge.refunded
+-- webhook, and cleared if a failed Stripe refund rolls the row back.
+-- Rows refunded before this column existed take their last update,
+-- which is the refund flip itself.
+
+ALTER TABLE payments ADD COLUMN refunded_at DATETIME;
+
+UPDATE payments SET refunded_at = updated_at WHERE status = 'Refunded';
@@ -0,0 +1,38 @@
+-- Durable queue between IntegrationManager::handle_event and the
+-- integrations. One row per (integration, event) pair still owed a
+-- delivery: handle_event writes the row, makes the first attempt, and
+-- deletes the row if it succeeds. A failed attempt is rescheduled with
+-- exponential backoff and a background worker retries it once
+-- next_attempt_at has passed, so a Discord outage or a restart no
+-- longer drops the role sync on the floor.
+--
+-- After `integrations.outbox_max_attempts` failures the row is marked
+-- 'failed' and left for the admin to see (and replay from the
+-- integration log, which still records every attempt). Failed rows are
+-- pruned with the integration log.
+--
+-- `next_attempt_at` doubles as a lease: it's pushed forward while an
+-- attempt is in flight, so the worker doesn't pick the row up twice
+-- and a crash mid-attempt leaves it due again a few minutes later.
+
+CREATE TABLE IF NOT EXISTS integration_outbox (
+    id TEXT PRIMARY KEY NOT NULL,
+    integration TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_integration_outbox_due ON integration_outbox(status, next_attempt_at);
+
+INSERT OR IGNORE INTO app_settings
+    (key, value, value_type, category, description, is_sensitive)
+VALUES
+    ('integrations.outbox_max_attempts', '8', 'number', 'integrations',
+     'Delivery attempts per integration event before it is marked failed. Retries back off exponentially from 30 seconds.',
+     0);
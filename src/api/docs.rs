@@ -1,8 +1,8 @@
 //! OpenAPI specification for the public API surface.
 //!
 //! Only endpoints intended for the public website integration are
-//! documented here (signup, public event/announcement reads, donations,
-//! RSS/iCal feeds, plus root/health metadata). Authenticated portal
+//! documented here (signup, public event/announcement reads, guest
+//! RSVPs, donations, RSS/iCal feeds, plus root/health metadata). Authenticated portal
 //! routes are deliberately excluded.
 
 use utoipa::OpenApi;
@@ -22,34 +22,56 @@ use crate::domain;
     paths(
         handlers::root::root,
         handlers::root::health_check,
+        handlers::root::readiness_check,
         handlers::root::api_info,
         handlers::public::signup,
+        handlers::public::signup_fields,
         handlers::public::list_events,
         handlers::public::private_event_count,
+        handlers::public::guest_rsvp,
+        handlers::public::contact,
         handlers::public::list_announcements,
         handlers::public::rss_feed,
         handlers::public::calendar_feed,
+        handlers::public::event_calendar,
         handlers::public::donate,
+        handlers::public::list_types,
         handlers::announcements::private_count,
+        handlers::announcements::list_comments,
     ),
     components(schemas(
         // Root metadata
         handlers::root::ApiInfo,
         handlers::root::HealthStatus,
+        handlers::root::ReadinessStatus,
+        handlers::root::DependencyStatus,
         // Public DTOs
         handlers::public::SignupRequest,
         handlers::public::SignupResponse,
+        handlers::public::SignupFieldErrors,
+        handlers::public::SignupFormResponse,
         handlers::public::PrivateEventCount,
+        handlers::public::GuestRsvpRequest,
+        handlers::public::GuestRsvpResponse,
+        handlers::public::PublicContactRequest,
+        handlers::public::PublicContactResponse,
         handlers::public::PublicDonateRequest,
         handlers::public::PublicDonateResponse,
+        handlers::public::AnnouncementPage,
+        handlers::public::PublicType,
+        handlers::public::PublicTypesOverview,
         handlers::announcements::PrivateAnnouncementCount,
+        handlers::announcements::PublicComment,
         // Domain types referenced from responses
         domain::Event,
         domain::EventType,
         domain::EventVisibility,
+        domain::ContactCategory,
         domain::Announcement,
         domain::AnnouncementType,
         domain::MemberStatus,
+        domain::SignupField,
+        domain::SignupFieldKind,
     )),
     tags(
         (name = "public", description = "Public API for website integration"),
@@ -1,18 +1,28 @@
 //! Public announcements surface. The full admin CRUD on announcements
-//! lives in the portal (`web/portal/admin/announcements.rs`); the only
-//! JSON endpoint on announcements is the count of members-only
-//! published announcements, exposed to the public marketing site so it
-//! can show "N members-only posts available — sign up" CTAs.
+//! lives in the portal (`web/portal/admin/announcements.rs`); the JSON
+//! endpoints here are the count of members-only published
+//! announcements, exposed to the public marketing site so it can show
+//! "N members-only posts available — sign up" CTAs, and the comment
+//! thread of a public announcement.
 
 use std::sync::Arc;
 
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 use crate::{
     error::Result,
     repository::AnnouncementRepository,
+    service::{
+        announcement_comment_service::{AnnouncementCommentService, CommentViewer},
+        settings_service::SettingsService,
+    },
 };
 
 #[derive(Serialize, ToSchema)]
@@ -40,3 +50,49 @@ pub async fn private_count(
 
     Ok(Json(PrivateAnnouncementCount { count }))
 }
+
+#[derive(Serialize, ToSchema)]
+pub struct PublicComment {
+    pub id: Uuid,
+    /// The author's username.
+    pub author: String,
+    /// Markdown as the member typed it.
+    pub body: String,
+    /// `body` rendered to sanitized HTML, safe to insert as-is.
+    pub body_html: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Visible comments on a public announcement, oldest first. Members-only,
+/// unpublished and expired announcements, and ones with comments turned
+/// off, are all a 404.
+#[utoipa::path(
+    get,
+    path = "/public/announcements/{id}/comments",
+    tag = "public",
+    params(("id" = Uuid, Path, description = "Announcement id")),
+    responses(
+        (status = 200, description = "Comments, oldest first", body = [PublicComment]),
+        (status = 404, description = "No such public announcement, or comments are off"),
+    ),
+)]
+pub async fn list_comments(
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PublicComment>>> {
+    let policy = settings_service.html_policy().await;
+    let comments = comment_service
+        .list_visible(id, CommentViewer::Public)
+        .await?
+        .into_iter()
+        .map(|c| PublicComment {
+            id: c.id,
+            author: c.author,
+            body_html: crate::web::markdown::render(&c.body, &policy),
+            body: c.body,
+            created_at: c.created_at,
+        })
+        .collect();
+    Ok(Json(comments))
+}
@@ -10,11 +10,14 @@ use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 use crate::{
-    api::state::{self, LoginLimiter},
+    api::state::{self, FailedLoginTracker, LoginLimiter},
     auth::{self, AuthService},
     config::Settings,
     error::{AppError, Result},
-    service::audit_service::AuditService,
+    service::{
+        audit_service::AuditService,
+        login_history_service::{LoginHistoryService, LoginMethod},
+    },
 };
 
 #[derive(Debug, Deserialize)]
@@ -28,11 +31,14 @@ pub struct LoginResponse {
     pub message: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn login(
     State(auth_service): State<Arc<AuthService>>,
     State(settings): State<Arc<Settings>>,
     State(login_limiter): State<LoginLimiter>,
+    State(login_failures): State<FailedLoginTracker>,
     State(db_pool): State<SqlitePool>,
+    State(login_history_service): State<Arc<LoginHistoryService>>,
     headers: HeaderMap,
     jar: CookieJar,
     Json(req): Json<LoginRequest>,
@@ -42,6 +48,10 @@ pub async fn login(
     if !login_limiter.0.check_and_record(ip) {
         return Err(AppError::TooManyRequests);
     }
+    // Per-account lockout; same response whether or not the email exists.
+    if login_failures.is_locked(&req.email, ip) {
+        return Err(AppError::TooManyRequests);
+    }
 
     // Get password hash from database
     let password_hash = auth::get_password_hash(&db_pool, &req.email)
@@ -52,14 +62,17 @@ pub async fn login(
         None => {
             // User not found — burn Argon2 time to prevent timing-based enumeration.
             auth::AuthService::verify_dummy(&req.password).await;
+            login_failures.record_failure(&req.email, ip);
             return Err(AppError::Unauthorized);
         }
     };
 
     // Verify password
     if !auth::AuthService::verify_password(&req.password, &password_hash).await? {
+        login_failures.record_failure(&req.email, ip);
         return Err(AppError::Unauthorized);
     }
+    login_failures.reset(&req.email, ip);
 
     // Get member
     let member = auth::get_member_by_email(&db_pool, &req.email)
@@ -83,13 +96,21 @@ pub async fn login(
 
     // Create session (returns both session and token)
     let (_session, token) = auth_service
-        .create_session(member.id, 24)
+        .create_session(member.id, settings.auth.session_duration_hours)
         .await?;
 
+    login_history_service
+        .record_login(&member, ip, state::user_agent(&headers), LoginMethod::Api)
+        .await;
+
     // Create cookie with the actual token. The Secure flag tracks whether
     // the deployment is TLS-terminated; see ServerConfig::cookies_are_secure.
     let cookie = auth_service
-        .create_session_cookie(&token, settings.server.cookies_are_secure());
+        .create_session_cookie(
+            &token,
+            settings.auth.session_duration_hours,
+            settings.server.cookies_are_secure(),
+        );
 
     Ok((
         jar.add(cookie),
@@ -1,10 +1,11 @@
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -14,21 +15,28 @@ use uuid::Uuid;
 
 use crate::{
     api::{
-        middleware::bot_challenge::BotChallengeVerifier,
-        state::MoneyLimiter,
+        middleware::{auth::CurrentUser, bot_challenge::BotChallengeVerifier},
+        state::{AnnouncementBasicTypeService, ContactLimiter, EventBasicTypeService, MoneyLimiter},
     },
     config::Settings,
-    domain::{CreateMemberRequest, Event, Announcement, EventVisibility, MemberStatus},
+    domain::{
+        can_view_event, AdminNotificationKind, AnnouncementType, BasicType, ContactCategory, CreateMemberRequest, Event, Announcement,
+        EventStatus, EventVisibility, MemberStatus, MembershipTypeConfig, SignupField, slugify,
+    },
     email::EmailSender,
     error::{AppError, Result},
+    integrations::{IntegrationEvent, IntegrationManager},
     payments::StripeClient,
     repository::{
         AnnouncementRepository, DonationCampaignRepository, EventRepository, MemberRepository,
         PaymentRepository,
     },
     service::{
+        contact_service::{ContactInput, ContactService},
         membership_type_service::MembershipTypeService, settings_service::SettingsService,
+        signup_field_service::{check_answers, SignupFieldService},
     },
+    util::ical,
 };
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -46,6 +54,43 @@ pub struct SignupRequest {
     /// `bot_challenge.provider = "disabled"`. See `BotChallengeConfig`.
     #[serde(default)]
     pub captcha_token: Option<String>,
+    /// Answers to the org's extra signup fields, keyed by field key
+    /// (see `GET /public/signup/fields`). Keys that aren't configured
+    /// are ignored.
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+}
+
+/// 422 body when required signup fields are missing or invalid.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignupFieldErrors {
+    pub error: String,
+    /// One message per offending field, keyed by field key. Baseline
+    /// fields use their request names (`username`, `full_name`).
+    pub fields: BTreeMap<String, String>,
+}
+
+/// The org's extra signup fields, in form order. Email, username, full
+/// name and password are always required and not listed.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignupFormResponse {
+    pub fields: Vec<SignupField>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/public/signup/fields",
+    tag = "public",
+    responses(
+        (status = 200, description = "Extra fields to render on the signup form", body = SignupFormResponse),
+    ),
+)]
+pub async fn signup_fields(
+    State(signup_field_service): State<Arc<SignupFieldService>>,
+) -> Json<SignupFormResponse> {
+    Json(SignupFormResponse {
+        fields: signup_field_service.fields().await,
+    })
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -53,6 +98,16 @@ pub struct SignupResponse {
     pub member_id: Uuid,
     pub status: MemberStatus,
     pub message: String,
+    /// Stripe-hosted Checkout URL for the first dues payment. The
+    /// frontend redirects the new member here; the member is activated
+    /// when the webhook reports the session paid. Abandoning checkout
+    /// leaves them `Pending`. Set only when payment is required at
+    /// signup, the membership type has a fee, and Stripe is configured.
+    pub checkout_url: Option<String>,
+    /// How to pay offline, for the frontend to show. Set instead of
+    /// `checkout_url` when payment is required but Stripe isn't
+    /// available; admins are alerted to expect the payment.
+    pub payment_instructions: Option<String>,
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -70,9 +125,14 @@ pub struct PublicEventsQuery {
     tag = "public",
     request_body = SignupRequest,
     responses(
-        (status = 201, description = "Member created; verification email sent", body = SignupResponse),
-        (status = 400, description = "Invalid email or weak password"),
-        (status = 409, description = "Email or username already in use"),
+        (status = 201, description = "Member created; verification email sent. Redirect to \
+            checkout_url when present, otherwise show payment_instructions when present",
+            body = SignupResponse),
+        (status = 400, description = "Invalid email, weak password, or the requested \
+            membership type is full"),
+        (status = 409, description = "Email or username already in use. Names the field \
+            (as `fields`) only when `membership.signup_duplicate_field_errors` is on"),
+        (status = 422, description = "Required fields missing or invalid", body = SignupFieldErrors),
     ),
 )]
 pub async fn signup(
@@ -83,9 +143,12 @@ pub async fn signup(
     State(settings): State<Arc<Settings>>,
     State(settings_service): State<Arc<SettingsService>>,
     State(db_pool): State<SqlitePool>,
+    State(stripe_client): State<Option<Arc<StripeClient>>>,
+    State(integration_manager): State<Arc<IntegrationManager>>,
+    State(signup_field_service): State<Arc<SignupFieldService>>,
     headers: HeaderMap,
     Json(request): Json<SignupRequest>,
-) -> Result<(StatusCode, Json<SignupResponse>)> {
+) -> Result<Response> {
     // Bot-challenge verification BEFORE any work. Fail closed: if the
     // org has configured a provider, every request must carry a token
     // the provider verifies. The DisabledVerifier is a no-op so dev
@@ -112,6 +175,29 @@ pub async fn signup(
         return Err(AppError::BadRequest(msg.to_string()));
     }
 
+    // Baseline and admin-configured fields, reported together so the
+    // form can mark every problem at once.
+    let configured_fields = signup_field_service.fields().await;
+    let (answers, mut field_errors) = match check_answers(&configured_fields, &request.fields) {
+        Ok(answers) => (answers, BTreeMap::new()),
+        Err(errors) => (Vec::new(), errors),
+    };
+    if request.username.trim().is_empty() {
+        field_errors.insert("username".to_string(), "Username is required".to_string());
+    } else if let Err(msg) = crate::domain::validate_username(request.username.trim()) {
+        field_errors.insert("username".to_string(), msg.to_string());
+    }
+    if request.full_name.trim().is_empty() {
+        field_errors.insert("full_name".to_string(), "Full name is required".to_string());
+    }
+    if !field_errors.is_empty() {
+        let body = SignupFieldErrors {
+            error: "Please fill in the required fields".to_string(),
+            fields: field_errors,
+        };
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(body)).into_response());
+    }
+
     // Resolve the requested membership_type slug to an FK. Unknown
     // slugs fail loudly (BadRequest) — silently mapping to a default
     // would mask client typos.
@@ -123,9 +209,17 @@ pub async fn signup(
                 .ok_or_else(|| AppError::BadRequest(format!(
                     "Unknown membership type slug: {}", slug,
                 )))?;
+            membership_type_service.ensure_capacity(&mt).await?;
             Some(mt.id)
         }
-        None => None,
+        None => {
+            // The repo falls back to the first active type; a full
+            // default closes slug-less signup too.
+            if let Some(default) = membership_type_service.list(false).await?.first() {
+                membership_type_service.ensure_capacity(default).await?;
+            }
+            None
+        }
     };
 
     // Create member with Pending status
@@ -138,17 +232,30 @@ pub async fn signup(
         ..Default::default()
     };
 
-    // Create the member. Use a generic error for UNIQUE violations to
-    // prevent attackers from enumerating valid emails/usernames.
-    let member = member_repo.create(create_request).await
-        .map_err(|e| {
-            if let AppError::Database(sqlx::Error::Database(ref db_err)) = e {
-                if db_err.is_unique_violation() {
-                    return AppError::Conflict("Registration failed: an account with this information already exists".to_string());
-                }
-            }
-            e
-        })?;
+    // Create the member. A taken email or username gets a generic error
+    // unless the org has opted into naming the field, so by default the
+    // endpoint can't be used to enumerate accounts.
+    let member = match member_repo.create(create_request).await {
+        Ok(member) => member,
+        Err(AppError::DuplicateMember(field)) => {
+            let name_field = settings_service
+                .get_bool("membership.signup_duplicate_field_errors")
+                .await
+                .unwrap_or(false);
+            return Err(if name_field {
+                AppError::DuplicateMember(field)
+            } else {
+                AppError::Conflict("Registration failed: an account with this information already exists".to_string())
+            });
+        }
+        Err(e) => return Err(e),
+    };
+
+    // The account exists either way; a member missing an answer is
+    // something an admin can follow up on.
+    if let Err(e) = signup_field_service.save(member.id, &answers).await {
+        tracing::error!("Failed to save signup fields for member {}: {}", member.id, e);
+    }
 
     // Send email verification. Soft-fail on send error: the account is
     // already created and an admin can manually verify / resend later.
@@ -165,13 +272,136 @@ pub async fn signup(
         );
     }
 
+    let handoff = payment_handoff(
+        &settings,
+        &settings_service,
+        &membership_type_service,
+        stripe_client.as_deref(),
+        &member,
+    ).await;
+
+    // One notification per signup; a member sent to pay offline is
+    // flagged in it so someone knows to watch for the payment.
+    integration_manager
+        .handle_event(IntegrationEvent::AdminNotification {
+            kind: AdminNotificationKind::NewSignup,
+            key: format!("signup:{}", member.id),
+            subject: format!("New signup: {}", member.full_name),
+            body: format!(
+                "{} (@{}, {}) signed up and is Pending.{}\nReview them at {}/portal/admin/members/{}",
+                member.full_name,
+                member.username,
+                member.email,
+                handoff
+                    .offline_payment_note
+                    .as_deref()
+                    .map(|note| format!(" {}", note))
+                    .unwrap_or_default(),
+                settings.server.base_url.trim_end_matches('/'),
+                member.id,
+            ),
+        })
+        .await;
+
     let response = SignupResponse {
         member_id: member.id,
         status: member.status,
         message: "Registration successful. Please check your email to verify your account.".to_string(),
+        checkout_url: handoff.checkout_url,
+        payment_instructions: handoff.payment_instructions,
     };
 
-    Ok((StatusCode::CREATED, Json(response)))
+    Ok((StatusCode::CREATED, Json(response)).into_response())
+}
+
+#[derive(Default)]
+struct PaymentHandoff {
+    checkout_url: Option<String>,
+    payment_instructions: Option<String>,
+    /// For the admins' signup notification when the member was sent
+    /// to pay offline.
+    offline_payment_note: Option<String>,
+}
+
+/// Decide what a new member is asked to pay after signup. Nothing when
+/// `membership.require_payment_for_activation` is off or the type is
+/// free; a Stripe Checkout session when Stripe is configured; otherwise
+/// the org's offline instructions, plus a note for the admins' signup
+/// notification so someone knows to watch for the payment. Never fails
+/// the signup — the account
+/// exists either way, and a member left Pending is what an admin
+/// handles today.
+async fn payment_handoff(
+    settings: &Settings,
+    settings_service: &SettingsService,
+    membership_type_service: &MembershipTypeService,
+    stripe_client: Option<&StripeClient>,
+    member: &crate::domain::Member,
+) -> PaymentHandoff {
+    if !settings_service
+        .get_bool("membership.require_payment_for_activation")
+        .await
+        .unwrap_or(false)
+    {
+        return PaymentHandoff::default();
+    }
+    let membership_type = match membership_type_service.get(member.membership_type_id).await {
+        Ok(Some(mt)) if mt.fee_cents > 0 => mt,
+        Ok(_) => return PaymentHandoff::default(),
+        Err(e) => {
+            tracing::error!(
+                "Signup payment handoff: couldn't load membership type for member {}: {}",
+                member.id, e
+            );
+            return PaymentHandoff::default();
+        }
+    };
+
+    if let Some(stripe_client) = stripe_client {
+        let base_url = settings.server.base_url.trim_end_matches('/');
+        match stripe_client.create_membership_checkout_session(
+            member.id,
+            &membership_type.name,
+            &membership_type.slug,
+            membership_type.fee_cents,
+            format!("{}/signup/payment?result=paid", base_url),
+            format!("{}/signup/payment?result=cancelled", base_url),
+        ).await {
+            Ok((checkout_url, _payment_id)) => {
+                return PaymentHandoff {
+                    checkout_url: Some(checkout_url),
+                    payment_instructions: None,
+                };
+            }
+            // Fall through to the offline path rather than stranding
+            // the member with no way to pay.
+            Err(e) => tracing::error!(
+                "Signup payment handoff: Checkout session failed for member {}: {}",
+                member.id, e
+            ),
+        }
+    }
+
+    let instructions = settings_service
+        .get_value("membership.offline_payment_instructions")
+        .await
+        .unwrap_or_default();
+    let offline_payment_note = format!(
+        "They chose {} ({}) and were shown the offline payment \
+         instructions, so they stay Pending until an admin records \
+         their payment and activates them.",
+        membership_type.name,
+        settings_service
+            .org_locale()
+            .await
+            .money(&membership_type.fee()),
+    );
+
+    PaymentHandoff {
+        checkout_url: None,
+        payment_instructions: Some(instructions),
+        offline_payment_note: Some(offline_payment_note),
+    }
 }
 
 /// Generate a verification token and email the link to the member.
@@ -224,7 +454,8 @@ async fn org_name(settings_service: &SettingsService) -> String {
     responses(
         (status = 200, description = "Upcoming public + sanitized members-only events", body = [Event],
             content_type = "application/json"),
-        (status = 200, description = "iCal feed (when format=ical)", content_type = "text/calendar"),
+        (status = 200, description = "iCal feed of public events only (when format=ical)",
+            content_type = "text/calendar"),
     ),
 )]
 pub async fn list_events(
@@ -237,19 +468,17 @@ pub async fn list_events(
     // Get members-only events (will be sanitized)
     let private_events = event_repo.list_members_only().await?;
 
-    // Combine and filter to upcoming events only
+    // Combine and filter to upcoming events only. A calendar app would
+    // fill the subscriber's calendar with "Members-Only Event" slots,
+    // so the iCal feed keeps to public events, like /public/feed/calendar.
     let now = Utc::now();
+    let ical = params.format.as_deref() == Some("ical");
     let mut upcoming_events: Vec<Event> = public_events
         .into_iter()
-        .chain(private_events.into_iter().map(|mut e| {
-            // Sanitize private events
-            e.title = "Members-Only Event".to_string();
-            e.description = "This event is for members only. Log in to the portal to see details.".to_string();
-            e.location = None;
-            e.image_url = None;
-            e
-        }))
+        .chain(private_events)
+        .filter_map(anonymous_view)
         .filter(|e| e.start_time > now)
+        .filter(|e| !ical || e.visibility == EventVisibility::Public)
         .collect();
 
     // Sort by start time
@@ -258,9 +487,8 @@ pub async fn list_events(
     // Apply limit
     upcoming_events.truncate(params.limit.unwrap_or(50) as usize);
 
-    // Check if iCal format is requested
-    if params.format.as_deref() == Some("ical") {
-        let ical = generate_ical_feed(&upcoming_events);
+    if ical {
+        let ical = ical::publish_feed(&upcoming_events);
         Ok((
             StatusCode::OK,
             [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
@@ -271,46 +499,165 @@ pub async fn list_events(
     }
 }
 
+/// Page size when the caller doesn't ask for one, and the most a
+/// caller can ask for.
+const DEFAULT_ANNOUNCEMENTS_LIMIT: i64 = 20;
+const MAX_ANNOUNCEMENTS_LIMIT: i64 = 100;
+
+/// How long browsers and CDNs may reuse a public announcements page.
+/// Short, so a newly published announcement shows up within a minute.
+const ANNOUNCEMENTS_CACHE_CONTROL: &str = "public, max-age=60";
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PublicAnnouncementsQuery {
+    /// Page size (default 20, at most 100).
+    pub limit: Option<i64>,
+    /// Number of announcements to skip (default 0).
+    pub offset: Option<i64>,
+    /// Only announcements of this type, e.g. `News` or `CTFResult`.
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub announcement_type: Option<String>,
+}
+
+/// One page of public announcements, newest first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnnouncementPage {
+    pub items: Vec<Announcement>,
+    /// Announcements matching the filter across all pages.
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+}
+
 #[utoipa::path(
     get,
     path = "/public/announcements",
     tag = "public",
+    params(PublicAnnouncementsQuery),
     responses(
-        (status = 200, description = "Published public announcements", body = [Announcement]),
+        (status = 200, description = "A page of published, unexpired public announcements",
+            body = AnnouncementPage),
+        (status = 400, description = "Unknown announcement type or negative offset"),
     ),
 )]
 pub async fn list_announcements(
     State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
-) -> Result<Json<Vec<Announcement>>> {
-    // Get public announcements only
-    let announcements = announcement_repo.list_public().await?;
+    Query(params): Query<PublicAnnouncementsQuery>,
+) -> Result<Response> {
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_ANNOUNCEMENTS_LIMIT)
+        .clamp(1, MAX_ANNOUNCEMENTS_LIMIT);
+    let offset = params.offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(AppError::BadRequest("offset must not be negative".to_string()));
+    }
+    let announcement_type = params
+        .announcement_type
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| t.parse::<AnnouncementType>())
+        .transpose()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
 
-    // Filter to published announcements only
-    let published: Vec<Announcement> = announcements
-        .into_iter()
-        .filter(|a| a.published_at.is_some())
-        .collect();
+    let (items, total) = announcement_repo
+        .list_public_page(announcement_type.as_ref(), limit, offset)
+        .await?;
 
-    Ok(Json(published))
+    Ok((
+        [(header::CACHE_CONTROL, ANNOUNCEMENTS_CACHE_CONTROL)],
+        Json(AnnouncementPage {
+            items,
+            total,
+            limit,
+            offset,
+        }),
+    )
+        .into_response())
+}
+
+/// Hard cap on RSS feed items, whatever the setting or `?limit=` says.
+const MAX_FEED_ITEMS: i64 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FeedQuery {
+    /// Items to include (at most 100). Defaults to the
+    /// `announcements.feed_item_limit` setting.
+    pub limit: Option<i64>,
+    /// Only announcements of these types: one announcement type slug,
+    /// or several comma-separated, e.g. `news` or `news,awards`.
+    /// Omit for every public announcement.
+    #[serde(rename = "type")]
+    #[param(rename = "type")]
+    pub announcement_type: Option<String>,
 }
 
 #[utoipa::path(
     get,
     path = "/public/feed/rss",
     tag = "public",
+    params(FeedQuery),
     responses(
-        (status = 200, description = "RSS 2.0 feed of public announcements",
+        (status = 200, description = "RSS 2.0 feed of public announcements, newest first",
             content_type = "application/rss+xml"),
+        (status = 400, description = "Unknown announcement type slug"),
     ),
 )]
 pub async fn rss_feed(
     State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
+    State(AnnouncementBasicTypeService(announcement_types)): State<AnnouncementBasicTypeService>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Query(query): Query<FeedQuery>,
 ) -> Result<Response> {
-    // Get recent public announcements
-    let announcements = announcement_repo.list_public().await?;
+    let limit = match query.limit {
+        Some(limit) => limit,
+        None => settings_service.feed_item_limit().await,
+    }
+    .clamp(1, MAX_FEED_ITEMS);
+
+    // Resolve `?type=` slugs to configured types. An unknown slug is a
+    // 400 rather than an empty feed, so a typo'd subscription URL fails
+    // loudly instead of looking like a quiet feed.
+    let mut type_ids = Vec::new();
+    for slug in query
+        .announcement_type
+        .as_deref()
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let announcement_type = announcement_types
+            .get_by_slug(slug)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown announcement type: {}", slug)))?;
+        type_ids.push(announcement_type.id);
+    }
+    let announcements = if type_ids.is_empty() {
+        announcement_repo.list_public_page(None, limit, 0).await?.0
+    } else {
+        announcement_repo.list_public_of_types(&type_ids, limit).await?
+    };
+
+    // Full items are the announcement as members see it; summaries
+    // are plain text, like the share page's description.
+    let content: Vec<String> = if settings_service.feed_full_content().await {
+        let policy = settings_service.html_policy().await;
+        announcements
+            .iter()
+            .map(|a| crate::web::markdown::render(&a.content, &policy))
+            .collect()
+    } else {
+        announcements
+            .iter()
+            .map(|a| crate::web::templates::share::summary(&a.content))
+            .collect()
+    };
 
     // Generate RSS XML
-    let rss = generate_rss_feed(&announcements);
+    let rss = generate_rss_feed(&announcements, &content);
     
     Ok((
         StatusCode::OK,
@@ -324,26 +671,22 @@ pub async fn rss_feed(
     path = "/public/feed/calendar",
     tag = "public",
     responses(
-        (status = 200, description = "iCal feed of all events (private events are sanitized)",
-            content_type = "text/calendar"),
+        (status = 200, description = "iCal feed of public events", content_type = "text/calendar"),
     ),
 )]
 pub async fn calendar_feed(
     State(event_repo): State<Arc<dyn EventRepository>>,
 ) -> Result<Response> {
-    // Get public events (full details)
-    let public_events = event_repo.list_public().await?;
-
-    // Get members-only events (will be sanitized in feed)
-    let private_events = event_repo.list_members_only().await?;
-
-    // Combine all events for the calendar
-    let all_events: Vec<_> = public_events.into_iter()
-        .chain(private_events.into_iter())
+    // Public events only: a subscribed calendar has no use for the
+    // title-less members-only slots `/public/events` shows. The filter
+    // is the backstop in case `list_public` ever widens.
+    let events: Vec<_> = event_repo
+        .list_public()
+        .await?
+        .into_iter()
+        .filter(|e| e.visibility == EventVisibility::Public)
         .collect();
-
-    // Generate iCal format (private events will be sanitized)
-    let ical = generate_ical_feed(&all_events);
+    let ical = ical::publish_feed(&events);
 
     Ok((
         StatusCode::OK,
@@ -352,6 +695,73 @@ pub async fn calendar_feed(
     ).into_response())
 }
 
+#[utoipa::path(
+    get,
+    path = "/public/events/{id}/calendar",
+    tag = "public",
+    params(("id" = Uuid, Path, description = "Event id")),
+    responses(
+        (status = 200, description = "Single-event iCal file, as an attachment",
+            content_type = "text/calendar"),
+        (status = 403, description = "Members-only or admin-only event, requested without a session"),
+        (status = 404, description = "No such published event, or not one the member can see"),
+    ),
+)]
+pub async fn event_calendar(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    current_user: Option<Extension<CurrentUser>>,
+    Path(id): Path<Uuid>,
+) -> Result<Response> {
+    let event = event_repo
+        .find_by_id(id)
+        .await?
+        .filter(|e| e.status == EventStatus::Published)
+        .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+    let viewer = current_user.as_ref().map(|Extension(user)| &user.member);
+    if !can_view_event(viewer, &event) {
+        // Anonymous callers learn the event needs a login; a member
+        // gets the same not-found the portal gives for an event
+        // they can't see.
+        return Err(match viewer {
+            None => AppError::Forbidden,
+            Some(_) => AppError::NotFound("Event not found".to_string()),
+        });
+    }
+
+    let filename = slugify(&event.title);
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}.ics\"", filename),
+            ),
+        ],
+        ical::publish_feed(std::slice::from_ref(&event)),
+    )
+        .into_response())
+}
+
+/// What an anonymous visitor gets to see of `event`: public events
+/// as-is, members-only events as a title-less time slot, and admin-only
+/// events not at all. The repo queries feeding the public endpoints
+/// never return AdminOnly rows; this is the backstop if one changes.
+pub(crate) fn anonymous_view(mut event: Event) -> Option<Event> {
+    if can_view_event(None, &event) {
+        return Some(event);
+    }
+    if event.visibility != EventVisibility::MembersOnly {
+        return None;
+    }
+    event.title = "Members-Only Event".to_string();
+    event.description = "This event is for members only. Log in to the portal to see details.".to_string();
+    event.location = None;
+    event.image_url = None;
+    event.allow_guest_rsvp = false;
+    Some(event)
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PrivateEventCount {
     pub count: i64,
@@ -372,6 +782,87 @@ pub async fn private_event_count(
     Ok(Json(PrivateEventCount { count }))
 }
 
+/// How long browsers and CDNs may reuse the types overview. Types
+/// change rarely, so a few minutes' staleness after an admin edit is
+/// fine.
+const TYPES_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// Display metadata for one active type: enough for a website to style
+/// events and announcements by slug. Membership pricing is left out.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicType {
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    /// CSS color, e.g. `#2196F3`.
+    pub color: Option<String>,
+    pub icon: Option<String>,
+}
+
+impl From<BasicType> for PublicType {
+    fn from(t: BasicType) -> Self {
+        Self {
+            name: t.name,
+            slug: t.slug,
+            description: t.description,
+            color: t.color,
+            icon: t.icon,
+        }
+    }
+}
+
+impl From<MembershipTypeConfig> for PublicType {
+    fn from(t: MembershipTypeConfig) -> Self {
+        Self {
+            name: t.name,
+            slug: t.slug,
+            description: t.description,
+            color: t.color,
+            icon: t.icon,
+        }
+    }
+}
+
+/// Every active type, each list in admin-defined display order.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicTypesOverview {
+    pub event_types: Vec<PublicType>,
+    pub announcement_types: Vec<PublicType>,
+    pub membership_types: Vec<PublicType>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/public/types",
+    tag = "public",
+    responses(
+        (status = 200, description = "Active event, announcement and membership types",
+            body = PublicTypesOverview),
+    ),
+)]
+pub async fn list_types(
+    State(EventBasicTypeService(event_types)): State<EventBasicTypeService>,
+    State(AnnouncementBasicTypeService(announcement_types)): State<AnnouncementBasicTypeService>,
+    State(membership_type_service): State<Arc<MembershipTypeService>>,
+) -> Result<Response> {
+    let overview = PublicTypesOverview {
+        event_types: event_types.list(false).await?.into_iter().map(Into::into).collect(),
+        announcement_types: announcement_types
+            .list(false)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        membership_types: membership_type_service
+            .list(false)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    };
+    Ok(([(header::CACHE_CONTROL, TYPES_CACHE_CONTROL)], Json(overview)).into_response())
+}
+
 /// Escape text for use inside XML CDATA sections. The only sequence that
 /// can break a CDATA block is `]]>`, which we split into two adjacent
 /// CDATA sections: `]]]]><![CDATA[>`.
@@ -380,7 +871,7 @@ fn escape_cdata(s: &str) -> String {
 }
 
 // Helper function to generate RSS feed
-fn generate_rss_feed(announcements: &[Announcement]) -> String {
+fn generate_rss_feed(announcements: &[Announcement], content: &[String]) -> String {
     let mut rss = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>
 <rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
 <channel>
@@ -393,11 +884,11 @@ fn generate_rss_feed(announcements: &[Announcement]) -> String {
     rss.push_str(&Utc::now().to_rfc2822());
     rss.push_str("</lastBuildDate>\n");
 
-    for announcement in announcements.iter().take(20) {
+    for (announcement, content) in announcements.iter().zip(content) {
         if let Some(published) = announcement.published_at {
             rss.push_str("    <item>\n");
             rss.push_str(&format!("        <title><![CDATA[{}]]></title>\n", escape_cdata(&announcement.title)));
-            rss.push_str(&format!("        <description><![CDATA[{}]]></description>\n", escape_cdata(&announcement.content)));
+            rss.push_str(&format!("        <description><![CDATA[{}]]></description>\n", escape_cdata(content)));
             rss.push_str(&format!("        <guid isPermaLink=\"false\">{}</guid>\n", announcement.id));
             rss.push_str(&format!("        <pubDate>{}</pubDate>\n", published.to_rfc2822()));
             rss.push_str("    </item>\n");
@@ -408,58 +899,191 @@ fn generate_rss_feed(announcements: &[Announcement]) -> String {
     rss
 }
 
-/// Escape a text value for iCal (RFC 5545 Section 3.3.11).
-/// Backslashes, semicolons, commas, and newlines must be escaped.
-fn escape_ical_text(s: &str) -> String {
-    s.replace('\\', "\\\\")
-        .replace(';', "\\;")
-        .replace(',', "\\,")
-        .replace('\n', "\\n")
-        .replace('\r', "")
+// ---------------------------------------------------------------------
+// Guest RSVPs
+// ---------------------------------------------------------------------
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GuestRsvpRequest {
+    pub event_id: Uuid,
+    pub name: String,
+    pub email: String,
+    /// Bot-challenge token from the marketing site's CAPTCHA widget.
+    /// Required when the org has configured a provider; ignored when
+    /// `bot_challenge.provider = "disabled"`. See `BotChallengeConfig`.
+    #[serde(default)]
+    pub captcha_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GuestRsvpResponse {
+    pub message: String,
 }
 
-// Helper function to generate iCal feed
-// Private (MembersOnly) events are sanitized to show only time slot
-fn generate_ical_feed(events: &[Event]) -> String {
-    let mut ical = String::from("BEGIN:VCALENDAR\r\n");
-    ical.push_str("VERSION:2.0\r\n");
-    ical.push_str("PRODID:-//Coterie//Events//EN\r\n");
-    ical.push_str("CALSCALE:GREGORIAN\r\n");
-    ical.push_str("METHOD:PUBLISH\r\n");
-    ical.push_str("X-WR-CALNAME:Coterie Events\r\n");
+/// POST /public/events/rsvp — RSVP a non-member to an open event.
+///
+/// Only upcoming, published `Public` events with `allow_guest_rsvp`
+/// set take guests; anything else answers 404 so the endpoint can't be
+/// used to probe for hidden events. The guest is stored as an
+/// `event_attendance` row with no member, and counts toward
+/// `max_attendees`. RSVPing again with the same email updates the
+/// name instead of taking a second seat.
+///
+/// The event id travels in the body rather than the path so the route
+/// is a fixed string the CSRF exemption list can name.
+#[utoipa::path(
+    post,
+    path = "/public/events/rsvp",
+    tag = "public",
+    request_body = GuestRsvpRequest,
+    responses(
+        (status = 201, description = "Guest registered", body = GuestRsvpResponse),
+        (status = 400, description = "Missing name, invalid email, or RSVPs closed"),
+        (status = 404, description = "No upcoming public event accepting guest RSVPs"),
+        (status = 409, description = "Event is full"),
+    ),
+)]
+pub async fn guest_rsvp(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(bot_challenge_verifier): State<Arc<dyn BotChallengeVerifier>>,
+    State(settings): State<Arc<Settings>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    headers: HeaderMap,
+    Json(request): Json<GuestRsvpRequest>,
+) -> Result<(StatusCode, Json<GuestRsvpResponse>)> {
+    // Without a challenge anyone could fill an event with made-up
+    // guests, so this fails closed the same way signup does.
+    let ip = crate::api::state::client_ip(
+        &headers,
+        settings.server.trust_forwarded_for(),
+    );
+    if bot_challenge_verifier
+        .verify("public/events/rsvp", request.captcha_token.as_deref(), Some(ip))
+        .await
+        .is_err()
+    {
+        return Err(AppError::Forbidden);
+    }
 
-    for event in events {
-        let is_private = event.visibility != EventVisibility::Public;
+    let name = request.name.trim();
+    let email = request.email.trim().to_lowercase();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("Name is required".to_string()));
+    }
+    if name.len() > 200 {
+        return Err(AppError::BadRequest("Name too long".to_string()));
+    }
+    if !email.contains('@') || email.contains(char::is_whitespace) {
+        return Err(AppError::BadRequest("Valid email is required".to_string()));
+    }
+    if email.len() > 254 {
+        return Err(AppError::BadRequest("Email too long".to_string()));
+    }
 
-        ical.push_str("BEGIN:VEVENT\r\n");
-        ical.push_str(&format!("UID:{}\r\n", event.id));
-        ical.push_str(&format!("DTSTART:{}\r\n", event.start_time.format("%Y%m%dT%H%M%SZ")));
+    let not_found = || AppError::NotFound("Event not found".to_string());
+    let event = event_repo.find_by_id(request.event_id).await?.ok_or_else(not_found)?;
+    if event.status != EventStatus::Published
+        || !can_view_event(None, &event)
+        || !event.allow_guest_rsvp
+        || event.start_time <= Utc::now()
+    {
+        return Err(not_found());
+    }
 
-        if let Some(end_time) = event.end_time {
-            ical.push_str(&format!("DTEND:{}\r\n", end_time.format("%Y%m%dT%H%M%SZ")));
+    let registered = match event_repo.register_guest(event.id, name, &email).await {
+        Err(AppError::RsvpClosed(deadline)) => {
+            // Guests have no locale of their own; the org's is what
+            // the rest of the public site speaks.
+            let locale = settings_service.org_locale().await;
+            return Err(AppError::BadRequest(format!(
+                "RSVPs for this event closed on {}",
+                locale.long_date_time(&deadline)
+            )));
         }
+        result => result?,
+    };
+    if !registered {
+        return Err(AppError::Conflict("This event is full".to_string()));
+    }
 
-        if is_private {
-            // Sanitize private events - show only that something is happening
-            ical.push_str("SUMMARY:Members-Only Event\r\n");
-            ical.push_str("DESCRIPTION:This event is for members only. Log in to the portal to see details.\r\n");
-        } else {
-            ical.push_str(&format!("SUMMARY:{}\r\n", escape_ical_text(&event.title)));
-            ical.push_str(&format!("DESCRIPTION:{}\r\n", escape_ical_text(&event.description)));
+    Ok((
+        StatusCode::CREATED,
+        Json(GuestRsvpResponse {
+            message: format!("You're on the list for {}.", event.title),
+        }),
+    ))
+}
 
-            if let Some(location) = &event.location {
-                ical.push_str(&format!("LOCATION:{}\r\n", escape_ical_text(location)));
-            }
-        }
+// ---------------------------------------------------------------------
+// Contact form
+// ---------------------------------------------------------------------
 
-        ical.push_str(&format!("CREATED:{}\r\n", event.created_at.format("%Y%m%dT%H%M%SZ")));
-        ical.push_str(&format!("LAST-MODIFIED:{}\r\n", event.updated_at.format("%Y%m%dT%H%M%SZ")));
-        ical.push_str("STATUS:CONFIRMED\r\n");
-        ical.push_str("END:VEVENT\r\n");
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PublicContactRequest {
+    pub name: String,
+    pub email: String,
+    #[serde(default)]
+    pub category: ContactCategory,
+    pub message: String,
+    /// Honeypot. The public site should render it as a hidden `website`
+    /// input and send whatever it holds; people leave it empty.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicContactResponse {
+    pub message: String,
+}
+
+/// POST /public/contact — send the admins a message from the public
+/// site. Same inbox and admin notification as the `/contact` page.
+///
+/// Rate-limited to 5 messages per hour per IP. A filled-in honeypot
+/// gets the same 202 as a real message but nothing is stored.
+#[utoipa::path(
+    post,
+    path = "/public/contact",
+    tag = "public",
+    request_body = PublicContactRequest,
+    responses(
+        (status = 202, description = "Message received", body = PublicContactResponse),
+        (status = 400, description = "Missing name or message, or invalid email"),
+        (status = 404, description = "The contact form is turned off"),
+        (status = 429, description = "Rate-limit hit (per-IP contact limiter)"),
+    ),
+)]
+pub async fn contact(
+    State(settings): State<Arc<Settings>>,
+    State(contact_limiter): State<ContactLimiter>,
+    State(contact_service): State<Arc<ContactService>>,
+    headers: HeaderMap,
+    Json(request): Json<PublicContactRequest>,
+) -> Result<(StatusCode, Json<PublicContactResponse>)> {
+    let ip = crate::api::state::client_ip(
+        &headers,
+        settings.server.trust_forwarded_for(),
+    );
+    if !contact_limiter.0.check_and_record(ip) {
+        return Err(AppError::TooManyRequests);
     }
 
-    ical.push_str("END:VCALENDAR\r\n");
-    ical
+    contact_service
+        .submit(ContactInput {
+            name: request.name,
+            email: request.email,
+            category: request.category,
+            message: request.message,
+            honeypot: request.website,
+        })
+        .await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(PublicContactResponse {
+            message: "Thanks — an admin will reply by email.".to_string(),
+        }),
+    ))
 }
 
 // ---------------------------------------------------------------------
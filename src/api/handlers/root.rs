@@ -11,7 +11,7 @@ use serde::Serialize;
 use serde_json::json;
 use utoipa::ToSchema;
 
-use crate::auth::AuthService;
+use crate::{auth::AuthService, service::readiness_service::ReadinessService};
 
 #[derive(Serialize, ToSchema)]
 pub struct ApiInfo {
@@ -28,6 +28,24 @@ pub struct HealthStatus {
     pub timestamp: String,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessStatus {
+    /// `ready` or `not_ready`.
+    pub status: String,
+    /// Latest result per dependency; empty until the startup check has run.
+    pub checks: Vec<DependencyStatus>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DependencyStatus {
+    /// `database`, or an integration name such as `Discord`.
+    pub name: String,
+    /// Whether a failure here holds the instance not-ready.
+    pub critical: bool,
+    pub healthy: bool,
+    pub error: Option<String>,
+}
+
 /// Root endpoint with content negotiation:
 /// - Browsers (Accept: text/html): redirect to dashboard if logged in, else to login
 /// - API clients (Accept: application/json) get API info JSON
@@ -70,13 +88,14 @@ pub async fn root(
             "description": "Member management system for clubs and organizations",
             "status": "operational",
             "endpoints": {
-                "health": "GET /health - Health check",
+                "health": "GET /health - Liveness check",
+                "ready": "GET /ready - Readiness check",
                 "api_info": "GET /api - API information",
                 "public": {
                     "signup": "POST /public/signup - Register new member",
                     "events": "GET /public/events - List public events",
-                    "announcements": "GET /public/announcements - List public announcements",
-                    "rss": "GET /public/feed/rss - RSS feed",
+                    "announcements": "GET /public/announcements?limit=&offset=&type= - Page of public announcements",
+                    "rss": "GET /public/feed/rss?limit=&type= - RSS feed",
                     "calendar": "GET /public/feed/calendar - iCal feed"
                 },
                 "auth": {
@@ -113,6 +132,41 @@ pub async fn health_check() -> impl IntoResponse {
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Readiness probe — the startup dependency check passed",
+            body = ReadinessStatus),
+        (status = 503, description = "Not ready yet: the startup check hasn't run or a critical \
+            dependency failed it", body = ReadinessStatus),
+    ),
+)]
+pub async fn readiness_check(
+    State(readiness_service): State<Arc<ReadinessService>>,
+) -> impl IntoResponse {
+    let report = readiness_service.report().await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(ReadinessStatus {
+        status: if report.ready { "ready" } else { "not_ready" }.to_string(),
+        checks: report
+            .checks
+            .into_iter()
+            .map(|c| DependencyStatus {
+                name: c.name,
+                critical: c.critical,
+                healthy: c.healthy,
+                error: c.error,
+            })
+            .collect(),
+    }))
+}
+
 #[utoipa::path(
     get,
     path = "/api",
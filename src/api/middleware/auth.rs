@@ -1,20 +1,31 @@
 use axum::{
     extract::{Request, State},
-    http::Uri,
+    http::{Method, StatusCode, Uri},
     middleware::Next,
-    response::{IntoResponse, Redirect, Response},
+    response::{Html, IntoResponse, Redirect, Response},
 };
 use axum_extra::extract::CookieJar;
 
 use crate::{
     api::state::AppState,
-    domain::{Member, MemberStatus},
+    domain::{Locale, Member, MemberStatus},
     error::AppError,
 };
 
 #[derive(Clone)]
 pub struct CurrentUser {
     pub member: Member,
+    /// Display locale for this request: the member's override, else
+    /// the org default. Resolved once in the auth gate so handlers
+    /// don't each re-read settings.
+    pub locale: Locale,
+}
+
+impl CurrentUser {
+    async fn resolve(state: &AppState, member: Member) -> Self {
+        let locale = state.service_context.settings_service.locale_for(&member).await;
+        Self { member, locale }
+    }
 }
 
 #[derive(Clone)]
@@ -71,6 +82,13 @@ async fn authenticate(
         .await
         .map_err(|_| RejectReason::MemberNotFound)?
         .ok_or(RejectReason::MemberNotFound)?;
+    // Under the derived expiry mode a lapsed member is still `Active`
+    // in the database; gate (and show) them as `Expired`.
+    let member = state
+        .service_context
+        .settings_service
+        .with_effective_status(member)
+        .await;
     if !policy.allowed_statuses.contains(&member.status) {
         return Err(RejectReason::StatusBlocked(member.status.clone()));
     }
@@ -107,7 +125,21 @@ fn redirect_to_login(original_uri: &Uri) -> Response {
     Redirect::to(&format!("/login?redirect={}", urlencoding::encode(path))).into_response()
 }
 
-fn render_reject(reason: RejectReason, behavior: RejectBehavior, original_uri: &Uri) -> Response {
+/// Whether the rejected request was a plain page navigation (a
+/// redirect makes sense) rather than an HTMX swap or a form submission,
+/// where a redirect would be followed silently and render the target
+/// page in place of the fragment with a 200.
+fn is_page_navigation(request: &Request) -> bool {
+    matches!(*request.method(), Method::GET | Method::HEAD)
+        && !request.headers().contains_key("HX-Request")
+}
+
+fn render_reject(
+    reason: RejectReason,
+    behavior: RejectBehavior,
+    original_uri: &Uri,
+    page_navigation: bool,
+) -> Response {
     match behavior {
         RejectBehavior::Json401 => match reason {
             RejectReason::StatusBlocked(MemberStatus::Pending) => AppError::Forbidden.into_response(),
@@ -121,7 +153,10 @@ fn render_reject(reason: RejectReason, behavior: RejectBehavior, original_uri: &
             _ => redirect_to_login(original_uri),
         },
         RejectBehavior::RedirectToDashboardOrLogin => match reason {
-            RejectReason::NotAdmin => Redirect::to("/portal/dashboard").into_response(),
+            RejectReason::NotAdmin if page_navigation => {
+                Redirect::to("/portal/dashboard").into_response()
+            }
+            RejectReason::NotAdmin => (StatusCode::FORBIDDEN, Html("Access denied")).into_response(),
             RejectReason::AdminTotpMissing => {
                 Redirect::to("/portal/profile/security?reason=admin_totp_required").into_response()
             }
@@ -141,13 +176,14 @@ async fn gate(
     policy: &AccessPolicy,
 ) -> Response {
     let original_uri = request.uri().clone();
+    let page_navigation = is_page_navigation(&request);
     match authenticate(state, jar, policy).await {
         Ok(auth) => {
-            request.extensions_mut().insert(CurrentUser { member: auth.member });
+            request.extensions_mut().insert(CurrentUser::resolve(state, auth.member).await);
             request.extensions_mut().insert(SessionInfo { session_id: auth.session_id });
             next.run(request).await
         }
-        Err(reason) => render_reject(reason, policy.on_reject, &original_uri),
+        Err(reason) => render_reject(reason, policy.on_reject, &original_uri, page_navigation),
     }
 }
 
@@ -188,6 +224,23 @@ const POLICY_OPTIONAL_AUTH: AccessPolicy = AccessPolicy {
     on_reject: RejectBehavior::Json401,
 };
 
+/// Any live admin session, TOTP enforcement aside: used by middleware
+/// that runs ahead of routing, where an admin who still needs to enrol
+/// TOTP should reach the security page rather than be shut out.
+const POLICY_ADMIN_SESSION: AccessPolicy = AccessPolicy {
+    allowed_statuses: &[MemberStatus::Active, MemberStatus::Honorary],
+    require_admin: true,
+    enforce_admin_totp: false,
+    on_reject: RejectBehavior::Json401,
+};
+
+/// Whether the request carries a live admin session. For middleware
+/// outside the per-route gates (maintenance mode) that only needs a
+/// yes/no and never rejects.
+pub async fn is_admin_session(state: &AppState, jar: &CookieJar) -> bool {
+    authenticate(state, jar, &POLICY_ADMIN_SESSION).await.is_ok()
+}
+
 pub async fn require_auth(
     State(state): State<AppState>,
     jar: CookieJar,
@@ -196,7 +249,7 @@ pub async fn require_auth(
 ) -> Result<Response, AppError> {
     match authenticate(&state, &jar, &POLICY_REQUIRE_AUTH).await {
         Ok(auth) => {
-            request.extensions_mut().insert(CurrentUser { member: auth.member });
+            request.extensions_mut().insert(CurrentUser::resolve(&state, auth.member).await);
             request.extensions_mut().insert(SessionInfo { session_id: auth.session_id });
             Ok(next.run(request).await)
         }
@@ -236,6 +289,8 @@ pub async fn require_restorable(
 
 /// Like require_admin but redirects non-admins to the member dashboard
 /// instead of returning a 403 JSON response. Used for portal admin routes.
+/// HTMX requests and form submissions from a non-admin get a plain 403
+/// instead, since following the redirect would answer them with a 200.
 ///
 /// Also enforces the optional `auth.require_totp_for_admins` toggle:
 /// when set, an admin without `totp_enabled_at` is redirected to the
@@ -263,7 +318,6 @@ pub async fn require_admin_redirect(
 
 /// Middleware that optionally adds session info to requests.
 /// Useful for pages that work differently for logged-in vs logged-out users.
-#[allow(dead_code)]
 pub async fn optional_auth(
     State(state): State<AppState>,
     jar: CookieJar,
@@ -271,7 +325,7 @@ pub async fn optional_auth(
     next: Next,
 ) -> Response {
     if let Ok(auth) = authenticate(&state, &jar, &POLICY_OPTIONAL_AUTH).await {
-        request.extensions_mut().insert(CurrentUser { member: auth.member });
+        request.extensions_mut().insert(CurrentUser::resolve(&state, auth.member).await);
     }
     next.run(request).await
 }
@@ -1,9 +1,10 @@
 //! Cloudflare Turnstile (or compatible) bot-challenge verification.
 //!
-//! Sits in front of `POST /public/signup` and `POST /public/donate` —
-//! the two CSRF-exempt public endpoints that have side effects an
-//! attacker would care about (carding via Stripe Checkout, fake-account
-//! mass signup). Per-IP rate limiting catches single-source bursts;
+//! Sits in front of `POST /public/signup`, `POST /public/donate`, and
+//! `POST /public/events/rsvp` — the CSRF-exempt public endpoints that
+//! have side effects an attacker would care about (carding via Stripe
+//! Checkout, fake-account mass signup, filling an event with made-up
+//! guests). Per-IP rate limiting catches single-source bursts;
 //! this catches distributed bots.
 //!
 //! Failure mode is **fail closed**: when the org has configured a
@@ -0,0 +1,56 @@
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::api::state::AppState;
+
+/// How long shed clients are told to wait before retrying, in seconds.
+/// Spikes that trip the limit are short; a browser retrying after a
+/// couple of seconds usually gets through.
+const RETRY_AFTER_SECS: &str = "2";
+
+/// Paths that never count against the limit: load-balancer probes,
+/// which must keep answering while the instance is busy (a shed
+/// `/health` would get a merely busy instance restarted), and the
+/// Stripe webhook, whose 503s Stripe turns into a retry storm on top
+/// of the spike.
+const EXEMPT_PREFIXES: &[&str] = &["/health", "/ready", "/api/payments/webhook/stripe"];
+
+/// Middleware that caps concurrent in-flight requests at
+/// `server.max_in_flight_requests` and answers the excess with
+/// `503 Service Unavailable` plus `Retry-After`, instead of queueing
+/// them until the SQLite pool times out.
+///
+/// Shedding is immediate: a request either takes a slot on arrival
+/// or is refused. The slot is held until the response is produced.
+/// With the limit set to 0, `AppState::request_slots` is `None` and
+/// every request passes.
+pub async fn shed_load(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(slots) = state.request_slots.as_ref() else {
+        return next.run(request).await;
+    };
+    let path = request.uri().path();
+    if EXEMPT_PREFIXES.iter().any(|p| path.starts_with(p)) {
+        return next.run(request).await;
+    }
+
+    match slots.clone().try_acquire_owned() {
+        Ok(_permit) => next.run(request).await,
+        Err(_) => {
+            tracing::warn!("Shedding {} {}: too many requests in flight", request.method(), path);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, RETRY_AFTER_SECS)],
+                "Server busy, please retry shortly",
+            )
+                .into_response()
+        }
+    }
+}
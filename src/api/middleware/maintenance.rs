@@ -0,0 +1,123 @@
+use std::net::IpAddr;
+
+use askama::Template;
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{Html, IntoResponse, Response},
+};
+use axum_extra::extract::CookieJar;
+
+use crate::api::{
+    middleware::auth::is_admin_session,
+    state::{client_ip, AppState},
+};
+
+/// How long clients are told to wait before retrying, in seconds.
+const RETRY_AFTER_SECS: &str = "300";
+
+/// Paths that keep working during maintenance: logging in (so an admin
+/// can get past the page), load-balancer probes, the assets the
+/// maintenance and login pages need, and the Stripe webhook — Stripe
+/// retries failed deliveries for days, but a payment that completes
+/// mid-upgrade should still extend the member's dues right away.
+const EXEMPT_PREFIXES: &[&str] = &[
+    "/login",
+    "/auth/login",
+    "/health",
+    "/ready",
+    "/static",
+    "/favicon",
+    "/api/payments/webhook/stripe",
+];
+
+#[derive(Template)]
+#[template(path = "maintenance.html")]
+struct MaintenanceTemplate {
+    org_name: String,
+    message: String,
+}
+
+/// Middleware that serves a 503 maintenance page while
+/// `maintenance.enabled` is set.
+///
+/// The flag is read from `app_settings` on every request rather than
+/// cached: it's one primary-key lookup, and it means switching the
+/// flag from the admin settings page takes effect immediately on every
+/// worker without a restart.
+///
+/// Requests pass through when the path is in `EXEMPT_PREFIXES`, the
+/// client IP is listed in `maintenance.allowed_ips`, or the request
+/// carries an admin session.
+pub async fn maintenance_mode(
+    State(state): State<AppState>,
+    jar: CookieJar,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if EXEMPT_PREFIXES.iter().any(|p| path.starts_with(p)) {
+        return next.run(request).await;
+    }
+
+    let settings = &state.service_context.settings_service;
+    if !settings
+        .get_bool("maintenance.enabled")
+        .await
+        .unwrap_or(false)
+    {
+        return next.run(request).await;
+    }
+
+    let ip = client_ip(
+        request.headers(),
+        state.settings.server.trust_forwarded_for(),
+    );
+    let allowed_ips = settings
+        .get_value("maintenance.allowed_ips")
+        .await
+        .unwrap_or_default();
+    if parse_allowed_ips(&allowed_ips).contains(&ip) || is_admin_session(&state, &jar).await {
+        return next.run(request).await;
+    }
+
+    let org_name = settings
+        .get_value("org.name")
+        .await
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Coterie".to_string());
+    let message = settings
+        .get_value("maintenance.message")
+        .await
+        .unwrap_or_default();
+    let body = MaintenanceTemplate { org_name, message }
+        .render()
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to render maintenance page: {}", e);
+            "Down for maintenance".to_string()
+        });
+
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [
+            (header::RETRY_AFTER, RETRY_AFTER_SECS),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        Html(body),
+    )
+        .into_response()
+}
+
+/// Parse `maintenance.allowed_ips`. Entries that aren't IP addresses
+/// are skipped here; `SettingsService::update_setting` rejects them on
+/// save.
+pub fn parse_allowed_ips(value: &str) -> Vec<IpAddr> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse().ok())
+        .collect()
+}
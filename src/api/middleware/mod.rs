@@ -1,5 +1,7 @@
 pub mod auth;
 pub mod bot_challenge;
+pub mod load_shed;
+pub mod maintenance;
 pub mod security;
 pub mod security_headers;
 pub mod setup;
\ No newline at end of file
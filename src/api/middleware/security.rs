@@ -29,14 +29,16 @@
 use axum::{
     body::{to_bytes, Body},
     extract::{Request, State},
-    http::{header, Method},
+    http::{header, Method, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Json, Response},
 };
 use axum_extra::extract::CookieJar;
+use serde_json::json;
 
 use crate::{
     api::{middleware::auth::SessionInfo, state::AppState},
+    auth::CsrfRejection,
     error::AppError,
 };
 
@@ -60,6 +62,17 @@ use crate::{
 ///   the CORS allowed-origins list and rate-limited; that's the
 ///   security model for these endpoints.
 ///
+/// * **`POST /public/events/rsvp`** — guest RSVPs from the same
+///   marketing site, for visitors who have no account at all. Same
+///   model as signup: CORS allowed-origins plus the bot challenge.
+///
+/// * **`POST /contact`** and **`POST /public/contact`** — the
+///   contact-the-admins form, for visitors with no account or no
+///   working login, so there's no session to bind a token to. The
+///   worst a forged submission can do is leave a message in the
+///   admin inbox, which a bot can do directly anyway; both are
+///   rate-limited per IP and carry a honeypot field instead.
+///
 /// * **`POST /auth/login`** — by definition no session exists yet,
 ///   so there's nothing to bind a CSRF token to. Login CSRF is a
 ///   real but separate threat (an attacker forces you to log into
@@ -76,6 +89,9 @@ const CSRF_EXEMPT_PATHS: &[(&str, &str)] = &[
     ("POST", "/api/payments/webhook/stripe"),
     ("POST", "/public/signup"),
     ("POST", "/public/donate"),
+    ("POST", "/public/events/rsvp"),
+    ("POST", "/contact"),
+    ("POST", "/public/contact"),
     ("POST", "/auth/login"),
 ];
 
@@ -94,8 +110,12 @@ fn is_exempt(method: &Method, path: &str) -> bool {
 /// 3. State-changing methods on non-exempt paths: the request must
 ///    carry a valid session cookie AND a valid `X-CSRF-Token` header
 ///    (or, for plain `application/x-www-form-urlencoded` bodies, a
-///    `csrf_token` form field) bound to that session. Anything else
-///    is rejected with 403.
+///    `csrf_token` form field) bound to that session and not yet
+///    expired. A token that fails for an identifiable reason (expired,
+///    wrong session, wrong action) gets a 403 whose JSON body says why
+///    and whose `X-CSRF-Token` header carries a fresh token, so the
+///    client can recover without a full reload. No session at all is a
+///    plain 403.
 ///
 /// On success, this middleware injects [`SessionInfo`] into the
 /// request extensions so downstream per-route auth middleware doesn't
@@ -136,13 +156,8 @@ pub async fn csrf_protect_unless_exempt(
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string())
     {
-        let is_valid = state
-            .service_context
-            .csrf_service
-            .validate_token(&session_id, &token)
-            .await?;
-        if !is_valid {
-            return Err(AppError::Forbidden);
+        if let Err(rejection) = check_token(&state, &session_id, &token, &path) {
+            return Ok(csrf_rejected(&state, &session_id, rejection).await);
         }
         let mut request = request;
         request.extensions_mut().insert(SessionInfo { session_id });
@@ -159,10 +174,10 @@ pub async fn csrf_protect_unless_exempt(
         .to_string();
 
     if content_type.starts_with("application/x-www-form-urlencoded") {
-        return validate_form_body(state, session_id, request, next).await;
+        return validate_form_body(state, session_id, &path, request, next).await;
     }
     if content_type.starts_with("multipart/form-data") {
-        return validate_multipart_body(state, session_id, &content_type, request, next).await;
+        return validate_multipart_body(state, session_id, &path, &content_type, request, next).await;
     }
     // JSON / missing / other — expected to bring the header.
     Err(AppError::Forbidden)
@@ -175,6 +190,7 @@ pub async fn csrf_protect_unless_exempt(
 async fn validate_form_body(
     state: AppState,
     session_id: String,
+    path: &str,
     request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
@@ -187,14 +203,11 @@ async fn validate_form_body(
     struct CsrfField {
         csrf_token: String,
     }
-    let parsed: CsrfField = serde_urlencoded::from_bytes(&bytes).map_err(|_| AppError::Forbidden)?;
-    let is_valid = state
-        .service_context
-        .csrf_service
-        .validate_token(&session_id, &parsed.csrf_token)
-        .await?;
-    if !is_valid {
-        return Err(AppError::Forbidden);
+    let token = serde_urlencoded::from_bytes::<CsrfField>(&bytes)
+        .map(|f| f.csrf_token)
+        .unwrap_or_default();
+    if let Err(rejection) = check_token(&state, &session_id, &token, path) {
+        return Ok(csrf_rejected(&state, &session_id, rejection).await);
     }
 
     parts.extensions.insert(SessionInfo { session_id });
@@ -215,6 +228,7 @@ async fn validate_form_body(
 async fn validate_multipart_body(
     state: AppState,
     session_id: String,
+    path: &str,
     content_type: &str,
     request: Request,
     next: Next,
@@ -240,18 +254,47 @@ async fn validate_multipart_body(
             break;
         }
     }
-    let token = token.ok_or(AppError::Forbidden)?;
-
-    let is_valid = state
-        .service_context
-        .csrf_service
-        .validate_token(&session_id, &token)
-        .await?;
-    if !is_valid {
-        return Err(AppError::Forbidden);
+    let token = token.unwrap_or_default();
+    if let Err(rejection) = check_token(&state, &session_id, &token, path) {
+        return Ok(csrf_rejected(&state, &session_id, rejection).await);
     }
 
     parts.extensions.insert(SessionInfo { session_id });
     let request = Request::from_parts(parts, Body::from(bytes));
     Ok(next.run(request).await)
 }
+
+fn check_token(
+    state: &AppState,
+    session_id: &str,
+    token: &str,
+    path: &str,
+) -> std::result::Result<(), CsrfRejection> {
+    state.service_context.csrf_service.verify(session_id, token, path)
+}
+
+/// 403 for a session that's real but whose token didn't pass. The
+/// body names the reason; the fresh session-wide token rides in the
+/// `X-CSRF-Token` header (and the body, for form posts that render
+/// the JSON) so `base.html` can swap it into the meta tag.
+async fn csrf_rejected(state: &AppState, session_id: &str, rejection: CsrfRejection) -> Response {
+    tracing::debug!("CSRF token rejected: {:?}", rejection);
+    let fresh = state
+        .service_context
+        .csrf_service
+        .generate_token(session_id)
+        .await
+        .unwrap_or_default();
+    let mut response = (
+        StatusCode::FORBIDDEN,
+        Json(json!({
+            "error": rejection.message(),
+            "csrf_token": fresh,
+        })),
+    )
+        .into_response();
+    if let Ok(value) = fresh.parse() {
+        response.headers_mut().insert("X-CSRF-Token", value);
+    }
+    response
+}
@@ -8,7 +8,7 @@ use axum::{
 use base64::Engine;
 use rand::RngCore;
 
-use crate::api::state::AppState;
+use crate::{api::state::AppState, config::SecurityHeadersConfig};
 
 /// Sentinel that templates use in `<script nonce="__CSP_NONCE__">`.
 /// The middleware substitutes the per-request nonce on the way out.
@@ -21,6 +21,10 @@ const NONCE_PLACEHOLDER: &str = "__CSP_NONCE__";
 /// also bounds memory under any pathological response.
 const MAX_REWRITE_BYTES: usize = 4 * 1024 * 1024;
 
+/// Paths under this prefix are embeddable widgets; see
+/// `SecurityHeadersConfig::embed_frame_ancestors`.
+const EMBED_PATH_PREFIX: &str = "/public/embed/";
+
 /// Adds baseline security response headers including a strict
 /// Content-Security-Policy with a per-request script nonce.
 ///
@@ -42,6 +46,17 @@ const MAX_REWRITE_BYTES: usize = 4 * 1024 * 1024;
 ///
 /// CDN scripts are pinned with SRI hashes in the HTML.
 /// Stripe.js is loaded from js.stripe.com on payment pages only.
+///
+/// Everything deployment-specific — report-only rollout, the CDN
+/// allowance, frame-ancestors, Referrer-Policy, HSTS max-age — comes
+/// from `[security_headers]` (see `SecurityHeadersConfig`).
+///
+/// Layered outermost in `main.rs`, after the API and portal routers
+/// are merged, so rendered pages, CSRF rejections and the maintenance
+/// page all carry the headers.
+///
+/// The `/public/embed/` widgets are meant to be framed by other sites,
+/// so they get `embed_frame_ancestors` instead of `frame_ancestors`.
 pub async fn security_headers(
     State(state): State<AppState>,
     request: Request,
@@ -52,49 +67,87 @@ pub async fn security_headers(
     let mut bytes = [0u8; 24];
     rand::thread_rng().fill_bytes(&mut bytes);
     let nonce = base64::engine::general_purpose::STANDARD.encode(bytes);
+    let is_embed = request.uri().path().starts_with(EMBED_PATH_PREFIX);
 
     let response = next.run(request).await;
     let mut response = rewrite_html_nonce(response, &nonce).await;
+    let config = &state.settings.security_headers;
+    let frame_ancestors = if is_embed {
+        config.embed_frame_ancestors.trim()
+    } else {
+        config.frame_ancestors.trim()
+    };
     let headers = response.headers_mut();
 
-    headers.insert(
-        header::X_FRAME_OPTIONS,
-        HeaderValue::from_static("DENY"),
-    );
+    // X-Frame-Options for browsers that predate frame-ancestors. It
+    // can only express "nowhere" or "same origin"; any other
+    // frame-ancestors list is left to the CSP alone.
+    match frame_ancestors {
+        "'none'" => {
+            headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("DENY"));
+        }
+        "'self'" => {
+            headers.insert(header::X_FRAME_OPTIONS, HeaderValue::from_static("SAMEORIGIN"));
+        }
+        _ => {}
+    }
     headers.insert(
         header::X_CONTENT_TYPE_OPTIONS,
         HeaderValue::from_static("nosniff"),
     );
-    headers.insert(
-        header::REFERRER_POLICY,
-        HeaderValue::from_static("strict-origin-when-cross-origin"),
-    );
+    if let Ok(value) = HeaderValue::from_str(&config.referrer_policy) {
+        headers.insert(header::REFERRER_POLICY, value);
+    }
+
+    let csp_header = if config.csp_report_only {
+        header::CONTENT_SECURITY_POLICY_REPORT_ONLY
+    } else {
+        header::CONTENT_SECURITY_POLICY
+    };
+    if let Ok(value) = HeaderValue::from_str(&content_security_policy(config, frame_ancestors, &nonce)) {
+        headers.insert(csp_header, value);
+    }
+
+    // HSTS only meaningful on TLS deployments — sending it over plain HTTP
+    // is ignored by browsers but sending it at all on dev would be noise.
+    if state.settings.server.cookies_are_secure() && config.hsts_max_age_secs > 0 {
+        let hsts = format!("max-age={}; includeSubDomains", config.hsts_max_age_secs);
+        if let Ok(value) = HeaderValue::from_str(&hsts) {
+            headers.insert(header::STRICT_TRANSPORT_SECURITY, value);
+        }
+    }
 
-    let csp = format!(
+    response
+}
+
+/// The policy for one response. The configured CDN origins are
+/// allowed for scripts and stylesheets alongside 'self'.
+fn content_security_policy(
+    config: &SecurityHeadersConfig,
+    frame_ancestors: &str,
+    nonce: &str,
+) -> String {
+    let cdn = config
+        .cdn_source_list()
+        .iter()
+        .map(|origin| format!(" {origin}"))
+        .collect::<String>();
+    let mut csp = format!(
         "default-src 'self'; \
-         script-src 'self' 'nonce-{nonce}' 'strict-dynamic' https://js.stripe.com https://unpkg.com; \
-         style-src 'self' 'unsafe-inline'; \
+         script-src 'self' 'nonce-{nonce}' 'strict-dynamic' https://js.stripe.com{cdn}; \
+         style-src 'self' 'unsafe-inline'{cdn}; \
          img-src 'self' data:; \
          connect-src 'self' https://api.stripe.com; \
          frame-src https://js.stripe.com; \
-         frame-ancestors 'none'; \
+         frame-ancestors {frame_ancestors}; \
          object-src 'none'; \
          base-uri 'self'",
     );
-    if let Ok(value) = HeaderValue::from_str(&csp) {
-        headers.insert(header::CONTENT_SECURITY_POLICY, value);
+    if let Some(uri) = config.csp_report_uri.as_deref().filter(|u| !u.is_empty()) {
+        csp.push_str("; report-uri ");
+        csp.push_str(uri);
     }
-
-    // HSTS only meaningful on TLS deployments — sending it over plain HTTP
-    // is ignored by browsers but sending it at all on dev would be noise.
-    if state.settings.server.cookies_are_secure() {
-        headers.insert(
-            header::STRICT_TRANSPORT_SECURITY,
-            HeaderValue::from_static("max-age=31536000; includeSubDomains"),
-        );
-    }
-
-    response
+    csp
 }
 
 /// Substitute the per-request nonce into HTML responses. Other
@@ -36,6 +36,7 @@ pub fn create_app(app_state: AppState) -> Router {
         // Root and health endpoints
         .route("/", get(handlers::root::root))
         .route("/health", get(handlers::root::health_check))
+        .route("/ready", get(handlers::root::readiness_check))
         .route("/api", get(handlers::root::api_info))
 
         // OpenAPI / Swagger UI for the public API. The UI is served at
@@ -76,11 +77,8 @@ pub fn create_app(app_state: AppState) -> Router {
         // `Router::merge`. Layers added before a `merge` call do not
         // propagate to the merged routes in axum 0.7 — applying CSRF
         // here would leave every state-changing /portal/* route
-        // unprotected.
-        .layer(axum::middleware::from_fn_with_state(
-            app_state.clone(),
-            middleware::security_headers::security_headers,
-        ))
+        // unprotected. Security headers are layered there too, for
+        // the same reason.
         .layer(CompressionLayer::new())
         .layer(cors_layer)
         .layer(TraceLayer::new_for_http())
@@ -140,15 +138,28 @@ fn payment_routes(state: AppState) -> Router<AppState> {
         )
 }
 
-fn public_routes(_state: AppState) -> Router<AppState> {
+fn public_routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/signup", post(handlers::public::signup))
+        .route("/signup/fields", get(handlers::public::signup_fields))
         .route("/donate", post(handlers::public::donate))
         .route("/events", get(handlers::public::list_events))
         .route("/events/private-count", get(handlers::public::private_event_count))
+        .route("/events/rsvp", post(handlers::public::guest_rsvp))
+        // Session optional: a logged-in member can also download the
+        // members-only events they can see in the portal.
+        .route(
+            "/events/:id/calendar",
+            get(handlers::public::event_calendar).route_layer(
+                axum::middleware::from_fn_with_state(state, middleware::auth::optional_auth),
+            ),
+        )
+        .route("/contact", post(handlers::public::contact))
         .route("/announcements", get(handlers::public::list_announcements))
         .route("/announcements/private-count", get(handlers::announcements::private_count))
+        .route("/announcements/:id/comments", get(handlers::announcements::list_comments))
         .route("/feed/rss", get(handlers::public::rss_feed))
         .route("/feed/calendar", get(handlers::public::calendar_feed))
+        .route("/types", get(handlers::public::list_types))
 }
 
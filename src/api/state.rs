@@ -7,7 +7,7 @@ use std::time::{Duration, Instant};
 use axum::extract::FromRef;
 use axum::http::HeaderMap;
 use sqlx::SqlitePool;
-use tokio::sync::Mutex as AsyncMutex;
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
 
 use crate::{
     api::middleware::bot_challenge::BotChallengeVerifier,
@@ -15,19 +15,31 @@ use crate::{
     config::Settings,
     email::EmailSender,
     integrations::IntegrationManager,
-    payments::{StripeClient, WebhookDispatcher},
+    payments::{StripeClient, WebhookDispatcher, WebhookMetrics},
     repository::{
         AnnouncementRepository, BasicTypeRepository, DonationCampaignRepository, EventRepository,
         EventSeriesRepository, MemberRepository, MembershipTypeRepository, PaymentRepository,
         ProcessedEventsRepository, SavedCardRepository, ScheduledPaymentRepository,
     },
     service::{
-        announcement_admin_service::AnnouncementAdminService, audit_service::AuditService,
-        basic_type_service::BasicTypeService, billing_service::BillingService,
-        event_admin_service::EventAdminService, member_service::MemberService,
+        admin_notification_service::AdminNotificationService,
+        announcement_admin_service::AnnouncementAdminService,
+        announcement_comment_service::AnnouncementCommentService, audit_service::AuditService,
+        backup_service::BackupService, basic_type_service::BasicTypeService, billing_service::BillingService,
+        celebration_service::CelebrationService, contact_service::ContactService,
+        landing_page_service::LandingPageService,
+        event_admin_service::EventAdminService, event_host_service::EventHostService,
+        event_invite_service::EventInviteService,
+        event_proposal_service::EventProposalService,
+        data_check_service::DataCheckService, directory_service::DirectoryService,
+        emergency_contact_service::EmergencyContactService,
+        integration_log_service::IntegrationLogService,
+        integration_outbox_service::IntegrationOutboxService,
+        login_history_service::LoginHistoryService, member_service::MemberService,
         membership_type_service::MembershipTypeService,
         payment_admin_service::PaymentAdminService, payment_service::PaymentService,
-        recurring_event_service::RecurringEventService, settings_service::SettingsService,
+        readiness_service::ReadinessService, recurring_event_service::RecurringEventService,
+        settings_service::SettingsService, signup_field_service::SignupFieldService,
         ServiceContext,
     },
 };
@@ -62,6 +74,12 @@ pub fn client_ip(headers: &HeaderMap, trust_forwarded: bool) -> IpAddr {
     IpAddr::from([127, 0, 0, 1])
 }
 
+/// The request's User-Agent, for login history. Non-UTF-8 values are
+/// treated as absent.
+pub fn user_agent(headers: &HeaderMap) -> Option<&str> {
+    headers.get(axum::http::header::USER_AGENT).and_then(|v| v.to_str().ok())
+}
+
 /// Simple in-memory rate limiter keyed by IP address.
 #[derive(Clone)]
 pub struct RateLimiter {
@@ -126,6 +144,103 @@ impl RateLimiter {
     }
 }
 
+/// In-memory failed-login tracker keyed by (account, IP).
+///
+/// Complements the per-IP `RateLimiter`: that one caps how often an IP
+/// may try at all, this one locks out a single account from a single IP
+/// once it has racked up `max_failures` wrong passwords within `window`.
+/// The lock holds for `cooldown` and is checked before any password
+/// work. Callers pass the account identifier; it's compared
+/// case-insensitively, and an identifier that matches no member locks
+/// out exactly like a real one.
+#[derive(Clone)]
+pub struct FailedLoginTracker {
+    entries: Arc<Mutex<HashMap<(String, IpAddr), FailedLogins>>>,
+    max_failures: usize,
+    window: Duration,
+    cooldown: Duration,
+}
+
+#[derive(Default)]
+struct FailedLogins {
+    failures: Vec<Instant>,
+    locked_until: Option<Instant>,
+}
+
+impl FailedLoginTracker {
+    pub fn new(max_failures: usize, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            max_failures,
+            window,
+            cooldown,
+        }
+    }
+
+    pub fn from_config(config: &crate::config::LoginRateLimitConfig) -> Self {
+        Self::new(
+            config.max_failures,
+            Duration::from_secs(config.window_minutes * 60),
+            Duration::from_secs(config.cooldown_minutes * 60),
+        )
+    }
+
+    fn key(account: &str, ip: IpAddr) -> (String, IpAddr) {
+        (account.trim().to_lowercase(), ip)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(String, IpAddr), FailedLogins>> {
+        // Best-effort like RateLimiter: recover a poisoned map rather
+        // than failing every login.
+        match self.entries.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Whether `account` is currently locked out from `ip`.
+    pub fn is_locked(&self, account: &str, ip: IpAddr) -> bool {
+        let map = self.lock();
+        map.get(&Self::key(account, ip))
+            .and_then(|e| e.locked_until)
+            .is_some_and(|until| until > Instant::now())
+    }
+
+    /// Record a wrong password (or unknown account). Starts the
+    /// cooldown once the window holds `max_failures` failures.
+    pub fn record_failure(&self, account: &str, ip: IpAddr) {
+        let mut map = self.lock();
+        let now = Instant::now();
+        let cutoff = now - self.window;
+        let entry = map.entry(Self::key(account, ip)).or_default();
+        entry.failures.retain(|t| *t > cutoff);
+        entry.failures.push(now);
+        if entry.failures.len() >= self.max_failures {
+            entry.failures.clear();
+            entry.locked_until = Some(now + self.cooldown);
+        }
+    }
+
+    /// Forget past failures after a successful login.
+    pub fn reset(&self, account: &str, ip: IpAddr) {
+        self.lock().remove(&Self::key(account, ip));
+    }
+
+    /// Drop pairs with no recent failures and no active lock.
+    pub fn cleanup(&self) {
+        let mut map = self.lock();
+        let now = Instant::now();
+        let cutoff = now - self.window;
+        map.retain(|_, entry| {
+            entry.failures.retain(|t| *t > cutoff);
+            if entry.locked_until.is_some_and(|until| until <= now) {
+                entry.locked_until = None;
+            }
+            !entry.failures.is_empty() || entry.locked_until.is_some()
+        });
+    }
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub service_context: Arc<ServiceContext>,
@@ -143,6 +258,9 @@ pub struct AppState {
     pub settings: Arc<Settings>,
     /// Rate limiter for login endpoints (5 attempts per 15 minutes per IP).
     pub login_limiter: RateLimiter,
+    /// Per-account, per-IP lockout after repeated failed logins. Sized
+    /// by `auth.login_rate_limit`.
+    pub login_failures: FailedLoginTracker,
     /// Rate limiter for money-moving endpoints (charge, donate, refund,
     /// auto-renew toggle). 10 attempts/min per IP — well above any
     /// legitimate workflow but tight enough to box in scripted abuse,
@@ -151,6 +269,11 @@ pub struct AppState {
     /// not the authenticated identity (which an attacker controlling
     /// a stolen session would also control).
     pub money_limiter: RateLimiter,
+    /// Rate limiter for the contact-the-admins form (`/contact` and
+    /// `/public/contact`): 5 messages per hour per IP. Nobody with a
+    /// real question needs more, and it keeps the admin inbox from
+    /// being flooded by one source.
+    pub contact_limiter: RateLimiter,
     /// Serializes first-admin setup to prevent concurrent requests from
     /// both passing the "no admin exists" check and creating two admins.
     pub setup_lock: Arc<AsyncMutex<()>>,
@@ -163,6 +286,16 @@ pub struct AppState {
     /// `bot_challenge.provider = "disabled"` (the default) this is the
     /// no-op `DisabledVerifier`, so existing dev flows keep working.
     pub bot_challenge_verifier: Arc<dyn BotChallengeVerifier>,
+    /// On-demand and scheduled SQLite snapshots, written under
+    /// `settings.server.backups_path()`.
+    pub backup_service: Arc<BackupService>,
+    /// Backs `/ready`. Not ready until `main` has run a passing startup
+    /// check — see `service::readiness_service`.
+    pub readiness_service: Arc<ReadinessService>,
+    /// One permit per request allowed in flight; `None` when
+    /// `server.max_in_flight_requests` is 0. See
+    /// `api::middleware::load_shed`.
+    pub request_slots: Option<Arc<Semaphore>>,
 }
 
 impl AppState {
@@ -175,6 +308,20 @@ impl AppState {
         bot_challenge_verifier: Arc<dyn BotChallengeVerifier>,
         money_limiter: MoneyLimiter,
     ) -> Self {
+        let backup_service = Arc::new(BackupService::new(
+            service_context.db_pool.clone(),
+            settings.server.backups_path(),
+        ));
+        let readiness_service = Arc::new(ReadinessService::new(
+            service_context.db_pool.clone(),
+            service_context.integration_manager.clone(),
+            settings.integrations.startup_policy,
+        ));
+        let request_slots = match settings.server.max_in_flight_requests {
+            0 => None,
+            n => Some(Arc::new(Semaphore::new(n))),
+        };
+        let login_failures = FailedLoginTracker::from_config(&settings.auth.login_rate_limit);
         Self {
             service_context,
             stripe_client,
@@ -182,10 +329,15 @@ impl AppState {
             billing_service,
             settings,
             login_limiter: RateLimiter::new(5, Duration::from_secs(15 * 60)),
+            login_failures,
             money_limiter: money_limiter.0,
+            contact_limiter: RateLimiter::new(5, Duration::from_secs(60 * 60)),
             setup_lock: Arc::new(AsyncMutex::new(())),
             admin_exists_observed: Arc::new(AtomicBool::new(false)),
             bot_challenge_verifier,
+            backup_service,
+            readiness_service,
+            request_slots,
         }
     }
 }
@@ -267,6 +419,12 @@ impl FromRef<AppState> for Arc<dyn ProcessedEventsRepository> {
     }
 }
 
+impl FromRef<AppState> for Arc<WebhookMetrics> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.webhook_metrics.clone()
+    }
+}
+
 // --- Services ---
 
 impl FromRef<AppState> for Arc<AuthService> {
@@ -344,6 +502,66 @@ impl FromRef<AppState> for AnnouncementBasicTypeService {
     }
 }
 
+impl FromRef<AppState> for Arc<BackupService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.backup_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ReadinessService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.readiness_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<DataCheckService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.data_check_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<IntegrationLogService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.integration_log_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<IntegrationOutboxService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.integration_outbox_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EventInviteService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.event_invite_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LoginHistoryService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.login_history_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SignupFieldService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.signup_field_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<DirectoryService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.directory_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EmergencyContactService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.emergency_contact_service.clone()
+    }
+}
+
 impl FromRef<AppState> for Arc<MemberService> {
     fn from_ref(state: &AppState) -> Self {
         state.service_context.member_service.clone()
@@ -356,12 +574,54 @@ impl FromRef<AppState> for Arc<EventAdminService> {
     }
 }
 
+impl FromRef<AppState> for Arc<EventHostService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.event_host_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EventProposalService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.event_proposal_service.clone()
+    }
+}
+
 impl FromRef<AppState> for Arc<AnnouncementAdminService> {
     fn from_ref(state: &AppState) -> Self {
         state.service_context.announcement_admin_service.clone()
     }
 }
 
+impl FromRef<AppState> for Arc<AnnouncementCommentService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.announcement_comment_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AdminNotificationService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.admin_notification_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<CelebrationService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.celebration_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LandingPageService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.landing_page_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ContactService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.service_context.contact_service.clone()
+    }
+}
+
 impl FromRef<AppState> for Arc<PaymentAdminService> {
     fn from_ref(state: &AppState) -> Self {
         state.service_context.payment_admin_service.clone()
@@ -420,9 +680,9 @@ impl FromRef<AppState> for SqlitePool {
 
 // --- Rate limiters and locks ---
 //
-// RateLimiter appears twice on AppState (login_limiter, money_limiter), so
-// a bare `FromRef<AppState> for RateLimiter` would be ambiguous. Each
-// limiter gets a newtype wrapper.
+// RateLimiter appears several times on AppState (login_limiter,
+// money_limiter, contact_limiter), so a bare `FromRef<AppState> for
+// RateLimiter` would be ambiguous. Each limiter gets a newtype wrapper.
 
 #[derive(Clone)]
 pub struct LoginLimiter(pub RateLimiter);
@@ -430,18 +690,33 @@ pub struct LoginLimiter(pub RateLimiter);
 #[derive(Clone)]
 pub struct MoneyLimiter(pub RateLimiter);
 
+#[derive(Clone)]
+pub struct ContactLimiter(pub RateLimiter);
+
 impl FromRef<AppState> for LoginLimiter {
     fn from_ref(state: &AppState) -> Self {
         LoginLimiter(state.login_limiter.clone())
     }
 }
 
+impl FromRef<AppState> for FailedLoginTracker {
+    fn from_ref(state: &AppState) -> Self {
+        state.login_failures.clone()
+    }
+}
+
 impl FromRef<AppState> for MoneyLimiter {
     fn from_ref(state: &AppState) -> Self {
         MoneyLimiter(state.money_limiter.clone())
     }
 }
 
+impl FromRef<AppState> for ContactLimiter {
+    fn from_ref(state: &AppState) -> Self {
+        ContactLimiter(state.contact_limiter.clone())
+    }
+}
+
 impl FromRef<AppState> for Arc<AsyncMutex<()>> {
     fn from_ref(state: &AppState) -> Self {
         state.setup_lock.clone()
@@ -1,16 +1,35 @@
-//! CSRF token service. Stateless: tokens are `nonce || HMAC(key, session_id || nonce)`
+//! CSRF token service. Stateless: tokens are
+//! `nonce || issued_at || scope || HMAC(key, session_id || issued_at || scope || action || nonce)`
 //! hex-encoded, validated by recomputing the MAC with constant-time comparison.
 //!
 //! **Why stateless.** The previous per-session DB design overwrote the
 //! token on every `generate_token` call, which broke multi-tab admin
 //! workflows. HMAC has no such problem — any tab can generate any
 //! number of tokens for the same session, and they all validate
-//! independently until the session itself expires.
+//! independently until they expire.
+//!
+//! **Expiry.** The issue time is inside the MAC, so a token can't be
+//! "refreshed" by editing its timestamp. A token older than the
+//! configured TTL (`auth.csrf_token_ttl_hours`) is rejected even if
+//! its session is still alive — a page left open overnight gets one
+//! 403 carrying a fresh token, not an indefinitely replayable one.
+//!
+//! **Action binding.** `generate_token_for_action` mints a token that
+//! only validates for one request path. Most pages use the
+//! session-wide token from `BaseContext`; sensitive one-shot forms
+//! (2FA enrollment) bind theirs so a token lifted from that fragment
+//! can't be replayed against a different endpoint.
+//!
+//! **Not a double-submit cookie.** Nothing here compares a token to a
+//! cookie value, so planting a cookie from a sibling subdomain doesn't
+//! help — the MAC key never leaves the server and the session id it
+//! covers comes from the validated session, not the request.
 //!
 //! The `csrf_tokens` table is dropped by migration 010; the
 //! `delete_token` / `cleanup_orphaned` methods remain as no-ops so
 //! existing callers (logout cleanup, background task) compile unchanged.
 
+use chrono::{Duration, Utc};
 use hmac::{Hmac, Mac};
 use rand::{RngCore, rngs::OsRng};
 use sha2::{Digest, Sha256};
@@ -23,14 +42,57 @@ type HmacSha256 = Hmac<Sha256>;
 /// Nonce length in bytes. 128 bits is overkill for uniqueness but the
 /// extra bytes are cheap and make accidental collisions impossible.
 const NONCE_LEN: usize = 16;
+/// Issue time, unix seconds, big-endian.
+const ISSUED_LEN: usize = 8;
+/// Scope byte: `SCOPE_SESSION` or `SCOPE_ACTION`.
+const SCOPE_LEN: usize = 1;
 /// HMAC-SHA256 output length.
 const MAC_LEN: usize = 32;
-const TOKEN_LEN: usize = NONCE_LEN + MAC_LEN;
+const TOKEN_LEN: usize = NONCE_LEN + ISSUED_LEN + SCOPE_LEN + MAC_LEN;
+
+const SCOPE_SESSION: u8 = 0;
+const SCOPE_ACTION: u8 = 1;
+
+/// Tolerated clock skew for tokens stamped slightly in the future
+/// (multi-instance deployments with drifting clocks).
+const MAX_FUTURE_SKEW_SECS: i64 = 60;
+
+/// Default token lifetime when `auth.csrf_token_ttl_hours` isn't set.
+pub const DEFAULT_CSRF_TOKEN_TTL_HOURS: i64 = 12;
+
+/// Why a token was refused. The middleware turns this into a 403 with
+/// a message and a fresh token; callers that only care about pass/fail
+/// use `validate_token`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsrfRejection {
+    /// Not hex, wrong length, or no token at all.
+    Malformed,
+    /// Well-formed and correctly signed, but older than the TTL.
+    Expired,
+    /// Signed for a different session, a different action, or with a
+    /// different key.
+    Mismatch,
+}
+
+impl CsrfRejection {
+    pub fn message(&self) -> &'static str {
+        match self {
+            CsrfRejection::Malformed => "Missing or malformed CSRF token.",
+            CsrfRejection::Expired => {
+                "This form has expired. Reload the page (or retry — a fresh token was issued) and submit again."
+            }
+            CsrfRejection::Mismatch => {
+                "CSRF token does not match this session. Reload the page and try again."
+            }
+        }
+    }
+}
 
 pub struct CsrfService {
     /// HMAC key, derived from the application's session_secret with
     /// domain separation.
     key: [u8; 32],
+    ttl: Duration,
 }
 
 impl CsrfService {
@@ -39,44 +101,105 @@ impl CsrfService {
     /// submitting forms during the rotation get a 403 and retry).
     pub fn new(session_secret: &str) -> Self {
         let mut hasher = Sha256::new();
-        hasher.update(b"coterie-csrf-v1|");
+        hasher.update(b"coterie-csrf-v2|");
         hasher.update(session_secret.as_bytes());
         let key: [u8; 32] = hasher.finalize().into();
-        Self { key }
+        Self {
+            key,
+            ttl: Duration::hours(DEFAULT_CSRF_TOKEN_TTL_HOURS),
+        }
     }
 
-    /// Generate a fresh CSRF token scoped to the given session.
+    /// Override the token lifetime. Non-positive values fall back to
+    /// the default rather than minting tokens that are born expired.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        if ttl > Duration::zero() {
+            self.ttl = ttl;
+        }
+        self
+    }
+
+    /// Generate a fresh session-wide CSRF token.
     /// Async signature kept for API compatibility; no IO happens.
     pub async fn generate_token(&self, session_id: &str) -> Result<String> {
-        let mut nonce = [0u8; NONCE_LEN];
-        OsRng.fill_bytes(&mut nonce);
-        let mac = self.mac(session_id, &nonce);
+        Ok(self.mint(session_id, SCOPE_SESSION, "", Utc::now().timestamp()))
+    }
 
-        let mut out = Vec::with_capacity(TOKEN_LEN);
-        out.extend_from_slice(&nonce);
-        out.extend_from_slice(&mac);
-        Ok(hex::encode(out))
+    /// Generate a token that only validates for requests to `action`
+    /// (the request path, e.g. `/portal/profile/security/totp/enroll/confirm`).
+    pub async fn generate_token_for_action(&self, session_id: &str, action: &str) -> Result<String> {
+        Ok(self.mint(session_id, SCOPE_ACTION, action, Utc::now().timestamp()))
     }
 
-    /// Verify that `token` was generated by this service for the given
-    /// session. Returns false for any malformed or wrong-session token
-    /// without leaking timing info about which part failed.
+    /// Pass/fail check for a session-wide token. Action-bound tokens
+    /// never pass here — use `verify` with the request path for those.
     pub async fn validate_token(&self, session_id: &str, token: &str) -> Result<bool> {
+        Ok(self.verify(session_id, token, "").is_ok())
+    }
+
+    /// Full check: signature, session, action binding (if the token
+    /// carries one), then expiry. The MAC is checked before the
+    /// timestamp is trusted, so a forged "fresh" stamp is a
+    /// `Mismatch`, not a pass.
+    pub fn verify(
+        &self,
+        session_id: &str,
+        token: &str,
+        action: &str,
+    ) -> std::result::Result<(), CsrfRejection> {
         let bytes = match hex::decode(token) {
             Ok(b) if b.len() == TOKEN_LEN => b,
-            _ => return Ok(false),
+            _ => return Err(CsrfRejection::Malformed),
         };
-        let (nonce, provided_mac) = bytes.split_at(NONCE_LEN);
-        let expected_mac = self.mac(session_id, nonce);
-        Ok(expected_mac.ct_eq(provided_mac).into())
+        let (nonce, rest) = bytes.split_at(NONCE_LEN);
+        let (issued, rest) = rest.split_at(ISSUED_LEN);
+        let (scope, provided_mac) = rest.split_at(SCOPE_LEN);
+        let scope = scope[0];
+        let issued_at = i64::from_be_bytes(issued.try_into().expect("fixed-width slice"));
+
+        let bound_action = match scope {
+            SCOPE_SESSION => "",
+            SCOPE_ACTION => action,
+            _ => return Err(CsrfRejection::Malformed),
+        };
+        let expected_mac = self.mac(session_id, issued_at, scope, bound_action, nonce);
+        if !bool::from(expected_mac.ct_eq(provided_mac)) {
+            return Err(CsrfRejection::Mismatch);
+        }
+
+        let now = Utc::now().timestamp();
+        if issued_at > now + MAX_FUTURE_SKEW_SECS {
+            return Err(CsrfRejection::Mismatch);
+        }
+        if now - issued_at > self.ttl.num_seconds() {
+            return Err(CsrfRejection::Expired);
+        }
+        Ok(())
     }
 
-    fn mac(&self, session_id: &str, nonce: &[u8]) -> [u8; MAC_LEN] {
+    fn mint(&self, session_id: &str, scope: u8, action: &str, issued_at: i64) -> String {
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let mac = self.mac(session_id, issued_at, scope, action, &nonce);
+
+        let mut out = Vec::with_capacity(TOKEN_LEN);
+        out.extend_from_slice(&nonce);
+        out.extend_from_slice(&issued_at.to_be_bytes());
+        out.push(scope);
+        out.extend_from_slice(&mac);
+        hex::encode(out)
+    }
+
+    fn mac(&self, session_id: &str, issued_at: i64, scope: u8, action: &str, nonce: &[u8]) -> [u8; MAC_LEN] {
         // HmacSha256::new_from_slice accepts any length; our 32-byte
         // key is well within bounds so unwrap is safe.
         let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC key length valid");
         mac.update(session_id.as_bytes());
         mac.update(b"|");
+        mac.update(&issued_at.to_be_bytes());
+        mac.update(&[scope]);
+        mac.update(action.as_bytes());
+        mac.update(b"|");
         mac.update(nonce);
         mac.finalize().into_bytes().into()
     }
@@ -140,4 +263,38 @@ mod tests {
         assert!(svc.validate_token("s", &a).await.unwrap());
         assert!(svc.validate_token("s", &b).await.unwrap());
     }
+
+    #[test]
+    fn rejects_expired() {
+        let svc = CsrfService::new("x").with_ttl(Duration::hours(1));
+        let stale = svc.mint("s", SCOPE_SESSION, "", Utc::now().timestamp() - 3601);
+        assert_eq!(svc.verify("s", &stale, ""), Err(CsrfRejection::Expired));
+        let fresh = svc.mint("s", SCOPE_SESSION, "", Utc::now().timestamp() - 3500);
+        assert_eq!(svc.verify("s", &fresh, ""), Ok(()));
+    }
+
+    #[test]
+    fn tampered_timestamp_fails_signature() {
+        let svc = CsrfService::new("x").with_ttl(Duration::hours(1));
+        let stale = svc.mint("s", SCOPE_SESSION, "", Utc::now().timestamp() - 7200);
+        let mut bytes = hex::decode(&stale).unwrap();
+        bytes[NONCE_LEN..NONCE_LEN + ISSUED_LEN]
+            .copy_from_slice(&Utc::now().timestamp().to_be_bytes());
+        assert_eq!(
+            svc.verify("s", &hex::encode(bytes), ""),
+            Err(CsrfRejection::Mismatch)
+        );
+    }
+
+    #[tokio::test]
+    async fn action_bound_token_only_validates_for_its_action() {
+        let svc = CsrfService::new("x");
+        let token = svc.generate_token_for_action("s", "/portal/a").await.unwrap();
+        assert_eq!(svc.verify("s", &token, "/portal/a"), Ok(()));
+        assert_eq!(svc.verify("s", &token, "/portal/b"), Err(CsrfRejection::Mismatch));
+        assert!(!svc.validate_token("s", &token).await.unwrap());
+        // Session-wide tokens ignore the action entirely.
+        let wide = svc.generate_token("s").await.unwrap();
+        assert_eq!(svc.verify("s", &wide, "/portal/anything"), Ok(()));
+    }
 }
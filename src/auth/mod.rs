@@ -1,5 +1,6 @@
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
-use argon2::password_hash::{SaltString, rand_core::OsRng};
+use std::sync::Arc;
+
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::{Duration, Utc};
 use cookie::{Cookie, SameSite};
 use sqlx::SqlitePool;
@@ -12,21 +13,23 @@ use crate::{
 
 pub mod csrf;
 pub mod email_tokens;
+pub mod password;
 pub mod pending_login;
 pub mod recovery_codes;
+pub mod redis_session;
 pub mod secret_crypto;
 pub mod session;
 pub mod tokens;
 pub mod totp;
 
-use session::{Session, SessionStore};
-pub use csrf::CsrfService;
+use session::{Session, SessionStore, SqliteSessionStore};
+pub use csrf::{CsrfRejection, CsrfService};
 pub use pending_login::PendingLoginService;
 pub use secret_crypto::SecretCrypto;
 pub use totp::TotpService;
 
 pub struct AuthService {
-    session_store: SessionStore,
+    session_store: Arc<dyn SessionStore>,
 }
 
 impl AuthService {
@@ -35,10 +38,18 @@ impl AuthService {
         // Session security relies on cryptographically random tokens stored server-side,
         // not on signed tokens, so a signing secret isn't needed.
         Self {
-            session_store: SessionStore::new(pool),
+            session_store: Arc::new(SqliteSessionStore::new(pool)),
         }
     }
 
+    /// Keep sessions in `session_store` instead of the default SQLite
+    /// store. `main` builds it with `session::build_session_store`,
+    /// which also applies the idle timeout.
+    pub fn with_session_store(mut self, session_store: Arc<dyn SessionStore>) -> Self {
+        self.session_store = session_store;
+        self
+    }
+
     pub async fn verify_password(password: &str, hash: &str) -> Result<bool> {
         let parsed_hash = PasswordHash::new(hash)
             .map_err(|e| AppError::Internal(format!("Invalid password hash: {}", e)))?;
@@ -61,14 +72,7 @@ impl AuthService {
     /// Hash a password using Argon2. Used in tests and member creation.
     #[allow(dead_code)]
     pub async fn hash_password(password: &str) -> Result<String> {
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-        
-        let password_hash = argon2
-            .hash_password(password.as_bytes(), &salt)
-            .map_err(|e| AppError::Internal(format!("Password hashing failed: {}", e)))?;
-        
-        Ok(password_hash.to_string())
+        password::hash_with_cost(password, password::PasswordCost::Secure)
     }
 
     pub async fn create_session(&self, member_id: Uuid, duration_hours: i64) -> Result<(Session, String)> {
@@ -98,17 +102,28 @@ impl AuthService {
         self.session_store.delete_by_member(member_id).await
     }
 
+    /// The member's live sessions, most recently used first.
+    pub async fn list_sessions(&self, member_id: Uuid) -> Result<Vec<Session>> {
+        self.session_store.list_for_member(member_id).await
+    }
+
+    /// End one of the member's sessions. `false` if it wasn't theirs
+    /// or no longer exists.
+    pub async fn revoke_session(&self, member_id: Uuid, session_id: &str) -> Result<bool> {
+        self.session_store.delete_by_id(member_id, session_id).await
+    }
+
     pub async fn cleanup_expired_sessions(&self) -> Result<u64> {
         self.session_store.cleanup_expired().await
     }
 
-    pub fn create_session_cookie(&self, token: &str, secure: bool) -> Cookie<'static> {
+    pub fn create_session_cookie(&self, token: &str, duration_hours: i64, secure: bool) -> Cookie<'static> {
         Cookie::build(("session", token.to_string()))
             .path("/")
             .same_site(SameSite::Lax)
             .http_only(true)
             .secure(secure)
-            .max_age(cookie::time::Duration::hours(24))
+            .max_age(cookie::time::Duration::hours(duration_hours))
             .build()
     }
 
@@ -144,7 +159,7 @@ pub async fn get_password_hash(pool: &SqlitePool, email: &str) -> Result<Option<
     let result = sqlx::query_scalar::<_, String>(
         "SELECT password_hash FROM members WHERE email = ?"
     )
-    .bind(email)
+    .bind(crate::domain::normalize_email(email))
     .fetch_optional(pool)
     .await?;
     
@@ -0,0 +1,124 @@
+//! Password hashing for account creation, including the bulk path
+//! used by CSV import and the seed binary.
+//!
+//! Argon2 at its default cost takes tens of milliseconds and a chunk
+//! of memory per hash — right for a signup, painful for a 2,000-row
+//! import run one after another on the async executor. The bulk helper
+//! spreads the work over the blocking thread pool instead.
+//!
+//! `PasswordCost::TestData` exists for throwaway fixtures only. It is
+//! never the default, and nothing in the request path selects it.
+
+use argon2::password_hash::{rand_core::OsRng, SaltString};
+use argon2::{Algorithm, Argon2, Params, PasswordHasher, Version};
+
+use crate::error::{AppError, Result};
+
+/// Argon2 work factor for newly created hashes. Verification reads the
+/// parameters back out of the PHC string, so hashes of either cost
+/// verify through the same `AuthService::verify_password`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PasswordCost {
+    /// Argon2id library defaults. Every real account uses this.
+    #[default]
+    Secure,
+    /// Minimal Argon2id parameters (8 MiB, one pass) for seed and test
+    /// data, where thousands of known passwords like `password123` get
+    /// hashed and nobody will ever attack them.
+    TestData,
+}
+
+impl PasswordCost {
+    /// `--password-cost` values accepted by the seed binary.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "secure" => Some(Self::Secure),
+            "test" => Some(Self::TestData),
+            _ => None,
+        }
+    }
+
+    fn hasher(self) -> Argon2<'static> {
+        match self {
+            PasswordCost::Secure => Argon2::default(),
+            PasswordCost::TestData => {
+                let params = Params::new(8 * 1024, 1, 1, None)
+                    .expect("static argon2 params are valid");
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            }
+        }
+    }
+}
+
+/// Hash one password synchronously. CPU-bound — call from a blocking
+/// context, or use [`hash_passwords`] for more than a handful.
+pub fn hash_with_cost(password: &str, cost: PasswordCost) -> Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    cost.hasher()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|h| h.to_string())
+        .map_err(|e| AppError::Internal(format!("Password hashing failed: {}", e)))
+}
+
+/// Hash a batch of passwords across the blocking thread pool, one
+/// chunk per available core. Output order matches input order.
+pub async fn hash_passwords(passwords: Vec<String>, cost: PasswordCost) -> Result<Vec<String>> {
+    if passwords.is_empty() {
+        return Ok(Vec::new());
+    }
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let chunk_size = passwords.len().div_ceil(workers);
+
+    let mut handles = Vec::with_capacity(workers);
+    let mut iter = passwords.into_iter();
+    loop {
+        let chunk: Vec<String> = iter.by_ref().take(chunk_size).collect();
+        if chunk.is_empty() {
+            break;
+        }
+        handles.push(tokio::task::spawn_blocking(move || {
+            chunk
+                .iter()
+                .map(|p| hash_with_cost(p, cost))
+                .collect::<Result<Vec<String>>>()
+        }));
+    }
+
+    let mut hashes = Vec::new();
+    for handle in handles {
+        let chunk = handle
+            .await
+            .map_err(|e| AppError::Internal(format!("Password hashing task failed: {}", e)))??;
+        hashes.extend(chunk);
+    }
+    Ok(hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthService;
+
+    #[tokio::test]
+    async fn test_data_cost_produces_a_verifiable_hash() {
+        let hash = hash_with_cost("password123", PasswordCost::TestData).unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(hash.contains("m=8192,t=1,p=1"), "{}", hash);
+        assert!(AuthService::verify_password("password123", &hash).await.unwrap());
+        assert!(!AuthService::verify_password("password124", &hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn bulk_hashes_keep_input_order() {
+        let passwords: Vec<String> = (0..9).map(|i| format!("pw-{}", i)).collect();
+        let hashes = hash_passwords(passwords.clone(), PasswordCost::TestData)
+            .await
+            .unwrap();
+        assert_eq!(hashes.len(), passwords.len());
+        for (password, hash) in passwords.iter().zip(&hashes) {
+            assert!(AuthService::verify_password(password, hash).await.unwrap());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Redis-backed [`SessionStore`], for running several instances behind
+//! a load balancer without sharing the SQLite file.
+//!
+//! Layout, all under [`KEY_PREFIX`]:
+//!
+//! - `session:<token_hash>` — the session as JSON, set to expire (via
+//!   `PXAT`) at `expires_at` or, with an idle timeout, at
+//!   `last_used_at + idle_timeout`, whichever is sooner. Redis drops
+//!   dead sessions itself.
+//! - `member_sessions:<member_id>` — set of that member's token
+//!   hashes, so "log out everywhere" can find them. Entries for
+//!   sessions Redis has already expired are pruned by
+//!   `cleanup_expired`.
+//!
+//! Needs Redis 6.2 or later (`SET ... PXAT`).
+//!
+//! The billing expiry sweep still deletes rows from the SQLite
+//! `sessions` table directly; with this store that delete finds
+//! nothing, and expired members are turned away by the status check
+//! in `require_auth` instead.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    session::{Session, SessionStore, LAST_USED_WRITE_INTERVAL_SECS},
+    tokens::hash_token,
+};
+use crate::error::{AppError, Result};
+
+/// Namespace for every key this store writes, so Coterie can share a
+/// Redis database with other applications.
+const KEY_PREFIX: &str = "coterie:";
+
+/// Members' session sets scanned per `SCAN` round in `cleanup_expired`.
+const CLEANUP_SCAN_COUNT: usize = 200;
+
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    id: String,
+    member_id: Uuid,
+    expires_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    last_used_at: DateTime<Utc>,
+}
+
+pub struct RedisSessionStore {
+    conn: ConnectionManager,
+    /// Sessions unused for longer than this are treated as expired,
+    /// regardless of `expires_at`. `None` disables the check.
+    idle_timeout: Option<Duration>,
+}
+
+fn redis_error(e: redis::RedisError) -> AppError {
+    AppError::ServiceUnavailable(format!("Session store: {}", e))
+}
+
+fn session_key(token_hash: &str) -> String {
+    format!("{}session:{}", KEY_PREFIX, token_hash)
+}
+
+fn member_key(member_id: Uuid) -> String {
+    format!("{}member_sessions:{}", KEY_PREFIX, member_id)
+}
+
+impl RedisSessionStore {
+    /// Connect to `url` (`redis://host:6379/0`). The connection manager
+    /// reconnects on its own after a dropped connection.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url).map_err(redis_error)?;
+        let conn = ConnectionManager::new(client).await.map_err(redis_error)?;
+        Ok(Self {
+            conn,
+            idle_timeout: None,
+        })
+    }
+
+    /// Expire sessions that go unused for `idle_timeout`. A zero or
+    /// negative duration leaves the idle check off.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = (idle_timeout > Duration::zero()).then_some(idle_timeout);
+        self
+    }
+
+    /// When the stored session should vanish: its absolute expiry, or
+    /// the idle deadline if that comes first.
+    fn deadline(&self, stored: &StoredSession) -> DateTime<Utc> {
+        match self.idle_timeout {
+            Some(idle) => stored.expires_at.min(stored.last_used_at + idle),
+            None => stored.expires_at,
+        }
+    }
+
+    /// Write `stored` under `key`, expiring at its deadline. With
+    /// `only_if_exists`, a session deleted in the meantime stays
+    /// deleted.
+    async fn put(&self, key: &str, stored: &StoredSession, only_if_exists: bool) -> Result<()> {
+        let json = serde_json::to_string(stored)
+            .map_err(|e| AppError::Internal(format!("Session encode: {}", e)))?;
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(key)
+            .arg(json)
+            .arg("PXAT")
+            .arg(self.deadline(stored).timestamp_millis().max(1));
+        if only_if_exists {
+            cmd.arg("XX");
+        }
+        let mut conn = self.conn.clone();
+        cmd.query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(redis_error)
+    }
+}
+
+#[async_trait]
+impl SessionStore for RedisSessionStore {
+    async fn create(
+        &self,
+        member_id: Uuid,
+        token: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Session> {
+        let token_hash = hash_token(token);
+        let now = Utc::now();
+        let stored = StoredSession {
+            id: Uuid::new_v4().to_string(),
+            member_id,
+            expires_at,
+            created_at: now,
+            last_used_at: now,
+        };
+
+        self.put(&session_key(&token_hash), &stored, false).await?;
+        let mut conn = self.conn.clone();
+        conn.sadd::<_, _, ()>(member_key(member_id), &token_hash)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(Session {
+            id: stored.id,
+            member_id,
+            token_hash,
+            expires_at,
+            created_at: now,
+            last_used_at: now,
+        })
+    }
+
+    async fn find_by_token(&self, token: &str) -> Result<Option<Session>> {
+        let token_hash = hash_token(token);
+        let key = session_key(&token_hash);
+        let mut conn = self.conn.clone();
+        let json: Option<String> = conn.get(&key).await.map_err(redis_error)?;
+        let Some(json) = json else {
+            return Ok(None);
+        };
+        let mut stored: StoredSession = serde_json::from_str(&json)
+            .map_err(|e| AppError::Internal(format!("Session decode: {}", e)))?;
+
+        // The key's TTL should already have removed anything past its
+        // deadline; check anyway in case the clocks disagree.
+        let now = Utc::now();
+        if self.deadline(&stored) <= now {
+            return Ok(None);
+        }
+
+        // Record activity for the idle timeout, at most once per
+        // LAST_USED_WRITE_INTERVAL_SECS so every page load isn't a write.
+        if self.idle_timeout.is_some()
+            && stored.last_used_at < now - Duration::seconds(LAST_USED_WRITE_INTERVAL_SECS)
+        {
+            stored.last_used_at = now;
+            self.put(&key, &stored, true).await?;
+        }
+
+        Ok(Some(Session {
+            id: stored.id,
+            member_id: stored.member_id,
+            token_hash,
+            expires_at: stored.expires_at,
+            created_at: stored.created_at,
+            last_used_at: stored.last_used_at,
+        }))
+    }
+
+    async fn delete_by_token(&self, token: &str) -> Result<()> {
+        let token_hash = hash_token(token);
+        let key = session_key(&token_hash);
+        let mut conn = self.conn.clone();
+        let json: Option<String> = conn.get(&key).await.map_err(redis_error)?;
+        conn.del::<_, ()>(&key).await.map_err(redis_error)?;
+        if let Some(stored) = json.and_then(|j| serde_json::from_str::<StoredSession>(&j).ok()) {
+            conn.srem::<_, _, ()>(member_key(stored.member_id), &token_hash)
+                .await
+                .map_err(redis_error)?;
+        }
+        Ok(())
+    }
+
+    async fn delete_by_member(&self, member_id: Uuid) -> Result<()> {
+        let set = member_key(member_id);
+        let mut conn = self.conn.clone();
+        let hashes: Vec<String> = conn.smembers(&set).await.map_err(redis_error)?;
+        let mut keys: Vec<String> = hashes.iter().map(|h| session_key(h)).collect();
+        keys.push(set);
+        conn.del::<_, ()>(keys).await.map_err(redis_error)
+    }
+
+    async fn list_for_member(&self, member_id: Uuid) -> Result<Vec<Session>> {
+        let mut conn = self.conn.clone();
+        let hashes: Vec<String> = conn
+            .smembers(member_key(member_id))
+            .await
+            .map_err(redis_error)?;
+        let now = Utc::now();
+        let mut sessions = Vec::new();
+        for token_hash in hashes {
+            let json: Option<String> = conn
+                .get(session_key(&token_hash))
+                .await
+                .map_err(redis_error)?;
+            let Some(stored) = json.and_then(|j| serde_json::from_str::<StoredSession>(&j).ok())
+            else {
+                continue;
+            };
+            if self.deadline(&stored) <= now {
+                continue;
+            }
+            sessions.push(Session {
+                id: stored.id,
+                member_id: stored.member_id,
+                token_hash,
+                expires_at: stored.expires_at,
+                created_at: stored.created_at,
+                last_used_at: stored.last_used_at,
+            });
+        }
+        sessions
+            .sort_by(|a, b| (b.last_used_at, b.created_at).cmp(&(a.last_used_at, a.created_at)));
+        Ok(sessions)
+    }
+
+    /// Sessions are keyed by token hash, so this walks the member's
+    /// index to find the one with `session_id`.
+    async fn delete_by_id(&self, member_id: Uuid, session_id: &str) -> Result<bool> {
+        let set = member_key(member_id);
+        let mut conn = self.conn.clone();
+        let hashes: Vec<String> = conn.smembers(&set).await.map_err(redis_error)?;
+        for token_hash in hashes {
+            let key = session_key(&token_hash);
+            let json: Option<String> = conn.get(&key).await.map_err(redis_error)?;
+            let matches = json
+                .and_then(|j| serde_json::from_str::<StoredSession>(&j).ok())
+                .is_some_and(|stored| stored.id == session_id);
+            if matches {
+                conn.del::<_, ()>(&key).await.map_err(redis_error)?;
+                conn.srem::<_, _, ()>(&set, &token_hash)
+                    .await
+                    .map_err(redis_error)?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Redis expires the sessions themselves; this prunes the member
+    /// index of hashes whose session is gone and counts them.
+    async fn cleanup_expired(&self) -> Result<u64> {
+        let mut conn = self.conn.clone();
+        let pattern = format!("{}member_sessions:*", KEY_PREFIX);
+        let mut cursor: u64 = 0;
+        let mut removed = 0u64;
+        loop {
+            let (next, sets): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(CLEANUP_SCAN_COUNT)
+                .query_async(&mut conn)
+                .await
+                .map_err(redis_error)?;
+            for set in sets {
+                let hashes: Vec<String> = conn.smembers(&set).await.map_err(redis_error)?;
+                for hash in hashes {
+                    let live: bool = conn.exists(session_key(&hash)).await.map_err(redis_error)?;
+                    if !live {
+                        conn.srem::<_, _, ()>(&set, &hash)
+                            .await
+                            .map_err(redis_error)?;
+                        removed += 1;
+                    }
+                }
+            }
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        Ok(removed)
+    }
+}
@@ -1,9 +1,15 @@
-use chrono::{DateTime, Utc, NaiveDateTime};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc, NaiveDateTime};
 use sqlx::{SqlitePool, FromRow};
 use uuid::Uuid;
 
-use super::tokens::hash_token;
-use crate::error::{AppError, Result};
+use std::sync::Arc;
+
+use super::{redis_session::RedisSessionStore, tokens::hash_token};
+use crate::{
+    config::{AuthConfig, SessionStoreKind},
+    error::{AppError, Result},
+};
 
 #[derive(Debug, Clone)]
 pub struct Session {
@@ -25,16 +31,114 @@ struct SessionRow {
     last_used_at: NaiveDateTime,
 }
 
-pub struct SessionStore {
+impl SessionRow {
+    fn into_session(self) -> Result<Session> {
+        Ok(Session {
+            id: self.id,
+            member_id: Uuid::parse_str(&self.member_id)
+                .map_err(|e| AppError::Internal(e.to_string()))?,
+            token_hash: self.token_hash,
+            expires_at: DateTime::from_naive_utc_and_offset(self.expires_at, Utc),
+            created_at: DateTime::from_naive_utc_and_offset(self.created_at, Utc),
+            last_used_at: DateTime::from_naive_utc_and_offset(self.last_used_at, Utc),
+        })
+    }
+}
+
+/// How stale `last_used_at` may get before a request rewrites it.
+/// Bounds the write load of a busy session to one UPDATE a minute;
+/// the idle timeout is only as precise as this.
+pub(crate) const LAST_USED_WRITE_INTERVAL_SECS: i64 = 60;
+
+/// Where login sessions live. Tokens are never stored, only their
+/// hash; `find_by_token` hashes the presented token and looks that up.
+///
+/// `SqliteSessionStore` is the default. `RedisSessionStore` (see
+/// `auth::redis_session`) lets several instances behind a load
+/// balancer share sessions without sharing the database file.
+/// `auth.session_store` picks one; see [`build_session_store`].
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    async fn create(
+        &self,
+        member_id: Uuid,
+        token: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Session>;
+    /// The live session for `token`: not past `expires_at` and, with an
+    /// idle timeout, used recently enough. Records the use.
+    async fn find_by_token(&self, token: &str) -> Result<Option<Session>>;
+    async fn delete_by_token(&self, token: &str) -> Result<()>;
+    async fn delete_by_member(&self, member_id: Uuid) -> Result<()>;
+    /// The member's live sessions, most recently used first.
+    async fn list_for_member(&self, member_id: Uuid) -> Result<Vec<Session>>;
+    /// Delete one of the member's sessions by its id. Scoped to the
+    /// member so one member can't end another's session; `false` when
+    /// no such session was found.
+    async fn delete_by_id(&self, member_id: Uuid, session_id: &str) -> Result<bool>;
+    /// Drop expired and idle sessions. Returns how many were removed.
+    async fn cleanup_expired(&self) -> Result<u64>;
+}
+
+/// Build the store `auth.session_store` selects, applying
+/// `auth.session_idle_timeout_minutes`. Fails when Redis is selected
+/// without a `redis_url` or can't be reached.
+pub async fn build_session_store(
+    config: &AuthConfig,
     pool: SqlitePool,
+) -> Result<Arc<dyn SessionStore>> {
+    let idle_timeout = Duration::minutes(config.session_idle_timeout_minutes.unwrap_or(0));
+    Ok(match config.session_store {
+        SessionStoreKind::Sqlite => {
+            Arc::new(SqliteSessionStore::new(pool).with_idle_timeout(idle_timeout))
+        }
+        SessionStoreKind::Redis => {
+            let url = config.redis_url.as_deref().ok_or_else(|| {
+                AppError::Internal(
+                    "auth.session_store = \"redis\" needs auth.redis_url".to_string(),
+                )
+            })?;
+            Arc::new(
+                RedisSessionStore::connect(url)
+                    .await?
+                    .with_idle_timeout(idle_timeout),
+            )
+        }
+    })
 }
 
-impl SessionStore {
+pub struct SqliteSessionStore {
+    pool: SqlitePool,
+    /// Sessions unused for longer than this are treated as expired,
+    /// regardless of `expires_at`. `None` disables the check.
+    idle_timeout: Option<Duration>,
+}
+
+impl SqliteSessionStore {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self { pool, idle_timeout: None }
+    }
+
+    /// Expire sessions that go unused for `idle_timeout`. A zero or
+    /// negative duration leaves the idle check off.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = (idle_timeout > Duration::zero()).then_some(idle_timeout);
+        self
+    }
+
+    /// Oldest `last_used_at` still counted as active. With no idle
+    /// timeout this is the epoch, so every unexpired session passes.
+    fn idle_cutoff(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self.idle_timeout {
+            Some(idle) => (now - idle).naive_utc(),
+            None => DateTime::<Utc>::UNIX_EPOCH.naive_utc(),
+        }
     }
+}
 
-    pub async fn create(
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    async fn create(
         &self,
         member_id: Uuid,
         token: &str,
@@ -73,7 +177,7 @@ impl SessionStore {
         })
     }
 
-    pub async fn find_by_token(&self, token: &str) -> Result<Option<Session>> {
+    async fn find_by_token(&self, token: &str) -> Result<Option<Session>> {
         let token_hash = hash_token(token);
         let now = Utc::now();
 
@@ -83,23 +187,32 @@ impl SessionStore {
             r#"
             SELECT id, member_id, token_hash, expires_at, created_at, last_used_at
             FROM sessions
-            WHERE token_hash = ? AND expires_at > ?
+            WHERE token_hash = ? AND expires_at > ? AND last_used_at > ?
             "#
         )
         .bind(&token_hash)
         .bind(now_naive)
+        .bind(self.idle_cutoff(now))
         .fetch_optional(&self.pool)
         .await?;
 
         if let Some(row) = row {
-            // Update last_used_at
-            sqlx::query(
-                "UPDATE sessions SET last_used_at = ? WHERE id = ?"
-            )
-            .bind(now_naive)
-            .bind(&row.id)
-            .execute(&self.pool)
-            .await?;
+            // Record activity for the idle timeout, at most once per
+            // LAST_USED_WRITE_INTERVAL_SECS so every page load isn't a write.
+            let write_before = (now - Duration::seconds(LAST_USED_WRITE_INTERVAL_SECS)).naive_utc();
+            let last_used_at = if row.last_used_at < write_before {
+                sqlx::query(
+                    "UPDATE sessions SET last_used_at = ? WHERE id = ? AND last_used_at < ?"
+                )
+                .bind(now_naive)
+                .bind(&row.id)
+                .bind(write_before)
+                .execute(&self.pool)
+                .await?;
+                now
+            } else {
+                DateTime::from_naive_utc_and_offset(row.last_used_at, Utc)
+            };
 
             Ok(Some(Session {
                 id: row.id,
@@ -108,14 +221,14 @@ impl SessionStore {
                 token_hash: row.token_hash,
                 expires_at: DateTime::from_naive_utc_and_offset(row.expires_at, Utc),
                 created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
-                last_used_at: now,
+                last_used_at,
             }))
         } else {
             Ok(None)
         }
     }
 
-    pub async fn delete_by_token(&self, token: &str) -> Result<()> {
+    async fn delete_by_token(&self, token: &str) -> Result<()> {
         let token_hash = hash_token(token);
         
         sqlx::query("DELETE FROM sessions WHERE token_hash = ?")
@@ -126,7 +239,7 @@ impl SessionStore {
         Ok(())
     }
 
-    pub async fn delete_by_member(&self, member_id: Uuid) -> Result<()> {
+    async fn delete_by_member(&self, member_id: Uuid) -> Result<()> {
         let member_id_str = member_id.to_string();
         sqlx::query("DELETE FROM sessions WHERE member_id = ?")
             .bind(&member_id_str)
@@ -136,12 +249,42 @@ impl SessionStore {
         Ok(())
     }
 
-    pub async fn cleanup_expired(&self) -> Result<u64> {
+    async fn list_for_member(&self, member_id: Uuid) -> Result<Vec<Session>> {
+        let now = Utc::now();
+        let rows = sqlx::query_as::<_, SessionRow>(
+            r#"
+            SELECT id, member_id, token_hash, expires_at, created_at, last_used_at
+            FROM sessions
+            WHERE member_id = ? AND expires_at > ? AND last_used_at > ?
+            ORDER BY last_used_at DESC, created_at DESC
+            "#
+        )
+        .bind(member_id.to_string())
+        .bind(now.naive_utc())
+        .bind(self.idle_cutoff(now))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+
+    async fn delete_by_id(&self, member_id: Uuid, session_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM sessions WHERE id = ? AND member_id = ?")
+            .bind(session_id)
+            .bind(member_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn cleanup_expired(&self) -> Result<u64> {
         let now = Utc::now();
         
         let now_naive = now.naive_utc();
-        let result = sqlx::query("DELETE FROM sessions WHERE expires_at <= ?")
+        let result = sqlx::query("DELETE FROM sessions WHERE expires_at <= ? OR last_used_at <= ?")
             .bind(now_naive)
+            .bind(self.idle_cutoff(now))
             .execute(&self.pool)
             .await?;
 
@@ -38,6 +38,9 @@ const ALGO: Algorithm = Algorithm::SHA1;
 const DIGITS: usize = 6;
 const STEP: u64 = 30;
 
+/// Issuer used when neither `org.name` nor `auth.totp_issuer` is set.
+const DEFAULT_ISSUER: &str = "Coterie";
+
 /// One step on each side of "now" — accommodates ~30s of clock skew.
 /// Bigger skew means less defense against replay; smaller means real
 /// users with slightly-wrong phone clocks get rejected.
@@ -218,6 +221,18 @@ impl TotpService {
     }
 }
 
+/// The issuer authenticator apps show: the `org.name` setting, else
+/// the configured `auth.totp_issuer`, else "Coterie". Blank and
+/// whitespace-only values count as unset.
+pub fn resolve_issuer(org_name: Option<&str>, configured: &str) -> String {
+    org_name
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .or_else(|| Some(configured.trim()).filter(|s| !s.is_empty()))
+        .unwrap_or(DEFAULT_ISSUER)
+        .to_string()
+}
+
 // --------------------------------------------------------------------
 // Helpers
 // --------------------------------------------------------------------
@@ -317,6 +332,17 @@ mod tests {
         assert!(!check_code(&totp, "        ")); // empty after trim
     }
 
+    #[test]
+    fn issuer_prefers_org_name_then_config_then_default() {
+        assert_eq!(resolve_issuer(Some("Hackerspace"), "Configured"), "Hackerspace");
+        assert_eq!(resolve_issuer(Some(" Hackerspace "), ""), "Hackerspace");
+        assert_eq!(resolve_issuer(None, "Configured"), "Configured");
+        assert_eq!(resolve_issuer(Some(""), " Configured "), "Configured");
+        assert_eq!(resolve_issuer(Some("   "), "Configured"), "Configured");
+        assert_eq!(resolve_issuer(None, ""), "Coterie");
+        assert_eq!(resolve_issuer(Some(" "), "\t "), "Coterie");
+    }
+
     #[test]
     fn check_code_accepts_current_token() {
         let (totp, _) = fresh_totp_with_secret();
@@ -1,11 +1,12 @@
 use clap::Parser;
 use config::{Config, File};
 use coterie::{
+    auth::password::PasswordCost,
     domain::{
         CreateMemberRequest, MemberStatus, UpdateMemberRequest,
         BasicTypeKind, CreateBasicTypeRequest, CreateMembershipTypeRequest,
         MembershipTypeConfig as DbMembershipTypeConfig,
-        Event, EventType, EventVisibility,
+        Event, EventStatus, EventType, EventVisibility,
         Announcement, AnnouncementType,
         Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus, StripeRef,
     },
@@ -39,6 +40,16 @@ struct Args {
     /// Number of random members to generate (in addition to test users)
     #[arg(short, long, default_value = "100")]
     member_count: usize,
+
+    /// Argon2 cost for the generated members' passwords: "secure"
+    /// (default) or "test" for a much faster, weaker hash. Use "test"
+    /// only for throwaway databases.
+    #[arg(long, default_value = "secure", value_parser = parse_password_cost)]
+    password_cost: PasswordCost,
+}
+
+fn parse_password_cost(s: &str) -> Result<PasswordCost, String> {
+    PasswordCost::parse(s).ok_or_else(|| format!("expected \"secure\" or \"test\", got \"{}\"", s))
 }
 
 // ============================================================================
@@ -88,7 +99,7 @@ struct MembershipTypeConfig {
     name: String,
     slug: String,
     color: String,
-    fee_cents: i32,
+    fee_cents: i64,
     billing_frequency: String,
 }
 
@@ -205,6 +216,7 @@ fn make_payment(
         description: description.to_string(),
         kind: PaymentKind::Membership,
         paid_at,
+        refunded_at: None,
         created_at: created,
         updated_at: created,
     }
@@ -235,12 +247,17 @@ fn make_event(
         location: location.map(String::from),
         max_attendees: Some(30),
         rsvp_required: true,
+        allow_guest_rsvp: false,
+        registration_group: None,
+        rsvp_deadline: None,
         image_url: image_url.map(String::from),
         created_by,
         created_at: Utc::now() - Duration::days(days_offset.abs() + 7),
         updated_at: Utc::now() - Duration::days(days_offset.abs() + 7),
         series_id: None,
         occurrence_index: None,
+        status: EventStatus::Published,
+        review_feedback: None,
     }
 }
 
@@ -380,7 +397,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Seed event types from config
     for et in &config.event_types {
-        basic_type_repo.create(BasicTypeKind::Event, CreateBasicTypeRequest {
+        basic_type_repo.create(BasicTypeKind::Event, None, CreateBasicTypeRequest {
             name: et.name.clone(),
             slug: Some(et.slug.clone()),
             description: None,
@@ -392,7 +409,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Seed announcement types from config
     for at in &config.announcement_types {
-        basic_type_repo.create(BasicTypeKind::Announcement, CreateBasicTypeRequest {
+        basic_type_repo.create(BasicTypeKind::Announcement, None, CreateBasicTypeRequest {
             name: at.name.clone(),
             slug: Some(at.slug.clone()),
             description: None,
@@ -404,7 +421,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Seed membership types from config
     for mt in &config.membership_types {
-        membership_type_repo.create(CreateMembershipTypeRequest {
+        membership_type_repo.create(None, CreateMembershipTypeRequest {
             name: mt.name.clone(),
             slug: Some(mt.slug.clone()),
             description: None,
@@ -412,6 +429,7 @@ async fn main() -> anyhow::Result<()> {
             icon: None,
             fee_cents: mt.fee_cents,
             billing_period: mt.billing_frequency.clone(),
+            max_members: None,
         }).await?;
     }
     println!("    Created {} membership types", config.membership_types.len());
@@ -485,7 +503,7 @@ async fn main() -> anyhow::Result<()> {
         let joined = Utc::now() - Duration::days(user_config.months_active * 30);
 
         sqlx::query("UPDATE members SET status = ?, dues_paid_until = ?, joined_at = ?, bypass_dues = ? WHERE id = ?")
-            .bind(format!("{:?}", status))
+            .bind(status.as_str())
             .bind(dues_until)
             .bind(joined)
             .bind(user_config.bypass_dues)
@@ -507,11 +525,11 @@ async fn main() -> anyhow::Result<()> {
 
     // Generate random members
     let random_count = args.member_count.saturating_sub(1 + config.test_users.len());
-    let mut generated = 0;
+    let mut pending = Vec::new();
     let mut attempts = 0;
     const MAX_ATTEMPTS: usize = 1000;
 
-    while generated < random_count && attempts < MAX_ATTEMPTS {
+    while pending.len() < random_count && attempts < MAX_ATTEMPTS {
         attempts += 1;
 
         let first_name: String = FirstName().fake_with_rng(&mut rng);
@@ -538,14 +556,25 @@ async fn main() -> anyhow::Result<()> {
 
         let gen_config = generate_member_config(&mut rng, &active_types);
 
-        let member = member_repo.create(CreateMemberRequest {
+        pending.push((CreateMemberRequest {
             email: email.clone(),
             username: username.clone(),
             full_name,
             password: "password123".to_string(),
             membership_type_id: Some(gen_config.membership_type_id),
             ..Default::default()
-        }).await?;
+        }, gen_config));
+        used_usernames.insert(username);
+        used_emails.insert(email);
+    }
+
+    // One batch call so the password hashing runs in parallel.
+    let (requests, gen_configs): (Vec<_>, Vec<_>) = pending.into_iter().unzip();
+    let created = member_repo.create_batch(requests, args.password_cost).await?;
+    let mut generated = 0;
+
+    for (member, gen_config) in created.into_iter().zip(gen_configs) {
+        let member = member?;
 
         let months_ago = gen_config.months_active;
         let joined = Utc::now() - Duration::days(months_ago * 30 + rng.gen_range(0..30));
@@ -565,7 +594,7 @@ async fn main() -> anyhow::Result<()> {
         };
 
         sqlx::query("UPDATE members SET status = ?, dues_paid_until = ?, joined_at = ?, bypass_dues = ?, notes = ? WHERE id = ?")
-            .bind(format!("{:?}", gen_config.status))
+            .bind(gen_config.status.as_str())
             .bind(dues_until)
             .bind(joined)
             .bind(gen_config.bypass_dues)
@@ -575,8 +604,6 @@ async fn main() -> anyhow::Result<()> {
             .await?;
 
         all_members.push((member.id, gen_config));
-        used_usernames.insert(username);
-        used_emails.insert(email);
         generated += 1;
     }
 
@@ -680,6 +707,10 @@ async fn main() -> anyhow::Result<()> {
             image_url: ann_config.image_url.clone(),
             published_at: Some(Utc::now() - Duration::days(ann_config.days_ago)),
             scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
             created_by: admin.id,
             created_at: Utc::now() - Duration::days(ann_config.days_ago),
             updated_at: Utc::now() - Duration::days(ann_config.days_ago),
@@ -701,6 +732,10 @@ async fn main() -> anyhow::Result<()> {
             image_url: None,
             published_at: Some(Utc::now() - Duration::days(1)),
             scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
             created_by: admin.id,
             created_at: Utc::now() - Duration::days(1),
             updated_at: Utc::now() - Duration::days(1),
@@ -719,7 +754,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Get default dues amount from first membership type in config
     let default_dues = config.membership_types.first()
-        .map(|mt| mt.fee_cents as i64)
+        .map(|mt| mt.fee_cents)
         .unwrap_or(5000);
 
     for (member_id, gen_config) in &all_members {
@@ -14,6 +14,10 @@ pub struct Settings {
     pub seed: SeedConfig,
     #[serde(default)]
     pub bot_challenge: BotChallengeConfig,
+    #[serde(default)]
+    pub images: ImageConfig,
+    #[serde(default)]
+    pub security_headers: SecurityHeadersConfig,
 }
 
 // Email configuration lives in the database (app_settings table) so
@@ -32,6 +36,9 @@ pub struct ServerConfig {
     pub data_dir: String,
     /// Directory for uploaded files. Defaults to "{data_dir}/uploads"
     pub uploads_dir: Option<String>,
+    /// Directory for backups taken from the admin portal and the
+    /// scheduled backup job. Defaults to "{data_dir}/backups/app"
+    pub backups_dir: Option<String>,
     /// Force the Secure flag on session cookies. When None, inferred from
     /// base_url: https:// → true, anything else → false. Override to `true`
     /// when Coterie sits behind a TLS-terminating reverse proxy that
@@ -49,6 +56,12 @@ pub struct ServerConfig {
     /// Set to false explicitly if this server faces untrusted clients
     /// directly, to prevent IP spoofing.
     pub trust_forwarded_for: Option<bool>,
+    /// Most requests served at once; past this, new requests get an
+    /// immediate 503 with `Retry-After` instead of queueing on the
+    /// SQLite pool. Health probes and the Stripe webhook are exempt.
+    /// 0 disables the limit.
+    #[serde(default = "default_max_in_flight_requests")]
+    pub max_in_flight_requests: usize,
 }
 
 impl ServerConfig {
@@ -59,6 +72,13 @@ impl ServerConfig {
         })
     }
 
+    /// Get the in-app backups directory, defaulting to {data_dir}/backups/app
+    pub fn backups_path(&self) -> String {
+        self.backups_dir.clone().unwrap_or_else(|| {
+            format!("{}/backups/app", self.data_dir)
+        })
+    }
+
     pub fn cookies_are_secure(&self) -> bool {
         self.secure_cookies
             .unwrap_or_else(|| self.base_url.starts_with("https://"))
@@ -73,6 +93,10 @@ impl ServerConfig {
     }
 }
 
+fn default_max_in_flight_requests() -> usize {
+    256
+}
+
 fn default_data_dir() -> String {
     // Check locations in order of preference:
     // 1. /var/lib/coterie - standard Linux service location (if it exists)
@@ -100,16 +124,96 @@ pub struct DatabaseConfig {
 #[derive(Debug, Deserialize, Clone)]
 pub struct AuthConfig {
     pub session_secret: String,
+    /// Absolute lifetime of a login session. "Remember me" logins last
+    /// 30 days instead.
     pub session_duration_hours: i64,
+    /// Log a session out after this many minutes without a request,
+    /// even if it hasn't reached its absolute expiry. Unset or zero
+    /// means sessions only end at `session_duration_hours`.
+    #[serde(default)]
+    pub session_idle_timeout_minutes: Option<i64>,
+    /// Issuer name shown in authenticator apps when the org hasn't set
+    /// `org.name`.
     pub totp_issuer: String,
+    /// Lifetime of a CSRF token from the moment a page renders it.
+    /// Independent of the session: a long-lived session still gets its
+    /// form tokens rotated.
+    #[serde(default = "default_csrf_token_ttl_hours")]
+    pub csrf_token_ttl_hours: i64,
+    /// Optional MaxMind/DB-IP City database (`.mmdb`) used to show an
+    /// approximate "City, Country" in member login history. Unset means
+    /// no lookup; history still records IP and browser.
+    #[serde(default)]
+    pub geoip_db_path: Option<String>,
+    /// Where login sessions are kept. `sqlite` (default) uses the
+    /// main database; `redis` shares them across instances behind a
+    /// load balancer and needs `redis_url`.
+    #[serde(default)]
+    pub session_store: SessionStoreKind,
+    /// Redis connection URL for `session_store = "redis"`, e.g.
+    /// `redis://127.0.0.1:6379/0`. Ignored otherwise.
+    #[serde(default)]
+    pub redis_url: Option<String>,
+    /// Lockout for repeated failed logins against one account from one
+    /// IP. See `LoginRateLimitConfig`.
+    #[serde(default)]
+    pub login_rate_limit: LoginRateLimitConfig,
+}
+
+/// Failed-login lockout, keyed on the account tried and the client IP.
+///
+/// After `max_failures` wrong passwords within `window_minutes`, that
+/// account/IP pair gets a 429 without the password being checked until
+/// `cooldown_minutes` have passed. A successful login clears the count.
+/// Unknown accounts are counted the same way, so the lockout says
+/// nothing about whether an account exists.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LoginRateLimitConfig {
+    /// Failures allowed within the window. Default 5.
+    #[serde(default = "default_login_max_failures")]
+    pub max_failures: usize,
+    /// Sliding window the failures are counted over. Default 15.
+    #[serde(default = "default_login_window_minutes")]
+    pub window_minutes: u64,
+    /// How long the pair stays locked out. Default 15.
+    #[serde(default = "default_login_cooldown_minutes")]
+    pub cooldown_minutes: u64,
+}
+
+impl Default for LoginRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: default_login_max_failures(),
+            window_minutes: default_login_window_minutes(),
+            cooldown_minutes: default_login_cooldown_minutes(),
+        }
+    }
+}
+
+fn default_login_max_failures() -> usize { 5 }
+fn default_login_window_minutes() -> u64 { 15 }
+fn default_login_cooldown_minutes() -> u64 { 15 }
+
+/// Backend for login sessions. See `auth::session::SessionStore`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionStoreKind {
+    #[default]
+    Sqlite,
+    Redis,
+}
+
+fn default_csrf_token_ttl_hours() -> i64 {
+    crate::auth::csrf::DEFAULT_CSRF_TOKEN_TTL_HOURS
 }
 
 /// Bot-challenge config (Cloudflare Turnstile by default).
 ///
-/// Public POST endpoints (`/public/signup`, `/public/donate`) are CSRF-
-/// exempt by design (cross-origin posts from the marketing site), and
-/// per-IP rate limiting alone doesn't stop distributed bots from
-/// carding stolen cards or mass-creating fake signups. When `provider`
+/// Public POST endpoints (`/public/signup`, `/public/donate`,
+/// `/public/events/rsvp`) are CSRF-exempt by design (cross-origin
+/// posts from the marketing site), and per-IP rate limiting alone
+/// doesn't stop distributed bots from carding stolen cards or
+/// mass-creating fake signups. When `provider`
 /// is anything other than `disabled`, those handlers verify a token
 /// from the configured provider before doing any DB write or Stripe
 /// call.
@@ -158,19 +262,179 @@ fn default_bot_challenge_url() -> String {
 }
 fn default_bot_challenge_timeout_ms() -> u64 { 3000 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+/// Response security headers: the Content-Security-Policy plus HSTS,
+/// `X-Content-Type-Options`, `Referrer-Policy` and frame protection.
+///
+/// The defaults are enforcing. Set `csp_report_only` while rolling a
+/// tighter policy out: browsers then report violations (to
+/// `csp_report_uri`, if set) instead of blocking them.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SecurityHeadersConfig {
+    /// Send the policy as `Content-Security-Policy-Report-Only`.
+    /// Default false.
+    #[serde(default)]
+    pub csp_report_only: bool,
+    /// Where browsers POST violation reports (`report-uri`). Unset by
+    /// default.
+    #[serde(default)]
+    pub csp_report_uri: Option<String>,
+    /// Extra origins allowed to serve scripts and stylesheets,
+    /// space- or comma-separated. Default `"https://unpkg.com"`; set to
+    /// `""` when every asset is self-hosted under /static.
+    #[serde(default = "default_csp_cdn_sources")]
+    pub cdn_sources: String,
+    /// `frame-ancestors` value. Default `'none'`; `'self'` allows the
+    /// app to frame itself.
+    #[serde(default = "default_frame_ancestors")]
+    pub frame_ancestors: String,
+    /// `frame-ancestors` value for the `/public/embed/` widgets, which
+    /// exist to be framed by the org's own website. Default `*`; list
+    /// the site's origin (e.g. `https://example.org`) to lock it down.
+    #[serde(default = "default_embed_frame_ancestors")]
+    pub embed_frame_ancestors: String,
+    /// `Referrer-Policy` value. Default `strict-origin-when-cross-origin`.
+    #[serde(default = "default_referrer_policy")]
+    pub referrer_policy: String,
+    /// HSTS `max-age` in seconds, sent only when cookies are secure
+    /// (i.e. on TLS deployments). 0 turns HSTS off. Default one year.
+    #[serde(default = "default_hsts_max_age_secs")]
+    pub hsts_max_age_secs: u64,
+}
+
+impl SecurityHeadersConfig {
+    /// `cdn_sources` as a list of origins.
+    pub fn cdn_source_list(&self) -> Vec<&str> {
+        self.cdn_sources
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
+
+impl Default for SecurityHeadersConfig {
+    fn default() -> Self {
+        Self {
+            csp_report_only: false,
+            csp_report_uri: None,
+            cdn_sources: default_csp_cdn_sources(),
+            frame_ancestors: default_frame_ancestors(),
+            embed_frame_ancestors: default_embed_frame_ancestors(),
+            referrer_policy: default_referrer_policy(),
+            hsts_max_age_secs: default_hsts_max_age_secs(),
+        }
+    }
+}
+
+fn default_csp_cdn_sources() -> String { "https://unpkg.com".to_string() }
+fn default_frame_ancestors() -> String { "'none'".to_string() }
+fn default_embed_frame_ancestors() -> String { "*".to_string() }
+fn default_referrer_policy() -> String { "strict-origin-when-cross-origin".to_string() }
+fn default_hsts_max_age_secs() -> u64 { 31_536_000 }
+
+/// Processing applied to uploaded event / announcement images.
+///
+/// Phone photos arrive at 4000px and several MB; the event page shows
+/// them at a few hundred. On upload the image is decoded, scaled down
+/// to fit inside `max_width` × `max_height` (aspect ratio kept, never
+/// scaled up), and re-encoded as `format`. GIFs are stored as-is so
+/// animations survive.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ImageConfig {
+    /// Longest allowed edge, horizontally. Default 1600.
+    #[serde(default = "default_image_max_dimension")]
+    pub max_width: u32,
+    /// Longest allowed edge, vertically. Default 1600.
+    #[serde(default = "default_image_max_dimension")]
+    pub max_height: u32,
+    /// Stored format: `"jpeg"` (default, lossy, smallest for photos)
+    /// or `"webp"` (lossless, keeps transparency).
+    #[serde(default)]
+    pub format: ImageOutputFormat,
+    /// JPEG quality, 1–100. Ignored for WebP. Default 85.
+    #[serde(default = "default_image_jpeg_quality")]
+    pub jpeg_quality: u8,
+    /// Also keep the untouched upload next to the processed file
+    /// (`<id>.orig.<ext>`). Never served; there for re-processing later.
+    #[serde(default)]
+    pub keep_original: bool,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            max_width: default_image_max_dimension(),
+            max_height: default_image_max_dimension(),
+            format: ImageOutputFormat::default(),
+            jpeg_quality: default_image_jpeg_quality(),
+            keep_original: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+    #[default]
+    Jpeg,
+    Webp,
+}
+
+fn default_image_max_dimension() -> u32 { 1600 }
+fn default_image_jpeg_quality() -> u8 { 85 }
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct StripeConfig {
     pub publishable_key: Option<String>,
     pub secret_key: Option<String>,
     pub webhook_secret: Option<String>,
     #[serde(default)]
     pub enabled: bool,
+    /// Longest one Stripe API attempt may take before it's abandoned
+    /// (and retried). Default 30.
+    #[serde(default = "default_stripe_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Retries after a timeout, connection error, 429 or 5xx. Every
+    /// call is a GET/DELETE or carries an idempotency key, so a retry
+    /// can't double-charge. Default 2; 0 turns retrying off.
+    #[serde(default = "default_stripe_max_retries")]
+    pub max_retries: u32,
+}
+
+impl Default for StripeConfig {
+    fn default() -> Self {
+        Self {
+            publishable_key: None,
+            secret_key: None,
+            webhook_secret: None,
+            enabled: false,
+            request_timeout_secs: default_stripe_request_timeout_secs(),
+            max_retries: default_stripe_max_retries(),
+        }
+    }
 }
 
+fn default_stripe_request_timeout_secs() -> u64 { 30 }
+fn default_stripe_max_retries() -> u32 { 2 }
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct IntegrationConfig {
     pub discord: Option<DiscordConfig>,
     pub unifi: Option<UnifiConfig>,
+    /// What a failing health check on an enabled integration means at
+    /// boot. See `service::readiness_service`.
+    #[serde(default)]
+    pub startup_policy: IntegrationStartupPolicy,
+}
+
+/// `warn` logs the failure and lets the instance go ready anyway;
+/// `fail` holds `/ready` at 503 until every enabled integration passes.
+/// Liveness (`/health`) is unaffected either way.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrationStartupPolicy {
+    #[default]
+    Warn,
+    Fail,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -192,7 +456,12 @@ pub struct UnifiConfig {
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
 pub struct SeedConfig {
+    /// When set, the server creates `admin` and `membership_types` at
+    /// startup if the database has no members yet. Off by default;
+    /// the `seed` binary ignores it.
+    pub on_first_run: bool,
     pub admin: AdminSeedConfig,
     pub test_users: Vec<TestUserConfig>,
     pub membership_types: Vec<MembershipTypeSeedConfig>,
@@ -226,6 +495,7 @@ pub struct EmailConfig {
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
 pub struct AdminSeedConfig {
     pub email: String,
     pub username: String,
@@ -288,15 +558,22 @@ impl Settings {
     }
 }
 
+impl Default for AdminSeedConfig {
+    fn default() -> Self {
+        Self {
+            email: "admin@coterie.local".to_string(),
+            username: "admin".to_string(),
+            full_name: "Admin User".to_string(),
+            password: "admin123".to_string(),
+        }
+    }
+}
+
 impl Default for SeedConfig {
     fn default() -> Self {
         Self {
-            admin: AdminSeedConfig {
-                email: "admin@coterie.local".to_string(),
-                username: "admin".to_string(),
-                full_name: "Admin User".to_string(),
-                password: "admin123".to_string(),
-            },
+            on_first_run: false,
+            admin: AdminSeedConfig::default(),
             test_users: vec![
                 TestUserConfig {
                     email: "alice@example.com".to_string(),
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::ParseEnumError;
+
+/// Events routed to admins individually (as opposed to the org-wide
+/// `AdminAlert` channels). `notifications.admin_events` lists which
+/// of these are switched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminNotificationKind {
+    NewSignup,
+    PaymentFailed,
+    ProposalSubmitted,
+    ContactSubmitted,
+}
+
+impl AdminNotificationKind {
+    pub const ALL: [AdminNotificationKind; 4] = [
+        AdminNotificationKind::NewSignup,
+        AdminNotificationKind::PaymentFailed,
+        AdminNotificationKind::ProposalSubmitted,
+        AdminNotificationKind::ContactSubmitted,
+    ];
+
+    /// Canonical string: the `admin_notifications.kind` column value
+    /// and what the `notifications.admin_events` setting lists.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminNotificationKind::NewSignup => "new_signup",
+            AdminNotificationKind::PaymentFailed => "payment_failed",
+            AdminNotificationKind::ProposalSubmitted => "proposal_submitted",
+            AdminNotificationKind::ContactSubmitted => "contact_submitted",
+        }
+    }
+
+    /// Heading used for this kind's section of a digest.
+    pub fn label(&self) -> &'static str {
+        match self {
+            AdminNotificationKind::NewSignup => "New signups",
+            AdminNotificationKind::PaymentFailed => "Failed payments",
+            AdminNotificationKind::ProposalSubmitted => "Event proposals",
+            AdminNotificationKind::ContactSubmitted => "Contact form messages",
+        }
+    }
+}
+
+impl fmt::Display for AdminNotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AdminNotificationKind {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "new_signup" => Ok(AdminNotificationKind::NewSignup),
+            "payment_failed" => Ok(AdminNotificationKind::PaymentFailed),
+            "proposal_submitted" => Ok(AdminNotificationKind::ProposalSubmitted),
+            "contact_submitted" => Ok(AdminNotificationKind::ContactSubmitted),
+            _ => Err(ParseEnumError::new("admin notification kind", s)),
+        }
+    }
+}
+
+/// Where an admin wants their notifications delivered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminNotificationChannel {
+    #[default]
+    Email,
+    /// A direct message from the org's Discord bot. Falls back to
+    /// email when Discord is off or the admin has no `discord_id`.
+    Discord,
+    None,
+}
+
+impl AdminNotificationChannel {
+    pub const ALL: [AdminNotificationChannel; 3] = [
+        AdminNotificationChannel::Email,
+        AdminNotificationChannel::Discord,
+        AdminNotificationChannel::None,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminNotificationChannel::Email => "email",
+            AdminNotificationChannel::Discord => "discord",
+            AdminNotificationChannel::None => "none",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AdminNotificationChannel::Email => "Email",
+            AdminNotificationChannel::Discord => "Discord direct message",
+            AdminNotificationChannel::None => "Don't notify me",
+        }
+    }
+}
+
+impl fmt::Display for AdminNotificationChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AdminNotificationChannel {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(AdminNotificationChannel::Email),
+            "discord" => Ok(AdminNotificationChannel::Discord),
+            "none" => Ok(AdminNotificationChannel::None),
+            _ => Err(ParseEnumError::new("admin notification channel", s)),
+        }
+    }
+}
@@ -1,8 +1,13 @@
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::ParseEnumError;
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Announcement {
     pub id: Uuid,
@@ -15,11 +20,33 @@ pub struct Announcement {
     pub image_url: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
     pub scheduled_publish_at: Option<DateTime<Utc>>,
+    /// After this the announcement is hidden from public and member
+    /// listings (admins still see it). `None` means it never expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether members can comment on this announcement. Off by default.
+    #[serde(default)]
+    pub comments_enabled: bool,
+    /// Whether publishing broadcasts `AnnouncementPublished`. `None`
+    /// follows the announcement type's default.
+    #[serde(default)]
+    pub notify_members: Option<bool>,
+    /// When members were last notified. Set by the publish paths, not
+    /// by edits; a republish stays quiet while this is set unless the
+    /// admin asks to notify again.
+    #[serde(default)]
+    pub notified_at: Option<DateTime<Utc>>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Announcement {
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
 /// Legacy announcement type enum - DEPRECATED
 ///
 /// This enum is being phased out in favor of database-driven announcement types.
@@ -31,7 +58,7 @@ pub struct Announcement {
 /// let type_config = announcement_type_service.get(announcement.announcement_type_id).await?;
 /// let type_name = type_config.name;
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "TEXT")]
 pub enum AnnouncementType {
     News,
@@ -39,4 +66,39 @@ pub enum AnnouncementType {
     Meeting,
     CTFResult,
     General,
-}
\ No newline at end of file
+}
+
+impl AnnouncementType {
+    /// Canonical string: the `announcement_type` column value and what
+    /// filters and forms send.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementType::News => "News",
+            AnnouncementType::Achievement => "Achievement",
+            AnnouncementType::Meeting => "Meeting",
+            AnnouncementType::CTFResult => "CTFResult",
+            AnnouncementType::General => "General",
+        }
+    }
+}
+
+impl fmt::Display for AnnouncementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AnnouncementType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "News" => Ok(AnnouncementType::News),
+            "Achievement" => Ok(AnnouncementType::Achievement),
+            "Meeting" => Ok(AnnouncementType::Meeting),
+            "CTFResult" => Ok(AnnouncementType::CTFResult),
+            "General" => Ok(AnnouncementType::General),
+            _ => Err(ParseEnumError::new("announcement type", s)),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ParseEnumError;
+
+/// What a shout-out celebrates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CelebrationKind {
+    Birthday,
+    /// Another year since `members.joined_at`.
+    Anniversary,
+}
+
+impl CelebrationKind {
+    /// The `member_celebrations.kind` column value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CelebrationKind::Birthday => "birthday",
+            CelebrationKind::Anniversary => "anniversary",
+        }
+    }
+}
+
+impl fmt::Display for CelebrationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CelebrationKind {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "birthday" => Ok(CelebrationKind::Birthday),
+            "anniversary" => Ok(CelebrationKind::Anniversary),
+            _ => Err(ParseEnumError::new("celebration kind", s)),
+        }
+    }
+}
+
+/// A birthday without the year. Stored as `MM-DD` on `member_profiles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonthDay {
+    pub month: u32,
+    pub day: u32,
+}
+
+impl MonthDay {
+    /// `None` unless `month`/`day` name a real date in some year
+    /// (so 02-29 is fine, 04-31 isn't).
+    pub fn new(month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(2000, month, day).map(|_| Self { month, day })
+    }
+
+    pub fn of(date: NaiveDate) -> Self {
+        Self {
+            month: date.month(),
+            day: date.day(),
+        }
+    }
+
+    /// Whether this is celebrated on `date`. Feb 29 falls on Feb 28
+    /// in non-leap years.
+    pub fn falls_on(&self, date: NaiveDate) -> bool {
+        if (self.month, self.day) == (date.month(), date.day()) {
+            return true;
+        }
+        (self.month, self.day) == (2, 29)
+            && (date.month(), date.day()) == (2, 28)
+            && NaiveDate::from_ymd_opt(date.year(), 2, 29).is_none()
+    }
+}
+
+impl fmt::Display for MonthDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}-{:02}", self.month, self.day)
+    }
+}
+
+impl FromStr for MonthDay {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_once('-')
+            .and_then(|(m, d)| MonthDay::new(m.parse().ok()?, d.parse().ok()?))
+            .ok_or_else(|| ParseEnumError::new("month-day", s))
+    }
+}
+
+/// A member due a shout-out today.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Celebration {
+    pub kind: CelebrationKind,
+    pub member_id: Uuid,
+    pub username: String,
+    pub full_name: String,
+    pub email: String,
+    pub discord_id: Option<String>,
+    /// Years of membership; `None` for birthdays (we don't store the
+    /// birth year).
+    pub years: Option<i32>,
+}
+
+impl Celebration {
+    /// "1 year" / "3 years" for anniversaries.
+    pub fn years_label(&self) -> Option<String> {
+        self.years.map(|n| match n {
+            1 => "1 year".to_string(),
+            n => format!("{} years", n),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn month_day_round_trips_and_rejects_impossible_dates() {
+        let md: MonthDay = "03-07".parse().unwrap();
+        assert_eq!(md, MonthDay { month: 3, day: 7 });
+        assert_eq!(md.to_string(), "03-07");
+        assert!("02-29".parse::<MonthDay>().is_ok());
+        assert!("04-31".parse::<MonthDay>().is_err());
+        assert!("13-01".parse::<MonthDay>().is_err());
+        assert!("".parse::<MonthDay>().is_err());
+    }
+
+    #[test]
+    fn leap_day_falls_on_feb_28_in_common_years() {
+        let leap_day = MonthDay::new(2, 29).unwrap();
+        assert!(leap_day.falls_on(date(2027, 2, 28)));
+        assert!(!leap_day.falls_on(date(2028, 2, 28)));
+        assert!(leap_day.falls_on(date(2028, 2, 29)));
+        assert!(!MonthDay::new(2, 28).unwrap().falls_on(date(2028, 2, 29)));
+    }
+}
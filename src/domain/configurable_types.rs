@@ -1,7 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::Money;
+
 // =============================================================================
 // Basic Type (shared shape for event types and announcement types)
 // =============================================================================
@@ -18,6 +21,10 @@ pub struct BasicType {
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Admin who created the type; `None` for seeded types.
+    pub created_by: Option<Uuid>,
+    /// Admin who last edited the type.
+    pub updated_by: Option<Uuid>,
 }
 
 // Closed enum — `table()`, `usage_table()`, `usage_fk()`, and `display_name()`
@@ -137,10 +144,14 @@ pub struct MembershipTypeConfig {
     pub icon: Option<String>,
     pub sort_order: i32,
     pub is_active: bool,
-    pub fee_cents: i32,
+    pub fee_cents: i64,
     pub billing_period: String, // Stored as text, parsed via BillingPeriod
+    /// Most members this type may hold; `None` is uncapped.
+    pub max_members: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub created_by: Option<Uuid>,
+    pub updated_by: Option<Uuid>,
 }
 
 impl MembershipTypeConfig {
@@ -148,8 +159,14 @@ impl MembershipTypeConfig {
         BillingPeriod::from_str(&self.billing_period)
     }
 
-    pub fn fee_dollars(&self) -> f64 {
-        self.fee_cents as f64 / 100.0
+    pub fn fee(&self) -> Money {
+        Money::usd(self.fee_cents)
+    }
+
+    /// Whether `usage` members already fill the cap. Always false for
+    /// an uncapped type.
+    pub fn is_full(&self, usage: i64) -> bool {
+        self.max_members.is_some_and(|max| usage >= max)
     }
 }
 
@@ -160,8 +177,10 @@ pub struct CreateMembershipTypeRequest {
     pub description: Option<String>,
     pub color: Option<String>,
     pub icon: Option<String>,
-    pub fee_cents: i32,
+    pub fee_cents: i64,
     pub billing_period: String,
+    #[serde(default)]
+    pub max_members: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -172,24 +191,84 @@ pub struct UpdateMembershipTypeRequest {
     pub icon: Option<String>,
     pub sort_order: Option<i32>,
     pub is_active: Option<bool>,
-    pub fee_cents: Option<i32>,
+    pub fee_cents: Option<i64>,
     pub billing_period: Option<String>,
+    /// `None` leaves the cap alone; `Some(None)` removes it.
+    #[serde(default)]
+    pub max_members: Option<Option<i64>>,
 }
 
 // =============================================================================
 // Utility Functions
 // =============================================================================
 
-/// Generate a URL-safe slug from a name
+/// Longest slug `slugify` produces, in bytes (all ASCII).
+pub const SLUG_MAX_LEN: usize = 64;
+
+/// Generate a URL-safe slug from a name: lowercase ASCII letters,
+/// digits and single hyphens, never empty and at most `SLUG_MAX_LEN`
+/// long. Accented Latin letters lose their accents; anything else
+/// that isn't alphanumeric separates words. A name with nothing left
+/// (all emoji, all punctuation, empty) gets `type-` plus a short hash
+/// of the name, so different names still get different slugs.
 pub fn slugify(name: &str) -> String {
-    name.to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-")
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        let folded = fold_latin(c);
+        if !folded.is_empty() {
+            slug.push_str(folded);
+        } else if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    if slug.len() > SLUG_MAX_LEN {
+        // Cut at the last word boundary that fits, unless that would
+        // throw away most of the slug.
+        slug.truncate(SLUG_MAX_LEN);
+        if let Some(i) = slug.rfind('-').filter(|&i| i >= SLUG_MAX_LEN / 2) {
+            slug.truncate(i);
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if !slug.is_empty() {
+        return slug.to_string();
+    }
+
+    let digest = Sha256::digest(name.as_bytes());
+    format!(
+        "type-{}",
+        digest[..4].iter().map(|b| format!("{:02x}", b)).collect::<String>()
+    )
+}
+
+/// ASCII spelling of a lowercase accented Latin letter, or `""` for
+/// anything else.
+fn fold_latin(c: char) -> &'static str {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ß' => "ss",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => "",
+    }
 }
 
 /// Validate hex color format
@@ -201,6 +280,38 @@ pub fn validate_hex_color(color: &str) -> bool {
     (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
 }
 
+/// Black or white text, whichever contrasts more with `color` as a
+/// badge background (WCAG relative luminance). `None` when `color`
+/// isn't a valid hex color.
+pub fn contrasting_text_color(color: &str) -> Option<&'static str> {
+    if !validate_hex_color(color) {
+        return None;
+    }
+    let hex = &color[1..];
+    let channel = |i: usize| -> f64 {
+        let v = if hex.len() == 3 {
+            u8::from_str_radix(&hex[i..i + 1].repeat(2), 16)
+        } else {
+            u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+        }
+        .unwrap_or(0) as f64
+            / 255.0;
+        if v <= 0.03928 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let luminance = 0.2126 * channel(0) + 0.7152 * channel(1) + 0.0722 * channel(2);
+    // Contrast against black is (L + 0.05) / 0.05, against white
+    // 1.05 / (L + 0.05); they cross at L ≈ 0.179.
+    if (luminance + 0.05) / 0.05 >= 1.05 / (luminance + 0.05) {
+        Some("#000000")
+    } else {
+        Some("#FFFFFF")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,6 +324,37 @@ mod tests {
         assert_eq!(slugify("Special!@#$Characters"), "special-characters");
     }
 
+    #[test]
+    fn test_slugify_unicode() {
+        assert_eq!(slugify("Café Crème"), "cafe-creme");
+        assert_eq!(slugify("Straße Ñandú"), "strasse-nandu");
+        // Scripts without an ASCII spelling separate words rather than
+        // leaking into the URL.
+        assert_eq!(slugify("Board 理事会 Members"), "board-members");
+    }
+
+    #[test]
+    fn test_slugify_never_empty() {
+        for name in ["🎉🎉", "", "   ", "!!!", "理事会"] {
+            let slug = slugify(name);
+            assert!(slug.starts_with("type-"), "{:?} -> {:?}", name, slug);
+            assert_eq!(slug.len(), "type-".len() + 8);
+            assert!(slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+        }
+        assert_eq!(slugify("🎉"), slugify("🎉"));
+        assert_ne!(slugify("🎉"), slugify("🚀"));
+    }
+
+    #[test]
+    fn test_slugify_length_cap() {
+        let slug = slugify(&"Long Name ".repeat(30));
+        assert!(slug.len() <= SLUG_MAX_LEN, "{}", slug);
+        assert!(slug.ends_with("name"), "cut at a word boundary: {}", slug);
+
+        let slug = slugify(&"x".repeat(200));
+        assert_eq!(slug.len(), SLUG_MAX_LEN);
+    }
+
     #[test]
     fn test_validate_hex_color() {
         assert!(validate_hex_color("#FFF"));
@@ -224,6 +366,21 @@ mod tests {
         assert!(!validate_hex_color("#12345"));
     }
 
+    #[test]
+    fn test_contrasting_text_color() {
+        // Light backgrounds get dark text.
+        assert_eq!(contrasting_text_color("#FFEB3B"), Some("#000000"));
+        assert_eq!(contrasting_text_color("#fff"), Some("#000000"));
+        assert_eq!(contrasting_text_color("#4CAF50"), Some("#000000"));
+        // Dark backgrounds get light text.
+        assert_eq!(contrasting_text_color("#000"), Some("#FFFFFF"));
+        assert_eq!(contrasting_text_color("#1A237E"), Some("#FFFFFF"));
+        assert_eq!(contrasting_text_color("#9C27B0"), Some("#FFFFFF"));
+        // Malformed colors have no answer.
+        assert_eq!(contrasting_text_color("yellow"), None);
+        assert_eq!(contrasting_text_color("#12345"), None);
+    }
+
     #[test]
     fn test_billing_period() {
         assert_eq!(BillingPeriod::Yearly.as_str(), "yearly");
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::ParseEnumError;
+
+/// What a contact-form message is about. Lets admins triage the inbox
+/// and puts lockouts in front of whoever handles accounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactCategory {
+    #[default]
+    General,
+    Membership,
+    /// "I can't log in" — a member locked out of their account.
+    CantLogIn,
+}
+
+impl ContactCategory {
+    pub const ALL: [ContactCategory; 3] = [
+        ContactCategory::General,
+        ContactCategory::Membership,
+        ContactCategory::CantLogIn,
+    ];
+
+    /// Canonical string: the `contact_submissions.category` column
+    /// value and what the form posts.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactCategory::General => "general",
+            ContactCategory::Membership => "membership",
+            ContactCategory::CantLogIn => "cant_log_in",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ContactCategory::General => "General question",
+            ContactCategory::Membership => "Joining / membership",
+            ContactCategory::CantLogIn => "I can't log in",
+        }
+    }
+}
+
+impl fmt::Display for ContactCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ContactCategory {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "general" => Ok(ContactCategory::General),
+            "membership" => Ok(ContactCategory::Membership),
+            "cant_log_in" => Ok(ContactCategory::CantLogIn),
+            _ => Err(ParseEnumError::new("contact category", s)),
+        }
+    }
+}
+
+/// One message in the admin contact inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactSubmission {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub category: ContactCategory,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub handled_at: Option<DateTime<Utc>>,
+    pub handled_by: Option<Uuid>,
+}
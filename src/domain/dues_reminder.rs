@@ -0,0 +1,178 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// How long after its start a lapsed step may still go out. Keeps a
+/// newly configured sequence from emailing members who lapsed long ago.
+pub const LAPSED_STEP_WINDOW_DAYS: i64 = 7;
+
+/// How pointed a dues reminder is. Each tone has its own email.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuesReminderTone {
+    /// "Your dues are due soon."
+    Friendly,
+    /// "Your dues are due in a few days; pay now to keep your access."
+    Firm,
+    /// "Your membership has lapsed."
+    Lapsed,
+}
+
+impl DuesReminderTone {
+    pub const ALL: [DuesReminderTone; 3] = [
+        DuesReminderTone::Friendly,
+        DuesReminderTone::Firm,
+        DuesReminderTone::Lapsed,
+    ];
+
+    /// Name used in `membership.reminder_sequence`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuesReminderTone::Friendly => "friendly",
+            DuesReminderTone::Firm => "firm",
+            DuesReminderTone::Lapsed => "lapsed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+}
+
+/// One step of the dues reminder sequence: send the `tone` email
+/// `days_before` days before dues run out. Negative is days after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuesReminderStep {
+    pub days_before: i64,
+    pub tone: DuesReminderTone,
+}
+
+/// Parse `membership.reminder_sequence`, e.g. `14:friendly,3:firm,-7:lapsed`.
+/// Friendly and firm steps come before expiry, lapsed ones on or after
+/// it, and no two steps share a day. Steps come back earliest first.
+pub fn parse_dues_reminder_sequence(value: &str) -> Result<Vec<DuesReminderStep>, String> {
+    let mut steps = Vec::new();
+    for entry in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (days, tone) = entry
+            .split_once(':')
+            .ok_or_else(|| format!("{:?} should look like 14:friendly", entry))?;
+        let days_before: i64 = days
+            .trim()
+            .parse()
+            .ok()
+            .filter(|d: &i64| d.abs() <= 365)
+            .ok_or_else(|| format!("{:?} is not a number of days up to 365", days.trim()))?;
+        let tone = DuesReminderTone::parse(tone).ok_or_else(|| {
+            format!(
+                "{:?} is not a reminder tone. Supported: friendly, firm, lapsed",
+                tone.trim()
+            )
+        })?;
+        match tone {
+            DuesReminderTone::Lapsed if days_before > 0 => {
+                return Err(format!(
+                    "{:?}: a lapsed reminder goes out on or after the expiry date, \
+                     so its days must be 0 or negative",
+                    entry
+                ));
+            }
+            DuesReminderTone::Friendly | DuesReminderTone::Firm if days_before <= 0 => {
+                return Err(format!(
+                    "{:?}: only lapsed reminders go out on or after the expiry date",
+                    entry
+                ));
+            }
+            _ => {}
+        }
+        if steps
+            .iter()
+            .any(|s: &DuesReminderStep| s.days_before == days_before)
+        {
+            return Err(format!("Day {} is listed twice", days_before));
+        }
+        steps.push(DuesReminderStep { days_before, tone });
+    }
+    if steps.is_empty() {
+        return Err("Give at least one reminder step, e.g. 7:friendly".to_string());
+    }
+    steps.sort_by_key(|s| std::cmp::Reverse(s.days_before));
+    Ok(steps)
+}
+
+/// The step a member whose dues run out at `due` is in at `now`, if
+/// any. Each step lasts until the next one starts; the last step
+/// before expiry ends at expiry, and the last lapsed step ends
+/// [`LAPSED_STEP_WINDOW_DAYS`] after it starts. `steps` is earliest
+/// first, as [`parse_dues_reminder_sequence`] returns it.
+pub fn current_dues_reminder_step(
+    steps: &[DuesReminderStep],
+    due: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<DuesReminderStep> {
+    steps.iter().enumerate().find_map(|(i, step)| {
+        let start = due - Duration::days(step.days_before);
+        let mut end = match steps.get(i + 1) {
+            Some(next) => due - Duration::days(next.days_before),
+            None => start + Duration::days(LAPSED_STEP_WINDOW_DAYS),
+        };
+        if step.days_before > 0 {
+            end = end.min(due);
+        }
+        (start <= now && now < end).then_some(*step)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_orders_steps() {
+        let steps = parse_dues_reminder_sequence("-7:lapsed, 14:Friendly,3:firm").unwrap();
+        assert_eq!(
+            steps,
+            vec![
+                DuesReminderStep {
+                    days_before: 14,
+                    tone: DuesReminderTone::Friendly
+                },
+                DuesReminderStep {
+                    days_before: 3,
+                    tone: DuesReminderTone::Firm
+                },
+                DuesReminderStep {
+                    days_before: -7,
+                    tone: DuesReminderTone::Lapsed
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_steps() {
+        for value in [
+            "",
+            "14",
+            "x:friendly",
+            "400:friendly",
+            "14:stern",
+            "3:lapsed",
+            "-3:firm",
+            "7:friendly,7:firm",
+        ] {
+            assert!(parse_dues_reminder_sequence(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn each_step_lasts_until_the_next() {
+        let steps = parse_dues_reminder_sequence("14:friendly,3:firm,-7:lapsed").unwrap();
+        let due = Utc::now();
+        let at = |days: i64| current_dues_reminder_step(&steps, due, due + Duration::days(days));
+        assert_eq!(at(-20), None);
+        assert_eq!(at(-10).unwrap().tone, DuesReminderTone::Friendly);
+        assert_eq!(at(-1).unwrap().tone, DuesReminderTone::Firm);
+        assert_eq!(at(2), None);
+        assert_eq!(at(8).unwrap().tone, DuesReminderTone::Lapsed);
+        assert_eq!(at(20), None);
+    }
+}
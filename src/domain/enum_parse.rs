@@ -0,0 +1,157 @@
+//! Error for parsing the fixed-variant domain enums (`MemberStatus`,
+//! `EventType`, `EventVisibility`, `EventStatus`, `AttendanceStatus`,
+//! `AnnouncementType`, `PaymentStatus`) from their canonical strings.
+//! Each enum's `as_str` is that string: it's what the DB column holds,
+//! what query strings and forms carry, and what `Display` prints.
+//! `FromStr` is the exact inverse.
+//!
+//! Where an unknown value lands decides the mapping: a bad query
+//! parameter is the caller's fault (BadRequest or "no filter"), a bad
+//! DB value is ours (`AppError::Internal`).
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    /// Human name of the enum, e.g. "member status".
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl ParseEnumError {
+    pub fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid {}: {}", self.kind, self.value)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Display;
+    use std::str::FromStr;
+
+    use serde::Serialize;
+
+    use crate::domain::{
+        AdminNotificationChannel, AdminNotificationKind, AnnouncementType, AttendanceStatus,
+        ContactCategory, EventStatus, EventType, EventVisibility, LandingPage, MemberStatus,
+        PaymentStatus,
+    };
+
+    /// `Display` → `FromStr` gives the variant back, and the string is
+    /// the same one serde (and so the API) uses.
+    fn assert_round_trips<T>(variants: &[T])
+    where
+        T: Display + FromStr + Serialize + PartialEq + std::fmt::Debug,
+        T::Err: std::fmt::Debug,
+    {
+        for variant in variants {
+            let s = variant.to_string();
+            assert_eq!(&s.parse::<T>().unwrap(), variant, "{}", s);
+            assert_eq!(serde_json::to_value(variant).unwrap(), s.as_str());
+        }
+    }
+
+    #[test]
+    fn member_status_round_trips() {
+        assert_round_trips(&[
+            MemberStatus::Pending,
+            MemberStatus::Active,
+            MemberStatus::Expired,
+            MemberStatus::Suspended,
+            MemberStatus::Honorary,
+        ]);
+    }
+
+    #[test]
+    fn event_type_round_trips() {
+        assert_round_trips(&[
+            EventType::Meeting,
+            EventType::Workshop,
+            EventType::CTF,
+            EventType::Social,
+            EventType::Training,
+            EventType::Hackathon,
+        ]);
+    }
+
+    #[test]
+    fn event_visibility_round_trips() {
+        assert_round_trips(&[
+            EventVisibility::Public,
+            EventVisibility::MembersOnly,
+            EventVisibility::AdminOnly,
+        ]);
+    }
+
+    #[test]
+    fn event_and_attendance_status_round_trip() {
+        assert_round_trips(&[
+            EventStatus::Published,
+            EventStatus::Proposed,
+            EventStatus::Rejected,
+        ]);
+        assert_round_trips(&[
+            AttendanceStatus::Registered,
+            AttendanceStatus::Waitlisted,
+            AttendanceStatus::Cancelled,
+        ]);
+    }
+
+    #[test]
+    fn announcement_type_round_trips() {
+        assert_round_trips(&[
+            AnnouncementType::News,
+            AnnouncementType::Achievement,
+            AnnouncementType::Meeting,
+            AnnouncementType::CTFResult,
+            AnnouncementType::General,
+        ]);
+    }
+
+    #[test]
+    fn payment_status_round_trips() {
+        assert_round_trips(&[
+            PaymentStatus::Pending,
+            PaymentStatus::Completed,
+            PaymentStatus::Failed,
+            PaymentStatus::Refunded,
+        ]);
+    }
+
+    #[test]
+    fn admin_notification_enums_round_trip() {
+        assert_round_trips(&AdminNotificationKind::ALL);
+        assert_round_trips(&AdminNotificationChannel::ALL);
+    }
+
+    #[test]
+    fn landing_page_round_trips() {
+        assert_round_trips(&LandingPage::ALL);
+    }
+
+    #[test]
+    fn contact_category_round_trips() {
+        assert_round_trips(&ContactCategory::ALL);
+    }
+
+    #[test]
+    fn unknown_strings_are_rejected() {
+        let err = "active".parse::<MemberStatus>().unwrap_err();
+        assert_eq!(err.to_string(), "Invalid member status: active");
+        assert!("".parse::<EventType>().is_err());
+        assert!("Members Only".parse::<EventVisibility>().is_err());
+        assert!("CTF Result".parse::<AnnouncementType>().is_err());
+        assert!("Paid".parse::<PaymentStatus>().is_err());
+    }
+}
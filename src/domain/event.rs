@@ -1,8 +1,13 @@
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::{Member, ParseEnumError};
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
 pub struct Event {
     pub id: Uuid,
@@ -16,6 +21,20 @@ pub struct Event {
     pub location: Option<String>,
     pub max_attendees: Option<i32>,
     pub rsvp_required: bool,
+    /// Accept RSVPs from non-members through the public API. Only
+    /// honored on `Public` events; guests count toward `max_attendees`.
+    #[serde(default)]
+    pub allow_guest_rsvp: bool,
+    /// Events sharing a registration group allow a member only one
+    /// active RSVP across the whole group (one CTF team slot, one
+    /// workshop of several). `None` for an independent event.
+    #[serde(default)]
+    pub registration_group: Option<String>,
+    /// Last moment members (and guests) can RSVP; never after
+    /// `start_time`. `None` leaves RSVPs open until the event starts.
+    /// Admins can still register a member after it passes.
+    #[serde(default)]
+    pub rsvp_deadline: Option<DateTime<Utc>>,
     pub image_url: Option<String>,
     pub created_by: Uuid,
     pub created_at: DateTime<Utc>,
@@ -28,6 +47,20 @@ pub struct Event {
     /// 1-based position within the series, or `None` for one-offs.
     /// Used for display ("session 5 of 12") and stable ordering.
     pub occurrence_index: Option<i32>,
+    /// Review state. Admin-created events are `Published` from the
+    /// start; member proposals begin as `Proposed`.
+    pub status: EventStatus,
+    /// Admin's note to the proposer, set when a proposal is reviewed.
+    /// Meant for the proposer only, so it never goes out over the API.
+    #[serde(skip_serializing, default)]
+    pub review_feedback: Option<String>,
+}
+
+impl Event {
+    /// Whether the RSVP deadline, if any, has passed at `now`.
+    pub fn rsvp_closed(&self, now: DateTime<Utc>) -> bool {
+        self.rsvp_deadline.is_some_and(|deadline| now > deadline)
+    }
 }
 
 /// Persisted recurring-event series. The actual recurrence rule lives
@@ -61,7 +94,7 @@ pub struct EventSeries {
 /// let type_config = event_type_service.get(event.event_type_id).await?;
 /// let type_name = type_config.name;
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "TEXT")]
 pub enum EventType {
     Meeting,
@@ -72,6 +105,43 @@ pub enum EventType {
     Hackathon,
 }
 
+impl EventType {
+    /// Canonical string: the `event_type` column value and what
+    /// filters and forms send.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventType::Meeting => "Meeting",
+            EventType::Workshop => "Workshop",
+            EventType::CTF => "CTF",
+            EventType::Social => "Social",
+            EventType::Training => "Training",
+            EventType::Hackathon => "Hackathon",
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventType {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Meeting" => Ok(EventType::Meeting),
+            "Workshop" => Ok(EventType::Workshop),
+            "CTF" => Ok(EventType::CTF),
+            "Social" => Ok(EventType::Social),
+            "Training" => Ok(EventType::Training),
+            "Hackathon" => Ok(EventType::Hackathon),
+            _ => Err(ParseEnumError::new("event type", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
 #[sqlx(type_name = "TEXT")]
 pub enum EventVisibility {
@@ -80,6 +150,91 @@ pub enum EventVisibility {
     AdminOnly,
 }
 
+impl EventVisibility {
+    /// Canonical string: the `visibility` column value and what
+    /// filters and forms send.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventVisibility::Public => "Public",
+            EventVisibility::MembersOnly => "MembersOnly",
+            EventVisibility::AdminOnly => "AdminOnly",
+        }
+    }
+}
+
+impl fmt::Display for EventVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventVisibility {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Public" => Ok(EventVisibility::Public),
+            "MembersOnly" => Ok(EventVisibility::MembersOnly),
+            "AdminOnly" => Ok(EventVisibility::AdminOnly),
+            _ => Err(ParseEnumError::new("event visibility", s)),
+        }
+    }
+}
+
+/// Whether `viewer` may see `event` at all — listings, the dashboard,
+/// RSVPs. `None` is an anonymous visitor (public API, feeds). Public
+/// events are for everyone, MembersOnly for any logged-in member,
+/// AdminOnly for admins. Review status (`Proposed`, `Rejected`) is a
+/// separate check; this only answers the visibility question.
+pub fn can_view_event(viewer: Option<&Member>, event: &Event) -> bool {
+    match event.visibility {
+        EventVisibility::Public => true,
+        EventVisibility::MembersOnly => viewer.is_some(),
+        EventVisibility::AdminOnly => viewer.is_some_and(|m| m.is_admin),
+    }
+}
+
+/// Where an event sits in the proposal workflow. Only `Published`
+/// events show up in listings, RSVPs, reminders, and the public API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "TEXT")]
+pub enum EventStatus {
+    #[default]
+    Published,
+    Proposed,
+    Rejected,
+}
+
+impl EventStatus {
+    /// Canonical string: the `status` column value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventStatus::Published => "Published",
+            EventStatus::Proposed => "Proposed",
+            EventStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+impl fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EventStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Published" => Ok(EventStatus::Published),
+            "Proposed" => Ok(EventStatus::Proposed),
+            "Rejected" => Ok(EventStatus::Rejected),
+            _ => Err(ParseEnumError::new("event status", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventAttendance {
     pub event_id: Uuid,
@@ -89,10 +244,120 @@ pub struct EventAttendance {
     pub attended: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "TEXT")]
 pub enum AttendanceStatus {
     Registered,
     Waitlisted,
     Cancelled,
-}
\ No newline at end of file
+}
+
+impl AttendanceStatus {
+    /// Canonical string: the `event_attendance.status` column value
+    /// and what the attendee export writes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AttendanceStatus::Registered => "Registered",
+            AttendanceStatus::Waitlisted => "Waitlisted",
+            AttendanceStatus::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl fmt::Display for AttendanceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AttendanceStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Registered" => Ok(AttendanceStatus::Registered),
+            "Waitlisted" => Ok(AttendanceStatus::Waitlisted),
+            "Cancelled" => Ok(AttendanceStatus::Cancelled),
+            _ => Err(ParseEnumError::new("attendance status", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod visibility_tests {
+    use super::*;
+    use crate::domain::{BillingMode, MemberStatus};
+
+    fn member(is_admin: bool) -> Member {
+        let now = Utc::now();
+        Member {
+            id: Uuid::new_v4(),
+            email: "m@example.com".to_string(),
+            username: "m".to_string(),
+            full_name: "M".to_string(),
+            status: MemberStatus::Active,
+            membership_type_id: Uuid::new_v4(),
+            joined_at: now,
+            expires_at: None,
+            dues_paid_until: None,
+            bypass_dues: false,
+            is_admin,
+            notes: None,
+            stripe_customer_id: None,
+            stripe_subscription_id: None,
+            billing_mode: BillingMode::Manual,
+            email_verified_at: None,
+            dues_reminder_sent_at: None,
+            discord_id: None,
+            locale: None,
+            honorary_until: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn event(visibility: EventVisibility) -> Event {
+        let now = Utc::now();
+        Event {
+            id: Uuid::new_v4(),
+            title: "Lockpicking night".to_string(),
+            description: String::new(),
+            event_type: EventType::Social,
+            event_type_id: None,
+            visibility,
+            start_time: now,
+            end_time: None,
+            location: None,
+            max_attendees: None,
+            rsvp_required: false,
+            allow_guest_rsvp: false,
+            registration_group: None,
+            rsvp_deadline: None,
+            image_url: None,
+            created_by: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+            series_id: None,
+            occurrence_index: None,
+            status: EventStatus::Published,
+            review_feedback: None,
+        }
+    }
+
+    #[test]
+    fn visibility_by_viewer_role() {
+        let regular = member(false);
+        let admin = member(true);
+        let cases = [
+            (EventVisibility::Public, [true, true, true]),
+            (EventVisibility::MembersOnly, [false, true, true]),
+            (EventVisibility::AdminOnly, [false, false, true]),
+        ];
+        for (visibility, [anonymous, as_member, as_admin]) in cases {
+            let e = event(visibility.clone());
+            assert_eq!(can_view_event(None, &e), anonymous, "{:?} / anonymous", visibility);
+            assert_eq!(can_view_event(Some(&regular), &e), as_member, "{:?} / member", visibility);
+            assert_eq!(can_view_event(Some(&admin), &e), as_admin, "{:?} / admin", visibility);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Which raw HTML members may write in announcement and comment
+//! Markdown. Clubs tune it through the `announcements.html_*`
+//! settings (see `SettingsService::html_policy`); `web::markdown`
+//! applies it. Some things stay off whatever the settings say:
+//! `script` tags, `on*` event handlers, `srcdoc`, and script-bearing
+//! URL schemes.
+
+use std::collections::BTreeSet;
+
+pub const TAGS_KEY: &str = "announcements.html_allowed_tags";
+pub const ATTRIBUTES_KEY: &str = "announcements.html_allowed_attributes";
+pub const URL_SCHEMES_KEY: &str = "announcements.html_allowed_url_schemes";
+pub const IFRAME_HOSTS_KEY: &str = "announcements.html_allowed_iframe_hosts";
+
+pub const KEYS: [&str; 4] = [TAGS_KEY, ATTRIBUTES_KEY, URL_SCHEMES_KEY, IFRAME_HOSTS_KEY];
+
+/// The baseline, matching `059_html_allowlist.sql`: simple formatting
+/// and links, no embeds.
+pub const DEFAULT_TAGS: &str = "a, b, i, em, strong, code, pre, blockquote, ul, ol, li, br";
+pub const DEFAULT_ATTRIBUTES: &str = "a.href, a.title";
+pub const DEFAULT_URL_SCHEMES: &str = "https, http, mailto";
+pub const DEFAULT_IFRAME_HOSTS: &str = "";
+
+const ALWAYS_DENIED_TAGS: &[&str] = &["script"];
+const ALWAYS_DENIED_ATTRIBUTES: &[&str] = &["srcdoc"];
+const ALWAYS_DENIED_SCHEMES: &[&str] = &["javascript", "vbscript", "data"];
+
+/// Attributes whose value is a URL, and so must use an allowed scheme.
+pub const URL_ATTRIBUTES: &[&str] = &["href", "src", "cite", "poster", "action", "formaction"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlPolicy {
+    tags: BTreeSet<String>,
+    /// `(tag, attribute)` pairs.
+    attributes: BTreeSet<(String, String)>,
+    url_schemes: BTreeSet<String>,
+    iframe_hosts: BTreeSet<String>,
+}
+
+impl Default for HtmlPolicy {
+    fn default() -> Self {
+        let mut policy = Self {
+            tags: BTreeSet::new(),
+            attributes: BTreeSet::new(),
+            url_schemes: BTreeSet::new(),
+            iframe_hosts: BTreeSet::new(),
+        };
+        for (key, value) in [
+            (TAGS_KEY, DEFAULT_TAGS),
+            (ATTRIBUTES_KEY, DEFAULT_ATTRIBUTES),
+            (URL_SCHEMES_KEY, DEFAULT_URL_SCHEMES),
+            (IFRAME_HOSTS_KEY, DEFAULT_IFRAME_HOSTS),
+        ] {
+            policy.set(key, value).expect("default HTML policy parses");
+        }
+        policy
+    }
+}
+
+impl HtmlPolicy {
+    /// Replace the part of the policy stored under `key` (one of
+    /// `KEYS`) with the comma-separated list in `value`. On error the
+    /// policy is unchanged.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let entries = value.split(',').map(str::trim).filter(|s| !s.is_empty());
+        match key {
+            TAGS_KEY => {
+                self.tags = entries.map(parse_tag).collect::<Result<_, _>>()?;
+            }
+            ATTRIBUTES_KEY => {
+                self.attributes = entries
+                    .map(|entry| {
+                        let (tag, attribute) = entry.split_once('.').ok_or_else(|| {
+                            format!("{:?} should be tag.attribute, e.g. a.href", entry)
+                        })?;
+                        Ok((parse_tag(tag)?, parse_attribute(attribute)?))
+                    })
+                    .collect::<Result<_, String>>()?;
+            }
+            URL_SCHEMES_KEY => {
+                self.url_schemes = entries.map(parse_scheme).collect::<Result<_, _>>()?;
+            }
+            IFRAME_HOSTS_KEY => {
+                self.iframe_hosts = entries.map(parse_host).collect::<Result<_, _>>()?;
+            }
+            _ => return Err(format!("{} is not an HTML policy setting", key)),
+        }
+        Ok(())
+    }
+
+    /// `tag` is lowercase.
+    pub fn allows_tag(&self, tag: &str) -> bool {
+        !ALWAYS_DENIED_TAGS.contains(&tag) && self.tags.contains(tag)
+    }
+
+    /// `tag` and `attribute` are lowercase.
+    pub fn allows_attribute(&self, tag: &str, attribute: &str) -> bool {
+        !attribute.starts_with("on")
+            && !ALWAYS_DENIED_ATTRIBUTES.contains(&attribute)
+            && self
+                .attributes
+                .contains(&(tag.to_string(), attribute.to_string()))
+    }
+
+    /// Whether `url` may appear in a URL attribute of `tag`. Relative
+    /// URLs are fine except in an iframe, whose `src` has to be an
+    /// absolute URL on one of the allowed hosts (or a subdomain).
+    pub fn allows_url(&self, tag: &str, url: &str) -> bool {
+        // Browsers drop tabs and newlines inside URLs, so
+        // `java\tscript:` would still run.
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return false;
+        }
+        let scheme = url
+            .split_once(':')
+            .map(|(scheme, _)| scheme)
+            .filter(|scheme| !scheme.contains(['/', '?', '#']));
+        if let Some(scheme) = scheme {
+            let scheme = scheme.to_ascii_lowercase();
+            if ALWAYS_DENIED_SCHEMES.contains(&scheme.as_str())
+                || !self.url_schemes.contains(&scheme)
+            {
+                return false;
+            }
+        }
+        if tag != "iframe" {
+            return true;
+        }
+        let Some(after_scheme) = scheme.and_then(|s| url.get(s.len() + 1..)) else {
+            return false;
+        };
+        let Some(authority) = after_scheme.strip_prefix("//") else {
+            return false;
+        };
+        let authority = authority.split(['/', '?', '#']).next().unwrap_or_default();
+        if authority.contains('@') {
+            return false;
+        }
+        let host = authority
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        self.iframe_hosts.iter().any(|allowed| {
+            host == *allowed
+                || host
+                    .strip_suffix(allowed.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+    }
+}
+
+fn is_name(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn parse_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim().to_ascii_lowercase();
+    if !is_name(&tag) || tag.contains('-') {
+        return Err(format!("{:?} is not an HTML tag name", tag));
+    }
+    if ALWAYS_DENIED_TAGS.contains(&tag.as_str()) {
+        return Err(format!("{} can't be allowed", tag));
+    }
+    Ok(tag)
+}
+
+fn parse_attribute(attribute: &str) -> Result<String, String> {
+    let attribute = attribute.trim().to_ascii_lowercase();
+    if !is_name(&attribute) {
+        return Err(format!("{:?} is not an HTML attribute name", attribute));
+    }
+    if attribute.starts_with("on") || ALWAYS_DENIED_ATTRIBUTES.contains(&attribute.as_str()) {
+        return Err(format!("{} can't be allowed", attribute));
+    }
+    Ok(attribute)
+}
+
+fn parse_scheme(scheme: &str) -> Result<String, String> {
+    let scheme = scheme.trim_end_matches(':').to_ascii_lowercase();
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid {
+        return Err(format!("{:?} is not a URL scheme", scheme));
+    }
+    if ALWAYS_DENIED_SCHEMES.contains(&scheme.as_str()) {
+        return Err(format!("{} URLs can't be allowed", scheme));
+    }
+    Ok(scheme)
+}
+
+fn parse_host(host: &str) -> Result<String, String> {
+    let host = host.to_ascii_lowercase();
+    let valid = host.split('.').all(|label| {
+        !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+    if !valid {
+        return Err(format!("{:?} is not a host name", host));
+    }
+    Ok(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_iframes(hosts: &str) -> HtmlPolicy {
+        let mut policy = HtmlPolicy::default();
+        policy.set(TAGS_KEY, "iframe").unwrap();
+        policy.set(ATTRIBUTES_KEY, "iframe.src").unwrap();
+        policy.set(IFRAME_HOSTS_KEY, hosts).unwrap();
+        policy
+    }
+
+    #[test]
+    fn dangerous_entries_are_refused() {
+        let mut policy = HtmlPolicy::default();
+        assert!(policy.set(TAGS_KEY, "b, SCRIPT").is_err());
+        assert!(policy.set(ATTRIBUTES_KEY, "a.onclick").is_err());
+        assert!(policy.set(ATTRIBUTES_KEY, "iframe.srcdoc").is_err());
+        assert!(policy.set(ATTRIBUTES_KEY, "href").is_err());
+        assert!(policy.set(URL_SCHEMES_KEY, "https, javascript").is_err());
+        assert_eq!(policy, HtmlPolicy::default(), "failed sets change nothing");
+    }
+
+    #[test]
+    fn url_schemes() {
+        let policy = HtmlPolicy::default();
+        assert!(policy.allows_url("a", "https://example.com"));
+        assert!(policy.allows_url("a", "/portal/events"));
+        assert!(policy.allows_url("a", "mailto:board@example.com"));
+        assert!(!policy.allows_url("a", "ftp://example.com"));
+        assert!(!policy.allows_url("a", "JavaScript:alert(1)"));
+        assert!(!policy.allows_url("a", "java\tscript:alert(1)"));
+    }
+
+    #[test]
+    fn iframes_need_an_allowed_host() {
+        let policy = with_iframes("youtube.com");
+        assert!(policy.allows_url("iframe", "https://youtube.com/embed/x"));
+        assert!(policy.allows_url("iframe", "https://www.youtube.com/embed/x"));
+        assert!(!policy.allows_url("iframe", "https://notyoutube.com/embed/x"));
+        assert!(!policy.allows_url("iframe", "https://youtube.com.evil.test/"));
+        assert!(!policy.allows_url("iframe", "https://youtube.com@evil.test/"));
+        assert!(!policy.allows_url("iframe", "//youtube.com/embed/x"));
+        assert!(!policy.allows_url("iframe", "/embed/x"));
+    }
+}
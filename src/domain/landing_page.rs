@@ -0,0 +1,111 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::ParseEnumError;
+
+/// Where a member lands after signing in, unless the login carried a
+/// `redirect`. The club sets one default for members and one for
+/// admins (`auth.member_landing_page`, `auth.admin_landing_page`), and
+/// each member can override it on their profile. Members start on
+/// `Me`, their own summary; admins on the dashboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LandingPage {
+    #[default]
+    Dashboard,
+    Me,
+    Events,
+    Announcements,
+    Directory,
+    AdminMembers,
+    AdminBilling,
+}
+
+impl LandingPage {
+    pub const ALL: [LandingPage; 7] = [
+        LandingPage::Dashboard,
+        LandingPage::Me,
+        LandingPage::Events,
+        LandingPage::Announcements,
+        LandingPage::Directory,
+        LandingPage::AdminMembers,
+        LandingPage::AdminBilling,
+    ];
+
+    /// Canonical string: the setting value and the
+    /// `member_profiles.landing_page` column value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LandingPage::Dashboard => "dashboard",
+            LandingPage::Me => "me",
+            LandingPage::Events => "events",
+            LandingPage::Announcements => "announcements",
+            LandingPage::Directory => "directory",
+            LandingPage::AdminMembers => "admin_members",
+            LandingPage::AdminBilling => "admin_billing",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LandingPage::Dashboard => "Dashboard",
+            LandingPage::Me => "My summary",
+            LandingPage::Events => "Events",
+            LandingPage::Announcements => "Announcements",
+            LandingPage::Directory => "Member directory",
+            LandingPage::AdminMembers => "Admin: Members",
+            LandingPage::AdminBilling => "Admin: Billing dashboard",
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            LandingPage::Dashboard => "/portal/dashboard",
+            LandingPage::Me => "/portal/me",
+            LandingPage::Events => "/portal/events",
+            LandingPage::Announcements => "/portal/announcements",
+            LandingPage::Directory => "/portal/directory",
+            LandingPage::AdminMembers => "/portal/admin/members",
+            LandingPage::AdminBilling => "/portal/admin/billing/dashboard",
+        }
+    }
+
+    /// Pages behind the admin gate.
+    pub fn admin_only(&self) -> bool {
+        matches!(self, LandingPage::AdminMembers | LandingPage::AdminBilling)
+    }
+
+    /// This page if the member may see it, otherwise the dashboard.
+    pub fn permitted_for(self, is_admin: bool) -> LandingPage {
+        if self.admin_only() && !is_admin {
+            LandingPage::Dashboard
+        } else {
+            self
+        }
+    }
+}
+
+impl fmt::Display for LandingPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LandingPage {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dashboard" => Ok(LandingPage::Dashboard),
+            "me" => Ok(LandingPage::Me),
+            "events" => Ok(LandingPage::Events),
+            "announcements" => Ok(LandingPage::Announcements),
+            "directory" => Ok(LandingPage::Directory),
+            "admin_members" => Ok(LandingPage::AdminMembers),
+            "admin_billing" => Ok(LandingPage::AdminBilling),
+            _ => Err(ParseEnumError::new("landing page", s)),
+        }
+    }
+}
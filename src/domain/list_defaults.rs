@@ -0,0 +1,61 @@
+//! Club-configurable defaults for the admin events and announcements
+//! lists. See `SettingsService::list_default`.
+
+/// A setting holding the default for one admin-list query param
+/// (`sort`, `order` or `time`), used when the request leaves that
+/// param out. `allowed` is every value the list handler understands;
+/// the first is the built-in default.
+#[derive(Debug, Clone, Copy)]
+pub struct ListDefaultSetting {
+    pub key: &'static str,
+    pub allowed: &'static [&'static str],
+}
+
+impl ListDefaultSetting {
+    /// `value` as one of `allowed`, or the built-in default when it
+    /// isn't one (only possible through a hand edit).
+    pub fn resolve(&self, value: &str) -> &'static str {
+        self.allowed
+            .iter()
+            .find(|a| **a == value)
+            .copied()
+            .unwrap_or(self.allowed[0])
+    }
+
+    pub fn accepts(&self, value: &str) -> bool {
+        self.allowed.contains(&value)
+    }
+}
+
+pub const EVENT_SORT: ListDefaultSetting = ListDefaultSetting {
+    key: "events.default_sort",
+    allowed: &["start_time", "title", "type", "visibility"],
+};
+
+pub const EVENT_ORDER: ListDefaultSetting = ListDefaultSetting {
+    key: "events.default_order",
+    allowed: &["asc", "desc"],
+};
+
+pub const EVENT_TIME: ListDefaultSetting = ListDefaultSetting {
+    key: "events.default_time_filter",
+    allowed: &["upcoming", "past", "all"],
+};
+
+pub const ANNOUNCEMENT_SORT: ListDefaultSetting = ListDefaultSetting {
+    key: "announcements.default_sort",
+    allowed: &["created_at", "published_at", "title", "type"],
+};
+
+pub const ANNOUNCEMENT_ORDER: ListDefaultSetting = ListDefaultSetting {
+    key: "announcements.default_order",
+    allowed: &["desc", "asc"],
+};
+
+pub const ALL: [ListDefaultSetting; 5] = [
+    EVENT_SORT,
+    EVENT_ORDER,
+    EVENT_TIME,
+    ANNOUNCEMENT_SORT,
+    ANNOUNCEMENT_ORDER,
+];
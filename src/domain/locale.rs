@@ -0,0 +1,246 @@
+//! Display locale for dates, times, numbers, and money.
+//!
+//! The org picks a default (`org.locale`); a member can override it
+//! from their profile. Everything user-facing that renders a date or an
+//! amount goes through these helpers instead of a hand-written
+//! `.format("%B %d, %Y")`, so a German club doesn't get US month-first
+//! dates in half the portal.
+//!
+//! Machine formats — `<input type="date">` values, iCal stamps, CSV
+//! exports — are NOT locale-dependent and keep their fixed patterns.
+//!
+//! The set is deliberately small. Adding a locale means one variant,
+//! its tag, and a row in each match below.
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Money;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    EnUs,
+    EnGb,
+    DeDe,
+    FrFr,
+}
+
+const DE_MONTHS: [&str; 12] = [
+    "Januar", "Februar", "März", "April", "Mai", "Juni",
+    "Juli", "August", "September", "Oktober", "November", "Dezember",
+];
+
+const FR_MONTHS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::EnUs, Locale::EnGb, Locale::DeDe, Locale::FrFr];
+
+    /// BCP 47 tag stored in `org.locale` / `members.locale`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::EnGb => "en-GB",
+            Locale::DeDe => "de-DE",
+            Locale::FrFr => "fr-FR",
+        }
+    }
+
+    /// Human label for the settings / profile dropdowns.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "English (United States)",
+            Locale::EnGb => "English (United Kingdom)",
+            Locale::DeDe => "Deutsch (Deutschland)",
+            Locale::FrFr => "Français (France)",
+        }
+    }
+
+    /// Parse a stored tag. Case-insensitive and accepts `_` for `-`
+    /// since both spellings show up in hand-edited configs.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let normalized = tag.trim().replace('_', "-").to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|l| l.tag().to_ascii_lowercase() == normalized)
+    }
+
+    /// Member preference if set and valid, otherwise the org default.
+    pub fn resolve(member_pref: Option<&str>, org_default: Locale) -> Self {
+        member_pref
+            .filter(|s| !s.is_empty())
+            .and_then(Self::from_tag)
+            .unwrap_or(org_default)
+    }
+
+    /// "September 12, 2025" / "12 September 2025" / "12. September 2025"
+    pub fn long_date(&self, d: &DateTime<Utc>) -> String {
+        match self {
+            Locale::EnUs => d.format("%B %d, %Y").to_string(),
+            Locale::EnGb => d.format("%-d %B %Y").to_string(),
+            Locale::DeDe => format!("{}. {} {}", d.day(), DE_MONTHS[d.month0() as usize], d.year()),
+            Locale::FrFr => format!("{} {} {}", d.day(), FR_MONTHS[d.month0() as usize], d.year()),
+        }
+    }
+
+    /// "Sep 12, 2025" / "12 Sep 2025" / "12.09.2025" / "12/09/2025"
+    pub fn short_date(&self, d: &DateTime<Utc>) -> String {
+        match self {
+            Locale::EnUs => d.format("%b %d, %Y").to_string(),
+            Locale::EnGb => d.format("%-d %b %Y").to_string(),
+            Locale::DeDe => d.format("%d.%m.%Y").to_string(),
+            Locale::FrFr => d.format("%d/%m/%Y").to_string(),
+        }
+    }
+
+    /// "September 2025" / "September 2025" / "septembre 2025"
+    pub fn month_year(&self, d: &DateTime<Utc>) -> String {
+        match self {
+            Locale::EnUs | Locale::EnGb => d.format("%B %Y").to_string(),
+            Locale::DeDe => format!("{} {}", DE_MONTHS[d.month0() as usize], d.year()),
+            Locale::FrFr => format!("{} {}", FR_MONTHS[d.month0() as usize], d.year()),
+        }
+    }
+
+    /// Wall-clock time: "2:30 PM" for en-US, "14:30" elsewhere.
+    pub fn time(&self, d: &DateTime<Utc>) -> String {
+        match self {
+            Locale::EnUs => d.format("%-I:%M %p").to_string(),
+            _ => d.format("%H:%M").to_string(),
+        }
+    }
+
+    /// Compact date + 24h time for admin tables: "Sep 12, 2025 14:30".
+    pub fn date_time(&self, d: &DateTime<Utc>) -> String {
+        format!("{} {}", self.short_date(d), d.format("%H:%M"))
+    }
+
+    /// Sentence-style date + time: "September 12, 2025 at 14:30".
+    pub fn long_date_time(&self, d: &DateTime<Utc>) -> String {
+        let at = match self {
+            Locale::EnUs | Locale::EnGb => "at",
+            Locale::DeDe => "um",
+            Locale::FrFr => "à",
+        };
+        format!("{} {} {}", self.long_date(d), at, d.format("%H:%M"))
+    }
+
+    /// Fixed-point number with locale grouping and decimal separators:
+    /// "1,234.50" / "1.234,50" / "1 234,50".
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let raw = format!("{:.*}", decimals, value.abs());
+        let negative = value < 0.0 && value.abs() >= 0.5 * 10f64.powi(-(decimals as i32));
+        self.group_decimal(&raw, negative)
+    }
+
+    /// Apply the locale's separators to an unsigned "1234.50" string.
+    fn group_decimal(&self, raw: &str, negative: bool) -> String {
+        let (group, decimal) = match self {
+            Locale::EnUs | Locale::EnGb => (",", "."),
+            Locale::DeDe => (".", ","),
+            Locale::FrFr => ("\u{202f}", ","),
+        };
+        let (int_part, frac_part) = raw.split_once('.').unwrap_or((raw, ""));
+
+        let mut grouped = String::with_capacity(raw.len() + raw.len() / 3);
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push_str(group);
+            }
+            grouped.push(c);
+        }
+
+        let sign = if negative { "-" } else { "" };
+        if frac_part.is_empty() {
+            format!("{}{}", sign, grouped)
+        } else {
+            format!("{}{}{}{}", sign, grouped, decimal, frac_part)
+        }
+    }
+
+    /// Money from integer cents. Symbol comes from the ISO currency
+    /// code (payments carry their own); placement from the locale:
+    /// "$1,234.50" / "1.234,50 €". Formatted from the integer cents,
+    /// so large amounts print exactly.
+    pub fn currency(&self, cents: i64, currency_code: &str) -> String {
+        let decimal = Money::new(cents, currency_code).to_decimal_string();
+        let amount = self.group_decimal(decimal.trim_start_matches('-'), cents < 0);
+        let symbol = match currency_code.to_ascii_uppercase().as_str() {
+            "USD" => "$".to_string(),
+            "EUR" => "€".to_string(),
+            "GBP" => "£".to_string(),
+            other => other.to_string(),
+        };
+        match self {
+            Locale::EnUs | Locale::EnGb => {
+                if symbol.chars().count() == 1 {
+                    format!("{}{}", symbol, amount)
+                } else {
+                    format!("{} {}", symbol, amount)
+                }
+            }
+            Locale::DeDe | Locale::FrFr => format!("{}\u{a0}{}", amount, symbol),
+        }
+    }
+
+    /// [`Locale::currency`] for a [`Money`].
+    pub fn money(&self, money: &Money) -> String {
+        self.currency(money.cents(), money.currency())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixture() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 9, 3, 14, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn long_date_follows_locale() {
+        let d = fixture();
+        assert_eq!(Locale::EnUs.long_date(&d), "September 03, 2025");
+        assert_eq!(Locale::EnGb.long_date(&d), "3 September 2025");
+        assert_eq!(Locale::DeDe.long_date(&d), "3. September 2025");
+        assert_eq!(Locale::FrFr.long_date(&d), "3 septembre 2025");
+    }
+
+    #[test]
+    fn short_date_and_time_follow_locale() {
+        let d = fixture();
+        assert_eq!(Locale::EnUs.short_date(&d), "Sep 03, 2025");
+        assert_eq!(Locale::DeDe.short_date(&d), "03.09.2025");
+        assert_eq!(Locale::FrFr.short_date(&d), "03/09/2025");
+        assert_eq!(Locale::EnUs.time(&d), "2:30 PM");
+        assert_eq!(Locale::EnGb.time(&d), "14:30");
+        assert_eq!(Locale::DeDe.long_date_time(&d), "3. September 2025 um 14:30");
+    }
+
+    #[test]
+    fn numbers_and_money_use_locale_separators() {
+        assert_eq!(Locale::EnUs.number(1234567.5, 2), "1,234,567.50");
+        assert_eq!(Locale::DeDe.number(1234.5, 2), "1.234,50");
+        assert_eq!(Locale::EnUs.number(-12.0, 0), "-12");
+        assert_eq!(Locale::EnUs.currency(123450, "USD"), "$1,234.50");
+        assert_eq!(Locale::DeDe.currency(123450, "eur"), "1.234,50\u{a0}€");
+        assert_eq!(Locale::EnGb.currency(500, "CHF"), "CHF 5.00");
+        assert_eq!(
+            Locale::EnUs.currency(9_007_199_254_740_993, "USD"),
+            "$90,071,992,547,409.93"
+        );
+        assert_eq!(Locale::EnUs.currency(-5, "USD"), "$-0.05");
+    }
+
+    #[test]
+    fn resolve_prefers_valid_member_override() {
+        assert_eq!(Locale::resolve(Some("de_de"), Locale::EnUs), Locale::DeDe);
+        assert_eq!(Locale::resolve(Some(""), Locale::FrFr), Locale::FrFr);
+        assert_eq!(Locale::resolve(Some("xx-XX"), Locale::EnGb), Locale::EnGb);
+        assert_eq!(Locale::resolve(None, Locale::EnUs), Locale::EnUs);
+    }
+}
@@ -1,9 +1,12 @@
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::payment_method::BillingMode;
+use crate::domain::{payment_method::BillingMode, ParseEnumError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Member {
@@ -34,6 +37,11 @@ pub struct Member {
     /// Discord user ID (snowflake). NULL means we don't know who they
     /// are on Discord — role sync skips them.
     pub discord_id: Option<String>,
+    /// Display-locale override (BCP 47 tag). NULL = org default.
+    pub locale: Option<String>,
+    /// End of a time-limited Honorary grant; NULL for permanent grants
+    /// and for non-honorary members.
+    pub honorary_until: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -42,6 +50,86 @@ impl Member {
     pub fn email_verified(&self) -> bool {
         self.email_verified_at.is_some()
     }
+
+    /// The status the rest of the app should treat this member as
+    /// having. Under [`ExpiryMode::Derived`] the sweep leaves lapsed
+    /// members `Active` in the database, so an `Active` member whose
+    /// dues are past the grace period reads as `Expired` here. Under
+    /// [`ExpiryMode::Stored`] this is just `status`.
+    pub fn effective_status(
+        &self,
+        mode: ExpiryMode,
+        now: DateTime<Utc>,
+        grace_period_days: i64,
+    ) -> MemberStatus {
+        if mode == ExpiryMode::Derived
+            && self.status == MemberStatus::Active
+            && DuesStatus::from_member(self, now, grace_period_days) == DuesStatus::Expired
+        {
+            MemberStatus::Expired
+        } else {
+            self.status
+        }
+    }
+}
+
+/// Which unique member field a new member collided on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateMemberField {
+    Email,
+    Username,
+}
+
+impl DuplicateMemberField {
+    /// Form/JSON field name.
+    pub fn field(&self) -> &'static str {
+        match self {
+            DuplicateMemberField::Email => "email",
+            DuplicateMemberField::Username => "username",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            DuplicateMemberField::Email => "Email already in use",
+            DuplicateMemberField::Username => "Username taken",
+        }
+    }
+}
+
+/// Usernames are 2–64 characters.
+pub const USERNAME_MIN_CHARS: usize = 2;
+pub const USERNAME_MAX_CHARS: usize = 64;
+
+/// The form an email is stored and looked up in: trimmed and
+/// lowercased, so `Alice@Example.com` and `alice@example.com` are one
+/// account.
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Username policy for create, signup and import: ASCII letters,
+/// digits, `_`, `.` and `-`, starting with a letter or digit. Returns
+/// `Err(message)` describing the problem. Callers pass the trimmed
+/// value.
+pub fn validate_username(username: &str) -> Result<(), &'static str> {
+    let len = username.chars().count();
+    if len < USERNAME_MIN_CHARS {
+        return Err("Username must be at least 2 characters");
+    }
+    if len > USERNAME_MAX_CHARS {
+        return Err("Username must be at most 64 characters");
+    }
+    if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err("Username must start with a letter or number");
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        return Err("Use letters, numbers, underscores, dots and dashes only");
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, PartialEq, ToSchema)]
@@ -70,21 +158,6 @@ impl MemberStatus {
         }
     }
 
-    /// Parse from the wire/DB string. Returns `None` for unknown
-    /// values — callers should treat that as an explicit failure
-    /// (BadRequest at the boundary, AppError::Database deeper down)
-    /// rather than mapping to a default.
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s {
-            "Pending" => Some(MemberStatus::Pending),
-            "Active" => Some(MemberStatus::Active),
-            "Expired" => Some(MemberStatus::Expired),
-            "Suspended" => Some(MemberStatus::Suspended),
-            "Honorary" => Some(MemberStatus::Honorary),
-            _ => None,
-        }
-    }
-
     pub fn is_active(self) -> bool { matches!(self, MemberStatus::Active) }
     pub fn is_pending(self) -> bool { matches!(self, MemberStatus::Pending) }
     pub fn is_expired(self) -> bool { matches!(self, MemberStatus::Expired) }
@@ -92,6 +165,30 @@ impl MemberStatus {
     pub fn is_honorary(self) -> bool { matches!(self, MemberStatus::Honorary) }
 }
 
+impl fmt::Display for MemberStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parse from the wire/DB string. Unknown values are an explicit
+/// failure — callers should turn that into BadRequest at the boundary
+/// and AppError::Internal deeper down rather than mapping to a default.
+impl FromStr for MemberStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(MemberStatus::Pending),
+            "Active" => Ok(MemberStatus::Active),
+            "Expired" => Ok(MemberStatus::Expired),
+            "Suspended" => Ok(MemberStatus::Suspended),
+            "Honorary" => Ok(MemberStatus::Honorary),
+            _ => Err(ParseEnumError::new("member status", s)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod member_status_predicate_tests {
     use super::MemberStatus;
@@ -148,6 +245,9 @@ pub struct MemberProfile {
     pub bio: Option<String>,
     pub skills: Vec<String>,
     pub show_in_directory: bool,
+    /// Show the member's email to other members in the directory.
+    /// Only meaningful alongside `show_in_directory`.
+    pub show_contact: bool,
     pub blog_url: Option<String>,
     pub github_username: Option<String>,
     pub discord_id: Option<String>,
@@ -184,4 +284,435 @@ pub struct UpdateMemberRequest {
     pub expires_at: Option<DateTime<Utc>>,
     pub bypass_dues: Option<bool>,
     pub notes: Option<String>,
-}
\ No newline at end of file
+}
+
+/// Which date an admin "extend dues by N months" counts from. The club
+/// default lives in `billing.dues_extension_base`; the admin member
+/// page can pick a different one for a single extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DuesExtensionBase {
+    /// Later of now and the current expiry: current members stack,
+    /// lapsed members restart from today.
+    #[default]
+    LaterOfNowOrExpiry,
+    /// Always today. A current member's unused time is dropped.
+    FromNow,
+    /// Always the current expiry, even if it's in the past — a lapsed
+    /// member is back-billed for the gap. Never-paid members use now.
+    FromExpiry,
+}
+
+impl DuesExtensionBase {
+    pub const ALL: [DuesExtensionBase; 3] = [
+        DuesExtensionBase::LaterOfNowOrExpiry,
+        DuesExtensionBase::FromNow,
+        DuesExtensionBase::FromExpiry,
+    ];
+
+    /// Value stored in `billing.dues_extension_base` and posted by the
+    /// admin form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuesExtensionBase::LaterOfNowOrExpiry => "later",
+            DuesExtensionBase::FromNow => "now",
+            DuesExtensionBase::FromExpiry => "expiry",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|b| b.as_str() == s.trim())
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuesExtensionBase::LaterOfNowOrExpiry => "Later of today or current expiry",
+            DuesExtensionBase::FromNow => "From today",
+            DuesExtensionBase::FromExpiry => "From current expiry",
+        }
+    }
+
+    /// The date the extension is added to.
+    pub fn base_date(
+        &self,
+        dues_paid_until: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        match self {
+            DuesExtensionBase::LaterOfNowOrExpiry => {
+                dues_paid_until.filter(|d| *d > now).unwrap_or(now)
+            }
+            DuesExtensionBase::FromNow => now,
+            DuesExtensionBase::FromExpiry => dues_paid_until.unwrap_or(now),
+        }
+    }
+}
+
+/// What the expiration sweep does to a member whose dues have lapsed
+/// past the grace period. Stored in `membership.expiry_mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ExpiryMode {
+    /// Flip `status` to `Expired` in the database.
+    #[default]
+    Stored,
+    /// Leave `status` as `Active` and derive `Expired` on read (see
+    /// [`Member::effective_status`]), so the record still says the
+    /// member was active when their dues ran out.
+    Derived,
+}
+
+impl ExpiryMode {
+    pub const ALL: [ExpiryMode; 2] = [ExpiryMode::Stored, ExpiryMode::Derived];
+
+    /// Value stored in `membership.expiry_mode`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiryMode::Stored => "stored",
+            ExpiryMode::Derived => "derived",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == s.trim())
+    }
+}
+
+/// Where a member stands on dues, combining `dues_paid_until`, the
+/// org's grace period and the status of their latest dues payment.
+/// Membership status is a separate axis: an `Active` member can still
+/// be `PaymentFailed` here, and the UI shows both side by side.
+///
+/// Every view that shows a member's dues derives it from here so the
+/// dashboard, profile and admin pages can't disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuesStatus {
+    /// Paid through a date in the future.
+    Current,
+    /// Lapsed, but within `membership.grace_period_days` — the
+    /// expiration sweep hasn't come for them yet.
+    InGrace,
+    /// `bypass_dues` or Honorary — dues don't apply.
+    Exempt,
+    /// Dues lapsed (or never paid) and a payment is in flight.
+    PaymentPending,
+    /// Dues lapsed (or never paid) and the latest payment failed.
+    PaymentFailed,
+    /// Paid once, lapsed past the grace period, nothing in flight.
+    Expired,
+    /// Never paid, nothing in flight.
+    Unpaid,
+}
+
+impl DuesStatus {
+    /// Dues standing from the member row alone. The grace window ends
+    /// on the same calendar day the expiration sweep would expire the
+    /// member: still `InGrace` on day `grace_period_days`, `Expired`
+    /// the day after.
+    pub fn from_member(member: &Member, now: DateTime<Utc>, grace_period_days: i64) -> Self {
+        if member.bypass_dues || member.status.is_honorary() {
+            return DuesStatus::Exempt;
+        }
+        let Some(due) = member.dues_paid_until else {
+            return DuesStatus::Unpaid;
+        };
+        let grace_ends = (due + chrono::Duration::days(grace_period_days)).date_naive();
+        if due > now {
+            DuesStatus::Current
+        } else if now.date_naive() <= grace_ends {
+            DuesStatus::InGrace
+        } else {
+            DuesStatus::Expired
+        }
+    }
+
+    /// `from_member`, refined by the member's most recent dues payment
+    /// (`PaymentRepository::latest_payment`). The payment only matters
+    /// once the paid-through date has lapsed — a renewal that's pending
+    /// or failed while dues are still current doesn't change anything
+    /// yet.
+    pub fn compute(
+        member: &Member,
+        latest_payment: Option<&crate::domain::Payment>,
+        now: DateTime<Utc>,
+        grace_period_days: i64,
+    ) -> Self {
+        use crate::domain::PaymentStatus;
+
+        let status = Self::from_member(member, now, grace_period_days);
+        if !status.is_lapsed() {
+            return status;
+        }
+        match latest_payment.map(|p| &p.status) {
+            Some(PaymentStatus::Pending) => DuesStatus::PaymentPending,
+            Some(PaymentStatus::Failed) => DuesStatus::PaymentFailed,
+            _ => status,
+        }
+    }
+
+    /// Dues are owed: lapsed (in grace or past it) or never paid.
+    /// These are the states the latest payment can refine and where a
+    /// view should prompt the member to pay.
+    pub fn is_lapsed(&self) -> bool {
+        !matches!(self, DuesStatus::Current | DuesStatus::Exempt)
+    }
+
+    /// Stable key for templates and the portal dues-status pill.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuesStatus::Current => "current",
+            DuesStatus::InGrace => "in_grace",
+            DuesStatus::Exempt => "exempt",
+            DuesStatus::PaymentPending => "payment_pending",
+            DuesStatus::PaymentFailed => "payment_failed",
+            DuesStatus::Expired => "expired",
+            DuesStatus::Unpaid => "unpaid",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuesStatus::Current => "Current",
+            DuesStatus::InGrace => "In grace period",
+            DuesStatus::Exempt => "Exempt",
+            DuesStatus::PaymentPending => "Payment pending",
+            DuesStatus::PaymentFailed => "Payment failed",
+            DuesStatus::Expired => "Expired",
+            DuesStatus::Unpaid => "Unpaid",
+        }
+    }
+}
+
+#[cfg(test)]
+mod dues_status_tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::domain::{Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus};
+
+    fn member(status: MemberStatus, dues_paid_until: Option<DateTime<Utc>>) -> Member {
+        let now = Utc::now();
+        Member {
+            id: Uuid::new_v4(),
+            email: "m@example.com".to_string(),
+            username: "m".to_string(),
+            full_name: "M".to_string(),
+            status,
+            membership_type_id: Uuid::new_v4(),
+            joined_at: now,
+            expires_at: None,
+            dues_paid_until,
+            bypass_dues: false,
+            is_admin: false,
+            notes: None,
+            stripe_customer_id: None,
+            stripe_subscription_id: None,
+            billing_mode: BillingMode::Manual,
+            email_verified_at: None,
+            dues_reminder_sent_at: None,
+            discord_id: None,
+            locale: None,
+            honorary_until: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn payment(member_id: Uuid, status: PaymentStatus) -> Payment {
+        let now = Utc::now();
+        Payment {
+            id: Uuid::new_v4(),
+            payer: Payer::Member(member_id),
+            amount_cents: 5000,
+            currency: "USD".to_string(),
+            status,
+            payment_method: PaymentMethod::Stripe,
+            kind: PaymentKind::Membership,
+            external_id: None,
+            description: "Dues".to_string(),
+            paid_at: None,
+            refunded_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn active_with_lapsed_dues_and_pending_payment() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now - Duration::days(3)));
+        let p = payment(m.id, PaymentStatus::Pending);
+        assert_eq!(DuesStatus::compute(&m, Some(&p), now, 0), DuesStatus::PaymentPending);
+    }
+
+    #[test]
+    fn active_with_lapsed_dues_and_failed_payment() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now - Duration::days(3)));
+        let p = payment(m.id, PaymentStatus::Failed);
+        assert_eq!(DuesStatus::compute(&m, Some(&p), now, 0), DuesStatus::PaymentFailed);
+    }
+
+    #[test]
+    fn never_paid_with_pending_first_payment() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Pending, None);
+        let p = payment(m.id, PaymentStatus::Pending);
+        assert_eq!(DuesStatus::compute(&m, Some(&p), now, 0), DuesStatus::PaymentPending);
+        assert_eq!(DuesStatus::compute(&m, None, now, 0), DuesStatus::Unpaid);
+    }
+
+    #[test]
+    fn current_dues_ignore_pending_or_failed_renewal() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now + Duration::days(10)));
+        let failed = payment(m.id, PaymentStatus::Failed);
+        let pending = payment(m.id, PaymentStatus::Pending);
+        assert_eq!(DuesStatus::compute(&m, Some(&failed), now, 0), DuesStatus::Current);
+        assert_eq!(DuesStatus::compute(&m, Some(&pending), now, 0), DuesStatus::Current);
+    }
+
+    #[test]
+    fn lapsed_with_completed_payment_is_expired() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now - Duration::days(3)));
+        let p = payment(m.id, PaymentStatus::Completed);
+        assert_eq!(DuesStatus::compute(&m, Some(&p), now, 0), DuesStatus::Expired);
+    }
+
+    #[test]
+    fn bypass_and_honorary_are_exempt() {
+        let now = Utc::now();
+        let mut m = member(MemberStatus::Active, None);
+        m.bypass_dues = true;
+        assert_eq!(DuesStatus::compute(&m, None, now, 0), DuesStatus::Exempt);
+        let h = member(MemberStatus::Honorary, None);
+        let p = payment(h.id, PaymentStatus::Failed);
+        assert_eq!(DuesStatus::compute(&h, Some(&p), now, 0), DuesStatus::Exempt);
+    }
+
+    #[test]
+    fn derived_expiry_mode_reads_lapsed_active_as_expired() {
+        let now = Utc::now();
+        let lapsed = member(MemberStatus::Active, Some(now - Duration::days(40)));
+        let in_grace = member(MemberStatus::Active, Some(now - Duration::days(10)));
+        let suspended = member(MemberStatus::Suspended, Some(now - Duration::days(40)));
+        assert_eq!(
+            lapsed.effective_status(ExpiryMode::Derived, now, 30),
+            MemberStatus::Expired
+        );
+        assert_eq!(
+            lapsed.effective_status(ExpiryMode::Stored, now, 30),
+            MemberStatus::Active
+        );
+        assert_eq!(
+            in_grace.effective_status(ExpiryMode::Derived, now, 30),
+            MemberStatus::Active
+        );
+        assert_eq!(
+            suspended.effective_status(ExpiryMode::Derived, now, 30),
+            MemberStatus::Suspended
+        );
+    }
+
+    // Boundaries of `from_member`, with a 30-day grace period.
+
+    #[test]
+    fn future_paid_through_is_current() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now + Duration::seconds(1)));
+        assert_eq!(DuesStatus::from_member(&m, now, 30), DuesStatus::Current);
+    }
+
+    #[test]
+    fn lapse_moment_starts_grace() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now));
+        assert_eq!(DuesStatus::from_member(&m, now, 30), DuesStatus::InGrace);
+    }
+
+    #[test]
+    fn last_day_of_grace_is_still_in_grace() {
+        let now = Utc.with_ymd_and_hms(2026, 4, 30, 23, 0, 0).unwrap();
+        let due = Utc.with_ymd_and_hms(2026, 3, 31, 1, 0, 0).unwrap();
+        let m = member(MemberStatus::Active, Some(due));
+        assert_eq!(DuesStatus::from_member(&m, now, 30), DuesStatus::InGrace);
+    }
+
+    #[test]
+    fn day_after_grace_is_expired() {
+        let now = Utc.with_ymd_and_hms(2026, 5, 1, 0, 0, 0).unwrap();
+        let due = Utc.with_ymd_and_hms(2026, 3, 31, 23, 0, 0).unwrap();
+        let m = member(MemberStatus::Active, Some(due));
+        assert_eq!(DuesStatus::from_member(&m, now, 30), DuesStatus::Expired);
+    }
+
+    #[test]
+    fn zero_grace_expires_the_day_after_lapse() {
+        let due = Utc.with_ymd_and_hms(2026, 4, 1, 10, 0, 0).unwrap();
+        let m = member(MemberStatus::Active, Some(due));
+        let same_day = Utc.with_ymd_and_hms(2026, 4, 1, 23, 0, 0).unwrap();
+        assert_eq!(DuesStatus::from_member(&m, same_day, 0), DuesStatus::InGrace);
+        let next_day = Utc.with_ymd_and_hms(2026, 4, 2, 0, 0, 0).unwrap();
+        assert_eq!(DuesStatus::from_member(&m, next_day, 0), DuesStatus::Expired);
+    }
+
+    #[test]
+    fn never_paid_is_unpaid() {
+        let m = member(MemberStatus::Active, None);
+        assert_eq!(DuesStatus::from_member(&m, Utc::now(), 30), DuesStatus::Unpaid);
+    }
+
+    #[test]
+    fn exempt_wins_over_lapsed_dues() {
+        let now = Utc::now();
+        let mut m = member(MemberStatus::Active, Some(now - Duration::days(365)));
+        m.bypass_dues = true;
+        assert_eq!(DuesStatus::from_member(&m, now, 30), DuesStatus::Exempt);
+        let h = member(MemberStatus::Honorary, Some(now - Duration::days(365)));
+        assert_eq!(DuesStatus::from_member(&h, now, 30), DuesStatus::Exempt);
+    }
+
+    #[test]
+    fn payment_refines_grace_but_not_current() {
+        let now = Utc::now();
+        let m = member(MemberStatus::Active, Some(now - Duration::days(3)));
+        let failed = payment(m.id, PaymentStatus::Failed);
+        assert_eq!(DuesStatus::compute(&m, None, now, 30), DuesStatus::InGrace);
+        assert_eq!(DuesStatus::compute(&m, Some(&failed), now, 30), DuesStatus::PaymentFailed);
+    }
+
+    #[test]
+    fn lapsed_states() {
+        assert!(!DuesStatus::Current.is_lapsed());
+        assert!(!DuesStatus::Exempt.is_lapsed());
+        for s in [
+            DuesStatus::InGrace,
+            DuesStatus::Expired,
+            DuesStatus::Unpaid,
+            DuesStatus::PaymentPending,
+            DuesStatus::PaymentFailed,
+        ] {
+            assert!(s.is_lapsed(), "{:?}", s);
+        }
+    }
+}
+
+#[cfg(test)]
+mod identity_normalization_tests {
+    use super::{normalize_email, validate_username};
+
+    #[test]
+    fn emails_are_trimmed_and_lowercased() {
+        assert_eq!(normalize_email("  Alice@Example.COM "), "alice@example.com");
+    }
+
+    #[test]
+    fn username_policy() {
+        assert!(validate_username("river_song").is_ok());
+        assert!(validate_username("jane.doe-2").is_ok());
+        assert!(validate_username("a").is_err());
+        assert!(validate_username(&"a".repeat(65)).is_err());
+        assert!(validate_username("_leading").is_err());
+        assert!(validate_username("has space").is_err());
+        assert!(validate_username("émile").is_err());
+    }
+}
@@ -1,21 +1,46 @@
+pub mod enum_parse;
 pub mod member;
+pub mod locale;
 pub mod event;
 pub mod recurrence;
 pub mod announcement;
+pub mod money;
 pub mod payment;
 pub mod payment_method;
 pub mod scheduled_payment;
 pub mod donation;
 pub mod settings;
+pub mod signup_field;
 pub mod configurable_types;
+pub mod admin_notification;
+pub mod celebration;
+pub mod contact;
+pub mod landing_page;
+pub mod dues_reminder;
+pub mod list_defaults;
+pub mod html_policy;
 
+pub use enum_parse::ParseEnumError;
 pub use member::*;
+pub use locale::Locale;
 pub use event::*;
 pub use recurrence::{Recurrence, WeekdayCode, generate_occurrences};
 pub use announcement::*;
+pub use money::{Money, ParseMoneyError, DEFAULT_CURRENCY};
 pub use payment::*;
 pub use payment_method::*;
 pub use scheduled_payment::*;
 pub use donation::*;
 pub use settings::*;
-pub use configurable_types::*;
\ No newline at end of file
+pub use signup_field::*;
+pub use configurable_types::*;
+pub use admin_notification::*;
+pub use celebration::*;
+pub use contact::{ContactCategory, ContactSubmission};
+pub use landing_page::LandingPage;
+pub use dues_reminder::{
+    current_dues_reminder_step, parse_dues_reminder_sequence, DuesReminderStep, DuesReminderTone,
+    LAPSED_STEP_WINDOW_DAYS,
+};
+pub use list_defaults::ListDefaultSetting;
+pub use html_policy::HtmlPolicy;
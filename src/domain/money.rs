@@ -0,0 +1,180 @@
+//! An amount of money: integer cents plus the ISO currency code.
+//!
+//! Every amount in Coterie is stored and sent to Stripe as i64 minor
+//! units. `Money` is the one place that converts between that and the
+//! human "1234.50" form, using integer arithmetic only, so a value
+//! never passes through an `f64` (or a narrower integer) on its way to
+//! a form, a receipt or a CSV.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Currency for everything Coterie charges today. Stripe calls are
+/// made in USD; stored rows carry their own code.
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Money {
+    cents: i64,
+    currency: String,
+}
+
+/// Why a dollar string didn't parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMoneyError {
+    /// Empty, non-numeric, or more than two decimal places.
+    Invalid,
+    /// Parsed, but the cents don't fit in an i64.
+    Overflow,
+}
+
+impl fmt::Display for ParseMoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoneyError::Invalid => write!(f, "Invalid amount"),
+            ParseMoneyError::Overflow => write!(f, "Amount is too large"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoneyError {}
+
+impl Money {
+    pub fn new(cents: i64, currency: &str) -> Self {
+        Self {
+            cents,
+            currency: currency.to_ascii_uppercase(),
+        }
+    }
+
+    pub fn usd(cents: i64) -> Self {
+        Self::new(cents, DEFAULT_CURRENCY)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.cents
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// "100", "100.00", "100.5", "-3.25" → 10000, 10000, 10050, -325.
+    /// Refuses more than two decimal places rather than rounding, and
+    /// any value whose cents overflow an i64.
+    pub fn parse_dollars(s: &str, currency: &str) -> Result<Self, ParseMoneyError> {
+        let s = s.trim();
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (whole, frac) = digits.split_once('.').unwrap_or((digits, ""));
+        let all_digits = |p: &str| p.chars().all(|c| c.is_ascii_digit());
+        if whole.is_empty() || frac.len() > 2 || !all_digits(whole) || !all_digits(frac) {
+            return Err(ParseMoneyError::Invalid);
+        }
+        let whole: i64 = whole.parse().map_err(|_| ParseMoneyError::Overflow)?;
+        let frac: i64 = format!("{:0<2}", frac)
+            .parse()
+            .map_err(|_| ParseMoneyError::Invalid)?;
+        let cents = whole
+            .checked_mul(100)
+            .and_then(|c| c.checked_add(frac))
+            .ok_or(ParseMoneyError::Overflow)?;
+        Ok(Self::new(if negative { -cents } else { cents }, currency))
+    }
+
+    /// Locale-free decimal ("1234.50", "-0.05"): the exact inverse of
+    /// `parse_dollars`. For display to members use `Locale::money`.
+    pub fn to_decimal_string(&self) -> String {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        let abs = self.cents.unsigned_abs();
+        format!("{}{}.{:02}", sign, abs / 100, abs % 100)
+    }
+
+    /// Sum of two amounts in the same currency; `None` on a currency
+    /// mismatch or overflow.
+    pub fn checked_add(&self, other: &Money) -> Option<Money> {
+        if self.currency != other.currency {
+            return None;
+        }
+        Some(Self {
+            cents: self.cents.checked_add(other.cents)?,
+            currency: self.currency.clone(),
+        })
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_decimal_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_whole_and_two_decimals() {
+        assert_eq!(Money::parse_dollars("100", "USD").unwrap().cents(), 10000);
+        assert_eq!(Money::parse_dollars("100.5", "USD").unwrap().cents(), 10050);
+        assert_eq!(Money::parse_dollars(" 0.07 ", "USD").unwrap().cents(), 7);
+        assert_eq!(Money::parse_dollars("-3.25", "USD").unwrap().cents(), -325);
+    }
+
+    #[test]
+    fn parse_rejects_junk_and_rounding() {
+        for bad in ["", ".", "-", "1.234", "1e3", "$5", "1,000", "1.-5", "abc"] {
+            assert_eq!(
+                Money::parse_dollars(bad, "USD"),
+                Err(ParseMoneyError::Invalid),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_rejects_overflow() {
+        assert_eq!(
+            Money::parse_dollars("92233720368547758.08", "USD"),
+            Err(ParseMoneyError::Overflow)
+        );
+        assert_eq!(
+            Money::parse_dollars("99999999999999999999", "USD"),
+            Err(ParseMoneyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn decimal_string_round_trips_losslessly() {
+        for cents in [
+            0,
+            5,
+            -5,
+            100,
+            123_456,
+            2_147_483_648,
+            i64::MAX,
+            i64::MIN + 1,
+        ] {
+            let money = Money::usd(cents);
+            let parsed = Money::parse_dollars(&money.to_decimal_string(), "USD").unwrap();
+            assert_eq!(parsed, money, "{cents}");
+        }
+        assert_eq!(Money::usd(-5).to_decimal_string(), "-0.05");
+        assert_eq!(
+            Money::usd(i64::MIN).to_decimal_string(),
+            "-92233720368547758.08"
+        );
+    }
+
+    #[test]
+    fn checked_add_guards_currency_and_overflow() {
+        let usd = Money::usd(150);
+        assert_eq!(usd.checked_add(&Money::usd(50)), Some(Money::usd(200)));
+        assert_eq!(usd.checked_add(&Money::new(50, "eur")), None);
+        assert_eq!(Money::usd(i64::MAX).checked_add(&Money::usd(1)), None);
+    }
+}
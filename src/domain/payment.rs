@@ -1,7 +1,12 @@
+use std::fmt;
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::{Money, ParseEnumError};
+
 /// Hard ceiling on a single payment / donation / refund, in cents.
 /// Picked to be well above any legitimate Coterie transaction
 /// ($100k) but low enough that an unintended extra zero or a
@@ -148,6 +153,10 @@ pub struct Payment {
     pub external_id: Option<StripeRef>,
     pub description: String,
     pub paid_at: Option<DateTime<Utc>>,
+    /// When the payment flipped to `Refunded`. `None` for any other
+    /// status.
+    #[serde(default)]
+    pub refunded_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -161,6 +170,13 @@ impl Payment {
     pub fn member_id(&self) -> Option<Uuid> {
         self.payer.member_id()
     }
+
+    /// `amount_cents` and `currency` as a [`Money`]. The row keeps
+    /// the two columns flat so the JSON shape stays what API clients
+    /// already read.
+    pub fn amount(&self) -> Money {
+        Money::new(self.amount_cents, &self.currency)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
@@ -172,6 +188,39 @@ pub enum PaymentStatus {
     Refunded,
 }
 
+impl PaymentStatus {
+    /// Canonical string: the `status` column value and what filters
+    /// send.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "Pending",
+            PaymentStatus::Completed => "Completed",
+            PaymentStatus::Failed => "Failed",
+            PaymentStatus::Refunded => "Refunded",
+        }
+    }
+}
+
+impl fmt::Display for PaymentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PaymentStatus {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pending" => Ok(PaymentStatus::Pending),
+            "Completed" => Ok(PaymentStatus::Completed),
+            "Failed" => Ok(PaymentStatus::Failed),
+            "Refunded" => Ok(PaymentStatus::Refunded),
+            _ => Err(ParseEnumError::new("payment status", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq)]
 #[sqlx(type_name = "TEXT")]
 pub enum PaymentMethod {
@@ -179,3 +228,16 @@ pub enum PaymentMethod {
     Manual,
     Waived,
 }
+
+impl FromStr for PaymentMethod {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Stripe" => Ok(PaymentMethod::Stripe),
+            "Manual" => Ok(PaymentMethod::Manual),
+            "Waived" => Ok(PaymentMethod::Waived),
+            _ => Err(ParseEnumError::new("payment method", s)),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Extra fields on the public signup form, configured by admins in the
+//! `membership.signup_fields` setting as a JSON array:
+//!
+//! ```json
+//! [{"key": "github_username", "label": "GitHub username", "required": true},
+//!  {"key": "emergency_contact", "label": "Emergency contact", "required": true},
+//!  {"key": "bio", "label": "Tell us about yourself", "kind": "textarea"}]
+//! ```
+//!
+//! Keys that name a `member_profiles` column (`bio`, `blog_url`,
+//! `github_username`) are saved there; any other key is a custom field
+//! saved to `member_custom_fields`. Email, username, full name and
+//! password are always asked for and can't be configured here.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The fields every signup needs regardless of configuration.
+pub const BASELINE_SIGNUP_FIELDS: [&str; 4] = ["email", "username", "full_name", "password"];
+
+/// `member_profiles` columns a signup field can fill in.
+pub const PROFILE_SIGNUP_FIELDS: [&str; 3] = ["bio", "blog_url", "github_username"];
+
+/// Longest answer we keep for any one field.
+pub const MAX_SIGNUP_FIELD_LEN: usize = 2000;
+
+/// How the form should render the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SignupFieldKind {
+    #[default]
+    Text,
+    Textarea,
+    Url,
+}
+
+impl SignupFieldKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignupFieldKind::Text => "text",
+            SignupFieldKind::Textarea => "textarea",
+            SignupFieldKind::Url => "url",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SignupField {
+    pub key: String,
+    pub label: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub kind: SignupFieldKind,
+}
+
+impl SignupField {
+    /// Whether the answer lands on `member_profiles` rather than in
+    /// `member_custom_fields`.
+    pub fn is_profile_field(&self) -> bool {
+        PROFILE_SIGNUP_FIELDS.contains(&self.key.as_str())
+    }
+}
+
+/// Parse and check the `membership.signup_fields` setting. Keys must be
+/// lowercase `[a-z0-9_]`, unique, and not one of the baseline fields;
+/// labels must be non-empty.
+pub fn parse_signup_fields(json: &str) -> Result<Vec<SignupField>, String> {
+    if json.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let fields: Vec<SignupField> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid signup fields JSON: {}", e))?;
+
+    let mut seen = std::collections::HashSet::new();
+    for field in &fields {
+        let key_ok = !field.key.is_empty()
+            && field
+                .key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !key_ok {
+            return Err(format!(
+                "Signup field key {:?} must be lowercase letters, digits and underscores",
+                field.key
+            ));
+        }
+        if BASELINE_SIGNUP_FIELDS.contains(&field.key.as_str()) {
+            return Err(format!(
+                "Signup field {:?} is always required and can't be configured",
+                field.key
+            ));
+        }
+        if field.label.trim().is_empty() {
+            return Err(format!("Signup field {:?} needs a label", field.key));
+        }
+        if !seen.insert(field.key.as_str()) {
+            return Err(format!("Signup field {:?} is listed twice", field.key));
+        }
+    }
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fields_with_defaults() {
+        let fields = parse_signup_fields(
+            r#"[{"key": "github_username", "label": "GitHub", "required": true},
+                {"key": "pronouns", "label": "Pronouns"}]"#,
+        )
+        .unwrap();
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].required && fields[0].is_profile_field());
+        assert!(!fields[1].required && !fields[1].is_profile_field());
+        assert_eq!(fields[1].kind, SignupFieldKind::Text);
+        assert!(parse_signup_fields("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_keys_and_duplicates() {
+        for json in [
+            r#"[{"key": "Phone", "label": "Phone"}]"#,
+            r#"[{"key": "email", "label": "Email"}]"#,
+            r#"[{"key": "phone", "label": " "}]"#,
+            r#"[{"key": "phone", "label": "A"}, {"key": "phone", "label": "B"}]"#,
+            r#"{"key": "phone"}"#,
+        ] {
+            assert!(parse_signup_fields(json).is_err(), "{}", json);
+        }
+    }
+}
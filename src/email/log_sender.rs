@@ -20,16 +20,23 @@ impl LogSender {
 #[async_trait]
 impl EmailSender for LogSender {
     async fn send(&self, message: &EmailMessage) -> Result<()> {
+        let attachments: Vec<&str> = message
+            .attachments
+            .iter()
+            .map(|a| a.filename.as_str())
+            .collect();
         tracing::info!(
             "=== Email (log mode) ===\n\
              From: {} <{}>\n\
              To: {}\n\
              Subject: {}\n\
+             Attachments: {:?}\n\
              ---- Text body ----\n{}\n\
              ========================",
             self.from_name, self.from_address,
             message.to,
             message.subject,
+            attachments,
             message.text_body,
         );
         Ok(())
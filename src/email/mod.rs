@@ -19,13 +19,25 @@ pub use log_sender::LogSender;
 pub use smtp_sender::SmtpSender;
 
 /// A single email message. Both `html_body` and `text_body` are sent as
-/// a multipart/alternative so clients can choose.
+/// a multipart/alternative so clients can choose; any `attachments`
+/// ride alongside in a multipart/mixed wrapper.
 #[derive(Debug, Clone)]
 pub struct EmailMessage {
     pub to: String,
     pub subject: String,
     pub html_body: String,
     pub text_body: String,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+/// A file attached to an [`EmailMessage`], e.g. an `.ics` invite.
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    pub filename: String,
+    /// Full MIME type including parameters, e.g.
+    /// `text/calendar; charset=utf-8; method=REQUEST`.
+    pub content_type: String,
+    pub content: Vec<u8>,
 }
 
 #[async_trait]
@@ -56,5 +68,6 @@ where
         subject,
         html_body,
         text_body,
+        attachments: Vec::new(),
     })
 }
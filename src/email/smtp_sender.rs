@@ -3,7 +3,7 @@
 use async_trait::async_trait;
 use lettre::{
     AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
-    message::{MultiPart, SinglePart, header},
+    message::{Attachment, MultiPart, SinglePart, header},
     transport::smtp::authentication::Credentials,
 };
 
@@ -60,24 +60,43 @@ impl EmailSender for SmtpSender {
             .parse()
             .map_err(|e| AppError::Validation(format!("Invalid recipient address: {}", e)))?;
 
-        let email = Message::builder()
+        let body = MultiPart::alternative()
+            .singlepart(
+                SinglePart::builder()
+                    .header(header::ContentType::TEXT_PLAIN)
+                    .body(message.text_body.clone()),
+            )
+            .singlepart(
+                SinglePart::builder()
+                    .header(header::ContentType::TEXT_HTML)
+                    .body(message.html_body.clone()),
+            );
+
+        let builder = Message::builder()
             .from(from)
             .to(to)
-            .subject(&message.subject)
-            .multipart(
-                MultiPart::alternative()
-                    .singlepart(
-                        SinglePart::builder()
-                            .header(header::ContentType::TEXT_PLAIN)
-                            .body(message.text_body.clone()),
-                    )
-                    .singlepart(
-                        SinglePart::builder()
-                            .header(header::ContentType::TEXT_HTML)
-                            .body(message.html_body.clone()),
-                    ),
-            )
-            .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+            .subject(&message.subject);
+
+        let email = if message.attachments.is_empty() {
+            builder.multipart(body)
+        } else {
+            let mut mixed = MultiPart::mixed().multipart(body);
+            for attachment in &message.attachments {
+                let content_type = header::ContentType::parse(&attachment.content_type)
+                    .map_err(|e| {
+                        AppError::Internal(format!(
+                            "Invalid attachment content type {:?}: {}",
+                            attachment.content_type, e
+                        ))
+                    })?;
+                mixed = mixed.singlepart(
+                    Attachment::new(attachment.filename.clone())
+                        .body(attachment.content.clone(), content_type),
+                );
+            }
+            builder.multipart(mixed)
+        }
+        .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
 
         self.transport
             .send(email)
@@ -45,6 +45,10 @@ pub struct WelcomeHtml<'a> {
     /// If set, the welcome email includes a "join Discord" line. The
     /// admin configures this URL in Discord settings.
     pub discord_invite: Option<&'a str>,
+    /// Only on an admin resend: links for a member who hasn't verified
+    /// their email or set a password yet.
+    pub verify_url: Option<&'a str>,
+    pub set_password_url: Option<&'a str>,
 }
 
 #[derive(Template)]
@@ -54,6 +58,8 @@ pub struct WelcomeText<'a> {
     pub org_name: &'a str,
     pub portal_url: &'a str,
     pub discord_invite: Option<&'a str>,
+    pub verify_url: Option<&'a str>,
+    pub set_password_url: Option<&'a str>,
 }
 
 #[derive(Template)]
@@ -81,6 +87,47 @@ pub struct ReminderText<'a> {
     pub card_invalid: bool,
 }
 
+#[derive(Template)]
+#[template(path = "emails/dues_reminder_firm.html")]
+pub struct DuesReminderFirmHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub due_date: &'a str,
+    pub days_remaining: i64,
+    pub pay_url: &'a str,
+    pub card_invalid: bool,
+}
+
+#[derive(Template)]
+#[template(path = "emails/dues_reminder_firm.txt")]
+pub struct DuesReminderFirmText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub due_date: &'a str,
+    pub days_remaining: i64,
+    pub pay_url: &'a str,
+    pub card_invalid: bool,
+}
+
+#[derive(Template)]
+#[template(path = "emails/dues_lapsed.html")]
+pub struct DuesLapsedHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    /// The date dues ran out.
+    pub due_date: &'a str,
+    pub pay_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/dues_lapsed.txt")]
+pub struct DuesLapsedText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub due_date: &'a str,
+    pub pay_url: &'a str,
+}
+
 #[derive(Template)]
 #[template(path = "emails/renewal_notice.html")]
 pub struct RenewalNoticeHtml<'a> {
@@ -173,6 +220,31 @@ pub struct EventReminderText<'a> {
     pub event_url: &'a str,
 }
 
+// `kind`: confirmed | updated | cancelled | event_cancelled
+#[derive(Template)]
+#[template(path = "emails/event_invite.html")]
+pub struct EventInviteHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub kind: &'a str,
+    pub event_title: &'a str,
+    pub event_start: &'a str,
+    pub event_location: Option<&'a str>,
+    pub event_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/event_invite.txt")]
+pub struct EventInviteText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub kind: &'a str,
+    pub event_title: &'a str,
+    pub event_start: &'a str,
+    pub event_location: Option<&'a str>,
+    pub event_url: &'a str,
+}
+
 #[derive(Template)]
 #[template(path = "emails/admin_alert.html")]
 pub struct AdminAlertHtml<'a> {
@@ -188,3 +260,88 @@ pub struct AdminAlertText<'a> {
     pub subject: &'a str,
     pub body: &'a str,
 }
+
+#[derive(Template)]
+#[template(path = "emails/admin_notification.html")]
+pub struct AdminNotificationHtml<'a> {
+    pub org_name: &'a str,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub profile_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/admin_notification.txt")]
+pub struct AdminNotificationText<'a> {
+    pub org_name: &'a str,
+    pub subject: &'a str,
+    pub body: &'a str,
+    pub profile_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/event_proposal_decision.html")]
+pub struct EventProposalDecisionHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub event_title: &'a str,
+    pub approved: bool,
+    pub feedback: Option<&'a str>,
+    pub portal_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/event_proposal_decision.txt")]
+pub struct EventProposalDecisionText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub event_title: &'a str,
+    pub approved: bool,
+    pub feedback: Option<&'a str>,
+    pub portal_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/new_device_login.html")]
+pub struct NewDeviceLoginHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub when: &'a str,
+    pub device: &'a str,
+    pub ip_address: &'a str,
+    pub location: Option<&'a str>,
+    pub sessions_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/new_device_login.txt")]
+pub struct NewDeviceLoginText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub when: &'a str,
+    pub device: &'a str,
+    pub ip_address: &'a str,
+    pub location: Option<&'a str>,
+    pub sessions_url: &'a str,
+}
+
+// `years` is the pre-formatted anniversary ("3 years"); `None` for a
+// birthday.
+#[derive(Template)]
+#[template(path = "emails/celebration.html")]
+pub struct CelebrationHtml<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub headline: &'a str,
+    pub years: Option<&'a str>,
+    pub profile_url: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "emails/celebration.txt")]
+pub struct CelebrationText<'a> {
+    pub full_name: &'a str,
+    pub org_name: &'a str,
+    pub years: Option<&'a str>,
+    pub profile_url: &'a str,
+}
@@ -45,6 +45,17 @@ pub enum AppError {
 
     #[error("Too many requests")]
     TooManyRequests,
+
+    /// A member create hit an existing email or username. Answered as
+    /// a 409 naming the field, never as the underlying constraint text.
+    #[error("{}", .0.message())]
+    DuplicateMember(crate::domain::DuplicateMemberField),
+
+    /// An RSVP arrived after the event's deadline. Carries the deadline
+    /// so handlers can word it in the viewer's locale; answered as a
+    /// plain 400 by anything that doesn't.
+    #[error("RSVPs for this event closed at {0}")]
+    RsvpClosed(chrono::DateTime<chrono::Utc>),
 }
 
 impl IntoResponse for AppError {
@@ -81,7 +92,16 @@ impl IntoResponse for AppError {
                     "Upstream service error. Please try again or contact support.",
                 )
             }
-            AppError::TooManyRequests => (StatusCode::TOO_MANY_REQUESTS, "Too many requests. Please try again later.")
+            AppError::TooManyRequests => (StatusCode::TOO_MANY_REQUESTS, "Too many requests. Please try again later."),
+            AppError::RsvpClosed(_) => (StatusCode::BAD_REQUEST, "RSVPs for this event have closed"),
+            AppError::DuplicateMember(field) => {
+                // Same shape as the signup form's field errors.
+                let body = Json(json!({
+                    "error": field.message(),
+                    "fields": { field.field(): field.message() },
+                }));
+                return (StatusCode::CONFLICT, body).into_response();
+            }
         };
 
         let body = Json(json!({
@@ -92,3 +112,12 @@ impl IntoResponse for AppError {
     }
 }
 
+
+/// An enum string that doesn't parse is only an internal error when it
+/// came out of our own database; that's the case `?` covers. Handlers
+/// parsing request input should map it to `BadRequest` themselves.
+impl From<crate::domain::ParseEnumError> for AppError {
+    fn from(e: crate::domain::ParseEnumError) -> Self {
+        AppError::Internal(e.to_string())
+    }
+}
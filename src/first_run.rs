@@ -0,0 +1,127 @@
+//! Opt-in "seed on first run" for the server binary.
+//!
+//! With `seed.on_first_run` set, `main` calls `seed_if_empty` right
+//! after migrations so a fresh deploy comes up with the configured
+//! admin and membership types, no separate `seed` or `create_admin`
+//! run needed. Unlike the `seed` binary this never clears or
+//! overwrites anything: a database with any member in it is left
+//! alone, and membership types whose slug already exists are skipped.
+//! Test users are not created; they belong to the `seed` binary.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    auth::validate_password,
+    config::SeedConfig,
+    domain::{
+        validate_username, CreateMemberRequest, CreateMembershipTypeRequest, MemberStatus,
+        UpdateMemberRequest, MAX_PAYMENT_CENTS,
+    },
+    repository::{
+        MemberRepository, MembershipTypeRepository, SqliteMemberRepository,
+        SqliteMembershipTypeRepository,
+    },
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FirstRunOutcome {
+    /// The database was empty; `membership_types` counts the types
+    /// that were newly created (migration-seeded slugs are skipped).
+    Seeded {
+        admin_id: Uuid,
+        membership_types: usize,
+    },
+    /// Members already exist, so nothing was touched.
+    AlreadyPopulated,
+}
+
+/// Seed the admin and membership types from `config` if, and only if,
+/// the database has no members. The admin password must pass the same
+/// strength rules as the setup wizard; the built-in default doesn't,
+/// so an operator can't bring up a deploy with a guessable admin by
+/// flipping the flag alone.
+pub async fn seed_if_empty(pool: &SqlitePool, config: &SeedConfig) -> Result<FirstRunOutcome> {
+    let (populated,): (i64,) = sqlx::query_as("SELECT EXISTS(SELECT 1 FROM members)")
+        .fetch_one(pool)
+        .await
+        .context("checking for existing members")?;
+    if populated != 0 {
+        return Ok(FirstRunOutcome::AlreadyPopulated);
+    }
+
+    let admin = &config.admin;
+    validate_password(&admin.password)
+        .map_err(|e| anyhow!("seed.admin.password is too weak: {}", e))?;
+    validate_username(admin.username.trim())
+        .map_err(|e| anyhow!("seed.admin.username is invalid: {}", e))?;
+
+    let type_repo = SqliteMembershipTypeRepository::new(pool.clone());
+    let mut created_types = 0;
+    for mt in &config.membership_types {
+        if type_repo.find_by_slug(&mt.slug).await?.is_some() {
+            continue;
+        }
+        let fee_cents = mt.fee_cents;
+        if !(0..=MAX_PAYMENT_CENTS).contains(&fee_cents) {
+            return Err(anyhow!(
+                "fee_cents out of range for membership type {} (0 to {})",
+                mt.slug,
+                MAX_PAYMENT_CENTS
+            ));
+        }
+        type_repo
+            .create(None, CreateMembershipTypeRequest {
+                name: mt.name.clone(),
+                slug: Some(mt.slug.clone()),
+                description: None,
+                color: Some(mt.color.clone()),
+                icon: None,
+                fee_cents,
+                billing_period: mt.billing_frequency.clone(),
+                max_members: None,
+            })
+            .await
+            .with_context(|| format!("creating membership type {}", mt.slug))?;
+        created_types += 1;
+    }
+
+    // The admin joins the first configured type; with none configured
+    // the repository falls back to the first active one.
+    let membership_type_id = match config.membership_types.first() {
+        Some(mt) => type_repo.find_by_slug(&mt.slug).await?.map(|t| t.id),
+        None => None,
+    };
+
+    let member_repo = SqliteMemberRepository::new(pool.clone());
+    let member = member_repo
+        .create(CreateMemberRequest {
+            email: admin.email.clone(),
+            username: admin.username.clone(),
+            full_name: admin.full_name.clone(),
+            password: admin.password.clone(),
+            membership_type_id,
+            email_verified_at: Some(Utc::now()),
+            ..Default::default()
+        })
+        .await
+        .context("creating seed admin")?;
+    member_repo
+        .update(
+            member.id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                bypass_dues: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+    member_repo.set_admin(member.id, true).await?;
+
+    Ok(FirstRunOutcome::Seeded {
+        admin_id: member.id,
+        membership_types: created_types,
+    })
+}
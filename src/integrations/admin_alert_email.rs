@@ -10,9 +10,10 @@
 //!   - `org.contact_email` is empty (operator hasn't set one)
 //!   - the event isn't an AdminAlert
 //!
-//! All failures are logged but never bubble up — like every other
-//! integration handler, we don't want a notification path to fail an
-//! upstream admin action.
+//! Send failures are returned to the `IntegrationManager`, which logs
+//! them to the integration log and carries on — like every other
+//! integration, a notification path never fails an upstream admin
+//! action.
 
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -86,7 +87,12 @@ impl Integration for AdminAlertEmailIntegration {
 
         if let Err(e) = self.sender.send(&message).await {
             tracing::error!("AdminAlertEmail send to {} failed: {}", to, e);
+            return Err(e);
         }
         Ok(())
     }
+
+    fn handles(&self, event: &IntegrationEvent) -> bool {
+        matches!(event, IntegrationEvent::AdminAlert { .. })
+    }
 }
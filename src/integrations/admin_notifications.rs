@@ -0,0 +1,60 @@
+//! Routes `AdminNotification` events to the admins individually via
+//! `AdminNotificationService`. Going through the integration manager
+//! means any subsystem that can raise an `AdminAlert` can raise one of
+//! these too, and each routing attempt lands in the integration log
+//! (replayable — the service's dedupe key keeps a replay from
+//! notifying an admin who already got it).
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{
+    error::Result,
+    integrations::{Integration, IntegrationEvent},
+    service::admin_notification_service::AdminNotificationService,
+};
+
+pub struct AdminNotificationIntegration {
+    service: Arc<AdminNotificationService>,
+}
+
+impl AdminNotificationIntegration {
+    pub fn new(service: Arc<AdminNotificationService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl Integration for AdminNotificationIntegration {
+    fn name(&self) -> &str {
+        "AdminNotifications"
+    }
+
+    fn is_enabled(&self) -> bool {
+        // Always registered; `notifications.admin_events` is checked
+        // per event so admins can change it at runtime.
+        true
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: &IntegrationEvent) -> Result<()> {
+        let IntegrationEvent::AdminNotification {
+            kind,
+            key,
+            subject,
+            body,
+        } = event
+        else {
+            return Ok(());
+        };
+        self.service.notify(*kind, key, subject, body).await?;
+        Ok(())
+    }
+
+    fn handles(&self, event: &IntegrationEvent) -> bool {
+        matches!(event, IntegrationEvent::AdminNotification { .. })
+    }
+}
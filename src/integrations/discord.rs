@@ -7,8 +7,13 @@
 //!     on Discord; nothing to sync)
 //!   - Required role IDs aren't configured
 //!
-//! Failures are logged but never bubble up to the caller — a Discord
-//! outage shouldn't fail an admin's "suspend member" action.
+//! Besides the base member/expired roles, each membership type can map
+//! to a tier role (`discord.type_role_ids`), held alongside the member
+//! role while the member is in good standing.
+//!
+//! HTTP failures are returned to the `IntegrationManager`, which
+//! records them in the integration log and swallows them there — a
+//! Discord outage shouldn't fail an admin's "suspend member" action.
 
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -20,7 +25,7 @@ use crate::{
         Integration, IntegrationEvent,
         discord_client::DiscordClient,
     },
-    repository::MemberRepository,
+    repository::{MemberRepository, MembershipTypeRepository},
     service::settings_service::{DbDiscordConfig, SettingsService},
 };
 
@@ -86,8 +91,55 @@ fn build_announcement_preview(content: &str) -> String {
     }
 }
 
+/// Role changes that bring one member in line with the config. Built
+/// by `role_plan` so the add/remove decision can be tested without
+/// talking to Discord.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RolePlan {
+    add: Vec<String>,
+    remove: Vec<String>,
+}
+
+/// Roles a member with `status` and membership type `type_slug` should
+/// gain and lose. In good standing: the member role plus their type's
+/// tier role, and none of the other tier roles (that's the swap when
+/// the type changes). Otherwise: no member or tier roles, and the
+/// expired role for Expired/Suspended. Unset role IDs are skipped, and
+/// a role on both lists (two types sharing one) is only added.
+fn role_plan(cfg: &DbDiscordConfig, status: MemberStatus, type_slug: Option<&str>) -> RolePlan {
+    let tier_role = type_slug.and_then(|slug| cfg.type_role_ids.get(slug));
+    let (add, mut remove) = match status {
+        MemberStatus::Active | MemberStatus::Honorary => (
+            std::iter::once(&cfg.member_role_id).chain(tier_role).collect(),
+            vec![&cfg.expired_role_id],
+        ),
+        MemberStatus::Expired | MemberStatus::Suspended => {
+            (vec![&cfg.expired_role_id], vec![&cfg.member_role_id])
+        }
+        // Hasn't been approved yet — no Coterie-owned role at all.
+        MemberStatus::Pending => (Vec::new(), vec![&cfg.member_role_id, &cfg.expired_role_id]),
+    };
+    remove.extend(cfg.type_role_ids.values());
+
+    let mut plan = RolePlan::default();
+    for role in add {
+        if !role.is_empty() && !plan.add.contains(role) {
+            plan.add.push(role.clone());
+        }
+    }
+    for role in remove {
+        if !role.is_empty() && !plan.add.contains(role) && !plan.remove.contains(role) {
+            plan.remove.push(role.clone());
+        }
+    }
+    plan
+}
+
 pub struct DiscordIntegration {
     settings: Arc<SettingsService>,
+    /// Resolves a member's membership type to the slug that keys
+    /// `type_role_ids`.
+    membership_types: Arc<dyn MembershipTypeRepository>,
     /// Absolute Coterie base URL (from ServerConfig::base_url), used
     /// to build links in outgoing Discord messages so members can
     /// click through to events/announcements/payment pages.
@@ -95,8 +147,12 @@ pub struct DiscordIntegration {
 }
 
 impl DiscordIntegration {
-    pub fn new(settings: Arc<SettingsService>, base_url: String) -> Self {
-        Self { settings, base_url }
+    pub fn new(
+        settings: Arc<SettingsService>,
+        membership_types: Arc<dyn MembershipTypeRepository>,
+        base_url: String,
+    ) -> Self {
+        Self { settings, membership_types, base_url }
     }
 
     /// Pull the live config + a ready-to-use HTTP client. Returns
@@ -117,110 +173,127 @@ impl DiscordIntegration {
         Some((cfg, client))
     }
 
-    /// Apply roles for a member's CURRENT status. Logs and returns Ok
-    /// for missing-role / missing-discord-id cases — skipping them is
-    /// a feature, not a bug, and we don't want the admin's primary
-    /// action to error because of an integration gap.
-    async fn sync_roles(&self, member: &Member) {
+    /// Apply roles for a member's CURRENT status and type. Returns Ok for
+    /// missing-role / missing-discord-id cases — skipping them is a
+    /// feature, not a bug. Every role call is attempted even if an
+    /// earlier one fails; the first error is returned.
+    async fn sync_roles(&self, member: &Member) -> Result<()> {
         let Some((cfg, client)) = self.load().await else {
-            return;
+            return Ok(());
         };
         let Some(discord_id) = &member.discord_id else {
             tracing::debug!(
                 "Discord sync skipped for member {}: no discord_id on file",
                 member.id
             );
-            return;
+            return Ok(());
         };
         if !is_valid_snowflake(discord_id) {
             tracing::warn!(
                 "Discord sync skipped for member {}: invalid discord_id {:?}",
                 member.id, discord_id
             );
-            return;
+            return Ok(());
         }
 
-        match member.status {
-            MemberStatus::Active | MemberStatus::Honorary => {
-                if !cfg.member_role_id.is_empty() {
-                    if let Err(e) = client.add_role(&cfg.guild_id, discord_id, &cfg.member_role_id).await {
-                        tracing::error!("Discord add member role for {}: {}", member.id, e);
-                    }
-                }
-                if !cfg.expired_role_id.is_empty() {
-                    if let Err(e) = client.remove_role(&cfg.guild_id, discord_id, &cfg.expired_role_id).await {
-                        tracing::error!("Discord remove expired role for {}: {}", member.id, e);
-                    }
-                }
-            }
-            MemberStatus::Expired | MemberStatus::Suspended => {
-                if !cfg.expired_role_id.is_empty() {
-                    if let Err(e) = client.add_role(&cfg.guild_id, discord_id, &cfg.expired_role_id).await {
-                        tracing::error!("Discord add expired role for {}: {}", member.id, e);
-                    }
-                }
-                if !cfg.member_role_id.is_empty() {
-                    if let Err(e) = client.remove_role(&cfg.guild_id, discord_id, &cfg.member_role_id).await {
-                        tracing::error!("Discord remove member role for {}: {}", member.id, e);
-                    }
-                }
-            }
-            MemberStatus::Pending => {
-                // Hasn't been approved yet — they shouldn't have ANY
-                // Coterie-owned role. Strip both. They typically aren't
-                // in the guild at all at this stage so the calls 404
-                // quietly.
-                if !cfg.member_role_id.is_empty() {
-                    let _ = client.remove_role(&cfg.guild_id, discord_id, &cfg.member_role_id).await;
-                }
-                if !cfg.expired_role_id.is_empty() {
-                    let _ = client.remove_role(&cfg.guild_id, discord_id, &cfg.expired_role_id).await;
+        let type_slug = self.type_slug(&cfg, member).await?;
+        let plan = role_plan(&cfg, member.status, type_slug.as_deref());
+
+        // Pending members typically aren't in the guild yet, so their
+        // removals 404 quietly and any other failure isn't worth
+        // reporting.
+        let report = !matches!(member.status, MemberStatus::Pending);
+        let mut first_err = None;
+        let mut note = |what: &str, role_id: &str, result: Result<()>| {
+            if let Err(e) = result {
+                if report {
+                    tracing::error!("Discord {} {} for {}: {}", what, role_id, member.id, e);
+                    first_err.get_or_insert(e);
                 }
             }
+        };
+
+        for role_id in &plan.add {
+            note("add role", role_id, client.add_role(&cfg.guild_id, discord_id, role_id).await);
+        }
+        for role_id in &plan.remove {
+            note("remove role", role_id, client.remove_role(&cfg.guild_id, discord_id, role_id).await);
         }
+
+        first_err.map_or(Ok(()), Err)
+    }
+
+    /// Slug of the member's membership type, looked up only when some
+    /// type has a tier role configured.
+    async fn type_slug(&self, cfg: &DbDiscordConfig, member: &Member) -> Result<Option<String>> {
+        if cfg.type_role_ids.is_empty() {
+            return Ok(None);
+        }
+        let membership_type = self.membership_types.find_by_id(member.membership_type_id).await?;
+        Ok(membership_type.map(|t| t.slug))
     }
 
     /// Post a message to a configured channel. No-op (with a debug
     /// trace) when the channel ID is empty — the operator just hasn't
     /// set up that channel.
-    async fn post_to_channel(&self, channel_id: &str, content: &str) {
+    async fn post_to_channel(&self, channel_id: &str, content: &str) -> Result<()> {
         let Some((_, client)) = self.load().await else {
-            return;
+            return Ok(());
         };
         if channel_id.is_empty() {
-            return;
+            return Ok(());
         }
-        if let Err(e) = client.send_message(channel_id, content).await {
+        client.send_message(channel_id, content).await.map_err(|e| {
             tracing::error!(
                 "Discord send_message to channel {}: {}",
                 channel_id, e
             );
-        }
+            e
+        })
     }
 
     /// Walk every member with a discord_id and re-apply roles from
-    /// scratch based on their current status. Idempotent — Discord's
-    /// PUT-role endpoint is fine with re-adding a role they already
-    /// have, and remove returns 404 (treated as success) if it's
-    /// already gone.
+    /// scratch based on their current effective status. Idempotent —
+    /// Discord's PUT-role endpoint is fine with re-adding a role they
+    /// already have, and remove returns 404 (treated as success) if
+    /// it's already gone.
     ///
     /// Caller is responsible for not running this concurrently with
     /// itself; a single 500-member club takes ~few seconds at the
     /// rate Discord allows. Failures per-member are logged and don't
     /// abort the rest of the sweep.
     pub async fn reconcile_all(&self, members: Arc<dyn MemberRepository>) -> ReconcileSummary {
-        let mut summary = ReconcileSummary::default();
         if self.load().await.is_none() {
-            return summary;
+            return ReconcileSummary::default();
+        }
+        let (targets, mut summary) = self.reconcile_targets(members.as_ref()).await;
+        for m in &targets {
+            // Errors are already traced inside sync_roles; the sweep
+            // keeps going regardless.
+            let _ = self.sync_roles(m).await;
+            summary.processed += 1;
         }
+        summary
+    }
+
+    /// The members `reconcile_all` syncs, each with `status` replaced
+    /// by its effective status — under derived expiry a lapsed member
+    /// is still `Active` in the database but must lose the member
+    /// role. The summary counts the members skipped on the way.
+    async fn reconcile_targets(
+        &self,
+        members: &dyn MemberRepository,
+    ) -> (Vec<Member>, ReconcileSummary) {
+        let mut summary = ReconcileSummary::default();
         let all = match members.list_with_discord_id().await {
             Ok(v) => v,
             Err(e) => {
                 tracing::error!("Discord reconcile: couldn't list members: {}", e);
-                return summary;
+                return (Vec::new(), summary);
             }
         };
-        for m in &all {
+        let mut targets = Vec::with_capacity(all.len());
+        for m in all {
             // Validate before counting as "processed" — we want the
             // summary to reflect actual sync attempts.
             let Some(id) = &m.discord_id else { continue };
@@ -232,13 +305,13 @@ impl DiscordIntegration {
                 summary.skipped_pending += 1;
                 continue;
             }
-            self.sync_roles(m).await;
-            summary.processed += 1;
+            targets.push(self.settings.with_effective_status(m).await);
         }
-        summary
+        (targets, summary)
     }
 
-    /// Strip any Coterie-managed role. Used on member deletion.
+    /// Strip any Coterie-managed role, tier roles included. Used on
+    /// member deletion.
     async fn clear_roles(&self, member: &Member) {
         let Some((cfg, client)) = self.load().await else {
             return;
@@ -249,11 +322,8 @@ impl DiscordIntegration {
         if !is_valid_snowflake(discord_id) {
             return;
         }
-        if !cfg.member_role_id.is_empty() {
-            let _ = client.remove_role(&cfg.guild_id, discord_id, &cfg.member_role_id).await;
-        }
-        if !cfg.expired_role_id.is_empty() {
-            let _ = client.remove_role(&cfg.guild_id, discord_id, &cfg.expired_role_id).await;
+        for role_id in role_plan(&cfg, MemberStatus::Pending, None).remove {
+            let _ = client.remove_role(&cfg.guild_id, discord_id, &role_id).await;
         }
     }
 }
@@ -287,28 +357,24 @@ impl Integration for DiscordIntegration {
 
     async fn handle_event(&self, event: &IntegrationEvent) -> Result<()> {
         match event {
-            IntegrationEvent::MemberActivated(m) => {
-                self.sync_roles(m).await;
-                Ok(())
-            }
-            IntegrationEvent::MemberExpired(m) => {
-                self.sync_roles(m).await;
-                Ok(())
-            }
+            IntegrationEvent::MemberActivated(m) => self.sync_roles(m).await,
+            IntegrationEvent::MemberExpired(m) => self.sync_roles(m).await,
             IntegrationEvent::MemberUpdated { old, new } => {
-                // Two reasons we'd need to act:
+                // Three reasons we'd need to act:
                 //   1. Status changed → roles need to follow
                 //   2. discord_id changed → strip old, apply new
+                //   3. Membership type changed → swap tier roles
                 let status_changed = old.status != new.status;
                 let id_changed = old.discord_id != new.discord_id;
+                let type_changed = old.membership_type_id != new.membership_type_id;
                 if id_changed {
                     // Make sure roles aren't lingering on the old user.
                     if old.discord_id.is_some() {
                         self.clear_roles(old).await;
                     }
                 }
-                if status_changed || id_changed {
-                    self.sync_roles(new).await;
+                if status_changed || id_changed || type_changed {
+                    self.sync_roles(new).await?;
                 }
                 Ok(())
             }
@@ -332,7 +398,10 @@ impl Integration for DiscordIntegration {
                     crate::domain::EventVisibility::MembersOnly => "**[Members only]** ",
                     _ => "",
                 };
-                let when = event.start_time.format("%a %b %d, %Y at %H:%M UTC");
+                let when = format!(
+                    "{} UTC",
+                    self.settings.org_locale().await.long_date_time(&event.start_time)
+                );
                 let location = event.location.as_deref().unwrap_or("(no location set)");
                 let link = format!(
                     "{}/portal/events/{}",
@@ -343,8 +412,7 @@ impl Integration for DiscordIntegration {
                     "{}📅 **New event: {}**\n{}\nWhere: {}\nDetails: {}",
                     prefix, event.title, when, location, link,
                 );
-                self.post_to_channel(channel, &content).await;
-                Ok(())
+                self.post_to_channel(channel, &content).await
             }
 
             IntegrationEvent::AnnouncementPublished(announcement) => {
@@ -374,8 +442,7 @@ impl Integration for DiscordIntegration {
                     "{}📣 **{}**\n{}\n\n{}",
                     visibility_tag, announcement.title, preview, link,
                 );
-                self.post_to_channel(&cfg.announcements_channel_id, &content).await;
-                Ok(())
+                self.post_to_channel(&cfg.announcements_channel_id, &content).await
             }
 
             IntegrationEvent::AdminAlert { subject, body } => {
@@ -386,11 +453,63 @@ impl Integration for DiscordIntegration {
                     return Ok(());
                 }
                 let content = format!("⚠️ **{}**\n{}", subject, body);
-                self.post_to_channel(&cfg.admin_alerts_channel_id, &content).await;
-                Ok(())
+                self.post_to_channel(&cfg.admin_alerts_channel_id, &content).await
+            }
+
+            // Delivered per admin by AdminNotificationIntegration,
+            // which DMs through `send_direct_message` itself.
+            IntegrationEvent::AdminNotification { .. } => Ok(()),
+
+            IntegrationEvent::MemberCelebration(celebration) => {
+                let Some((cfg, _)) = self.load().await else {
+                    return Ok(());
+                };
+                // Shout-outs get their own channel when one is set,
+                // otherwise they go with the announcements.
+                let channel = self
+                    .settings
+                    .get_value("celebrations.discord_channel_id")
+                    .await
+                    .unwrap_or_default();
+                let channel = if channel.trim().is_empty() {
+                    cfg.announcements_channel_id.clone()
+                } else {
+                    channel.trim().to_string()
+                };
+                if channel.is_empty() {
+                    return Ok(());
+                }
+                // Mention them when we know who they are on Discord.
+                let who = match &celebration.discord_id {
+                    Some(id) if is_valid_snowflake(id) => format!("<@{}>", id),
+                    _ => format!("**{}**", celebration.full_name),
+                };
+                let content = match celebration.years_label() {
+                    Some(years) => format!(
+                        "🎉 Happy membership anniversary, {}! {} with us today.",
+                        who, years,
+                    ),
+                    None => format!("🎂 Happy birthday, {}!", who),
+                };
+                self.post_to_channel(&channel, &content).await
             }
+
+            // Payment events: nothing is posted for these yet (see
+            // `handles`).
+            _ => Ok(()),
         }
     }
+
+    /// Payment events don't drive roles or posts, so they're left
+    /// out of the integration log rather than logged as no-ops.
+    fn handles(&self, event: &IntegrationEvent) -> bool {
+        !matches!(
+            event,
+            IntegrationEvent::PaymentCompleted(_)
+                | IntegrationEvent::PaymentFailed(_)
+                | IntegrationEvent::PaymentRefunded(_)
+        )
+    }
 }
 
 #[cfg(test)]
@@ -415,6 +534,78 @@ mod snowflake_tests {
     }
 }
 
+#[cfg(test)]
+mod role_plan_tests {
+    use super::*;
+
+    const MEMBER: &str = "100000000000000001";
+    const EXPIRED: &str = "100000000000000002";
+    const REGULAR: &str = "100000000000000003";
+    const CORPORATE: &str = "100000000000000004";
+
+    fn cfg() -> DbDiscordConfig {
+        DbDiscordConfig {
+            member_role_id: MEMBER.into(),
+            expired_role_id: EXPIRED.into(),
+            type_role_ids: [("regular", REGULAR), ("corporate", CORPORATE)]
+                .into_iter()
+                .map(|(slug, role)| (slug.to_string(), role.to_string()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn plan(add: &[&str], remove: &[&str]) -> RolePlan {
+        RolePlan {
+            add: add.iter().map(|r| r.to_string()).collect(),
+            remove: remove.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn type_change_swaps_tier_roles() {
+        assert_eq!(
+            role_plan(&cfg(), MemberStatus::Active, Some("regular")),
+            plan(&[MEMBER, REGULAR], &[EXPIRED, CORPORATE]),
+        );
+        assert_eq!(
+            role_plan(&cfg(), MemberStatus::Active, Some("corporate")),
+            plan(&[MEMBER, CORPORATE], &[EXPIRED, REGULAR]),
+        );
+    }
+
+    #[test]
+    fn unmapped_type_gets_only_the_base_role() {
+        assert_eq!(
+            role_plan(&cfg(), MemberStatus::Honorary, Some("student")),
+            plan(&[MEMBER], &[EXPIRED, CORPORATE, REGULAR]),
+        );
+    }
+
+    #[test]
+    fn expiry_strips_tier_roles() {
+        assert_eq!(
+            role_plan(&cfg(), MemberStatus::Expired, Some("corporate")),
+            plan(&[EXPIRED], &[MEMBER, CORPORATE, REGULAR]),
+        );
+        assert_eq!(
+            role_plan(&cfg(), MemberStatus::Pending, Some("corporate")),
+            plan(&[], &[MEMBER, EXPIRED, CORPORATE, REGULAR]),
+        );
+    }
+
+    #[test]
+    fn shared_or_unset_roles_are_not_removed() {
+        let mut cfg = cfg();
+        cfg.expired_role_id.clear();
+        cfg.type_role_ids.insert("family".into(), CORPORATE.into());
+        assert_eq!(
+            role_plan(&cfg, MemberStatus::Active, Some("family")),
+            plan(&[MEMBER, CORPORATE], &[REGULAR]),
+        );
+    }
+}
+
 #[cfg(test)]
 mod preview_tests {
     use super::build_announcement_preview;
@@ -466,3 +657,89 @@ mod preview_tests {
         assert_eq!(s, "Real content");
     }
 }
+
+#[cfg(test)]
+mod reconcile_tests {
+    use super::*;
+    use crate::{
+        auth::SecretCrypto,
+        domain::{CreateMemberRequest, UpdateMemberRequest},
+        repository::{SqliteMemberRepository, SqliteMembershipTypeRepository},
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::SqlitePool;
+
+    async fn fresh_pool() -> SqlitePool {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect(":memory:");
+        sqlx::migrate!("./migrations").run(&pool).await.expect("migrate");
+        pool
+    }
+
+    /// An Active member linked to `discord_id` whose dues ran out
+    /// `lapsed_days` ago.
+    async fn linked_member(
+        repo: &SqliteMemberRepository,
+        discord_id: &str,
+        lapsed_days: i64,
+    ) -> Member {
+        let member = repo
+            .create(CreateMemberRequest {
+                email: format!("{}@example.com", discord_id),
+                username: format!("u{}", discord_id),
+                full_name: "Test User".to_string(),
+                password: "secure_password123".to_string(),
+                dues_paid_until: Some(Utc::now() - Duration::days(lapsed_days)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        repo.update(
+            member.id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        repo.update_discord_id(member.id, Some(discord_id)).await.unwrap();
+        member
+    }
+
+    #[tokio::test]
+    async fn derived_expiry_reconciles_lapsed_members_as_expired() {
+        let pool = fresh_pool().await;
+        sqlx::query("UPDATE app_settings SET value = 'derived' WHERE key = 'membership.expiry_mode'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let repo = SqliteMemberRepository::new(pool.clone());
+        let lapsed = linked_member(&repo, "100000000000000001", 60).await;
+        let current = linked_member(&repo, "100000000000000002", -30).await;
+        linked_member(&repo, "not-a-snowflake", -30).await;
+
+        let discord = DiscordIntegration::new(
+            Arc::new(SettingsService::new(
+                pool.clone(),
+                Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+            )),
+            Arc::new(SqliteMembershipTypeRepository::new(pool.clone())),
+            "http://test.local".to_string(),
+        );
+        let (targets, summary) = discord.reconcile_targets(&repo).await;
+
+        assert_eq!(summary.skipped_invalid_id, 1);
+        let status_of = |id| targets.iter().find(|m| m.id == id).map(|m| m.status);
+        assert_eq!(status_of(lapsed.id), Some(MemberStatus::Expired));
+        assert_eq!(status_of(current.id), Some(MemberStatus::Active));
+        // The sweep reads effective status; it never writes it back.
+        assert_eq!(
+            repo.find_by_id(lapsed.id).await.unwrap().unwrap().status,
+            MemberStatus::Active
+        );
+    }
+}
@@ -150,6 +150,32 @@ impl DiscordClient {
         check_status(&resp.status())
             .map_err(|e| AppError::External(format!("Discord {}: {}", label, e)))
     }
+
+    /// `POST /users/@me/channels` then `send_message` — a direct
+    /// message from the bot, used for per-admin notifications. The
+    /// user must share a guild with the bot.
+    pub async fn send_direct_message(&self, user_id: &str, content: &str) -> Result<()> {
+        #[derive(Deserialize)]
+        struct DmChannel {
+            id: String,
+        }
+
+        let url = format!("{}/users/@me/channels", API_BASE);
+        let body = serde_json::json!({ "recipient_id": user_id });
+        let label = format!("open_dm user={}", user_id);
+        let resp = send_with_retry(&label, || {
+            self.http.post(&url)
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .json(&body)
+        }).await?;
+        check_status(&resp.status())
+            .map_err(|e| AppError::External(format!("Discord {}: {}", label, e)))?;
+        let body = resp.text().await
+            .map_err(|e| AppError::External(format!("Discord response read failed: {}", e)))?;
+        let channel: DmChannel = serde_json::from_str(&body)
+            .map_err(|e| AppError::External(format!("Discord response parse: {} (body: {})", e, body)))?;
+        self.send_message(&channel.id, content).await
+    }
 }
 
 /// Drive a request through up to MAX_ATTEMPTS, retrying transient
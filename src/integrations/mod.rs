@@ -1,15 +1,25 @@
 use async_trait::async_trait;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
-use crate::domain::{Announcement, Event, Member};
-use crate::error::Result;
+use uuid::Uuid;
+use crate::domain::{
+    AdminNotificationKind, Announcement, Celebration, Event, Member, ParseEnumError, Payment,
+};
+use crate::error::{AppError, Result};
+use crate::service::integration_log_service::{IntegrationLogService, NewIntegrationLogEntry};
+use crate::service::integration_outbox_service::{IntegrationOutboxService, OutboxEntry};
+use crate::service::settings_service::SettingsService;
 
 pub mod admin_alert_email;
+pub mod admin_notifications;
 pub mod discord;
 pub mod discord_client;
 pub mod unifi;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IntegrationEvent {
     MemberActivated(Member),
     MemberExpired(Member),
@@ -26,6 +36,161 @@ pub enum IntegrationEvent {
     /// any subsystem can dispatch one without coordinating with the
     /// integration layer's enums.
     AdminAlert { subject: String, body: String },
+    /// Something addressed to the admins individually, routed by
+    /// `AdminNotificationService` to each admin's preferred channel.
+    /// `key` identifies the underlying occurrence (e.g.
+    /// `signup:<member id>`) so a repeat dispatch doesn't notify twice.
+    AdminNotification {
+        kind: AdminNotificationKind,
+        key: String,
+        subject: String,
+        body: String,
+    },
+    /// A member's birthday or membership anniversary, posted as a
+    /// shout-out by `CelebrationService` when Discord shout-outs are on.
+    MemberCelebration(Celebration),
+    /// A payment went through: Stripe confirmed a checkout, invoice or
+    /// charge by webhook, or an admin recorded a manual or waived
+    /// payment.
+    PaymentCompleted(Payment),
+    /// A Stripe charge for a payment was declined or errored.
+    PaymentFailed(Payment),
+    /// A payment was refunded, from the admin Refund button or Stripe's
+    /// dashboard.
+    PaymentRefunded(Payment),
+}
+
+/// The kinds of [`IntegrationEvent`], without their payloads. What an
+/// integration subscribes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationEventKind {
+    MemberActivated,
+    MemberExpired,
+    MemberUpdated,
+    EventPublished,
+    AnnouncementPublished,
+    AdminAlert,
+    AdminNotification,
+    MemberCelebration,
+    PaymentCompleted,
+    PaymentFailed,
+    PaymentRefunded,
+}
+
+impl IntegrationEventKind {
+    pub const ALL: &'static [IntegrationEventKind] = &[
+        IntegrationEventKind::MemberActivated,
+        IntegrationEventKind::MemberExpired,
+        IntegrationEventKind::MemberUpdated,
+        IntegrationEventKind::EventPublished,
+        IntegrationEventKind::AnnouncementPublished,
+        IntegrationEventKind::AdminAlert,
+        IntegrationEventKind::AdminNotification,
+        IntegrationEventKind::MemberCelebration,
+        IntegrationEventKind::PaymentCompleted,
+        IntegrationEventKind::PaymentFailed,
+        IntegrationEventKind::PaymentRefunded,
+    ];
+
+    /// Stable snake_case tag: the integration log's `event_type` and
+    /// what the `integrations.<name>.events` settings list.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntegrationEventKind::MemberActivated => "member_activated",
+            IntegrationEventKind::MemberExpired => "member_expired",
+            IntegrationEventKind::MemberUpdated => "member_updated",
+            IntegrationEventKind::EventPublished => "event_published",
+            IntegrationEventKind::AnnouncementPublished => "announcement_published",
+            IntegrationEventKind::AdminAlert => "admin_alert",
+            IntegrationEventKind::AdminNotification => "admin_notification",
+            IntegrationEventKind::MemberCelebration => "member_celebration",
+            IntegrationEventKind::PaymentCompleted => "payment_completed",
+            IntegrationEventKind::PaymentFailed => "payment_failed",
+            IntegrationEventKind::PaymentRefunded => "payment_refunded",
+        }
+    }
+}
+
+impl fmt::Display for IntegrationEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IntegrationEventKind {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        IntegrationEventKind::ALL
+            .iter()
+            .copied()
+            .find(|k| k.as_str() == s)
+            .ok_or_else(|| ParseEnumError::new("integration event kind", s))
+    }
+}
+
+impl IntegrationEvent {
+    pub fn kind(&self) -> IntegrationEventKind {
+        match self {
+            IntegrationEvent::MemberActivated(_) => IntegrationEventKind::MemberActivated,
+            IntegrationEvent::MemberExpired(_) => IntegrationEventKind::MemberExpired,
+            IntegrationEvent::MemberUpdated { .. } => IntegrationEventKind::MemberUpdated,
+            IntegrationEvent::EventPublished(_) => IntegrationEventKind::EventPublished,
+            IntegrationEvent::AnnouncementPublished(_) => {
+                IntegrationEventKind::AnnouncementPublished
+            }
+            IntegrationEvent::AdminAlert { .. } => IntegrationEventKind::AdminAlert,
+            IntegrationEvent::AdminNotification { .. } => IntegrationEventKind::AdminNotification,
+            IntegrationEvent::MemberCelebration(_) => IntegrationEventKind::MemberCelebration,
+            IntegrationEvent::PaymentCompleted(_) => IntegrationEventKind::PaymentCompleted,
+            IntegrationEvent::PaymentFailed(_) => IntegrationEventKind::PaymentFailed,
+            IntegrationEvent::PaymentRefunded(_) => IntegrationEventKind::PaymentRefunded,
+        }
+    }
+
+    /// What the event is about — an entity ID, or "admin" for alerts.
+    pub fn target(&self) -> String {
+        match self {
+            IntegrationEvent::MemberActivated(m) | IntegrationEvent::MemberExpired(m) => {
+                m.id.to_string()
+            }
+            IntegrationEvent::MemberUpdated { new, .. } => new.id.to_string(),
+            IntegrationEvent::EventPublished(e) => e.id.to_string(),
+            IntegrationEvent::AnnouncementPublished(a) => a.id.to_string(),
+            IntegrationEvent::AdminAlert { .. } => "admin".to_string(),
+            IntegrationEvent::AdminNotification { key, .. } => key.clone(),
+            IntegrationEvent::MemberCelebration(c) => c.member_id.to_string(),
+            IntegrationEvent::PaymentCompleted(p)
+            | IntegrationEvent::PaymentFailed(p)
+            | IntegrationEvent::PaymentRefunded(p) => p.id.to_string(),
+        }
+    }
+
+    /// One-line human description for the log viewer.
+    pub fn summary(&self) -> String {
+        match self {
+            IntegrationEvent::MemberActivated(m) | IntegrationEvent::MemberExpired(m) => {
+                format!("{} ({})", m.username, m.status.as_str())
+            }
+            IntegrationEvent::MemberUpdated { old, new } => format!(
+                "{}: {} → {}",
+                new.username,
+                old.status.as_str(),
+                new.status.as_str()
+            ),
+            IntegrationEvent::EventPublished(e) => e.title.clone(),
+            IntegrationEvent::AnnouncementPublished(a) => a.title.clone(),
+            IntegrationEvent::AdminAlert { subject, .. }
+            | IntegrationEvent::AdminNotification { subject, .. } => subject.clone(),
+            IntegrationEvent::MemberCelebration(c) => format!("{}: {}", c.username, c.kind),
+            IntegrationEvent::PaymentCompleted(p)
+            | IntegrationEvent::PaymentFailed(p)
+            | IntegrationEvent::PaymentRefunded(p) => {
+                format!("${} {} ({})", p.amount(), p.kind.as_str(), p.status)
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -34,16 +199,120 @@ pub trait Integration: Send + Sync {
     fn is_enabled(&self) -> bool;
     async fn health_check(&self) -> Result<()>;
     async fn handle_event(&self, event: &IntegrationEvent) -> Result<()>;
+
+    /// Whether this integration does anything with `event`. The
+    /// manager skips (and doesn't log) events an integration ignores,
+    /// so the integration log isn't buried in no-op rows.
+    fn handles(&self, _event: &IntegrationEvent) -> bool {
+        true
+    }
+
+    /// Event kinds this integration is sent. Defaults to all of them;
+    /// an admin can narrow it per integration with the
+    /// `integrations.<name>.events` setting (see
+    /// [`subscription_setting_key`]).
+    fn subscribed_events(&self) -> &[IntegrationEventKind] {
+        IntegrationEventKind::ALL
+    }
+}
+
+/// Settings key that overrides an integration's subscriptions:
+/// `integrations.<name in snake_case>.events`, e.g.
+/// `integrations.admin_alert_email.events`. A comma-separated list of
+/// [`IntegrationEventKind`] tags; empty or missing means the
+/// integration's own [`Integration::subscribed_events`].
+pub fn subscription_setting_key(integration_name: &str) -> String {
+    let mut snake = String::with_capacity(integration_name.len() + 4);
+    for (i, c) in integration_name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    format!("integrations.{}.events", snake)
 }
 
 pub struct IntegrationManager {
     integrations: RwLock<Vec<Arc<dyn Integration>>>,
+    /// Set once at startup by `ServiceContext::new`. Managers built
+    /// without one (unit tests) dispatch without logging.
+    log: OnceLock<Arc<IntegrationLogService>>,
+    /// Also set by `ServiceContext::new`; source of the per-integration
+    /// subscription overrides. Without it every integration gets its
+    /// own `subscribed_events`.
+    settings: OnceLock<Arc<SettingsService>>,
+    /// Also set by `ServiceContext::new`. With it, every delivery
+    /// `handle_event` makes is written down first, and one that fails
+    /// is left for `deliver_due` to retry.
+    outbox: OnceLock<Arc<IntegrationOutboxService>>,
 }
 
+/// Attempts per queued delivery when `integrations.outbox_max_attempts`
+/// isn't readable.
+const DEFAULT_OUTBOX_MAX_ATTEMPTS: i64 = 8;
+/// Rows `deliver_due` claims per round.
+const OUTBOX_BATCH: i64 = 50;
+
 impl IntegrationManager {
     pub fn new() -> Self {
         Self {
             integrations: RwLock::new(Vec::new()),
+            log: OnceLock::new(),
+            settings: OnceLock::new(),
+            outbox: OnceLock::new(),
+        }
+    }
+
+    /// Attach the integration log. Later calls are ignored — there's
+    /// one log per process.
+    pub fn attach_log(&self, log: Arc<IntegrationLogService>) {
+        let _ = self.log.set(log);
+    }
+
+    /// Attach the settings the subscription overrides are read from.
+    /// Later calls are ignored.
+    pub fn attach_settings(&self, settings: Arc<SettingsService>) {
+        let _ = self.settings.set(settings);
+    }
+
+    /// Attach the delivery outbox. Later calls are ignored.
+    pub fn attach_outbox(&self, outbox: Arc<IntegrationOutboxService>) {
+        let _ = self.outbox.set(outbox);
+    }
+
+    /// Whether `integration` should be sent events of `kind`: the
+    /// admin's `integrations.<name>.events` list when one is set,
+    /// otherwise the integration's own subscriptions. Read on every
+    /// dispatch so a settings change applies without a restart.
+    /// Unknown entries (a typo in the setting) are logged and skipped.
+    async fn is_subscribed(&self, integration: &dyn Integration, kind: IntegrationEventKind) -> bool {
+        let configured = match self.settings.get() {
+            Some(settings) => settings
+                .get_value(&subscription_setting_key(integration.name()))
+                .await
+                .unwrap_or_default(),
+            None => String::new(),
+        };
+        let configured: Vec<IntegrationEventKind> = configured
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| match s.parse() {
+                Ok(kind) => Some(kind),
+                Err(e) => {
+                    tracing::warn!(
+                        "Ignoring {} entry: {}",
+                        subscription_setting_key(integration.name()),
+                        e
+                    );
+                    None
+                }
+            })
+            .collect();
+        if configured.is_empty() {
+            integration.subscribed_events().contains(&kind)
+        } else {
+            configured.contains(&kind)
         }
     }
 
@@ -55,15 +324,53 @@ impl IntegrationManager {
         }
     }
 
+    /// Send `event` to every integration subscribed to it. A failing
+    /// integration never fails the caller. With an outbox attached
+    /// each delivery is queued before it's attempted, so one that
+    /// fails — or is cut short by a restart — is retried by
+    /// `deliver_due` instead of being lost.
     pub async fn handle_event(&self, event: IntegrationEvent) {
         let integrations = self.integrations.read().await;
-        
+        let max_attempts = self.outbox_max_attempts().await;
+
         for integration in integrations.iter() {
-            if !integration.is_enabled() {
+            if !integration.is_enabled() || !integration.handles(&event) {
+                continue;
+            }
+            if !self.is_subscribed(integration.as_ref(), event.kind()).await {
+                tracing::debug!(
+                    "Integration {} is not subscribed to {}",
+                    integration.name(),
+                    event.kind()
+                );
                 continue;
             }
 
-            match integration.handle_event(&event).await {
+            let queued = match self.outbox.get() {
+                Some(outbox) => {
+                    let payload = serde_json::to_string(&event).unwrap_or_default();
+                    match outbox
+                        .enqueue(integration.name(), event.kind().as_str(), &payload)
+                        .await
+                    {
+                        Ok(entry) => Some((outbox, entry)),
+                        Err(e) => {
+                            // Still make the attempt, just without a retry behind it.
+                            tracing::error!(
+                                "Couldn't queue {} for integration {}: {}",
+                                event.kind(),
+                                integration.name(),
+                                e
+                            );
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let result = integration.handle_event(&event).await;
+            match &result {
                 Ok(_) => {
                     tracing::debug!(
                         "Integration {} handled event successfully",
@@ -79,7 +386,213 @@ impl IntegrationManager {
                     // Continue processing other integrations even if one fails
                 }
             }
+            self.record(integration.name(), &event, &result, None).await;
+
+            if let Some((outbox, entry)) = queued {
+                let result = result.map_err(|e| (e.to_string(), true));
+                settle(outbox, &entry, result, max_attempts).await;
+            }
+        }
+    }
+
+    /// Retry the queued deliveries that are due: failed attempts whose
+    /// backoff has run out, and anything a restart cut short. Each
+    /// retry is logged like a first attempt; a failure is rescheduled
+    /// again until `integrations.outbox_max_attempts` is used up.
+    /// Returns the number of deliveries attempted. A no-op without an
+    /// outbox.
+    pub async fn deliver_due(&self) -> usize {
+        let Some(outbox) = self.outbox.get() else {
+            return 0;
+        };
+        let max_attempts = self.outbox_max_attempts().await;
+
+        let mut attempted = 0;
+        loop {
+            let entries = match outbox.claim_due(OUTBOX_BATCH).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    tracing::error!("Couldn't claim queued integration deliveries: {}", e);
+                    break;
+                }
+            };
+            if entries.is_empty() {
+                break;
+            }
+            for entry in entries {
+                attempted += 1;
+                let result = self.deliver(&entry).await;
+                settle(outbox, &entry, result, max_attempts).await;
+            }
         }
+        attempted
+    }
+
+    /// One queued delivery. On failure returns the error text and
+    /// whether a retry could help — an unreadable payload or an
+    /// integration that's no longer registered won't fix itself.
+    async fn deliver(&self, entry: &OutboxEntry) -> std::result::Result<(), (String, bool)> {
+        let event: IntegrationEvent = serde_json::from_str(&entry.event_payload)
+            .map_err(|e| (format!("Unreadable event payload: {}", e), false))?;
+        let integration = {
+            let integrations = self.integrations.read().await;
+            integrations
+                .iter()
+                .find(|i| i.name() == entry.integration)
+                .cloned()
+        }
+        .ok_or_else(|| (format!("Integration {} is not registered", entry.integration), false))?;
+
+        let result = integration.handle_event(&event).await;
+        self.record(integration.name(), &event, &result, None).await;
+        result.map_err(|e| {
+            tracing::warn!(
+                "Integration {} failed {} again (attempt {}): {:?}",
+                entry.integration,
+                entry.event_type,
+                entry.attempts + 1,
+                e
+            );
+            (e.to_string(), true)
+        })
+    }
+
+    /// `integrations.outbox_max_attempts`, read per dispatch like the
+    /// subscription overrides.
+    async fn outbox_max_attempts(&self) -> i64 {
+        let configured = match self.settings.get() {
+            Some(settings) => settings
+                .get_number("integrations.outbox_max_attempts")
+                .await
+                .ok(),
+            None => None,
+        };
+        configured.unwrap_or(DEFAULT_OUTBOX_MAX_ATTEMPTS).max(1)
+    }
+
+    /// Names of the registered integrations, in registration order.
+    pub async fn names(&self) -> Vec<String> {
+        self.integrations
+            .read()
+            .await
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect()
+    }
+
+    /// Push `event` on an admin's request: to every registered
+    /// integration, or only the one named `only`. Unlike
+    /// `handle_event` this reports back per integration. Integrations
+    /// that ignore the event kind are left out (or, when named in
+    /// `only`, reported as [`ResyncOutcome::NotSubscribed`]). Each
+    /// attempt is recorded in the integration log.
+    pub async fn resync(&self, event: IntegrationEvent, only: Option<&str>) -> Vec<ResyncResult> {
+        let integrations: Vec<Arc<dyn Integration>> = self
+            .integrations
+            .read()
+            .await
+            .iter()
+            .filter(|i| only.is_none_or(|name| i.name() == name))
+            .cloned()
+            .collect();
+
+        let mut results = Vec::new();
+        for integration in integrations {
+            let subscribed = integration.is_enabled()
+                && integration.handles(&event)
+                && self.is_subscribed(integration.as_ref(), event.kind()).await;
+            if !subscribed {
+                if only.is_some() {
+                    results.push(ResyncResult {
+                        integration: integration.name().to_string(),
+                        outcome: ResyncOutcome::NotSubscribed,
+                    });
+                }
+                continue;
+            }
+
+            let result = integration.handle_event(&event).await;
+            self.record(integration.name(), &event, &result, None).await;
+            results.push(ResyncResult {
+                integration: integration.name().to_string(),
+                outcome: match result {
+                    Ok(()) => ResyncOutcome::Sent,
+                    Err(e) => ResyncOutcome::Failed(e.to_string()),
+                },
+            });
+        }
+        results
+    }
+
+    /// Re-run a logged attempt against the integration that made it.
+    /// Only that integration is invoked — replaying a failed Discord
+    /// post must not re-send the admin-alert email that succeeded the
+    /// first time. Returns the outcome of the new attempt, which is
+    /// itself logged with `replay_of` pointing at the original.
+    ///
+    /// Only a failed entry that hasn't been replayed yet qualifies —
+    /// the same rule the admin page uses to show the replay button —
+    /// so a delivery that already landed is never sent twice.
+    pub async fn replay(&self, entry_id: Uuid) -> Result<()> {
+        let log = self
+            .log
+            .get()
+            .ok_or_else(|| AppError::Internal("Integration log not configured".to_string()))?;
+        let entry = log.get(entry_id).await?;
+        if entry.ok {
+            return Err(AppError::BadRequest(
+                "Only failed deliveries can be replayed".to_string(),
+            ));
+        }
+        if entry.replayed_at.is_some() {
+            return Err(AppError::Conflict(
+                "This delivery has already been replayed".to_string(),
+            ));
+        }
+        let event: IntegrationEvent = serde_json::from_str(&entry.event_payload)
+            .map_err(|e| AppError::Internal(format!("Unreadable event payload: {}", e)))?;
+
+        let integration = {
+            let integrations = self.integrations.read().await;
+            integrations
+                .iter()
+                .find(|i| i.name() == entry.integration)
+                .cloned()
+        }
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Integration {} is not registered", entry.integration))
+        })?;
+
+        let result = integration.handle_event(&event).await;
+        self.record(integration.name(), &event, &result, Some(entry_id)).await;
+        if let Err(e) = log.mark_replayed(entry_id).await {
+            tracing::warn!("Couldn't mark integration log entry {} replayed: {}", entry_id, e);
+        }
+        result
+    }
+
+    async fn record(
+        &self,
+        integration: &str,
+        event: &IntegrationEvent,
+        result: &Result<()>,
+        replay_of: Option<Uuid>,
+    ) {
+        let Some(log) = self.log.get() else {
+            return;
+        };
+        let payload = serde_json::to_string(event).unwrap_or_default();
+        let error = result.as_ref().err().map(|e| e.to_string());
+        log.record(NewIntegrationLogEntry {
+            integration,
+            event_type: event.kind().as_str(),
+            target: &event.target(),
+            request_summary: &event.summary(),
+            error: error.as_deref(),
+            event_payload: &payload,
+            replay_of,
+        })
+        .await;
     }
 
     pub async fn health_check_all(&self) -> Vec<(String, Result<()>)> {
@@ -96,6 +609,39 @@ impl IntegrationManager {
     }
 }
 
+/// Close out one attempt at a queued delivery: clear the row on
+/// success, otherwise count the failure (which reschedules or gives up).
+async fn settle(
+    outbox: &IntegrationOutboxService,
+    entry: &OutboxEntry,
+    result: std::result::Result<(), (String, bool)>,
+    max_attempts: i64,
+) {
+    let written = match result {
+        Ok(()) => outbox.delivered(&entry.id).await,
+        Err((error, retry)) => outbox.attempt_failed(entry, &error, max_attempts, retry).await,
+    };
+    if let Err(e) = written {
+        tracing::error!("Couldn't update integration outbox row {}: {}", entry.id, e);
+    }
+}
+
+/// One integration's part in an [`IntegrationManager::resync`].
+#[derive(Debug, Clone)]
+pub struct ResyncResult {
+    pub integration: String,
+    pub outcome: ResyncOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResyncOutcome {
+    Sent,
+    Failed(String),
+    /// The integration ignores this kind of event, or an admin has
+    /// unsubscribed it (`integrations.<name>.events`).
+    NotSubscribed,
+}
+
 // Base implementation for common integration functionality
 pub struct BaseIntegration {
     pub name: String,
@@ -90,4 +90,13 @@ impl Integration for UnifiIntegration {
         }
         Ok(())
     }
+
+    fn handles(&self, event: &IntegrationEvent) -> bool {
+        matches!(
+            event,
+            IntegrationEvent::MemberActivated(_)
+                | IntegrationEvent::MemberExpired(_)
+                | IntegrationEvent::MemberUpdated { .. }
+        )
+    }
 }
\ No newline at end of file
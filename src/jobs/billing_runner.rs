@@ -2,13 +2,17 @@ use std::sync::Arc;
 use tokio::time::{self, Duration};
 
 use crate::service::{
+    admin_notification_service::AdminNotificationService,
     announcement_admin_service::AnnouncementAdminService,
     billing_service::BillingService,
+    celebration_service::CelebrationService,
 };
 
 pub struct BillingRunner {
     billing_service: Arc<BillingService>,
     announcement_admin_service: Arc<AnnouncementAdminService>,
+    admin_notification_service: Arc<AdminNotificationService>,
+    celebration_service: Arc<CelebrationService>,
     interval: Duration,
 }
 
@@ -16,11 +20,15 @@ impl BillingRunner {
     pub fn new(
         billing_service: Arc<BillingService>,
         announcement_admin_service: Arc<AnnouncementAdminService>,
+        admin_notification_service: Arc<AdminNotificationService>,
+        celebration_service: Arc<CelebrationService>,
         interval_secs: u64,
     ) -> Self {
         Self {
             billing_service,
             announcement_admin_service,
+            admin_notification_service,
+            celebration_service,
             interval: Duration::from_secs(interval_secs),
         }
     }
@@ -69,9 +77,9 @@ impl BillingRunner {
             }
         }
 
-        // Send dues-expiring-soon reminders (idempotent per cycle via
-        // dues_reminder_sent_at flag, so running hourly is fine — only
-        // newly-eligible members get email).
+        // Send dues reminders, escalating per membership.reminder_sequence
+        // (idempotent per step and dues period via dues_reminders_sent,
+        // so running hourly is fine — only newly-reached steps get email).
         match self.billing_service.notifications.send_dues_reminders().await {
             Ok(_) => {}
             Err(e) => {
@@ -79,9 +87,9 @@ impl BillingRunner {
             }
         }
 
-        // Send RSVP'd-event reminders (idempotent per RSVP via the
-        // event_attendance.reminder_sent_at flag, so hourly ticks are
-        // safe — only newly-eligible RSVPs get email).
+        // Send RSVP'd-event reminders (idempotent per RSVP and lead
+        // time via event_reminders_sent, so hourly ticks are safe —
+        // only newly-eligible reminders get email).
         match self.billing_service.notifications.send_event_reminders().await {
             Ok(_) => {}
             Err(e) => {
@@ -103,5 +111,37 @@ impl BillingRunner {
                 tracing::error!("Scheduled-announcement publish cycle error: {}", e);
             }
         }
+
+        // Send admin-notification digests that have come due. Rows are
+        // marked sent as each digest goes out, so an hourly tick only
+        // picks up admins whose oldest queued entry just turned a day.
+        match self
+            .admin_notification_service
+            .send_due_digests(chrono::Utc::now())
+            .await
+        {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Sent {} admin notification digest(s)", count);
+                }
+            }
+            Err(e) => {
+                tracing::error!("Admin notification digest cycle error: {}", e);
+            }
+        }
+
+        // Birthday / anniversary shout-outs. Each one is recorded in
+        // member_celebrations before it goes out, so only the first
+        // tick of the day sends anything.
+        match self.celebration_service.send_due(chrono::Utc::now()).await {
+            Ok(count) => {
+                if count > 0 {
+                    tracing::info!("Sent {} member shout-out(s)", count);
+                }
+            }
+            Err(e) => {
+                tracing::error!("Member shout-out cycle error: {}", e);
+            }
+        }
     }
 }
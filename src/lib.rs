@@ -5,6 +5,7 @@ pub mod config;
 pub mod domain;
 pub mod email;
 pub mod error;
+pub mod first_run;
 pub mod integrations;
 pub mod jobs;
 pub mod payments;
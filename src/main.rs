@@ -4,6 +4,7 @@ mod config;
 mod domain;
 mod email;
 mod error;
+mod first_run;
 mod integrations;
 mod jobs;
 mod payments;
@@ -22,6 +23,7 @@ use crate::{
     integrations::{
         IntegrationManager,
         admin_alert_email::AdminAlertEmailIntegration,
+        admin_notifications::AdminNotificationIntegration,
         discord::DiscordIntegration,
         unifi::UnifiIntegration,
     },
@@ -97,11 +99,33 @@ async fn main() -> anyhow::Result<()> {
         .run(&db_pool)
         .await?;
 
-    // Initialize auth service
-    let auth_service = Arc::new(auth::AuthService::new(
-        db_pool.clone(),
-        settings.auth.session_secret.clone(),
-    ));
+    // Opt-in: bring a fresh deploy up with the configured admin and
+    // membership types. A database that already has members is left
+    // untouched.
+    if settings.seed.on_first_run {
+        match first_run::seed_if_empty(&db_pool, &settings.seed).await? {
+            first_run::FirstRunOutcome::Seeded { admin_id, membership_types } => tracing::info!(
+                "First-run seed: created admin {} ({}) and {} membership type(s)",
+                settings.seed.admin.username,
+                admin_id,
+                membership_types
+            ),
+            first_run::FirstRunOutcome::AlreadyPopulated => {
+                tracing::debug!("First-run seed skipped: database already has members")
+            }
+        }
+    }
+
+    // Initialize auth service. Sessions live in SQLite unless
+    // `auth.session_store = "redis"`; either store applies the idle
+    // timeout.
+    let session_store =
+        auth::session::build_session_store(&settings.auth, db_pool.clone()).await?;
+    tracing::info!("Session store: {:?}", settings.auth.session_store);
+    let auth_service = Arc::new(
+        auth::AuthService::new(db_pool.clone(), settings.auth.session_secret.clone())
+            .with_session_store(session_store),
+    );
 
     // Encryption helper for secrets-at-rest (e.g. SMTP password in
     // settings). Key is derived from session_secret — if the operator
@@ -124,20 +148,22 @@ async fn main() -> anyhow::Result<()> {
 
     // CSRF tokens are stateless HMAC; the service derives its key from
     // session_secret so rotating that secret invalidates outstanding
-    // tokens (users get a 403 on next submit and retry).
-    let csrf_service = Arc::new(auth::CsrfService::new(&settings.auth.session_secret));
+    // tokens (users get a 403 on next submit and retry). Tokens also
+    // expire on their own after `csrf_token_ttl_hours`.
+    let csrf_service = Arc::new(
+        auth::CsrfService::new(&settings.auth.session_secret)
+            .with_ttl(chrono::Duration::hours(settings.auth.csrf_token_ttl_hours)),
+    );
 
     // TOTP / 2FA. Issuer is the org name shown in authenticator apps;
-    // we look it up once at startup, fall back to "Coterie" if unset.
-    // Live org-name changes don't propagate without restart, but
-    // existing enrollments aren't affected (issuer is metadata in the
-    // enrolled otpauth URL, not part of the verification math).
-    let totp_issuer = settings_service
-        .get_setting("org.name").await
-        .ok()
-        .map(|s| s.value)
-        .filter(|s| !s.is_empty())
-        .unwrap_or_else(|| "Coterie".to_string());
+    // we look it up once at startup, fall back to `auth.totp_issuer`
+    // (then "Coterie") if unset. Live org-name changes don't propagate
+    // without restart, but existing enrollments aren't affected (issuer
+    // is metadata in the enrolled otpauth URL, not part of the
+    // verification math).
+    let org_name = settings_service.get_setting("org.name").await.ok().map(|s| s.value);
+    let totp_issuer =
+        auth::totp::resolve_issuer(org_name.as_deref(), &settings.auth.totp_issuer);
     let totp_service = Arc::new(auth::TotpService::new(
         db_pool.clone(),
         crypto.clone(),
@@ -164,6 +190,7 @@ async fn main() -> anyhow::Result<()> {
     // intentionally doesn't expose Discord-specific operations).
     let discord_integration = Arc::new(DiscordIntegration::new(
         settings_service.clone(),
+        Arc::new(repository::SqliteMembershipTypeRepository::new(db_pool.clone())),
         settings.server.base_url.clone(),
     ));
     integration_manager
@@ -186,15 +213,6 @@ async fn main() -> anyhow::Result<()> {
         integration_manager.register(Arc::new(unifi)).await;
     }
 
-    // Check integration health
-    let health_results = integration_manager.health_check_all().await;
-    for (name, result) in health_results {
-        match result {
-            Ok(_) => tracing::info!("Integration {} is healthy", name),
-            Err(e) => tracing::warn!("Integration {} health check failed: {:?}", name, e),
-        }
-    }
-
     // Initialize Stripe client up front (before ServiceContext) so
     // PaymentAdminService can take it as a constructor dep. The
     // companion WebhookDispatcher is built further down — it needs
@@ -210,6 +228,7 @@ async fn main() -> anyhow::Result<()> {
                 tracing::info!("Stripe payment processing enabled");
                 Some(Arc::new(payments::StripeClient::new(
                     api_key,
+                    payments::gateway::StripeRetryPolicy::from_config(&settings.stripe),
                     payment_repo.clone(),
                     member_repo.clone(),
                 )))
@@ -254,14 +273,38 @@ async fn main() -> anyhow::Result<()> {
         db_pool.clone(),
     ));
 
-    // Spawn background cleanup task (runs hourly) for expired sessions
-    // and for pruning old audit-log entries based on the operator-set
-    // retention window.
+    // Per-admin notifications need the service, which lives on the
+    // context, so this one registers after it's built.
+    service_context
+        .integration_manager
+        .register(Arc::new(AdminNotificationIntegration::new(
+            service_context.admin_notification_service.clone(),
+        )))
+        .await;
+
+    // Approximate login locations are optional; a bad path is logged
+    // and login history carries on without them.
+    if let Some(path) = settings.auth.geoip_db_path.as_deref().filter(|p| !p.is_empty()) {
+        match service_context.login_history_service.attach_geoip(std::path::Path::new(path)) {
+            Ok(()) => tracing::info!("GeoIP database loaded from {}", path),
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    // Spawn background cleanup task (runs hourly) for expired sessions,
+    // lapsed time-limited honorary grants, and for pruning old audit-log
+    // and integration-log entries based on the operator-set retention
+    // windows.
     {
         let auth_service = service_context.auth_service.clone();
+        let member_service = service_context.member_service.clone();
         let audit_service = service_context.audit_service.clone();
+        let integration_log_service = service_context.integration_log_service.clone();
+        let integration_outbox_service = service_context.integration_outbox_service.clone();
+        let login_history_service = service_context.login_history_service.clone();
         let settings_service = service_context.settings_service.clone();
-        let cleanup_pool = db_pool.clone();
+        let processed_events_repo = service_context.processed_events_repo.clone();
+        let webhook_metrics = service_context.webhook_metrics.clone();
         tokio::spawn(async move {
             let cleanup_interval = tokio::time::Duration::from_secs(60 * 60); // 1 hour
             loop {
@@ -278,6 +321,17 @@ async fn main() -> anyhow::Result<()> {
                     _ => {}
                 }
 
+                // Time-limited honorary grants whose expiry has passed
+                match member_service.revert_expired_honorary().await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Reverted {} expired honorary grants", count);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to revert expired honorary grants: {:?}", e);
+                    }
+                    _ => {}
+                }
+
                 // Audit-log retention (default 365 days, clamped in
                 // `prune_older_than` to sane bounds).
                 let retention_days = settings_service
@@ -294,29 +348,87 @@ async fn main() -> anyhow::Result<()> {
                     _ => {}
                 }
 
-                // Stripe webhook idempotency table. Stripe retries for
-                // ~3 days max; anything older than 30 days has zero
-                // chance of a legitimate replay. Without this prune
-                // the table grows unbounded over the lifetime of the
-                // deployment.
-                match sqlx::query(
-                    "DELETE FROM processed_stripe_events \
-                     WHERE processed_at < datetime('now', '-30 days')",
-                )
-                .execute(&cleanup_pool)
-                .await
-                {
-                    Ok(res) if res.rows_affected() > 0 => {
-                        tracing::info!(
-                            "Pruned {} processed_stripe_events older than 30 days",
-                            res.rows_affected(),
-                        );
+                // Integration log retention (default 30 days). Shorter
+                // than the audit log: these rows are for debugging a
+                // sync problem, not for accountability.
+                let log_retention_days = settings_service
+                    .get_number("integrations.log_retention_days")
+                    .await
+                    .unwrap_or(30);
+                match integration_log_service.prune_older_than(log_retention_days).await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Pruned {} integration-log entries older than {} days", count, log_retention_days);
                     }
                     Err(e) => {
-                        tracing::warn!("Failed to prune processed_stripe_events: {:?}", e);
+                        tracing::warn!("Failed to prune integration log: {:?}", e);
                     }
                     _ => {}
                 }
+
+                // Outbox rows that ran out of attempts go with the log
+                // entries that recorded them.
+                match integration_outbox_service.prune_failed_older_than(log_retention_days).await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Pruned {} failed integration deliveries older than {} days", count, log_retention_days);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to prune integration outbox: {:?}", e);
+                    }
+                    _ => {}
+                }
+
+                // Login history retention (default 90 days). Long enough
+                // for a member to notice an odd sign-in, short enough
+                // that we're not keeping a years-long IP trail.
+                let login_retention_days = settings_service
+                    .get_number("auth.login_history_retention_days")
+                    .await
+                    .unwrap_or(90);
+                match login_history_service.prune_older_than(login_retention_days).await {
+                    Ok(count) if count > 0 => {
+                        tracing::info!("Pruned {} login-history entries older than {} days", count, login_retention_days);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to prune login history: {:?}", e);
+                    }
+                    _ => {}
+                }
+
+                // Stripe webhook idempotency table. A claim only guards
+                // against redelivery while Stripe might still retry, so
+                // anything past the replay window (plus margin, see
+                // `processed_events_repository`) goes. Without this
+                // prune the table grows unbounded over the lifetime of
+                // the deployment.
+                match processed_events_repo.prune_expired(chrono::Utc::now()).await {
+                    Ok(count) => {
+                        webhook_metrics.record_pruned(count);
+                        if count > 0 {
+                            tracing::info!("Pruned {} processed Stripe events", count);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to prune processed_stripe_events: {:?}", e);
+                    }
+                }
+            }
+        });
+    }
+
+    // Integration outbox retries. `handle_event` makes the first
+    // attempt at every delivery itself; this picks up the ones that
+    // failed once their backoff has run out, plus anything a restart
+    // cut short (hence running once right at boot).
+    {
+        let manager = service_context.integration_manager.clone();
+        tokio::spawn(async move {
+            let interval = tokio::time::Duration::from_secs(30);
+            loop {
+                let attempted = manager.deliver_due().await;
+                if attempted > 0 {
+                    tracing::info!("Integration outbox: retried {} deliveries", attempted);
+                }
+                tokio::time::sleep(interval).await;
             }
         });
     }
@@ -378,9 +490,9 @@ async fn main() -> anyhow::Result<()> {
     // Stripe webhook dispatcher — paired with the StripeClient built
     // above. Stays here (after ServiceContext::new) because it pulls
     // several service_context-owned fields (processed_events_repo,
-    // membership_type_service, integration_manager). Built only when
-    // a configured stripe_client is present; the API-key / secret
-    // pair check already happened up top.
+    // membership_type_service, integration_manager, member_service).
+    // Built only when a configured stripe_client is present; the
+    // API-key / secret pair check already happened up top.
     let webhook_dispatcher: Option<Arc<payments::WebhookDispatcher>> = match &stripe_client {
         Some(client) => settings.stripe.webhook_secret.clone().map(|webhook_secret| {
             Arc::new(payments::WebhookDispatcher::new(
@@ -391,6 +503,8 @@ async fn main() -> anyhow::Result<()> {
                 service_context.processed_events_repo.clone(),
                 service_context.membership_type_service.clone(),
                 service_context.integration_manager.clone(),
+                service_context.member_service.clone(),
+                service_context.webhook_metrics.clone(),
             ))
         }),
         None => None,
@@ -410,13 +524,15 @@ async fn main() -> anyhow::Result<()> {
         let runner = jobs::BillingRunner::new(
             billing_service.clone(),
             service_context.announcement_admin_service.clone(),
+            service_context.admin_notification_service.clone(),
+            service_context.celebration_service.clone(),
             60 * 60,
         );
         runner.spawn();
         tracing::info!("Billing runner spawned");
     }
 
-    // Bot-challenge verifier for /public/signup + /public/donate. Reuses
+    // Bot-challenge verifier for the public POST endpoints. Reuses
     // a fresh reqwest client; reqwest::Client is internally Arc'd so a
     // dedicated instance keeps its connection pool warm without
     // entangling with the Stripe / Discord clients' pools.
@@ -443,13 +559,40 @@ async fn main() -> anyhow::Result<()> {
         money_limiter,
     );
 
-    // Spawn periodic cleanup for the login rate limiter
+    // Startup dependency check: the database plus every enabled
+    // integration. Under `integrations.startup_policy = "fail"` a
+    // failing integration holds `/ready` at 503; we keep serving
+    // (liveness stays green, admins can still reach the portal to fix
+    // the config) and retry until it passes.
+    {
+        let readiness = app_state.readiness_service.clone();
+        if !readiness.check().await.ready {
+            tracing::error!(
+                "Startup dependency check failed (integration startup policy: {:?}); \
+                 /ready will report not-ready until it passes",
+                readiness.policy()
+            );
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                    if readiness.check().await.ready {
+                        tracing::info!("Startup dependency check passed; instance is ready");
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    // Spawn periodic cleanup for the login rate limiter and lockouts
     {
         let limiter = app_state.login_limiter.clone();
+        let failures = app_state.login_failures.clone();
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(15 * 60)).await;
                 limiter.cleanup();
+                failures.cleanup();
             }
         });
     }
@@ -466,6 +609,51 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // And for the contact-form limiter.
+    {
+        let limiter = app_state.contact_limiter.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(15 * 60)).await;
+                limiter.cleanup();
+            }
+        });
+    }
+
+    // Scheduled database backups. Checks hourly; a backup is only
+    // taken when `backup.scheduled_enabled` is on and the newest one is
+    // older than `backup.interval_hours`. Settings are re-read each
+    // tick so toggling it in the admin UI needs no restart.
+    {
+        let backup_service = app_state.backup_service.clone();
+        let settings_service = app_state.service_context.settings_service.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(60 * 60)).await;
+
+                if !settings_service
+                    .get_bool("backup.scheduled_enabled")
+                    .await
+                    .unwrap_or(false)
+                {
+                    continue;
+                }
+                let interval_hours = settings_service
+                    .get_number("backup.interval_hours")
+                    .await
+                    .unwrap_or(24);
+                let keep = settings_service
+                    .get_number("backup.keep")
+                    .await
+                    .unwrap_or(7)
+                    .clamp(1, 365) as usize;
+                if let Err(e) = backup_service.run_scheduled(interval_hours, keep).await {
+                    tracing::error!("Scheduled database backup failed: {:?}", e);
+                }
+            }
+        });
+    }
+
     let api_app = api::create_app(app_state.clone());
     let web_app = web::create_web_routes(app_state.clone());
 
@@ -483,6 +671,15 @@ async fn main() -> anyhow::Result<()> {
     // would otherwise fire, which is the right precedence for both
     // security (no body parsing on bad CSRF) and UX (GETs still
     // redirect to the setup wizard during first-boot).
+    //
+    // Maintenance mode sits between the two: outside setup so the
+    // maintenance page wins over the setup redirect, inside CSRF so a
+    // forged login POST is still rejected while the site is down.
+    //
+    // Security headers wrap everything else so every response carries
+    // them, the CSRF rejection and maintenance page included. Load
+    // shedding sits just inside them: a shed request costs no CSRF
+    // lookup or settings read, but its 503 still gets the headers.
     let app = api_app
         .merge(web_app)
         .layer(axum::middleware::from_fn_with_state(
@@ -490,8 +687,20 @@ async fn main() -> anyhow::Result<()> {
             api::middleware::setup::require_setup,
         ))
         .layer(axum::middleware::from_fn_with_state(
-            app_state,
+            app_state.clone(),
+            api::middleware::maintenance::maintenance_mode,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
             api::middleware::security::csrf_protect_unless_exempt,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            api::middleware::load_shed::shed_load,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            app_state,
+            api::middleware::security_headers::security_headers,
         ));
 
     let listener = tokio::net::TcpListener::bind(
@@ -39,28 +39,55 @@ use stripe::{
     Refund, RequestStrategy, SetupIntent, Subscription, SubscriptionId,
 };
 
-use crate::error::{AppError, Result};
-
-/// 30s ceiling on every Stripe call. async-stripe 0.39 doesn't expose
-/// per-request timeouts on its Client, and a hung response would tie up
-/// an Axum handler indefinitely. Worst case before this: a webhook
-/// handler that never returns, blocking Stripe's retry from making
-/// forward progress.
-const STRIPE_TIMEOUT: Duration = Duration::from_secs(30);
-
-/// Apply STRIPE_TIMEOUT to any stripe-rs future and translate both the
-/// timeout and SDK errors into `AppError::External`.
-async fn timed<T, F>(fut: F) -> Result<T>
-where
-    F: std::future::Future<Output = std::result::Result<T, stripe::StripeError>>,
-{
-    match tokio::time::timeout(STRIPE_TIMEOUT, fut).await {
-        Ok(Ok(v)) => Ok(v),
-        Ok(Err(e)) => Err(AppError::External(format!("Stripe error: {}", e))),
-        Err(_) => Err(AppError::External(format!(
-            "Stripe API timed out after {}s",
-            STRIPE_TIMEOUT.as_secs(),
-        ))),
+use crate::{
+    config::StripeConfig,
+    error::{AppError, Result},
+};
+
+/// How `RealStripeGateway` bounds each Stripe call. async-stripe 0.39
+/// doesn't expose per-request timeouts on its Client, and a hung
+/// response would tie up an Axum handler indefinitely, so every attempt
+/// runs under `timeout`. Transient failures — the timeout itself, a
+/// connection error, 429 or 5xx — are retried up to `max_retries`
+/// times. Only safe because every call is a GET/DELETE or a POST
+/// carrying an idempotency key: a retried charge replays the first
+/// one's result instead of charging again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StripeRetryPolicy {
+    pub timeout: Duration,
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles for each one after.
+    pub backoff: Duration,
+}
+
+impl Default for StripeRetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_retries: 2,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl StripeRetryPolicy {
+    pub fn from_config(config: &StripeConfig) -> Self {
+        Self {
+            timeout: Duration::from_secs(config.request_timeout_secs),
+            max_retries: config.max_retries,
+            ..Self::default()
+        }
+    }
+}
+
+/// Whether a failed attempt is worth repeating. Anything Stripe
+/// rejected on its merits (4xx other than 429, card declines, a
+/// response we couldn't parse) fails the same way every time.
+fn is_transient(e: &stripe::StripeError) -> bool {
+    match e {
+        stripe::StripeError::Timeout | stripe::StripeError::ClientError(_) => true,
+        stripe::StripeError::Stripe(r) => r.http_status == 429 || r.http_status >= 500,
+        _ => false,
     }
 }
 
@@ -261,11 +288,18 @@ pub trait StripeGateway: Send + Sync {
 
 pub struct RealStripeGateway {
     client: Client,
+    policy: StripeRetryPolicy,
 }
 
 impl RealStripeGateway {
-    pub fn new(api_key: String) -> Self {
-        Self { client: Client::new(api_key) }
+    pub fn new(api_key: String, policy: StripeRetryPolicy) -> Self {
+        Self::with_client(Client::new(api_key), policy)
+    }
+
+    /// Build around an existing stripe-rs client — e.g. one from
+    /// `Client::from_url` pointed at a mock server in tests.
+    pub fn with_client(client: Client, policy: StripeRetryPolicy) -> Self {
+        Self { client, policy }
     }
 
     /// Test/seam access to the underlying stripe-rs client. Used during
@@ -275,6 +309,57 @@ impl RealStripeGateway {
     pub fn raw_client(&self) -> &Client {
         &self.client
     }
+
+    /// Run one Stripe request under the retry policy, translating SDK
+    /// errors into `AppError::External`. `request` builds a fresh
+    /// attempt from the client each time. With an `idempotency_key`
+    /// every attempt sends the same key; GET and DELETE requests pass
+    /// `None`. Once retries run out the caller gets
+    /// `AppError::ServiceUnavailable` so the user sees "try again"
+    /// rather than a generic upstream failure.
+    async fn send<T, F, Fut>(&self, idempotency_key: Option<String>, request: F) -> Result<T>
+    where
+        F: Fn(&Client) -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, stripe::StripeError>>,
+    {
+        let client = match idempotency_key {
+            Some(key) => self.client.clone().with_strategy(RequestStrategy::Idempotent(key)),
+            None => self.client.clone(),
+        };
+        let attempts = self.policy.max_retries + 1;
+        let mut last_error = String::new();
+        for attempt in 1..=attempts {
+            if attempt > 1 {
+                tokio::time::sleep(self.policy.backoff * 2u32.pow(attempt - 2)).await;
+            }
+            last_error = match tokio::time::timeout(self.policy.timeout, request(&client)).await {
+                Ok(Ok(v)) => return Ok(v),
+                Ok(Err(e)) if is_transient(&e) => e.to_string(),
+                Ok(Err(e)) => return Err(AppError::External(format!("Stripe error: {}", e))),
+                Err(_) => format!("timed out after {:?}", self.policy.timeout),
+            };
+            tracing::warn!(
+                "Stripe request failed (attempt {}/{}): {}",
+                attempt,
+                attempts,
+                last_error
+            );
+        }
+        tracing::error!(
+            "Stripe request gave up after {} attempts: {}",
+            attempts,
+            last_error
+        );
+        Err(AppError::ServiceUnavailable(
+            "Stripe is not responding right now. Please try again in a few minutes.".to_string(),
+        ))
+    }
+
+    /// Key for a create call that has no natural one of its own, so
+    /// its retries are still safe.
+    fn fresh_key() -> Option<String> {
+        Some(uuid::Uuid::new_v4().to_string())
+    }
 }
 
 #[async_trait]
@@ -317,7 +402,9 @@ impl StripeGateway for RealStripeGateway {
             params.client_reference_id = Some(ref_id);
         }
 
-        let session = timed(CheckoutSession::create(&self.client, params)).await?;
+        let session = self
+            .send(Self::fresh_key(), |c| CheckoutSession::create(c, params.clone()))
+            .await?;
         let url = session.url
             .ok_or_else(|| AppError::External("No checkout URL returned".to_string()))?;
         Ok(CheckoutOutput {
@@ -335,7 +422,7 @@ impl StripeGateway for RealStripeGateway {
         })?;
         let mut params = stripe::ListCheckoutSessions::new();
         params.payment_intent = Some(pi_id);
-        let list = timed(CheckoutSession::list(&self.client, &params)).await?;
+        let list = self.send(None, |c| CheckoutSession::list(c, &params)).await?;
         Ok(list.data.into_iter().map(|s| s.id.to_string()).collect())
     }
 
@@ -346,7 +433,9 @@ impl StripeGateway for RealStripeGateway {
         let cs_id: CheckoutSessionId = session_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid CheckoutSession ID: {}", session_id))
         })?;
-        let session = timed(CheckoutSession::retrieve(&self.client, &cs_id, &[])).await?;
+        let session = self
+            .send(None, |c| CheckoutSession::retrieve(c, &cs_id, &[]))
+            .await?;
         Ok(RetrievedCheckoutSession {
             payment_intent_id: session.payment_intent.map(|exp| exp.id().to_string()),
         })
@@ -361,7 +450,9 @@ impl StripeGateway for RealStripeGateway {
         if !input.metadata.is_empty() {
             params.metadata = Some(input.metadata.clone());
         }
-        let customer = timed(Customer::create(&self.client, params)).await?;
+        let customer = self
+            .send(Self::fresh_key(), |c| Customer::create(c, params.clone()))
+            .await?;
         Ok(customer.id.to_string())
     }
 
@@ -369,7 +460,7 @@ impl StripeGateway for RealStripeGateway {
         let cid: CustomerId = customer_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid customer ID: {}", customer_id))
         })?;
-        let customer = timed(Customer::retrieve(&self.client, &cid, &[])).await?;
+        let customer = self.send(None, |c| Customer::retrieve(c, &cid, &[])).await?;
         let default_pm = customer.invoice_settings
             .as_ref()
             .and_then(|s| s.default_payment_method.as_ref())
@@ -394,7 +485,9 @@ impl StripeGateway for RealStripeGateway {
         if !input.metadata.is_empty() {
             params.metadata = Some(input.metadata.clone());
         }
-        let setup_intent = timed(SetupIntent::create(&self.client, params)).await?;
+        let setup_intent = self
+            .send(Self::fresh_key(), |c| SetupIntent::create(c, params.clone()))
+            .await?;
         let client_secret = setup_intent.client_secret
             .ok_or_else(|| AppError::External("SetupIntent missing client_secret".to_string()))?;
         Ok(SetupIntentOutput {
@@ -425,10 +518,11 @@ impl StripeGateway for RealStripeGateway {
             params.metadata = Some(input.metadata.clone());
         }
 
-        let idempotent_client = self.client.clone().with_strategy(
-            RequestStrategy::Idempotent(input.idempotency_key.clone())
-        );
-        let intent = timed(PaymentIntent::create(&idempotent_client, params)).await
+        let intent = self
+            .send(Some(input.idempotency_key.clone()), |c| {
+                PaymentIntent::create(c, params.clone())
+            })
+            .await
             .map_err(|e| match e {
                 AppError::External(msg) => AppError::External(format!("Stripe charge failed: {}", msg)),
                 other => other,
@@ -452,7 +546,7 @@ impl StripeGateway for RealStripeGateway {
         let mut params = ListPaymentMethods::new();
         params.customer = Some(cid);
         params.type_ = Some(PaymentMethodTypeFilter::Card);
-        let list = timed(PaymentMethod::list(&self.client, &params)).await?;
+        let list = self.send(None, |c| PaymentMethod::list(c, &params)).await?;
         Ok(list.data.into_iter().filter_map(|pm| {
             let card = pm.card?;
             Some(PaymentMethodSummary {
@@ -472,7 +566,7 @@ impl StripeGateway for RealStripeGateway {
         let pm_id: PaymentMethodId = payment_method_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid PaymentMethod ID: {}", payment_method_id))
         })?;
-        let pm = timed(PaymentMethod::retrieve(&self.client, &pm_id, &[])).await?;
+        let pm = self.send(None, |c| PaymentMethod::retrieve(c, &pm_id, &[])).await?;
         let (brand, last4, exp_month, exp_year) = pm.card
             .as_ref()
             .map(|c| (
@@ -496,7 +590,8 @@ impl StripeGateway for RealStripeGateway {
         let pm_id: PaymentMethodId = payment_method_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid PaymentMethod ID: {}", payment_method_id))
         })?;
-        timed(PaymentMethod::detach(&self.client, &pm_id)).await?;
+        self.send(Self::fresh_key(), |c| PaymentMethod::detach(c, &pm_id))
+            .await?;
         Ok(())
     }
 
@@ -507,10 +602,11 @@ impl StripeGateway for RealStripeGateway {
         let mut params = CreateRefund::new();
         params.payment_intent = Some(pi_id);
 
-        let idempotent_client = self.client.clone().with_strategy(
-            RequestStrategy::Idempotent(input.idempotency_key.clone())
-        );
-        let refund = timed(Refund::create(&idempotent_client, params)).await
+        let refund = self
+            .send(Some(input.idempotency_key.clone()), |c| {
+                Refund::create(c, params.clone())
+            })
+            .await
             .map_err(|e| match e {
                 AppError::External(msg) => AppError::External(format!("Stripe refund failed: {}", msg)),
                 other => other,
@@ -522,7 +618,8 @@ impl StripeGateway for RealStripeGateway {
         let sub_id: SubscriptionId = subscription_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid subscription ID: {}", subscription_id))
         })?;
-        timed(Subscription::delete(&self.client, &sub_id)).await
+        self.send(None, |c| Subscription::delete(c, &sub_id))
+            .await
             .map_err(|e| match e {
                 AppError::External(msg) => AppError::External(format!("Stripe cancel failed: {}", msg)),
                 other => other,
@@ -534,7 +631,7 @@ impl StripeGateway for RealStripeGateway {
         let inv_id: InvoiceId = invoice_id.parse().map_err(|_| {
             AppError::BadRequest(format!("Invalid invoice ID: {}", invoice_id))
         })?;
-        let invoice = timed(Invoice::retrieve(&self.client, &inv_id, &[])).await?;
+        let invoice = self.send(None, |c| Invoice::retrieve(c, &inv_id, &[])).await?;
         Ok(RetrievedInvoice {
             payment_intent_id: invoice.payment_intent.map(|exp| exp.id().to_string()),
         })
@@ -1,9 +1,11 @@
 pub mod gateway;
 pub mod stripe_client;
 pub mod webhook_dispatcher;
+pub mod webhook_metrics;
 
 #[cfg(any(test, feature = "test-utils"))]
 pub mod fake_gateway;
 
 pub use stripe_client::StripeClient;
-pub use webhook_dispatcher::WebhookDispatcher;
\ No newline at end of file
+pub use webhook_dispatcher::WebhookDispatcher;
+pub use webhook_metrics::WebhookMetrics;
\ No newline at end of file
@@ -8,7 +8,7 @@ use crate::{
     payments::gateway::{
         CreateCheckoutInput, CreateCustomerInput, CreatePaymentIntentInput,
         CreateRefundInput, CreateSetupIntentInput, LineItemInput,
-        PaymentIntentResult, StripeGateway,
+        PaymentIntentResult, StripeGateway, StripeRetryPolicy,
     },
     repository::{MemberRepository, PaymentRepository},
 };
@@ -27,14 +27,15 @@ pub struct StripeClient {
 
 impl StripeClient {
     /// Production constructor: builds a `RealStripeGateway` from the
-    /// API key.
+    /// API key and the configured timeout/retry policy.
     pub fn new(
         api_key: String,
+        policy: StripeRetryPolicy,
         payment_repo: Arc<dyn PaymentRepository>,
         member_repo: Arc<dyn MemberRepository>,
     ) -> Self {
         let gateway: Arc<dyn StripeGateway> =
-            Arc::new(crate::payments::gateway::RealStripeGateway::new(api_key));
+            Arc::new(crate::payments::gateway::RealStripeGateway::new(api_key, policy));
         Self::with_gateway(gateway, payment_repo, member_repo)
     }
 
@@ -101,6 +102,7 @@ impl StripeClient {
             description: format!("{} Membership Payment", membership_type_name),
             kind: PaymentKind::Membership,
             paid_at: None,
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -163,6 +165,7 @@ impl StripeClient {
             description: product_name,
             kind: PaymentKind::Donation { campaign_id },
             paid_at: None,
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -237,6 +240,7 @@ impl StripeClient {
             description: format!("{} — {}", product_name, donor_name),
             kind: PaymentKind::Donation { campaign_id },
             paid_at: None,
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -313,7 +317,12 @@ impl StripeClient {
     /// row even if the synchronous response is lost — closing the
     /// "charged but no record" silent-loss case.
     ///
+    /// `membership_type_slug` is set for dues charges and also rides
+    /// on the metadata, so the webhook extends dues by the type that
+    /// was paid for rather than whatever the member holds by then.
+    ///
     /// Returns the PaymentIntent ID if successful.
+    #[allow(clippy::too_many_arguments)]
     pub async fn charge_saved_card(
         &self,
         member_id: Uuid,
@@ -322,6 +331,7 @@ impl StripeClient {
         description: &str,
         idempotency_key: &str,
         payment_id: Uuid,
+        membership_type_slug: Option<&str>,
     ) -> Result<String> {
         // Get the member's stripe_customer_id
         let customer_id = self.member_repo.find_by_id(member_id).await?
@@ -331,6 +341,9 @@ impl StripeClient {
         let mut metadata = std::collections::HashMap::new();
         metadata.insert("member_id".to_string(), member_id.to_string());
         metadata.insert("payment_id".to_string(), payment_id.to_string());
+        if let Some(slug) = membership_type_slug {
+            metadata.insert("membership_type_slug".to_string(), slug.to_string());
+        }
 
         let result = self.gateway.create_payment_intent(CreatePaymentIntentInput {
             amount_cents,
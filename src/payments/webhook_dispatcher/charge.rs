@@ -1,5 +1,5 @@
 use crate::{
-    domain::{Payment, PaymentStatus},
+    domain::{Money, Payment, PaymentStatus},
     error::Result,
     integrations::IntegrationEvent,
 };
@@ -86,13 +86,13 @@ impl WebhookDispatcher {
             self.integration_manager
                 .handle_event(IntegrationEvent::AdminAlert {
                     subject: format!(
-                        "Partial Stripe refund — payment {} (${:.2} of ${:.2})",
+                        "Partial Stripe refund — payment {} (${} of ${})",
                         payment.id,
-                        amount_refunded as f64 / 100.0,
-                        charge_amount as f64 / 100.0,
+                        Money::usd(amount_refunded),
+                        Money::usd(charge_amount),
                     ),
                     body: format!(
-                        "Stripe charge {} was partially refunded (${:.2} of ${:.2}).\n\n\
+                        "Stripe charge {} was partially refunded (${} of ${}).\n\n\
                      The local Coterie payment row {} is unchanged — partial \
                      refunds aren't supported in our admin UI because they \
                      muddle dues / campaign accounting.\n\n\
@@ -101,8 +101,8 @@ impl WebhookDispatcher {
                      to match. Otherwise investigate who issued the \
                      partial refund in Stripe's dashboard.",
                         charge.id,
-                        amount_refunded as f64 / 100.0,
-                        charge_amount as f64 / 100.0,
+                        Money::usd(amount_refunded),
+                        Money::usd(charge_amount),
                         payment.id,
                     ),
                 })
@@ -121,6 +121,8 @@ impl WebhookDispatcher {
 
         // Out-of-band full refund. Flip to Refunded.
         self.payment_repo.mark_refunded(payment.id).await?;
+        self.announce_payment(payment.id, IntegrationEvent::PaymentRefunded)
+            .await;
 
         tracing::info!(
             "Synced refund from Stripe dashboard: payment {} marked Refunded (charge {})",
@@ -44,8 +44,8 @@ impl WebhookDispatcher {
             .as_ref()
             .map(|exp| exp.id().to_string())
             .unwrap_or_else(|| session_id.clone());
-        let won_flip = self
-            .payment_repo
+        let won_flip = billing_service
+            .auto_renew
             .complete_pending_payment(payment.id, &pi_for_row)
             .await?;
         if !won_flip {
@@ -111,7 +111,7 @@ impl WebhookDispatcher {
         };
 
         // Look up the slug from metadata and run the dues-extend +
-        // reschedule-if-enrolled chain.
+        // activate-if-pending + reschedule-if-enrolled chain.
         let membership_type_slug = session
             .metadata
             .as_ref()
@@ -146,22 +146,8 @@ impl WebhookDispatcher {
         };
 
         if let Some(slug) = &resolved_slug {
-            billing_service
-                .auto_renew
-                .extend_member_dues_by_slug(payment.id, member_id, slug)
+            self.apply_membership_payment(payment.id, member_id, slug, billing_service)
                 .await?;
-
-            if let Err(e) = billing_service
-                .auto_renew
-                .reschedule_after_payment(member_id, slug)
-                .await
-            {
-                tracing::error!(
-                    "Member {} paid via Checkout but reschedule failed: {}",
-                    member_id,
-                    e,
-                );
-            }
         } else {
             tracing::error!(
                 "Couldn't resolve membership type for paid Checkout session {}; \
@@ -3,7 +3,7 @@ use uuid::Uuid;
 
 use crate::{
     domain::{
-        configurable_types::BillingPeriod, Payer, Payment, PaymentKind, PaymentMethod,
+        configurable_types::BillingPeriod, Money, Payer, Payment, PaymentKind, PaymentMethod,
         PaymentStatus, StripeRef,
     },
     error::Result,
@@ -103,11 +103,15 @@ impl WebhookDispatcher {
             description: format!("Subscription payment ({})", subscription_id),
             kind: PaymentKind::Membership,
             paid_at: Some(Utc::now()),
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
 
-        self.payment_repo.create(payment).await?;
+        let payment = self.payment_repo.create(payment).await?;
+        self.integration_manager
+            .handle_event(IntegrationEvent::PaymentCompleted(payment))
+            .await;
 
         // Extend dues - look up membership type from member's current type
         let membership_type_slug = self
@@ -206,7 +210,7 @@ impl WebhookDispatcher {
         // figure for "what we tried to charge"; fall back to amount_remaining.
         let amount_cents = invoice.amount_due.or(invoice.amount_remaining).unwrap_or(0);
         let amount_display = if amount_cents > 0 {
-            Some(format!("${:.2}", amount_cents as f64 / 100.0))
+            Some(format!("${}", Money::usd(amount_cents)))
         } else {
             None
         };
@@ -21,13 +21,18 @@ mod subscription;
 
 use std::sync::Arc;
 use stripe::{CheckoutSession, EventObject, EventType, Webhook, WebhookError};
+use uuid::Uuid;
 
 use crate::{
+    domain::Payment,
     error::{AppError, Result},
-    integrations::IntegrationManager,
-    payments::gateway::StripeGateway,
+    integrations::{IntegrationEvent, IntegrationManager},
+    payments::{gateway::StripeGateway, WebhookMetrics},
     repository::{MemberRepository, PaymentRepository, ProcessedEventsRepository},
-    service::{billing_service::BillingService, membership_type_service::MembershipTypeService},
+    service::{
+        billing_service::BillingService, member_service::MemberService,
+        membership_type_service::MembershipTypeService,
+    },
 };
 
 pub struct WebhookDispatcher {
@@ -42,9 +47,13 @@ pub struct WebhookDispatcher {
     processed_events_repo: Arc<dyn ProcessedEventsRepository>,
     membership_type_service: Arc<MembershipTypeService>,
     integration_manager: Arc<IntegrationManager>,
+    /// Activates Pending members whose signup Checkout session clears.
+    member_service: Arc<MemberService>,
+    metrics: Arc<WebhookMetrics>,
 }
 
 impl WebhookDispatcher {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         gateway: Arc<dyn StripeGateway>,
         webhook_secret: String,
@@ -53,6 +62,8 @@ impl WebhookDispatcher {
         processed_events_repo: Arc<dyn ProcessedEventsRepository>,
         membership_type_service: Arc<MembershipTypeService>,
         integration_manager: Arc<IntegrationManager>,
+        member_service: Arc<MemberService>,
+        metrics: Arc<WebhookMetrics>,
     ) -> Self {
         Self {
             gateway,
@@ -62,6 +73,8 @@ impl WebhookDispatcher {
             processed_events_repo,
             membership_type_service,
             integration_manager,
+            member_service,
+            metrics,
         }
     }
 
@@ -96,6 +109,7 @@ impl WebhookDispatcher {
             .claim(&event_id, &event_type)
             .await?;
         if !claimed {
+            self.metrics.record_deduplicated();
             tracing::info!("Skipping already-processed Stripe event {}", event_id);
             return Ok(());
         }
@@ -178,6 +192,9 @@ impl WebhookDispatcher {
         }
         .await;
 
+        if outcome.is_ok() {
+            self.metrics.record_processed();
+        }
         if let Err(e) = &outcome {
             tracing::error!(
                 "Webhook handler for event {} ({}) failed: {}; rolling back idempotency claim so Stripe retry can re-run",
@@ -202,6 +219,72 @@ impl WebhookDispatcher {
     }
 }
 
+impl WebhookDispatcher {
+    /// Tell integrations a payment reached a new state. Re-reads the
+    /// row so the event carries what the handler just wrote (status,
+    /// Stripe id, timestamps). Lookup failures are logged rather than
+    /// returned: the state change already happened, and an error here
+    /// would roll back the idempotency claim for finished work.
+    async fn announce_payment(&self, payment_id: Uuid, event: fn(Payment) -> IntegrationEvent) {
+        match self.payment_repo.find_by_id(payment_id).await {
+            Ok(Some(payment)) => self.integration_manager.handle_event(event(payment)).await,
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Couldn't reload payment {} to notify integrations: {}",
+                payment_id,
+                e,
+            ),
+        }
+    }
+
+    /// Post-work for a membership payment whose row this handler just
+    /// flipped to Completed: extend dues by the type's term, reschedule
+    /// auto-renew if enrolled, and activate the member if this was the
+    /// first payment after signup. Callers own the flip, so running
+    /// this at most once per payment is already guaranteed; the dues
+    /// extension's per-payment claim backs that up.
+    async fn apply_membership_payment(
+        &self,
+        payment_id: Uuid,
+        member_id: Uuid,
+        slug: &str,
+        billing_service: &BillingService,
+    ) -> Result<()> {
+        billing_service
+            .auto_renew
+            .extend_member_dues_by_slug(payment_id, member_id, slug)
+            .await?;
+
+        // First payment after public signup: the member is still
+        // Pending and this is what activates them. An abandoned
+        // Checkout session never reaches here (it expires instead), so
+        // the member stays Pending. Non-fatal — the payment is already
+        // Completed, so a rollback-and-retry wouldn't re-run this.
+        if let Err(e) = self.member_service.activate_paid_signup(member_id).await {
+            tracing::error!(
+                "Member {} paid signup dues (payment {}) but activation failed: {}",
+                member_id,
+                payment_id,
+                e,
+            );
+        }
+
+        if let Err(e) = billing_service
+            .auto_renew
+            .reschedule_after_payment(member_id, slug)
+            .await
+        {
+            tracing::error!(
+                "Member {} paid (payment {}) but reschedule failed: {}",
+                member_id,
+                payment_id,
+                e,
+            );
+        }
+        Ok(())
+    }
+}
+
 /// Test-only access to the per-event handlers that `handle_webhook`
 /// dispatches to. Production code must go through `handle_webhook` so
 /// that signature verification and the event-id idempotency claim
@@ -240,6 +323,10 @@ impl WebhookDispatcher {
             .await
     }
 
+    pub async fn dispatch_checkout_session_expired(&self, session: CheckoutSession) -> Result<()> {
+        self.handle_expired_session(session).await
+    }
+
     pub async fn dispatch_invoice_paid(
         &self,
         invoice: stripe::Invoice,
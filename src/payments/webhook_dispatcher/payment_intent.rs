@@ -4,6 +4,7 @@ use uuid::Uuid;
 use crate::{
     domain::{PaymentKind, PaymentStatus},
     error::{AppError, Result},
+    integrations::IntegrationEvent,
     service::billing_service::BillingService,
 };
 
@@ -16,12 +17,18 @@ impl WebhookDispatcher {
             .find_by_stripe_id(&stripe_payment_id)
             .await?
         {
+            let already_failed = payment.status == PaymentStatus::Failed;
             payment.status = PaymentStatus::Failed;
             payment.updated_at = Utc::now();
 
-            self.payment_repo.update(payment.id, payment).await?;
+            let payment = self.payment_repo.update(payment.id, payment).await?;
 
             tracing::warn!("Payment failed: {}", stripe_payment_id);
+            if !already_failed {
+                self.integration_manager
+                    .handle_event(IntegrationEvent::PaymentFailed(payment))
+                    .await;
+            }
         }
 
         Ok(())
@@ -125,8 +132,8 @@ impl WebhookDispatcher {
         }
 
         let pi_id = intent.id.to_string();
-        let won_flip = self
-            .payment_repo
+        let won_flip = billing_service
+            .auto_renew
             .complete_pending_payment(payment_id, &pi_id)
             .await?;
         if !won_flip {
@@ -146,10 +153,9 @@ impl WebhookDispatcher {
 
         // Post-work depends on payment kind. Donations have none —
         // the row flip is the entire job. Membership payments need
-        // dues extended and (if auto-renew enrolled) the next renewal
-        // rescheduled. We look up the slug from the member's current
-        // membership_type since saved-card charges don't carry it on
-        // the Payment row.
+        // dues extended, the member activated if this was their first
+        // payment, and (if auto-renew enrolled) the next renewal
+        // rescheduled.
         if matches!(payment.kind, PaymentKind::Membership) {
             // Membership payments must have a member; data integrity
             // violation otherwise (CHECK constraint should prevent it).
@@ -176,33 +182,28 @@ impl WebhookDispatcher {
                     return Ok(());
                 }
             };
-            let mt_id = member.membership_type_id;
-            let mt = self.membership_type_service.get(mt_id).await?;
-            let slug = match mt {
-                Some(t) => t.slug,
+            // Prefer the slug charge_saved_card stamped on the PI: it's
+            // the type that was actually paid for, even if an admin
+            // changed the member's type since. Older PIs don't carry
+            // it, so fall back to the member's current type.
+            let slug = match intent.metadata.get("membership_type_slug") {
+                Some(slug) => slug.clone(),
                 None => {
-                    tracing::warn!(
-                        "Member {}'s membership_type {} not found; can't extend dues for self-healed payment {}",
-                        member_id, mt_id, payment_id,
-                    );
-                    return Ok(());
+                    let mt_id = member.membership_type_id;
+                    match self.membership_type_service.get(mt_id).await? {
+                        Some(t) => t.slug,
+                        None => {
+                            tracing::warn!(
+                                "Member {}'s membership_type {} not found; can't extend dues for self-healed payment {}",
+                                member_id, mt_id, payment_id,
+                            );
+                            return Ok(());
+                        }
+                    }
                 }
             };
-            billing_service
-                .auto_renew
-                .extend_member_dues_by_slug(payment_id, member_id, &slug)
+            self.apply_membership_payment(payment_id, member_id, &slug, billing_service)
                 .await?;
-            if let Err(e) = billing_service
-                .auto_renew
-                .reschedule_after_payment(member_id, &slug)
-                .await
-            {
-                tracing::error!(
-                    "Self-healed payment {} but reschedule failed: {}",
-                    payment_id,
-                    e,
-                );
-            }
         }
 
         Ok(())
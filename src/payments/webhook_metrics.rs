@@ -0,0 +1,49 @@
+//! Counters for the Stripe webhook idempotency table.
+//!
+//! `processed` and `deduplicated` are bumped by `WebhookDispatcher` as
+//! events arrive; `pruned` by the hourly cleanup task. They live in
+//! memory and start from zero on every restart, which is all the
+//! billing dashboard needs to tell "Stripe is redelivering a lot" or
+//! "the prune job is running" at a glance.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Default)]
+pub struct WebhookMetrics {
+    processed: AtomicU64,
+    deduplicated: AtomicU64,
+    pruned: AtomicU64,
+}
+
+/// A point-in-time read of [`WebhookMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WebhookCounts {
+    /// Events claimed and handled without error.
+    pub processed: u64,
+    /// Redeliveries skipped because the event was already claimed.
+    pub deduplicated: u64,
+    /// Claims removed by the retention prune.
+    pub pruned: u64,
+}
+
+impl WebhookMetrics {
+    pub fn record_processed(&self) {
+        self.processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_deduplicated(&self) {
+        self.deduplicated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_pruned(&self, count: u64) {
+        self.pruned.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> WebhookCounts {
+        WebhookCounts {
+            processed: self.processed.load(Ordering::Relaxed),
+            deduplicated: self.deduplicated.load(Ordering::Relaxed),
+            pruned: self.pruned.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -1,20 +1,125 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc, NaiveDateTime};
 use sqlx::{SqlitePool, FromRow};
 use uuid::Uuid;
 
+use super::malformed_rows::skip_malformed;
+use super::SortOrder;
 use crate::{
-    domain::{Announcement, AnnouncementType},
+    domain::{Announcement, AnnouncementType, ParseEnumError},
     error::{AppError, Result},
 };
 
+/// Which announcements the admin list shows, and in what order. Built
+/// from the announcements page's query string and handed to
+/// `search`/`count`, the same way `MemberFilter` drives the roster.
+/// Pagination is the caller's business, not the filter's.
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementFilter {
+    /// Case-insensitive substring match on `title` and `content`.
+    /// `None` or empty string skips the filter.
+    pub search: Option<String>,
+    /// Filter to one legacy `announcement_type`. `None` skips.
+    pub announcement_type: Option<AnnouncementType>,
+    pub status: Option<AnnouncementStatusFilter>,
+    pub sort: AnnouncementSort,
+    pub order: SortOrder,
+}
+
+/// The admin list's `?status=` choices. Not exclusive states:
+/// "featured" and "public" cut across published and draft.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnouncementStatusFilter {
+    Published,
+    Draft,
+    Featured,
+    Public,
+    /// Past its `expires_at`.
+    Expired,
+}
+
+impl FromStr for AnnouncementStatusFilter {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "published" => Ok(AnnouncementStatusFilter::Published),
+            "draft" => Ok(AnnouncementStatusFilter::Draft),
+            "featured" => Ok(AnnouncementStatusFilter::Featured),
+            "public" => Ok(AnnouncementStatusFilter::Public),
+            "expired" => Ok(AnnouncementStatusFilter::Expired),
+            _ => Err(ParseEnumError::new("announcement status filter", s)),
+        }
+    }
+}
+
+/// Admin list sort column. The wire names are the `?sort=` values,
+/// and the ones `list_defaults::ANNOUNCEMENT_SORT` allows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnnouncementSort {
+    #[default]
+    CreatedAt,
+    PublishedAt,
+    Title,
+    Type,
+}
+
+impl FromStr for AnnouncementSort {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "created_at" => Ok(AnnouncementSort::CreatedAt),
+            "published_at" => Ok(AnnouncementSort::PublishedAt),
+            "title" => Ok(AnnouncementSort::Title),
+            "type" => Ok(AnnouncementSort::Type),
+            _ => Err(ParseEnumError::new("announcement sort", s)),
+        }
+    }
+}
+
 #[async_trait]
 pub trait AnnouncementRepository: Send + Sync {
     async fn create(&self, announcement: Announcement) -> Result<Announcement>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Announcement>>;
+    /// Every announcement, drafts and expired ones included. Admin use.
     async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Announcement>>;
+    /// One page of the announcements matching `filter`, in its order.
+    /// Used by the admin announcements page; pair with `count` for the
+    /// total.
+    async fn search(
+        &self,
+        filter: &AnnouncementFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Announcement>>;
+    /// How many announcements match `filter`.
+    async fn count(&self, filter: &AnnouncementFilter) -> Result<i64>;
+    /// Published, unexpired announcements, newest first.
     async fn list_recent(&self, limit: i64) -> Result<Vec<Announcement>>;
+    /// Published, unexpired, public announcements, newest first.
     async fn list_public(&self) -> Result<Vec<Announcement>>;
+    /// One page of `list_public`, optionally narrowed to one type.
+    /// Returns `(rows, total_match_count)` like `MemberRepository::search`.
+    async fn list_public_page(
+        &self,
+        announcement_type: Option<&AnnouncementType>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Announcement>, i64)>;
+    /// Published, unexpired public announcements whose configured type
+    /// (`announcement_type_id`) is one of `type_ids`, newest first.
+    async fn list_public_of_types(
+        &self,
+        type_ids: &[Uuid],
+        limit: i64,
+    ) -> Result<Vec<Announcement>>;
+    /// Published, unexpired, featured announcements, newest first.
+    /// Members-only ones are left out unless `include_private`.
+    async fn list_featured(&self, include_private: bool, limit: i64) -> Result<Vec<Announcement>>;
+    /// Published, unexpired, members-only announcements.
     async fn count_private_published(&self) -> Result<i64>;
     async fn update(&self, id: Uuid, announcement: Announcement) -> Result<Announcement>;
     async fn delete(&self, id: Uuid) -> Result<()>;
@@ -25,6 +130,16 @@ pub trait AnnouncementRepository: Send + Sync {
     /// was claimed (status was still Draft); `false` if someone else
     /// already flipped it. Used by the runner to avoid double-dispatch.
     async fn mark_published_now(&self, id: Uuid) -> Result<bool>;
+    /// Stamp `notified_at` ahead of an `AnnouncementPublished`
+    /// dispatch. Returns `true` iff the caller should broadcast: the
+    /// row hadn't been notified yet, or `again` is set. Conditional
+    /// like `mark_published_now`, so two publish paths racing on the
+    /// same row broadcast once.
+    async fn mark_notified(&self, id: Uuid, again: bool) -> Result<bool>;
+    /// Whether announcements of this type notify members on publish
+    /// when the admin didn't choose. `true` for an unknown type.
+    async fn type_notifies_members(&self, type_id: Uuid) -> Result<bool>;
+    async fn set_type_notifies_members(&self, type_id: Uuid, notify: bool) -> Result<()>;
 }
 
 #[derive(FromRow)]
@@ -39,6 +154,10 @@ struct AnnouncementRow {
     image_url: Option<String>,
     published_at: Option<NaiveDateTime>,
     scheduled_publish_at: Option<NaiveDateTime>,
+    expires_at: Option<NaiveDateTime>,
+    comments_enabled: i32,
+    notify_members: Option<i32>,
+    notified_at: Option<NaiveDateTime>,
     created_by: String,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
@@ -64,51 +183,102 @@ impl SqliteAnnouncementRepository {
             id: Uuid::parse_str(&row.id).map_err(|e| AppError::Internal(e.to_string()))?,
             title: row.title,
             content: row.content,
-            announcement_type: Self::parse_announcement_type(&row.announcement_type)?,
+            announcement_type: row.announcement_type.parse()?,
             announcement_type_id,
             is_public: row.is_public != 0,
             featured: row.featured != 0,
             image_url: row.image_url,
             published_at: row.published_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             scheduled_publish_at: row.scheduled_publish_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            expires_at: row.expires_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            comments_enabled: row.comments_enabled != 0,
+            notify_members: row.notify_members.map(|n| n != 0),
+            notified_at: row.notified_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             created_by: Uuid::parse_str(&row.created_by).map_err(|e| AppError::Internal(e.to_string()))?,
             created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
         })
     }
+}
 
-    fn parse_announcement_type(s: &str) -> Result<AnnouncementType> {
-        match s {
-            "News" => Ok(AnnouncementType::News),
-            "Achievement" => Ok(AnnouncementType::Achievement),
-            "Meeting" => Ok(AnnouncementType::Meeting),
-            "CTFResult" => Ok(AnnouncementType::CTFResult),
-            "General" => Ok(AnnouncementType::General),
-            _ => Err(AppError::Internal(format!("Invalid announcement type: {}", s))),
-        }
+/// `WHERE` clause (with a leading space, or empty) and its bound
+/// values for an `AnnouncementFilter`. User-provided values always
+/// bind.
+fn filter_where(filter: &AnnouncementFilter) -> (String, Vec<String>) {
+    let mut clauses: Vec<&str> = Vec::new();
+    let mut binds = Vec::new();
+    if let Some(pat) = filter
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| format!("%{}%", escape_like(&s.to_lowercase())))
+    {
+        clauses.push("(LOWER(title) LIKE ? ESCAPE '\\' OR LOWER(content) LIKE ? ESCAPE '\\')");
+        binds.extend([pat.clone(), pat]);
     }
-
-    fn announcement_type_to_str(announcement_type: &AnnouncementType) -> &'static str {
-        match announcement_type {
-            AnnouncementType::News => "News",
-            AnnouncementType::Achievement => "Achievement",
-            AnnouncementType::Meeting => "Meeting",
-            AnnouncementType::CTFResult => "CTFResult",
-            AnnouncementType::General => "General",
+    if let Some(announcement_type) = &filter.announcement_type {
+        clauses.push("announcement_type = ?");
+        binds.push(announcement_type.as_str().to_string());
+    }
+    match filter.status {
+        Some(AnnouncementStatusFilter::Published) => clauses.push("published_at IS NOT NULL"),
+        Some(AnnouncementStatusFilter::Draft) => clauses.push("published_at IS NULL"),
+        Some(AnnouncementStatusFilter::Featured) => clauses.push("featured = 1"),
+        Some(AnnouncementStatusFilter::Public) => clauses.push("is_public = 1"),
+        Some(AnnouncementStatusFilter::Expired) => {
+            clauses.push("expires_at IS NOT NULL AND expires_at <= ?");
+            // The text sqlx stores a `NaiveDateTime` as, so the
+            // comparison is the same one a bound timestamp makes.
+            binds.push(Utc::now().naive_utc().format("%F %T%.f").to_string());
         }
+        None => {}
     }
+    let sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", clauses.join(" AND "))
+    };
+    (sql, binds)
+}
+
+/// Escape `LIKE` wildcards so a search for "50%" or "a_b" matches
+/// those characters literally. Pairs with `ESCAPE '\'` in the query.
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// `ORDER BY` for an `AnnouncementFilter`. Sort field/direction map to
+/// constant strings; the id tiebreak keeps pages stable. Drafts have
+/// no `published_at`, so they sort first ascending and last
+/// descending.
+fn filter_order(filter: &AnnouncementFilter) -> String {
+    let dir = match filter.order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+    let column = match filter.sort {
+        AnnouncementSort::CreatedAt => "created_at",
+        AnnouncementSort::PublishedAt => "published_at",
+        AnnouncementSort::Title => "LOWER(title)",
+        AnnouncementSort::Type => "announcement_type",
+    };
+    format!("{} {}, id", column, dir)
 }
 
 #[async_trait]
 impl AnnouncementRepository for SqliteAnnouncementRepository {
     async fn create(&self, announcement: Announcement) -> Result<Announcement> {
         let id_str = announcement.id.to_string();
-        let announcement_type_str = Self::announcement_type_to_str(&announcement.announcement_type);
+        let announcement_type_str = announcement.announcement_type.as_str();
         let announcement_type_id_str = announcement.announcement_type_id.map(|id| id.to_string());
         let is_public_int = if announcement.is_public { 1i32 } else { 0i32 };
         let featured_int = if announcement.featured { 1i32 } else { 0i32 };
         let published_at_naive = announcement.published_at.map(|dt| dt.naive_utc());
         let scheduled_publish_at_naive = announcement.scheduled_publish_at.map(|dt| dt.naive_utc());
+        let expires_at_naive = announcement.expires_at.map(|dt| dt.naive_utc());
         let created_by_str = announcement.created_by.to_string();
         let now = Utc::now().naive_utc();
 
@@ -116,8 +286,9 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
             r#"
             INSERT INTO announcements (
                 id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                notify_members, notified_at, created_by, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id_str)
@@ -130,6 +301,10 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         .bind(&announcement.image_url)
         .bind(published_at_naive)
         .bind(scheduled_publish_at_naive)
+        .bind(expires_at_naive)
+        .bind(announcement.comments_enabled)
+        .bind(announcement.notify_members)
+        .bind(announcement.notified_at.map(|dt| dt.naive_utc()))
         .bind(&created_by_str)
         .bind(now)
         .bind(now)
@@ -147,7 +322,8 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         let row = sqlx::query_as::<_, AnnouncementRow>(
             r#"
             SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                   image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
             FROM announcements
             WHERE id = ?
             "#
@@ -167,7 +343,8 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         let rows = sqlx::query_as::<_, AnnouncementRow>(
             r#"
             SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                   image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
             FROM announcements
             ORDER BY created_at DESC
             LIMIT ? OFFSET ?
@@ -179,49 +356,201 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_announcement)
-            .collect()
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
+    }
+
+    async fn search(
+        &self,
+        filter: &AnnouncementFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Announcement>> {
+        let (where_sql, binds) = filter_where(filter);
+        let sql = format!(
+            "SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured, \
+                    image_url, published_at, scheduled_publish_at, expires_at, comments_enabled, \
+                    notify_members, notified_at, created_by, created_at, updated_at \
+             FROM announcements{} \
+             ORDER BY {} \
+             LIMIT ? OFFSET ?",
+            where_sql,
+            filter_order(filter),
+        );
+        let mut q = sqlx::query_as::<_, AnnouncementRow>(&sql);
+        for b in &binds {
+            q = q.bind(b);
+        }
+        let rows = q
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
+    }
+
+    async fn count(&self, filter: &AnnouncementFilter) -> Result<i64> {
+        let (where_sql, binds) = filter_where(filter);
+        let sql = format!("SELECT COUNT(*) FROM announcements{}", where_sql);
+        let mut q = sqlx::query_scalar::<_, i64>(&sql);
+        for b in &binds {
+            q = q.bind(b);
+        }
+        q.fetch_one(&self.pool).await.map_err(AppError::Database)
     }
 
     async fn list_recent(&self, limit: i64) -> Result<Vec<Announcement>> {
         let rows = sqlx::query_as::<_, AnnouncementRow>(
             r#"
             SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                   image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
             FROM announcements
             WHERE published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
             ORDER BY published_at DESC
             LIMIT ?
             "#
         )
+        .bind(Utc::now().naive_utc())
         .bind(limit)
         .fetch_all(&self.pool)
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_announcement)
-            .collect()
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
     }
 
     async fn list_public(&self) -> Result<Vec<Announcement>> {
         let rows = sqlx::query_as::<_, AnnouncementRow>(
             r#"
             SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                   image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
             FROM announcements
             WHERE is_public = 1 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
             ORDER BY published_at DESC
             "#
         )
+        .bind(Utc::now().naive_utc())
         .fetch_all(&self.pool)
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_announcement)
-            .collect()
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
+    }
+
+    async fn list_public_page(
+        &self,
+        announcement_type: Option<&AnnouncementType>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Announcement>, i64)> {
+        let type_str = announcement_type.map(|t| t.as_str());
+        let now = Utc::now().naive_utc();
+
+        let rows = sqlx::query_as::<_, AnnouncementRow>(
+            r#"
+            SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
+            FROM announcements
+            WHERE is_public = 1 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
+              AND (? IS NULL OR announcement_type = ?)
+            ORDER BY published_at DESC, id
+            LIMIT ? OFFSET ?
+            "#
+        )
+        .bind(now)
+        .bind(type_str)
+        .bind(type_str)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM announcements
+            WHERE is_public = 1 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
+              AND (? IS NULL OR announcement_type = ?)
+            "#
+        )
+        .bind(now)
+        .bind(type_str)
+        .bind(type_str)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        let announcements =
+            skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement);
+        Ok((announcements, total))
+    }
+
+    async fn list_public_of_types(
+        &self,
+        type_ids: &[Uuid],
+        limit: i64,
+    ) -> Result<Vec<Announcement>> {
+        if type_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders = vec!["?"; type_ids.len()].join(", ");
+        let sql = format!(
+            r#"
+            SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
+            FROM announcements
+            WHERE is_public = 1 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
+              AND announcement_type_id IN ({})
+            ORDER BY published_at DESC, id
+            LIMIT ?
+            "#,
+            placeholders,
+        );
+        let mut query = sqlx::query_as::<_, AnnouncementRow>(&sql).bind(Utc::now().naive_utc());
+        for id in type_ids {
+            query = query.bind(id.to_string());
+        }
+        let rows = query
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
+    }
+
+    async fn list_featured(&self, include_private: bool, limit: i64) -> Result<Vec<Announcement>> {
+        let rows = sqlx::query_as::<_, AnnouncementRow>(
+            r#"
+            SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
+            FROM announcements
+            WHERE featured = 1 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
+              AND (is_public = 1 OR ?)
+            ORDER BY published_at DESC, id
+            LIMIT ?
+            "#
+        )
+        .bind(Utc::now().naive_utc())
+        .bind(include_private)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
     }
 
     async fn count_private_published(&self) -> Result<i64> {
@@ -230,8 +559,10 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
             SELECT COUNT(*) as count
             FROM announcements
             WHERE is_public = 0 AND published_at IS NOT NULL
+              AND (expires_at IS NULL OR expires_at > ?)
             "#
         )
+        .bind(Utc::now().naive_utc())
         .fetch_one(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -241,12 +572,13 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
 
     async fn update(&self, id: Uuid, announcement: Announcement) -> Result<Announcement> {
         let id_str = id.to_string();
-        let announcement_type_str = Self::announcement_type_to_str(&announcement.announcement_type);
+        let announcement_type_str = announcement.announcement_type.as_str();
         let announcement_type_id_str = announcement.announcement_type_id.map(|id| id.to_string());
         let is_public_int = if announcement.is_public { 1i32 } else { 0i32 };
         let featured_int = if announcement.featured { 1i32 } else { 0i32 };
         let published_at_naive = announcement.published_at.map(|dt| dt.naive_utc());
         let scheduled_publish_at_naive = announcement.scheduled_publish_at.map(|dt| dt.naive_utc());
+        let expires_at_naive = announcement.expires_at.map(|dt| dt.naive_utc());
         let now = Utc::now().naive_utc();
 
         sqlx::query(
@@ -254,7 +586,8 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
             UPDATE announcements
             SET title = ?, content = ?, announcement_type = ?, announcement_type_id = ?,
                 is_public = ?, featured = ?, image_url = ?, published_at = ?,
-                scheduled_publish_at = ?, updated_at = ?
+                scheduled_publish_at = ?, expires_at = ?, comments_enabled = ?,
+                notify_members = ?, updated_at = ?
             WHERE id = ?
             "#
         )
@@ -267,6 +600,9 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         .bind(&announcement.image_url)
         .bind(published_at_naive)
         .bind(scheduled_publish_at_naive)
+        .bind(expires_at_naive)
+        .bind(announcement.comments_enabled)
+        .bind(announcement.notify_members)
         .bind(now)
         .bind(&id_str)
         .execute(&self.pool)
@@ -294,7 +630,8 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         let rows = sqlx::query_as::<_, AnnouncementRow>(
             r#"
             SELECT id, title, content, announcement_type, announcement_type_id, is_public, featured,
-                   image_url, published_at, scheduled_publish_at, created_by, created_at, updated_at
+                   image_url, published_at, scheduled_publish_at, expires_at, comments_enabled,
+                   notify_members, notified_at, created_by, created_at, updated_at
             FROM announcements
             WHERE published_at IS NULL
               AND scheduled_publish_at IS NOT NULL
@@ -307,9 +644,7 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_announcement)
-            .collect()
+        Ok(skip_malformed(rows, "announcements", |r| r.id.clone(), Self::row_to_announcement))
     }
 
     async fn mark_published_now(&self, id: Uuid) -> Result<bool> {
@@ -335,4 +670,43 @@ impl AnnouncementRepository for SqliteAnnouncementRepository {
 
         Ok(result.rows_affected() > 0)
     }
-}
\ No newline at end of file
+
+    async fn mark_notified(&self, id: Uuid, again: bool) -> Result<bool> {
+        let now = Utc::now().naive_utc();
+        let result = sqlx::query(
+            "UPDATE announcements SET notified_at = ? \
+             WHERE id = ? AND (notified_at IS NULL OR ?)",
+        )
+        .bind(now)
+        .bind(id.to_string())
+        .bind(again)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn type_notifies_members(&self, type_id: Uuid) -> Result<bool> {
+        let notify: Option<bool> = sqlx::query_scalar(
+            "SELECT notify_members FROM announcement_types WHERE id = ?",
+        )
+        .bind(type_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(notify.unwrap_or(true))
+    }
+
+    async fn set_type_notifies_members(&self, type_id: Uuid, notify: bool) -> Result<()> {
+        sqlx::query("UPDATE announcement_types SET notify_members = ? WHERE id = ?")
+            .bind(notify)
+            .bind(type_id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+        Ok(())
+    }
+}
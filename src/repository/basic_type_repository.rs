@@ -32,21 +32,28 @@ struct BasicTypeRow {
     is_active: i32,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
+    created_by: Option<String>,
+    updated_by: Option<String>,
 }
 
 #[async_trait]
 pub trait BasicTypeRepository: Send + Sync {
+    /// `actor` is the admin creating the type, recorded as both
+    /// `created_by` and `updated_by`; `None` for seeded types.
     async fn create(
         &self,
         kind: BasicTypeKind,
+        actor: Option<Uuid>,
         request: CreateBasicTypeRequest,
     ) -> Result<BasicType>;
     async fn find_by_id(&self, kind: BasicTypeKind, id: Uuid) -> Result<Option<BasicType>>;
     async fn find_by_slug(&self, kind: BasicTypeKind, slug: &str) -> Result<Option<BasicType>>;
     async fn list(&self, kind: BasicTypeKind, include_inactive: bool) -> Result<Vec<BasicType>>;
+    /// `actor` replaces `updated_by`.
     async fn update(
         &self,
         kind: BasicTypeKind,
+        actor: Option<Uuid>,
         id: Uuid,
         request: UpdateBasicTypeRequest,
     ) -> Result<BasicType>;
@@ -76,15 +83,25 @@ impl SqliteBasicTypeRepository {
             is_active: row.is_active != 0,
             created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
+            created_by: parse_actor(row.created_by)?,
+            updated_by: parse_actor(row.updated_by)?,
         })
     }
 }
 
+fn parse_actor(id: Option<String>) -> Result<Option<Uuid>> {
+    id.as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
 #[async_trait]
 impl BasicTypeRepository for SqliteBasicTypeRepository {
     async fn create(
         &self,
         kind: BasicTypeKind,
+        actor: Option<Uuid>,
         request: CreateBasicTypeRequest,
     ) -> Result<BasicType> {
         let id = Uuid::new_v4();
@@ -96,8 +113,9 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
         let sql = format!(
             "INSERT INTO {} (\
                 id, name, slug, description, color, icon, \
-                sort_order, is_active, created_at, updated_at\
-             ) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)",
+                sort_order, is_active, created_at, updated_at, \
+                created_by, updated_by\
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?)",
             kind.table()
         );
 
@@ -111,6 +129,8 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
             .bind(sort_order)
             .bind(now)
             .bind(now)
+            .bind(actor.map(|id| id.to_string()))
+            .bind(actor.map(|id| id.to_string()))
             .execute(&self.pool)
             .await
             .map_err(AppError::Database)?;
@@ -127,7 +147,8 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
         let id_str = id.to_string();
         let sql = format!(
             "SELECT id, name, slug, description, color, icon, \
-                    sort_order, is_active, created_at, updated_at \
+                    sort_order, is_active, created_at, updated_at, \
+                    created_by, updated_by \
              FROM {} \
              WHERE id = ?",
             kind.table()
@@ -151,7 +172,8 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
     ) -> Result<Option<BasicType>> {
         let sql = format!(
             "SELECT id, name, slug, description, color, icon, \
-                    sort_order, is_active, created_at, updated_at \
+                    sort_order, is_active, created_at, updated_at, \
+                    created_by, updated_by \
              FROM {} \
              WHERE slug = ?",
             kind.table()
@@ -176,7 +198,8 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
         let sql = if include_inactive {
             format!(
                 "SELECT id, name, slug, description, color, icon, \
-                        sort_order, is_active, created_at, updated_at \
+                        sort_order, is_active, created_at, updated_at, \
+                    created_by, updated_by \
                  FROM {} \
                  ORDER BY sort_order ASC, name ASC",
                 kind.table()
@@ -184,7 +207,8 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
         } else {
             format!(
                 "SELECT id, name, slug, description, color, icon, \
-                        sort_order, is_active, created_at, updated_at \
+                        sort_order, is_active, created_at, updated_at, \
+                    created_by, updated_by \
                  FROM {} \
                  WHERE is_active = 1 \
                  ORDER BY sort_order ASC, name ASC",
@@ -203,6 +227,7 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
     async fn update(
         &self,
         kind: BasicTypeKind,
+        actor: Option<Uuid>,
         id: Uuid,
         request: UpdateBasicTypeRequest,
     ) -> Result<BasicType> {
@@ -226,7 +251,7 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
         let sql = format!(
             "UPDATE {} \
              SET name = ?, description = ?, color = ?, icon = ?, \
-                 sort_order = ?, is_active = ?, updated_at = ? \
+                 sort_order = ?, is_active = ?, updated_at = ?, updated_by = ? \
              WHERE id = ?",
             kind.table()
         );
@@ -239,6 +264,7 @@ impl BasicTypeRepository for SqliteBasicTypeRepository {
             .bind(sort_order)
             .bind(if is_active { 1i32 } else { 0i32 })
             .bind(now)
+            .bind(actor.map(|id| id.to_string()))
             .bind(&id_str)
             .execute(&self.pool)
             .await
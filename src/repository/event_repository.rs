@@ -3,8 +3,9 @@ use chrono::{DateTime, Utc, NaiveDateTime};
 use sqlx::{SqlitePool, FromRow};
 use uuid::Uuid;
 
+use super::malformed_rows::skip_malformed;
 use crate::{
-    domain::{AttendanceStatus, Event, EventType, EventVisibility},
+    domain::{AttendanceStatus, Event, EventStatus, EventVisibility},
     error::{AppError, Result},
 };
 
@@ -21,6 +22,41 @@ pub struct EventReminderRow {
     pub member_id: Uuid,
     pub member_email: String,
     pub member_full_name: String,
+    /// Member's display-locale override, if any (see `Locale::resolve`).
+    pub member_locale: Option<String>,
+}
+
+/// One RSVP on an event, member or guest, for the admin attendee
+/// export. Name and email come from `members` for a member row and
+/// from the captured contact fields for a guest.
+#[derive(Debug, Clone)]
+pub struct EventAttendeeRow {
+    /// `None` for a guest.
+    pub member_id: Option<Uuid>,
+    pub name: String,
+    pub email: String,
+    pub status: AttendanceStatus,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl EventAttendeeRow {
+    pub fn is_guest(&self) -> bool {
+        self.member_id.is_none()
+    }
+}
+
+/// One of a member's RSVPs joined to its event, for the "events
+/// attended" history on the admin member detail and the member's own
+/// profile.
+#[derive(Debug, Clone)]
+pub struct MemberAttendanceRow {
+    pub event_id: Uuid,
+    pub event_title: String,
+    pub event_start: DateTime<Utc>,
+    pub event_location: Option<String>,
+    pub status: AttendanceStatus,
+    /// Checked in at the door (`event_attendance.attended`).
+    pub attended: bool,
 }
 
 #[async_trait]
@@ -33,28 +69,107 @@ pub trait EventRepository: Send + Sync {
     async fn list_members_only(&self) -> Result<Vec<Event>>;
     async fn count_members_only_upcoming(&self) -> Result<i64>;
     async fn update(&self, id: Uuid, event: Event) -> Result<Event>;
+    /// Delete the event. Its RSVPs, reminder log and co-hosts go with
+    /// it through their `ON DELETE CASCADE` foreign keys, so the pool
+    /// must have `foreign_keys` on (see `main.rs`).
     async fn delete(&self, id: Uuid) -> Result<()>;
-    async fn register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<()>;
-    async fn cancel_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<()>;
+    /// RSVP `member_id`, or re-activate their cancelled RSVP. When the
+    /// event is at `max_attendees` (guests included) the member goes
+    /// on the waitlist instead, keeping their place if they're already
+    /// on it; the returned status says which. A member already
+    /// registered always stays registered. Fails with `Conflict`,
+    /// naming the other event, when the member already holds a seat
+    /// elsewhere in the event's `registration_group` (a waitlist place
+    /// there doesn't count), and with
+    /// `RsvpClosed` once the event's `rsvp_deadline` has passed.
+    async fn register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<AttendanceStatus>;
+    /// Admin sign-up of a member: the same capacity and
+    /// registration-group rules as `register_attendance`, but not
+    /// bound by the RSVP deadline and with no waitlist. Returns false,
+    /// writing nothing, when the event is full.
+    async fn admin_register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<bool>;
+    /// Cancel `member_id`'s RSVP or waitlist place, then fill any seat
+    /// that frees with `promote_from_waitlist`. Returns the member who
+    /// was promoted, if anyone was.
+    async fn cancel_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<Option<Uuid>>;
+    /// Move the longest-waiting member on the waitlist to `Registered`
+    /// if the event has a free seat, skipping anyone who holds a seat
+    /// elsewhere in the event's registration group. Returns who moved
+    /// up.
+    async fn promote_from_waitlist(&self, event_id: Uuid) -> Result<Option<Uuid>>;
+    /// `member_id`'s place on the waitlist, counting from 1, or `None`
+    /// when they aren't on it.
+    async fn waitlist_position(&self, event_id: Uuid, member_id: Uuid) -> Result<Option<i64>>;
+    /// Registered attendees, members and guests alike.
     async fn get_attendee_count(&self, event_id: Uuid) -> Result<i64>;
     async fn get_member_attendance_status(&self, event_id: Uuid, member_id: Uuid) -> Result<Option<AttendanceStatus>>;
 
+    // ---- Guest RSVPs --------------------------------------------------
+
+    /// RSVP a non-member by name and email. `email` should already be
+    /// normalized (trimmed, lowercased); a second RSVP with the same
+    /// email updates the name rather than taking another seat. Same
+    /// capacity rule, deadline and return value as
+    /// `register_attendance`. The caller checks the event's
+    /// `allow_guest_rsvp` flag.
+    async fn register_guest(&self, event_id: Uuid, name: &str, email: &str) -> Result<bool>;
+    /// Every RSVP row on the event, members and guests, in sign-up
+    /// order. Includes cancelled rows so the export shows drop-outs.
+    async fn list_attendees(&self, event_id: Uuid) -> Result<Vec<EventAttendeeRow>>;
+    /// Every event `member_id` has RSVP'd to, most recent start first.
+    /// Cancelled RSVPs are left out unless `include_cancelled`.
+    async fn list_member_attendance(
+        &self,
+        member_id: Uuid,
+        include_cancelled: bool,
+    ) -> Result<Vec<MemberAttendanceRow>>;
+
     // ---- Event-reminder support ---------------------------------------
 
-    /// Candidate RSVPs whose event starts in `(now, until]`, are
-    /// status='Registered', and haven't been reminded yet. The runner
+    /// Candidate RSVPs whose event starts in `(from, until]`, are
+    /// status='Registered', haven't had the `lead_hours` reminder yet,
+    /// and whose member hasn't turned event reminders off. The runner
     /// iterates this list and tries to atomically claim each via
     /// `mark_reminder_sent` before sending the email.
     async fn list_pending_reminders(
         &self,
-        now: DateTime<Utc>,
+        from: DateTime<Utc>,
         until: DateTime<Utc>,
+        lead_hours: i64,
     ) -> Result<Vec<EventReminderRow>>;
-    /// Conditional UPDATE that stamps `reminder_sent_at` only if it
-    /// was NULL — returns true exactly when a row was claimed. The
-    /// runner uses this as a concurrency-safe lock before sending the
-    /// email so two ticks (or two processes) can't double-send.
-    async fn mark_reminder_sent(&self, event_id: Uuid, member_id: Uuid) -> Result<bool>;
+    /// Records the `lead_hours` reminder in `event_reminders_sent`
+    /// unless it's already there — returns true exactly when this call
+    /// claimed it. The runner uses this as a concurrency-safe lock
+    /// before sending the email so two ticks (or two processes) can't
+    /// double-send within a window.
+    async fn mark_reminder_sent(
+        &self,
+        event_id: Uuid,
+        member_id: Uuid,
+        lead_hours: i64,
+    ) -> Result<bool>;
+    /// Whether the member gets event reminder emails. Defaults to yes.
+    async fn reminders_enabled(&self, member_id: Uuid) -> Result<bool>;
+    async fn set_reminders_enabled(&self, member_id: Uuid, enabled: bool) -> Result<()>;
+
+    // ---- Member proposals ---------------------------------------------
+
+    /// Every event in `status`, oldest submission first. The admin
+    /// review queue reads `Proposed` through this.
+    async fn list_by_status(&self, status: EventStatus) -> Result<Vec<Event>>;
+    /// Every event `member_id` created, newest first, regardless of
+    /// status. For a regular member these are all their proposals.
+    async fn list_by_creator(&self, member_id: Uuid) -> Result<Vec<Event>>;
+    /// Move a `Proposed` event to `status` and record the reviewer's
+    /// feedback. Conditional on the row still being `Proposed`, so two
+    /// admins deciding at once can't both win — returns true only for
+    /// the one that did.
+    async fn review_proposal(
+        &self,
+        id: Uuid,
+        status: EventStatus,
+        feedback: Option<&str>,
+    ) -> Result<bool>;
 
     // ---- Recurring-series support -------------------------------------
 
@@ -72,10 +187,11 @@ pub trait EventRepository: Send + Sync {
         after: chrono::DateTime<chrono::Utc>,
     ) -> Result<u64>;
     /// Apply the editable subset of fields (title, description, type,
-    /// visibility, location, max_attendees, rsvp_required) to every
-    /// occurrence in the series whose `start_time >= from`. Used by
-    /// the "edit this and all future" admin action — start_time and
-    /// per-row image_url are deliberately preserved per occurrence.
+    /// visibility, location, max_attendees, rsvp_required,
+    /// allow_guest_rsvp, registration_group) to every occurrence in the series whose
+    /// `start_time >= from`. Used by the "edit this and all future"
+    /// admin action — start_time and per-row image_url are
+    /// deliberately preserved per occurrence.
     async fn update_series_occurrences_from(
         &self,
         series_id: Uuid,
@@ -103,6 +219,11 @@ struct EventRow {
     updated_at: NaiveDateTime,
     series_id: Option<String>,
     occurrence_index: Option<i32>,
+    status: String,
+    review_feedback: Option<String>,
+    allow_guest_rsvp: bool,
+    registration_group: Option<String>,
+    rsvp_deadline: Option<NaiveDateTime>,
 }
 
 pub struct SqliteEventRepository {
@@ -130,9 +251,9 @@ impl SqliteEventRepository {
             id: Uuid::parse_str(&row.id).map_err(|e| AppError::Internal(e.to_string()))?,
             title: row.title,
             description: row.description,
-            event_type: Self::parse_event_type(&row.event_type)?,
+            event_type: row.event_type.parse()?,
             event_type_id,
-            visibility: Self::parse_visibility(&row.visibility)?,
+            visibility: row.visibility.parse()?,
             start_time: DateTime::from_naive_utc_and_offset(row.start_time, Utc),
             end_time: row.end_time.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             location: row.location,
@@ -144,46 +265,26 @@ impl SqliteEventRepository {
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
             series_id,
             occurrence_index: row.occurrence_index,
+            status: row.status.parse()?,
+            review_feedback: row.review_feedback,
+            allow_guest_rsvp: row.allow_guest_rsvp,
+            registration_group: row.registration_group,
+            rsvp_deadline: row.rsvp_deadline.map(|dt| dt.and_utc()),
         })
     }
 
-    fn parse_event_type(s: &str) -> Result<EventType> {
-        match s {
-            "Meeting" => Ok(EventType::Meeting),
-            "Workshop" => Ok(EventType::Workshop),
-            "CTF" => Ok(EventType::CTF),
-            "Social" => Ok(EventType::Social),
-            "Training" => Ok(EventType::Training),
-            "Hackathon" => Ok(EventType::Hackathon),
-            _ => Err(AppError::Internal(format!("Invalid event type: {}", s))),
-        }
-    }
-
-    fn event_type_to_str(event_type: &EventType) -> &'static str {
-        match event_type {
-            EventType::Meeting => "Meeting",
-            EventType::Workshop => "Workshop",
-            EventType::CTF => "CTF",
-            EventType::Social => "Social",
-            EventType::Training => "Training",
-            EventType::Hackathon => "Hackathon",
-        }
-    }
-
-    fn parse_visibility(s: &str) -> Result<EventVisibility> {
-        match s {
-            "Public" => Ok(EventVisibility::Public),
-            "MembersOnly" => Ok(EventVisibility::MembersOnly),
-            "AdminOnly" => Ok(EventVisibility::AdminOnly),
-            _ => Err(AppError::Internal(format!("Invalid visibility: {}", s))),
-        }
-    }
-
-    fn visibility_to_str(visibility: &EventVisibility) -> &'static str {
-        match visibility {
-            EventVisibility::Public => "Public",
-            EventVisibility::MembersOnly => "MembersOnly",
-            EventVisibility::AdminOnly => "AdminOnly",
+    /// `RsvpClosed` when `event_id` has an RSVP deadline and it has
+    /// passed. A missing event is left for the insert to not find.
+    async fn ensure_rsvp_open(&self, event_id: Uuid) -> Result<()> {
+        let deadline: Option<Option<NaiveDateTime>> =
+            sqlx::query_scalar("SELECT rsvp_deadline FROM events WHERE id = ?")
+                .bind(event_id.to_string())
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(AppError::Database)?;
+        match deadline.flatten().map(|dt| dt.and_utc()) {
+            Some(deadline) if Utc::now() > deadline => Err(AppError::RsvpClosed(deadline)),
+            _ => Ok(()),
         }
     }
 }
@@ -192,9 +293,9 @@ impl SqliteEventRepository {
 impl EventRepository for SqliteEventRepository {
     async fn create(&self, event: Event) -> Result<Event> {
         let id_str = event.id.to_string();
-        let event_type_str = Self::event_type_to_str(&event.event_type);
+        let event_type_str = event.event_type.as_str();
         let event_type_id_str = event.event_type_id.map(|id| id.to_string());
-        let visibility_str = Self::visibility_to_str(&event.visibility);
+        let visibility_str = event.visibility.as_str();
         let start_time_naive = event.start_time.naive_utc();
         let end_time_naive = event.end_time.map(|dt| dt.naive_utc());
         let max_attendees_int = event.max_attendees;
@@ -210,8 +311,9 @@ impl EventRepository for SqliteEventRepository {
                 id, title, description, event_type, event_type_id, visibility,
                 start_time, end_time, location, max_attendees, rsvp_required,
                 image_url, created_by, created_at, updated_at,
-                series_id, occurrence_index
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                series_id, occurrence_index, status, review_feedback,
+                allow_guest_rsvp, registration_group, rsvp_deadline
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id_str)
@@ -231,6 +333,11 @@ impl EventRepository for SqliteEventRepository {
         .bind(now)
         .bind(&series_id_str)
         .bind(event.occurrence_index)
+        .bind(event.status.as_str())
+        .bind(&event.review_feedback)
+        .bind(event.allow_guest_rsvp)
+        .bind(&event.registration_group)
+        .bind(event.rsvp_deadline.map(|dt| dt.naive_utc()))
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -247,7 +354,8 @@ impl EventRepository for SqliteEventRepository {
             SELECT id, title, description, event_type, event_type_id, visibility,
                    start_time, end_time, location, max_attendees, rsvp_required,
                    image_url, created_by, created_at, updated_at,
-                   series_id, occurrence_index
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
             FROM events
             WHERE id = ?
             "#
@@ -269,8 +377,10 @@ impl EventRepository for SqliteEventRepository {
             SELECT id, title, description, event_type, event_type_id, visibility,
                    start_time, end_time, location, max_attendees, rsvp_required,
                    image_url, created_by, created_at, updated_at,
-                   series_id, occurrence_index
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
             FROM events
+            WHERE status = 'Published'
             ORDER BY start_time DESC
             LIMIT ? OFFSET ?
             "#
@@ -281,9 +391,7 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_event)
-            .collect()
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
     }
 
     async fn list_upcoming(&self, limit: i64) -> Result<Vec<Event>> {
@@ -294,9 +402,10 @@ impl EventRepository for SqliteEventRepository {
             SELECT id, title, description, event_type, event_type_id, visibility,
                    start_time, end_time, location, max_attendees, rsvp_required,
                    image_url, created_by, created_at, updated_at,
-                   series_id, occurrence_index
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
             FROM events
-            WHERE start_time > ?
+            WHERE start_time > ? AND status = 'Published'
             ORDER BY start_time ASC
             LIMIT ?
             "#
@@ -307,22 +416,21 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_event)
-            .collect()
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
     }
 
     async fn list_public(&self) -> Result<Vec<Event>> {
-        let visibility_str = Self::visibility_to_str(&EventVisibility::Public);
+        let visibility_str = EventVisibility::Public.as_str();
 
         let rows = sqlx::query_as::<_, EventRow>(
             r#"
             SELECT id, title, description, event_type, event_type_id, visibility,
                    start_time, end_time, location, max_attendees, rsvp_required,
                    image_url, created_by, created_at, updated_at,
-                   series_id, occurrence_index
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
             FROM events
-            WHERE visibility = ?
+            WHERE visibility = ? AND status = 'Published'
             ORDER BY start_time DESC
             "#
         )
@@ -331,22 +439,21 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_event)
-            .collect()
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
     }
 
     async fn list_members_only(&self) -> Result<Vec<Event>> {
-        let visibility_str = Self::visibility_to_str(&EventVisibility::MembersOnly);
+        let visibility_str = EventVisibility::MembersOnly.as_str();
 
         let rows = sqlx::query_as::<_, EventRow>(
             r#"
             SELECT id, title, description, event_type, event_type_id, visibility,
                    start_time, end_time, location, max_attendees, rsvp_required,
                    image_url, created_by, created_at, updated_at,
-                   series_id, occurrence_index
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
             FROM events
-            WHERE visibility = ?
+            WHERE visibility = ? AND status = 'Published'
             ORDER BY start_time DESC
             "#
         )
@@ -355,20 +462,18 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_event)
-            .collect()
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
     }
 
     async fn count_members_only_upcoming(&self) -> Result<i64> {
-        let visibility_str = Self::visibility_to_str(&EventVisibility::MembersOnly);
+        let visibility_str = EventVisibility::MembersOnly.as_str();
         let now = Utc::now().naive_utc();
 
         let count: (i64,) = sqlx::query_as(
             r#"
             SELECT COUNT(*) as count
             FROM events
-            WHERE visibility = ? AND start_time > ?
+            WHERE visibility = ? AND start_time > ? AND status = 'Published'
             "#
         )
         .bind(visibility_str)
@@ -382,9 +487,9 @@ impl EventRepository for SqliteEventRepository {
 
     async fn update(&self, id: Uuid, event: Event) -> Result<Event> {
         let id_str = id.to_string();
-        let event_type_str = Self::event_type_to_str(&event.event_type);
+        let event_type_str = event.event_type.as_str();
         let event_type_id_str = event.event_type_id.map(|id| id.to_string());
-        let visibility_str = Self::visibility_to_str(&event.visibility);
+        let visibility_str = event.visibility.as_str();
         let start_time_naive = event.start_time.naive_utc();
         let end_time_naive = event.end_time.map(|dt| dt.naive_utc());
         let max_attendees_int = event.max_attendees;
@@ -396,7 +501,8 @@ impl EventRepository for SqliteEventRepository {
             UPDATE events
             SET title = ?, description = ?, event_type = ?, event_type_id = ?, visibility = ?,
                 start_time = ?, end_time = ?, location = ?, max_attendees = ?,
-                rsvp_required = ?, image_url = ?, updated_at = ?
+                rsvp_required = ?, allow_guest_rsvp = ?, registration_group = ?,
+                rsvp_deadline = ?, image_url = ?, updated_at = ?
             WHERE id = ?
             "#
         )
@@ -410,6 +516,9 @@ impl EventRepository for SqliteEventRepository {
         .bind(&event.location)
         .bind(max_attendees_int)
         .bind(rsvp_required_int)
+        .bind(event.allow_guest_rsvp)
+        .bind(&event.registration_group)
+        .bind(event.rsvp_deadline.map(|dt| dt.naive_utc()))
         .bind(&event.image_url)
         .bind(now)
         .bind(&id_str)
@@ -433,28 +542,237 @@ impl EventRepository for SqliteEventRepository {
         Ok(())
     }
 
-    async fn register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<()> {
+    async fn register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<AttendanceStatus> {
+        self.ensure_rsvp_open(event_id).await?;
+        if self.admin_register_attendance(event_id, member_id).await? {
+            return Ok(AttendanceStatus::Registered);
+        }
+
+        // Full, and the registration group didn't object. Join the
+        // waitlist; someone already on it keeps their place in line.
+        // A member who already holds a seat keeps it, even when an
+        // admin has since lowered `max_attendees` below the head count.
+        let result = sqlx::query(
+            r#"
+            INSERT INTO event_attendance (event_id, member_id, status, registered_at)
+            SELECT ?, ?, 'Waitlisted', CURRENT_TIMESTAMP
+            WHERE EXISTS (SELECT 1 FROM events WHERE id = ?)
+            ON CONFLICT (event_id, member_id)
+            DO UPDATE SET status = 'Waitlisted',
+                          registered_at = CASE WHEN status = 'Waitlisted'
+                                               THEN registered_at
+                                               ELSE CURRENT_TIMESTAMP END
+            WHERE event_attendance.status != 'Registered'
+            "#
+        )
+        .bind(event_id.to_string())
+        .bind(member_id.to_string())
+        .bind(event_id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        if result.rows_affected() == 0 {
+            return match self.get_member_attendance_status(event_id, member_id).await? {
+                Some(AttendanceStatus::Registered) => Ok(AttendanceStatus::Registered),
+                _ => Err(AppError::NotFound("Event not found".to_string())),
+            };
+        }
+
+        // A seat may have freed between the two statements with nobody
+        // waiting to take it. Fill it now so the member isn't left
+        // queueing for an open seat.
+        if self.promote_from_waitlist(event_id).await? == Some(member_id) {
+            return Ok(AttendanceStatus::Registered);
+        }
+        Ok(self
+            .get_member_attendance_status(event_id, member_id)
+            .await?
+            .unwrap_or(AttendanceStatus::Waitlisted))
+    }
+
+    async fn admin_register_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<bool> {
         let event_id_str = event_id.to_string();
         let member_id_str = member_id.to_string();
-        
-        sqlx::query(
+
+        // The capacity check rides in the INSERT's WHERE so two RSVPs
+        // for the last seat can't both see room. The member's own row
+        // is left out of the count: re-confirming an existing RSVP
+        // never needs a new seat. The registration-group check rides
+        // along for the same reason — two RSVPs racing for different
+        // events in one group can't both land. Only a held seat counts
+        // against the group; a waitlist place elsewhere doesn't.
+        let result = sqlx::query(
             r#"
             INSERT INTO event_attendance (event_id, member_id, status, registered_at)
-            VALUES (?, ?, 'Registered', CURRENT_TIMESTAMP)
-            ON CONFLICT (event_id, member_id) 
+            SELECT ?, ?, 'Registered', CURRENT_TIMESTAMP
+            WHERE EXISTS (
+                SELECT 1 FROM events e
+                WHERE e.id = ?
+                  AND (e.max_attendees IS NULL
+                       OR (SELECT COUNT(*) FROM event_attendance
+                           WHERE event_id = e.id AND status = 'Registered'
+                             AND member_id IS NOT ?) < e.max_attendees)
+                  AND (e.registration_group IS NULL
+                       OR NOT EXISTS (
+                           SELECT 1 FROM event_attendance ea
+                           JOIN events g ON g.id = ea.event_id
+                           WHERE ea.member_id = ? AND ea.status = 'Registered'
+                             AND g.registration_group = e.registration_group
+                             AND g.id != e.id))
+            )
+            ON CONFLICT (event_id, member_id)
             DO UPDATE SET status = 'Registered', registered_at = CURRENT_TIMESTAMP
             "#
         )
         .bind(&event_id_str)
         .bind(&member_id_str)
+        .bind(&event_id_str)
+        .bind(&member_id_str)
+        .bind(&member_id_str)
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
 
-        Ok(())
+        if result.rows_affected() > 0 {
+            return Ok(true);
+        }
+
+        // Nothing written: either full, or blocked by the group. Only
+        // the latter has another event to name.
+        let held: Option<(String,)> = sqlx::query_as(
+            r#"
+            SELECT g.title FROM events e
+            JOIN events g ON g.registration_group = e.registration_group AND g.id != e.id
+            JOIN event_attendance ea ON ea.event_id = g.id
+            WHERE e.id = ? AND ea.member_id = ? AND ea.status = 'Registered'
+            LIMIT 1
+            "#,
+        )
+        .bind(&event_id_str)
+        .bind(&member_id_str)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        match held {
+            Some((title,)) => Err(AppError::Conflict(format!(
+                "You're already registered for \"{}\", and members can only hold one \
+                 registration among these events. Cancel that RSVP first to switch.",
+                title
+            ))),
+            None => Ok(false),
+        }
+    }
+
+    async fn register_guest(&self, event_id: Uuid, name: &str, email: &str) -> Result<bool> {
+        self.ensure_rsvp_open(event_id).await?;
+        let event_id_str = event_id.to_string();
+
+        // Same shape as `register_attendance`, keyed on the guest's
+        // email via the partial unique index from migration 032.
+        let result = sqlx::query(
+            r#"
+            INSERT INTO event_attendance
+                (event_id, member_id, status, registered_at, guest_name, guest_email)
+            SELECT ?, NULL, 'Registered', CURRENT_TIMESTAMP, ?, ?
+            WHERE EXISTS (
+                SELECT 1 FROM events e
+                WHERE e.id = ?
+                  AND (e.max_attendees IS NULL
+                       OR (SELECT COUNT(*) FROM event_attendance
+                           WHERE event_id = e.id AND status = 'Registered'
+                             AND (member_id IS NOT NULL OR guest_email != ?)) < e.max_attendees)
+            )
+            ON CONFLICT (event_id, guest_email) WHERE member_id IS NULL
+            DO UPDATE SET status = 'Registered', guest_name = excluded.guest_name,
+                          registered_at = CURRENT_TIMESTAMP
+            "#
+        )
+        .bind(&event_id_str)
+        .bind(name)
+        .bind(email)
+        .bind(&event_id_str)
+        .bind(email)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_attendees(&self, event_id: Uuid) -> Result<Vec<EventAttendeeRow>> {
+        let rows: Vec<(Option<String>, Option<String>, Option<String>, String, NaiveDateTime)> =
+            sqlx::query_as(
+                r#"
+                SELECT ea.member_id,
+                       COALESCE(m.full_name, ea.guest_name),
+                       COALESCE(m.email, ea.guest_email),
+                       ea.status, ea.registered_at
+                FROM event_attendance ea
+                LEFT JOIN members m ON m.id = ea.member_id
+                WHERE ea.event_id = ?
+                ORDER BY ea.registered_at ASC, ea.rowid ASC
+                "#,
+            )
+            .bind(event_id.to_string())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+        rows.into_iter()
+            .map(|(member_id, name, email, status, registered_at)| {
+                Ok(EventAttendeeRow {
+                    member_id: member_id
+                        .as_deref()
+                        .map(Uuid::parse_str)
+                        .transpose()
+                        .map_err(|e| AppError::Internal(e.to_string()))?,
+                    name: name.unwrap_or_default(),
+                    email: email.unwrap_or_default(),
+                    status: status.parse()?,
+                    registered_at: DateTime::from_naive_utc_and_offset(registered_at, Utc),
+                })
+            })
+            .collect()
+    }
+
+    async fn list_member_attendance(
+        &self,
+        member_id: Uuid,
+        include_cancelled: bool,
+    ) -> Result<Vec<MemberAttendanceRow>> {
+        let rows: Vec<(String, String, NaiveDateTime, Option<String>, String, bool)> =
+            sqlx::query_as(
+                r#"
+                SELECT e.id, e.title, e.start_time, e.location, ea.status, ea.attended
+                FROM event_attendance ea
+                JOIN events e ON e.id = ea.event_id
+                WHERE ea.member_id = ?
+                  AND (? OR ea.status != 'Cancelled')
+                ORDER BY e.start_time DESC
+                "#,
+            )
+            .bind(member_id.to_string())
+            .bind(include_cancelled)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+        rows.into_iter()
+            .map(|(eid, title, start, location, status, attended)| {
+                Ok(MemberAttendanceRow {
+                    event_id: Uuid::parse_str(&eid).map_err(|e| AppError::Internal(e.to_string()))?,
+                    event_title: title,
+                    event_start: DateTime::from_naive_utc_and_offset(start, Utc),
+                    event_location: location,
+                    status: status.parse()?,
+                    attended,
+                })
+            })
+            .collect()
     }
 
-    async fn cancel_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<()> {
+    async fn cancel_attendance(&self, event_id: Uuid, member_id: Uuid) -> Result<Option<Uuid>> {
         let event_id_str = event_id.to_string();
         let member_id_str = member_id.to_string();
 
@@ -471,7 +789,74 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        Ok(())
+        self.promote_from_waitlist(event_id).await
+    }
+
+    async fn promote_from_waitlist(&self, event_id: Uuid) -> Result<Option<Uuid>> {
+        let event_id_str = event_id.to_string();
+
+        // One statement, with the capacity check in the WHERE, so two
+        // cancellations promoting at once can't overfill the event.
+        // `registered_at` is left alone: it keeps recording when the
+        // member first asked for a seat. A member who has since taken a
+        // seat at another event in the registration group is passed
+        // over, keeping their place in case they give that one up.
+        let promoted: Option<String> = sqlx::query_scalar(
+            r#"
+            UPDATE event_attendance
+            SET status = 'Registered'
+            WHERE rowid = (
+                SELECT w.rowid FROM event_attendance w
+                JOIN events e ON e.id = w.event_id
+                WHERE w.event_id = ? AND w.status = 'Waitlisted' AND w.member_id IS NOT NULL
+                  AND (e.registration_group IS NULL
+                       OR NOT EXISTS (
+                           SELECT 1 FROM event_attendance ea
+                           JOIN events g ON g.id = ea.event_id
+                           WHERE ea.member_id = w.member_id AND ea.status = 'Registered'
+                             AND g.registration_group = e.registration_group
+                             AND g.id != e.id))
+                ORDER BY w.registered_at ASC, w.rowid ASC
+                LIMIT 1)
+              AND EXISTS (
+                SELECT 1 FROM events e
+                WHERE e.id = ?
+                  AND (e.max_attendees IS NULL
+                       OR (SELECT COUNT(*) FROM event_attendance
+                           WHERE event_id = e.id AND status = 'Registered') < e.max_attendees))
+            RETURNING member_id
+            "#
+        )
+        .bind(&event_id_str)
+        .bind(&event_id_str)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        promoted
+            .map(|id| Uuid::parse_str(&id).map_err(|e| AppError::Internal(e.to_string())))
+            .transpose()
+    }
+
+    async fn waitlist_position(&self, event_id: Uuid, member_id: Uuid) -> Result<Option<i64>> {
+        let position: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM event_attendance me
+            JOIN event_attendance ahead
+              ON ahead.event_id = me.event_id AND ahead.status = 'Waitlisted'
+             AND (ahead.registered_at < me.registered_at
+                  OR (ahead.registered_at = me.registered_at AND ahead.rowid <= me.rowid))
+            WHERE me.event_id = ? AND me.member_id = ? AND me.status = 'Waitlisted'
+            "#
+        )
+        .bind(event_id.to_string())
+        .bind(member_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok((position > 0).then_some(position))
     }
 
     async fn get_attendee_count(&self, event_id: Uuid) -> Result<i64> {
@@ -509,18 +894,7 @@ impl EventRepository for SqliteEventRepository {
         .await
         .map_err(AppError::Database)?;
 
-        match row {
-            Some((status,)) => {
-                let attendance_status = match status.as_str() {
-                    "Registered" => AttendanceStatus::Registered,
-                    "Waitlisted" => AttendanceStatus::Waitlisted,
-                    "Cancelled" => AttendanceStatus::Cancelled,
-                    _ => return Err(AppError::Internal(format!("Invalid attendance status: {}", status))),
-                };
-                Ok(Some(attendance_status))
-            }
-            None => Ok(None),
-        }
+        row.map(|(status,)| status.parse().map_err(AppError::from)).transpose()
     }
 
     async fn max_occurrence_index_for_series(&self, series_id: Uuid) -> Result<Option<i32>> {
@@ -559,8 +933,8 @@ impl EventRepository for SqliteEventRepository {
         // Apply the "edit this and all future" subset. Per-occurrence
         // start_time/end_time/image_url stay intact — those are
         // properties of the specific occurrence, not the series.
-        let event_type_str = Self::event_type_to_str(&template.event_type);
-        let visibility_str = Self::visibility_to_str(&template.visibility);
+        let event_type_str = template.event_type.as_str();
+        let visibility_str = template.visibility.as_str();
         let event_type_id_str = template.event_type_id.map(|id| id.to_string());
         let rsvp_int = if template.rsvp_required { 1i32 } else { 0i32 };
 
@@ -575,6 +949,8 @@ impl EventRepository for SqliteEventRepository {
                 location = ?,
                 max_attendees = ?,
                 rsvp_required = ?,
+                allow_guest_rsvp = ?,
+                registration_group = ?,
                 updated_at = ?
             WHERE series_id = ? AND start_time >= ?
             "#,
@@ -587,6 +963,8 @@ impl EventRepository for SqliteEventRepository {
         .bind(&template.location)
         .bind(template.max_attendees)
         .bind(rsvp_int)
+        .bind(template.allow_guest_rsvp)
+        .bind(&template.registration_group)
         .bind(Utc::now().naive_utc())
         .bind(series_id.to_string())
         .bind(from.naive_utc())
@@ -598,31 +976,41 @@ impl EventRepository for SqliteEventRepository {
 
     async fn list_pending_reminders(
         &self,
-        now: DateTime<Utc>,
+        from: DateTime<Utc>,
         until: DateTime<Utc>,
+        lead_hours: i64,
     ) -> Result<Vec<EventReminderRow>> {
-        let rows: Vec<(String, String, NaiveDateTime, Option<String>, String, String, String)> =
+        let rows: Vec<(String, String, NaiveDateTime, Option<String>, String, String, String, Option<String>)> =
             sqlx::query_as(
                 r#"
                 SELECT e.id, e.title, e.start_time, e.location,
-                       m.id, m.email, m.full_name
+                       m.id, m.email, m.full_name, m.locale
                 FROM event_attendance ea
                 JOIN events e ON e.id = ea.event_id
                 JOIN members m ON m.id = ea.member_id
+                LEFT JOIN member_profiles p ON p.member_id = m.id
                 WHERE ea.status = 'Registered'
-                  AND ea.reminder_sent_at IS NULL
                   AND e.start_time > ?
                   AND e.start_time <= ?
+                  AND e.status = 'Published'
+                  AND COALESCE(p.event_reminders, 1) = 1
+                  AND NOT EXISTS (
+                      SELECT 1 FROM event_reminders_sent r
+                      WHERE r.event_id = ea.event_id
+                        AND r.member_id = ea.member_id
+                        AND r.lead_hours = ?
+                  )
                 "#,
             )
-            .bind(now.naive_utc())
+            .bind(from.naive_utc())
             .bind(until.naive_utc())
+            .bind(lead_hours)
             .fetch_all(&self.pool)
             .await
             .map_err(AppError::Database)?;
 
         rows.into_iter()
-            .map(|(eid, title, start, location, mid, email, full_name)| {
+            .map(|(eid, title, start, location, mid, email, full_name, locale)| {
                 Ok(EventReminderRow {
                     event_id: Uuid::parse_str(&eid).map_err(|e| AppError::Internal(e.to_string()))?,
                     event_title: title,
@@ -631,24 +1019,121 @@ impl EventRepository for SqliteEventRepository {
                     member_id: Uuid::parse_str(&mid).map_err(|e| AppError::Internal(e.to_string()))?,
                     member_email: email,
                     member_full_name: full_name,
+                    member_locale: locale,
                 })
             })
             .collect()
     }
 
-    async fn mark_reminder_sent(&self, event_id: Uuid, member_id: Uuid) -> Result<bool> {
+    async fn mark_reminder_sent(
+        &self,
+        event_id: Uuid,
+        member_id: Uuid,
+        lead_hours: i64,
+    ) -> Result<bool> {
         let result = sqlx::query(
             r#"
-            UPDATE event_attendance
-            SET reminder_sent_at = CURRENT_TIMESTAMP
-            WHERE event_id = ? AND member_id = ? AND reminder_sent_at IS NULL
+            INSERT OR IGNORE INTO event_reminders_sent (event_id, member_id, lead_hours)
+            VALUES (?, ?, ?)
             "#,
         )
         .bind(event_id.to_string())
         .bind(member_id.to_string())
+        .bind(lead_hours)
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
         Ok(result.rows_affected() == 1)
     }
-}
\ No newline at end of file
+
+    async fn reminders_enabled(&self, member_id: Uuid) -> Result<bool> {
+        let enabled: Option<bool> = sqlx::query_scalar(
+            "SELECT event_reminders FROM member_profiles WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(enabled.unwrap_or(true))
+    }
+
+    async fn set_reminders_enabled(&self, member_id: Uuid, enabled: bool) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO member_profiles (member_id, event_reminders) VALUES (?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 event_reminders = excluded.event_reminders, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(enabled)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn list_by_status(&self, status: EventStatus) -> Result<Vec<Event>> {
+        let rows = sqlx::query_as::<_, EventRow>(
+            r#"
+            SELECT id, title, description, event_type, event_type_id, visibility,
+                   start_time, end_time, location, max_attendees, rsvp_required,
+                   image_url, created_by, created_at, updated_at,
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
+            FROM events
+            WHERE status = ?
+            ORDER BY created_at ASC
+            "#
+        )
+        .bind(status.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
+    }
+
+    async fn list_by_creator(&self, member_id: Uuid) -> Result<Vec<Event>> {
+        let rows = sqlx::query_as::<_, EventRow>(
+            r#"
+            SELECT id, title, description, event_type, event_type_id, visibility,
+                   start_time, end_time, location, max_attendees, rsvp_required,
+                   image_url, created_by, created_at, updated_at,
+                   series_id, occurrence_index, status, review_feedback,
+                   allow_guest_rsvp, registration_group, rsvp_deadline
+            FROM events
+            WHERE created_by = ?
+            ORDER BY created_at DESC
+            "#
+        )
+        .bind(member_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(skip_malformed(rows, "events", |r| r.id.clone(), Self::row_to_event))
+    }
+
+    async fn review_proposal(
+        &self,
+        id: Uuid,
+        status: EventStatus,
+        feedback: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE events
+            SET status = ?, review_feedback = ?, updated_at = ?
+            WHERE id = ? AND status = 'Proposed'
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(feedback)
+        .bind(Utc::now().naive_utc())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(result.rows_affected() == 1)
+    }
+}
@@ -0,0 +1,32 @@
+//! Row conversion for list queries that shouldn't fail as a whole.
+//!
+//! The `row_to_*` converters return an error for a row whose enum
+//! column holds an unknown value or whose id isn't a UUID. That's right
+//! for a single lookup, but collecting a list into `Result<Vec<_>>`
+//! lets one corrupt row turn the whole page into a 500. List queries
+//! go through [`skip_malformed`] instead; the admin data check page
+//! (`DataCheckService`) lists the rows it skipped so they can be fixed.
+
+use crate::error::Result;
+
+/// Convert every row, leaving out (and logging) those that fail.
+/// `id` names the row in the log line, before `convert` consumes it.
+pub(crate) fn skip_malformed<R, T>(
+    rows: Vec<R>,
+    table: &str,
+    id: impl Fn(&R) -> String,
+    convert: impl Fn(R) -> Result<T>,
+) -> Vec<T> {
+    rows.into_iter()
+        .filter_map(|row| {
+            let row_id = id(&row);
+            match convert(row) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tracing::warn!("Skipping malformed {} row {}: {}", table, row_id, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
@@ -1,19 +1,34 @@
+use std::str::FromStr;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc, NaiveDateTime};
 use sqlx::{SqlitePool, FromRow};
 use uuid::Uuid;
 
+use super::malformed_rows::skip_malformed;
 use crate::{
-    domain::{Member, MemberStatus, CreateMemberRequest, UpdateMemberRequest, BillingMode},
+    auth::password::{self, PasswordCost},
+    domain::{
+        Member, MemberStatus, CreateMemberRequest, UpdateMemberRequest, BillingMode,
+        DuplicateMemberField, normalize_email, ParseEnumError,
+    },
     error::{AppError, Result},
 };
 
-/// Inputs for `MemberRepository::search`. Strongly-typed so the
-/// caller can't pass an unknown sort field and the impl can map
-/// `MemberSortField` → column name in one place (no string-debug
-/// sort keys, no SQL injection risk).
-#[derive(Debug, Clone)]
-pub struct MemberQuery {
+/// Rows per transaction in `create_batch`. Big enough that commit
+/// overhead disappears, small enough that a long import doesn't hold
+/// the write lock for the whole run.
+const BATCH_INSERT_SIZE: usize = 200;
+
+/// Which members an admin roster shows, and in what order. The
+/// members page and the CSV export both build this from the same
+/// query string (`AdminMembersQuery::filter`) and hand it to
+/// `search`/`count`/`export_rows`, so a filter means the same rows
+/// everywhere. Strongly typed so the impl maps `MemberSort` to a
+/// column in one place (no string-debug sort keys, no SQL injection
+/// risk). Pagination is the caller's business, not the filter's.
+#[derive(Debug, Clone, Default)]
+pub struct MemberFilter {
     /// Case-insensitive substring match on `full_name`, `email`, and
     /// `username`. `None` or empty string skips the filter.
     pub search: Option<String>,
@@ -21,27 +36,78 @@ pub struct MemberQuery {
     pub status: Option<crate::domain::MemberStatus>,
     /// Filter to exactly one membership type by FK. `None` skips.
     pub membership_type_id: Option<Uuid>,
-    pub sort: MemberSortField,
+    pub sort: MemberSort,
     pub order: SortOrder,
-    pub limit: i64,
-    pub offset: i64,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum MemberSortField {
+/// Roster sort column. The wire names are the `?sort=` values the
+/// members page links use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemberSort {
+    #[default]
     Name,
     Status,
+    /// By type name, not FK, so the order means something.
     MembershipType,
     Joined,
     DuesPaidUntil,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl MemberSort {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MemberSort::Name => "name",
+            MemberSort::Status => "status",
+            MemberSort::MembershipType => "type",
+            MemberSort::Joined => "joined",
+            MemberSort::DuesPaidUntil => "dues",
+        }
+    }
+}
+
+impl FromStr for MemberSort {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(MemberSort::Name),
+            "status" => Ok(MemberSort::Status),
+            "type" => Ok(MemberSort::MembershipType),
+            "joined" => Ok(MemberSort::Joined),
+            "dues" => Ok(MemberSort::DuesPaidUntil),
+            _ => Err(ParseEnumError::new("member sort", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SortOrder {
+    #[default]
     Asc,
     Desc,
 }
 
+impl SortOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(ParseEnumError::new("sort order", s)),
+        }
+    }
+}
+
 /// Flat DTO containing exactly the columns the admin CSV export emits.
 /// Joined against `membership_types` so the type name is denormalized
 /// into `membership_type` (rather than the FK), which keeps the
@@ -54,6 +120,7 @@ pub struct MemberExportRow {
     pub full_name: String,
     pub status: MemberStatus,
     pub membership_type: String,
+    pub membership_type_slug: String,
     pub joined_at: DateTime<Utc>,
     pub dues_paid_until: Option<DateTime<Utc>>,
     pub is_admin: bool,
@@ -63,9 +130,32 @@ pub struct MemberExportRow {
     pub notes: Option<String>,
 }
 
+/// One (month, membership type) bucket of the dues projection: what
+/// the members whose dues run out that month would pay to renew.
+/// An aggregation row like `MonthlyRevenue`, not a member.
+#[derive(Debug, Clone)]
+pub struct ProjectedDues {
+    pub year: i32,
+    pub month: u32,
+    pub membership_type: String,
+    pub fee_cents: i64,
+    pub member_count: i64,
+}
+
 #[async_trait]
 pub trait MemberRepository: Send + Sync {
+    /// Fails with `AppError::DuplicateMember` when the email or
+    /// username is already taken.
     async fn create(&self, member: CreateMemberRequest) -> Result<Member>;
+    /// Create many members at once: passwords are hashed in parallel
+    /// at `cost`, rows are inserted in batched transactions. The result
+    /// has one entry per request, in order — a row that fails (say, a
+    /// duplicate email) doesn't stop the others.
+    async fn create_batch(
+        &self,
+        requests: Vec<CreateMemberRequest>,
+        cost: PasswordCost,
+    ) -> Result<Vec<Result<Member>>>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Member>>;
     async fn find_by_email(&self, email: &str) -> Result<Option<Member>>;
     async fn find_by_username(&self, username: &str) -> Result<Option<Member>>;
@@ -82,19 +172,24 @@ pub trait MemberRepository: Send + Sync {
     /// Validation is the caller's responsibility (see
     /// `integrations::discord::is_valid_snowflake`).
     async fn update_discord_id(&self, id: Uuid, discord_id: Option<&str>) -> Result<()>;
-    /// Filtered, sorted, paginated lookup. Used by the admin members
-    /// page; replaces the previous "list 1000 then filter in Rust"
-    /// shape (which silently dropped rows past 1000 and used
-    /// `format!("{:?}", status)` as a sort key tied to the Debug
-    /// derive). Returns `(rows, total_match_count)` so the caller can
-    /// compute total pages without a second round trip.
-    async fn search(&self, query: MemberQuery) -> Result<(Vec<Member>, i64)>;
-    /// Like `search` but returns flat rows joined against
-    /// `membership_types` for the display name, with pagination
-    /// removed. Used by the admin CSV export — the export wants
-    /// every matching row, not a page. `MemberQuery::limit` and
-    /// `MemberQuery::offset` are ignored here; sort still applies.
-    async fn export_rows(&self, filter: MemberQuery) -> Result<Vec<MemberExportRow>>;
+    /// Set or clear the member's display-locale override. `None`
+    /// means "follow the org default". Validation (known tag) is the
+    /// caller's responsibility.
+    async fn update_locale(&self, id: Uuid, locale: Option<&str>) -> Result<()>;
+    /// One page of the members matching `filter`, in its order. Used
+    /// by the admin members page; pair with `count` for the total.
+    async fn search(&self, filter: &MemberFilter, limit: i64, offset: i64) -> Result<Vec<Member>>;
+    /// How many members match `filter`.
+    async fn count(&self, filter: &MemberFilter) -> Result<i64>;
+    /// How many members have `status` in the `status` column: the
+    /// stored status, not the effective one. Under
+    /// `ExpiryMode::Derived` a member whose dues lapsed still counts
+    /// as Active here (see `SettingsService::effective_status`).
+    async fn count_by_status(&self, status: MemberStatus) -> Result<i64>;
+    /// Every member matching `filter`, in its order, as flat rows
+    /// carrying the membership type's display name. Used by the admin
+    /// CSV export — the export wants every matching row, not a page.
+    async fn export_rows(&self, filter: &MemberFilter) -> Result<Vec<MemberExportRow>>;
     /// Set the member's `dues_paid_until`, revive Expired→Active in
     /// the same UPDATE, and clear the dues-reminder flag so the next
     /// dues cycle can re-fire a reminder. Suspended/Honorary/Pending
@@ -114,6 +209,18 @@ pub trait MemberRepository: Send + Sync {
     /// anyway, but admins reasonably expect the change to be live
     /// immediately.
     async fn expire_dues_now(&self, id: Uuid) -> Result<()>;
+    /// Make the member Honorary and dues-exempt in one UPDATE,
+    /// remembering their previous `bypass_dues` so a later revert can
+    /// restore it. `until = None` is a permanent grant. Re-granting an
+    /// already-Honorary member only moves the expiry.
+    async fn grant_honorary(&self, id: Uuid, until: Option<DateTime<Utc>>) -> Result<()>;
+    /// Honorary members whose `honorary_until` is at or before `now`.
+    async fn list_expired_honorary(&self, now: DateTime<Utc>) -> Result<Vec<Member>>;
+    /// Undo a grant: Active if dues are paid past now, Expired
+    /// otherwise, `bypass_dues` restored. Returns false when the
+    /// member is no longer Honorary (an admin changed them by hand
+    /// between the list and the revert).
+    async fn revert_honorary(&self, id: Uuid) -> Result<bool>;
     /// Stamp `dues_reminder_sent_at = CURRENT_TIMESTAMP`. Called from
     /// the dues-reminder runner once the email has gone out, so the
     /// next sweep won't re-send for this dues cycle. Cleared on
@@ -146,6 +253,16 @@ pub trait MemberRepository: Send + Sync {
     /// job that flips every `stripe_subscription` member to
     /// `coterie_managed`.
     async fn list_ids_by_billing_mode(&self, mode: BillingMode) -> Result<Vec<Uuid>>;
+    /// Expected renewal income from Active members whose
+    /// `dues_paid_until` falls in `[from, to)`, bucketed by month and
+    /// membership type, soonest month first. Each member counts once,
+    /// at their type's current fee. Exempt members (`bypass_dues`,
+    /// Honorary) and lifetime or free types contribute nothing.
+    async fn dues_projection(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ProjectedDues>>;
 }
 
 // Database row struct that matches SQLite schema
@@ -169,6 +286,8 @@ struct MemberRow {
     email_verified_at: Option<NaiveDateTime>,
     dues_reminder_sent_at: Option<NaiveDateTime>,
     discord_id: Option<String>,
+    locale: Option<String>,
+    honorary_until: Option<NaiveDateTime>,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
 }
@@ -208,14 +327,15 @@ impl SqliteMemberRepository {
             email_verified_at: row.email_verified_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             dues_reminder_sent_at: row.dues_reminder_sent_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             discord_id: row.discord_id,
+            locale: row.locale,
+            honorary_until: row.honorary_until.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
         })
     }
 
     fn parse_member_status(s: &str) -> Result<MemberStatus> {
-        MemberStatus::from_str(s)
-            .ok_or_else(|| AppError::Internal(format!("Invalid member status: {}", s)))
+        Ok(s.parse()?)
     }
 
     /// Resolve a `CreateMemberRequest`'s membership_type_id, defaulting
@@ -248,32 +368,19 @@ impl SqliteMemberRepository {
             )),
         }
     }
-}
-
-#[async_trait]
-impl MemberRepository for SqliteMemberRepository {
-    async fn create(&self, request: CreateMemberRequest) -> Result<Member> {
-        let id = Uuid::new_v4();
-        let now = Utc::now();
-        let status = MemberStatus::Pending;
-        let membership_type_id = self.resolve_membership_type_id(request.membership_type_id).await?;
-
-        // Hash the password with argon2
-        use argon2::{Argon2, PasswordHasher};
-        use argon2::password_hash::{SaltString, rand_core::OsRng};
-
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-
-        let password_hash = argon2
-            .hash_password(request.password.as_bytes(), &salt)
-            .map_err(|e| AppError::Internal(e.to_string()))?
-            .to_string();
-
-        let status_str = status.as_str();
-        let id_str = id.to_string();
-        let mt_id_str = membership_type_id.to_string();
-        let now_naive = now.naive_utc();
+    /// The one `INSERT INTO members`, shared by `create` and
+    /// `create_batch` so the column list can't drift between them.
+    async fn insert_member<'e, E>(
+        executor: E,
+        id: Uuid,
+        request: &CreateMemberRequest,
+        membership_type_id: Uuid,
+        password_hash: &str,
+    ) -> Result<()>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Sqlite>,
+    {
+        let now_naive = Utc::now().naive_utc();
         // Bulk-import billing-migration fields. When None, fall back to
         // the same defaults the prior INSERT shape produced: `joined_at`
         // defaults to `now`, the rest to SQL NULL.
@@ -293,13 +400,13 @@ impl MemberRepository for SqliteMemberRepository {
             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
-        .bind(&id_str)
+        .bind(id.to_string())
         .bind(&request.email)
         .bind(&request.username)
         .bind(&request.full_name)
-        .bind(&password_hash)
-        .bind(status_str)
-        .bind(&mt_id_str)
+        .bind(password_hash)
+        .bind(MemberStatus::Pending.as_str())
+        .bind(membership_type_id.to_string())
         .bind(joined_at_naive)
         .bind(0i32)  // bypass_dues as integer (0 = false)
         .bind(dues_paid_until_naive)
@@ -308,15 +415,171 @@ impl MemberRepository for SqliteMemberRepository {
         .bind(email_verified_at_naive)
         .bind(now_naive)
         .bind(now_naive)
-        .execute(&self.pool)
+        .execute(executor)
         .await
-        .map_err(AppError::Database)?;
+        .map_err(duplicate_member_or_database)?;
+        Ok(())
+    }
+}
+
+/// `WHERE` clause (with a leading space, or empty) and its bound
+/// values for a `MemberFilter`, against `members m`. User-provided
+/// values always bind.
+fn filter_where(filter: &MemberFilter) -> (String, Vec<String>) {
+    let mut clauses: Vec<&str> = Vec::new();
+    let mut binds = Vec::new();
+    if let Some(pat) = filter
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| format!("%{}%", s.to_lowercase()))
+    {
+        clauses.push(
+            "(LOWER(m.full_name) LIKE ? OR LOWER(m.email) LIKE ? OR LOWER(m.username) LIKE ?)",
+        );
+        binds.extend([pat.clone(), pat.clone(), pat]);
+    }
+    if let Some(status) = filter.status {
+        clauses.push("m.status = ?");
+        binds.push(status.as_str().to_string());
+    }
+    if let Some(type_id) = filter.membership_type_id {
+        clauses.push("m.membership_type_id = ?");
+        binds.push(type_id.to_string());
+    }
+    let sql = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", clauses.join(" AND "))
+    };
+    (sql, binds)
+}
+
+/// `ORDER BY` for a `MemberFilter`, against `members m` joined to
+/// `membership_types mt`. Sort field/direction map to constant
+/// strings. NULL dues_paid_until sorts to the bottom regardless of
+/// direction (admins want "set" rows above "not set" rows when
+/// sorting by that column). The id tiebreak keeps pages stable and
+/// every caller's order identical.
+fn filter_order(filter: &MemberFilter) -> String {
+    let dir = match filter.order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+    let column = match filter.sort {
+        MemberSort::Name => format!("LOWER(m.full_name) {}", dir),
+        MemberSort::Status => format!("m.status {}", dir),
+        MemberSort::MembershipType => format!("LOWER(mt.name) {}", dir),
+        MemberSort::Joined => format!("m.joined_at {}", dir),
+        MemberSort::DuesPaidUntil => {
+            format!("m.dues_paid_until IS NULL, m.dues_paid_until {}", dir)
+        }
+    };
+    format!("{}, m.id", column)
+}
+
+/// Store emails in their normalized form and usernames trimmed, so the
+/// duplicate checks and the UNIQUE constraints see what lookups see.
+fn normalize_create_request(mut request: CreateMemberRequest) -> CreateMemberRequest {
+    request.email = normalize_email(&request.email);
+    request.username = request.username.trim().to_string();
+    request
+}
+
+/// A UNIQUE violation on `members.email` / `members.username` becomes
+/// `AppError::DuplicateMember` naming the field. `create` checks both
+/// before inserting; this covers a concurrent insert that slips in
+/// between, and the batch path, which doesn't pre-check.
+fn duplicate_member_or_database(err: sqlx::Error) -> AppError {
+    if let sqlx::Error::Database(ref db_err) = err {
+        if db_err.is_unique_violation() {
+            if db_err.message().contains("members.email") {
+                return AppError::DuplicateMember(DuplicateMemberField::Email);
+            }
+            if db_err.message().contains("members.username") {
+                return AppError::DuplicateMember(DuplicateMemberField::Username);
+            }
+        }
+    }
+    AppError::Database(err)
+}
+
+#[async_trait]
+impl MemberRepository for SqliteMemberRepository {
+    async fn create(&self, request: CreateMemberRequest) -> Result<Member> {
+        let request = normalize_create_request(request);
+        // Before hashing, so a taken email or username costs two
+        // lookups rather than a bcrypt round.
+        if self.find_by_email(&request.email).await?.is_some() {
+            return Err(AppError::DuplicateMember(DuplicateMemberField::Email));
+        }
+        if self.find_by_username(&request.username).await?.is_some() {
+            return Err(AppError::DuplicateMember(DuplicateMemberField::Username));
+        }
+
+        let id = Uuid::new_v4();
+        let membership_type_id = self.resolve_membership_type_id(request.membership_type_id).await?;
+        let password_hash = password::hash_with_cost(&request.password, PasswordCost::Secure)?;
+
+        Self::insert_member(&self.pool, id, &request, membership_type_id, &password_hash).await?;
 
         self.find_by_id(id).await?.ok_or_else(|| {
             AppError::Internal("Failed to retrieve created member".to_string())
         })
     }
 
+    async fn create_batch(
+        &self,
+        requests: Vec<CreateMemberRequest>,
+        cost: PasswordCost,
+    ) -> Result<Vec<Result<Member>>> {
+        // Resolve membership types up front (a handful of distinct
+        // values at most) so the transactions below only INSERT.
+        let requests: Vec<_> = requests.into_iter().map(normalize_create_request).collect();
+        let mut resolved = Vec::with_capacity(requests.len());
+        for request in &requests {
+            resolved.push(self.resolve_membership_type_id(request.membership_type_id).await);
+        }
+
+        let hashes = password::hash_passwords(
+            requests.iter().map(|r| r.password.clone()).collect(),
+            cost,
+        )
+        .await?;
+
+        let mut ids: Vec<Result<Uuid>> = Vec::with_capacity(requests.len());
+        let rows = requests.iter().zip(resolved).zip(&hashes);
+        let mut rows = rows.peekable();
+        while rows.peek().is_some() {
+            let mut tx = self.pool.begin().await.map_err(AppError::Database)?;
+            for ((request, membership_type_id), hash) in rows.by_ref().take(BATCH_INSERT_SIZE) {
+                let id = Uuid::new_v4();
+                let inserted = match membership_type_id {
+                    // A failed INSERT only aborts its own statement in
+                    // SQLite, so one duplicate doesn't sink the batch.
+                    Ok(mt_id) => Self::insert_member(&mut *tx, id, request, mt_id, hash)
+                        .await
+                        .map(|_| id),
+                    Err(e) => Err(e),
+                };
+                ids.push(inserted);
+            }
+            tx.commit().await.map_err(AppError::Database)?;
+        }
+
+        let mut created = Vec::with_capacity(ids.len());
+        for id in ids {
+            created.push(match id {
+                Ok(id) => self.find_by_id(id).await?.ok_or_else(|| {
+                    AppError::Internal("Failed to retrieve created member".to_string())
+                }),
+                Err(e) => Err(e),
+            });
+        }
+        Ok(created)
+    }
+
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Member>> {
         let id_str = id.to_string();
         let row = sqlx::query_as::<_, MemberRow>(
@@ -324,7 +587,7 @@ impl MemberRepository for SqliteMemberRepository {
             SELECT id, email, username, full_name, status, membership_type_id,
                    joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes,
                    stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at,
-                   dues_reminder_sent_at, discord_id, created_at, updated_at
+                   dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at
             FROM members
             WHERE id = ?
             "#
@@ -346,12 +609,12 @@ impl MemberRepository for SqliteMemberRepository {
             SELECT id, email, username, full_name, status, membership_type_id,
                    joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes,
                    stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at,
-                   dues_reminder_sent_at, discord_id, created_at, updated_at
+                   dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at
             FROM members
             WHERE email = ?
             "#
         )
-        .bind(email)
+        .bind(normalize_email(email))
         .fetch_optional(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -368,12 +631,12 @@ impl MemberRepository for SqliteMemberRepository {
             SELECT id, email, username, full_name, status, membership_type_id,
                    joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes,
                    stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at,
-                   dues_reminder_sent_at, discord_id, created_at, updated_at
+                   dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at
             FROM members
             WHERE username = ?
             "#
         )
-        .bind(username)
+        .bind(username.trim())
         .fetch_optional(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -390,7 +653,7 @@ impl MemberRepository for SqliteMemberRepository {
             SELECT id, email, username, full_name, status, membership_type_id,
                    joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes,
                    stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at,
-                   dues_reminder_sent_at, discord_id, created_at, updated_at
+                   dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at
             FROM members
             WHERE discord_id IS NOT NULL AND discord_id != ''
             ORDER BY status, joined_at
@@ -400,9 +663,7 @@ impl MemberRepository for SqliteMemberRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_member)
-            .collect()
+        Ok(skip_malformed(rows, "members", |r| r.id.clone(), Self::row_to_member))
     }
 
     async fn update(&self, id: Uuid, update: UpdateMemberRequest) -> Result<Member> {
@@ -514,6 +775,17 @@ impl MemberRepository for SqliteMemberRepository {
         Ok(())
     }
 
+    async fn update_locale(&self, id: Uuid, locale: Option<&str>) -> Result<()> {
+        sqlx::query("UPDATE members SET locale = ?, updated_at = ? WHERE id = ?")
+            .bind(locale)
+            .bind(Utc::now().naive_utc())
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+        Ok(())
+    }
+
     async fn set_dues_paid_until_with_revival(
         &self,
         id: Uuid,
@@ -552,6 +824,64 @@ impl MemberRepository for SqliteMemberRepository {
         Ok(())
     }
 
+    async fn grant_honorary(&self, id: Uuid, until: Option<DateTime<Utc>>) -> Result<()> {
+        sqlx::query(
+            "UPDATE members \
+             SET honorary_prev_bypass_dues = CASE WHEN status = 'Honorary' \
+                     THEN honorary_prev_bypass_dues ELSE bypass_dues END, \
+                 status = 'Honorary', \
+                 bypass_dues = 1, \
+                 honorary_until = ?, \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ?",
+        )
+        .bind(until.map(|t| t.naive_utc()))
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(())
+    }
+
+    async fn list_expired_honorary(&self, now: DateTime<Utc>) -> Result<Vec<Member>> {
+        let rows = sqlx::query_as::<_, MemberRow>(
+            r#"
+            SELECT id, email, username, full_name, status, membership_type_id,
+                   joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes,
+                   stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at,
+                   dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at
+            FROM members
+            WHERE status = 'Honorary'
+              AND honorary_until IS NOT NULL
+              AND honorary_until <= ?
+            "#,
+        )
+        .bind(now.naive_utc())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(skip_malformed(rows, "members", |r| r.id.clone(), Self::row_to_member))
+    }
+
+    async fn revert_honorary(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE members \
+             SET status = CASE WHEN dues_paid_until IS NOT NULL \
+                     AND dues_paid_until > CURRENT_TIMESTAMP \
+                     THEN 'Active' ELSE 'Expired' END, \
+                 bypass_dues = COALESCE(honorary_prev_bypass_dues, 0), \
+                 honorary_until = NULL, \
+                 honorary_prev_bypass_dues = NULL, \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND status = 'Honorary'",
+        )
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn set_dues_reminder_sent(&self, id: Uuid) -> Result<()> {
         sqlx::query(
             "UPDATE members \
@@ -608,7 +938,7 @@ impl MemberRepository for SqliteMemberRepository {
                     joined_at, expires_at, dues_paid_until, \
                     bypass_dues, is_admin, notes, stripe_customer_id, \
                     stripe_subscription_id, billing_mode, email_verified_at, \
-                    dues_reminder_sent_at, discord_id, created_at, updated_at \
+                    dues_reminder_sent_at, discord_id, locale, honorary_until, created_at, updated_at \
              FROM members WHERE stripe_customer_id = ?",
         )
         .bind(customer_id)
@@ -622,6 +952,17 @@ impl MemberRepository for SqliteMemberRepository {
         }
     }
 
+    async fn count_by_status(&self, status: MemberStatus) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM members WHERE status = ?",
+        )
+        .bind(status.as_str())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+        Ok(count)
+    }
+
     async fn count_by_billing_mode(&self, mode: BillingMode) -> Result<i64> {
         let count: i64 = sqlx::query_scalar(
             "SELECT COUNT(*) FROM members WHERE billing_mode = ?",
@@ -650,152 +991,112 @@ impl MemberRepository for SqliteMemberRepository {
             .collect()
     }
 
-    async fn search(&self, query: MemberQuery) -> Result<(Vec<Member>, i64)> {
-        // Build WHERE clause + bound params from the typed query.
-        // Sort field/direction map to constant strings (no injection
-        // risk); user-provided values (search, status, type) bind.
-        let search_pat = query.search
-            .as_ref()
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .map(|s| format!("%{}%", s.to_lowercase()));
-        let status_str = query.status.as_ref().map(|s| s.as_str().to_string());
-        let mtype_id_str = query.membership_type_id.map(|id| id.to_string());
-
-        let mut where_clauses: Vec<&str> = Vec::new();
-        if search_pat.is_some() {
-            where_clauses.push(
-                "(LOWER(full_name) LIKE ? OR LOWER(email) LIKE ? OR LOWER(username) LIKE ?)",
-            );
-        }
-        if status_str.is_some() {
-            where_clauses.push("status = ?");
-        }
-        if mtype_id_str.is_some() {
-            where_clauses.push("membership_type_id = ?");
-        }
-        let where_sql = if where_clauses.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", where_clauses.join(" AND "))
-        };
-
-        // ORDER BY mapping. NULL dues_paid_until sorts to the bottom
-        // regardless of direction (admins want "set" rows above "not
-        // set" rows when sorting by that column).
-        let order_dir = match query.order {
-            SortOrder::Asc => "ASC",
-            SortOrder::Desc => "DESC",
-        };
-        let order_sql = match query.sort {
-            MemberSortField::Name => format!("LOWER(full_name) {}", order_dir),
-            MemberSortField::Status => format!("status {}", order_dir),
-            MemberSortField::MembershipType => format!("membership_type_id {}", order_dir),
-            MemberSortField::Joined => format!("joined_at {}", order_dir),
-            MemberSortField::DuesPaidUntil => {
-                format!("dues_paid_until IS NULL, dues_paid_until {}", order_dir)
-            }
-        };
+    async fn dues_projection(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<ProjectedDues>> {
+        // Honorary is its own status, so `status = 'Active'` already
+        // leaves it out; `bypass_dues` is the other exemption.
+        let rows: Vec<(String, String, String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT
+                strftime('%Y', m.dues_paid_until)  AS year_str,
+                strftime('%m', m.dues_paid_until)  AS month_str,
+                t.name                             AS type_name,
+                SUM(t.fee_cents)                   AS fee_cents,
+                COUNT(*)                           AS member_count
+            FROM members m
+            JOIN membership_types t ON t.id = m.membership_type_id
+            WHERE m.status = 'Active'
+              AND m.bypass_dues = 0
+              AND m.dues_paid_until >= ?
+              AND m.dues_paid_until < ?
+              AND t.billing_period != 'lifetime'
+              AND t.fee_cents > 0
+            GROUP BY year_str, month_str, t.id
+            ORDER BY year_str, month_str, t.sort_order, t.name
+            "#,
+        )
+        .bind(from.naive_utc())
+        .bind(to.naive_utc())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
 
-        let select_sql = format!(
-            "SELECT id, email, username, full_name, status, membership_type_id, \
-                    joined_at, expires_at, dues_paid_until, bypass_dues, is_admin, notes, \
-                    stripe_customer_id, stripe_subscription_id, billing_mode, email_verified_at, \
-                    dues_reminder_sent_at, discord_id, created_at, updated_at \
-             FROM members{} \
+        rows.into_iter()
+            .map(|(year_str, month_str, membership_type, fee_cents, member_count)| {
+                Ok(ProjectedDues {
+                    year: year_str
+                        .parse()
+                        .map_err(|e: std::num::ParseIntError| AppError::Internal(e.to_string()))?,
+                    month: month_str
+                        .parse()
+                        .map_err(|e: std::num::ParseIntError| AppError::Internal(e.to_string()))?,
+                    membership_type,
+                    fee_cents,
+                    member_count,
+                })
+            })
+            .collect()
+    }
+
+    async fn search(&self, filter: &MemberFilter, limit: i64, offset: i64) -> Result<Vec<Member>> {
+        let (where_sql, binds) = filter_where(filter);
+        let sql = format!(
+            "SELECT m.id, m.email, m.username, m.full_name, m.status, m.membership_type_id, \
+                    m.joined_at, m.expires_at, m.dues_paid_until, m.bypass_dues, m.is_admin, m.notes, \
+                    m.stripe_customer_id, m.stripe_subscription_id, m.billing_mode, m.email_verified_at, \
+                    m.dues_reminder_sent_at, m.discord_id, m.locale, m.honorary_until, \
+                    m.created_at, m.updated_at \
+             FROM members m \
+             LEFT JOIN membership_types mt ON mt.id = m.membership_type_id{} \
              ORDER BY {} \
              LIMIT ? OFFSET ?",
-            where_sql, order_sql,
+            where_sql,
+            filter_order(filter),
         );
-        let count_sql = format!("SELECT COUNT(*) FROM members{}", where_sql);
-
-        // Bind WHERE params first (used by both queries), then LIMIT/OFFSET.
-        let mut rows_q = sqlx::query_as::<_, MemberRow>(&select_sql);
-        let mut count_q = sqlx::query_scalar::<_, i64>(&count_sql);
-        if let Some(p) = &search_pat {
-            rows_q = rows_q.bind(p).bind(p).bind(p);
-            count_q = count_q.bind(p).bind(p).bind(p);
-        }
-        if let Some(s) = &status_str {
-            rows_q = rows_q.bind(s);
-            count_q = count_q.bind(s);
+        let mut q = sqlx::query_as::<_, MemberRow>(&sql);
+        for b in &binds {
+            q = q.bind(b);
         }
-        if let Some(t) = &mtype_id_str {
-            rows_q = rows_q.bind(t);
-            count_q = count_q.bind(t);
-        }
-        rows_q = rows_q.bind(query.limit).bind(query.offset);
-
-        let rows = rows_q.fetch_all(&self.pool).await
-            .map_err(AppError::Database)?;
-        let total: i64 = count_q.fetch_one(&self.pool).await
+        let rows = q
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
             .map_err(AppError::Database)?;
-
-        let members = rows.into_iter().map(Self::row_to_member).collect::<Result<Vec<_>>>()?;
-        Ok((members, total))
+        Ok(skip_malformed(rows, "members", |r| r.id.clone(), Self::row_to_member))
     }
 
-    async fn export_rows(&self, query: MemberQuery) -> Result<Vec<MemberExportRow>> {
-        let search_pat = query.search
-            .as_ref()
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .map(|s| format!("%{}%", s.to_lowercase()));
-        let status_str = query.status.as_ref().map(|s| s.as_str().to_string());
-        let mtype_id_str = query.membership_type_id.map(|id| id.to_string());
-
-        let mut where_clauses: Vec<&str> = Vec::new();
-        if search_pat.is_some() {
-            where_clauses.push(
-                "(LOWER(m.full_name) LIKE ? OR LOWER(m.email) LIKE ? OR LOWER(m.username) LIKE ?)",
-            );
-        }
-        if status_str.is_some() {
-            where_clauses.push("m.status = ?");
+    async fn count(&self, filter: &MemberFilter) -> Result<i64> {
+        let (where_sql, binds) = filter_where(filter);
+        let sql = format!("SELECT COUNT(*) FROM members m{}", where_sql);
+        let mut q = sqlx::query_scalar::<_, i64>(&sql);
+        for b in &binds {
+            q = q.bind(b);
         }
-        if mtype_id_str.is_some() {
-            where_clauses.push("m.membership_type_id = ?");
-        }
-        let where_sql = if where_clauses.is_empty() {
-            String::new()
-        } else {
-            format!(" WHERE {}", where_clauses.join(" AND "))
-        };
-
-        let order_dir = match query.order {
-            SortOrder::Asc => "ASC",
-            SortOrder::Desc => "DESC",
-        };
-        let order_sql = match query.sort {
-            MemberSortField::Name => format!("LOWER(m.full_name) {}", order_dir),
-            MemberSortField::Status => format!("m.status {}", order_dir),
-            MemberSortField::MembershipType => format!("LOWER(mt.name) {}", order_dir),
-            MemberSortField::Joined => format!("m.joined_at {}", order_dir),
-            MemberSortField::DuesPaidUntil => {
-                format!("m.dues_paid_until IS NULL, m.dues_paid_until {}", order_dir)
-            }
-        };
+        q.fetch_one(&self.pool).await.map_err(AppError::Database)
+    }
 
+    async fn export_rows(&self, filter: &MemberFilter) -> Result<Vec<MemberExportRow>> {
+        let (where_sql, binds) = filter_where(filter);
         let select_sql = format!(
             "SELECT m.id, m.email, m.username, m.full_name, m.status, \
                     COALESCE(mt.name, '') AS membership_type, \
+                    COALESCE(mt.slug, '') AS membership_type_slug, \
                     m.joined_at, m.dues_paid_until, m.is_admin, m.bypass_dues, \
                     m.discord_id, m.email_verified_at, m.notes \
              FROM members m \
              LEFT JOIN membership_types mt ON mt.id = m.membership_type_id{} \
              ORDER BY {}",
-            where_sql, order_sql,
+            where_sql,
+            filter_order(filter),
         );
 
         let mut q = sqlx::query_as::<_, ExportRow>(&select_sql);
-        if let Some(p) = &search_pat {
-            q = q.bind(p).bind(p).bind(p);
-        }
-        if let Some(s) = &status_str {
-            q = q.bind(s);
-        }
-        if let Some(t) = &mtype_id_str {
-            q = q.bind(t);
+        for b in &binds {
+            q = q.bind(b);
         }
 
         let rows = q.fetch_all(&self.pool).await.map_err(AppError::Database)?;
@@ -807,6 +1108,7 @@ impl MemberRepository for SqliteMemberRepository {
                 full_name: r.full_name,
                 status: Self::parse_member_status(&r.status)?,
                 membership_type: r.membership_type,
+                membership_type_slug: r.membership_type_slug,
                 joined_at: DateTime::from_naive_utc_and_offset(r.joined_at, Utc),
                 dues_paid_until: r.dues_paid_until
                     .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
@@ -829,6 +1131,7 @@ struct ExportRow {
     full_name: String,
     status: String,
     membership_type: String,
+    membership_type_slug: String,
     joined_at: NaiveDateTime,
     dues_paid_until: Option<NaiveDateTime>,
     is_admin: i32,
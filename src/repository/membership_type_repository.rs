@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use sqlx::{FromRow, SqlitePool};
@@ -21,21 +23,30 @@ struct MembershipTypeRow {
     icon: Option<String>,
     sort_order: i32,
     is_active: i32,
-    fee_cents: i32,
+    fee_cents: i64,
     billing_period: String,
+    max_members: Option<i64>,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
+    created_by: Option<String>,
+    updated_by: Option<String>,
 }
 
 #[async_trait]
 pub trait MembershipTypeRepository: Send + Sync {
-    async fn create(&self, request: CreateMembershipTypeRequest) -> Result<MembershipTypeConfig>;
+    /// `actor` is the admin creating the type, recorded as both
+    /// `created_by` and `updated_by`; `None` for seeded types.
+    async fn create(&self, actor: Option<Uuid>, request: CreateMembershipTypeRequest) -> Result<MembershipTypeConfig>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<MembershipTypeConfig>>;
     async fn find_by_slug(&self, slug: &str) -> Result<Option<MembershipTypeConfig>>;
     async fn list(&self, include_inactive: bool) -> Result<Vec<MembershipTypeConfig>>;
-    async fn update(&self, id: Uuid, request: UpdateMembershipTypeRequest) -> Result<MembershipTypeConfig>;
+    /// `actor` replaces `updated_by`.
+    async fn update(&self, actor: Option<Uuid>, id: Uuid, request: UpdateMembershipTypeRequest) -> Result<MembershipTypeConfig>;
     async fn delete(&self, id: Uuid) -> Result<()>;
     async fn count_usage(&self, id: Uuid) -> Result<i64>;
+    /// `count_usage` for every type in one query. Types with no
+    /// members are absent from the map.
+    async fn count_usage_by_type(&self) -> Result<HashMap<Uuid, i64>>;
     async fn get_next_sort_order(&self) -> Result<i32>;
 }
 
@@ -60,15 +71,25 @@ impl SqliteMembershipTypeRepository {
             is_active: row.is_active != 0,
             fee_cents: row.fee_cents,
             billing_period: row.billing_period,
+            max_members: row.max_members,
             created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
+            created_by: parse_actor(row.created_by)?,
+            updated_by: parse_actor(row.updated_by)?,
         })
     }
 }
 
+fn parse_actor(id: Option<String>) -> Result<Option<Uuid>> {
+    id.as_deref()
+        .map(Uuid::parse_str)
+        .transpose()
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
 #[async_trait]
 impl MembershipTypeRepository for SqliteMembershipTypeRepository {
-    async fn create(&self, request: CreateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
+    async fn create(&self, actor: Option<Uuid>, request: CreateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
         let id = Uuid::new_v4();
         let id_str = id.to_string();
         let slug = request.slug.unwrap_or_else(|| slugify(&request.name));
@@ -79,9 +100,9 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
             r#"
             INSERT INTO membership_types (
                 id, name, slug, description, color, icon,
-                sort_order, is_active, fee_cents, billing_period,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?)
+                sort_order, is_active, fee_cents, billing_period, max_members,
+                created_at, updated_at, created_by, updated_by
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id_str)
@@ -93,8 +114,11 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         .bind(sort_order)
         .bind(request.fee_cents)
         .bind(&request.billing_period)
+        .bind(request.max_members)
         .bind(now)
         .bind(now)
+        .bind(actor.map(|id| id.to_string()))
+        .bind(actor.map(|id| id.to_string()))
         .execute(&self.pool)
         .await
         .map_err(AppError::Database)?;
@@ -109,8 +133,8 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         let row = sqlx::query_as::<_, MembershipTypeRow>(
             r#"
             SELECT id, name, slug, description, color, icon,
-                   sort_order, is_active, fee_cents, billing_period,
-                   created_at, updated_at
+                   sort_order, is_active, fee_cents, billing_period, max_members,
+                   created_at, updated_at, created_by, updated_by
             FROM membership_types
             WHERE id = ?
             "#,
@@ -130,8 +154,8 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         let row = sqlx::query_as::<_, MembershipTypeRow>(
             r#"
             SELECT id, name, slug, description, color, icon,
-                   sort_order, is_active, fee_cents, billing_period,
-                   created_at, updated_at
+                   sort_order, is_active, fee_cents, billing_period, max_members,
+                   created_at, updated_at, created_by, updated_by
             FROM membership_types
             WHERE slug = ?
             "#,
@@ -151,16 +175,16 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         let query = if include_inactive {
             r#"
             SELECT id, name, slug, description, color, icon,
-                   sort_order, is_active, fee_cents, billing_period,
-                   created_at, updated_at
+                   sort_order, is_active, fee_cents, billing_period, max_members,
+                   created_at, updated_at, created_by, updated_by
             FROM membership_types
             ORDER BY sort_order ASC, name ASC
             "#
         } else {
             r#"
             SELECT id, name, slug, description, color, icon,
-                   sort_order, is_active, fee_cents, billing_period,
-                   created_at, updated_at
+                   sort_order, is_active, fee_cents, billing_period, max_members,
+                   created_at, updated_at, created_by, updated_by
             FROM membership_types
             WHERE is_active = 1
             ORDER BY sort_order ASC, name ASC
@@ -175,7 +199,7 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         rows.into_iter().map(Self::row_to_config).collect()
     }
 
-    async fn update(&self, id: Uuid, request: UpdateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
+    async fn update(&self, actor: Option<Uuid>, id: Uuid, request: UpdateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
         let existing = self.find_by_id(id).await?.ok_or_else(|| {
             AppError::NotFound("Membership type not found".to_string())
         })?;
@@ -191,13 +215,14 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         let is_active = request.is_active.unwrap_or(existing.is_active);
         let fee_cents = request.fee_cents.unwrap_or(existing.fee_cents);
         let billing_period = request.billing_period.unwrap_or(existing.billing_period);
+        let max_members = request.max_members.unwrap_or(existing.max_members);
 
         sqlx::query(
             r#"
             UPDATE membership_types
             SET name = ?, description = ?, color = ?, icon = ?,
                 sort_order = ?, is_active = ?, fee_cents = ?, billing_period = ?,
-                updated_at = ?
+                max_members = ?, updated_at = ?, updated_by = ?
             WHERE id = ?
             "#,
         )
@@ -209,7 +234,9 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         .bind(if is_active { 1i32 } else { 0i32 })
         .bind(fee_cents)
         .bind(&billing_period)
+        .bind(max_members)
         .bind(now)
+        .bind(actor.map(|id| id.to_string()))
         .bind(&id_str)
         .execute(&self.pool)
         .await
@@ -250,6 +277,27 @@ impl MembershipTypeRepository for SqliteMembershipTypeRepository {
         Ok(row.0)
     }
 
+    async fn count_usage_by_type(&self) -> Result<HashMap<Uuid, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT membership_type_id, COUNT(*) as count
+            FROM members
+            WHERE membership_type_id IS NOT NULL
+            GROUP BY membership_type_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        rows.into_iter()
+            .map(|(id, count)| {
+                let id = Uuid::parse_str(&id).map_err(|e| AppError::Internal(e.to_string()))?;
+                Ok((id, count))
+            })
+            .collect()
+    }
+
     async fn get_next_sort_order(&self) -> Result<i32> {
         let row: (Option<i32>,) = sqlx::query_as(
             "SELECT MAX(sort_order) FROM membership_types"
@@ -9,14 +9,20 @@ pub mod donation_repository;
 pub mod basic_type_repository;
 pub mod membership_type_repository;
 pub mod processed_events_repository;
+mod malformed_rows;
 
 pub use member_repository::{
     MemberRepository, SqliteMemberRepository,
-    MemberQuery, MemberSortField, SortOrder, MemberExportRow,
+    MemberFilter, MemberSort, SortOrder, MemberExportRow, ProjectedDues,
+};
+pub use event_repository::{
+    EventAttendeeRow, EventRepository, MemberAttendanceRow, SqliteEventRepository,
 };
-pub use event_repository::{EventRepository, SqliteEventRepository};
 pub use event_series_repository::{EventSeriesRepository, SqliteEventSeriesRepository};
-pub use announcement_repository::{AnnouncementRepository, SqliteAnnouncementRepository};
+pub use announcement_repository::{
+    AnnouncementFilter, AnnouncementRepository, AnnouncementSort, AnnouncementStatusFilter,
+    SqliteAnnouncementRepository,
+};
 pub use payment_repository::{PaymentRepository, SqlitePaymentRepository, MonthlyRevenue};
 pub use saved_card_repository::{SavedCardRepository, SqliteSavedCardRepository};
 pub use scheduled_payment_repository::{ScheduledPaymentRepository, SqliteScheduledPaymentRepository};
@@ -3,9 +3,10 @@ use chrono::{DateTime, Utc, NaiveDateTime};
 use sqlx::{SqlitePool, FromRow};
 use uuid::Uuid;
 
+use super::malformed_rows::skip_malformed;
 use crate::{
     domain::{
-        Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus, StripeRef,
+        Payer, Payment, PaymentKind, PaymentMethod, StripeRef,
         configurable_types::BillingPeriod,
     },
     error::{AppError, Result},
@@ -26,11 +27,37 @@ pub struct MonthlyRevenue {
     pub payment_count: i64,
 }
 
+/// Aggregates over every payment on file for one member, whatever page
+/// of them is on screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemberPaymentTotals {
+    /// Rows of any status.
+    pub payment_count: i64,
+    /// Sum of Completed payments only — refunded, pending and failed
+    /// money wasn't collected.
+    pub completed_cents: i64,
+}
+
 #[async_trait]
 pub trait PaymentRepository: Send + Sync {
     async fn create(&self, payment: Payment) -> Result<Payment>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Payment>>;
     async fn find_by_member(&self, member_id: Uuid) -> Result<Vec<Payment>>;
+    /// One page of `find_by_member`, newest first.
+    async fn find_by_member_paginated(
+        &self,
+        member_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Payment>>;
+    /// Count and collected total over all of the member's payments,
+    /// computed in SQL so a paginated view doesn't sum just its page.
+    async fn member_payment_totals(&self, member_id: Uuid) -> Result<MemberPaymentTotals>;
+    /// The member's most recent dues payment (`payment_type =
+    /// 'membership'`) by `created_at`, whatever its status. Feeds
+    /// `DuesStatus::compute` — donations and other payments don't say
+    /// anything about dues, so they're skipped.
+    async fn latest_payment(&self, member_id: Uuid) -> Result<Option<Payment>>;
     async fn find_by_stripe_id(&self, stripe_id: &str) -> Result<Option<Payment>>;
     async fn update(&self, id: Uuid, payment: Payment) -> Result<Payment>;
     /// Atomically flip a Pending payment to Completed and stamp the
@@ -52,6 +79,7 @@ pub trait PaymentRepository: Send + Sync {
     /// Claim a Completed payment for refund. Atomic conditional UPDATE
     /// (`WHERE status='Completed'`) — only the first caller observes
     /// rows_affected==1; concurrent admin clicks see false and bail.
+    /// Stamps `refunded_at`.
     /// Pair with `unclaim_refund` if the subsequent Stripe call fails.
     async fn claim_payment_for_refund(&self, id: Uuid) -> Result<bool>;
     /// Roll back `claim_payment_for_refund` after a Stripe failure so
@@ -62,7 +90,8 @@ pub trait PaymentRepository: Send + Sync {
     /// Mark a payment Refunded, unconditionally. Used by the Stripe
     /// `charge.refunded` webhook handler when the row hasn't already
     /// been flipped by our own admin-button refund (caller filters
-    /// out `Refunded` echoes). Idempotent under repeat calls.
+    /// out `Refunded` echoes). Idempotent under repeat calls: a repeat
+    /// keeps the first `refunded_at`.
     async fn mark_refunded(&self, id: Uuid) -> Result<()>;
     /// Idempotently extend a member's dues for a single Payment.
     ///
@@ -115,6 +144,7 @@ struct PaymentRow {
     donor_name: Option<String>,
     donor_email: Option<String>,
     paid_at: Option<NaiveDateTime>,
+    refunded_at: Option<NaiveDateTime>,
     created_at: NaiveDateTime,
     updated_at: NaiveDateTime,
 }
@@ -177,43 +207,20 @@ impl SqlitePaymentRepository {
             payer,
             amount_cents: row.amount_cents,
             currency: row.currency,
-            status: Self::parse_payment_status(&row.status)?,
+            status: row.status.parse()?,
             payment_method: Self::parse_payment_method(&row.payment_method)?,
             kind,
             external_id,
             description: row.description,
             paid_at: row.paid_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            refunded_at: row.refunded_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
             created_at: DateTime::from_naive_utc_and_offset(row.created_at, Utc),
             updated_at: DateTime::from_naive_utc_and_offset(row.updated_at, Utc),
         })
     }
 
-    fn parse_payment_status(s: &str) -> Result<PaymentStatus> {
-        match s {
-            "Pending" => Ok(PaymentStatus::Pending),
-            "Completed" => Ok(PaymentStatus::Completed),
-            "Failed" => Ok(PaymentStatus::Failed),
-            "Refunded" => Ok(PaymentStatus::Refunded),
-            _ => Err(AppError::Internal(format!("Invalid payment status: {}", s))),
-        }
-    }
-
-    fn payment_status_to_str(status: &PaymentStatus) -> &'static str {
-        match status {
-            PaymentStatus::Pending => "Pending",
-            PaymentStatus::Completed => "Completed",
-            PaymentStatus::Failed => "Failed",
-            PaymentStatus::Refunded => "Refunded",
-        }
-    }
-
     fn parse_payment_method(s: &str) -> Result<PaymentMethod> {
-        match s {
-            "Stripe" => Ok(PaymentMethod::Stripe),
-            "Manual" => Ok(PaymentMethod::Manual),
-            "Waived" => Ok(PaymentMethod::Waived),
-            _ => Err(AppError::Internal(format!("Invalid payment method: {}", s))),
-        }
+        Ok(s.parse()?)
     }
 
     fn payment_method_to_str(method: &PaymentMethod) -> &'static str {
@@ -230,9 +237,10 @@ impl PaymentRepository for SqlitePaymentRepository {
     async fn create(&self, payment: Payment) -> Result<Payment> {
         let id_str = payment.id.to_string();
         let amount_cents_int = payment.amount_cents;
-        let status_str = Self::payment_status_to_str(&payment.status);
+        let status_str = payment.status.as_str();
         let method_str = Self::payment_method_to_str(&payment.payment_method);
         let paid_at_naive = payment.paid_at.map(|dt| dt.naive_utc());
+        let refunded_at_naive = payment.refunded_at.map(|dt| dt.naive_utc());
         let now = Utc::now().naive_utc();
 
         // Decompose the typed Payer / PaymentKind / StripeRef back
@@ -253,8 +261,8 @@ impl PaymentRepository for SqlitePaymentRepository {
                 payment_method, stripe_payment_id, description,
                 payment_type, donation_campaign_id,
                 donor_name, donor_email,
-                paid_at, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                paid_at, refunded_at, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&id_str)
@@ -270,6 +278,7 @@ impl PaymentRepository for SqlitePaymentRepository {
         .bind(&donor_name)
         .bind(&donor_email)
         .bind(paid_at_naive)
+        .bind(refunded_at_naive)
         .bind(now)
         .bind(now)
         .execute(&self.pool)
@@ -289,7 +298,7 @@ impl PaymentRepository for SqlitePaymentRepository {
                    payment_method, stripe_payment_id, description,
                    payment_type, donation_campaign_id,
                    donor_name, donor_email,
-                   paid_at, created_at, updated_at
+                   paid_at, refunded_at, created_at, updated_at
             FROM payments
             WHERE id = ?
             "#
@@ -313,7 +322,7 @@ impl PaymentRepository for SqlitePaymentRepository {
                    payment_method, stripe_payment_id, description,
                    payment_type, donation_campaign_id,
                    donor_name, donor_email,
-                   paid_at, created_at, updated_at
+                   paid_at, refunded_at, created_at, updated_at
             FROM payments
             WHERE member_id = ?
             ORDER BY created_at DESC
@@ -324,9 +333,75 @@ impl PaymentRepository for SqlitePaymentRepository {
         .await
         .map_err(AppError::Database)?;
 
-        rows.into_iter()
-            .map(Self::row_to_payment)
-            .collect()
+        Ok(skip_malformed(rows, "payments", |r| r.id.clone(), Self::row_to_payment))
+    }
+
+    async fn find_by_member_paginated(
+        &self,
+        member_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Payment>> {
+        let rows = sqlx::query_as::<_, PaymentRow>(
+            r#"
+            SELECT id, member_id, amount_cents, currency, status,
+                   payment_method, stripe_payment_id, description,
+                   payment_type, donation_campaign_id,
+                   donor_name, donor_email,
+                   paid_at, refunded_at, created_at, updated_at
+            FROM payments
+            WHERE member_id = ?
+            ORDER BY created_at DESC, rowid DESC
+            LIMIT ? OFFSET ?
+            "#
+        )
+        .bind(member_id.to_string())
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(skip_malformed(rows, "payments", |r| r.id.clone(), Self::row_to_payment))
+    }
+
+    async fn member_payment_totals(&self, member_id: Uuid) -> Result<MemberPaymentTotals> {
+        let (payment_count, completed_cents): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*),
+                   COALESCE(SUM(CASE WHEN status = 'Completed' THEN amount_cents ELSE 0 END), 0)
+            FROM payments
+            WHERE member_id = ?
+            "#
+        )
+        .bind(member_id.to_string())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(MemberPaymentTotals { payment_count, completed_cents })
+    }
+
+    async fn latest_payment(&self, member_id: Uuid) -> Result<Option<Payment>> {
+        let row = sqlx::query_as::<_, PaymentRow>(
+            r#"
+            SELECT id, member_id, amount_cents, currency, status,
+                   payment_method, stripe_payment_id, description,
+                   payment_type, donation_campaign_id,
+                   donor_name, donor_email,
+                   paid_at, refunded_at, created_at, updated_at
+            FROM payments
+            WHERE member_id = ? AND payment_type = 'membership'
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#
+        )
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::Database)?;
+
+        row.map(Self::row_to_payment).transpose()
     }
 
     async fn find_by_stripe_id(&self, stripe_id: &str) -> Result<Option<Payment>> {
@@ -336,7 +411,7 @@ impl PaymentRepository for SqlitePaymentRepository {
                    payment_method, stripe_payment_id, description,
                    payment_type, donation_campaign_id,
                    donor_name, donor_email,
-                   paid_at, created_at, updated_at
+                   paid_at, refunded_at, created_at, updated_at
             FROM payments
             WHERE stripe_payment_id = ?
             "#
@@ -355,7 +430,7 @@ impl PaymentRepository for SqlitePaymentRepository {
     async fn update(&self, id: Uuid, payment: Payment) -> Result<Payment> {
         let id_str = id.to_string();
         let now = Utc::now().naive_utc();
-        let status_str = Self::payment_status_to_str(&payment.status);
+        let status_str = payment.status.as_str();
         let method_str = Self::payment_method_to_str(&payment.payment_method);
         let paid_at_naive = payment.paid_at.map(|dt| dt.naive_utc());
 
@@ -436,10 +511,11 @@ impl PaymentRepository for SqlitePaymentRepository {
         let now = Utc::now().naive_utc();
         let res = sqlx::query(
             "UPDATE payments \
-             SET status = 'Refunded', updated_at = ? \
+             SET status = 'Refunded', refunded_at = ?, updated_at = ? \
              WHERE id = ? AND status = 'Completed'",
         )
         .bind(now)
+        .bind(now)
         .bind(id.to_string())
         .execute(&self.pool)
         .await
@@ -451,7 +527,7 @@ impl PaymentRepository for SqlitePaymentRepository {
         let now = Utc::now().naive_utc();
         sqlx::query(
             "UPDATE payments \
-             SET status = 'Completed', updated_at = ? \
+             SET status = 'Completed', refunded_at = NULL, updated_at = ? \
              WHERE id = ? AND status = 'Refunded'",
         )
         .bind(now)
@@ -463,10 +539,14 @@ impl PaymentRepository for SqlitePaymentRepository {
     }
 
     async fn mark_refunded(&self, id: Uuid) -> Result<()> {
+        let now = Utc::now().naive_utc();
         sqlx::query(
-            "UPDATE payments SET status = 'Refunded', updated_at = ? WHERE id = ?",
+            "UPDATE payments \
+             SET status = 'Refunded', refunded_at = COALESCE(refunded_at, ?), updated_at = ? \
+             WHERE id = ?",
         )
-        .bind(Utc::now().naive_utc())
+        .bind(now)
+        .bind(now)
         .bind(id.to_string())
         .execute(&self.pool)
         .await
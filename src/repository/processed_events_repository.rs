@@ -7,12 +7,26 @@
 //! methods here is small but load-bearing — without `release`, a
 //! mid-handler failure would leave the event permanently claimed and
 //! the next Stripe retry would skip without re-running the failed step.
+//!
+//! Claims only matter while Stripe might still redeliver the event, so
+//! the hourly cleanup task drops rows older than [`REPLAY_WINDOW`] plus
+//! [`PRUNE_MARGIN`].
 
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
 use sqlx::SqlitePool;
 
 use crate::error::{AppError, Result};
 
+/// How long Stripe keeps retrying an undelivered event. A claim younger
+/// than this can still be the only thing standing between a redelivery
+/// and a second dues extension.
+pub const REPLAY_WINDOW: Duration = Duration::days(3);
+
+/// Extra time a claim is kept past [`REPLAY_WINDOW`], for a manual
+/// resend from the Stripe dashboard or clock skew between us and them.
+pub const PRUNE_MARGIN: Duration = Duration::days(27);
+
 #[async_trait]
 pub trait ProcessedEventsRepository: Send + Sync {
     /// Atomically claim an event id. Returns `true` if this caller
@@ -24,6 +38,11 @@ pub trait ProcessedEventsRepository: Send + Sync {
     /// after the claim, so the next Stripe retry can re-run. Best-effort
     /// — if THIS fails the original error is the more important signal.
     async fn release(&self, event_id: &str) -> Result<()>;
+    /// Delete claims older than [`REPLAY_WINDOW`] + [`PRUNE_MARGIN`] as
+    /// of `now`. Returns how many were removed.
+    async fn prune_expired(&self, now: DateTime<Utc>) -> Result<u64>;
+    /// Claims currently on file.
+    async fn count(&self) -> Result<i64>;
 }
 
 pub struct SqliteProcessedEventsRepository {
@@ -58,4 +77,25 @@ impl ProcessedEventsRepository for SqliteProcessedEventsRepository {
             .map_err(|e| AppError::Internal(format!("Idempotency release failed: {}", e)))?;
         Ok(())
     }
+
+    async fn prune_expired(&self, now: DateTime<Utc>) -> Result<u64> {
+        // `processed_at` is SQLite's CURRENT_TIMESTAMP text, so the
+        // cutoff is bound in the same format for a plain string compare.
+        let cutoff = (now - (REPLAY_WINDOW + PRUNE_MARGIN))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let result = sqlx::query("DELETE FROM processed_stripe_events WHERE processed_at < ?")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::Internal(format!("Idempotency prune failed: {}", e)))?;
+        Ok(result.rows_affected())
+    }
+
+    async fn count(&self) -> Result<i64> {
+        let count = sqlx::query_scalar("SELECT COUNT(*) FROM processed_stripe_events")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(count)
+    }
 }
@@ -0,0 +1,399 @@
+//! Per-admin notifications for events addressed to the admins
+//! themselves: new signups, failed payments, event proposals. These
+//! go only through here, so an admin on digest gets them in the
+//! digest and nowhere else. The org-wide `AdminAlert` path (contact
+//! email + Discord admin channel) stays for operational alerts —
+//! webhook signature failures, renewals that fell off the schedule —
+//! that aren't routed per admin.
+//!
+//! Which events notify, and which admins hear about them, are app
+//! settings (`notifications.*`). Each admin picks a channel on their
+//! profile page: email (the default), a Discord DM from the org's bot,
+//! or nothing. Every (admin, event) pair is recorded once in
+//! `admin_notifications`, keyed by the event's dedupe key, so a
+//! redelivered webhook or a re-dispatch never notifies twice.
+//!
+//! In digest mode rows are only queued; `send_due_digests` (run from
+//! the hourly billing runner) batches an admin's queue into one
+//! message once its oldest entry is a day old. The same sweep retries
+//! immediate notifications whose delivery failed.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::{AdminNotificationChannel, AdminNotificationKind},
+    email::{
+        self,
+        templates::{AdminNotificationHtml, AdminNotificationText},
+        EmailSender,
+    },
+    error::Result,
+    integrations::{discord::is_valid_snowflake, discord_client::DiscordClient},
+    service::settings_service::SettingsService,
+};
+
+/// How long the oldest queued notification waits before the admin's
+/// digest goes out.
+pub const DIGEST_INTERVAL_HOURS: i64 = 24;
+
+/// Discord rejects messages longer than this.
+const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+#[derive(Debug, Clone, FromRow)]
+struct Recipient {
+    id: String,
+    username: String,
+    email: String,
+    discord_id: Option<String>,
+    channel: Option<String>,
+}
+
+impl Recipient {
+    fn channel(&self) -> AdminNotificationChannel {
+        self.channel
+            .as_deref()
+            .and_then(|c| c.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct QueuedRow {
+    id: String,
+    kind: String,
+    subject: String,
+    body: String,
+    created_at: NaiveDateTime,
+}
+
+const SELECT_ADMINS: &str = "SELECT m.id, m.username, m.email, m.discord_id, p.channel \
+     FROM members m \
+     LEFT JOIN admin_notification_preferences p ON p.member_id = m.id \
+     WHERE m.is_admin = 1 AND m.status IN ('Active', 'Honorary')";
+
+pub struct AdminNotificationService {
+    pool: SqlitePool,
+    settings: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    base_url: String,
+}
+
+impl AdminNotificationService {
+    pub fn new(
+        pool: SqlitePool,
+        settings: Arc<SettingsService>,
+        email_sender: Arc<dyn EmailSender>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            pool,
+            settings,
+            email_sender,
+            base_url,
+        }
+    }
+
+    /// The admin's chosen channel; email when they haven't picked one.
+    pub async fn channel(&self, member_id: Uuid) -> Result<AdminNotificationChannel> {
+        let channel: Option<String> = sqlx::query_scalar(
+            "SELECT channel FROM admin_notification_preferences WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(channel.and_then(|c| c.parse().ok()).unwrap_or_default())
+    }
+
+    pub async fn set_channel(
+        &self,
+        member_id: Uuid,
+        channel: AdminNotificationChannel,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO admin_notification_preferences (member_id, channel) VALUES (?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 channel = excluded.channel, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(channel.as_str())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Kinds listed in `notifications.admin_events`. Unknown entries
+    /// (only possible through a typo in the setting) are logged and
+    /// skipped.
+    pub async fn enabled_kinds(&self) -> Vec<AdminNotificationKind> {
+        let value = self
+            .settings
+            .get_value("notifications.admin_events")
+            .await
+            .unwrap_or_default();
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| match s.parse() {
+                Ok(kind) => Some(kind),
+                Err(e) => {
+                    tracing::warn!("Ignoring notifications.admin_events entry: {}", e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    async fn digest_mode(&self) -> bool {
+        self.settings
+            .get_bool("notifications.admin_digest")
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Admins who should hear about events: every active admin, or
+    /// those named in `notifications.admin_recipients` (usernames or
+    /// emails, case-insensitive).
+    async fn recipients(&self) -> Result<Vec<Recipient>> {
+        let admins: Vec<Recipient> = sqlx::query_as(SELECT_ADMINS).fetch_all(&self.pool).await?;
+        let wanted: Vec<String> = self
+            .settings
+            .get_value("notifications.admin_recipients")
+            .await
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if wanted.is_empty() {
+            return Ok(admins);
+        }
+        Ok(admins
+            .into_iter()
+            .filter(|a| {
+                wanted.contains(&a.username.to_lowercase())
+                    || wanted.contains(&a.email.to_lowercase())
+            })
+            .collect())
+    }
+
+    /// Route one event to every recipient admin. Returns how many
+    /// admins were newly notified (or queued, in digest mode); admins
+    /// who already have this `key` are skipped. Delivery failures are
+    /// logged, left queued for the next digest sweep, and reported as
+    /// the error once every admin has been tried.
+    pub async fn notify(
+        &self,
+        kind: AdminNotificationKind,
+        key: &str,
+        subject: &str,
+        body: &str,
+    ) -> Result<usize> {
+        if !self.enabled_kinds().await.contains(&kind) {
+            return Ok(0);
+        }
+        let digest = self.digest_mode().await;
+
+        let mut notified = 0;
+        let mut first_err = None;
+        for admin in self.recipients().await? {
+            if admin.channel() == AdminNotificationChannel::None {
+                continue;
+            }
+            let id = Uuid::new_v4().to_string();
+            let inserted = sqlx::query(
+                "INSERT OR IGNORE INTO admin_notifications \
+                     (id, member_id, kind, dedupe_key, subject, body) \
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&id)
+            .bind(&admin.id)
+            .bind(kind.as_str())
+            .bind(key)
+            .bind(subject)
+            .bind(body)
+            .execute(&self.pool)
+            .await?;
+            if inserted.rows_affected() == 0 {
+                continue;
+            }
+            notified += 1;
+            if digest {
+                continue;
+            }
+
+            match self.deliver(&admin, subject, body).await {
+                Ok(()) => self.mark_sent(&[id]).await?,
+                Err(e) => {
+                    tracing::error!(
+                        "Admin notification {} to {} failed: {}",
+                        key,
+                        admin.username,
+                        e
+                    );
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+        match first_err {
+            Some(e) => Err(e),
+            None => Ok(notified),
+        }
+    }
+
+    /// Send each admin whose oldest unsent notification is at least
+    /// `DIGEST_INTERVAL_HOURS` old one message covering everything
+    /// queued for them. Returns the number of digests sent.
+    pub async fn send_due_digests(&self, now: DateTime<Utc>) -> Result<usize> {
+        let due_before = (now - Duration::hours(DIGEST_INTERVAL_HOURS)).naive_utc();
+        let admins: Vec<Recipient> = sqlx::query_as(&format!(
+            "{} AND EXISTS (SELECT 1 FROM admin_notifications n \
+                 WHERE n.member_id = m.id AND n.sent_at IS NULL AND n.created_at <= ?)",
+            SELECT_ADMINS
+        ))
+        .bind(due_before)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut sent = 0;
+        for admin in admins {
+            let rows: Vec<QueuedRow> = sqlx::query_as(
+                "SELECT id, kind, subject, body, created_at FROM admin_notifications \
+                 WHERE member_id = ? AND sent_at IS NULL \
+                 ORDER BY created_at, id",
+            )
+            .bind(&admin.id)
+            .fetch_all(&self.pool)
+            .await?;
+            let ids: Vec<String> = rows.iter().map(|r| r.id.clone()).collect();
+
+            // Opted out since these were queued: drop them unsent.
+            if admin.channel() == AdminNotificationChannel::None {
+                self.mark_sent(&ids).await?;
+                continue;
+            }
+
+            let subject = format!(
+                "Daily digest: {} notification{}",
+                rows.len(),
+                if rows.len() == 1 { "" } else { "s" }
+            );
+            let body = digest_body(&rows);
+            match self.deliver(&admin, &subject, &body).await {
+                Ok(()) => {
+                    self.mark_sent(&ids).await?;
+                    sent += 1;
+                }
+                Err(e) => tracing::error!(
+                    "Admin notification digest to {} failed: {}",
+                    admin.username,
+                    e
+                ),
+            }
+        }
+        Ok(sent)
+    }
+
+    async fn mark_sent(&self, ids: &[String]) -> Result<()> {
+        for id in ids {
+            sqlx::query("UPDATE admin_notifications SET sent_at = CURRENT_TIMESTAMP WHERE id = ?")
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Deliver on the admin's channel. Discord falls back to email
+    /// when the integration is off or the admin has no usable
+    /// `discord_id`.
+    async fn deliver(&self, admin: &Recipient, subject: &str, body: &str) -> Result<()> {
+        if admin.channel() == AdminNotificationChannel::Discord {
+            if let Some((client, user_id)) = self.discord_target(admin).await {
+                let content = truncate_chars(
+                    &format!("🔔 **{}**\n{}", subject, body),
+                    DISCORD_MESSAGE_LIMIT,
+                );
+                return client.send_direct_message(&user_id, &content).await;
+            }
+        }
+
+        let org_name = self
+            .settings
+            .get_value("org.name")
+            .await
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Coterie".to_string());
+        let profile_url = format!("{}/portal/profile", self.base_url.trim_end_matches('/'));
+        let html = AdminNotificationHtml {
+            org_name: &org_name,
+            subject,
+            body,
+            profile_url: &profile_url,
+        };
+        let text = AdminNotificationText {
+            org_name: &org_name,
+            subject,
+            body,
+            profile_url: &profile_url,
+        };
+        let message = email::message_from_templates(
+            admin.email.clone(),
+            format!("[{}] {}", org_name, subject),
+            &html,
+            &text,
+        )?;
+        self.email_sender.send(&message).await
+    }
+
+    async fn discord_target(&self, admin: &Recipient) -> Option<(DiscordClient, String)> {
+        let user_id = admin
+            .discord_id
+            .as_deref()
+            .filter(|id| is_valid_snowflake(id))?;
+        let cfg = self.settings.get_discord_config().await.ok()?;
+        if !cfg.enabled || cfg.bot_token.is_empty() {
+            return None;
+        }
+        Some((DiscordClient::new(cfg.bot_token), user_id.to_string()))
+    }
+}
+
+/// Digest text: one section per kind, in `AdminNotificationKind::ALL`
+/// order, each entry its subject, time and body.
+fn digest_body(rows: &[QueuedRow]) -> String {
+    let mut sections = Vec::new();
+    for kind in AdminNotificationKind::ALL {
+        let entries: Vec<String> = rows
+            .iter()
+            .filter(|r| r.kind == kind.as_str())
+            .map(|r| {
+                format!(
+                    "• {} ({})\n{}",
+                    r.subject,
+                    r.created_at.format("%Y-%m-%d %H:%M UTC"),
+                    r.body
+                )
+            })
+            .collect();
+        if !entries.is_empty() {
+            sections.push(format!("{}\n\n{}", kind.label(), entries.join("\n\n")));
+        }
+    }
+    sections.join("\n\n\n")
+}
+
+fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.push('…');
+    out
+}
@@ -26,7 +26,8 @@ use crate::{
 /// multipart form into one of these and hands it off. When
 /// `publish_now` is true the service stamps `published_at` and
 /// dispatches `IntegrationEvent::AnnouncementPublished`; when false
-/// the row is persisted as a Draft.
+/// the row is persisted as a Draft. Whether publishing notifies
+/// members follows `notify_members`.
 pub struct CreateAnnouncementInput {
     pub title: String,
     pub content: String,
@@ -40,6 +41,12 @@ pub struct CreateAnnouncementInput {
     /// true (publish-now wins). A Draft row with this set is what the
     /// background runner picks up at-or-after the scheduled time.
     pub scheduled_publish_at: Option<DateTime<Utc>>,
+    /// Optional expiry; must fall after the announcement publishes.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Open a member comment thread under the announcement.
+    pub comments_enabled: bool,
+    /// Broadcast on publish. `None` follows the type's default.
+    pub notify_members: Option<bool>,
 }
 
 /// Typed input for updating an announcement. Carries the editable
@@ -57,6 +64,31 @@ pub struct UpdateAnnouncementInput {
     /// Optional future-publish time. Persisted as-is on the row;
     /// empty/None clears any prior schedule.
     pub scheduled_publish_at: Option<DateTime<Utc>>,
+    /// Optional expiry; must fall after the announcement publishes.
+    /// None clears it.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Turning comments off hides the thread but keeps the comments.
+    pub comments_enabled: bool,
+    /// Broadcast on a later publish. `None` follows the type's default.
+    pub notify_members: Option<bool>,
+}
+
+pub const EXPIRY_BEFORE_PUBLISH: &str = "Expiry must be after the publish time";
+
+/// An expiry has to come after the moment the announcement goes (or
+/// went) live: its `published_at`, else its schedule, else now for a
+/// plain draft. Anything earlier would hide it before anyone saw it.
+pub fn check_expiry(
+    expires_at: Option<DateTime<Utc>>,
+    publishes_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    match expires_at {
+        Some(expires_at) if expires_at <= publishes_at.unwrap_or(now) => {
+            Err(AppError::Validation(EXPIRY_BEFORE_PUBLISH.to_string()))
+        }
+        _ => Ok(()),
+    }
 }
 
 pub struct AnnouncementAdminService {
@@ -96,6 +128,7 @@ impl AnnouncementAdminService {
         } else {
             input.scheduled_publish_at
         };
+        check_expiry(input.expires_at, published_at.or(scheduled_publish_at), now)?;
 
         let announcement = Announcement {
             id: Uuid::new_v4(),
@@ -108,6 +141,10 @@ impl AnnouncementAdminService {
             image_url: input.image_url,
             published_at,
             scheduled_publish_at,
+            expires_at: input.expires_at,
+            comments_enabled: input.comments_enabled,
+            notify_members: input.notify_members,
+            notified_at: None,
             created_by: actor_id,
             created_at: now,
             updated_at: now,
@@ -131,17 +168,17 @@ impl AnnouncementAdminService {
         // published_at) don't fire; they'll fire when the admin hits
         // the Publish button later.
         if created.published_at.is_some() {
-            self.integration_manager
-                .handle_event(IntegrationEvent::AnnouncementPublished(created.clone()))
-                .await;
+            return Ok(self.notify_published(created, None).await);
         }
 
         Ok(created)
     }
 
-    /// Update an announcement. Preserves `published_at`, `created_by`,
-    /// and `created_at` from the existing row. Audits `update_announcement`.
-    /// No integration dispatch — updates are silent.
+    /// Update an announcement. Preserves `published_at`, `notified_at`,
+    /// `created_by`, and `created_at` from the existing row. Audits `update_announcement`.
+    /// No integration dispatch — updates are silent. An already-expired
+    /// announcement stays editable: its expiry is only checked against
+    /// its original `published_at`.
     pub async fn update(
         &self,
         actor_id: Uuid,
@@ -150,6 +187,11 @@ impl AnnouncementAdminService {
     ) -> Result<Announcement> {
         let existing = self.announcement_repo.find_by_id(announcement_id).await?
             .ok_or_else(|| AppError::NotFound("Announcement not found".to_string()))?;
+        check_expiry(
+            input.expires_at,
+            existing.published_at.or(input.scheduled_publish_at),
+            Utc::now(),
+        )?;
 
         let updated = Announcement {
             id: announcement_id,
@@ -162,6 +204,10 @@ impl AnnouncementAdminService {
             image_url: input.image_url,
             published_at: existing.published_at,
             scheduled_publish_at: input.scheduled_publish_at,
+            expires_at: input.expires_at,
+            comments_enabled: input.comments_enabled,
+            notify_members: input.notify_members,
+            notified_at: existing.notified_at,
             created_by: existing.created_by,
             created_at: existing.created_at,
             updated_at: Utc::now(),
@@ -200,10 +246,14 @@ impl AnnouncementAdminService {
     /// Publish a Draft announcement. Idempotent: re-publishing an
     /// already-published row updates `updated_at` and writes an audit
     /// row but does NOT re-dispatch the integration event.
+    ///
+    /// `notify` is the admin's choice at publish time; see
+    /// `notify_published` for what `None` means.
     pub async fn publish(
         &self,
         actor_id: Uuid,
         announcement_id: Uuid,
+        notify: Option<bool>,
     ) -> Result<Announcement> {
         let existing = self.announcement_repo.find_by_id(announcement_id).await?
             .ok_or_else(|| AppError::NotFound("Announcement not found".to_string()))?;
@@ -230,9 +280,7 @@ impl AnnouncementAdminService {
         // public announcement (which can happen if an admin clicks
         // Publish twice for some reason).
         if !was_already_published {
-            self.integration_manager
-                .handle_event(IntegrationEvent::AnnouncementPublished(saved.clone()))
-                .await;
+            return Ok(self.notify_published(saved, notify).await);
         }
 
         Ok(saved)
@@ -243,7 +291,8 @@ impl AnnouncementAdminService {
     /// Published (via the repo's conditional UPDATE), and on each
     /// successful claim writes an `auto_publish_announcement` audit
     /// row (actor_id = None — system action) and dispatches
-    /// `IntegrationEvent::AnnouncementPublished`. Returns the number
+    /// `IntegrationEvent::AnnouncementPublished` if the row's
+    /// `notify_members` (or its type's default) says to. Returns the number
     /// of rows published. Errors on individual rows are logged but do
     /// not stop the loop.
     pub async fn publish_scheduled(&self) -> Result<u32> {
@@ -274,9 +323,7 @@ impl AnnouncementAdminService {
                         Some(&published.title),
                         None,
                     ).await;
-                    self.integration_manager
-                        .handle_event(IntegrationEvent::AnnouncementPublished(published))
-                        .await;
+                    self.notify_published(published, None).await;
                     sent += 1;
                 }
                 Ok(false) => {
@@ -290,6 +337,52 @@ impl AnnouncementAdminService {
         Ok(sent)
     }
 
+    /// Dispatch `AnnouncementPublished` for a row that just went live,
+    /// and stamp `notified_at` so it only happens once. With `choice`
+    /// unset, the row's `notify_members` decides (falling back to its
+    /// type's default) and an already-notified row stays quiet. An
+    /// explicit `Some(true)` notifies even if members heard about this
+    /// announcement before; `Some(false)` never does.
+    async fn notify_published(
+        &self,
+        mut announcement: Announcement,
+        choice: Option<bool>,
+    ) -> Announcement {
+        let notify = match choice.or(announcement.notify_members) {
+            Some(notify) => notify,
+            None => match announcement.announcement_type_id {
+                Some(type_id) => self
+                    .announcement_repo
+                    .type_notifies_members(type_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("announcement type notify default lookup failed for {}: {}", type_id, e);
+                        true
+                    }),
+                None => true,
+            },
+        };
+        if !notify {
+            return announcement;
+        }
+
+        match self.announcement_repo.mark_notified(announcement.id, choice == Some(true)).await {
+            Ok(true) => {
+                announcement.notified_at = Some(Utc::now());
+                self.integration_manager
+                    .handle_event(IntegrationEvent::AnnouncementPublished(announcement.clone()))
+                    .await;
+            }
+            Ok(false) => {
+                // Members were notified on an earlier publish.
+            }
+            Err(e) => {
+                tracing::error!("mark_notified failed for announcement {}: {}", announcement.id, e);
+            }
+        }
+        announcement
+    }
+
     /// Unpublish a Published announcement (back to Draft). Audits
     /// `unpublish_announcement`. No integration dispatch — unpublish
     /// is silent on the integration channel.
@@ -392,6 +485,9 @@ mod tests {
             image_url: None,
             publish_now,
             scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
         }
     }
 
@@ -454,6 +550,9 @@ mod tests {
             featured: true,
             image_url: None,
             scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
         };
 
         let result = svc.update(actor, announcement.id, input).await.unwrap();
@@ -486,7 +585,7 @@ mod tests {
         let announcement = svc.create(actor, create_input(false)).await.unwrap();
         assert!(announcement.published_at.is_none());
 
-        let result = svc.publish(actor, announcement.id).await.unwrap();
+        let result = svc.publish(actor, announcement.id, None).await.unwrap();
         assert!(result.published_at.is_some(), "publish should stamp published_at");
         assert_eq!(audit_count(&pool, "publish_announcement", &announcement.id.to_string()).await, 1);
     }
@@ -507,8 +606,8 @@ mod tests {
         let announcement = svc.create(actor, create_input(true)).await.unwrap();
         assert!(announcement.published_at.is_some());
 
-        let _ = svc.publish(actor, announcement.id).await.unwrap();
-        let again = svc.publish(actor, announcement.id).await.unwrap();
+        let _ = svc.publish(actor, announcement.id, None).await.unwrap();
+        let again = svc.publish(actor, announcement.id, None).await.unwrap();
         assert!(again.published_at.is_some());
 
         assert_eq!(audit_count(&pool, "publish_announcement", &announcement.id.to_string()).await, 2);
@@ -527,4 +626,28 @@ mod tests {
         assert!(result.published_at.is_none(), "unpublish should clear published_at");
         assert_eq!(audit_count(&pool, "unpublish_announcement", &announcement.id.to_string()).await, 1);
     }
+
+    #[tokio::test]
+    async fn expiry_must_follow_publish_time() {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_actor(&pool).await;
+
+        let mut input = create_input(true);
+        input.expires_at = Some(Utc::now() - chrono::Duration::hours(1));
+        let err = svc.create(actor, input).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m == EXPIRY_BEFORE_PUBLISH));
+
+        let scheduled = Utc::now() + chrono::Duration::days(3);
+        let mut input = create_input(false);
+        input.scheduled_publish_at = Some(scheduled);
+        input.expires_at = Some(scheduled - chrono::Duration::days(1));
+        assert!(svc.create(actor, input).await.is_err());
+
+        let mut input = create_input(false);
+        input.scheduled_publish_at = Some(scheduled);
+        input.expires_at = Some(scheduled + chrono::Duration::days(1));
+        let created = svc.create(actor, input).await.unwrap();
+        assert_eq!(created.expires_at, Some(scheduled + chrono::Duration::days(1)));
+    }
 }
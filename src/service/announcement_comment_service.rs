@@ -0,0 +1,300 @@
+//! Member comment threads on announcements. A thread exists only while
+//! the announcement has `comments_enabled`, and it's readable by
+//! exactly the audience that can read the announcement: anyone for a
+//! published public one, signed-in members for a members-only one.
+//! Drafts and expired announcements have no visible thread.
+//!
+//! Bodies are stored as typed and rendered through `web::markdown` on
+//! display. Admins moderate by hiding (reversible) or deleting (soft,
+//! `deleted_at`); both drop the comment from member and public views
+//! and are audited.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::Announcement,
+    error::{AppError, Result},
+    repository::AnnouncementRepository,
+    service::audit_service::AuditService,
+};
+
+/// Longest comment body we accept, in characters.
+pub const MAX_COMMENT_LEN: usize = 4000;
+
+/// At most this many comments per member per `COMMENT_RATE_WINDOW_MINUTES`,
+/// across all announcements.
+pub const COMMENT_RATE_LIMIT: i64 = 5;
+pub const COMMENT_RATE_WINDOW_MINUTES: i64 = 10;
+
+/// Who is asking to see a thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommentViewer {
+    Public,
+    Member,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnnouncementComment {
+    pub id: Uuid,
+    pub announcement_id: Uuid,
+    pub member_id: Uuid,
+    /// The author's username.
+    pub author: String,
+    /// Markdown as the member typed it.
+    pub body: String,
+    /// Set when an admin has hidden the comment. Only admin listings
+    /// include hidden comments.
+    pub hidden_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct CommentRow {
+    id: String,
+    announcement_id: String,
+    member_id: String,
+    author: String,
+    body: String,
+    hidden_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl From<CommentRow> for AnnouncementComment {
+    fn from(r: CommentRow) -> Self {
+        Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            announcement_id: Uuid::parse_str(&r.announcement_id).unwrap_or_default(),
+            member_id: Uuid::parse_str(&r.member_id).unwrap_or_default(),
+            author: r.author,
+            body: r.body,
+            hidden_at: r
+                .hidden_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            created_at: DateTime::from_naive_utc_and_offset(r.created_at, Utc),
+        }
+    }
+}
+
+const SELECT_COMMENTS: &str = "SELECT c.id, c.announcement_id, c.member_id, m.username AS author, \
+            c.body, c.hidden_at, c.created_at \
+     FROM comments c JOIN members m ON m.id = c.member_id \
+     WHERE c.announcement_id = ? AND c.deleted_at IS NULL";
+
+pub struct AnnouncementCommentService {
+    pool: SqlitePool,
+    announcement_repo: Arc<dyn AnnouncementRepository>,
+    audit_service: Arc<AuditService>,
+}
+
+impl AnnouncementCommentService {
+    pub fn new(
+        pool: SqlitePool,
+        announcement_repo: Arc<dyn AnnouncementRepository>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            pool,
+            announcement_repo,
+            audit_service,
+        }
+    }
+
+    /// The announcement, if `viewer` may read it: published, not
+    /// expired, and public unless the viewer is a member. Anything
+    /// else is NotFound so a members-only announcement's existence
+    /// doesn't leak.
+    pub async fn visible_announcement(
+        &self,
+        announcement_id: Uuid,
+        viewer: CommentViewer,
+    ) -> Result<Announcement> {
+        let not_found = || AppError::NotFound("Announcement not found".to_string());
+        let announcement = self
+            .announcement_repo
+            .find_by_id(announcement_id)
+            .await?
+            .ok_or_else(not_found)?;
+        let readable = announcement.published_at.is_some()
+            && !announcement.is_expired_at(Utc::now())
+            && (announcement.is_public || viewer == CommentViewer::Member);
+        if readable {
+            Ok(announcement)
+        } else {
+            Err(not_found())
+        }
+    }
+
+    /// Visible comments on an announcement the viewer can read, oldest
+    /// first. NotFound when the announcement isn't readable or has
+    /// comments turned off.
+    pub async fn list_visible(
+        &self,
+        announcement_id: Uuid,
+        viewer: CommentViewer,
+    ) -> Result<Vec<AnnouncementComment>> {
+        let announcement = self.visible_announcement(announcement_id, viewer).await?;
+        if !announcement.comments_enabled {
+            return Err(AppError::NotFound("Comments are not enabled".to_string()));
+        }
+        let rows: Vec<CommentRow> = sqlx::query_as(&format!(
+            "{} AND c.hidden_at IS NULL ORDER BY c.created_at, c.id",
+            SELECT_COMMENTS
+        ))
+        .bind(announcement_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Every non-deleted comment, hidden ones included. Admin use.
+    pub async fn list_for_admin(&self, announcement_id: Uuid) -> Result<Vec<AnnouncementComment>> {
+        let rows: Vec<CommentRow> =
+            sqlx::query_as(&format!("{} ORDER BY c.created_at, c.id", SELECT_COMMENTS))
+                .bind(announcement_id.to_string())
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Post a comment as a member. The body is trimmed and must be
+    /// non-empty and at most `MAX_COMMENT_LEN` characters.
+    pub async fn post(
+        &self,
+        member_id: Uuid,
+        announcement_id: Uuid,
+        body: &str,
+    ) -> Result<AnnouncementComment> {
+        let announcement = self
+            .visible_announcement(announcement_id, CommentViewer::Member)
+            .await?;
+        if !announcement.comments_enabled {
+            return Err(AppError::Forbidden);
+        }
+
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(AppError::Validation("Comment can't be empty".to_string()));
+        }
+        if body.chars().count() > MAX_COMMENT_LEN {
+            return Err(AppError::Validation(format!(
+                "Comments are limited to {} characters",
+                MAX_COMMENT_LEN
+            )));
+        }
+
+        let now = Utc::now();
+        let since = now - Duration::minutes(COMMENT_RATE_WINDOW_MINUTES);
+        let recent: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM comments WHERE member_id = ? AND created_at > ?",
+        )
+        .bind(member_id.to_string())
+        .bind(since.naive_utc())
+        .fetch_one(&self.pool)
+        .await?;
+        if recent >= COMMENT_RATE_LIMIT {
+            return Err(AppError::TooManyRequests);
+        }
+
+        let id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO comments (id, announcement_id, member_id, body, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id.to_string())
+        .bind(announcement_id.to_string())
+        .bind(member_id.to_string())
+        .bind(body)
+        .bind(now.naive_utc())
+        .bind(now.naive_utc())
+        .execute(&self.pool)
+        .await?;
+
+        let row: CommentRow = sqlx::query_as(&format!("{} AND c.id = ?", SELECT_COMMENTS))
+            .bind(announcement_id.to_string())
+            .bind(id.to_string())
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.into())
+    }
+
+    /// Hide a comment from members and the public. Audits `hide_comment`.
+    pub async fn hide(&self, actor_id: Uuid, comment_id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE comments SET hidden_at = CURRENT_TIMESTAMP, hidden_by = ?, \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(actor_id.to_string())
+        .bind(comment_id.to_string())
+        .execute(&self.pool)
+        .await?;
+        self.audit_moderation(actor_id, comment_id, result.rows_affected(), "hide_comment")
+            .await
+    }
+
+    /// Undo `hide`. Audits `unhide_comment`.
+    pub async fn unhide(&self, actor_id: Uuid, comment_id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE comments SET hidden_at = NULL, hidden_by = NULL, \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(comment_id.to_string())
+        .execute(&self.pool)
+        .await?;
+        self.audit_moderation(
+            actor_id,
+            comment_id,
+            result.rows_affected(),
+            "unhide_comment",
+        )
+        .await
+    }
+
+    /// Soft-delete a comment. It disappears everywhere, admin listing
+    /// included. Audits `delete_comment`.
+    pub async fn delete(&self, actor_id: Uuid, comment_id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE comments SET deleted_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(comment_id.to_string())
+        .execute(&self.pool)
+        .await?;
+        self.audit_moderation(
+            actor_id,
+            comment_id,
+            result.rows_affected(),
+            "delete_comment",
+        )
+        .await
+    }
+
+    async fn audit_moderation(
+        &self,
+        actor_id: Uuid,
+        comment_id: Uuid,
+        rows_affected: u64,
+        action: &str,
+    ) -> Result<()> {
+        if rows_affected == 0 {
+            return Err(AppError::NotFound("Comment not found".to_string()));
+        }
+        self.audit_service
+            .log(
+                Some(actor_id),
+                action,
+                "comment",
+                &comment_id.to_string(),
+                None,
+                None,
+                None,
+            )
+            .await;
+        Ok(())
+    }
+}
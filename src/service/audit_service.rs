@@ -103,6 +103,23 @@ impl AuditService {
         Ok(result.rows_affected())
     }
 
+    /// How many times `action` was logged against `entity_id` within
+    /// the last `window_secs`. Backs per-target throttles (e.g. resend
+    /// welcome) that need to survive a restart.
+    pub async fn count_recent(&self, action: &str, entity_id: &str, window_secs: i64) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM audit_logs \
+             WHERE action = ? AND entity_id = ? \
+               AND created_at >= datetime('now', '-' || ? || ' seconds')"
+        )
+        .bind(action)
+        .bind(entity_id)
+        .bind(window_secs.max(0))
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
     /// Fetch the N most recent audit entries, joined with member for
     /// the actor's display name.
     pub async fn recent(&self, limit: i64) -> Result<Vec<AuditEntry>> {
@@ -0,0 +1,210 @@
+//! In-app SQLite backups. An admin can take one on demand from
+//! `/portal/admin/backup` and download it; the hourly cleanup task can
+//! also take one every `backup.interval_hours` and keep the newest
+//! `backup.keep`.
+//!
+//! Snapshots use `VACUUM INTO`, same as `deploy/backup.sh`: a single
+//! self-contained file that's consistent even while the app keeps
+//! writing, with no WAL/SHM siblings to copy. The two can coexist —
+//! this service writes to its own directory (`{data_dir}/backups/app`
+//! by default) and only ever lists or deletes files it named.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::SqlitePool;
+
+use crate::error::{AppError, Result};
+
+const FILE_PREFIX: &str = "coterie-";
+const FILE_SUFFIX: &str = ".db";
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+pub struct BackupService {
+    pool: SqlitePool,
+    dir: PathBuf,
+}
+
+/// A backup file on disk.
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    pub name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BackupService {
+    pub fn new(pool: SqlitePool, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            pool,
+            dir: dir.into(),
+        }
+    }
+
+    /// Snapshot the live database into a new timestamped file.
+    pub async fn create_backup(&self) -> Result<BackupFile> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| {
+            AppError::Internal(format!(
+                "Cannot create backup directory {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+
+        // VACUUM INTO refuses to overwrite, so two backups in the same
+        // second get a numeric suffix rather than an error.
+        let stamp = Utc::now().format(STAMP_FORMAT).to_string();
+        let mut name = format!("{}{}{}", FILE_PREFIX, stamp, FILE_SUFFIX);
+        let mut n = 1;
+        while self.dir.join(&name).exists() {
+            name = format!("{}{}-{}{}", FILE_PREFIX, stamp, n, FILE_SUFFIX);
+            n += 1;
+        }
+        let path = self.dir.join(&name);
+
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.pool)
+            .await?;
+
+        tracing::info!("Database backup written to {}", path.display());
+        self.describe(&name).await?.ok_or_else(|| {
+            AppError::Internal("Backup file vanished after VACUUM INTO".to_string())
+        })
+    }
+
+    /// Backups in the directory, newest first. A missing directory is
+    /// just "no backups yet".
+    pub async fn list_backups(&self) -> Result<Vec<BackupFile>> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(AppError::Internal(format!(
+                    "Cannot read backup directory {}: {}",
+                    self.dir.display(),
+                    e
+                )))
+            }
+        };
+
+        let mut backups = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| AppError::Internal(format!("Cannot read backup directory: {}", e)))?
+        {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(file) = self.describe(&name).await? {
+                backups.push(file);
+            }
+        }
+        // Same-second backups carry a `-N` suffix; longer name = later.
+        backups.sort_by(|a, b| {
+            (b.created_at, b.name.len(), &b.name).cmp(&(a.created_at, a.name.len(), &a.name))
+        });
+        Ok(backups)
+    }
+
+    /// Look up a backup by file name for download. Only names this
+    /// service generates resolve, so a crafted `../coterie.db` can't
+    /// reach outside the backup directory.
+    pub async fn find(&self, name: &str) -> Result<BackupFile> {
+        self.describe(name)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Backup not found".to_string()))
+    }
+
+    /// Delete all but the newest `keep` backups. Returns how many were
+    /// removed.
+    pub async fn rotate(&self, keep: usize) -> Result<usize> {
+        let backups = self.list_backups().await?;
+        let mut removed = 0;
+        for old in backups.into_iter().skip(keep.max(1)) {
+            match tokio::fs::remove_file(&old.path).await {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!("Failed to delete old backup {}: {}", old.name, e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Take a backup if the newest one is at least `interval_hours`
+    /// old (or there is none), then rotate down to `keep`. Returns the
+    /// new backup when one was taken.
+    pub async fn run_scheduled(
+        &self,
+        interval_hours: i64,
+        keep: usize,
+    ) -> Result<Option<BackupFile>> {
+        let interval = chrono::Duration::hours(interval_hours.max(1));
+        let due = match self.list_backups().await?.first() {
+            Some(newest) => Utc::now() - newest.created_at >= interval,
+            None => true,
+        };
+        if !due {
+            return Ok(None);
+        }
+
+        let backup = self.create_backup().await?;
+        let removed = self.rotate(keep).await?;
+        if removed > 0 {
+            tracing::info!("Rotated out {} old database backups", removed);
+        }
+        Ok(Some(backup))
+    }
+
+    async fn describe(&self, name: &str) -> Result<Option<BackupFile>> {
+        let Some(created_at) = parse_backup_name(name) else {
+            return Ok(None);
+        };
+        let path = self.dir.join(name);
+        match tokio::fs::metadata(&path).await {
+            Ok(meta) if meta.is_file() => Ok(Some(BackupFile {
+                name: name.to_string(),
+                size_bytes: meta.len(),
+                path,
+                created_at,
+            })),
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::Internal(format!(
+                "Cannot stat backup {}: {}",
+                name, e
+            ))),
+        }
+    }
+}
+
+/// `coterie-20250903-143000.db` or `coterie-20250903-143000-2.db` →
+/// the timestamp. Anything else → `None`.
+fn parse_backup_name(name: &str) -> Option<DateTime<Utc>> {
+    let stem = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    let stamp = stem.get(..15)?;
+    let suffix_ok = match stem[15..].strip_prefix('-') {
+        None => stem.len() == 15,
+        Some(n) => !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()),
+    };
+    if !suffix_ok {
+        return None;
+    }
+    NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_backup_name;
+
+    #[test]
+    fn only_generated_names_parse() {
+        assert!(parse_backup_name("coterie-20250903-143000.db").is_some());
+        assert!(parse_backup_name("coterie-20250903-143000-2.db").is_some());
+        assert!(parse_backup_name("../coterie.db").is_none());
+        assert!(parse_backup_name("coterie-20250903-143000/../../x.db").is_none());
+        assert!(parse_backup_name("coterie-20250903-143000-.db").is_none());
+        assert!(parse_backup_name("manual-2025-09-03.db").is_none());
+    }
+}
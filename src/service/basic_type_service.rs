@@ -13,11 +13,24 @@ use crate::{
     repository::BasicTypeRepository,
     service::configurable_types::{
         check_delete_unused_for_basic, check_unique_slug_for_basic,
-        validate_hex_color_for_request,
+        validate_hex_color_for_request, SeedReport,
     },
     util::string::capitalize_first,
 };
 
+/// (name, slug, description, color) for the types a fresh install
+/// starts with. Same rows as `001_initial_schema.sql`; the migration
+/// seeds a new database, `seed_defaults` restores them on a live one.
+const DEFAULT_EVENT_TYPES: &[(&str, &str, &str, &str)] = &[
+    ("Member Meeting", "member-meeting", "Regular member meetings", "#2196F3"),
+    ("Social", "social", "Social gatherings and events", "#4CAF50"),
+];
+
+const DEFAULT_ANNOUNCEMENT_TYPES: &[(&str, &str, &str, &str)] = &[
+    ("News", "news", "General news and updates", "#2196F3"),
+    ("Awards", "awards", "Member awards and recognition", "#FFC107"),
+];
+
 pub struct BasicTypeService {
     repo: Arc<dyn BasicTypeRepository>,
     kind: BasicTypeKind,
@@ -44,7 +57,13 @@ impl BasicTypeService {
         self.repo.find_by_slug(self.kind, slug).await
     }
 
-    pub async fn create(&self, request: CreateBasicTypeRequest) -> Result<BasicType> {
+    /// `actor` is the admin creating the type, or `None` for seeded
+    /// types.
+    pub async fn create(
+        &self,
+        actor: Option<Uuid>,
+        request: CreateBasicTypeRequest,
+    ) -> Result<BasicType> {
         validate_hex_color_for_request(request.color.as_deref())?;
 
         if let Some(ref slug) = request.slug {
@@ -57,16 +76,47 @@ impl BasicTypeService {
             .await?;
         }
 
-        self.repo.create(self.kind, request).await
+        self.repo.create(self.kind, actor, request).await
     }
 
+    /// Apply `request`, recording `actor` as the type's last editor.
     pub async fn update(
         &self,
+        actor: Option<Uuid>,
         id: Uuid,
         request: UpdateBasicTypeRequest,
     ) -> Result<BasicType> {
         validate_hex_color_for_request(request.color.as_deref())?;
-        self.repo.update(self.kind, id, request).await
+        self.repo.update(self.kind, actor, id, request).await
+    }
+
+    /// Create any default type whose slug doesn't exist yet. Existing
+    /// slugs are skipped untouched — an admin's edits to a default
+    /// survive, and running this twice is a no-op the second time.
+    pub async fn seed_defaults(&self) -> Result<SeedReport> {
+        let defaults = match self.kind {
+            BasicTypeKind::Event => DEFAULT_EVENT_TYPES,
+            BasicTypeKind::Announcement => DEFAULT_ANNOUNCEMENT_TYPES,
+        };
+
+        let mut report = SeedReport::default();
+        for (name, slug, description, color) in defaults {
+            if self.get_by_slug(slug).await?.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            let created = self
+                .create(None, CreateBasicTypeRequest {
+                    name: name.to_string(),
+                    slug: Some(slug.to_string()),
+                    description: Some(description.to_string()),
+                    color: Some(color.to_string()),
+                    icon: None,
+                })
+                .await;
+            report.record(created)?;
+        }
+        Ok(report)
     }
 
     pub async fn delete(&self, id: Uuid) -> Result<()> {
@@ -16,6 +16,7 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 
 use crate::{
+    auth::AuthService,
     email::EmailSender,
     integrations::IntegrationManager,
     payments::StripeClient,
@@ -45,6 +46,7 @@ impl BillingService {
         settings_service: Arc<SettingsService>,
         email_sender: Arc<dyn EmailSender>,
         integration_manager: Arc<IntegrationManager>,
+        auth_service: Arc<AuthService>,
         stripe_client: Option<Arc<StripeClient>>,
         base_url: String,
         db_pool: SqlitePool,
@@ -75,6 +77,7 @@ impl BillingService {
             member_repo,
             settings_service,
             integration_manager,
+            auth_service,
             db_pool,
         );
         Self { auto_renew, notifications, expiration }
@@ -4,10 +4,16 @@
 //! local-state-flips-before-Stripe-cancel rollback ordering), so
 //! they live together rather than splitting further.
 //!
-//! Doesn't own notifications — auto-renew failure paths fire
-//! `IntegrationEvent::AdminAlert` directly via the integration_manager.
+//! Doesn't own notifications — auto-renew failure paths dispatch
+//! `AdminAlert` / `AdminNotification` events directly via the
+//! integration_manager.
 //! The member-facing card-declined notice goes through `Notifications`
 //! (driven from `WebhookDispatcher`), not from here.
+//!
+//! Settling a payment row also runs through here
+//! (`complete_pending_payment` / `fail_pending_payment`), so every path
+//! that flips a payment's status dispatches `PaymentCompleted` /
+//! `PaymentFailed` the same way.
 
 use chrono::Utc;
 use std::sync::Arc;
@@ -15,7 +21,8 @@ use uuid::Uuid;
 
 use crate::{
     domain::{
-        configurable_types::BillingPeriod, BillingMode, Payer, Payment, PaymentKind,
+        configurable_types::BillingPeriod, AdminNotificationKind, BillingMode, MemberStatus, Payer,
+        Payment, PaymentKind,
         PaymentMethod, PaymentStatus, SavedCard, ScheduledPayment, ScheduledPaymentStatus,
         StripeRef,
     },
@@ -47,7 +54,7 @@ pub struct AutoRenew {
     integration_manager: Arc<IntegrationManager>,
     stripe_client: Option<Arc<StripeClient>>,
     /// Absolute URL to this Coterie instance — used to build the
-    /// member-detail link in the terminal-failure AdminAlert body.
+    /// member-detail link in the terminal-failure notification body.
     /// Threaded in from `BillingService::new` (same source as
     /// `Notifications::base_url`).
     base_url: String,
@@ -122,7 +129,7 @@ impl AutoRenew {
             id: Uuid::new_v4(),
             member_id,
             membership_type_id,
-            amount_cents: membership_type.fee_cents as i64,
+            amount_cents: membership_type.fee_cents,
             currency: "USD".to_string(),
             due_date: next_due,
             status: ScheduledPaymentStatus::Pending,
@@ -573,6 +580,7 @@ impl AutoRenew {
                 &description,
                 &idempotency_key,
                 payment_id,
+                membership_type.as_ref().map(|mt| mt.slug.as_str()),
             )
             .await
         {
@@ -591,11 +599,12 @@ impl AutoRenew {
                     description,
                     kind: PaymentKind::Membership,
                     paid_at: Some(Utc::now()),
+                    refunded_at: None,
                     created_at: Utc::now(),
                     updated_at: Utc::now(),
                 };
 
-                let payment = self.payment_repo.create(payment).await?;
+                let payment = self.record_settled_payment(payment).await?;
 
                 // Link payment and mark completed
                 self.scheduled_payment_repo
@@ -672,6 +681,34 @@ impl AutoRenew {
                         e
                     );
 
+                    // Give the final attempt a Failed row of its own so
+                    // the member's payment history (and their dues
+                    // status) shows the decline, and integrations hear
+                    // `PaymentFailed`. Earlier attempts leave nothing —
+                    // they're retried under the same scheduled payment.
+                    let failed = Payment {
+                        id: payment_id,
+                        payer: Payer::Member(sp.member_id),
+                        amount_cents: sp.amount_cents,
+                        currency: sp.currency.clone(),
+                        status: PaymentStatus::Failed,
+                        payment_method: PaymentMethod::Stripe,
+                        external_id: None,
+                        description,
+                        kind: PaymentKind::Membership,
+                        paid_at: None,
+                        refunded_at: None,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    };
+                    if let Err(record_err) = self.record_settled_payment(failed).await {
+                        tracing::error!(
+                            "Couldn't record the failed payment for scheduled payment {}: {}",
+                            id,
+                            record_err,
+                        );
+                    }
+
                     // Re-fetch member for the alert body. Best-effort: if the
                     // lookup fails (shouldn't, the member was just charged),
                     // log and skip — the scheduled_payment row is already
@@ -679,11 +716,11 @@ impl AutoRenew {
                     if let Ok(Some(member)) =
                         self.member_repo.find_by_id(sp.member_id).await
                     {
-                        let amount_display =
-                            format!("${:.2}", sp.amount_cents as f64 / 100.0);
+                        let locale = self.settings_service.org_locale().await;
+                        let amount_display = locale.currency(sp.amount_cents, &sp.currency);
                         let dues_until = member
                             .dues_paid_until
-                            .map(|d| d.format("%B %d, %Y").to_string())
+                            .map(|d| locale.long_date(&d))
                             .unwrap_or_else(|| "(unknown)".to_string());
                         let portal_url = format!(
                             "{}/portal/admin/members/{}",
@@ -715,12 +752,17 @@ impl AutoRenew {
                             dues_until,
                         );
                         self.integration_manager
-                            .handle_event(IntegrationEvent::AdminAlert { subject, body })
+                            .handle_event(IntegrationEvent::AdminNotification {
+                                kind: AdminNotificationKind::PaymentFailed,
+                                key: format!("payment_failed:scheduled:{}", id),
+                                subject,
+                                body,
+                            })
                             .await;
                     } else {
                         tracing::warn!(
                             "Couldn't re-fetch member {} after terminal scheduled-payment \
-                             failure — AdminAlert not dispatched. Logs are the only \
+                             failure — admins not notified. Logs are the only \
                              record of this failure.",
                             sp.member_id,
                         );
@@ -824,6 +866,15 @@ impl AutoRenew {
             .billing_period_enum()
             .unwrap_or(BillingPeriod::Yearly);
 
+        // The extension flips Expired back to Active (stored, or derived
+        // from lapsed dues); remember whether this payment is what
+        // revives the member so integrations that dropped them on
+        // expiry (Discord roles, door access) hear it.
+        let was_expired = match self.member_repo.find_by_id(member_id).await? {
+            Some(m) => self.settings_service.effective_status(&m).await == MemberStatus::Expired,
+            None => false,
+        };
+
         // Atomic per-payment claim + member update — see
         // PaymentRepository::extend_dues_for_payment_atomic for why
         // this isn't a SELECT/compute/UPDATE pair anymore.
@@ -836,6 +887,16 @@ impl AutoRenew {
                 "Extended dues for member {} (payment: {}, billing period: {:?})",
                 member_id, payment_id, billing_period,
             );
+            if was_expired {
+                if let Some(member) = self.member_repo.find_by_id(member_id).await? {
+                    let member = self.settings_service.with_effective_status(member).await;
+                    if member.status == MemberStatus::Active {
+                        self.integration_manager
+                            .handle_event(IntegrationEvent::MemberActivated(member))
+                            .await;
+                    }
+                }
+            }
         } else {
             tracing::debug!(
                 "Dues already extended for payment {}; skipping",
@@ -846,6 +907,68 @@ impl AutoRenew {
         Ok(())
     }
 
+    /// Flip a Pending payment to Completed and, if this caller won the
+    /// flip, dispatch `PaymentCompleted`. The saved-card handlers and
+    /// the Stripe webhooks race for the same row; because they all
+    /// settle it through here, integrations hear about the payment
+    /// exactly once, from whichever side got there first. Returns
+    /// `true` when this caller owns the post-payment work.
+    pub async fn complete_pending_payment(
+        &self,
+        payment_id: Uuid,
+        stripe_payment_id: &str,
+    ) -> Result<bool> {
+        let won_flip = self
+            .payment_repo
+            .complete_pending_payment(payment_id, stripe_payment_id)
+            .await?;
+        if won_flip {
+            self.announce_payment(payment_id, IntegrationEvent::PaymentCompleted)
+                .await;
+        }
+        Ok(won_flip)
+    }
+
+    /// Failure counterpart of `complete_pending_payment`: flip a
+    /// Pending row to Failed and dispatch `PaymentFailed` on a win.
+    pub async fn fail_pending_payment(&self, payment_id: Uuid) -> Result<bool> {
+        let won_flip = self.payment_repo.fail_pending_payment(payment_id).await?;
+        if won_flip {
+            self.announce_payment(payment_id, IntegrationEvent::PaymentFailed)
+                .await;
+        }
+        Ok(won_flip)
+    }
+
+    /// Persist a payment that was already settled when its row was
+    /// written — the scheduled-payment runner only inserts once Stripe
+    /// has answered — and dispatch the matching event.
+    async fn record_settled_payment(&self, payment: Payment) -> Result<Payment> {
+        let payment = self.payment_repo.create(payment).await?;
+        let event = match payment.status {
+            PaymentStatus::Completed => IntegrationEvent::PaymentCompleted(payment.clone()),
+            PaymentStatus::Failed => IntegrationEvent::PaymentFailed(payment.clone()),
+            _ => return Ok(payment),
+        };
+        self.integration_manager.handle_event(event).await;
+        Ok(payment)
+    }
+
+    /// Re-read a payment this service just flipped and hand it to
+    /// integrations. Lookup failures are logged, not returned: the flip
+    /// already happened and the caller still owns the post-work.
+    async fn announce_payment(&self, payment_id: Uuid, event: fn(Payment) -> IntegrationEvent) {
+        match self.payment_repo.find_by_id(payment_id).await {
+            Ok(Some(payment)) => self.integration_manager.handle_event(event(payment)).await,
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Couldn't reload payment {} to notify integrations: {}",
+                payment_id,
+                e,
+            ),
+        }
+    }
+
     async fn get_max_retries(&self) -> i32 {
         self.settings_service
             .get_number("billing.max_retry_attempts")
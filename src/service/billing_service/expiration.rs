@@ -1,5 +1,9 @@
 //! Daily expiration sweep: members past dues + grace period get
-//! status flipped to `Expired` and their live sessions invalidated.
+//! status flipped to `Expired` and their live sessions invalidated
+//! through `AuthService`, so the purge reaches whichever session
+//! store is configured (SQLite or Redis).
+//! Under `ExpiryMode::Derived` the status is left alone (it's derived
+//! on read instead) and the sweep only tells integrations.
 //!
 //! Standalone — only the daily job in `main.rs` (via `BillingService`
 //! facade → `Expiration::check_expired_members`) drives it. Doesn't
@@ -11,6 +15,8 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
+    auth::AuthService,
+    domain::ExpiryMode,
     error::{AppError, Result},
     integrations::{IntegrationEvent, IntegrationManager},
     repository::MemberRepository,
@@ -21,9 +27,12 @@ pub struct Expiration {
     member_repo: Arc<dyn MemberRepository>,
     settings_service: Arc<SettingsService>,
     integration_manager: Arc<IntegrationManager>,
-    /// Held for the multi-table sweep (UPDATE members RETURNING +
-    /// DELETE FROM sessions). F1 left this site as raw SQL because
-    /// no repo method covers the cross-table dependency.
+    /// Ends the expired members' sessions. Not a raw `DELETE FROM
+    /// sessions`: under `auth.session_store = "redis"` that table is
+    /// empty and the sessions would outlive the expiry.
+    auth_service: Arc<AuthService>,
+    /// Held for the bulk `UPDATE members ... RETURNING id`. F1 left
+    /// this site as raw SQL because no repo method covers it.
     db_pool: SqlitePool,
 }
 
@@ -32,12 +41,14 @@ impl Expiration {
         member_repo: Arc<dyn MemberRepository>,
         settings_service: Arc<SettingsService>,
         integration_manager: Arc<IntegrationManager>,
+        auth_service: Arc<AuthService>,
         db_pool: SqlitePool,
     ) -> Self {
         Self {
             member_repo,
             settings_service,
             integration_manager,
+            auth_service,
             db_pool,
         }
     }
@@ -46,7 +57,10 @@ impl Expiration {
     /// any live sessions for the affected members so they stop having
     /// portal access on the next request rather than the one after.
     pub async fn check_expired_members(&self) -> Result<u32> {
-        let grace_days = self.get_grace_period_days().await;
+        let grace_days = self.settings_service.grace_period_days().await;
+        if self.settings_service.expiry_mode().await == ExpiryMode::Derived {
+            return self.dispatch_derived_expirations(grace_days).await;
+        }
 
         // UPDATE...RETURNING gives us the affected IDs in one round-trip
         // so we can invalidate their sessions below.
@@ -72,22 +86,17 @@ impl Expiration {
         // bounce them to /portal/restore on their next request anyway,
         // but killing the session makes the expiration immediate from
         // the browser's perspective.
-        //
-        // Placeholder count is derived from our own DB row count, not
-        // user input — format!() is safe here.
-        if !expired_ids.is_empty() {
-            let placeholders = vec!["?"; expired_ids.len()].join(",");
-            let sql = format!("DELETE FROM sessions WHERE member_id IN ({})", placeholders);
-            let mut q = sqlx::query(&sql);
-            for (id,) in &expired_ids {
-                q = q.bind(id);
-            }
-            if let Err(e) = q.execute(&self.db_pool).await {
+        for (id_str,) in &expired_ids {
+            let Ok(member_id) = Uuid::parse_str(id_str) else {
+                continue;
+            };
+            if let Err(e) = self.auth_service.invalidate_all_sessions(member_id).await {
                 tracing::warn!(
-                    "Marked {} members Expired but session cleanup failed: {}. \
-                     Middleware still rejects Expired status, so members are \
+                    "Marked member {} Expired but session cleanup failed: {}. \
+                     Middleware still rejects Expired status, so they are \
                      bounced to /portal/restore on next request.",
-                    expired_count, e
+                    member_id,
+                    e
                 );
             }
         }
@@ -116,10 +125,169 @@ impl Expiration {
         Ok(expired_count)
     }
 
-    async fn get_grace_period_days(&self) -> i64 {
-        self.settings_service
-            .get_number("membership.grace_period_days")
+    /// The derived-mode sweep: `status` stays `Active`, access is
+    /// already gated by the effective status, so all that's left is
+    /// firing `MemberExpired` (with the effective status) so
+    /// integrations swap roles. `expiry_dispatched_for` remembers the
+    /// paid-through date each member was last dispatched for, so a
+    /// lapse fires once rather than every day, and fires again after
+    /// a renewal lapses.
+    async fn dispatch_derived_expirations(&self, grace_days: i64) -> Result<u32> {
+        let lapsed_ids: Vec<(String,)> = sqlx::query_as(
+            r#"
+            UPDATE members
+            SET expiry_dispatched_for = dues_paid_until
+            WHERE status = 'Active'
+              AND dues_paid_until IS NOT NULL
+              AND date(dues_paid_until, '+' || ? || ' days') < date('now')
+              AND bypass_dues = 0
+              AND (expiry_dispatched_for IS NULL
+                   OR expiry_dispatched_for != dues_paid_until)
+            RETURNING id
+            "#,
+        )
+        .bind(grace_days)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(|e| AppError::Internal(format!("Database error: {}", e)))?;
+
+        for (id_str,) in &lapsed_ids {
+            if let Ok(uuid) = Uuid::parse_str(id_str) {
+                if let Ok(Some(member)) = self.member_repo.find_by_id(uuid).await {
+                    let member = self.settings_service.with_effective_status(member).await;
+                    self.integration_manager
+                        .handle_event(IntegrationEvent::MemberExpired(member))
+                        .await;
+                }
+            }
+        }
+
+        if !lapsed_ids.is_empty() {
+            tracing::info!(
+                "{} members lapsed past grace period ({} days); status left Active (derived expiry mode)",
+                lapsed_ids.len(),
+                grace_days
+            );
+        }
+
+        Ok(lapsed_ids.len() as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{
+            session::{Session, SessionStore},
+            SecretCrypto,
+        },
+        domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+        repository::SqliteMemberRepository,
+    };
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use tokio::sync::Mutex;
+
+    /// Stands in for the Redis store: holds nothing in SQLite, only
+    /// records whose sessions were ended.
+    #[derive(Default)]
+    struct RecordingStore {
+        ended: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl SessionStore for RecordingStore {
+        async fn create(
+            &self,
+            member_id: Uuid,
+            _: &str,
+            expires_at: DateTime<Utc>,
+        ) -> Result<Session> {
+            let now = Utc::now();
+            Ok(Session {
+                id: Uuid::new_v4().to_string(),
+                member_id,
+                token_hash: String::new(),
+                expires_at,
+                created_at: now,
+                last_used_at: now,
+            })
+        }
+        async fn find_by_token(&self, _: &str) -> Result<Option<Session>> {
+            Ok(None)
+        }
+        async fn delete_by_token(&self, _: &str) -> Result<()> {
+            Ok(())
+        }
+        async fn delete_by_member(&self, member_id: Uuid) -> Result<()> {
+            self.ended.lock().await.push(member_id);
+            Ok(())
+        }
+        async fn list_for_member(&self, _: Uuid) -> Result<Vec<Session>> {
+            Ok(Vec::new())
+        }
+        async fn delete_by_id(&self, _: Uuid, _: &str) -> Result<bool> {
+            Ok(false)
+        }
+        async fn cleanup_expired(&self) -> Result<u64> {
+            Ok(0)
+        }
+    }
+
+    async fn fresh_pool() -> SqlitePool {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect(":memory:");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
             .await
-            .unwrap_or(3)
+            .expect("migrate");
+        pool
+    }
+
+    #[tokio::test]
+    async fn sweep_ends_sessions_through_the_configured_store() {
+        let pool = fresh_pool().await;
+        let repo = Arc::new(SqliteMemberRepository::new(pool.clone()));
+        let lapsed = repo
+            .create(CreateMemberRequest {
+                email: "lapsed@example.com".to_string(),
+                username: "lapsed".to_string(),
+                full_name: "Lapsed Member".to_string(),
+                password: "secure_password123".to_string(),
+                dues_paid_until: Some(Utc::now() - Duration::days(60)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        repo.update(
+            lapsed.id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let store = Arc::new(RecordingStore::default());
+        let expiration = Expiration::new(
+            repo,
+            Arc::new(SettingsService::new(
+                pool.clone(),
+                Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+            )),
+            Arc::new(IntegrationManager::new()),
+            Arc::new(
+                AuthService::new(pool.clone(), String::new()).with_session_store(store.clone()),
+            ),
+            pool,
+        );
+
+        assert_eq!(expiration.check_expired_members().await.unwrap(), 1);
+        assert_eq!(*store.ended.lock().await, vec![lapsed.id]);
     }
 }
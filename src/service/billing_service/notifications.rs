@@ -5,15 +5,16 @@
 //! - The daily reminder runner (`send_dues_reminders`)
 //!
 //! Split out of the original `BillingService` so the email-template
-//! and AdminAlert plumbing has its own home, separate from the auto-
+//! and admin-alert plumbing has its own home, separate from the auto-
 //! renew lifecycle and expiration sweeps that share none of its deps.
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use sqlx::SqlitePool;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
+    domain::{AdminNotificationKind, Locale},
     email::EmailSender,
     error::{AppError, Result},
     integrations::{IntegrationEvent, IntegrationManager},
@@ -21,6 +22,18 @@ use crate::{
     service::{membership_type_service::MembershipTypeService, settings_service::SettingsService},
 };
 
+/// (id, email, full_name, dues_paid_until, billing_mode,
+/// membership_type_id, locale) — see the dues-reminder query.
+type ReminderCandidateRow = (
+    String,
+    String,
+    String,
+    chrono::NaiveDateTime,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
 pub struct Notifications {
     member_repo: Arc<dyn MemberRepository>,
     saved_card_repo: Arc<dyn SavedCardRepository>,
@@ -89,8 +102,9 @@ impl Notifications {
             self.base_url.trim_end_matches('/'),
         );
 
+        let locale = self.settings_service.locale_for(&member).await;
         let dues_until = member.dues_paid_until
-            .map(|d| d.format("%B %d, %Y").to_string())
+            .map(|d| locale.long_date(&d))
             .unwrap_or_else(|| "(unknown)".to_string());
 
         let html = SubscriptionCancelledHtml {
@@ -135,8 +149,8 @@ impl Notifications {
     }
 
     /// A Stripe-managed subscription charge failed. Emails the member
-    /// directly so they can update their card, and dispatches an
-    /// AdminAlert so operators get a heads-up via Discord/email.
+    /// directly so they can update their card, and sends the admins a
+    /// `PaymentFailed` notification routed by their preferences.
     ///
     /// `is_final` should be true when Stripe has exhausted retries —
     /// we soften the email copy in that case ("this was the last
@@ -162,8 +176,9 @@ impl Notifications {
         let base = self.base_url.trim_end_matches('/');
         let portal_url = format!("{}/portal/payments/methods", base);
 
+        let locale = self.settings_service.locale_for(&member).await;
         let dues_until = member.dues_paid_until
-            .map(|d| d.format("%B %d, %Y").to_string())
+            .map(|d| locale.long_date(&d))
             .unwrap_or_else(|| "(unknown)".to_string());
 
         let html = CardDeclinedHtml {
@@ -200,8 +215,8 @@ impl Notifications {
             );
         }
 
-        // AdminAlert: the body is plain-text since it's piped into
-        // both Discord (markdown-ish) and email.
+        // Plain-text body: admins may take it by Discord DM
+        // (markdown-ish) or email.
         let alert_subject = if is_final {
             format!("Stripe subscription charge failed (final) — {}", member.full_name)
         } else {
@@ -221,8 +236,16 @@ impl Notifications {
                 "Stripe will retry automatically; member can update card to fix"
             },
         );
+        // Stripe retries a failing invoice over days; one notification
+        // per member per day is enough.
         self.integration_manager
-            .handle_event(IntegrationEvent::AdminAlert {
+            .handle_event(IntegrationEvent::AdminNotification {
+                kind: AdminNotificationKind::PaymentFailed,
+                key: format!(
+                    "payment_failed:subscription:{}:{}",
+                    member.id,
+                    Utc::now().date_naive()
+                ),
                 subject: alert_subject,
                 body: alert_body,
             })
@@ -231,71 +254,94 @@ impl Notifications {
         Ok(())
     }
 
-    /// Find members whose dues will lapse within the reminder window
-    /// and email each one — or skip them silently if they're on
-    /// auto-renew with a valid card for a short-period plan.
+    /// Send dues reminders now. See [`Self::send_dues_reminders_at`].
+    pub async fn send_dues_reminders(&self) -> Result<u32> {
+        self.send_dues_reminders_at(Utc::now()).await
+    }
+
+    /// Walk members whose dues are running out (or just ran out)
+    /// through `membership.reminder_sequence` as of `now`: each member
+    /// gets the email for the step they're in, once per dues period.
+    /// A member who only comes into range late gets the current step
+    /// and never the ones before it.
     ///
-    /// Four cases:
-    ///  1. Manual billing → "pay your dues" reminder.
+    /// Before expiry there are four cases:
+    ///  1. Manual billing → the step's reminder (friendly or firm).
     ///  2. Auto-renew, card will be valid at charge time, period
     ///     <= monthly → skip (the charge will just happen).
     ///  3. Auto-renew, valid card, period >= yearly → "heads up,
-    ///     we're going to auto-charge you $X" notice.
+    ///     we're going to auto-charge you $X" notice at the first
+    ///     step, nothing at later ones.
     ///  4. Auto-renew, card expired/missing by charge time → same
     ///     reminder as case 1 but with a "your card is invalid"
     ///     callout so the member knows auto-charge won't save them.
     ///
-    /// Idempotent per cycle via `dues_reminder_sent_at`. Case 2 does
-    /// NOT set the flag — we want those members to become eligible
-    /// again if their card or billing mode changes mid-window.
-    pub async fn send_dues_reminders(&self) -> Result<u32> {
+    /// Lapsed steps go to everyone still unpaid, whatever their
+    /// billing mode: if the charge had gone through, dues would have
+    /// moved.
+    ///
+    /// Idempotent per step via `dues_reminders_sent`, keyed by the
+    /// dues date, so a payment ends the escalation: the new date
+    /// starts a fresh sequence. Skipped members are NOT recorded — we
+    /// want them to become eligible again if their card or billing
+    /// mode changes mid-window.
+    pub async fn send_dues_reminders_at(&self, now: DateTime<Utc>) -> Result<u32> {
         use crate::{
-            domain::{configurable_types::BillingPeriod, BillingMode},
-            email::templates::{ReminderHtml, ReminderText, RenewalNoticeHtml, RenewalNoticeText},
+            domain::{
+                configurable_types::BillingPeriod, current_dues_reminder_step, BillingMode,
+                DuesReminderTone, LAPSED_STEP_WINDOW_DAYS,
+            },
+            email::templates::{
+                DuesLapsedHtml, DuesLapsedText, DuesReminderFirmHtml, DuesReminderFirmText,
+                ReminderHtml, ReminderText, RenewalNoticeHtml, RenewalNoticeText,
+            },
         };
 
-        let reminder_days = self.settings_service
-            .get_number("membership.reminder_days_before")
-            .await
-            .unwrap_or(7)
-            .clamp(1, 90);
+        let steps = self.settings_service.dues_reminder_sequence().await;
+        // Sorted earliest first and never empty.
+        let first_step = steps[0];
+        let last_step = steps[steps.len() - 1];
 
         let org_name = self.settings_service
             .get_value("org.name").await
             .ok().filter(|s| !s.is_empty())
             .unwrap_or_else(|| "Coterie".to_string());
+        let org_locale = self.settings_service.org_locale().await;
 
         let base = self.base_url.trim_end_matches('/');
         let pay_url = format!("{}/portal/payments/new", base);
         let portal_url = format!("{}/portal/payments/methods", base);
 
-        // Candidate members: Active, dues in the window, not yet reminded.
-        // We fetch billing_mode and membership_type_id here so we can
-        // branch in code rather than doing N+1 joins.
-        let rows: Vec<(String, String, String, chrono::NaiveDateTime, String, Option<String>)> =
+        // Candidate members: dues running out anywhere in the sequence.
+        // Expired members are included for the lapsed steps. We fetch
+        // billing_mode and membership_type_id here so we can branch in
+        // code rather than doing N+1 joins.
+        let window_start =
+            now - Duration::days((-last_step.days_before).max(0) + LAPSED_STEP_WINDOW_DAYS);
+        let window_end = now + Duration::days(first_step.days_before);
+        let rows: Vec<ReminderCandidateRow> =
             sqlx::query_as(
                 r#"
-                SELECT id, email, full_name, dues_paid_until, billing_mode, membership_type_id
+                SELECT id, email, full_name, dues_paid_until, billing_mode, membership_type_id, locale
                 FROM members
-                WHERE status = 'Active'
+                WHERE status IN ('Active', 'Expired')
                   AND bypass_dues = 0
                   AND dues_paid_until IS NOT NULL
-                  AND dues_paid_until > CURRENT_TIMESTAMP
-                  AND date(dues_paid_until) <= date(CURRENT_TIMESTAMP, '+' || ? || ' days')
-                  AND dues_reminder_sent_at IS NULL
+                  AND datetime(dues_paid_until) > datetime(?)
+                  AND datetime(dues_paid_until) <= datetime(?)
                 "#
             )
-            .bind(reminder_days)
+            .bind(window_start.naive_utc())
+            .bind(window_end.naive_utc())
             .fetch_all(&self.db_pool)
             .await
             .map_err(|e| AppError::Internal(format!("DB error in reminder query: {}", e)))?;
 
-        let total = rows.len();
+        let mut total = 0u32;
         let mut sent = 0u32;
         let mut skipped = 0u32;
-        let now = Utc::now();
 
-        for (id_str, email_addr, full_name, due_naive, billing_mode_str, mt_id_opt) in rows {
+        for (id_str, email_addr, full_name, due_naive, billing_mode_str, mt_id_opt, locale_pref) in rows {
             let member_id = match Uuid::parse_str(&id_str) {
                 Ok(id) => id,
                 Err(e) => {
@@ -304,6 +350,19 @@ impl Notifications {
                 }
             };
             let due = chrono::DateTime::<Utc>::from_naive_utc_and_offset(due_naive, Utc);
+            let Some(step) = current_dues_reminder_step(&steps, due, now) else {
+                continue;
+            };
+            match self.dues_reminder_sent(member_id, due_naive, step.days_before).await {
+                Ok(false) => {}
+                Ok(true) => continue,
+                Err(e) => {
+                    tracing::error!("Dues reminder lookup failed for {}: {}", id_str, e);
+                    continue;
+                }
+            }
+            total += 1;
+
             let billing_mode = BillingMode::from_str(&billing_mode_str)
                 .unwrap_or(BillingMode::Manual);
 
@@ -338,7 +397,7 @@ impl Notifications {
                 BillingMode::CoterieManaged | BillingMode::StripeSubscription
             );
 
-            let due_formatted = due.format("%B %d, %Y").to_string();
+            let due_formatted = Locale::resolve(locale_pref.as_deref(), org_locale).long_date(&due);
             let days_remaining = (due - now).num_days().max(0);
 
             // Lifetime members shouldn't be in the reminder window to
@@ -350,6 +409,25 @@ impl Notifications {
                 continue;
             }
 
+            if step.tone == DuesReminderTone::Lapsed {
+                let html = DuesLapsedHtml {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, pay_url: &pay_url,
+                };
+                let text = DuesLapsedText {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, pay_url: &pay_url,
+                };
+                let subject = format!("Your {} membership has lapsed", org_name);
+                if self.try_send_and_mark(
+                    member_id, due_naive, step.days_before,
+                    &email_addr, &subject, &html, &text,
+                ).await {
+                    sent += 1;
+                }
+                continue;
+            }
+
             // Case 2: auto-renew + card will be valid + short period → skip.
             if is_auto_renew && card_good_at_charge
                 && matches!(billing_period, BillingPeriod::Monthly)
@@ -358,14 +436,19 @@ impl Notifications {
                 continue;
             }
 
-            // Case 3: auto-renew + card valid + long period → renewal notice.
+            // Case 3: auto-renew + card valid + long period → one
+            // renewal notice, at the first step.
             if is_auto_renew && card_good_at_charge
                 && matches!(billing_period, BillingPeriod::Yearly)
             {
+                if step != first_step {
+                    skipped += 1;
+                    continue;
+                }
                 // Amount display for the renewal notice.
                 let amount = match mt_id_opt.as_ref().and_then(|s| Uuid::parse_str(s).ok()) {
                     Some(mt_id) => match self.membership_type_service.get(mt_id).await {
-                        Ok(Some(mt)) => format!("${:.2}", mt.fee_cents as f64 / 100.0),
+                        Ok(Some(mt)) => format!("${}", mt.fee()),
                         _ => "(your membership fee)".to_string(),
                     },
                     None => "(your membership fee)".to_string(),
@@ -389,7 +472,8 @@ impl Notifications {
                 };
                 let subject = format!("Your {} membership will renew {}", org_name, due_formatted);
                 if self.try_send_and_mark(
-                    &id_str, &email_addr, &subject, &html, &text,
+                    member_id, due_naive, step.days_before,
+                    &email_addr, &subject, &html, &text,
                 ).await {
                     sent += 1;
                 }
@@ -400,38 +484,81 @@ impl Notifications {
             // Case 4 = auto-renew but card won't be valid at charge time.
             let card_invalid = is_auto_renew && !card_good_at_charge;
 
-            let html = ReminderHtml {
-                full_name: &full_name, org_name: &org_name,
-                due_date: &due_formatted, days_remaining,
-                pay_url: &pay_url, card_invalid,
-            };
-            let text = ReminderText {
-                full_name: &full_name, org_name: &org_name,
-                due_date: &due_formatted, days_remaining,
-                pay_url: &pay_url, card_invalid,
+            let delivered = if step.tone == DuesReminderTone::Firm {
+                let html = DuesReminderFirmHtml {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, days_remaining,
+                    pay_url: &pay_url, card_invalid,
+                };
+                let text = DuesReminderFirmText {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, days_remaining,
+                    pay_url: &pay_url, card_invalid,
+                };
+                let subject = format!("Reminder: your {} dues are due {}", org_name, due_formatted);
+                self.try_send_and_mark(
+                    member_id, due_naive, step.days_before,
+                    &email_addr, &subject, &html, &text,
+                ).await
+            } else {
+                let html = ReminderHtml {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, days_remaining,
+                    pay_url: &pay_url, card_invalid,
+                };
+                let text = ReminderText {
+                    full_name: &full_name, org_name: &org_name,
+                    due_date: &due_formatted, days_remaining,
+                    pay_url: &pay_url, card_invalid,
+                };
+                let subject = format!("Your {} dues are due soon", org_name);
+                self.try_send_and_mark(
+                    member_id, due_naive, step.days_before,
+                    &email_addr, &subject, &html, &text,
+                ).await
             };
-            let subject = format!("Your {} dues are due soon", org_name);
-            if self.try_send_and_mark(
-                &id_str, &email_addr, &subject, &html, &text,
-            ).await {
+            if delivered {
                 sent += 1;
             }
         }
 
         if total > 0 {
             tracing::info!(
-                "Dues reminders: {} sent, {} skipped (auto-renew OK) out of {} candidates (window: {} days)",
-                sent, skipped, total, reminder_days
+                "Dues reminders: {} sent, {} skipped (auto-renew OK) out of {} candidates (steps: {:?})",
+                sent, skipped, total, steps
             );
         }
         Ok(sent)
     }
 
-    /// Helper: render + send + mark the reminder-sent flag on success.
-    /// Returns true if the email went out.
+    /// Whether this step's reminder already went out for the dues
+    /// period ending at `dues_paid_until`.
+    async fn dues_reminder_sent(
+        &self,
+        member_id: Uuid,
+        dues_paid_until: chrono::NaiveDateTime,
+        step_days: i64,
+    ) -> Result<bool> {
+        let sent: Option<i64> = sqlx::query_scalar(
+            "SELECT 1 FROM dues_reminders_sent \
+             WHERE member_id = ? AND datetime(dues_paid_until) = datetime(?) AND step_days = ?",
+        )
+        .bind(member_id.to_string())
+        .bind(dues_paid_until)
+        .bind(step_days)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        Ok(sent.is_some())
+    }
+
+    /// Helper: render + send + record the step in `dues_reminders_sent`
+    /// on success. Returns true if the email went out.
+    #[allow(clippy::too_many_arguments)]
     async fn try_send_and_mark<H, T>(
         &self,
-        id_str: &str,
+        member_id: Uuid,
+        dues_paid_until: chrono::NaiveDateTime,
+        step_days: i64,
         email_addr: &str,
         subject: &str,
         html: &H,
@@ -450,24 +577,36 @@ impl Notifications {
         ) {
             Ok(m) => m,
             Err(e) => {
-                tracing::error!("Reminder template render failed for {}: {}", id_str, e);
+                tracing::error!("Reminder template render failed for {}: {}", member_id, e);
                 return false;
             }
         };
         match self.email_sender.send(&message).await {
             Ok(()) => {
-                // Mark sent. If the UPDATE fails the email already
-                // went out; the next cycle would resend (annoying but
+                // Record the step. If the INSERT fails the email already
+                // went out; the next tick would resend (annoying but
                 // not catastrophic) — log loudly so an operator can
-                // intervene before the cycle re-runs.
-                if let Ok(member_uuid) = Uuid::parse_str(id_str) {
-                    if let Err(e) = self.member_repo.set_dues_reminder_sent(member_uuid).await {
-                        tracing::error!(
-                            "Sent reminder to {} but failed to mark reminder_sent_at — \
-                             next cycle may re-send: {}",
-                            email_addr, e
-                        );
-                    }
+                // intervene before it re-runs.
+                let recorded = sqlx::query(
+                    "INSERT OR IGNORE INTO dues_reminders_sent \
+                         (member_id, dues_paid_until, step_days) \
+                     VALUES (?, ?, ?)",
+                )
+                .bind(member_id.to_string())
+                .bind(dues_paid_until)
+                .bind(step_days)
+                .execute(&self.db_pool)
+                .await;
+                if let Err(e) = recorded {
+                    tracing::error!(
+                        "Sent reminder to {} but failed to record it — \
+                         next tick may re-send: {}",
+                        email_addr, e
+                    );
+                }
+                // Kept as "last reminded at" on the member row.
+                if let Err(e) = self.member_repo.set_dues_reminder_sent(member_id).await {
+                    tracing::warn!("Couldn't stamp dues_reminder_sent_at for {}: {}", member_id, e);
                 }
                 true
             }
@@ -478,112 +617,124 @@ impl Notifications {
         }
     }
 
-    /// Send one reminder email per eligible RSVP. An RSVP is eligible
-    /// when its event starts in `(now, now + lead_hours]`, the
-    /// attendance row is `Registered`, and `reminder_sent_at` is NULL.
+    /// Send event reminder emails, one per RSVP per configured lead
+    /// time. `events.reminder_lead_hours` is a list (e.g. `168,24`),
+    /// read on every invocation so operators can adjust without
+    /// restarting the runner.
     ///
-    /// The lead-hours window is read from the
-    /// `events.reminder_lead_hours` setting on every invocation
-    /// (default 24 on missing/unparseable), so operators can adjust
-    /// without restarting the runner.
+    /// Each lead time owns the slice of time between it and the next
+    /// shorter one: with `168,24`, an event 3 days out is in the
+    /// 168-hour window and one 6 hours out is in the 24-hour window.
+    /// An RSVP is eligible in a window when its event starts inside
+    /// that slice, the attendance row is `Registered`, the member
+    /// hasn't turned event reminders off, and that window's reminder
+    /// hasn't gone out. So a member gets a week-before and a day-before
+    /// nudge, while someone who RSVPs the day before gets only the
+    /// day-before one.
     ///
-    /// Concurrency-safe: each row is claimed via a conditional
-    /// UPDATE before the email is sent, so two ticks cannot both
-    /// email the same RSVP. A claimed-but-failed send stays stamped
-    /// — operators clear `reminder_sent_at` manually to retry. See
+    /// Concurrency-safe: each (event, member, window) is claimed in
+    /// `event_reminders_sent` before the email is sent, so two ticks
+    /// cannot both email the same reminder. A claimed-but-failed send
+    /// stays claimed — operators delete the row manually to retry. See
     /// `event-reminders` spec D3 for the trade-off rationale.
     pub async fn send_event_reminders(&self) -> Result<u32> {
         use crate::email::{self, templates::{EventReminderHtml, EventReminderText}};
 
-        let lead_hours = self.settings_service
-            .get_number("events.reminder_lead_hours")
-            .await
-            .ok()
-            .filter(|n| *n > 0)
-            .unwrap_or(24);
+        let lead_hours = self.settings_service.event_reminder_lead_hours().await;
 
         let org_name = self.settings_service
             .get_value("org.name").await
             .ok().filter(|s| !s.is_empty())
             .unwrap_or_else(|| "Coterie".to_string());
+        let org_locale = self.settings_service.org_locale().await;
 
         let now = Utc::now();
-        let until = now + Duration::hours(lead_hours);
-
-        let candidates = self.event_repo.list_pending_reminders(now, until).await?;
-        let total = candidates.len();
-        let mut sent = 0u32;
-
         let base = self.base_url.trim_end_matches('/');
+        let mut total = 0usize;
+        let mut sent = 0u32;
 
-        for row in candidates {
-            let claimed = match self.event_repo
-                .mark_reminder_sent(row.event_id, row.member_id).await
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    tracing::error!(
-                        "Event reminder claim failed for event {} member {}: {}",
-                        row.event_id, row.member_id, e
-                    );
+        // Shortest first; each window starts where the previous ends.
+        let mut window_start = now;
+        for &lead in &lead_hours {
+            let window_end = now + Duration::hours(lead);
+            let candidates = self.event_repo
+                .list_pending_reminders(window_start, window_end, lead)
+                .await?;
+            window_start = window_end;
+            total += candidates.len();
+
+            for row in candidates {
+                let claimed = match self.event_repo
+                    .mark_reminder_sent(row.event_id, row.member_id, lead).await
+                {
+                    Ok(c) => c,
+                    Err(e) => {
+                        tracing::error!(
+                            "Event reminder claim failed for event {} member {}: {}",
+                            row.event_id, row.member_id, e
+                        );
+                        continue;
+                    }
+                };
+                if !claimed {
+                    // Lost the race — another tick already claimed this row.
                     continue;
                 }
-            };
-            if !claimed {
-                // Lost the race — another tick already claimed this row.
-                continue;
-            }
 
-            let start_formatted = row.event_start.format("%B %d, %Y at %H:%M UTC").to_string();
-            let event_url = format!("{}/portal/events", base);
-            let location_ref = row.event_location.as_deref();
-
-            let html = EventReminderHtml {
-                full_name: &row.member_full_name,
-                org_name: &org_name,
-                event_title: &row.event_title,
-                event_start: &start_formatted,
-                event_location: location_ref,
-                event_url: &event_url,
-            };
-            let text = EventReminderText {
-                full_name: &row.member_full_name,
-                org_name: &org_name,
-                event_title: &row.event_title,
-                event_start: &start_formatted,
-                event_location: location_ref,
-                event_url: &event_url,
-            };
-            let subject = format!("Reminder: {} is coming up", row.event_title);
+                let start_formatted = format!(
+                    "{} UTC",
+                    Locale::resolve(row.member_locale.as_deref(), org_locale).long_date_time(&row.event_start),
+                );
+                let event_url = format!("{}/portal/events", base);
+                let location_ref = row.event_location.as_deref();
+
+                let html = EventReminderHtml {
+                    full_name: &row.member_full_name,
+                    org_name: &org_name,
+                    event_title: &row.event_title,
+                    event_start: &start_formatted,
+                    event_location: location_ref,
+                    event_url: &event_url,
+                };
+                let text = EventReminderText {
+                    full_name: &row.member_full_name,
+                    org_name: &org_name,
+                    event_title: &row.event_title,
+                    event_start: &start_formatted,
+                    event_location: location_ref,
+                    event_url: &event_url,
+                };
+                let subject = format!("Reminder: {} is coming up", row.event_title);
 
-            let message = match email::message_from_templates(
-                row.member_email.clone(), subject, &html, &text,
-            ) {
-                Ok(m) => m,
-                Err(e) => {
-                    tracing::error!(
-                        "Event reminder render failed for event {} member {}: {}",
-                        row.event_id, row.member_id, e
-                    );
-                    continue;
-                }
-            };
+                let message = match email::message_from_templates(
+                    row.member_email.clone(), subject, &html, &text,
+                ) {
+                    Ok(m) => m,
+                    Err(e) => {
+                        tracing::error!(
+                            "Event reminder render failed for event {} member {}: {}",
+                            row.event_id, row.member_id, e
+                        );
+                        continue;
+                    }
+                };
 
-            match self.email_sender.send(&message).await {
-                Ok(()) => sent += 1,
-                Err(e) => {
-                    tracing::warn!(
-                        "Event reminder send failed for {} (event {} member {}): {} \
-                         — row stays stamped per claim-then-send policy",
-                        row.member_email, row.event_id, row.member_id, e,
-                    );
+                match self.email_sender.send(&message).await {
+                    Ok(()) => sent += 1,
+                    Err(e) => {
+                        tracing::warn!(
+                            "Event reminder send failed for {} (event {} member {}): {} \
+                             — reminder stays claimed per claim-then-send policy",
+                            row.member_email, row.event_id, row.member_id, e,
+                        );
+                    }
                 }
             }
         }
 
         if total > 0 {
             tracing::info!(
-                "Event reminders: {} sent out of {} candidates (lead window: {} hours)",
+                "Event reminders: {} sent out of {} candidates (lead times: {:?} hours)",
                 sent, total, lead_hours,
             );
         }
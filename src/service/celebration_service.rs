@@ -0,0 +1,273 @@
+//! Birthday and membership-anniversary shout-outs.
+//!
+//! Members can add a birthday (month and day) on their profile and can
+//! opt out of shout-outs entirely; anniversaries come from
+//! `members.joined_at`. `send_due` (run from the hourly billing runner)
+//! finds today's Active/Honorary members and, per the
+//! `celebrations.*` toggles, posts to Discord through the integration
+//! manager and/or emails the member. Each (member, kind, year) is
+//! recorded in `member_celebrations` before anything goes out, so the
+//! hourly ticks celebrate each occasion once.
+//!
+//! "Today" is the UTC date.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::{Celebration, CelebrationKind, MonthDay},
+    email::{
+        self,
+        templates::{CelebrationHtml, CelebrationText},
+        EmailSender,
+    },
+    error::Result,
+    integrations::{IntegrationEvent, IntegrationManager},
+    service::settings_service::SettingsService,
+};
+
+/// A member's shout-out choices. Members without a profile row have
+/// no birthday on file and are celebrated (anniversaries only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CelebrationPreferences {
+    pub birthday: Option<MonthDay>,
+    pub celebrate: bool,
+}
+
+impl Default for CelebrationPreferences {
+    fn default() -> Self {
+        Self {
+            birthday: None,
+            celebrate: true,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct CandidateRow {
+    id: String,
+    username: String,
+    full_name: String,
+    email: String,
+    discord_id: Option<String>,
+    joined_at: NaiveDateTime,
+    birthday: Option<String>,
+}
+
+/// Members who could be celebrated: current, and not opted out.
+const SELECT_CANDIDATES: &str = "SELECT m.id, m.username, m.full_name, m.email, \
+        m.discord_id, m.joined_at, p.birthday \
+     FROM members m \
+     LEFT JOIN member_profiles p ON p.member_id = m.id \
+     WHERE m.status IN ('Active', 'Honorary') \
+       AND COALESCE(p.celebrate, 1) = 1";
+
+pub struct CelebrationService {
+    pool: SqlitePool,
+    settings: Arc<SettingsService>,
+    email_sender: Arc<dyn EmailSender>,
+    integration_manager: Arc<IntegrationManager>,
+    base_url: String,
+}
+
+impl CelebrationService {
+    pub fn new(
+        pool: SqlitePool,
+        settings: Arc<SettingsService>,
+        email_sender: Arc<dyn EmailSender>,
+        integration_manager: Arc<IntegrationManager>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            pool,
+            settings,
+            email_sender,
+            integration_manager,
+            base_url,
+        }
+    }
+
+    pub async fn preferences(&self, member_id: Uuid) -> Result<CelebrationPreferences> {
+        let row: Option<(Option<String>, bool)> =
+            sqlx::query_as("SELECT birthday, celebrate FROM member_profiles WHERE member_id = ?")
+                .bind(member_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row
+            .map(|(birthday, celebrate)| CelebrationPreferences {
+                birthday: birthday.and_then(|b| b.parse().ok()),
+                celebrate,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Save a member's choices, creating their profile row if needed.
+    pub async fn set_preferences(
+        &self,
+        member_id: Uuid,
+        prefs: CelebrationPreferences,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO member_profiles (member_id, birthday, celebrate) \
+             VALUES (?, ?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 birthday = excluded.birthday, \
+                 celebrate = excluded.celebrate, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(prefs.birthday.map(|b| b.to_string()))
+        .bind(prefs.celebrate)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Birthdays and anniversaries falling on `today` that haven't been
+    /// celebrated yet this year. A member who joined on `today` a year
+    /// or more ago has an anniversary; joining today doesn't count.
+    pub async fn due_on(&self, today: NaiveDate) -> Result<Vec<Celebration>> {
+        let rows: Vec<CandidateRow> = sqlx::query_as(SELECT_CANDIDATES)
+            .fetch_all(&self.pool)
+            .await?;
+        let sent: HashSet<(String, String)> =
+            sqlx::query_as("SELECT member_id, kind FROM member_celebrations WHERE year = ?")
+                .bind(today.year())
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .collect();
+
+        let mut due = Vec::new();
+        for row in rows {
+            let Ok(member_id) = Uuid::parse_str(&row.id) else {
+                continue;
+            };
+            let celebration = |kind: CelebrationKind, years: Option<i32>| Celebration {
+                kind,
+                member_id,
+                username: row.username.clone(),
+                full_name: row.full_name.clone(),
+                email: row.email.clone(),
+                discord_id: row.discord_id.clone(),
+                years,
+            };
+            let not_sent = |kind: CelebrationKind| {
+                !sent.contains(&(row.id.clone(), kind.as_str().to_string()))
+            };
+
+            let birthday = row
+                .birthday
+                .as_deref()
+                .and_then(|b| b.parse::<MonthDay>().ok());
+            if birthday.is_some_and(|b| b.falls_on(today)) && not_sent(CelebrationKind::Birthday) {
+                due.push(celebration(CelebrationKind::Birthday, None));
+            }
+
+            let joined = row.joined_at.date();
+            let years = today.year() - joined.year();
+            if years >= 1
+                && MonthDay::of(joined).falls_on(today)
+                && not_sent(CelebrationKind::Anniversary)
+            {
+                due.push(celebration(CelebrationKind::Anniversary, Some(years)));
+            }
+        }
+        Ok(due)
+    }
+
+    /// Celebrate everyone due today on the enabled channels. Returns
+    /// how many shout-outs went out. Nothing is recorded while both
+    /// channels are off, so switching one on mid-day still catches
+    /// today's members.
+    pub async fn send_due(&self, now: DateTime<Utc>) -> Result<usize> {
+        let discord = self.enabled("celebrations.discord_enabled").await;
+        let email = self.enabled("celebrations.email_enabled").await;
+        if !discord && !email {
+            return Ok(0);
+        }
+
+        let today = now.date_naive();
+        let mut sent = 0;
+        for celebration in self.due_on(today).await? {
+            if !self.claim(&celebration, today.year()).await? {
+                // Another tick got here first.
+                continue;
+            }
+            if discord {
+                self.integration_manager
+                    .handle_event(IntegrationEvent::MemberCelebration(celebration.clone()))
+                    .await;
+            }
+            if email {
+                if let Err(e) = self.send_email(&celebration).await {
+                    tracing::warn!(
+                        "Celebration email to member {} failed: {}",
+                        celebration.member_id,
+                        e
+                    );
+                }
+            }
+            sent += 1;
+        }
+        Ok(sent)
+    }
+
+    async fn enabled(&self, key: &str) -> bool {
+        self.settings.get_bool(key).await.unwrap_or(false)
+    }
+
+    /// Record the shout-out; false when it was already recorded.
+    async fn claim(&self, celebration: &Celebration, year: i32) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO member_celebrations (member_id, kind, year) VALUES (?, ?, ?)",
+        )
+        .bind(celebration.member_id.to_string())
+        .bind(celebration.kind.as_str())
+        .bind(year)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn send_email(&self, celebration: &Celebration) -> Result<()> {
+        let org_name = self
+            .settings
+            .get_value("org.name")
+            .await
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Coterie".to_string());
+        let profile_url = format!("{}/portal/profile", self.base_url.trim_end_matches('/'));
+        let years = celebration.years_label();
+        let subject = match &years {
+            Some(years) => format!("Happy {} with {}!", years, org_name),
+            None => format!("Happy birthday from {}!", org_name),
+        };
+        let headline = match celebration.kind {
+            CelebrationKind::Birthday => "Happy birthday!",
+            CelebrationKind::Anniversary => "Happy membership anniversary!",
+        };
+
+        let html = CelebrationHtml {
+            full_name: &celebration.full_name,
+            org_name: &org_name,
+            headline,
+            years: years.as_deref(),
+            profile_url: &profile_url,
+        };
+        let text = CelebrationText {
+            full_name: &celebration.full_name,
+            org_name: &org_name,
+            years: years.as_deref(),
+            profile_url: &profile_url,
+        };
+        let message =
+            email::message_from_templates(celebration.email.clone(), subject, &html, &text)?;
+        self.email_sender.send(&message).await
+    }
+}
@@ -2,7 +2,9 @@
 //! services (event-type, announcement-type, membership-type) call
 //! `validate_hex_color_for_request`; the basic-type service additionally uses
 //! `check_unique_slug_for_basic` and `check_delete_unused_for_basic`.
+//! `SeedReport` is what each service's `seed_defaults` hands back.
 
+use serde::Serialize;
 use uuid::Uuid;
 
 use crate::{
@@ -66,3 +68,26 @@ pub(crate) async fn check_delete_unused_for_basic(
     }
     Ok(())
 }
+
+/// Outcome of one `seed_defaults` call: how many default types were
+/// inserted and how many were left alone because their slug already
+/// existed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SeedReport {
+    pub created: usize,
+    pub skipped: usize,
+}
+
+impl SeedReport {
+    /// Tally one create attempt. A `Conflict` means the slug turned up
+    /// between the existence check and the insert (two admins clicking
+    /// at once), which is a skip, not a failure.
+    pub(crate) fn record<T>(&mut self, outcome: Result<T>) -> Result<()> {
+        match outcome {
+            Ok(_) => self.created += 1,
+            Err(AppError::Conflict(_)) => self.skipped += 1,
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+}
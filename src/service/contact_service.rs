@@ -0,0 +1,257 @@
+//! The "contact the admins" form and the admin contact inbox.
+//!
+//! Visitors without an account (someone thinking of joining, or a
+//! member who can't log in) send a message from `/contact` or through
+//! `POST /public/contact`. Each one is stored in `contact_submissions`
+//! and raises a `ContactSubmitted` admin notification; admins work
+//! through them at `/portal/admin/contact`.
+//!
+//! Both entry points are rate-limited per IP by the caller. The form
+//! also carries a honeypot field that people never see: a submission
+//! that fills it in is dropped here without being stored, and the
+//! caller answers exactly as if it had gone through so a bot learns
+//! nothing.
+
+use std::sync::Arc;
+
+use chrono::{NaiveDateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::{AdminNotificationKind, ContactCategory, ContactSubmission},
+    error::{AppError, Result},
+    integrations::{IntegrationEvent, IntegrationManager},
+    service::{audit_service::AuditService, settings_service::SettingsService},
+};
+
+pub const MAX_NAME_LEN: usize = 100;
+pub const MAX_EMAIL_LEN: usize = 254;
+pub const MAX_MESSAGE_LEN: usize = 5000;
+
+/// A message as submitted. `honeypot` is the hidden form field; any
+/// non-blank value marks the sender as a bot.
+#[derive(Debug, Clone, Default)]
+pub struct ContactInput {
+    pub name: String,
+    pub email: String,
+    pub category: ContactCategory,
+    pub message: String,
+    pub honeypot: Option<String>,
+}
+
+#[derive(FromRow)]
+struct ContactRow {
+    id: String,
+    name: String,
+    email: String,
+    category: String,
+    message: String,
+    created_at: NaiveDateTime,
+    handled_at: Option<NaiveDateTime>,
+    handled_by: Option<String>,
+}
+
+impl ContactRow {
+    fn into_submission(self) -> Result<ContactSubmission> {
+        Ok(ContactSubmission {
+            id: Uuid::parse_str(&self.id).map_err(|e| AppError::Internal(e.to_string()))?,
+            name: self.name,
+            email: self.email,
+            category: self
+                .category
+                .parse()
+                .map_err(|e: crate::domain::ParseEnumError| AppError::Internal(e.to_string()))?,
+            message: self.message,
+            created_at: self.created_at.and_utc(),
+            handled_at: self.handled_at.map(|t| t.and_utc()),
+            handled_by: self.handled_by.and_then(|id| Uuid::parse_str(&id).ok()),
+        })
+    }
+}
+
+const SELECT_SUBMISSIONS: &str = "SELECT id, name, email, category, message, created_at, \
+     handled_at, handled_by FROM contact_submissions";
+
+pub struct ContactService {
+    pool: SqlitePool,
+    settings: Arc<SettingsService>,
+    integration_manager: Arc<IntegrationManager>,
+    audit_service: Arc<AuditService>,
+    base_url: String,
+}
+
+impl ContactService {
+    pub fn new(
+        pool: SqlitePool,
+        settings: Arc<SettingsService>,
+        integration_manager: Arc<IntegrationManager>,
+        audit_service: Arc<AuditService>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            pool,
+            settings,
+            integration_manager,
+            audit_service,
+            base_url,
+        }
+    }
+
+    /// `features.contact_form_enabled`; on unless an admin turns it off.
+    pub async fn enabled(&self) -> bool {
+        self.settings
+            .get_bool("features.contact_form_enabled")
+            .await
+            .unwrap_or(true)
+    }
+
+    /// Store a message and notify the admins. Returns `None` when the
+    /// honeypot was filled in: nothing is stored or sent, and the
+    /// caller should report success anyway.
+    pub async fn submit(&self, input: ContactInput) -> Result<Option<ContactSubmission>> {
+        if !self.enabled().await {
+            return Err(AppError::NotFound(
+                "Contact form is not available".to_string(),
+            ));
+        }
+        if input
+            .honeypot
+            .as_deref()
+            .is_some_and(|v| !v.trim().is_empty())
+        {
+            tracing::info!("Dropped contact form submission with honeypot filled in");
+            return Ok(None);
+        }
+
+        let name = input.name.trim();
+        let email = input.email.trim();
+        let message = input.message.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(AppError::BadRequest(format!(
+                "Name is required (up to {} characters)",
+                MAX_NAME_LEN
+            )));
+        }
+        if !email.contains('@') || email.len() > MAX_EMAIL_LEN {
+            return Err(AppError::BadRequest(
+                "A valid email address is required".to_string(),
+            ));
+        }
+        if message.is_empty() || message.chars().count() > MAX_MESSAGE_LEN {
+            return Err(AppError::BadRequest(format!(
+                "Message is required (up to {} characters)",
+                MAX_MESSAGE_LEN
+            )));
+        }
+
+        let submission = ContactSubmission {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            email: email.to_string(),
+            category: input.category,
+            message: message.to_string(),
+            created_at: Utc::now(),
+            handled_at: None,
+            handled_by: None,
+        };
+        sqlx::query(
+            "INSERT INTO contact_submissions (id, name, email, category, message, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(submission.id.to_string())
+        .bind(&submission.name)
+        .bind(&submission.email)
+        .bind(submission.category.as_str())
+        .bind(&submission.message)
+        .bind(submission.created_at.naive_utc())
+        .execute(&self.pool)
+        .await?;
+
+        self.integration_manager
+            .handle_event(IntegrationEvent::AdminNotification {
+                kind: AdminNotificationKind::ContactSubmitted,
+                key: format!("contact:{}", submission.id),
+                subject: format!(
+                    "Contact form ({}): {}",
+                    submission.category.label(),
+                    submission.name
+                ),
+                body: format!(
+                    "{} <{}> wrote:\n\n{}\n\nReply by email, then mark it handled at {}/portal/admin/contact",
+                    submission.name,
+                    submission.email,
+                    submission.message,
+                    self.base_url.trim_end_matches('/'),
+                ),
+            })
+            .await;
+
+        Ok(Some(submission))
+    }
+
+    /// The inbox, newest first. Handled messages are left out unless
+    /// `include_handled`.
+    pub async fn list(&self, include_handled: bool) -> Result<Vec<ContactSubmission>> {
+        let sql = if include_handled {
+            format!("{} ORDER BY created_at DESC", SELECT_SUBMISSIONS)
+        } else {
+            format!(
+                "{} WHERE handled_at IS NULL ORDER BY created_at DESC",
+                SELECT_SUBMISSIONS
+            )
+        };
+        sqlx::query_as::<_, ContactRow>(&sql)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(ContactRow::into_submission)
+            .collect()
+    }
+
+    /// Messages nobody has marked handled yet.
+    pub async fn open_count(&self) -> Result<i64> {
+        Ok(
+            sqlx::query_scalar("SELECT COUNT(*) FROM contact_submissions WHERE handled_at IS NULL")
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Mark a message dealt with. Marking one twice keeps the first
+    /// admin and time.
+    pub async fn mark_handled(&self, id: Uuid, admin_id: Uuid) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE contact_submissions SET handled_at = CURRENT_TIMESTAMP, handled_by = ? \
+             WHERE id = ? AND handled_at IS NULL",
+        )
+        .bind(admin_id.to_string())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            let exists: Option<String> =
+                sqlx::query_scalar("SELECT id FROM contact_submissions WHERE id = ?")
+                    .bind(id.to_string())
+                    .fetch_optional(&self.pool)
+                    .await?;
+            if exists.is_none() {
+                return Err(AppError::NotFound("Contact message not found".to_string()));
+            }
+            return Ok(());
+        }
+
+        self.audit_service
+            .log(
+                Some(admin_id),
+                "handle_contact_submission",
+                "contact_submission",
+                &id.to_string(),
+                None,
+                None,
+                None,
+            )
+            .await;
+        Ok(())
+    }
+}
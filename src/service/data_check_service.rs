@@ -0,0 +1,127 @@
+//! Finds rows whose enum/status columns hold values the app can't
+//! parse. List queries skip such rows (see
+//! `repository::malformed_rows`) rather than failing the page, so this
+//! is where an admin finds out they exist. Report only: fixing a row
+//! means deciding what the value should have been.
+
+use std::str::FromStr;
+
+use sqlx::SqlitePool;
+
+use crate::{
+    domain::{
+        AnnouncementType, EventStatus, EventType, EventVisibility, MemberStatus,
+        ParseEnumError, PaymentMethod, PaymentStatus,
+    },
+    error::{AppError, Result},
+};
+
+/// One stored value that doesn't parse.
+#[derive(Debug, Clone)]
+pub struct MalformedRow {
+    pub table: &'static str,
+    pub id: String,
+    pub column: &'static str,
+    pub value: String,
+    /// The parser's message, e.g. "Invalid member status: Lapsed".
+    pub problem: String,
+}
+
+struct EnumColumn {
+    table: &'static str,
+    column: &'static str,
+    check: fn(&str) -> Option<String>,
+}
+
+fn check<T: FromStr<Err = ParseEnumError>>(value: &str) -> Option<String> {
+    value.parse::<T>().err().map(|e| e.to_string())
+}
+
+/// Every enum column a list query parses. Table and column names are
+/// interpolated into SQL; they're constants, never user input.
+const ENUM_COLUMNS: &[EnumColumn] = &[
+    EnumColumn {
+        table: "members",
+        column: "status",
+        check: check::<MemberStatus>,
+    },
+    EnumColumn {
+        table: "events",
+        column: "event_type",
+        check: check::<EventType>,
+    },
+    EnumColumn {
+        table: "events",
+        column: "visibility",
+        check: check::<EventVisibility>,
+    },
+    EnumColumn {
+        table: "events",
+        column: "status",
+        check: check::<EventStatus>,
+    },
+    EnumColumn {
+        table: "announcements",
+        column: "announcement_type",
+        check: check::<AnnouncementType>,
+    },
+    EnumColumn {
+        table: "payments",
+        column: "status",
+        check: check::<PaymentStatus>,
+    },
+    EnumColumn {
+        table: "payments",
+        column: "payment_method",
+        check: check::<PaymentMethod>,
+    },
+];
+
+pub struct DataCheckService {
+    pool: SqlitePool,
+}
+
+impl DataCheckService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Every row with an unparseable enum value, grouped by table and
+    /// column in `ENUM_COLUMNS` order.
+    pub async fn malformed_rows(&self) -> Result<Vec<MalformedRow>> {
+        let mut found = Vec::new();
+        for col in ENUM_COLUMNS {
+            // Check each distinct value once, then fetch the rows
+            // holding the bad ones.
+            let values: Vec<String> = sqlx::query_scalar(&format!(
+                "SELECT DISTINCT CAST({} AS TEXT) FROM {} WHERE {} IS NOT NULL",
+                col.column, col.table, col.column
+            ))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::Database)?;
+
+            for value in values {
+                let Some(problem) = (col.check)(&value) else {
+                    continue;
+                };
+                let ids: Vec<String> = sqlx::query_scalar(&format!(
+                    "SELECT id FROM {} WHERE CAST({} AS TEXT) = ? ORDER BY id",
+                    col.table, col.column
+                ))
+                .bind(&value)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(AppError::Database)?;
+                found.extend(ids.into_iter().map(|id| MalformedRow {
+                    table: col.table,
+                    id,
+                    column: col.column,
+                    value: value.clone(),
+                    problem: problem.clone(),
+                }));
+            }
+        }
+        Ok(found)
+    }
+}
@@ -0,0 +1,136 @@
+//! Member directory. Members opt in from their profile; the directory
+//! lists opted-in Active/Honorary members to other members.
+//!
+//! Two separate flags on `member_profiles`:
+//!   * `show_in_directory` — listed at all (name, username, type).
+//!   * `show_contact` — email shown too. Without it other members get
+//!     a "contact via admin" link instead.
+//!
+//! The email is blanked in SQL rather than by the caller, so no view
+//! built on [`DirectoryEntry`] can leak a hidden address by forgetting
+//! to check the flag.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// A member's directory choices. Members without a profile row get
+/// the default: not listed, contact hidden.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DirectoryPrivacy {
+    pub show_in_directory: bool,
+    pub show_contact: bool,
+}
+
+/// A member as other members see them in the directory.
+#[derive(Debug, Clone)]
+pub struct DirectoryEntry {
+    pub member_id: Uuid,
+    pub username: String,
+    pub full_name: String,
+    pub membership_type: Option<String>,
+    pub joined_at: DateTime<Utc>,
+    /// `None` unless the member chose to show their contact details.
+    pub email: Option<String>,
+}
+
+#[derive(FromRow)]
+struct DirectoryRow {
+    id: String,
+    username: String,
+    full_name: String,
+    membership_type: Option<String>,
+    joined_at: NaiveDateTime,
+    email: Option<String>,
+}
+
+impl From<DirectoryRow> for DirectoryEntry {
+    fn from(r: DirectoryRow) -> Self {
+        Self {
+            member_id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            username: r.username,
+            full_name: r.full_name,
+            membership_type: r.membership_type,
+            joined_at: DateTime::from_naive_utc_and_offset(r.joined_at, Utc),
+            email: r.email,
+        }
+    }
+}
+
+/// Shared SELECT for listed members. `email` is NULL unless the member
+/// opted into showing it.
+const DIRECTORY_SELECT: &str = "SELECT m.id, m.username, m.full_name, \
+        mt.name AS membership_type, m.joined_at, \
+        CASE WHEN p.show_contact THEN m.email END AS email \
+     FROM members m \
+     JOIN member_profiles p ON p.member_id = m.id \
+     LEFT JOIN membership_types mt ON mt.id = m.membership_type_id \
+     WHERE p.show_in_directory = 1 \
+       AND m.status IN ('Active', 'Honorary')";
+
+pub struct DirectoryService {
+    pool: SqlitePool,
+}
+
+impl DirectoryService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn privacy(&self, member_id: Uuid) -> Result<DirectoryPrivacy> {
+        let row: Option<(bool, bool)> = sqlx::query_as(
+            "SELECT show_in_directory, show_contact FROM member_profiles WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row
+            .map(|(show_in_directory, show_contact)| DirectoryPrivacy {
+                show_in_directory,
+                show_contact,
+            })
+            .unwrap_or_default())
+    }
+
+    /// Save a member's choices, creating their profile row if needed.
+    pub async fn set_privacy(&self, member_id: Uuid, privacy: DirectoryPrivacy) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO member_profiles (member_id, show_in_directory, show_contact) \
+             VALUES (?, ?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 show_in_directory = excluded.show_in_directory, \
+                 show_contact = excluded.show_contact, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(privacy.show_in_directory)
+        .bind(privacy.show_contact)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Every listed member, by name.
+    pub async fn list(&self) -> Result<Vec<DirectoryEntry>> {
+        let rows: Vec<DirectoryRow> = sqlx::query_as(&format!(
+            "{} ORDER BY m.full_name COLLATE NOCASE",
+            DIRECTORY_SELECT
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// One listed member. `None` when the member doesn't exist or isn't
+    /// in the directory — callers shouldn't distinguish the two.
+    pub async fn get(&self, member_id: Uuid) -> Result<Option<DirectoryEntry>> {
+        let row: Option<DirectoryRow> =
+            sqlx::query_as(&format!("{} AND m.id = ?", DIRECTORY_SELECT))
+                .bind(member_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(Into::into))
+    }
+}
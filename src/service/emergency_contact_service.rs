@@ -0,0 +1,169 @@
+//! Emergency contact and medical notes.
+//!
+//! Members fill these in on their profile. Only the member and admins
+//! see them: they live in `member_emergency_contacts`, which no
+//! directory or export query joins, and an event's attendee export
+//! carries them only when the admin asks for them (`for_members`).
+
+use std::collections::HashMap;
+
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+/// Longest name, phone or relationship accepted.
+pub const MAX_FIELD_LEN: usize = 200;
+/// Longest medical notes accepted.
+pub const MAX_NOTES_LEN: usize = 2000;
+
+/// A member's emergency details. Empty strings mean "not given".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmergencyContact {
+    pub name: String,
+    pub phone: String,
+    pub relationship: String,
+    /// Allergies, conditions, anything organizers should know.
+    pub medical_notes: String,
+}
+
+impl EmergencyContact {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty()
+            && self.phone.is_empty()
+            && self.relationship.is_empty()
+            && self.medical_notes.is_empty()
+    }
+
+    /// Trim every field and check the lengths.
+    fn normalized(self) -> Result<Self> {
+        let contact = Self {
+            name: self.name.trim().to_string(),
+            phone: self.phone.trim().to_string(),
+            relationship: self.relationship.trim().to_string(),
+            medical_notes: self.medical_notes.trim().to_string(),
+        };
+        for (label, value) in [
+            ("Contact name", &contact.name),
+            ("Contact phone", &contact.phone),
+            ("Relationship", &contact.relationship),
+        ] {
+            if value.chars().count() > MAX_FIELD_LEN {
+                return Err(AppError::BadRequest(format!(
+                    "{} must be at most {} characters",
+                    label, MAX_FIELD_LEN
+                )));
+            }
+        }
+        if contact.medical_notes.chars().count() > MAX_NOTES_LEN {
+            return Err(AppError::BadRequest(format!(
+                "Medical notes must be at most {} characters",
+                MAX_NOTES_LEN
+            )));
+        }
+        Ok(contact)
+    }
+}
+
+#[derive(FromRow)]
+struct ContactRow {
+    member_id: String,
+    contact_name: Option<String>,
+    contact_phone: Option<String>,
+    contact_relationship: Option<String>,
+    medical_notes: Option<String>,
+}
+
+impl From<ContactRow> for EmergencyContact {
+    fn from(r: ContactRow) -> Self {
+        Self {
+            name: r.contact_name.unwrap_or_default(),
+            phone: r.contact_phone.unwrap_or_default(),
+            relationship: r.contact_relationship.unwrap_or_default(),
+            medical_notes: r.medical_notes.unwrap_or_default(),
+        }
+    }
+}
+
+const SELECT_CONTACT: &str = "SELECT member_id, contact_name, contact_phone, \
+        contact_relationship, medical_notes \
+     FROM member_emergency_contacts";
+
+pub struct EmergencyContactService {
+    pool: SqlitePool,
+}
+
+impl EmergencyContactService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// The member's details, all empty if they haven't given any.
+    pub async fn get(&self, member_id: Uuid) -> Result<EmergencyContact> {
+        let row: Option<ContactRow> =
+            sqlx::query_as(&format!("{} WHERE member_id = ?", SELECT_CONTACT))
+                .bind(member_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(Into::into).unwrap_or_default())
+    }
+
+    /// Save the member's details. All-empty clears them.
+    pub async fn set(&self, member_id: Uuid, contact: EmergencyContact) -> Result<()> {
+        let contact = contact.normalized()?;
+        if contact.is_empty() {
+            sqlx::query("DELETE FROM member_emergency_contacts WHERE member_id = ?")
+                .bind(member_id.to_string())
+                .execute(&self.pool)
+                .await?;
+            return Ok(());
+        }
+
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        sqlx::query(
+            "INSERT INTO member_emergency_contacts \
+                 (member_id, contact_name, contact_phone, contact_relationship, medical_notes) \
+             VALUES (?, ?, ?, ?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 contact_name = excluded.contact_name, \
+                 contact_phone = excluded.contact_phone, \
+                 contact_relationship = excluded.contact_relationship, \
+                 medical_notes = excluded.medical_notes, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(non_empty(&contact.name))
+        .bind(non_empty(&contact.phone))
+        .bind(non_empty(&contact.relationship))
+        .bind(non_empty(&contact.medical_notes))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Details for each of `member_ids` who gave any, for an event's
+    /// check-in export.
+    pub async fn for_members(
+        &self,
+        member_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, EmergencyContact>> {
+        if member_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let placeholders = vec!["?"; member_ids.len()].join(", ");
+        let sql = format!("{} WHERE member_id IN ({})", SELECT_CONTACT, placeholders);
+        let mut query = sqlx::query_as::<_, ContactRow>(&sql);
+        for id in member_ids {
+            query = query.bind(id.to_string());
+        }
+        Ok(query
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .filter_map(|r| {
+                let id = Uuid::parse_str(&r.member_id).ok()?;
+                Some((id, r.into()))
+            })
+            .collect())
+    }
+}
@@ -15,11 +15,14 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::{
-    domain::{Event, EventType, EventVisibility, Recurrence},
+    domain::{Event, EventStatus, EventType, EventVisibility, Recurrence},
     error::{AppError, Result},
     integrations::{IntegrationEvent, IntegrationManager},
     repository::{EventRepository, EventSeriesRepository},
-    service::{audit_service::AuditService, recurring_event_service::RecurringEventService},
+    service::{
+        audit_service::AuditService, event_invite_service::EventInviteService,
+        recurring_event_service::RecurringEventService,
+    },
 };
 
 /// Typed input for creating an event. The handler parses the
@@ -37,6 +40,10 @@ pub struct CreateEventInput {
     pub location: Option<String>,
     pub max_attendees: Option<i32>,
     pub rsvp_required: bool,
+    pub allow_guest_rsvp: bool,
+    pub registration_group: Option<String>,
+    /// Must not be after `start_time`.
+    pub rsvp_deadline: Option<DateTime<Utc>>,
     pub image_url: Option<String>,
     /// Some → materialize a full recurring series via
     /// `RecurringEventService`. None → single-row insert.
@@ -61,15 +68,34 @@ pub struct UpdateEventInput {
     pub location: Option<String>,
     pub max_attendees: Option<i32>,
     pub rsvp_required: bool,
+    pub allow_guest_rsvp: bool,
+    pub registration_group: Option<String>,
+    /// Must not be after `start_time`. Per occurrence, like the start
+    /// time: "this and all future" edits leave other occurrences' own
+    /// deadlines alone.
+    pub rsvp_deadline: Option<DateTime<Utc>>,
     pub image_url: Option<String>,
 }
 
+/// Shown when an RSVP deadline falls after the event's start.
+pub const RSVP_DEADLINE_AFTER_START: &str = "RSVP deadline must be on or before the start time";
+
+fn check_rsvp_deadline(start: DateTime<Utc>, deadline: Option<DateTime<Utc>>) -> Result<()> {
+    match deadline {
+        Some(deadline) if deadline > start => {
+            Err(AppError::BadRequest(RSVP_DEADLINE_AFTER_START.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
 pub struct EventAdminService {
     event_repo: Arc<dyn EventRepository>,
     event_series_repo: Arc<dyn EventSeriesRepository>,
     recurring_event_service: Arc<RecurringEventService>,
     audit_service: Arc<AuditService>,
     integration_manager: Arc<IntegrationManager>,
+    event_invite_service: Arc<EventInviteService>,
 }
 
 impl EventAdminService {
@@ -79,6 +105,7 @@ impl EventAdminService {
         recurring_event_service: Arc<RecurringEventService>,
         audit_service: Arc<AuditService>,
         integration_manager: Arc<IntegrationManager>,
+        event_invite_service: Arc<EventInviteService>,
     ) -> Self {
         Self {
             event_repo,
@@ -86,6 +113,7 @@ impl EventAdminService {
             recurring_event_service,
             audit_service,
             integration_manager,
+            event_invite_service,
         }
     }
 
@@ -99,6 +127,7 @@ impl EventAdminService {
         actor_id: Uuid,
         input: CreateEventInput,
     ) -> Result<Event> {
+        check_rsvp_deadline(input.start_time, input.rsvp_deadline)?;
         let template = Event {
             id: Uuid::new_v4(),
             title: input.title,
@@ -111,12 +140,17 @@ impl EventAdminService {
             location: input.location,
             max_attendees: input.max_attendees,
             rsvp_required: input.rsvp_required,
+            allow_guest_rsvp: input.allow_guest_rsvp,
+            registration_group: input.registration_group,
+            rsvp_deadline: input.rsvp_deadline,
             image_url: input.image_url,
             created_by: actor_id,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             series_id: None,
             occurrence_index: None,
+            status: EventStatus::Published,
+            review_feedback: None,
         };
         let visibility_for_dispatch = template.visibility.clone();
 
@@ -168,14 +202,16 @@ impl EventAdminService {
         Ok(event)
     }
 
-    /// Update a single event row. Audits `update_event`. No
-    /// integration dispatch — updates are silent per existing design.
+    /// Update a single event row. Audits `update_event` and re-sends
+    /// calendar invites to registered members. No integration
+    /// dispatch — updates are silent per existing design.
     pub async fn update_one(
         &self,
         actor_id: Uuid,
         event_id: Uuid,
         input: UpdateEventInput,
     ) -> Result<Event> {
+        check_rsvp_deadline(input.start_time, input.rsvp_deadline)?;
         let existing = self.event_repo.find_by_id(event_id).await?
             .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
 
@@ -191,12 +227,17 @@ impl EventAdminService {
             location: input.location,
             max_attendees: input.max_attendees,
             rsvp_required: input.rsvp_required,
+            allow_guest_rsvp: input.allow_guest_rsvp,
+            registration_group: input.registration_group,
+            rsvp_deadline: input.rsvp_deadline,
             image_url: input.image_url,
             created_by: existing.created_by,
             created_at: existing.created_at,
             updated_at: Utc::now(),
             series_id: existing.series_id,
             occurrence_index: existing.occurrence_index,
+            status: existing.status,
+            review_feedback: existing.review_feedback,
         };
 
         let result = self.event_repo.update(event_id, updated).await?;
@@ -211,6 +252,8 @@ impl EventAdminService {
             None,
         ).await;
 
+        self.event_invite_service.send_event_update(&result).await;
+
         Ok(result)
     }
 
@@ -239,12 +282,17 @@ impl EventAdminService {
             location: input.location,
             max_attendees: input.max_attendees,
             rsvp_required: input.rsvp_required,
+            allow_guest_rsvp: input.allow_guest_rsvp,
+            registration_group: input.registration_group,
+            rsvp_deadline: input.rsvp_deadline,
             image_url: input.image_url,
             created_by: actor_id,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             series_id: Some(series_id),
             occurrence_index: None,
+            status: EventStatus::Published,
+            review_feedback: None,
         };
 
         let count = self.event_repo
@@ -264,8 +312,17 @@ impl EventAdminService {
         Ok(count)
     }
 
-    /// Delete a single event row. Audits `delete_event`.
+    /// Delete a single event row. Audits `delete_event` and sends
+    /// calendar cancellations to whoever was registered.
     pub async fn delete_one(&self, actor_id: Uuid, event_id: Uuid) -> Result<()> {
+        // Attendance rows cascade with the event, so collect who to
+        // send cancellations to first.
+        let event = self.event_repo.find_by_id(event_id).await?;
+        let recipients = match &event {
+            Some(_) => self.event_invite_service.registered_recipients(event_id).await,
+            None => Vec::new(),
+        };
+
         self.event_repo.delete(event_id).await?;
         self.audit_service.log(
             Some(actor_id),
@@ -276,6 +333,12 @@ impl EventAdminService {
             None,
             None,
         ).await;
+
+        if let Some(event) = event {
+            self.event_invite_service
+                .send_event_cancellation(&event, &recipients)
+                .await;
+        }
         Ok(())
     }
 
@@ -329,12 +392,15 @@ impl EventAdminService {
 mod tests {
     use super::*;
     use crate::{
+        auth::SecretCrypto,
         domain::{EventType, EventVisibility, Recurrence, WeekdayCode, CreateMemberRequest},
+        email::{EmailSender, LogSender},
         integrations::IntegrationManager,
         repository::{
             MemberRepository, SqliteEventRepository, SqliteEventSeriesRepository,
             SqliteMemberRepository,
         },
+        service::settings_service::SettingsService,
     };
     use chrono::{Datelike, Duration, Weekday};
     use sqlx::{Executor, SqlitePool};
@@ -395,6 +461,18 @@ mod tests {
         ));
         let audit = Arc::new(AuditService::new(pool.clone()));
         let integrations = Arc::new(IntegrationManager::new());
+        let email_sender: Arc<dyn EmailSender> = Arc::new(LogSender::new(
+            "test@example.com".to_string(),
+            "Test".to_string(),
+        ));
+        let crypto = Arc::new(SecretCrypto::new("test-secret-please-ignore"));
+        let settings = Arc::new(SettingsService::new(pool.clone(), crypto));
+        let invites = Arc::new(EventInviteService::new(
+            pool.clone(),
+            email_sender,
+            settings,
+            "http://test.local".to_string(),
+        ));
 
         EventAdminService::new(
             event_repo,
@@ -402,6 +480,7 @@ mod tests {
             recurring,
             audit,
             integrations,
+            invites,
         )
     }
 
@@ -440,6 +519,9 @@ mod tests {
             location: None,
             max_attendees: None,
             rsvp_required: false,
+            allow_guest_rsvp: false,
+            registration_group: None,
+            rsvp_deadline: None,
             image_url: None,
             recurrence: None,
             recurrence_until: None,
@@ -548,6 +630,9 @@ mod tests {
             location: event.location.clone(),
             max_attendees: event.max_attendees,
             rsvp_required: event.rsvp_required,
+            allow_guest_rsvp: event.allow_guest_rsvp,
+            registration_group: event.registration_group.clone(),
+            rsvp_deadline: event.rsvp_deadline,
             image_url: event.image_url.clone(),
         }
     }
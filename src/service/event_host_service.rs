@@ -0,0 +1,292 @@
+//! Event hosts: the member who created an event plus any co-hosts
+//! they've added. Hosts edit their event's details from the portal
+//! without being admins; the creator (or an admin) manages the co-host
+//! list.
+//!
+//! The creator is `events.created_by`; co-hosts are rows in
+//! `event_organizers`. Edits go through `EventAdminService::update_one`
+//! so they're audited and invitees hear about time changes the same
+//! way as an admin edit.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{Event, Member, MemberStatus},
+    error::{AppError, Result},
+    repository::{EventRepository, MemberRepository},
+    service::{
+        audit_service::AuditService,
+        event_admin_service::{EventAdminService, UpdateEventInput},
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct EventHost {
+    pub member_id: Uuid,
+    pub full_name: String,
+    pub username: String,
+    pub is_creator: bool,
+}
+
+/// What a host can change on `/portal/events/:id/edit`. Type,
+/// visibility, capacity, RSVP settings and the image stay as an admin
+/// set them.
+pub struct HostEventInput {
+    pub title: String,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub location: Option<String>,
+}
+
+pub struct EventHostService {
+    pool: SqlitePool,
+    event_repo: Arc<dyn EventRepository>,
+    member_repo: Arc<dyn MemberRepository>,
+    event_admin_service: Arc<EventAdminService>,
+    audit_service: Arc<AuditService>,
+}
+
+impl EventHostService {
+    pub fn new(
+        pool: SqlitePool,
+        event_repo: Arc<dyn EventRepository>,
+        member_repo: Arc<dyn MemberRepository>,
+        event_admin_service: Arc<EventAdminService>,
+        audit_service: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            pool,
+            event_repo,
+            member_repo,
+            event_admin_service,
+            audit_service,
+        }
+    }
+
+    async fn event(&self, event_id: Uuid) -> Result<Event> {
+        self.event_repo
+            .find_by_id(event_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))
+    }
+
+    /// The creator first, then co-hosts in the order they were added.
+    /// A creator whose account has been deleted is left out.
+    pub async fn hosts(&self, event: &Event) -> Result<Vec<EventHost>> {
+        let mut hosts = Vec::new();
+        if let Some(creator) = self.member_repo.find_by_id(event.created_by).await? {
+            hosts.push(EventHost {
+                member_id: creator.id,
+                full_name: creator.full_name,
+                username: creator.username,
+                is_creator: true,
+            });
+        }
+
+        let rows: Vec<(String, String, String)> = sqlx::query_as(
+            "SELECT m.id, m.full_name, m.username
+             FROM event_organizers o
+             JOIN members m ON m.id = o.member_id
+             WHERE o.event_id = ?
+             ORDER BY o.created_at, m.full_name",
+        )
+        .bind(event.id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        hosts.extend(rows.into_iter().map(|(id, full_name, username)| EventHost {
+            member_id: Uuid::parse_str(&id).unwrap_or_default(),
+            full_name,
+            username,
+            is_creator: false,
+        }));
+        Ok(hosts)
+    }
+
+    pub async fn is_host(&self, event: &Event, member_id: Uuid) -> Result<bool> {
+        if event.created_by == member_id {
+            return Ok(true);
+        }
+        let co_host: Option<i64> = sqlx::query_scalar(
+            "SELECT 1 FROM event_organizers WHERE event_id = ? AND member_id = ?",
+        )
+        .bind(event.id.to_string())
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(co_host.is_some())
+    }
+
+    /// Admins can edit any event; anyone else only the ones they host.
+    pub async fn can_edit(&self, member: &Member, event: &Event) -> Result<bool> {
+        Ok(member.is_admin || self.is_host(event, member.id).await?)
+    }
+
+    /// Load an event for `member` to edit: `NotFound` if it doesn't
+    /// exist, `Forbidden` if they can't edit it.
+    pub async fn editable_event(&self, member: &Member, event_id: Uuid) -> Result<Event> {
+        let event = self.event(event_id).await?;
+        if !self.can_edit(member, &event).await? {
+            return Err(AppError::Forbidden);
+        }
+        Ok(event)
+    }
+
+    /// Only the creator or an admin changes who co-hosts.
+    pub fn can_manage_hosts(member: &Member, event: &Event) -> bool {
+        member.is_admin || member.id == event.created_by
+    }
+
+    /// Events `member_id` created or co-hosts, for marking them
+    /// editable in the portal list.
+    pub async fn hosted_event_ids(&self, member_id: Uuid) -> Result<HashSet<Uuid>> {
+        let ids: Vec<String> = sqlx::query_scalar(
+            "SELECT id FROM events WHERE created_by = ?
+             UNION
+             SELECT event_id FROM event_organizers WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .bind(member_id.to_string())
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect())
+    }
+
+    /// A host's edit of the event details. Refused with `Forbidden`
+    /// unless `member` hosts the event or is an admin.
+    pub async fn update(
+        &self,
+        member: &Member,
+        event_id: Uuid,
+        input: HostEventInput,
+    ) -> Result<Event> {
+        let event = self.editable_event(member, event_id).await?;
+        let title = input.title.trim().to_string();
+        if title.is_empty() {
+            return Err(AppError::BadRequest("A title is required".to_string()));
+        }
+        if input.end_time.is_some_and(|end| end < input.start_time) {
+            return Err(AppError::BadRequest(
+                "The end time can't be before the start time".to_string(),
+            ));
+        }
+
+        let update = UpdateEventInput {
+            title,
+            description: input.description,
+            event_type: event.event_type,
+            event_type_id: event.event_type_id,
+            visibility: event.visibility,
+            start_time: input.start_time,
+            end_time: input.end_time,
+            location: input.location.filter(|l| !l.trim().is_empty()),
+            max_attendees: event.max_attendees,
+            rsvp_required: event.rsvp_required,
+            allow_guest_rsvp: event.allow_guest_rsvp,
+            registration_group: event.registration_group,
+            rsvp_deadline: event.rsvp_deadline,
+            image_url: event.image_url,
+        };
+        self.event_admin_service
+            .update_one(member.id, event_id, update)
+            .await
+    }
+
+    /// Make the member with `username` a co-host. They must be a
+    /// current member, and not already a host.
+    pub async fn add_co_host(&self, actor: &Member, event_id: Uuid, username: &str) -> Result<()> {
+        let event = self.event(event_id).await?;
+        if !Self::can_manage_hosts(actor, &event) {
+            return Err(AppError::Forbidden);
+        }
+        let username = username.trim();
+        let member = self
+            .member_repo
+            .find_by_username(username)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("No member with username {}", username)))?;
+        if !matches!(member.status, MemberStatus::Active | MemberStatus::Honorary) {
+            return Err(AppError::BadRequest(format!(
+                "{} isn't a current member",
+                member.username
+            )));
+        }
+        if member.id == event.created_by {
+            return Err(AppError::BadRequest(format!(
+                "{} already hosts this event",
+                member.username
+            )));
+        }
+
+        let added = sqlx::query(
+            "INSERT OR IGNORE INTO event_organizers (event_id, member_id, added_by)
+             VALUES (?, ?, ?)",
+        )
+        .bind(event_id.to_string())
+        .bind(member.id.to_string())
+        .bind(actor.id.to_string())
+        .execute(&self.pool)
+        .await?;
+        if added.rows_affected() == 0 {
+            return Err(AppError::BadRequest(format!(
+                "{} already hosts this event",
+                member.username
+            )));
+        }
+
+        self.audit_service
+            .log(
+                Some(actor.id),
+                "add_event_co_host",
+                "event",
+                &event_id.to_string(),
+                None,
+                Some(&member.username),
+                None,
+            )
+            .await;
+        Ok(())
+    }
+
+    pub async fn remove_co_host(
+        &self,
+        actor: &Member,
+        event_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<()> {
+        let event = self.event(event_id).await?;
+        if !Self::can_manage_hosts(actor, &event) {
+            return Err(AppError::Forbidden);
+        }
+        let removed =
+            sqlx::query("DELETE FROM event_organizers WHERE event_id = ? AND member_id = ?")
+                .bind(event_id.to_string())
+                .bind(member_id.to_string())
+                .execute(&self.pool)
+                .await?;
+        if removed.rows_affected() == 0 {
+            return Err(AppError::NotFound("Co-host not found".to_string()));
+        }
+
+        self.audit_service
+            .log(
+                Some(actor.id),
+                "remove_event_co_host",
+                "event",
+                &event_id.to_string(),
+                Some(&member_id.to_string()),
+                None,
+                None,
+            )
+            .await;
+        Ok(())
+    }
+}
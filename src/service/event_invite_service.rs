@@ -0,0 +1,305 @@
+//! Calendar invites for member RSVPs. Every email a registrant gets
+//! about an event — the RSVP confirmation, an admin edit, their own
+//! cancellation, the event being deleted — carries a single-event
+//! `.ics` so the entry lands in (or leaves) their calendar.
+//!
+//! All invites for one event share the event id as UID; the per-RSVP
+//! `event_attendance.invite_sequence` is bumped on every send so the
+//! calendar app replaces its copy rather than ignoring the newer one.
+//! Guest RSVPs don't get invites.
+//!
+//! Same contract as `LoginHistoryService`: nothing here fails the
+//! caller. The RSVP or edit already happened; a failed email is
+//! logged and dropped.
+
+use std::sync::Arc;
+
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::{Event, Locale, Member},
+    email::{
+        self,
+        templates::{EventInviteHtml, EventInviteText},
+        EmailAttachment, EmailSender,
+    },
+    error::Result,
+    service::settings_service::SettingsService,
+    util::ical::{self, InviteMethod, InviteParties},
+};
+
+/// Why the invite is going out. Picks the email copy; the `.ics`
+/// method follows from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InviteKind {
+    Confirmed,
+    Updated,
+    Cancelled,
+    EventCancelled,
+}
+
+impl InviteKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            InviteKind::Confirmed => "confirmed",
+            InviteKind::Updated => "updated",
+            InviteKind::Cancelled => "cancelled",
+            InviteKind::EventCancelled => "event_cancelled",
+        }
+    }
+
+    fn method(&self) -> InviteMethod {
+        match self {
+            InviteKind::Confirmed | InviteKind::Updated => InviteMethod::Request,
+            InviteKind::Cancelled | InviteKind::EventCancelled => InviteMethod::Cancel,
+        }
+    }
+
+    fn subject(&self, title: &str) -> String {
+        match self {
+            InviteKind::Confirmed => format!("You're registered: {}", title),
+            InviteKind::Updated => format!("Updated: {}", title),
+            InviteKind::Cancelled => format!("RSVP cancelled: {}", title),
+            InviteKind::EventCancelled => format!("Cancelled: {}", title),
+        }
+    }
+}
+
+/// A registered member who should hear about changes to an event,
+/// with the sequence their next invite must carry.
+#[derive(Debug, Clone, FromRow)]
+pub struct InviteRecipient {
+    member_id: String,
+    email: String,
+    full_name: String,
+    locale: Option<String>,
+    next_sequence: i64,
+}
+
+pub struct EventInviteService {
+    pool: SqlitePool,
+    email_sender: Arc<dyn EmailSender>,
+    settings_service: Arc<SettingsService>,
+    base_url: String,
+}
+
+impl EventInviteService {
+    pub fn new(
+        pool: SqlitePool,
+        email_sender: Arc<dyn EmailSender>,
+        settings_service: Arc<SettingsService>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            pool,
+            email_sender,
+            settings_service,
+            base_url,
+        }
+    }
+
+    /// Email `member` a REQUEST invite for the event they just
+    /// RSVP'd to.
+    pub async fn send_rsvp_confirmation(&self, event: &Event, member: &Member) {
+        if let Some(sequence) = self.bump_sequence(event.id, member.id).await {
+            self.send(
+                event,
+                InviteKind::Confirmed,
+                &member_recipient(member, sequence),
+            )
+            .await;
+        }
+    }
+
+    /// Email `member` a CANCEL for the event they just backed out of.
+    pub async fn send_rsvp_cancellation(&self, event: &Event, member: &Member) {
+        if let Some(sequence) = self.bump_sequence(event.id, member.id).await {
+            self.send(
+                event,
+                InviteKind::Cancelled,
+                &member_recipient(member, sequence),
+            )
+            .await;
+        }
+    }
+
+    /// Re-send a REQUEST to everyone registered for `event` after an
+    /// edit, so their calendar picks up the new time and place.
+    pub async fn send_event_update(&self, event: &Event) {
+        for recipient in self.registered_recipients(event.id).await {
+            let Ok(member_id) = Uuid::parse_str(&recipient.member_id) else {
+                continue;
+            };
+            if let Some(sequence) = self.bump_sequence(event.id, member_id).await {
+                let recipient = InviteRecipient {
+                    next_sequence: sequence,
+                    ..recipient
+                };
+                self.send(event, InviteKind::Updated, &recipient).await;
+            }
+        }
+    }
+
+    /// Everyone registered for `event`, for a cancellation that will
+    /// delete the attendance rows. Collect these before deleting the
+    /// event, then pass them to `send_event_cancellation` once the
+    /// delete has gone through.
+    pub async fn registered_recipients(&self, event_id: Uuid) -> Vec<InviteRecipient> {
+        sqlx::query_as::<_, InviteRecipient>(
+            "SELECT m.id AS member_id, m.email, m.full_name, m.locale, \
+                    COALESCE(a.invite_sequence + 1, 0) AS next_sequence \
+             FROM event_attendance a \
+             JOIN members m ON m.id = a.member_id \
+             WHERE a.event_id = ? AND a.status = 'Registered'",
+        )
+        .bind(event_id.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!(
+                "Failed to list invite recipients for event {}: {}",
+                event_id,
+                e
+            );
+            Vec::new()
+        })
+    }
+
+    /// Email each of `recipients` a CANCEL for `event`, which has been
+    /// deleted. Sequences come from the recipient rows since the
+    /// attendance rows are gone.
+    pub async fn send_event_cancellation(&self, event: &Event, recipients: &[InviteRecipient]) {
+        for recipient in recipients {
+            self.send(event, InviteKind::EventCancelled, recipient)
+                .await;
+        }
+    }
+
+    /// Advance the RSVP's invite sequence and return the new value,
+    /// `0` for its first invite. `None` when there's no attendance row
+    /// (or the update failed, logged).
+    async fn bump_sequence(&self, event_id: Uuid, member_id: Uuid) -> Option<i64> {
+        let result = sqlx::query_scalar::<_, i64>(
+            "UPDATE event_attendance \
+             SET invite_sequence = COALESCE(invite_sequence + 1, 0) \
+             WHERE event_id = ? AND member_id = ? \
+             RETURNING invite_sequence",
+        )
+        .bind(event_id.to_string())
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await;
+        match result {
+            Ok(sequence) => sequence,
+            Err(e) => {
+                tracing::error!(
+                    "Failed to bump invite sequence for event {} member {}: {}",
+                    event_id,
+                    member_id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    async fn send(&self, event: &Event, kind: InviteKind, recipient: &InviteRecipient) {
+        if let Err(e) = self.try_send(event, kind, recipient).await {
+            tracing::error!(
+                "Failed to send {} invite for event {} to {}: {}",
+                kind.as_str(),
+                event.id,
+                recipient.email,
+                e
+            );
+        }
+    }
+
+    async fn try_send(
+        &self,
+        event: &Event,
+        kind: InviteKind,
+        recipient: &InviteRecipient,
+    ) -> Result<()> {
+        let org_name = self
+            .settings_service
+            .get_value("org.name")
+            .await
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Coterie".to_string());
+        let org_email = self
+            .settings_service
+            .get_value("org.contact_email")
+            .await
+            .unwrap_or_default();
+        let org_locale = self.settings_service.org_locale().await;
+
+        let event_start = format!(
+            "{} UTC",
+            Locale::resolve(recipient.locale.as_deref(), org_locale)
+                .long_date_time(&event.start_time),
+        );
+        let event_url = format!("{}/portal/events", self.base_url.trim_end_matches('/'));
+
+        let method = kind.method();
+        let ics = ical::event_invite(
+            event,
+            method,
+            recipient.next_sequence,
+            &InviteParties {
+                organizer_name: &org_name,
+                organizer_email: &org_email,
+                attendee_name: &recipient.full_name,
+                attendee_email: &recipient.email,
+            },
+            &event_url,
+        );
+
+        let html = EventInviteHtml {
+            full_name: &recipient.full_name,
+            org_name: &org_name,
+            kind: kind.as_str(),
+            event_title: &event.title,
+            event_start: &event_start,
+            event_location: event.location.as_deref(),
+            event_url: &event_url,
+        };
+        let text = EventInviteText {
+            full_name: &recipient.full_name,
+            org_name: &org_name,
+            kind: kind.as_str(),
+            event_title: &event.title,
+            event_start: &event_start,
+            event_location: event.location.as_deref(),
+            event_url: &event_url,
+        };
+        let mut message = email::message_from_templates(
+            recipient.email.clone(),
+            kind.subject(&event.title),
+            &html,
+            &text,
+        )?;
+        message.attachments.push(EmailAttachment {
+            filename: match method {
+                InviteMethod::Request => "invite.ics".to_string(),
+                InviteMethod::Cancel => "cancel.ics".to_string(),
+            },
+            content_type: format!("text/calendar; charset=utf-8; method={}", method.as_str()),
+            content: ics.into_bytes(),
+        });
+
+        self.email_sender.send(&message).await
+    }
+}
+
+fn member_recipient(member: &Member, sequence: i64) -> InviteRecipient {
+    InviteRecipient {
+        member_id: member.id.to_string(),
+        email: member.email.clone(),
+        full_name: member.full_name.clone(),
+        locale: member.locale.clone(),
+        next_sequence: sequence,
+    }
+}
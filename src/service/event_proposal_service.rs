@@ -0,0 +1,289 @@
+//! Member-proposed events: submission from the portal, the admin
+//! review queue, and the decision email back to the proposer.
+//!
+//! A proposal is an ordinary `events` row with `status = Proposed`.
+//! Every listing query filters on `Published`, so nothing here has to
+//! remember to hide it — approval flips the status and the event shows
+//! up everywhere at once, the same way an admin-created one does.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    domain::{AdminNotificationKind, Event, EventStatus, EventType, EventVisibility, Member, MemberStatus},
+    email::{
+        self,
+        templates::{EventProposalDecisionHtml, EventProposalDecisionText},
+        EmailSender,
+    },
+    error::{AppError, Result},
+    integrations::{IntegrationEvent, IntegrationManager},
+    repository::{EventRepository, MemberRepository},
+    service::{audit_service::AuditService, settings_service::SettingsService},
+};
+
+/// What a member fills in on `/portal/events/propose`. Deliberately
+/// narrower than `CreateEventInput`: no recurrence, capacity, or image
+/// — an admin can add those after approval.
+pub struct ProposeEventInput {
+    pub title: String,
+    pub description: String,
+    pub event_type: EventType,
+    pub visibility: EventVisibility,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub location: Option<String>,
+}
+
+pub struct EventProposalService {
+    event_repo: Arc<dyn EventRepository>,
+    member_repo: Arc<dyn MemberRepository>,
+    audit_service: Arc<AuditService>,
+    integration_manager: Arc<IntegrationManager>,
+    email_sender: Arc<dyn EmailSender>,
+    settings_service: Arc<SettingsService>,
+    base_url: String,
+}
+
+impl EventProposalService {
+    pub fn new(
+        event_repo: Arc<dyn EventRepository>,
+        member_repo: Arc<dyn MemberRepository>,
+        audit_service: Arc<AuditService>,
+        integration_manager: Arc<IntegrationManager>,
+        email_sender: Arc<dyn EmailSender>,
+        settings_service: Arc<SettingsService>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            event_repo,
+            member_repo,
+            audit_service,
+            integration_manager,
+            email_sender,
+            settings_service,
+            base_url,
+        }
+    }
+
+    /// Submit a proposal on behalf of `member`. The event is stored as
+    /// `Proposed` and admins get an alert that something is waiting.
+    pub async fn propose(&self, member: &Member, input: ProposeEventInput) -> Result<Event> {
+        if !matches!(member.status, MemberStatus::Active | MemberStatus::Honorary) {
+            return Err(AppError::Forbidden);
+        }
+        let title = input.title.trim().to_string();
+        if title.is_empty() {
+            return Err(AppError::BadRequest("A title is required".to_string()));
+        }
+        if input.start_time <= Utc::now() {
+            return Err(AppError::BadRequest("The start time must be in the future".to_string()));
+        }
+        if input.end_time.is_some_and(|end| end < input.start_time) {
+            return Err(AppError::BadRequest("The end time must be after the start time".to_string()));
+        }
+        if input.visibility == EventVisibility::AdminOnly {
+            return Err(AppError::BadRequest(
+                "Proposed events must be public or members-only".to_string(),
+            ));
+        }
+
+        let now = Utc::now();
+        let event = self.event_repo.create(Event {
+            id: Uuid::new_v4(),
+            title,
+            description: input.description.trim().to_string(),
+            event_type: input.event_type,
+            event_type_id: None,
+            visibility: input.visibility,
+            start_time: input.start_time,
+            end_time: input.end_time,
+            location: input.location.filter(|l| !l.trim().is_empty()),
+            max_attendees: None,
+            rsvp_required: false,
+            allow_guest_rsvp: false,
+            registration_group: None,
+            rsvp_deadline: None,
+            image_url: None,
+            created_by: member.id,
+            created_at: now,
+            updated_at: now,
+            series_id: None,
+            occurrence_index: None,
+            status: EventStatus::Proposed,
+            review_feedback: None,
+        }).await?;
+
+        self.audit_service.log(
+            Some(member.id),
+            "propose_event",
+            "event",
+            &event.id.to_string(),
+            None,
+            Some(&event.title),
+            None,
+        ).await;
+
+        self.integration_manager
+            .handle_event(IntegrationEvent::AdminNotification {
+                kind: AdminNotificationKind::ProposalSubmitted,
+                key: format!("proposal:{}", event.id),
+                subject: format!("Event proposed: {}", event.title),
+                body: format!(
+                    "{} <{}> proposed \"{}\" for {}.\nReview it at {}/portal/admin/events/proposals",
+                    member.full_name,
+                    member.email,
+                    event.title,
+                    event.start_time.format("%Y-%m-%d %H:%M UTC"),
+                    self.base_url.trim_end_matches('/'),
+                ),
+            })
+            .await;
+
+        Ok(event)
+    }
+
+    /// Oldest-first queue of proposals waiting for a decision.
+    pub async fn pending(&self) -> Result<Vec<Event>> {
+        self.event_repo.list_by_status(EventStatus::Proposed).await
+    }
+
+    /// Everything `member_id` has proposed, newest first.
+    pub async fn proposals_by(&self, member_id: Uuid) -> Result<Vec<Event>> {
+        self.event_repo.list_by_creator(member_id).await
+    }
+
+    /// Publish a proposal. Audits `approve_event_proposal`, dispatches
+    /// `EventPublished` exactly as an admin-created event would, and
+    /// emails the proposer. `feedback` is an optional note for them.
+    pub async fn approve(
+        &self,
+        actor_id: Uuid,
+        event_id: Uuid,
+        feedback: Option<String>,
+    ) -> Result<Event> {
+        let feedback = feedback.map(|f| f.trim().to_string()).filter(|f| !f.is_empty());
+        let event = self.decide(event_id, EventStatus::Published, feedback.as_deref()).await?;
+
+        self.audit_service.log(
+            Some(actor_id),
+            "approve_event_proposal",
+            "event",
+            &event_id.to_string(),
+            Some(EventStatus::Proposed.as_str()),
+            Some(EventStatus::Published.as_str()),
+            None,
+        ).await;
+
+        self.integration_manager
+            .handle_event(IntegrationEvent::EventPublished(event.clone()))
+            .await;
+
+        self.notify_proposer(&event, true).await;
+        Ok(event)
+    }
+
+    /// Turn a proposal down. Feedback is required — the proposer
+    /// deserves to know why. Audits `reject_event_proposal` and emails
+    /// the proposer.
+    pub async fn reject(&self, actor_id: Uuid, event_id: Uuid, feedback: String) -> Result<Event> {
+        let feedback = feedback.trim();
+        if feedback.is_empty() {
+            return Err(AppError::BadRequest(
+                "Please tell the proposer why the event was rejected".to_string(),
+            ));
+        }
+        let event = self.decide(event_id, EventStatus::Rejected, Some(feedback)).await?;
+
+        self.audit_service.log(
+            Some(actor_id),
+            "reject_event_proposal",
+            "event",
+            &event_id.to_string(),
+            Some(EventStatus::Proposed.as_str()),
+            Some(feedback),
+            None,
+        ).await;
+
+        self.notify_proposer(&event, false).await;
+        Ok(event)
+    }
+
+    async fn decide(
+        &self,
+        event_id: Uuid,
+        status: EventStatus,
+        feedback: Option<&str>,
+    ) -> Result<Event> {
+        self.event_repo.find_by_id(event_id).await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))?;
+        if !self.event_repo.review_proposal(event_id, status, feedback).await? {
+            return Err(AppError::Conflict(
+                "This proposal has already been reviewed".to_string(),
+            ));
+        }
+        self.event_repo.find_by_id(event_id).await?
+            .ok_or_else(|| AppError::NotFound("Event not found".to_string()))
+    }
+
+    /// Best-effort decision email. The decision is already committed,
+    /// so a missing proposer or a failed send is logged, not returned.
+    async fn notify_proposer(&self, event: &Event, approved: bool) {
+        let proposer = match self.member_repo.find_by_id(event.created_by).await {
+            Ok(Some(m)) => m,
+            Ok(None) => return,
+            Err(e) => {
+                tracing::warn!("Could not load proposer for event {}: {}", event.id, e);
+                return;
+            }
+        };
+
+        let org_name = self
+            .settings_service
+            .get_value("org.name")
+            .await
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Coterie".to_string());
+        let portal_url = format!("{}/portal/events/propose", self.base_url.trim_end_matches('/'));
+        let html = EventProposalDecisionHtml {
+            full_name: &proposer.full_name,
+            org_name: &org_name,
+            event_title: &event.title,
+            approved,
+            feedback: event.review_feedback.as_deref(),
+            portal_url: &portal_url,
+        };
+        let text = EventProposalDecisionText {
+            full_name: &proposer.full_name,
+            org_name: &org_name,
+            event_title: &event.title,
+            approved,
+            feedback: event.review_feedback.as_deref(),
+            portal_url: &portal_url,
+        };
+        let subject = if approved {
+            format!("Your event \"{}\" was approved", event.title)
+        } else {
+            format!("Your event proposal \"{}\" was declined", event.title)
+        };
+
+        let message = match email::message_from_templates(proposer.email.clone(), subject, &html, &text) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::error!("Failed to render proposal decision email: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.email_sender.send(&message).await {
+            tracing::error!(
+                "Failed to send proposal decision for event {} to {}: {}",
+                event.id,
+                proposer.email,
+                e
+            );
+        }
+    }
+}
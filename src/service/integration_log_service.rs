@@ -0,0 +1,267 @@
+//! Outbound integration log. One row per attempt an integration makes
+//! to handle an `IntegrationEvent` — written by `IntegrationManager`
+//! around each `handle_event` call, browsed from the admin UI, and
+//! pruned hourly by the cleanup task.
+//!
+//! Same contract as `AuditService`: `record` never fails the caller.
+//! Losing a log row is preferable to turning a Discord hiccup into a
+//! 500 on the admin action that triggered it.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+pub struct IntegrationLogService {
+    pool: SqlitePool,
+}
+
+/// What a single integration attempt produced, handed to `record`.
+pub struct NewIntegrationLogEntry<'a> {
+    pub integration: &'a str,
+    pub event_type: &'a str,
+    pub target: &'a str,
+    pub request_summary: &'a str,
+    /// `None` on success; the integration's error text otherwise.
+    pub error: Option<&'a str>,
+    /// Serialized `IntegrationEvent`, kept so the entry can be replayed.
+    pub event_payload: &'a str,
+    /// Set when this attempt is a replay of an earlier entry.
+    pub replay_of: Option<Uuid>,
+}
+
+/// A log entry as returned to the admin UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrationLogEntry {
+    pub id: Uuid,
+    pub integration: String,
+    pub event_type: String,
+    pub target: String,
+    pub request_summary: String,
+    pub ok: bool,
+    pub error: Option<String>,
+    pub event_payload: String,
+    pub replay_of: Option<Uuid>,
+    pub replayed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters for `recent`. Empty strings mean "don't filter."
+#[derive(Debug, Clone, Default)]
+pub struct IntegrationLogFilter {
+    pub integration: String,
+    /// "ok", "error", or empty.
+    pub status: String,
+    pub limit: i64,
+}
+
+#[derive(FromRow)]
+struct IntegrationLogRow {
+    id: String,
+    integration: String,
+    event_type: String,
+    target: String,
+    request_summary: String,
+    response_status: String,
+    error: Option<String>,
+    event_payload: String,
+    replay_of: Option<String>,
+    replayed_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl From<IntegrationLogRow> for IntegrationLogEntry {
+    fn from(r: IntegrationLogRow) -> Self {
+        Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            integration: r.integration,
+            event_type: r.event_type,
+            target: r.target,
+            request_summary: r.request_summary,
+            ok: r.response_status == "ok",
+            error: r.error,
+            event_payload: r.event_payload,
+            replay_of: r.replay_of.and_then(|s| Uuid::parse_str(&s).ok()),
+            replayed_at: r.replayed_at.map(|t| DateTime::from_naive_utc_and_offset(t, Utc)),
+            created_at: DateTime::from_naive_utc_and_offset(r.created_at, Utc),
+        }
+    }
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, integration, event_type, target, request_summary, \
+     response_status, error, event_payload, replay_of, replayed_at, created_at \
+     FROM integration_log";
+
+impl IntegrationLogService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Write one attempt. Summary and error text go through
+    /// `redact_secrets` first — HTTP client errors can echo request
+    /// headers back, and we don't want a bot token sitting in a table
+    /// every admin can read. Never fails the caller.
+    pub async fn record(&self, entry: NewIntegrationLogEntry<'_>) -> Option<Uuid> {
+        let id = Uuid::new_v4();
+        let summary = redact_secrets(entry.request_summary);
+        let error = entry.error.map(redact_secrets);
+        let status = if error.is_some() { "error" } else { "ok" };
+
+        let result = sqlx::query(
+            "INSERT INTO integration_log \
+             (id, integration, event_type, target, request_summary, response_status, \
+              error, event_payload, replay_of) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id.to_string())
+        .bind(entry.integration)
+        .bind(entry.event_type)
+        .bind(entry.target)
+        .bind(&summary)
+        .bind(status)
+        .bind(&error)
+        .bind(entry.event_payload)
+        .bind(entry.replay_of.map(|u| u.to_string()))
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Some(id),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to write integration log (integration={}, event={}): {}",
+                    entry.integration, entry.event_type, e
+                );
+                None
+            }
+        }
+    }
+
+    /// Most recent entries, newest first, narrowed by `filter`.
+    pub async fn recent(&self, filter: &IntegrationLogFilter) -> Result<Vec<IntegrationLogEntry>> {
+        let sql = format!(
+            "{} WHERE (? = '' OR integration = ?) \
+               AND (? = '' OR response_status = ?) \
+             ORDER BY created_at DESC, rowid DESC \
+             LIMIT ?",
+            SELECT_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, IntegrationLogRow>(&sql)
+            .bind(&filter.integration)
+            .bind(&filter.integration)
+            .bind(&filter.status)
+            .bind(&filter.status)
+            .bind(filter.limit.clamp(1, 500))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<IntegrationLogEntry> {
+        let sql = format!("{} WHERE id = ?", SELECT_COLUMNS);
+        sqlx::query_as::<_, IntegrationLogRow>(&sql)
+            .bind(id.to_string())
+            .fetch_optional(&self.pool)
+            .await?
+            .map(Into::into)
+            .ok_or_else(|| AppError::NotFound("Integration log entry not found".to_string()))
+    }
+
+    /// Distinct integration names seen in the log, for the filter
+    /// dropdown.
+    pub async fn integration_names(&self) -> Result<Vec<String>> {
+        let names = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT integration FROM integration_log ORDER BY integration",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(names)
+    }
+
+    /// Stamp `replayed_at` on the original entry after a replay.
+    pub async fn mark_replayed(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE integration_log SET replayed_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Delete entries older than `retention_days`. Returns the number
+    /// of rows removed.
+    pub async fn prune_older_than(&self, retention_days: i64) -> Result<u64> {
+        let days = retention_days.clamp(1, 3650);
+        let result = sqlx::query(
+            "DELETE FROM integration_log WHERE created_at < datetime('now', '-' || ? || ' days')",
+        )
+        .bind(days)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Mask credentials that tend to leak into error strings: auth header
+/// values (`Bot …`, `Bearer …`), Stripe keys and webhook secrets, and
+/// `password=` / `token=` query parameters. Everything after the
+/// marker up to the next whitespace, quote, or `&` becomes `[REDACTED]`.
+pub fn redact_secrets(input: &str) -> String {
+    const MARKERS: &[&str] = &[
+        "Bot ", "Bearer ", "sk_live_", "sk_test_", "rk_live_", "rk_test_", "whsec_",
+        "password=", "token=",
+    ];
+
+    let mut out = input.to_string();
+    for marker in MARKERS {
+        let mut search_from = 0;
+        while let Some(pos) = out[search_from..].find(marker) {
+            let start = search_from + pos + marker.len();
+            let end = out[start..]
+                .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '&')
+                .map(|i| start + i)
+                .unwrap_or(out.len());
+            if end > start {
+                out.replace_range(start..end, "[REDACTED]");
+            }
+            search_from = start + if end > start { "[REDACTED]".len() } else { 0 };
+            if search_from >= out.len() {
+                break;
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redact_secrets;
+
+    #[test]
+    fn masks_auth_headers_and_keys() {
+        assert_eq!(
+            redact_secrets("Authorization: Bot abc.def.ghi failed"),
+            "Authorization: Bot [REDACTED] failed"
+        );
+        assert_eq!(redact_secrets("key sk_live_123abc"), "key sk_live_[REDACTED]");
+        assert_eq!(
+            redact_secrets("GET /x?password=hunter2&user=bob"),
+            "GET /x?password=[REDACTED]&user=bob"
+        );
+    }
+
+    #[test]
+    fn leaves_plain_text_alone() {
+        let s = "Discord add_role guild=1 user=2 role=3: HTTP 403";
+        assert_eq!(redact_secrets(s), s);
+    }
+
+    #[test]
+    fn masks_every_occurrence() {
+        assert_eq!(
+            redact_secrets("Bearer a Bearer b"),
+            "Bearer [REDACTED] Bearer [REDACTED]"
+        );
+    }
+}
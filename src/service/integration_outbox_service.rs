@@ -0,0 +1,196 @@
+//! Durable outbox for integration deliveries. `IntegrationManager`
+//! enqueues one row per (integration, event) pair in `handle_event`
+//! before attempting it, and deletes the row once the integration
+//! takes the event. A failed attempt is rescheduled with exponential
+//! backoff for the delivery worker to retry; after the configured
+//! number of attempts the row is marked failed and stays put for the
+//! admin to see.
+
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::error::Result;
+
+/// First retry delay; each further failure doubles it.
+const BASE_BACKOFF_SECS: i64 = 30;
+/// Ceiling on the retry delay, so a long outage is still retried
+/// hourly rather than once a day.
+const MAX_BACKOFF_SECS: i64 = 60 * 60;
+/// How far a claim pushes `next_attempt_at` out. A process that dies
+/// mid-attempt leaves the row due again after this long.
+const CLAIM_LEASE_SECS: i64 = 5 * 60;
+
+pub struct IntegrationOutboxService {
+    pool: SqlitePool,
+}
+
+/// A claimed row, ready to hand to its integration.
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub integration: String,
+    pub event_type: String,
+    pub event_payload: String,
+    /// Attempts made before this one.
+    pub attempts: i64,
+}
+
+/// Queue depth for the admin integration page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct OutboxCounts {
+    /// Waiting for a retry, or being attempted right now.
+    pub pending: i64,
+    /// Out of attempts.
+    pub failed: i64,
+}
+
+impl IntegrationOutboxService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue `event_payload` (a serialized `IntegrationEvent`) for
+    /// `integration`. The row comes back already claimed: the caller
+    /// makes the first attempt itself and settles it with `delivered`
+    /// or `attempt_failed`. If it never does (the process died), the
+    /// claim runs out and the worker picks the row up.
+    pub async fn enqueue(
+        &self,
+        integration: &str,
+        event_type: &str,
+        event_payload: &str,
+    ) -> Result<OutboxEntry> {
+        let id = Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO integration_outbox \
+             (id, integration, event_type, event_payload, next_attempt_at) \
+             VALUES (?, ?, ?, ?, datetime('now', '+' || ? || ' seconds'))",
+        )
+        .bind(&id)
+        .bind(integration)
+        .bind(event_type)
+        .bind(event_payload)
+        .bind(CLAIM_LEASE_SECS)
+        .execute(&self.pool)
+        .await?;
+        Ok(OutboxEntry {
+            id,
+            integration: integration.to_string(),
+            event_type: event_type.to_string(),
+            event_payload: event_payload.to_string(),
+            attempts: 0,
+        })
+    }
+
+    /// Claim up to `limit` pending rows that are due, oldest first.
+    /// The claim is a single UPDATE pushing `next_attempt_at` out by
+    /// the lease, so two workers can't pick up the same row.
+    pub async fn claim_due(&self, limit: i64) -> Result<Vec<OutboxEntry>> {
+        let rows = sqlx::query_as::<_, OutboxEntry>(
+            "UPDATE integration_outbox \
+             SET next_attempt_at = datetime('now', '+' || ? || ' seconds'), \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id IN ( \
+                 SELECT id FROM integration_outbox \
+                 WHERE status = 'pending' AND next_attempt_at <= datetime('now') \
+                 ORDER BY next_attempt_at, created_at \
+                 LIMIT ?) \
+             RETURNING id, integration, event_type, event_payload, attempts",
+        )
+        .bind(CLAIM_LEASE_SECS)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// The integration took the event; the row is done.
+    pub async fn delivered(&self, id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM integration_outbox WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Count a failed attempt. Reschedules with backoff while attempts
+    /// remain under `max_attempts`; past that, or when `retry` is
+    /// false (nothing a retry could fix), marks the row failed.
+    pub async fn attempt_failed(
+        &self,
+        entry: &OutboxEntry,
+        error: &str,
+        max_attempts: i64,
+        retry: bool,
+    ) -> Result<()> {
+        let attempts = entry.attempts + 1;
+        let status = if retry && attempts < max_attempts {
+            "pending"
+        } else {
+            "failed"
+        };
+        sqlx::query(
+            "UPDATE integration_outbox \
+             SET status = ?, attempts = ?, last_error = ?, \
+                 next_attempt_at = datetime('now', '+' || ? || ' seconds'), \
+                 updated_at = CURRENT_TIMESTAMP \
+             WHERE id = ?",
+        )
+        .bind(status)
+        .bind(attempts)
+        .bind(super::integration_log_service::redact_secrets(error))
+        .bind(backoff_secs(attempts))
+        .bind(&entry.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn counts(&self) -> Result<OutboxCounts> {
+        let (pending, failed): (i64, i64) = sqlx::query_as(
+            "SELECT \
+                 COALESCE(SUM(status = 'pending'), 0), \
+                 COALESCE(SUM(status = 'failed'), 0) \
+             FROM integration_outbox",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(OutboxCounts { pending, failed })
+    }
+
+    /// Delete failed rows older than `retention_days`. Pending rows are
+    /// never pruned — they're still owed a delivery.
+    pub async fn prune_failed_older_than(&self, retention_days: i64) -> Result<u64> {
+        let days = retention_days.clamp(1, 3650);
+        let result = sqlx::query(
+            "DELETE FROM integration_outbox \
+             WHERE status = 'failed' AND updated_at < datetime('now', '-' || ? || ' days')",
+        )
+        .bind(days)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// Delay before retry number `attempts`: 30s, 1m, 2m, … capped at an
+/// hour.
+fn backoff_secs(attempts: i64) -> i64 {
+    let doublings = (attempts - 1).clamp(0, 20) as u32;
+    (BASE_BACKOFF_SECS << doublings).min(MAX_BACKOFF_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backoff_secs;
+
+    #[test]
+    fn backoff_doubles_up_to_an_hour() {
+        assert_eq!(backoff_secs(1), 30);
+        assert_eq!(backoff_secs(2), 60);
+        assert_eq!(backoff_secs(5), 480);
+        assert_eq!(backoff_secs(8), 3600);
+        assert_eq!(backoff_secs(50), 3600);
+    }
+}
@@ -0,0 +1,74 @@
+//! Where a member lands after signing in.
+//!
+//! The member's own choice (`member_profiles.landing_page`) wins over
+//! the club default for their role (`auth.member_landing_page` /
+//! `auth.admin_landing_page`). Whatever is picked is checked against
+//! the role at sign-in, so a member who loses admin, or a hand-edited
+//! row naming an admin page, lands on the dashboard instead of a
+//! redirect back out of the admin area.
+//!
+//! Expired members (including `Active` ones whose status is derived as
+//! `Expired`, see `ExpiryMode`) always go to `/portal/restore`: it's
+//! the only page they can reach. A validated `redirect` on the login itself beats
+//! all of this; that check stays in the login handlers.
+
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{LandingPage, Member, MemberStatus},
+    error::Result,
+    service::settings_service::SettingsService,
+};
+
+pub struct LandingPageService {
+    pool: SqlitePool,
+    settings: Arc<SettingsService>,
+}
+
+impl LandingPageService {
+    pub fn new(pool: SqlitePool, settings: Arc<SettingsService>) -> Self {
+        Self { pool, settings }
+    }
+
+    /// The member's saved override, `None` to follow the club default.
+    /// An unparseable value is treated as no override.
+    pub async fn preference(&self, member_id: Uuid) -> Result<Option<LandingPage>> {
+        let value: Option<Option<String>> =
+            sqlx::query_scalar("SELECT landing_page FROM member_profiles WHERE member_id = ?")
+                .bind(member_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(value.flatten().and_then(|v| v.parse().ok()))
+    }
+
+    /// Save (or clear, with `None`) the member's override, creating
+    /// their profile row if needed.
+    pub async fn set_preference(&self, member_id: Uuid, page: Option<LandingPage>) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO member_profiles (member_id, landing_page) VALUES (?, ?) \
+             ON CONFLICT (member_id) DO UPDATE SET \
+                 landing_page = excluded.landing_page, \
+                 updated_at = CURRENT_TIMESTAMP",
+        )
+        .bind(member_id.to_string())
+        .bind(page.map(|p| p.as_str()))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Path to send `member` to after a login without a `redirect`.
+    pub async fn destination(&self, member: &Member) -> String {
+        if self.settings.effective_status(member).await == MemberStatus::Expired {
+            return "/portal/restore".to_string();
+        }
+        let page = match self.preference(member.id).await {
+            Ok(Some(page)) => page,
+            _ => self.settings.default_landing_page(member.is_admin).await,
+        };
+        page.permitted_for(member.is_admin).path().to_string()
+    }
+}
@@ -0,0 +1,442 @@
+//! Member login history. One row per successful sign-in — written by
+//! the login handlers once a session has been issued, shown to the
+//! member on `/portal/profile/sessions`, and pruned hourly by the
+//! cleanup task.
+//!
+//! A sign-in from a device the member has never used before gets an
+//! alert email. "Device" is a fingerprint of the user-agent plus the
+//! network prefix, so the alert fires once per new browser/network
+//! combination rather than on every DHCP lease change.
+//!
+//! Same contract as `AuditService`: `record_login` never fails the
+//! caller. A login that went through stays through even if the history
+//! insert or the alert email doesn't.
+
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+use crate::{
+    domain::Member,
+    email::{
+        self,
+        templates::{NewDeviceLoginHtml, NewDeviceLoginText},
+        EmailSender,
+    },
+    error::{AppError, Result},
+    service::settings_service::SettingsService,
+};
+
+/// Longest user-agent we keep. Real ones are a few hundred bytes;
+/// anything past this is noise or abuse.
+const MAX_USER_AGENT_LEN: usize = 512;
+
+/// How the member proved who they were. Stored as `method`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LoginMethod {
+    Password,
+    Totp,
+    RecoveryCode,
+    Api,
+}
+
+impl LoginMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoginMethod::Password => "password",
+            LoginMethod::Totp => "totp",
+            LoginMethod::RecoveryCode => "recovery_code",
+            LoginMethod::Api => "api",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoginMethod::Password => "Password",
+            LoginMethod::Totp => "Password + authenticator",
+            LoginMethod::RecoveryCode => "Password + recovery code",
+            LoginMethod::Api => "API",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "totp" => LoginMethod::Totp,
+            "recovery_code" => LoginMethod::RecoveryCode,
+            "api" => LoginMethod::Api,
+            _ => LoginMethod::Password,
+        }
+    }
+}
+
+/// A login as shown to the member.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoginHistoryEntry {
+    pub id: Uuid,
+    pub member_id: Uuid,
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+    pub location: Option<String>,
+    pub method: LoginMethod,
+    /// This login was the first from its device fingerprint.
+    pub new_device: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct LoginHistoryRow {
+    id: String,
+    member_id: String,
+    ip_address: String,
+    user_agent: Option<String>,
+    location: Option<String>,
+    method: String,
+    new_device: bool,
+    created_at: NaiveDateTime,
+}
+
+impl From<LoginHistoryRow> for LoginHistoryEntry {
+    fn from(r: LoginHistoryRow) -> Self {
+        Self {
+            id: Uuid::parse_str(&r.id).unwrap_or_default(),
+            member_id: Uuid::parse_str(&r.member_id).unwrap_or_default(),
+            ip_address: r.ip_address,
+            user_agent: r.user_agent,
+            location: r.location,
+            method: LoginMethod::parse(&r.method),
+            new_device: r.new_device,
+            created_at: DateTime::from_naive_utc_and_offset(r.created_at, Utc),
+        }
+    }
+}
+
+pub struct LoginHistoryService {
+    pool: SqlitePool,
+    email_sender: Arc<dyn EmailSender>,
+    settings_service: Arc<SettingsService>,
+    base_url: String,
+    /// Set once at startup when `auth.geoip_db_path` is configured.
+    geoip: OnceLock<maxminddb::Reader<Vec<u8>>>,
+}
+
+impl LoginHistoryService {
+    pub fn new(
+        pool: SqlitePool,
+        email_sender: Arc<dyn EmailSender>,
+        settings_service: Arc<SettingsService>,
+        base_url: String,
+    ) -> Self {
+        Self {
+            pool,
+            email_sender,
+            settings_service,
+            base_url,
+            geoip: OnceLock::new(),
+        }
+    }
+
+    /// Load a GeoIP City database for approximate locations. Only the
+    /// first call takes effect.
+    pub fn attach_geoip(&self, path: &Path) -> Result<()> {
+        let reader = maxminddb::Reader::open_readfile(path).map_err(|e| {
+            AppError::Internal(format!("Failed to open GeoIP database {}: {}", path.display(), e))
+        })?;
+        let _ = self.geoip.set(reader);
+        Ok(())
+    }
+
+    /// Record a successful login and, if it came from a device this
+    /// member hasn't used before, email them about it. The very first
+    /// login on record never alerts — there's nothing to compare it
+    /// against. Returns the new row's id, or `None` if the insert
+    /// failed (logged).
+    pub async fn record_login(
+        &self,
+        member: &Member,
+        ip: IpAddr,
+        user_agent: Option<&str>,
+        method: LoginMethod,
+    ) -> Option<Uuid> {
+        let user_agent = user_agent
+            .map(|ua| truncate_chars(ua.trim(), MAX_USER_AGENT_LEN))
+            .filter(|ua| !ua.is_empty());
+        let fingerprint = device_fingerprint(user_agent.as_deref(), ip);
+        let location = self.lookup_location(ip);
+
+        let (has_history, seen_before) = match sqlx::query_as::<_, (bool, bool)>(
+            "SELECT EXISTS(SELECT 1 FROM login_history WHERE member_id = ?), \
+                    EXISTS(SELECT 1 FROM login_history WHERE member_id = ? AND device_fingerprint = ?)",
+        )
+        .bind(member.id.to_string())
+        .bind(member.id.to_string())
+        .bind(&fingerprint)
+        .fetch_one(&self.pool)
+        .await
+        {
+            Ok(flags) => flags,
+            Err(e) => {
+                tracing::error!("Failed to read login history for member {}: {}", member.id, e);
+                return None;
+            }
+        };
+        let new_device = has_history && !seen_before;
+
+        let id = Uuid::new_v4();
+        let result = sqlx::query(
+            "INSERT INTO login_history \
+             (id, member_id, ip_address, user_agent, location, device_fingerprint, method, new_device) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(id.to_string())
+        .bind(member.id.to_string())
+        .bind(ip.to_string())
+        .bind(&user_agent)
+        .bind(&location)
+        .bind(&fingerprint)
+        .bind(method.as_str())
+        .bind(new_device)
+        .execute(&self.pool)
+        .await;
+
+        if let Err(e) = result {
+            tracing::error!("Failed to write login history for member {}: {}", member.id, e);
+            return None;
+        }
+
+        if new_device {
+            let alerts_enabled = self
+                .settings_service
+                .get_bool("auth.new_device_alerts")
+                .await
+                .unwrap_or(true);
+            if alerts_enabled {
+                self.send_new_device_alert(member, ip, user_agent.as_deref(), location.as_deref())
+                    .await;
+            }
+        }
+
+        Some(id)
+    }
+
+    /// A member's most recent logins, newest first.
+    pub async fn recent_for_member(&self, member_id: Uuid, limit: i64) -> Result<Vec<LoginHistoryEntry>> {
+        let rows = sqlx::query_as::<_, LoginHistoryRow>(
+            "SELECT id, member_id, ip_address, user_agent, location, method, new_device, created_at \
+             FROM login_history WHERE member_id = ? \
+             ORDER BY created_at DESC, rowid DESC \
+             LIMIT ?",
+        )
+        .bind(member_id.to_string())
+        .bind(limit.clamp(1, 200))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Delete entries older than `retention_days`. Returns the number
+    /// of rows removed.
+    pub async fn prune_older_than(&self, retention_days: i64) -> Result<u64> {
+        let days = retention_days.clamp(1, 3650);
+        let result = sqlx::query(
+            "DELETE FROM login_history WHERE created_at < datetime('now', '-' || ? || ' days')",
+        )
+        .bind(days)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// "City, Country" from the attached GeoIP database, or `None`
+    /// when there's no database or the address isn't in it (private
+    /// ranges, loopback).
+    fn lookup_location(&self, ip: IpAddr) -> Option<String> {
+        let reader = self.geoip.get()?;
+        let city: maxminddb::geoip2::City = reader.lookup(ip).ok()?;
+        let english = |names: Option<std::collections::BTreeMap<&str, &str>>| {
+            names.and_then(|n| n.get("en").map(|s| s.to_string()))
+        };
+        let city_name = city.city.and_then(|c| english(c.names));
+        let country_name = city.country.and_then(|c| english(c.names));
+        match (city_name, country_name) {
+            (Some(city), Some(country)) => Some(format!("{}, {}", city, country)),
+            (None, Some(country)) => Some(country),
+            (Some(city), None) => Some(city),
+            (None, None) => None,
+        }
+    }
+
+    /// Best-effort alert. The login already happened, so a failed send
+    /// is logged, not returned.
+    async fn send_new_device_alert(
+        &self,
+        member: &Member,
+        ip: IpAddr,
+        user_agent: Option<&str>,
+        location: Option<&str>,
+    ) {
+        let org_name = self
+            .settings_service
+            .get_value("org.name")
+            .await
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| "Coterie".to_string());
+        let sessions_url = format!("{}/portal/profile/sessions", self.base_url.trim_end_matches('/'));
+        let when = format!("{} UTC", Utc::now().format("%Y-%m-%d %H:%M"));
+        let device = describe_user_agent(user_agent);
+        let ip_address = ip.to_string();
+
+        let html = NewDeviceLoginHtml {
+            full_name: &member.full_name,
+            org_name: &org_name,
+            when: &when,
+            device: &device,
+            ip_address: &ip_address,
+            location,
+            sessions_url: &sessions_url,
+        };
+        let text = NewDeviceLoginText {
+            full_name: &member.full_name,
+            org_name: &org_name,
+            when: &when,
+            device: &device,
+            ip_address: &ip_address,
+            location,
+            sessions_url: &sessions_url,
+        };
+        let subject = format!("New sign-in to your {} account", org_name);
+
+        let message = match email::message_from_templates(member.email.clone(), subject, &html, &text) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::error!("Failed to render new-device alert: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.email_sender.send(&message).await {
+            tracing::error!(
+                "Failed to send new-device alert for member {} to {}: {}",
+                member.id,
+                member.email,
+                e
+            );
+        }
+    }
+}
+
+/// Hash of the user-agent and the network the login came from. The
+/// address is cut to its /24 (IPv4) or /48 (IPv6) so the same laptop on
+/// the same home network keeps one fingerprint across lease changes.
+pub fn device_fingerprint(user_agent: Option<&str>, ip: IpAddr) -> String {
+    let network = match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        IpAddr::V6(v6) => {
+            let s = v6.segments();
+            format!("{:x}:{:x}:{:x}::/48", s[0], s[1], s[2])
+        }
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(user_agent.unwrap_or("").as_bytes());
+    hasher.update(b"\n");
+    hasher.update(network.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Short "Browser on OS" summary of a user-agent for the alert email
+/// and the sessions page. Falls back to "Unknown device".
+pub fn describe_user_agent(user_agent: Option<&str>) -> String {
+    let ua = match user_agent {
+        Some(ua) if !ua.is_empty() => ua,
+        _ => return "Unknown device".to_string(),
+    };
+
+    // Order matters: Edge and Opera UAs also say "Chrome", and Chrome
+    // UAs also say "Safari".
+    let browser = if ua.contains("Edg/") {
+        Some("Edge")
+    } else if ua.contains("OPR/") {
+        Some("Opera")
+    } else if ua.contains("Firefox/") {
+        Some("Firefox")
+    } else if ua.contains("Chrome/") || ua.contains("CriOS/") {
+        Some("Chrome")
+    } else if ua.contains("Safari/") {
+        Some("Safari")
+    } else {
+        None
+    };
+    let os = if ua.contains("iPhone") || ua.contains("iPad") {
+        Some("iOS")
+    } else if ua.contains("Android") {
+        Some("Android")
+    } else if ua.contains("Windows") {
+        Some("Windows")
+    } else if ua.contains("Mac OS X") || ua.contains("Macintosh") {
+        Some("macOS")
+    } else if ua.contains("Linux") {
+        Some("Linux")
+    } else {
+        None
+    };
+
+    match (browser, os) {
+        (Some(b), Some(o)) => format!("{} on {}", b, o),
+        (Some(b), None) => b.to_string(),
+        (None, Some(o)) => format!("Browser on {}", o),
+        (None, None) => truncate_chars(ua, 60),
+    }
+}
+
+fn truncate_chars(s: &str, max: usize) -> String {
+    s.chars().take(max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREFOX_LINUX: &str =
+        "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+    const EDGE_WINDOWS: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+         (KHTML, like Gecko) Chrome/126.0.0.0 Safari/537.36 Edg/126.0.0.0";
+    const SAFARI_IPHONE: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) \
+         AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1";
+
+    #[test]
+    fn fingerprint_ignores_host_part_of_address() {
+        let a = device_fingerprint(Some(FIREFOX_LINUX), "203.0.113.10".parse().unwrap());
+        let b = device_fingerprint(Some(FIREFOX_LINUX), "203.0.113.200".parse().unwrap());
+        assert_eq!(a, b);
+
+        let v6a = device_fingerprint(Some(FIREFOX_LINUX), "2001:db8:1:2::1".parse().unwrap());
+        let v6b = device_fingerprint(Some(FIREFOX_LINUX), "2001:db8:1:ffff::9".parse().unwrap());
+        assert_eq!(v6a, v6b);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_network_or_browser() {
+        let home = device_fingerprint(Some(FIREFOX_LINUX), "203.0.113.10".parse().unwrap());
+        let elsewhere = device_fingerprint(Some(FIREFOX_LINUX), "198.51.100.10".parse().unwrap());
+        let other_browser = device_fingerprint(Some(EDGE_WINDOWS), "203.0.113.10".parse().unwrap());
+        assert_ne!(home, elsewhere);
+        assert_ne!(home, other_browser);
+    }
+
+    #[test]
+    fn describes_common_user_agents() {
+        assert_eq!(describe_user_agent(Some(FIREFOX_LINUX)), "Firefox on Linux");
+        assert_eq!(describe_user_agent(Some(EDGE_WINDOWS)), "Edge on Windows");
+        assert_eq!(describe_user_agent(Some(SAFARI_IPHONE)), "Safari on iOS");
+        assert_eq!(describe_user_agent(Some("curl/8.5.0")), "curl/8.5.0");
+        assert_eq!(describe_user_agent(None), "Unknown device");
+    }
+}
@@ -3,11 +3,18 @@
 //! Per-row failures don't abort the batch; the summary carries the
 //! full failure list with 1-based row indexes.
 
+use std::collections::{BTreeSet, HashSet};
+
 use uuid::Uuid;
 
 use crate::{
-    domain::{BillingMode, CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+    auth::password::PasswordCost,
+    domain::{
+        normalize_email, validate_username, BillingMode, CreateMemberRequest,
+        DuplicateMemberField, MemberStatus, SignupField, UpdateMemberRequest,
+    },
     error::{AppError, Result},
+    service::signup_field_service::{check_answer, save_answers},
 };
 
 use super::{BulkImportSummary, ImportFailure, ImportRow, MemberService};
@@ -26,6 +33,11 @@ impl MemberService {
     /// until the member completes a password reset. This matches the
     /// `bulk-member-csv-import` spec: the operator activates members
     /// later, and the password-reset flow handles credentialing.
+    ///
+    /// Rows are validated first, then every survivor goes through
+    /// `MemberRepository::create_batch` in one call, so the Argon2 work
+    /// runs in parallel instead of once per row. Sentinel passwords are
+    /// still hashed at `PasswordCost::Secure` — these are real accounts.
     pub async fn bulk_import(
         &self,
         actor_id: Uuid,
@@ -38,9 +50,13 @@ impl MemberService {
             succeeded: 0,
             failed: 0,
             failures: Vec::new(),
+            warnings: Vec::new(),
             created_member_ids: Vec::new(),
         };
 
+        let signup_fields = self.settings_service.signup_fields().await;
+        let mut unknown_keys: BTreeSet<String> = BTreeSet::new();
+
         // Pre-load active membership types once so each row's slug
         // lookup is an in-memory hash hit, not a DB round trip. Inactive
         // types fail the row (they shouldn't be assignable on import any
@@ -51,9 +67,17 @@ impl MemberService {
             .await
             .unwrap_or_default();
 
+        // Rows that passed validation, waiting for the batch insert.
+        // The DB duplicate checks can't see rows that aren't inserted
+        // yet, so in-file duplicates are caught against these sets.
+        let mut accepted: Vec<AcceptedRow> = Vec::new();
+        let mut requests: Vec<CreateMemberRequest> = Vec::new();
+        let mut batch_emails: HashSet<String> = HashSet::new();
+        let mut batch_usernames: HashSet<String> = HashSet::new();
+
         for (idx, row) in rows.into_iter().enumerate() {
             let row_index = idx + 1;
-            let email = row.email.trim().to_string();
+            let email = normalize_email(&row.email);
             let username = row.username.trim().to_string();
             let full_name = row.full_name.trim().to_string();
             let slug = row.membership_type_slug.trim().to_string();
@@ -92,6 +116,15 @@ impl MemberService {
                 });
                 continue;
             }
+            if let Err(msg) = validate_username(&username) {
+                summary.failed += 1;
+                summary.failures.push(ImportFailure {
+                    row_index,
+                    email: Some(email.clone()),
+                    reason: msg.to_string(),
+                });
+                continue;
+            }
             if full_name.is_empty() {
                 summary.failed += 1;
                 summary.failures.push(ImportFailure {
@@ -143,8 +176,26 @@ impl MemberService {
             }
 
             // Duplicate detection — INSERT-only semantics, no upsert.
-            // We process rows sequentially so a prior row's insert is
-            // already visible to this check.
+            // Earlier rows in this file aren't in the DB yet, so check
+            // the batch sets as well as the table.
+            if batch_emails.contains(&email) {
+                summary.failed += 1;
+                summary.failures.push(ImportFailure {
+                    row_index,
+                    email: Some(email.clone()),
+                    reason: "Email already exists".to_string(),
+                });
+                continue;
+            }
+            if batch_usernames.contains(&username) {
+                summary.failed += 1;
+                summary.failures.push(ImportFailure {
+                    row_index,
+                    email: Some(email.clone()),
+                    reason: "Username already exists".to_string(),
+                });
+                continue;
+            }
             match self.member_repo.find_by_email(&email).await {
                 Ok(Some(_)) => {
                     summary.failed += 1;
@@ -222,26 +273,77 @@ impl MemberService {
                 email_verified_at: row.email_verified_at,
             };
 
-            let member = match self.member_repo.create(create_request).await {
+            let mut custom_fields: Vec<(SignupField, String)> = Vec::new();
+            for (key, value) in row.custom_fields {
+                let Some(field) = signup_fields.iter().find(|f| f.key == key) else {
+                    unknown_keys.insert(key);
+                    continue;
+                };
+                let value = value.trim().to_string();
+                if value.is_empty() {
+                    continue;
+                }
+                match check_answer(field, &value) {
+                    Ok(()) => custom_fields.push((field.clone(), value)),
+                    Err(message) => summary.warnings.push(format!(
+                        "Row {}: {}; value not imported",
+                        row_index, message
+                    )),
+                }
+            }
+
+            batch_emails.insert(email.clone());
+            batch_usernames.insert(username.clone());
+            requests.push(create_request);
+            accepted.push(AcceptedRow {
+                row_index,
+                email,
+                stripe_subscription_id,
+                status: row.status,
+                notes: row.notes,
+                discord_id: row.discord_id,
+                custom_fields,
+            });
+        }
+
+        summary.warnings.extend(unknown_keys.into_iter().map(|key| {
+            format!(
+                "Column custom:{} skipped: no signup field has that key",
+                key
+            )
+        }));
+
+        let results = self
+            .member_repo
+            .create_batch(requests, PasswordCost::Secure)
+            .await?;
+
+        for (row, created) in accepted.into_iter().zip(results) {
+            let AcceptedRow {
+                row_index,
+                email,
+                stripe_subscription_id,
+                status,
+                notes,
+                discord_id,
+                custom_fields,
+            } = row;
+
+            let member = match created {
                 Ok(m) => m,
                 Err(e) => {
-                    // Likely a UNIQUE constraint violation that slipped
-                    // past the pre-checks (e.g., case-insensitive
-                    // collisions), or a real DB error. Either way, the
-                    // row fails and the batch keeps going.
+                    // Likely a duplicate that slipped past the
+                    // pre-checks (a member created mid-import), or a
+                    // real DB error. Either way, the row fails and the
+                    // batch keeps going.
                     let reason = match &e {
-                        AppError::Database(db_err) => {
-                            let msg = db_err.to_string();
-                            if msg.contains("UNIQUE") && msg.contains("email") {
-                                "Email already exists".to_string()
-                            } else if msg.contains("UNIQUE") && msg.contains("username") {
-                                "Username already exists".to_string()
-                            } else if msg.contains("UNIQUE") {
-                                "Email or username already exists".to_string()
-                            } else {
-                                format!("Database error: {}", db_err)
-                            }
+                        AppError::DuplicateMember(DuplicateMemberField::Email) => {
+                            "Email already exists".to_string()
+                        }
+                        AppError::DuplicateMember(DuplicateMemberField::Username) => {
+                            "Username already exists".to_string()
                         }
+                        AppError::Database(db_err) => format!("Database error: {}", db_err),
                         other => other.to_string(),
                     };
                     summary.failed += 1;
@@ -277,11 +379,11 @@ impl MemberService {
             // Apply optional fields. Status default is Pending (the
             // repo's default); only call update when the row asked for
             // something different or carries notes/discord_id.
-            let status_override = row.status.filter(|s| *s != MemberStatus::Pending);
-            if status_override.is_some() || row.notes.is_some() {
+            let status_override = status.filter(|s| *s != MemberStatus::Pending);
+            if status_override.is_some() || notes.is_some() {
                 let update = UpdateMemberRequest {
                     status: status_override,
-                    notes: row.notes.as_ref().map(|s| s.trim().to_string()),
+                    notes: notes.as_ref().map(|s| s.trim().to_string()),
                     ..Default::default()
                 };
                 if let Err(e) = self.member_repo.update(member.id, update).await {
@@ -295,8 +397,7 @@ impl MemberService {
                     );
                 }
             }
-            if let Some(discord_id) = row
-                .discord_id
+            if let Some(discord_id) = discord_id
                 .as_ref()
                 .map(|s| s.trim())
                 .filter(|s| !s.is_empty())
@@ -314,6 +415,16 @@ impl MemberService {
                 }
             }
 
+            if !custom_fields.is_empty() {
+                if let Err(e) = save_answers(&self.db_pool, member.id, &custom_fields).await {
+                    tracing::error!(
+                        "Bulk import: created {} but custom fields failed to save: {}",
+                        member.id,
+                        e,
+                    );
+                }
+            }
+
             self.audit_service
                 .log(
                     Some(actor_id),
@@ -330,6 +441,10 @@ impl MemberService {
             summary.created_member_ids.push(member.id);
         }
 
+        // Validation failures were recorded in the first pass and insert
+        // failures in the second; report them in file order.
+        summary.failures.sort_by_key(|f| f.row_index);
+
         // Aggregate batch row, regardless of partial failures. Matches
         // the `audit-logging` capability's aggregate-entity convention
         // (entity_id = "*" for cross-entity batch operations).
@@ -352,3 +467,16 @@ impl MemberService {
         Ok(summary)
     }
 }
+
+/// A row that passed validation, plus the fields the post-insert
+/// follow-ups (billing mode, status, notes, Discord, custom fields)
+/// still need.
+struct AcceptedRow {
+    row_index: usize,
+    email: String,
+    stripe_subscription_id: Option<String>,
+    status: Option<MemberStatus>,
+    notes: Option<String>,
+    discord_id: Option<String>,
+    custom_fields: Vec<(SignupField, String)>,
+}
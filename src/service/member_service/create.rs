@@ -1,21 +1,29 @@
-//! Single-member creation and the shared `send_welcome_email` helper.
-//! `bulk_import` is large enough to live in its own sibling module
-//! ([`super::bulk_import`]); `send_welcome_email` is `pub(super)` so
-//! both this module and [`super::status::activate`] can reach it.
+//! Single-member creation, the admin "resend welcome" action, and the
+//! shared `send_welcome_email` helper. `bulk_import` is large enough to
+//! live in its own sibling module ([`super::bulk_import`]);
+//! `send_welcome_email` is `pub(super)` so both this module and
+//! [`super::status::activate`] can reach it.
 
 use uuid::Uuid;
 
 use crate::{
-    domain::{CreateMemberRequest, Member},
+    auth,
+    domain::{CreateMemberRequest, Member, MemberStatus},
     email::{
         self,
         templates::{WelcomeHtml, WelcomeText},
     },
-    error::Result,
+    error::{AppError, Result},
 };
 
 use super::MemberService;
 
+/// Resent welcome emails allowed per member per `RESEND_WELCOME_WINDOW_SECS`.
+/// Enough to cover a typo'd address fixed twice; not enough to turn
+/// the button into a mail cannon.
+const RESEND_WELCOME_LIMIT: i64 = 3;
+const RESEND_WELCOME_WINDOW_SECS: i64 = 24 * 60 * 60;
+
 impl MemberService {
     /// Create a new member via the admin form. Persists the row,
     /// sends the welcome email (log+swallow on failure), audits.
@@ -48,9 +56,121 @@ impl MemberService {
         Ok(member)
     }
 
+    /// Admin-triggered: send the welcome email again, for a member
+    /// whose first one went astray or who never finished onboarding
+    /// (typically Pending or freshly imported). Fresh links ride along:
+    /// a verification link if the email isn't verified yet, and a
+    /// set-password link, since imported members have no usable
+    /// password. Outstanding tokens of both kinds are invalidated
+    /// first so only the newest email works.
+    ///
+    /// Throttled per member by counting recent audit rows, so the
+    /// limit survives a restart. Like `resend_verification`, a failed
+    /// send is an error and only a successful one is audited.
+    pub async fn resend_welcome(&self, actor_id: Uuid, member_id: Uuid) -> Result<()> {
+        let member = self
+            .member_repo
+            .find_by_id(member_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+
+        if matches!(
+            member.status,
+            MemberStatus::Expired | MemberStatus::Suspended
+        ) {
+            return Err(AppError::BadRequest(format!(
+                "{} members can't be sent a welcome email",
+                member.status.as_str(),
+            )));
+        }
+
+        let recent = self
+            .audit_service
+            .count_recent(
+                "resend_welcome",
+                &member_id.to_string(),
+                RESEND_WELCOME_WINDOW_SECS,
+            )
+            .await?;
+        if recent >= RESEND_WELCOME_LIMIT {
+            return Err(AppError::TooManyRequests);
+        }
+
+        let base_url = self.base_url.trim_end_matches('/');
+        let verify_url = if member.email_verified() {
+            None
+        } else {
+            if let Err(e) = auth::email_tokens::invalidate_verification_tokens_for_member(
+                &self.db_pool,
+                member_id,
+            )
+            .await
+            {
+                tracing::warn!(
+                    "Resending welcome to {} but couldn't invalidate verification tokens: {}",
+                    member_id,
+                    e,
+                );
+            }
+            let created = auth::email_tokens::create_verification_token(
+                &self.db_pool,
+                member_id,
+                chrono::Duration::hours(24),
+            )
+            .await?;
+            Some(format!("{}/verify?token={}", base_url, created.token))
+        };
+
+        if let Err(e) = auth::email_tokens::invalidate_password_reset_tokens_for_member(
+            &self.db_pool,
+            member_id,
+        )
+        .await
+        {
+            tracing::warn!(
+                "Resending welcome to {} but couldn't invalidate password-reset tokens: {}",
+                member_id,
+                e,
+            );
+        }
+        let created = auth::email_tokens::create_password_reset_token(
+            &self.db_pool,
+            member_id,
+            chrono::Duration::hours(24),
+        )
+        .await?;
+        let set_password_url = format!("{}/reset-password?token={}", base_url, created.token);
+
+        self.send_welcome_email_with_links(&member, verify_url.as_deref(), Some(&set_password_url))
+            .await?;
+
+        self.audit_service
+            .log(
+                Some(actor_id),
+                "resend_welcome",
+                "member",
+                &member_id.to_string(),
+                None,
+                Some(&member.email),
+                None,
+            )
+            .await;
+
+        Ok(())
+    }
+
     /// Send the welcome email after admin-driven activate or create.
     /// Pulls org name + Discord invite from settings.
     pub(super) async fn send_welcome_email(&self, member: &Member) -> Result<()> {
+        self.send_welcome_email_with_links(member, None, None).await
+    }
+
+    async fn send_welcome_email_with_links(
+        &self,
+        member: &Member,
+        verify_url: Option<&str>,
+        set_password_url: Option<&str>,
+    ) -> Result<()> {
         let portal_url = format!("{}/portal/dashboard", self.base_url.trim_end_matches('/'),);
         let org_name = self
             .settings_service
@@ -75,12 +195,16 @@ impl MemberService {
             org_name: &org_name,
             portal_url: &portal_url,
             discord_invite: discord_invite.as_deref(),
+            verify_url,
+            set_password_url,
         };
         let text = WelcomeText {
             full_name: &member.full_name,
             org_name: &org_name,
             portal_url: &portal_url,
             discord_invite: discord_invite.as_deref(),
+            verify_url,
+            set_password_url,
         };
         let message = email::message_from_templates(
             member.email.clone(),
@@ -94,8 +218,13 @@ impl MemberService {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::super::test_helpers::*;
-    use crate::domain::{CreateMemberRequest, MemberStatus};
+    use crate::{
+        domain::{CreateMemberRequest, MemberStatus},
+        error::AppError,
+    };
 
     #[tokio::test]
     async fn create_audits_and_skips_activation_event() {
@@ -118,4 +247,70 @@ mod tests {
         assert_eq!(created.status, MemberStatus::Pending);
         assert_eq!(audit_count(&pool, "create_member", &created.id).await, 1);
     }
+
+    /// Refuses every message, like an unreachable SMTP server.
+    struct DeadSender;
+
+    #[async_trait::async_trait]
+    impl crate::email::EmailSender for DeadSender {
+        async fn send(&self, _message: &crate::email::EmailMessage) -> crate::error::Result<()> {
+            Err(AppError::External("SMTP connection refused".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn create_sends_the_welcome_email_and_survives_a_dead_mail_server() {
+        let pool = fresh_pool().await;
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let request = |n: &str| CreateMemberRequest {
+            email: format!("{}@example.com", n),
+            username: n.to_string(),
+            full_name: "New User".to_string(),
+            password: "secure_password123".to_string(),
+            membership_type_id: None,
+            ..Default::default()
+        };
+
+        let sender = Arc::new(RecordingSender::default());
+        let svc = make_service_with_sender(pool.clone(), sender.clone());
+        svc.create(actor.id, request("welcomed")).await.unwrap();
+        {
+            let sent = sender.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, "welcomed@example.com");
+            assert!(sent[0].subject.starts_with("Welcome to"));
+        }
+
+        let svc = make_service_with_sender(pool.clone(), Arc::new(DeadSender));
+        let created = svc.create(actor.id, request("unwelcomed")).await.unwrap();
+        assert_eq!(audit_count(&pool, "create_member", &created.id).await, 1);
+    }
+
+    #[tokio::test]
+    async fn resend_welcome_sends_fresh_links_audits_and_throttles() {
+        let pool = fresh_pool().await;
+        let sender = Arc::new(RecordingSender::default());
+        let svc = make_service_with_sender(pool.clone(), sender.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+
+        svc.resend_welcome(actor.id, target.id).await.unwrap();
+        {
+            let sent = sender.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, "tgt@example.com");
+            assert!(sent[0].subject.starts_with("Welcome to"));
+            // Unverified, so both links come along.
+            assert!(sent[0].text_body.contains("/verify?token="));
+            assert!(sent[0].text_body.contains("/reset-password?token="));
+        }
+        assert_eq!(audit_count(&pool, "resend_welcome", &target.id).await, 1);
+
+        svc.resend_welcome(actor.id, target.id).await.unwrap();
+        svc.resend_welcome(actor.id, target.id).await.unwrap();
+        let throttled = svc.resend_welcome(actor.id, target.id).await;
+        assert!(matches!(throttled, Err(AppError::TooManyRequests)));
+        assert_eq!(sender.sent.lock().unwrap().len(), 3);
+        assert_eq!(audit_count(&pool, "resend_welcome", &target.id).await, 3);
+    }
 }
@@ -1,21 +1,22 @@
-//! Dues management: `extend_dues` (add months) and `set_dues`
-//! (set to a specific date). Both revive Expired→Active via the
+//! Dues management: `extend_dues` (add months from the configured
+//! base date) and `set_dues` (set to a specific date). Both revive Expired→Active via the
 //! repo's revival helper, audit, and dispatch `MemberUpdated`.
 
 use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
 
 use crate::{
-    domain::Member,
+    domain::{DuesExtensionBase, Member},
     error::{AppError, Result},
 };
 
 use super::MemberService;
 
 impl MemberService {
-    /// Add `months` to the member's `dues_paid_until` (or to "now" if
-    /// dues have already lapsed), revive Expired→Active, audit, and
-    /// dispatch `MemberUpdated`. Validates `1..=120` — negative or
+    /// Add `months` to the member's dues, revive Expired→Active,
+    /// audit, and dispatch `MemberUpdated`. `base` picks the date the
+    /// months count from; `None` uses the club's
+    /// `billing.dues_extension_base`. Validates `1..=120` — negative or
     /// absurd values would either wrap around as `u32` or dilute the
     /// audit log with junk entries.
     pub async fn extend_dues(
@@ -23,6 +24,7 @@ impl MemberService {
         actor_id: Uuid,
         member_id: Uuid,
         months: i32,
+        base: Option<DuesExtensionBase>,
     ) -> Result<Member> {
         use chrono::Months;
 
@@ -38,11 +40,11 @@ impl MemberService {
             .await?
             .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
 
-        let now = Utc::now();
-        let base_date = old_member
-            .dues_paid_until
-            .filter(|d| *d > now)
-            .unwrap_or(now);
+        let base = match base {
+            Some(b) => b,
+            None => self.settings_service.dues_extension_base().await,
+        };
+        let base_date = base.base_date(old_member.dues_paid_until, Utc::now());
 
         let new_dues_date = base_date
             .checked_add_months(Months::new(months as u32))
@@ -60,8 +62,9 @@ impl MemberService {
                 &member_id.to_string(),
                 None,
                 Some(&format!(
-                    "+{} months → {}",
+                    "+{} months from {} → {}",
                     months,
+                    base.as_str(),
                     new_dues_date.format("%Y-%m-%d")
                 )),
                 None,
@@ -111,8 +114,33 @@ impl MemberService {
 #[cfg(test)]
 mod tests {
     use super::super::test_helpers::*;
-    use crate::error::AppError;
-    use chrono::NaiveDate;
+    use crate::{domain::DuesExtensionBase, error::AppError};
+    use chrono::{Duration, NaiveDate, Utc};
+    use uuid::Uuid;
+
+    /// Set dues to `days_from_now` (negative = lapsed) and return the
+    /// resulting expiry after a one-month extension under `base`.
+    async fn extend_one_month(days_from_now: i64, base: Option<DuesExtensionBase>) -> Duration {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+        set_expiry(&svc, target.id, days_from_now).await;
+
+        let result = svc.extend_dues(actor.id, target.id, 1, base).await.unwrap();
+        result.dues_paid_until.unwrap() - Utc::now()
+    }
+
+    async fn set_expiry(svc: &super::MemberService, id: Uuid, days_from_now: i64) {
+        svc.member_repo
+            .set_dues_paid_until_with_revival(id, Utc::now() + Duration::days(days_from_now))
+            .await
+            .unwrap();
+    }
+
+    fn about(actual: Duration, days: i64) -> bool {
+        (actual - Duration::days(days)).num_hours().abs() <= 24 * 3
+    }
 
     #[tokio::test]
     async fn extend_dues_validates_range() {
@@ -121,12 +149,12 @@ mod tests {
         let actor = make_member(&pool, "admin@example.com", "admin").await;
         let target = make_member(&pool, "tgt@example.com", "target").await;
 
-        let bad = svc.extend_dues(actor.id, target.id, 0).await;
+        let bad = svc.extend_dues(actor.id, target.id, 0, None).await;
         assert!(matches!(bad, Err(AppError::BadRequest(_))));
-        let bad_high = svc.extend_dues(actor.id, target.id, 121).await;
+        let bad_high = svc.extend_dues(actor.id, target.id, 121, None).await;
         assert!(matches!(bad_high, Err(AppError::BadRequest(_))));
 
-        let ok = svc.extend_dues(actor.id, target.id, 12).await.unwrap();
+        let ok = svc.extend_dues(actor.id, target.id, 12, None).await.unwrap();
         assert!(ok.dues_paid_until.is_some());
         assert_eq!(audit_count(&pool, "extend_dues", &target.id).await, 1);
     }
@@ -145,4 +173,49 @@ mod tests {
         assert_eq!(dpu.format("%Y-%m-%d").to_string(), "2027-01-01");
         assert_eq!(audit_count(&pool, "set_dues", &target.id).await, 1);
     }
+
+    #[tokio::test]
+    async fn later_of_now_or_expiry_stacks_current_and_restarts_lapsed() {
+        let base = Some(DuesExtensionBase::LaterOfNowOrExpiry);
+        assert!(about(extend_one_month(10, base).await, 40));
+        assert!(about(extend_one_month(-10, base).await, 30));
+    }
+
+    #[tokio::test]
+    async fn from_now_ignores_remaining_time() {
+        let base = Some(DuesExtensionBase::FromNow);
+        assert!(about(extend_one_month(10, base).await, 30));
+        assert!(about(extend_one_month(-10, base).await, 30));
+    }
+
+    #[tokio::test]
+    async fn from_expiry_backfills_the_lapsed_gap() {
+        let base = Some(DuesExtensionBase::FromExpiry);
+        assert!(about(extend_one_month(10, base).await, 40));
+        assert!(about(extend_one_month(-10, base).await, 20));
+    }
+
+    #[tokio::test]
+    async fn club_default_applies_when_no_override() {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+        set_expiry(&svc, target.id, 10).await;
+
+        svc.settings_service
+            .update_setting(
+                "billing.dues_extension_base",
+                crate::domain::UpdateSettingRequest {
+                    value: "now".to_string(),
+                    reason: None,
+                },
+                actor.id,
+            )
+            .await
+            .unwrap();
+
+        let result = svc.extend_dues(actor.id, target.id, 1, None).await.unwrap();
+        assert!(about(result.dues_paid_until.unwrap() - Utc::now(), 30));
+    }
 }
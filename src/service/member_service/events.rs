@@ -1,14 +1,16 @@
-//! Shared integration-event dispatch helper. Re-fetches the member
-//! after a mutation and fires `MemberUpdated { old, new }` to
-//! integrations, returning the new member. Called from `dues.rs`,
+//! Integration-event dispatch for members. `dispatch_member_updated`
+//! re-fetches the member after a mutation and fires `MemberUpdated {
+//! old, new }`, returning the new member; called from `dues.rs`,
 //! `updates.rs`, and `status.rs` (the `expire_now` path).
+//! `resync_integrations` re-pushes a member's current state on an
+//! admin's request without changing anything.
 
 use uuid::Uuid;
 
 use crate::{
-    domain::Member,
+    domain::{Member, MemberStatus},
     error::{AppError, Result},
-    integrations::IntegrationEvent,
+    integrations::{IntegrationEvent, ResyncOutcome, ResyncResult},
 };
 
 use super::MemberService;
@@ -39,12 +41,72 @@ impl MemberService {
                     member_id,
                 ))
             })?;
+        // Integrations see effective statuses, so renewing a member
+        // whose lapse was only derived still restores their roles.
+        let old = self.settings_service.with_effective_status(old).await;
+        let effective_new = self.settings_service.with_effective_status(new.clone()).await;
         self.integration_manager
             .handle_event(IntegrationEvent::MemberUpdated {
                 old,
-                new: new.clone(),
+                new: effective_new,
             })
             .await;
         Ok(new)
     }
+
+    /// Re-push `member_id`'s current (effective) status to every
+    /// integration, or only the one named `only`, for when one was
+    /// down or a role drifted. Nothing about the member changes.
+    /// Members in good standing go out as `MemberActivated`, everyone
+    /// else as `MemberExpired`; both make Discord and Unifi sync to
+    /// the status the event carries.
+    pub async fn resync_integrations(
+        &self,
+        actor_id: Uuid,
+        member_id: Uuid,
+        only: Option<&str>,
+    ) -> Result<Vec<ResyncResult>> {
+        let member = self
+            .member_repo
+            .find_by_id(member_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+        let member = self.settings_service.with_effective_status(member).await;
+
+        let event = match member.status {
+            MemberStatus::Active | MemberStatus::Honorary => {
+                IntegrationEvent::MemberActivated(member.clone())
+            }
+            MemberStatus::Pending | MemberStatus::Expired | MemberStatus::Suspended => {
+                IntegrationEvent::MemberExpired(member.clone())
+            }
+        };
+        let results = self.integration_manager.resync(event, only).await;
+
+        let summary = results
+            .iter()
+            .map(|r| {
+                let outcome = match &r.outcome {
+                    ResyncOutcome::Sent => "sent",
+                    ResyncOutcome::Failed(_) => "failed",
+                    ResyncOutcome::NotSubscribed => "not subscribed",
+                };
+                format!("{}: {}", r.integration, outcome)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.audit_service
+            .log(
+                Some(actor_id),
+                "resync_member_integrations",
+                "member",
+                &member_id.to_string(),
+                None,
+                Some(&summary),
+                None,
+            )
+            .await;
+
+        Ok(results)
+    }
 }
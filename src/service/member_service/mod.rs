@@ -14,10 +14,11 @@
 //! file exceeds ~400 lines. When adding a new admin action, place its
 //! method in the submodule matching its concern:
 //!
-//! - [`status`] — `activate`, `suspend`, `expire_now`
+//! - [`status`] — `activate`, `suspend`, `expire_now`, `grant_honorary`,
+//!   `revert_expired_honorary`
 //! - [`dues`] — `extend_dues`, `set_dues`
 //! - [`updates`] — `update`, `update_discord_id`, `resend_verification`
-//! - [`create`] — `create`, `send_welcome_email`
+//! - [`create`] — `create`, `resend_welcome`, `send_welcome_email`
 //! - [`bulk_import`] — `bulk_import` (extracted for size)
 //! - [`queries`] — `audit_export`, `membership_type_name`
 //! - [`events`] — `dispatch_member_updated` (private helper)
@@ -74,6 +75,10 @@ pub struct ImportRow {
     pub stripe_subscription_id: Option<String>,
     pub joined_at: Option<DateTime<Utc>>,
     pub email_verified_at: Option<DateTime<Utc>>,
+    /// `(key, value)` for each non-empty `custom:<key>` cell, in column
+    /// order. Checked against the configured signup fields on import;
+    /// see `bulk_import`.
+    pub custom_fields: Vec<(String, String)>,
     /// Sentinel: parser sets this when a cell couldn't be coerced
     /// (e.g., malformed timestamp). `bulk_import` checks this first
     /// and fails the row with the carried reason so the row_index in
@@ -98,6 +103,10 @@ pub struct BulkImportSummary {
     pub succeeded: u32,
     pub failed: u32,
     pub failures: Vec<ImportFailure>,
+    /// Data that was dropped without failing its row: custom-field
+    /// columns with no matching signup field, and values a field's
+    /// rules reject.
+    pub warnings: Vec<String>,
     pub created_member_ids: Vec<Uuid>,
 }
 
@@ -1,12 +1,14 @@
-//! Member status transitions: `activate`, `suspend`, `expire_now`.
+//! Member status transitions: `activate`, `suspend`, `expire_now`,
+//! `grant_honorary`, and the `revert_expired_honorary` sweep.
 //! Each method handles the full side-effect chain (repo update →
 //! session invalidation → audit → integration dispatch → email).
 
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::{
     domain::{Member, MemberStatus, UpdateMemberRequest},
-    error::Result,
+    error::{AppError, Result},
     integrations::IntegrationEvent,
 };
 
@@ -20,6 +22,34 @@ impl MemberService {
     /// logged but don't fail the call — the primary repo mutation
     /// already succeeded.
     pub async fn activate(&self, actor_id: Uuid, member_id: Uuid) -> Result<Member> {
+        self.activate_with_audit(Some(actor_id), "activate_member", member_id)
+            .await
+    }
+
+    /// Activate a `Pending` member whose signup payment just cleared.
+    /// Any other status is left alone and returns `None`, so a renewal
+    /// payment can't quietly lift a suspension. Audited without an
+    /// actor: the payment, not an admin, made the call.
+    pub async fn activate_paid_signup(&self, member_id: Uuid) -> Result<Option<Member>> {
+        let member = self
+            .member_repo
+            .find_by_id(member_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+        if member.status != MemberStatus::Pending {
+            return Ok(None);
+        }
+        self.activate_with_audit(None, "activate_member_on_payment", member_id)
+            .await
+            .map(Some)
+    }
+
+    async fn activate_with_audit(
+        &self,
+        actor_id: Option<Uuid>,
+        action: &str,
+        member_id: Uuid,
+    ) -> Result<Member> {
         let update = UpdateMemberRequest {
             status: Some(MemberStatus::Active),
             ..Default::default()
@@ -38,8 +68,8 @@ impl MemberService {
 
         self.audit_service
             .log(
-                Some(actor_id),
-                "activate_member",
+                actor_id,
+                action,
                 "member",
                 &member_id.to_string(),
                 None,
@@ -123,8 +153,6 @@ impl MemberService {
     /// failure is logged but not fatal — middleware re-validates per
     /// request and bounces them anyway.
     pub async fn expire_now(&self, actor_id: Uuid, member_id: Uuid) -> Result<Member> {
-        use crate::error::AppError;
-
         let old_member = self
             .member_repo
             .find_by_id(member_id)
@@ -155,6 +183,114 @@ impl MemberService {
 
         self.dispatch_member_updated(member_id, old_member).await
     }
+
+    /// Make a member `Honorary` and dues-exempt. `reason` is required
+    /// and lands in the audit row next to the grantor (`actor_id`), so
+    /// "why is this person not paying?" has an answer later. `until`
+    /// makes the grant time-limited; the hourly sweep
+    /// (`revert_expired_honorary`) undoes it once that passes.
+    pub async fn grant_honorary(
+        &self,
+        actor_id: Uuid,
+        member_id: Uuid,
+        reason: &str,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Member> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::BadRequest(
+                "A reason is required to grant honorary status".to_string(),
+            ));
+        }
+        if until.is_some_and(|t| t <= Utc::now()) {
+            return Err(AppError::BadRequest(
+                "Honorary expiry must be in the future".to_string(),
+            ));
+        }
+
+        let old_member = self
+            .member_repo
+            .find_by_id(member_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Member not found".to_string()))?;
+
+        self.member_repo.grant_honorary(member_id, until).await?;
+
+        if let Err(e) = self.auth_service.invalidate_all_sessions(member_id).await {
+            tracing::error!(
+                "Granted honorary status to member {} but failed to invalidate sessions: {}",
+                member_id,
+                e,
+            );
+        }
+
+        let new_value = serde_json::json!({
+            "reason": reason,
+            "until": until.map(|t| t.to_rfc3339()),
+        })
+        .to_string();
+        self.audit_service
+            .log(
+                Some(actor_id),
+                "grant_honorary",
+                "member",
+                &member_id.to_string(),
+                Some(old_member.status.as_str()),
+                Some(&new_value),
+                None,
+            )
+            .await;
+
+        self.dispatch_member_updated(member_id, old_member).await
+    }
+
+    /// Revert every time-limited honorary grant whose expiry has
+    /// passed. Called from the hourly cleanup; returns how many
+    /// members were reverted. Per-member failures are logged and
+    /// skipped so one bad row doesn't stall the rest.
+    pub async fn revert_expired_honorary(&self) -> Result<u32> {
+        let expired = self.member_repo.list_expired_honorary(Utc::now()).await?;
+        let mut reverted = 0;
+
+        for old_member in expired {
+            let member_id = old_member.id;
+            match self.member_repo.revert_honorary(member_id).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::error!("Failed to revert honorary grant for {}: {}", member_id, e);
+                    continue;
+                }
+            }
+
+            if let Err(e) = self.auth_service.invalidate_all_sessions(member_id).await {
+                tracing::error!(
+                    "Reverted honorary grant for {} but failed to invalidate sessions: {}",
+                    member_id,
+                    e,
+                );
+            }
+
+            self.audit_service
+                .log(
+                    None,
+                    "honorary_expired",
+                    "member",
+                    &member_id.to_string(),
+                    Some(old_member.status.as_str()),
+                    None,
+                    None,
+                )
+                .await;
+
+            if let Err(e) = self.dispatch_member_updated(member_id, old_member).await {
+                tracing::error!("Honorary revert event for {} failed: {}", member_id, e);
+            }
+            reverted += 1;
+        }
+
+        Ok(reverted)
+    }
 }
 
 #[cfg(test)]
@@ -253,7 +389,7 @@ mod tests {
         let target = make_member(&pool, "tgt@example.com", "target").await;
         // Activate + extend so expire_now has something to flip.
         svc.activate(actor.id, target.id).await.unwrap();
-        svc.extend_dues(actor.id, target.id, 1).await.unwrap();
+        svc.extend_dues(actor.id, target.id, 1, None).await.unwrap();
         let (_s, _t) = svc
             .auth_service
             .create_session(target.id, 24)
@@ -273,4 +409,77 @@ mod tests {
         assert_eq!(sessions_after.0, 0);
         assert_eq!(audit_count(&pool, "expire_member_now", &target.id).await, 1);
     }
+
+    #[tokio::test]
+    async fn grant_honorary_makes_member_dues_exempt_and_logs_grantor() {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+        assert!(!target.bypass_dues);
+
+        let result = svc
+            .grant_honorary(actor.id, target.id, "Keynote speaker 2025", None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, MemberStatus::Honorary);
+        assert!(result.bypass_dues);
+        assert!(result.honorary_until.is_none());
+
+        let (actor_id, new_value): (Option<String>, Option<String>) = sqlx::query_as(
+            "SELECT actor_id, new_value FROM audit_logs \
+             WHERE action = 'grant_honorary' AND entity_id = ?",
+        )
+        .bind(target.id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(actor_id, Some(actor.id.to_string()));
+        assert!(new_value.unwrap().contains("Keynote speaker 2025"));
+    }
+
+    #[tokio::test]
+    async fn grant_honorary_requires_reason_and_future_expiry() {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+
+        assert!(svc.grant_honorary(actor.id, target.id, "  ", None).await.is_err());
+        let past = chrono::Utc::now() - chrono::Duration::days(1);
+        assert!(svc
+            .grant_honorary(actor.id, target.id, "Sponsor", Some(past))
+            .await
+            .is_err());
+        assert_eq!(audit_count(&pool, "grant_honorary", &target.id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn expired_honorary_grant_is_reverted() {
+        let pool = fresh_pool().await;
+        let svc = make_service(pool.clone());
+        let actor = make_member(&pool, "admin@example.com", "admin").await;
+        let target = make_member(&pool, "tgt@example.com", "target").await;
+
+        let until = chrono::Utc::now() + chrono::Duration::days(30);
+        svc.grant_honorary(actor.id, target.id, "Sponsor", Some(until))
+            .await
+            .unwrap();
+        assert_eq!(svc.revert_expired_honorary().await.unwrap(), 0);
+
+        // Pretend the month went by.
+        sqlx::query("UPDATE members SET honorary_until = datetime('now', '-1 hour') WHERE id = ?")
+            .bind(target.id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(svc.revert_expired_honorary().await.unwrap(), 1);
+
+        let member = svc.member_repo.find_by_id(target.id).await.unwrap().unwrap();
+        assert_eq!(member.status, MemberStatus::Expired);
+        assert!(!member.bypass_dues);
+        assert!(member.honorary_until.is_none());
+        assert_eq!(audit_count(&pool, "honorary_expired", &target.id).await, 1);
+    }
 }
@@ -4,15 +4,17 @@
 
 #![cfg(test)]
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use async_trait::async_trait;
 use sqlx::{Executor, SqlitePool};
 use uuid::Uuid;
 
 use crate::{
     auth::{AuthService, SecretCrypto},
     domain::{CreateMemberRequest, Member},
-    email::{EmailSender, LogSender},
+    email::{EmailMessage, EmailSender, LogSender},
+    error::Result,
     integrations::IntegrationManager,
     repository::{MemberRepository, SqliteMemberRepository, SqliteMembershipTypeRepository},
     service::{
@@ -40,16 +42,40 @@ pub async fn fresh_pool() -> SqlitePool {
     pool
 }
 
+/// Keeps every message it's handed so a test can assert on what the
+/// service sent.
+#[derive(Default)]
+pub struct RecordingSender {
+    pub sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for RecordingSender {
+    async fn send(&self, message: &EmailMessage) -> Result<()> {
+        self.sent.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
 pub fn make_service(pool: SqlitePool) -> MemberService {
+    make_service_with_sender(
+        pool,
+        Arc::new(LogSender::new(
+            "test@example.com".to_string(),
+            "Test".to_string(),
+        )),
+    )
+}
+
+pub fn make_service_with_sender(
+    pool: SqlitePool,
+    email_sender: Arc<dyn EmailSender>,
+) -> MemberService {
     let member_repo: Arc<dyn MemberRepository> =
         Arc::new(SqliteMemberRepository::new(pool.clone()));
     let auth_service = Arc::new(AuthService::new(pool.clone(), "test-secret".to_string()));
     let audit_service = Arc::new(AuditService::new(pool.clone()));
     let integration_manager = Arc::new(IntegrationManager::new());
-    let email_sender: Arc<dyn EmailSender> = Arc::new(LogSender::new(
-        "test@example.com".to_string(),
-        "Test".to_string(),
-    ));
     let membership_type_repo = Arc::new(SqliteMembershipTypeRepository::new(pool.clone()));
     let membership_type_service = Arc::new(MembershipTypeService::new(membership_type_repo));
     let crypto = Arc::new(SecretCrypto::new("test-secret-please-ignore"));
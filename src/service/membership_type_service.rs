@@ -1,16 +1,25 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use uuid::Uuid;
 
 use crate::{
     domain::{
         BillingPeriod, CreateMembershipTypeRequest, MembershipTypeConfig,
-        UpdateMembershipTypeRequest,
+        UpdateMembershipTypeRequest, MAX_PAYMENT_CENTS,
     },
     error::{AppError, Result},
     repository::MembershipTypeRepository,
-    service::configurable_types::validate_hex_color_for_request,
+    service::configurable_types::{validate_hex_color_for_request, SeedReport},
 };
 
+/// (name, slug, description, color, fee_cents, billing_period) for the
+/// membership types a fresh install starts with. Same rows as
+/// `001_initial_schema.sql`.
+const DEFAULT_MEMBERSHIP_TYPES: &[(&str, &str, &str, &str, i64, &str)] = &[
+    ("Member", "member", "Standard membership", "#2196F3", 500, "monthly"),
+    ("Associate", "associate", "Associate membership", "#9C27B0", 10000, "monthly"),
+    ("Life Member", "life-member", "Lifetime membership", "#FF9800", 1000000, "lifetime"),
+];
+
 pub struct MembershipTypeService {
     repo: Arc<dyn MembershipTypeRepository>,
 }
@@ -35,8 +44,13 @@ impl MembershipTypeService {
         self.repo.find_by_slug(slug).await
     }
 
-    /// Create a new membership type
-    pub async fn create(&self, request: CreateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
+    /// Create a new membership type. `actor` is the admin doing it,
+    /// or `None` for seeded types.
+    pub async fn create(
+        &self,
+        actor: Option<Uuid>,
+        request: CreateMembershipTypeRequest,
+    ) -> Result<MembershipTypeConfig> {
         validate_hex_color_for_request(request.color.as_deref())?;
 
         // Validate billing period
@@ -47,12 +61,8 @@ impl MembershipTypeService {
             )));
         }
 
-        // Validate fee is non-negative
-        if request.fee_cents < 0 {
-            return Err(AppError::BadRequest(
-                "Fee cannot be negative".to_string()
-            ));
-        }
+        check_fee(request.fee_cents)?;
+        check_max_members(request.max_members)?;
 
         // Check for duplicate slug if provided
         if let Some(ref slug) = request.slug {
@@ -64,11 +74,17 @@ impl MembershipTypeService {
             }
         }
 
-        self.repo.create(request).await
+        self.repo.create(actor, request).await
     }
 
-    /// Update an existing membership type
-    pub async fn update(&self, id: Uuid, request: UpdateMembershipTypeRequest) -> Result<MembershipTypeConfig> {
+    /// Update an existing membership type, recording `actor` as its
+    /// last editor.
+    pub async fn update(
+        &self,
+        actor: Option<Uuid>,
+        id: Uuid,
+        request: UpdateMembershipTypeRequest,
+    ) -> Result<MembershipTypeConfig> {
         validate_hex_color_for_request(request.color.as_deref())?;
 
         // Validate billing period if provided
@@ -81,16 +97,65 @@ impl MembershipTypeService {
             }
         }
 
-        // Validate fee is non-negative if provided
         if let Some(fee_cents) = request.fee_cents {
-            if fee_cents < 0 {
-                return Err(AppError::BadRequest(
-                    "Fee cannot be negative".to_string()
-                ));
+            check_fee(fee_cents)?;
+        }
+        if let Some(max_members) = request.max_members {
+            check_max_members(max_members)?;
+        }
+
+        self.repo.update(actor, id, request).await
+    }
+
+    /// Create any default membership type whose slug doesn't exist
+    /// yet; existing slugs are skipped untouched. Safe to run
+    /// repeatedly.
+    pub async fn seed_defaults(&self) -> Result<SeedReport> {
+        let mut report = SeedReport::default();
+        for (name, slug, description, color, fee_cents, billing_period) in DEFAULT_MEMBERSHIP_TYPES {
+            if self.get_by_slug(slug).await?.is_some() {
+                report.skipped += 1;
+                continue;
             }
+            let created = self
+                .create(None, CreateMembershipTypeRequest {
+                    name: name.to_string(),
+                    slug: Some(slug.to_string()),
+                    description: Some(description.to_string()),
+                    color: Some(color.to_string()),
+                    icon: None,
+                    fee_cents: *fee_cents,
+                    billing_period: billing_period.to_string(),
+                    max_members: None,
+                })
+                .await;
+            report.record(created)?;
         }
+        Ok(report)
+    }
 
-        self.repo.update(id, request).await
+    /// Member count per type, from one query. Types nobody holds are
+    /// absent.
+    pub async fn usage_counts(&self) -> Result<HashMap<Uuid, i64>> {
+        self.repo.count_usage_by_type().await
+    }
+
+    /// Refuse with `BadRequest` when `membership_type` has reached its
+    /// cap. Counts every member holding the type, whatever their status.
+    /// Check-then-insert, so two signups racing for the last place can
+    /// both get in; the cap is a soft limit.
+    pub async fn ensure_capacity(&self, membership_type: &MembershipTypeConfig) -> Result<()> {
+        if membership_type.max_members.is_none() {
+            return Ok(());
+        }
+        let usage = self.repo.count_usage(membership_type.id).await?;
+        if membership_type.is_full(usage) {
+            return Err(AppError::BadRequest(format!(
+                "{} membership is full",
+                membership_type.name,
+            )));
+        }
+        Ok(())
     }
 
     /// Delete a membership type
@@ -111,3 +176,29 @@ impl MembershipTypeService {
         self.repo.delete(id).await
     }
 }
+
+/// A fee has to be chargeable: not negative, and within the same cap
+/// as any single payment so checkout never builds an amount Stripe or
+/// the payment recorder would refuse.
+fn check_fee(fee_cents: i64) -> Result<()> {
+    if fee_cents < 0 {
+        return Err(AppError::BadRequest("Fee cannot be negative".to_string()));
+    }
+    if fee_cents > MAX_PAYMENT_CENTS {
+        return Err(AppError::BadRequest(format!(
+            "Fee exceeds the ${} cap on a single payment",
+            MAX_PAYMENT_CENTS / 100,
+        )));
+    }
+    Ok(())
+}
+
+/// A cap of zero would close the type; deactivating it does that.
+fn check_max_members(max_members: Option<i64>) -> Result<()> {
+    if max_members.is_some_and(|max| max < 1) {
+        return Err(AppError::BadRequest(
+            "Member cap must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
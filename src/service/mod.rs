@@ -1,14 +1,31 @@
+pub mod admin_notification_service;
 pub mod announcement_admin_service;
+pub mod announcement_comment_service;
 pub mod audit_service;
+pub mod backup_service;
 pub mod billing_service;
+pub mod celebration_service;
 pub mod configurable_types;
+pub mod contact_service;
+pub mod data_check_service;
+pub mod directory_service;
+pub mod emergency_contact_service;
 pub mod basic_type_service;
 pub mod event_admin_service;
+pub mod event_host_service;
+pub mod event_invite_service;
+pub mod event_proposal_service;
+pub mod integration_log_service;
+pub mod integration_outbox_service;
+pub mod landing_page_service;
+pub mod login_history_service;
 pub mod member_service;
 pub mod payment_admin_service;
 pub mod payment_service;
+pub mod readiness_service;
 pub mod recurring_event_service;
 pub mod settings_service;
+pub mod signup_field_service;
 pub mod membership_type_service;
 
 use std::sync::Arc;
@@ -19,14 +36,29 @@ use crate::integrations::IntegrationManager;
 use crate::auth::{AuthService, CsrfService, PendingLoginService, TotpService};
 use crate::domain::BasicTypeKind;
 use crate::email::EmailSender;
-use crate::payments::StripeClient;
+use crate::payments::{StripeClient, WebhookMetrics};
+use admin_notification_service::AdminNotificationService;
 use announcement_admin_service::AnnouncementAdminService;
+use announcement_comment_service::AnnouncementCommentService;
 use audit_service::AuditService;
+use celebration_service::CelebrationService;
+use contact_service::ContactService;
+use data_check_service::DataCheckService;
+use landing_page_service::LandingPageService;
+use directory_service::DirectoryService;
+use emergency_contact_service::EmergencyContactService;
 use event_admin_service::EventAdminService;
+use event_host_service::EventHostService;
+use event_invite_service::EventInviteService;
+use event_proposal_service::EventProposalService;
+use integration_log_service::IntegrationLogService;
+use integration_outbox_service::IntegrationOutboxService;
+use login_history_service::LoginHistoryService;
 use member_service::MemberService;
 use payment_admin_service::PaymentAdminService;
 use payment_service::PaymentService;
 use settings_service::SettingsService;
+use signup_field_service::SignupFieldService;
 use basic_type_service::BasicTypeService;
 use membership_type_service::MembershipTypeService;
 use recurring_event_service::RecurringEventService;
@@ -44,6 +76,9 @@ pub struct ServiceContext {
     pub basic_type_repo: Arc<dyn BasicTypeRepository>,
     pub membership_type_repo: Arc<dyn MembershipTypeRepository>,
     pub processed_events_repo: Arc<dyn ProcessedEventsRepository>,
+    /// Webhook dedupe / prune counters, shared by the dispatcher and
+    /// the cleanup task.
+    pub webhook_metrics: Arc<WebhookMetrics>,
     pub integration_manager: Arc<IntegrationManager>,
     pub auth_service: Arc<AuthService>,
     pub csrf_service: Arc<CsrfService>,
@@ -55,11 +90,26 @@ pub struct ServiceContext {
     pub membership_type_service: Arc<MembershipTypeService>,
     pub email_sender: Arc<dyn EmailSender>,
     pub audit_service: Arc<AuditService>,
+    pub integration_log_service: Arc<IntegrationLogService>,
+    pub integration_outbox_service: Arc<IntegrationOutboxService>,
+    pub login_history_service: Arc<LoginHistoryService>,
+    pub directory_service: Arc<DirectoryService>,
+    pub emergency_contact_service: Arc<EmergencyContactService>,
+    pub signup_field_service: Arc<SignupFieldService>,
     pub payment_service: Arc<PaymentService>,
     pub member_service: Arc<MemberService>,
     pub event_admin_service: Arc<EventAdminService>,
+    pub event_host_service: Arc<EventHostService>,
+    pub event_invite_service: Arc<EventInviteService>,
+    pub event_proposal_service: Arc<EventProposalService>,
     pub announcement_admin_service: Arc<AnnouncementAdminService>,
+    pub announcement_comment_service: Arc<AnnouncementCommentService>,
     pub payment_admin_service: Arc<PaymentAdminService>,
+    pub admin_notification_service: Arc<AdminNotificationService>,
+    pub celebration_service: Arc<CelebrationService>,
+    pub landing_page_service: Arc<LandingPageService>,
+    pub contact_service: Arc<ContactService>,
+    pub data_check_service: Arc<DataCheckService>,
     pub db_pool: SqlitePool,
 }
 
@@ -89,6 +139,11 @@ impl ServiceContext {
             db_pool.clone(),
         ));
         let audit_service = Arc::new(AuditService::new(db_pool.clone()));
+        let integration_log_service = Arc::new(IntegrationLogService::new(db_pool.clone()));
+        integration_manager.attach_log(integration_log_service.clone());
+        integration_manager.attach_settings(settings_service.clone());
+        let integration_outbox_service = Arc::new(IntegrationOutboxService::new(db_pool.clone()));
+        integration_manager.attach_outbox(integration_outbox_service.clone());
 
         // Create type repositories. One basic-type repo serves both event
         // and announcement kinds; membership types stay separate.
@@ -122,6 +177,7 @@ impl ServiceContext {
             member_repo.clone(),
             donation_campaign_repo.clone(),
             audit_service.clone(),
+            integration_manager.clone(),
         ));
 
         let member_service = Arc::new(MemberService::new(
@@ -133,7 +189,14 @@ impl ServiceContext {
             membership_type_service.clone(),
             settings_service.clone(),
             db_pool.clone(),
-            base_url,
+            base_url.clone(),
+        ));
+
+        let event_invite_service = Arc::new(EventInviteService::new(
+            db_pool.clone(),
+            email_sender.clone(),
+            settings_service.clone(),
+            base_url.clone(),
         ));
 
         let event_admin_service = Arc::new(EventAdminService::new(
@@ -142,6 +205,67 @@ impl ServiceContext {
             recurring_event_service.clone(),
             audit_service.clone(),
             integration_manager.clone(),
+            event_invite_service.clone(),
+        ));
+
+        let event_host_service = Arc::new(EventHostService::new(
+            db_pool.clone(),
+            event_repo.clone(),
+            member_repo.clone(),
+            event_admin_service.clone(),
+            audit_service.clone(),
+        ));
+
+        let login_history_service = Arc::new(LoginHistoryService::new(
+            db_pool.clone(),
+            email_sender.clone(),
+            settings_service.clone(),
+            base_url.clone(),
+        ));
+
+        let directory_service = Arc::new(DirectoryService::new(db_pool.clone()));
+
+        let signup_field_service = Arc::new(SignupFieldService::new(
+            db_pool.clone(),
+            settings_service.clone(),
+        ));
+
+        let admin_notification_service = Arc::new(AdminNotificationService::new(
+            db_pool.clone(),
+            settings_service.clone(),
+            email_sender.clone(),
+            base_url.clone(),
+        ));
+
+        let landing_page_service = Arc::new(LandingPageService::new(
+            db_pool.clone(),
+            settings_service.clone(),
+        ));
+
+        let celebration_service = Arc::new(CelebrationService::new(
+            db_pool.clone(),
+            settings_service.clone(),
+            email_sender.clone(),
+            integration_manager.clone(),
+            base_url.clone(),
+        ));
+
+        let contact_service = Arc::new(ContactService::new(
+            db_pool.clone(),
+            settings_service.clone(),
+            integration_manager.clone(),
+            audit_service.clone(),
+            base_url.clone(),
+        ));
+
+        let event_proposal_service = Arc::new(EventProposalService::new(
+            event_repo.clone(),
+            member_repo.clone(),
+            audit_service.clone(),
+            integration_manager.clone(),
+            email_sender.clone(),
+            settings_service.clone(),
+            base_url,
         ));
 
         let announcement_admin_service = Arc::new(AnnouncementAdminService::new(
@@ -150,6 +274,12 @@ impl ServiceContext {
             integration_manager.clone(),
         ));
 
+        let announcement_comment_service = Arc::new(AnnouncementCommentService::new(
+            db_pool.clone(),
+            announcement_repo.clone(),
+            audit_service.clone(),
+        ));
+
         let payment_admin_service = Arc::new(PaymentAdminService::new(
             payment_repo.clone(),
             stripe_client,
@@ -171,6 +301,7 @@ impl ServiceContext {
             basic_type_repo,
             membership_type_repo,
             processed_events_repo,
+            webhook_metrics: Arc::new(WebhookMetrics::default()),
             integration_manager,
             auth_service,
             csrf_service,
@@ -182,11 +313,26 @@ impl ServiceContext {
             membership_type_service,
             email_sender,
             audit_service,
+            integration_log_service,
+            integration_outbox_service,
+            login_history_service,
+            directory_service,
+            emergency_contact_service: Arc::new(EmergencyContactService::new(db_pool.clone())),
+            signup_field_service,
             payment_service,
             member_service,
             event_admin_service,
+            event_host_service,
+            event_invite_service,
+            event_proposal_service,
             announcement_admin_service,
+            announcement_comment_service,
             payment_admin_service,
+            admin_notification_service,
+            celebration_service,
+            landing_page_service,
+            contact_service,
+            data_check_service: Arc::new(DataCheckService::new(db_pool.clone())),
             db_pool,
         }
     }
@@ -211,6 +357,7 @@ impl ServiceContext {
             self.settings_service.clone(),
             self.email_sender.clone(),
             self.integration_manager.clone(),
+            self.auth_service.clone(),
             stripe_client,
             base_url,
             self.db_pool.clone(),
@@ -214,15 +214,15 @@ impl PaymentAdminService {
         // 6. Build the human-readable detail.
         let detail = match (&payment.payment_method, &stripe_refund_id) {
             (PaymentMethod::Stripe, Some(rid)) => format!(
-                "Refunded ${:.2} via Stripe (refund {})",
-                payment.amount_cents as f64 / 100.0,
+                "Refunded ${} via Stripe (refund {})",
+                payment.amount(),
                 rid,
             ),
             (PaymentMethod::Manual, _) => format!(
-                "Marked ${:.2} manual payment as Refunded (no API call — refund the cash/check yourself)",
-                payment.amount_cents as f64 / 100.0,
+                "Marked ${} manual payment as Refunded (no API call — refund the cash/check yourself)",
+                payment.amount(),
             ),
-            _ => format!("Refunded ${:.2}", payment.amount_cents as f64 / 100.0),
+            _ => format!("Refunded ${}", payment.amount()),
         };
 
         // 7. Audit. Failures are logged via tracing and swallowed
@@ -245,8 +245,8 @@ impl PaymentAdminService {
         self.integration_manager
             .handle_event(IntegrationEvent::AdminAlert {
                 subject: format!(
-                    "Payment refunded — ${:.2}",
-                    payment.amount_cents as f64 / 100.0,
+                    "Payment refunded — ${}",
+                    payment.amount(),
                 ),
                 body: format!(
                     "Refunded by: {}\nPayer: {:?}\nMethod: {:?}\nDetail: {}",
@@ -255,6 +255,22 @@ impl PaymentAdminService {
             })
             .await;
 
+        // 9. Lifecycle event for integrations that track payments.
+        //    Re-read so it carries the row as refunded.
+        match self.payment_repo.find_by_id(payment.id).await {
+            Ok(Some(refunded)) => {
+                self.integration_manager
+                    .handle_event(IntegrationEvent::PaymentRefunded(refunded))
+                    .await
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(
+                "Couldn't reload refunded payment {} to notify integrations: {}",
+                payment.id,
+                e,
+            ),
+        }
+
         Ok(RefundOutcome {
             amount_cents: payment.amount_cents,
             stripe_refund_id,
@@ -361,6 +377,7 @@ mod tests {
             external_id,
             description: "test".to_string(),
             paid_at: Some(now),
+            refunded_at: None,
             created_at: now,
             updated_at: now,
         };
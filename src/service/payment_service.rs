@@ -17,8 +17,11 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    domain::{Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus, MAX_PAYMENT_CENTS},
+    domain::{
+        Money, Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus, MAX_PAYMENT_CENTS,
+    },
     error::{AppError, Result},
+    integrations::{IntegrationEvent, IntegrationManager},
     repository::{DonationCampaignRepository, MemberRepository, PaymentRepository},
     service::{audit_service::AuditService, billing_service::BillingService},
 };
@@ -50,6 +53,7 @@ pub struct PaymentService {
     member_repo: Arc<dyn MemberRepository>,
     donation_campaign_repo: Arc<dyn DonationCampaignRepository>,
     audit_service: Arc<AuditService>,
+    integration_manager: Arc<IntegrationManager>,
 }
 
 impl PaymentService {
@@ -58,8 +62,15 @@ impl PaymentService {
         member_repo: Arc<dyn MemberRepository>,
         donation_campaign_repo: Arc<dyn DonationCampaignRepository>,
         audit_service: Arc<AuditService>,
+        integration_manager: Arc<IntegrationManager>,
     ) -> Self {
-        Self { payment_repo, member_repo, donation_campaign_repo, audit_service }
+        Self {
+            payment_repo,
+            member_repo,
+            donation_campaign_repo,
+            audit_service,
+            integration_manager,
+        }
     }
 
     /// Record a manual or waived payment, optionally extending dues.
@@ -70,7 +81,8 @@ impl PaymentService {
     /// and a slug was supplied, extends dues and reschedules the next
     /// auto-renew via `billing_service`. Failures in the post-work
     /// chain are logged but don't roll back the payment row — same
-    /// semantics as the original handlers.
+    /// semantics as the original handlers. Audits the record and
+    /// dispatches `PaymentCompleted` last.
     pub async fn record_manual(
         &self,
         input: RecordManualPaymentInput,
@@ -123,6 +135,7 @@ impl PaymentService {
             description: input.description.clone(),
             kind: input.kind.clone(),
             paid_at: Some(now),
+            refunded_at: None,
             created_at: now,
             updated_at: now,
         };
@@ -163,13 +176,17 @@ impl PaymentService {
             &input.member_id.to_string(),
             None,
             Some(&format!(
-                "${:.2} — {}",
-                input.amount_cents as f64 / 100.0,
+                "${} — {}",
+                Money::usd(input.amount_cents),
                 input.description,
             )),
             None,
         ).await;
 
+        self.integration_manager
+            .handle_event(IntegrationEvent::PaymentCompleted(payment.clone()))
+            .await;
+
         Ok(payment)
     }
 }
@@ -0,0 +1,115 @@
+//! Readiness, as opposed to liveness. `/health` answers 200 whenever
+//! the process is up; `/ready` answers 200 only once the startup check
+//! below has passed, so a load balancer or orchestrator holds traffic
+//! back from an instance that can't do its job yet.
+//!
+//! The check covers the database and every registered (i.e. enabled)
+//! integration. The database is always critical. Integrations are
+//! critical only under `integrations.startup_policy = "fail"`; under
+//! the default `"warn"` a failing one is logged and reported but the
+//! instance still goes ready, which is how boot behaved before.
+//!
+//! Readiness latches: once a check passes it stays ready. `main` runs
+//! the first check before serving and, if that one fails, keeps
+//! retrying in the background.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+
+use crate::{config::IntegrationStartupPolicy, integrations::IntegrationManager};
+
+/// Outcome of one dependency's check.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub name: String,
+    /// Whether a failure here keeps the instance not-ready.
+    pub critical: bool,
+    pub healthy: bool,
+    pub error: Option<String>,
+}
+
+/// The latest readiness verdict. `checks` is empty until the first
+/// check has run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<DependencyCheck>,
+}
+
+pub struct ReadinessService {
+    pool: SqlitePool,
+    integrations: Arc<IntegrationManager>,
+    policy: IntegrationStartupPolicy,
+    report: RwLock<ReadinessReport>,
+}
+
+impl ReadinessService {
+    pub fn new(
+        pool: SqlitePool,
+        integrations: Arc<IntegrationManager>,
+        policy: IntegrationStartupPolicy,
+    ) -> Self {
+        Self {
+            pool,
+            integrations,
+            policy,
+            report: RwLock::new(ReadinessReport {
+                ready: false,
+                checks: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn policy(&self) -> IntegrationStartupPolicy {
+        self.policy
+    }
+
+    /// Run every dependency check and record the result. Once ready,
+    /// later calls re-run the checks for the report but never flip the
+    /// instance back to not-ready.
+    pub async fn check(&self) -> ReadinessReport {
+        let mut checks = Vec::new();
+
+        let db = sqlx::query("SELECT 1").execute(&self.pool).await;
+        checks.push(DependencyCheck {
+            name: "database".to_string(),
+            critical: true,
+            healthy: db.is_ok(),
+            error: db.err().map(|e| e.to_string()),
+        });
+
+        let integrations_critical = self.policy == IntegrationStartupPolicy::Fail;
+        for (name, result) in self.integrations.health_check_all().await {
+            match &result {
+                Ok(_) => tracing::info!("Integration {} is healthy", name),
+                Err(e) if integrations_critical => {
+                    tracing::error!("Integration {} health check failed: {:?}", name, e)
+                }
+                Err(e) => tracing::warn!("Integration {} health check failed: {:?}", name, e),
+            }
+            checks.push(DependencyCheck {
+                name,
+                critical: integrations_critical,
+                healthy: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+
+        let passed = checks.iter().all(|c| c.healthy || !c.critical);
+        let mut report = self.report.write().await;
+        *report = ReadinessReport {
+            ready: report.ready || passed,
+            checks,
+        };
+        report.clone()
+    }
+
+    /// The verdict from the most recent [`check`](Self::check). Cheap:
+    /// doesn't touch the database or any integration.
+    pub async fn report(&self) -> ReadinessReport {
+        self.report.read().await.clone()
+    }
+}
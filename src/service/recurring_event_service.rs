@@ -60,7 +60,9 @@ impl RecurringEventService {
     ///
     /// `template` is treated as the prototype for every occurrence:
     /// title, description, type, visibility, location,
-    /// max_attendees, rsvp_required, image_url all carry over.
+    /// max_attendees, rsvp_required, allow_guest_rsvp,
+    /// registration_group, image_url all carry over; an RSVP deadline
+    /// keeps its lead time before each occurrence's start.
     /// `template.start_time` is the anchor (defines time-of-day and
     /// the first occurrence).
     ///
@@ -120,6 +122,9 @@ impl RecurringEventService {
         // offset from start_time.
         let original_duration = template.end_time
             .map(|e| e - template.start_time);
+        // The RSVP deadline likewise keeps its lead time before start.
+        let deadline_lead = template.rsvp_deadline
+            .map(|d| template.start_time - d);
         let mut inserted = Vec::with_capacity(occurrence_times.len());
         for (idx, start) in occurrence_times.iter().enumerate() {
             let occurrence = Event {
@@ -134,12 +139,17 @@ impl RecurringEventService {
                 location: template.location.clone(),
                 max_attendees: template.max_attendees,
                 rsvp_required: template.rsvp_required,
+                allow_guest_rsvp: template.allow_guest_rsvp,
+                registration_group: template.registration_group.clone(),
+                rsvp_deadline: deadline_lead.map(|d| *start - d),
                 image_url: template.image_url.clone(),
                 created_by,
                 created_at: now,
                 updated_at: now,
                 series_id: Some(series_id),
                 occurrence_index: Some((idx + 1) as i32),
+                status: template.status,
+                review_feedback: None,
             };
             inserted.push(self.event_repo.create(occurrence).await?);
         }
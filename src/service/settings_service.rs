@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use chrono::{Utc, NaiveDateTime, DateTime};
 use uuid::Uuid;
@@ -6,7 +6,12 @@ use sqlx::{SqlitePool, FromRow};
 
 use crate::{
     auth::SecretCrypto,
-    domain::{AppSetting, UpdateSettingRequest, SettingsCategory, SettingType},
+    domain::{
+        html_policy, list_defaults, parse_dues_reminder_sequence, parse_signup_fields, AppSetting,
+        DuesExtensionBase, DuesReminderStep, DuesReminderTone, ExpiryMode, HtmlPolicy, LandingPage,
+        ListDefaultSetting, Locale, Member, MemberStatus, SettingType, SettingsCategory,
+        SignupField, UpdateSettingRequest,
+    },
     error::{AppError, Result},
 };
 
@@ -65,6 +70,7 @@ pub mod discord_keys {
     pub const ANNOUNCEMENTS_CHANNEL_ID: &str = "discord.announcements_channel_id";
     pub const ADMIN_ALERTS_CHANNEL_ID: &str = "discord.admin_alerts_channel_id";
     pub const INVITE_URL: &str = "discord.invite_url";
+    pub const TYPE_ROLE_IDS: &str = "discord.type_role_ids";
     pub const LAST_TEST_AT: &str = "discord.last_test_at";
     pub const LAST_TEST_OK: &str = "discord.last_test_ok";
     pub const LAST_TEST_ERROR: &str = "discord.last_test_error";
@@ -81,6 +87,8 @@ pub struct DbDiscordConfig {
     pub announcements_channel_id: String,
     pub admin_alerts_channel_id: String,
     pub invite_url: String,
+    /// Membership type slug → tier role ID. See `parse_type_role_ids`.
+    pub type_role_ids: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone)]
@@ -93,11 +101,41 @@ pub struct UpdateDiscordConfig {
     pub announcements_channel_id: String,
     pub admin_alerts_channel_id: String,
     pub invite_url: String,
+    pub type_role_ids: BTreeMap<String, String>,
     /// None = leave existing token unchanged. Some(empty) = clear it.
     /// Some(nonempty) = encrypt and replace.
     pub bot_token: Option<String>,
 }
 
+/// Parse the `discord.type_role_ids` setting: one `slug=role_id` pair
+/// per line, blank lines ignored. Errors name the offending line so
+/// the admin form can show it. Role IDs aren't validated here; the
+/// form checks them as snowflakes.
+pub fn parse_type_role_ids(text: &str) -> std::result::Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some((slug, role_id)) = line.split_once('=') else {
+            return Err(format!("Expected slug=role_id, got: {}", line));
+        };
+        let (slug, role_id) = (slug.trim(), role_id.trim());
+        if slug.is_empty() || role_id.is_empty() {
+            return Err(format!("Expected slug=role_id, got: {}", line));
+        }
+        if map.insert(slug.to_string(), role_id.to_string()).is_some() {
+            return Err(format!("Membership type {} is listed twice", slug));
+        }
+    }
+    Ok(map)
+}
+
+/// Inverse of `parse_type_role_ids`, for storage and the admin form.
+pub fn format_type_role_ids(map: &BTreeMap<String, String>) -> String {
+    map.iter()
+        .map(|(slug, role_id)| format!("{}={}", slug, role_id))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(FromRow)]
 struct SettingRow {
     key: String,
@@ -210,6 +248,91 @@ impl SettingsService {
         request: UpdateSettingRequest,
         updated_by: Uuid,
     ) -> Result<AppSetting> {
+        if key == "org.locale" && Locale::from_tag(&request.value).is_none() {
+            return Err(AppError::BadRequest(format!(
+                "Unknown locale {:?}. Supported: {}",
+                request.value,
+                Locale::ALL.map(|l| l.tag()).join(", ")
+            )));
+        }
+
+        if key == "billing.dues_extension_base"
+            && DuesExtensionBase::parse(&request.value).is_none()
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unknown dues extension base {:?}. Supported: {}",
+                request.value,
+                DuesExtensionBase::ALL.map(|b| b.as_str()).join(", ")
+            )));
+        }
+
+        if key == "membership.expiry_mode" && ExpiryMode::parse(&request.value).is_none() {
+            return Err(AppError::BadRequest(format!(
+                "Unknown expiry mode {:?}. Supported: {}",
+                request.value,
+                ExpiryMode::ALL.map(|m| m.as_str()).join(", ")
+            )));
+        }
+
+        if key == "auth.member_landing_page" || key == "auth.admin_landing_page" {
+            let admin = key == "auth.admin_landing_page";
+            let permitted: Vec<&str> = LandingPage::ALL
+                .iter()
+                .filter(|p| admin || !p.admin_only())
+                .map(|p| p.as_str())
+                .collect();
+            if !permitted.contains(&request.value.as_str()) {
+                return Err(AppError::BadRequest(format!(
+                    "Unknown landing page {:?}. Supported: {}",
+                    request.value,
+                    permitted.join(", ")
+                )));
+            }
+        }
+
+        if let Some(setting) = list_defaults::ALL.iter().find(|s| s.key == key) {
+            if !setting.accepts(&request.value) {
+                return Err(AppError::BadRequest(format!(
+                    "Unknown value {:?} for {}. Supported: {}",
+                    request.value,
+                    key,
+                    setting.allowed.join(", ")
+                )));
+            }
+        }
+
+        if html_policy::KEYS.contains(&key) {
+            HtmlPolicy::default()
+                .set(key, &request.value)
+                .map_err(AppError::BadRequest)?;
+        }
+
+        if key == "membership.signup_fields" {
+            parse_signup_fields(&request.value).map_err(AppError::BadRequest)?;
+        }
+
+        if key == "events.reminder_lead_hours" {
+            parse_lead_hours(&request.value).map_err(AppError::BadRequest)?;
+        }
+
+        if key == "membership.reminder_sequence" {
+            parse_dues_reminder_sequence(&request.value).map_err(AppError::BadRequest)?;
+        }
+
+        if key == "maintenance.allowed_ips" {
+            if let Some(bad) = request
+                .value
+                .split(',')
+                .map(str::trim)
+                .find(|s| !s.is_empty() && s.parse::<std::net::IpAddr>().is_err())
+            {
+                return Err(AppError::BadRequest(format!(
+                    "{:?} is not an IP address",
+                    bad
+                )));
+            }
+        }
+
         // Get the current setting first
         let current = self.get_setting(key).await?;
 
@@ -272,6 +395,206 @@ impl SettingsService {
         value.parse().map_err(|_| AppError::Internal(format!("Invalid number value for {}", key)))
     }
 
+    /// The org-wide display locale. Falls back to en-US when unset or
+    /// unparseable so a bad hand edit can't break page rendering.
+    pub async fn org_locale(&self) -> Locale {
+        self.get_value("org.locale")
+            .await
+            .ok()
+            .and_then(|tag| Locale::from_tag(&tag))
+            .unwrap_or_default()
+    }
+
+    /// Club default for which date manual dues extensions count from.
+    /// Falls back to "later of now or expiry" when unset or invalid.
+    pub async fn dues_extension_base(&self) -> DuesExtensionBase {
+        self.get_value("billing.dues_extension_base")
+            .await
+            .ok()
+            .and_then(|v| DuesExtensionBase::parse(&v))
+            .unwrap_or_default()
+    }
+
+    /// Club default landing page after sign-in for an admin or a
+    /// regular member. An unset or invalid value gives the dashboard for
+    /// admins and the member's own summary otherwise; an admin-only page
+    /// for a member gives the dashboard.
+    pub async fn default_landing_page(&self, is_admin: bool) -> LandingPage {
+        let (key, fallback) = if is_admin {
+            ("auth.admin_landing_page", LandingPage::Dashboard)
+        } else {
+            ("auth.member_landing_page", LandingPage::Me)
+        };
+        self.get_value(key)
+            .await
+            .ok()
+            .and_then(|v| v.parse::<LandingPage>().ok())
+            .unwrap_or(fallback)
+            .permitted_for(is_admin)
+    }
+
+    /// Days a member stays `Active` after their dues lapse before the
+    /// expiration sweep expires them. Also decides where `DuesStatus`
+    /// draws the line between in-grace and expired.
+    pub async fn grace_period_days(&self) -> i64 {
+        self.get_number("membership.grace_period_days")
+            .await
+            .unwrap_or(3)
+    }
+
+    /// How many featured announcements the member dashboard shows,
+    /// clamped to 0..=10. 0 hides the card; unset or invalid gives 3.
+    pub async fn dashboard_featured_count(&self) -> i64 {
+        self.get_number("features.dashboard_featured_announcements")
+            .await
+            .unwrap_or(3)
+            .clamp(0, 10)
+    }
+
+    /// How many announcements the RSS feed carries when the request
+    /// doesn't ask for a number. Unset or invalid gives 20; the feed
+    /// handler applies its own cap.
+    pub async fn feed_item_limit(&self) -> i64 {
+        self.get_number("announcements.feed_item_limit")
+            .await
+            .unwrap_or(20)
+    }
+
+    /// Whether feed items carry the whole announcement as HTML rather
+    /// than a short plain-text summary. Unset or invalid gives full.
+    pub async fn feed_full_content(&self) -> bool {
+        self.get_bool("announcements.feed_full_content")
+            .await
+            .unwrap_or(true)
+    }
+
+    /// The admin-list default stored under `setting`, or the built-in
+    /// one when it's unset or not a value the list understands.
+    pub async fn list_default(&self, setting: &ListDefaultSetting) -> &'static str {
+        let value = self.get_value(setting.key).await.unwrap_or_default();
+        setting.resolve(&value)
+    }
+
+    /// The raw HTML allowed in announcement and comment Markdown, from
+    /// the `announcements.html_*` settings. A setting that's unset or
+    /// unreadable keeps its baseline, with a warning for the latter.
+    pub async fn html_policy(&self) -> HtmlPolicy {
+        let mut policy = HtmlPolicy::default();
+        for key in html_policy::KEYS {
+            let Ok(value) = self.get_value(key).await else {
+                continue;
+            };
+            if let Err(e) = policy.set(key, &value) {
+                tracing::warn!("Ignoring {}: {}", key, e);
+            }
+        }
+        policy
+    }
+
+    /// Event reminder lead times in hours, shortest first, from
+    /// `events.reminder_lead_hours`. Entries that aren't positive
+    /// whole numbers are skipped with a warning; an empty or
+    /// unreadable list falls back to a single 24-hour reminder.
+    pub async fn event_reminder_lead_hours(&self) -> Vec<i64> {
+        let raw = self
+            .get_value("events.reminder_lead_hours")
+            .await
+            .unwrap_or_default();
+        let mut hours: Vec<i64> = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .filter_map(|s| match s.parse::<i64>() {
+                Ok(n) if n > 0 => Some(n),
+                _ => {
+                    tracing::warn!("Ignoring invalid event reminder lead time {:?}", s);
+                    None
+                }
+            })
+            .collect();
+        hours.sort_unstable();
+        hours.dedup();
+        if hours.is_empty() {
+            hours.push(24);
+        }
+        hours
+    }
+
+    /// `membership.reminder_sequence`, earliest step first. An
+    /// unreadable sequence falls back to a single friendly reminder
+    /// `membership.reminder_days_before` days out.
+    pub async fn dues_reminder_sequence(&self) -> Vec<DuesReminderStep> {
+        let raw = self
+            .get_value("membership.reminder_sequence")
+            .await
+            .unwrap_or_default();
+        match parse_dues_reminder_sequence(&raw) {
+            Ok(steps) => steps,
+            Err(e) => {
+                if !raw.trim().is_empty() {
+                    tracing::warn!("Ignoring invalid dues reminder sequence {:?}: {}", raw, e);
+                }
+                let days_before = self
+                    .get_number("membership.reminder_days_before")
+                    .await
+                    .unwrap_or(7)
+                    .clamp(1, 90);
+                vec![DuesReminderStep {
+                    days_before,
+                    tone: DuesReminderTone::Friendly,
+                }]
+            }
+        }
+    }
+
+    /// Whether the expiration sweep writes `Expired` or leaves lapsed
+    /// members `Active` and lets their status be derived on read.
+    /// Falls back to writing it when unset or invalid.
+    pub async fn expiry_mode(&self) -> ExpiryMode {
+        self.get_value("membership.expiry_mode")
+            .await
+            .ok()
+            .and_then(|v| ExpiryMode::parse(&v))
+            .unwrap_or_default()
+    }
+
+    /// `member.effective_status` under the club's expiry mode and
+    /// grace period, as of now.
+    pub async fn effective_status(&self, member: &Member) -> MemberStatus {
+        member.effective_status(
+            self.expiry_mode().await,
+            Utc::now(),
+            self.grace_period_days().await,
+        )
+    }
+
+    /// `member` with `status` replaced by its effective status. For
+    /// anything that gates access or shows the member their own
+    /// status; never write the result back.
+    pub async fn with_effective_status(&self, mut member: Member) -> Member {
+        member.status = self.effective_status(&member).await;
+        member
+    }
+
+    /// Extra public-signup fields. A setting that doesn't parse (only
+    /// possible through a hand edit) is logged and treated as empty so
+    /// signup keeps working.
+    pub async fn signup_fields(&self) -> Vec<SignupField> {
+        let json = self
+            .get_value("membership.signup_fields")
+            .await
+            .unwrap_or_default();
+        parse_signup_fields(&json).unwrap_or_else(|e| {
+            tracing::error!("Ignoring membership.signup_fields: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Locale to render for `member`: their override, else the org's.
+    pub async fn locale_for(&self, member: &Member) -> Locale {
+        Locale::resolve(member.locale.as_deref(), self.org_locale().await)
+    }
+
     /// Load the full email configuration from the settings table,
     /// decrypting the SMTP password into plaintext.
     pub async fn get_email_config(&self) -> Result<DbEmailConfig> {
@@ -340,13 +663,18 @@ impl SettingsService {
         let announcements_channel_id = self.get_value(discord_keys::ANNOUNCEMENTS_CHANNEL_ID).await.unwrap_or_default();
         let admin_alerts_channel_id = self.get_value(discord_keys::ADMIN_ALERTS_CHANNEL_ID).await.unwrap_or_default();
         let invite_url = self.get_value(discord_keys::INVITE_URL).await.unwrap_or_default();
+        let type_role_ids = self.get_value(discord_keys::TYPE_ROLE_IDS).await.unwrap_or_default();
+        let type_role_ids = parse_type_role_ids(&type_role_ids).unwrap_or_else(|e| {
+            tracing::error!("Ignoring {}: {}", discord_keys::TYPE_ROLE_IDS, e);
+            BTreeMap::new()
+        });
         let encrypted = self.get_value(discord_keys::BOT_TOKEN).await.unwrap_or_default();
         let bot_token = self.crypto.decrypt(&encrypted)?;
 
         Ok(DbDiscordConfig {
             enabled, bot_token, guild_id, member_role_id, expired_role_id,
             events_channel_id, announcements_channel_id, admin_alerts_channel_id,
-            invite_url,
+            invite_url, type_role_ids,
         })
     }
 
@@ -374,6 +702,11 @@ impl SettingsService {
         self.set_value_raw(discord_keys::ANNOUNCEMENTS_CHANNEL_ID, &config.announcements_channel_id, updated_by).await?;
         self.set_value_raw(discord_keys::ADMIN_ALERTS_CHANNEL_ID, &config.admin_alerts_channel_id, updated_by).await?;
         self.set_value_raw(discord_keys::INVITE_URL, &config.invite_url, updated_by).await?;
+        self.set_value_raw(
+            discord_keys::TYPE_ROLE_IDS,
+            &format_type_role_ids(&config.type_role_ids),
+            updated_by,
+        ).await?;
 
         if let Some(new_token) = config.bot_token {
             let encrypted = self.crypto.encrypt(&new_token)?;
@@ -418,4 +751,21 @@ impl SettingsService {
         .map_err(AppError::Database)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+/// Strict check for an admin-entered `events.reminder_lead_hours`:
+/// at least one entry, each a positive whole number of hours.
+fn parse_lead_hours(value: &str) -> std::result::Result<Vec<i64>, String> {
+    let hours = value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<i64>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("{:?} is not a positive number of hours", s)),
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if hours.is_empty() {
+        return Err("Give at least one reminder lead time in hours".to_string());
+    }
+    Ok(hours)
+}
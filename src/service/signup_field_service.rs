@@ -0,0 +1,200 @@
+//! Admin-configured extra fields on the public signup form: checking a
+//! signup's answers against `membership.signup_fields`, saving them
+//! once the member exists, and reading them back for the admin member
+//! page. See `domain::signup_field` for the setting's shape.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::{
+    domain::{SignupField, SignupFieldKind, MAX_SIGNUP_FIELD_LEN},
+    error::Result,
+    service::settings_service::SettingsService,
+};
+
+/// One configured field with the member's answer, for display.
+#[derive(Debug, Clone)]
+pub struct SignupAnswer {
+    pub label: String,
+    pub value: String,
+}
+
+pub struct SignupFieldService {
+    pool: SqlitePool,
+    settings_service: Arc<SettingsService>,
+}
+
+impl SignupFieldService {
+    pub fn new(pool: SqlitePool, settings_service: Arc<SettingsService>) -> Self {
+        Self {
+            pool,
+            settings_service,
+        }
+    }
+
+    pub async fn fields(&self) -> Vec<SignupField> {
+        self.settings_service.signup_fields().await
+    }
+
+    /// Save a new member's answers. `answers` is the output of
+    /// [`check_answers`]: configured fields only, already trimmed.
+    pub async fn save(&self, member_id: Uuid, answers: &[(SignupField, String)]) -> Result<()> {
+        save_answers(&self.pool, member_id, answers).await
+    }
+
+    /// Every member's custom-field values, keyed by member then field
+    /// key, for the roster export. Profile-column answers aren't
+    /// included; they live on `member_profiles`.
+    pub async fn custom_values(&self) -> Result<HashMap<Uuid, HashMap<String, String>>> {
+        let rows: Vec<(String, String, String)> =
+            sqlx::query_as("SELECT member_id, field_key, value FROM member_custom_fields")
+                .fetch_all(&self.pool)
+                .await?;
+        let mut values: HashMap<Uuid, HashMap<String, String>> = HashMap::new();
+        for (member_id, key, value) in rows {
+            if let Ok(member_id) = Uuid::parse_str(&member_id) {
+                values.entry(member_id).or_default().insert(key, value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// The member's answers to the currently configured fields, in
+    /// form order, followed by answers to custom fields an admin has
+    /// since removed (labelled by key). Unanswered fields are skipped.
+    pub async fn answers(&self, member_id: Uuid) -> Result<Vec<SignupAnswer>> {
+        let profile: Option<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+            "SELECT bio, blog_url, github_username FROM member_profiles WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+        let (bio, blog_url, github_username) = profile.unwrap_or_default();
+
+        let mut custom: BTreeMap<String, String> = sqlx::query_as::<_, (String, String)>(
+            "SELECT field_key, value FROM member_custom_fields WHERE member_id = ?",
+        )
+        .bind(member_id.to_string())
+        .fetch_all(&self.pool)
+        .await?
+        .into_iter()
+        .collect();
+
+        let mut answers = Vec::new();
+        for field in self.fields().await {
+            let value = match field.key.as_str() {
+                "bio" => bio.clone(),
+                "blog_url" => blog_url.clone(),
+                "github_username" => github_username.clone(),
+                key => custom.remove(key),
+            };
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                answers.push(SignupAnswer {
+                    label: field.label,
+                    value,
+                });
+            }
+        }
+        answers.extend(
+            custom
+                .into_iter()
+                .map(|(label, value)| SignupAnswer { label, value }),
+        );
+        Ok(answers)
+    }
+}
+
+/// Write `answers` for `member_id`, upserting each one. Shared by
+/// signup and the bulk importer, which holds a pool but not this
+/// service.
+pub async fn save_answers(
+    pool: &SqlitePool,
+    member_id: Uuid,
+    answers: &[(SignupField, String)],
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    for (field, value) in answers {
+        if field.is_profile_field() {
+            // The column name comes from PROFILE_SIGNUP_FIELDS,
+            // never from the request.
+            sqlx::query(&format!(
+                "INSERT INTO member_profiles (member_id, {col}) VALUES (?, ?) \
+                 ON CONFLICT (member_id) DO UPDATE SET \
+                     {col} = excluded.{col}, updated_at = CURRENT_TIMESTAMP",
+                col = field.key,
+            ))
+            .bind(member_id.to_string())
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+        } else {
+            sqlx::query(
+                "INSERT INTO member_custom_fields (member_id, field_key, value) \
+                 VALUES (?, ?, ?) \
+                 ON CONFLICT (member_id, field_key) DO UPDATE SET \
+                     value = excluded.value, updated_at = CURRENT_TIMESTAMP",
+            )
+            .bind(member_id.to_string())
+            .bind(&field.key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Check one non-empty, trimmed answer against its field's length and
+/// kind rules.
+pub fn check_answer(field: &SignupField, value: &str) -> std::result::Result<(), String> {
+    if value.chars().count() > MAX_SIGNUP_FIELD_LEN {
+        return Err(format!(
+            "{} must be at most {} characters",
+            field.label, MAX_SIGNUP_FIELD_LEN
+        ));
+    }
+    if field.kind == SignupFieldKind::Url
+        && !(value.starts_with("https://") || value.starts_with("http://"))
+    {
+        return Err(format!(
+            "{} must be a link starting with https://",
+            field.label
+        ));
+    }
+    Ok(())
+}
+
+/// Check a signup's answers against the configured fields. On success
+/// returns the non-empty answers, trimmed and paired with their field;
+/// keys that aren't configured are dropped. On failure returns one
+/// message per offending field, keyed by field key.
+pub fn check_answers(
+    fields: &[SignupField],
+    answers: &HashMap<String, String>,
+) -> std::result::Result<Vec<(SignupField, String)>, BTreeMap<String, String>> {
+    let mut accepted = Vec::new();
+    let mut errors = BTreeMap::new();
+    for field in fields {
+        let value = answers.get(&field.key).map(|v| v.trim()).unwrap_or("");
+        if value.is_empty() {
+            if field.required {
+                errors.insert(field.key.clone(), format!("{} is required", field.label));
+            }
+            continue;
+        }
+        if let Err(message) = check_answer(field, value) {
+            errors.insert(field.key.clone(), message);
+            continue;
+        }
+        accepted.push((field.clone(), value.to_string()));
+    }
+    if errors.is_empty() {
+        Ok(accepted)
+    } else {
+        Err(errors)
+    }
+}
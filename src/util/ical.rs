@@ -0,0 +1,204 @@
+//! iCalendar (RFC 5545) helpers shared by the public `.ics` feed and
+//! the per-registrant RSVP invites.
+//!
+//! The feed is a METHOD:PUBLISH calendar of many events; an invite is a
+//! single-event iTIP message (RFC 5546) — METHOD:REQUEST to add or
+//! update the entry in the attendee's calendar, METHOD:CANCEL to remove
+//! it. Both use the event id as the UID so a calendar app treats them
+//! as the same entry, and the invite's SEQUENCE tells it which version
+//! is newest.
+
+use chrono::{DateTime, Utc};
+
+use crate::domain::Event;
+
+/// Which iTIP message an invite is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InviteMethod {
+    Request,
+    Cancel,
+}
+
+impl InviteMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InviteMethod::Request => "REQUEST",
+            InviteMethod::Cancel => "CANCEL",
+        }
+    }
+}
+
+/// Who the invite is from and to.
+pub struct InviteParties<'a> {
+    pub organizer_name: &'a str,
+    /// Omitted from the invite when empty; clients still import it,
+    /// they just can't reply.
+    pub organizer_email: &'a str,
+    pub attendee_name: &'a str,
+    pub attendee_email: &'a str,
+}
+
+/// Escape a text value for iCal (RFC 5545 Section 3.3.11).
+/// Backslashes, semicolons, commas, and newlines must be escaped.
+pub fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+        .replace('\r', "")
+}
+
+/// UTC DATE-TIME form, e.g. `20250301T180000Z`.
+pub fn format_utc(dt: &DateTime<Utc>) -> String {
+    dt.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Append one content line, folded at 75 octets (RFC 5545 Section
+/// 3.1) and terminated with CRLF. Continuation lines start with a
+/// single space, which counts toward their 75.
+fn push_line(out: &mut String, line: &str) {
+    let mut budget = 75;
+    let mut used = 0;
+    for c in line.chars() {
+        if used + c.len_utf8() > budget {
+            out.push_str("\r\n ");
+            budget = 74;
+            used = 0;
+        }
+        out.push(c);
+        used += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// A METHOD:PUBLISH calendar of `events`, for subscription feeds.
+/// Callers decide what an anonymous subscriber may see: every event
+/// passed in goes out with its title, description and location.
+pub fn publish_feed(events: &[Event]) -> String {
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//Coterie//Events//EN");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, "METHOD:PUBLISH");
+    push_line(&mut ics, "X-WR-CALNAME:Coterie Events");
+    let stamp = format_utc(&Utc::now());
+    for event in events {
+        push_line(&mut ics, "BEGIN:VEVENT");
+        push_line(&mut ics, &format!("UID:{}", event.id));
+        push_line(&mut ics, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut ics,
+            &format!("DTSTART:{}", format_utc(&event.start_time)),
+        );
+        if let Some(end_time) = &event.end_time {
+            push_line(&mut ics, &format!("DTEND:{}", format_utc(end_time)));
+        }
+        push_line(&mut ics, &format!("SUMMARY:{}", escape_text(&event.title)));
+        push_line(
+            &mut ics,
+            &format!("DESCRIPTION:{}", escape_text(&event.description)),
+        );
+        if let Some(location) = event.location.as_deref().filter(|l| !l.is_empty()) {
+            push_line(&mut ics, &format!("LOCATION:{}", escape_text(location)));
+        }
+        push_line(
+            &mut ics,
+            &format!("CREATED:{}", format_utc(&event.created_at)),
+        );
+        push_line(
+            &mut ics,
+            &format!("LAST-MODIFIED:{}", format_utc(&event.updated_at)),
+        );
+        push_line(&mut ics, "STATUS:CONFIRMED");
+        push_line(&mut ics, "END:VEVENT");
+    }
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+/// A one-event iTIP calendar for a single attendee. `sequence` must
+/// grow with every message sent for the same event to the same
+/// attendee, or calendar apps will ignore the newer one.
+pub fn event_invite(
+    event: &Event,
+    method: InviteMethod,
+    sequence: i64,
+    parties: &InviteParties<'_>,
+    event_url: &str,
+) -> String {
+    let mut ics = String::new();
+    push_line(&mut ics, "BEGIN:VCALENDAR");
+    push_line(&mut ics, "VERSION:2.0");
+    push_line(&mut ics, "PRODID:-//Coterie//Events//EN");
+    push_line(&mut ics, "CALSCALE:GREGORIAN");
+    push_line(&mut ics, &format!("METHOD:{}", method.as_str()));
+    push_line(&mut ics, "BEGIN:VEVENT");
+    push_line(&mut ics, &format!("UID:{}", event.id));
+    push_line(&mut ics, &format!("SEQUENCE:{}", sequence));
+    push_line(&mut ics, &format!("DTSTAMP:{}", format_utc(&Utc::now())));
+    push_line(
+        &mut ics,
+        &format!("DTSTART:{}", format_utc(&event.start_time)),
+    );
+    if let Some(end_time) = &event.end_time {
+        push_line(&mut ics, &format!("DTEND:{}", format_utc(end_time)));
+    }
+    push_line(&mut ics, &format!("SUMMARY:{}", escape_text(&event.title)));
+    push_line(
+        &mut ics,
+        &format!("DESCRIPTION:{}", escape_text(&event.description)),
+    );
+    if let Some(location) = &event.location {
+        push_line(&mut ics, &format!("LOCATION:{}", escape_text(location)));
+    }
+    push_line(&mut ics, &format!("URL:{}", event_url));
+    if !parties.organizer_email.is_empty() {
+        push_line(
+            &mut ics,
+            &format!(
+                "ORGANIZER;CN=\"{}\":mailto:{}",
+                parties.organizer_name.replace('"', "'"),
+                parties.organizer_email,
+            ),
+        );
+    }
+    push_line(
+        &mut ics,
+        &format!(
+            "ATTENDEE;CN=\"{}\";ROLE=REQ-PARTICIPANT;PARTSTAT={};RSVP=FALSE:mailto:{}",
+            parties.attendee_name.replace('"', "'"),
+            match method {
+                InviteMethod::Request => "ACCEPTED",
+                InviteMethod::Cancel => "DECLINED",
+            },
+            parties.attendee_email,
+        ),
+    );
+    push_line(
+        &mut ics,
+        match method {
+            InviteMethod::Request => "STATUS:CONFIRMED",
+            InviteMethod::Cancel => "STATUS:CANCELLED",
+        },
+    );
+    push_line(&mut ics, "END:VEVENT");
+    push_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_lines_fold_at_75_octets() {
+        let mut out = String::new();
+        push_line(&mut out, &format!("SUMMARY:{}", "é".repeat(60)));
+        for line in out.trim_end_matches("\r\n").split("\r\n") {
+            assert!(line.len() <= 75, "{} octets: {:?}", line.len(), line);
+        }
+        let unfolded = out.trim_end_matches("\r\n").replace("\r\n ", "");
+        assert_eq!(unfolded, format!("SUMMARY:{}", "é".repeat(60)));
+    }
+}
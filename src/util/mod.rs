@@ -1 +1,2 @@
+pub mod ical;
 pub mod string;
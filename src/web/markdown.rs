@@ -0,0 +1,364 @@
+//! A deliberately small Markdown renderer for member-written text
+//! (announcement comments and the public share pages). Everything
+//! is HTML-escaped first; the only markup that comes out is what this
+//! module emits itself:
+//!
+//! - blank-line separated paragraphs, single newlines as `<br>`
+//! - `**bold**`, `*emphasis*`, `` `code` ``
+//! - `[text](https://…)` links, http(s) only, opened with
+//!   `rel="nofollow noopener noreferrer"`
+//! - raw HTML tags the club's [`HtmlPolicy`] allows, rebuilt with only
+//!   the allowed attributes. Tags are balanced within a line: anything
+//!   left open is closed at the end of it.
+//!
+//! Anything else (other raw HTML, images, headings, other link schemes)
+//! renders as the literal text the member typed.
+
+use crate::{
+    domain::{html_policy::URL_ATTRIBUTES, HtmlPolicy},
+    web::escape_html,
+};
+
+/// Elements with no closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "br", "col", "embed", "hr", "img", "source", "track", "wbr",
+];
+
+/// Render `src` to sanitized HTML, letting through the raw tags
+/// `policy` allows.
+pub fn render(src: &str, policy: &HtmlPolicy) -> String {
+    let src = src.replace("\r\n", "\n");
+    let mut out = String::new();
+    for para in src.split("\n\n") {
+        let para = para.trim_matches('\n');
+        if para.trim().is_empty() {
+            continue;
+        }
+        out.push_str("<p>");
+        for (i, line) in para.lines().enumerate() {
+            if i > 0 {
+                out.push_str("<br>\n");
+            }
+            out.push_str(&inline(line, policy));
+        }
+        out.push_str("</p>\n");
+    }
+    out
+}
+
+fn inline(text: &str, policy: &HtmlPolicy) -> String {
+    let mut out = String::new();
+    // Raw tags opened so far, innermost last.
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix('`') {
+            if let Some(end) = after.find('`') {
+                out.push_str("<code>");
+                out.push_str(&escape_html(&after[..end]));
+                out.push_str("</code>");
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            if let Some(end) = after.find("**").filter(|&end| tight(&after[..end])) {
+                out.push_str("<strong>");
+                out.push_str(&inline(&after[..end], policy));
+                out.push_str("</strong>");
+                rest = &after[end + 2..];
+                continue;
+            }
+        }
+        if let Some(after) = rest.strip_prefix('*') {
+            if let Some(end) = after.find('*').filter(|&end| tight(&after[..end])) {
+                out.push_str("<em>");
+                out.push_str(&inline(&after[..end], policy));
+                out.push_str("</em>");
+                rest = &after[end + 1..];
+                continue;
+            }
+        }
+        if rest.starts_with('[') {
+            if let Some((label, url, len)) = link(rest) {
+                out.push_str(&format!(
+                    r#"<a href="{}" rel="nofollow noopener noreferrer" target="_blank" class="text-blue-600 hover:underline">{}</a>"#,
+                    escape_html(url),
+                    inline(label, policy),
+                ));
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        if rest.starts_with('<') {
+            if let Some((tag, len)) = raw_tag(rest).filter(|(t, _)| policy.allows_tag(&t.name)) {
+                if tag.closing {
+                    if let Some(pos) = open.iter().rposition(|name| *name == tag.name) {
+                        for name in open.drain(pos..).rev() {
+                            out.push_str(&format!("</{}>", name));
+                        }
+                        rest = &rest[len..];
+                        continue;
+                    }
+                } else if let Some(html) = open_tag(&tag, policy) {
+                    out.push_str(&html);
+                    if !VOID_ELEMENTS.contains(&tag.name.as_str()) {
+                        open.push(tag.name);
+                    }
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+        }
+        out.push_str(&escape_html(c.encode_utf8(&mut [0; 4])));
+        rest = &rest[c.len_utf8()..];
+    }
+    for name in open.into_iter().rev() {
+        out.push_str(&format!("</{}>", name));
+    }
+    out
+}
+
+/// A raw HTML tag as the member typed it, name and attribute names
+/// lowercased.
+struct RawTag<'a> {
+    name: String,
+    closing: bool,
+    attributes: Vec<(String, &'a str)>,
+}
+
+/// `<name attr="value" ...>`, `<name .../>` or `</name>` at the start
+/// of `s`, and the bytes consumed. Anything it doesn't fully
+/// understand is `None`, and so renders as text.
+fn raw_tag(s: &str) -> Option<(RawTag<'_>, usize)> {
+    let mut rest = s.strip_prefix('<')?;
+    let closing = match rest.strip_prefix('/') {
+        Some(after) => {
+            rest = after;
+            true
+        }
+        None => false,
+    };
+    let name_len = rest
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(rest.len());
+    if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name = rest[..name_len].to_ascii_lowercase();
+    rest = &rest[name_len..];
+
+    let mut attributes = Vec::new();
+    loop {
+        let trimmed = rest.trim_start_matches([' ', '\t']);
+        let spaced = trimmed.len() < rest.len();
+        rest = trimmed;
+        let end = rest
+            .strip_prefix('>')
+            .or_else(|| rest.strip_prefix("/>").filter(|_| !closing));
+        if let Some(after) = end {
+            let tag = RawTag {
+                name,
+                closing,
+                attributes,
+            };
+            return Some((tag, s.len() - after.len()));
+        }
+        if closing || !spaced {
+            return None;
+        }
+        let attr_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+            .unwrap_or(rest.len());
+        if attr_len == 0 {
+            return None;
+        }
+        let attribute = rest[..attr_len].to_ascii_lowercase();
+        rest = &rest[attr_len..];
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let (value, after) = attribute_value(after)?;
+                rest = after;
+                value
+            }
+            None => "",
+        };
+        attributes.push((attribute, value));
+    }
+}
+
+/// A quoted or bare attribute value at the start of `s`, and what
+/// follows it.
+fn attribute_value(s: &str) -> Option<(&str, &str)> {
+    if let Some(quote) = s.chars().next().filter(|&c| matches!(c, '"' | '\'')) {
+        let end = s[1..].find(quote)?;
+        return Some((&s[1..1 + end], &s[end + 2..]));
+    }
+    let end = s
+        .find(|c: char| c.is_whitespace() || c == '>')
+        .unwrap_or(s.len());
+    let value = &s[..end];
+    if value.is_empty() || value.contains(['"', '\'', '<', '=', '`']) {
+        return None;
+    }
+    Some((value, &s[end..]))
+}
+
+/// The opening tag rebuilt from the attributes `policy` allows, or
+/// `None` for an iframe with no allowed `src`.
+fn open_tag(tag: &RawTag, policy: &HtmlPolicy) -> Option<String> {
+    let mut out = format!("<{}", tag.name);
+    let mut has_src = false;
+    for (attribute, value) in &tag.attributes {
+        if !policy.allows_attribute(&tag.name, attribute) || (tag.name == "a" && attribute == "rel")
+        {
+            continue;
+        }
+        if URL_ATTRIBUTES.contains(&attribute.as_str()) {
+            if !policy.allows_url(&tag.name, value) {
+                continue;
+            }
+            has_src |= attribute == "src";
+        }
+        out.push_str(&format!(" {}=\"{}\"", attribute, escape_html(value)));
+    }
+    if tag.name == "iframe" && !has_src {
+        return None;
+    }
+    if tag.name == "a" {
+        out.push_str(r#" rel="nofollow noopener noreferrer""#);
+    }
+    out.push('>');
+    Some(out)
+}
+
+/// Emphasis only wraps text that doesn't start or end with a space,
+/// so `2 * 3 * 4` stays arithmetic.
+fn tight(inner: &str) -> bool {
+    !inner.is_empty() && inner.trim() == inner
+}
+
+/// `[label](url)` at the start of `s`, with an http(s) URL and no
+/// whitespace in it. Returns the label, the URL and the bytes consumed.
+fn link(s: &str) -> Option<(&str, &str, usize)> {
+    let close = s.find("](")?;
+    let label = &s[1..close];
+    if label.is_empty() || label.contains(['[', ']']) {
+        return None;
+    }
+    let url_start = close + 2;
+    let url_len = s[url_start..].find(')')?;
+    let url = &s[url_start..url_start + url_len];
+    let scheme_ok = url.starts_with("https://") || url.starts_with("http://");
+    if !scheme_ok || url.chars().any(char::is_whitespace) {
+        return None;
+    }
+    Some((label, url, url_start + url_len + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{html_policy, HtmlPolicy};
+
+    fn render(src: &str) -> String {
+        super::render(src, &HtmlPolicy::default())
+    }
+
+    #[test]
+    fn paragraphs_and_line_breaks() {
+        assert_eq!(
+            render("one\ntwo\n\nthree"),
+            "<p>one<br>\ntwo</p>\n<p>three</p>\n"
+        );
+        assert_eq!(render("  \n\n"), "");
+    }
+
+    #[test]
+    fn inline_markup() {
+        assert_eq!(
+            render("**bold** and *em* and `x < y`"),
+            "<p><strong>bold</strong> and <em>em</em> and <code>x &lt; y</code></p>\n"
+        );
+        assert_eq!(render("2 * 3 * 4"), "<p>2 * 3 * 4</p>\n");
+        assert_eq!(render("a ** b"), "<p>a ** b</p>\n");
+    }
+
+    #[test]
+    fn raw_html_is_escaped() {
+        assert_eq!(
+            render("<script>alert('x')</script>"),
+            "<p>&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt;</p>\n"
+        );
+        assert_eq!(
+            render("**<img src=x onerror=y>**"),
+            "<p><strong>&lt;img src=x onerror=y&gt;</strong></p>\n"
+        );
+    }
+
+    #[test]
+    fn only_http_links_become_anchors() {
+        let html = render("[site](https://example.com/a?b=1&c=\"2\")");
+        assert!(html.starts_with(
+            r#"<p><a href="https://example.com/a?b=1&amp;c=&quot;2&quot;" rel="nofollow noopener noreferrer""#
+        ));
+        assert!(html.ends_with(">site</a></p>\n"));
+
+        assert_eq!(
+            render("[x](javascript:alert(1))"),
+            "<p>[x](javascript:alert(1))</p>\n"
+        );
+        assert_eq!(
+            render("[x](https://a.b onclick=y)"),
+            "<p>[x](https://a.b onclick=y)</p>\n"
+        );
+    }
+
+    #[test]
+    fn allowed_tags_keep_only_allowed_attributes() {
+        assert_eq!(
+            render(r#"<B onclick="x">hi</b> <a href='https://e.com' rel=opener style="x">e</a>"#),
+            "<p><b>hi</b> <a href=\"https://e.com\" rel=\"nofollow noopener noreferrer\">e</a></p>\n"
+        );
+        assert_eq!(
+            render(r#"<a href="javascript:alert(1)">x</a>"#),
+            "<p><a rel=\"nofollow noopener noreferrer\">x</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn raw_tags_are_balanced_per_line() {
+        assert_eq!(render("<b>open\nnext"), "<p><b>open</b><br>\nnext</p>\n");
+        assert_eq!(render("stray</b>"), "<p>stray&lt;/b&gt;</p>\n");
+        assert_eq!(render("<em><b>x</em>"), "<p><em><b>x</b></em></p>\n");
+    }
+
+    #[test]
+    fn allowlisted_iframe_survives_and_script_never_does() {
+        let mut policy = HtmlPolicy::default();
+        policy.set(html_policy::TAGS_KEY, "b, iframe").unwrap();
+        policy
+            .set(
+                html_policy::ATTRIBUTES_KEY,
+                "iframe.src, iframe.allowfullscreen",
+            )
+            .unwrap();
+        policy
+            .set(html_policy::IFRAME_HOSTS_KEY, "youtube-nocookie.com")
+            .unwrap();
+        assert!(policy.set(html_policy::TAGS_KEY, "iframe, script").is_err());
+
+        let embed =
+            r#"<iframe src="https://www.youtube-nocookie.com/embed/abc" allowfullscreen></iframe>"#;
+        assert_eq!(
+            super::render(embed, &policy),
+            "<p><iframe src=\"https://www.youtube-nocookie.com/embed/abc\" allowfullscreen=\"\"></iframe></p>\n"
+        );
+
+        let elsewhere = super::render(r#"<iframe src="https://evil.test/x"></iframe>"#, &policy);
+        assert!(!elsewhere.contains("<iframe"), "{}", elsewhere);
+
+        let script = super::render("<script>alert(1)</script><b>ok</b>", &policy);
+        assert!(!script.contains("<script"), "{}", script);
+        assert!(script.contains("<b>ok</b>"), "{}", script);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod templates;
 pub mod portal;
+pub mod markdown;
 pub mod uploads;
 
 use axum::Router;
@@ -41,12 +42,26 @@ pub fn create_web_routes(state: AppState) -> Router {
         // Email verification landing (from signup email link)
         .route("/verify", get(templates::verify::verify_handler))
 
+        // Stripe Checkout return page for the post-signup dues payment
+        .route("/signup/payment", get(templates::signup::signup_payment_page))
+
         // Password reset flow
         .route("/forgot-password", get(templates::reset::forgot_password_page))
         .route("/forgot-password", post(templates::reset::forgot_password_handler))
         .route("/reset-password", get(templates::reset::reset_password_page))
         .route("/reset-password", post(templates::reset::reset_password_handler))
 
+        // Contact-the-admins form (linked from the login page)
+        .route("/contact", get(templates::contact::contact_page))
+        .route("/contact", post(templates::contact::contact_handler))
+
+        // Public share pages (link previews for social media and chat)
+        .route("/events/:id", get(templates::share::event_share_page))
+        .route("/announcements/:id", get(templates::share::announcement_share_page))
+
+        // Embeddable widgets for the org's website (framed by other sites)
+        .route("/public/embed/events", get(templates::embed::embed_events))
+
         // Portal routes
         .nest("/portal", portal::create_portal_routes(state.clone()))
 
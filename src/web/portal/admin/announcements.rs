@@ -6,6 +6,7 @@ use axum::{
     response::IntoResponse,
     Extension,
 };
+use axum_extra::extract::CookieJar;
 use serde::Deserialize;
 
 use crate::{
@@ -15,21 +16,28 @@ use crate::{
     },
     auth::CsrfService,
     config::Settings,
-    repository::AnnouncementRepository,
+    domain::{contrasting_text_color, list_defaults, HtmlPolicy},
+    repository::{AnnouncementFilter, AnnouncementRepository, SortOrder},
     service::announcement_admin_service::{
-        AnnouncementAdminService, CreateAnnouncementInput, UpdateAnnouncementInput,
+        check_expiry, AnnouncementAdminService, CreateAnnouncementInput, UpdateAnnouncementInput,
+        EXPIRY_BEFORE_PUBLISH,
+    },
+    service::announcement_comment_service::{AnnouncementComment, AnnouncementCommentService},
+    service::settings_service::SettingsService,
+    web::portal::admin::{
+        forms::{form_invalid, form_saved, FormErrors},
+        pagination, partials,
     },
-    web::portal::admin::partials,
     web::templates::{BaseContext, HtmlTemplate},
     web::uploads::save_uploaded_file,
 };
 
-/// Parse the form's `scheduled_publish_at` value (HTML `datetime-local`,
-/// `YYYY-MM-DDTHH:MM` or with seconds) into an Option<DateTime<Utc>>.
-/// Empty input or unparseable input → None (we treat invalid as "not
-/// scheduled" rather than rejecting the whole form for v1 — form-side
-/// validation can be tightened later).
-fn parse_scheduled_publish_at(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+/// Parse a `scheduled_publish_at` / `expires_at` form value (HTML
+/// `datetime-local`, `YYYY-MM-DDTHH:MM` or with seconds) into an
+/// Option<DateTime<Utc>>. Empty input or unparseable input → None. The
+/// create form reports the unparseable case back as a field error; the
+/// edit form still treats it as "not set".
+fn parse_datetime_local(raw: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -52,6 +60,8 @@ pub struct TypeOption {
     pub name: String,
     pub slug: String,
     pub color: Option<String>,
+    /// Badge text color for `color`; see `contrasting_text_color`.
+    pub text_color: Option<&'static str>,
 }
 
 #[derive(Template)]
@@ -93,6 +103,8 @@ pub struct AdminAnnouncementInfo {
     pub featured: bool,
     pub published_at: Option<String>,
     pub is_published: bool,
+    /// Past its `expires_at`: hidden from members and the public site.
+    pub is_expired: bool,
     pub created_at: String,
     pub content_preview: String,
     pub image_url: Option<String>,
@@ -105,6 +117,7 @@ pub struct AdminAnnouncementsQuery {
     pub announcement_type: Option<String>,
     pub status: Option<String>,
     pub page: Option<i64>,
+    pub per_page: Option<i64>,
     pub sort: Option<String>,
     pub order: Option<String>,
 }
@@ -112,9 +125,11 @@ pub struct AdminAnnouncementsQuery {
 pub async fn admin_announcements_page(
     State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
+    State(settings_service): State<Arc<SettingsService>>,
     headers: axum::http::HeaderMap,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
+    jar: CookieJar,
     Query(query): Query<AdminAnnouncementsQuery>,
 ) -> impl IntoResponse {
     let is_htmx = headers.get("HX-Request").is_some();
@@ -122,127 +137,63 @@ pub async fn admin_announcements_page(
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
 
     let page = query.page.unwrap_or(1).max(1);
-    let per_page: i64 = 20;
+    let (per_page, jar) = pagination::per_page(query.per_page, jar);
 
-    let search_query = query.q.clone().unwrap_or_default().to_lowercase();
     let type_filter = query.announcement_type.clone().unwrap_or_default();
     let status_filter = query.status.clone().unwrap_or_default();
-    let sort_field = query
-        .sort
-        .clone()
-        .unwrap_or_else(|| "created_at".to_string());
-    let sort_order = query.order.clone().unwrap_or_else(|| "desc".to_string());
-
-    let all_announcements = announcement_repo.list(1000, 0).await.unwrap_or_default();
-
-    let mut filtered_announcements: Vec<_> = all_announcements
-        .into_iter()
-        .filter(|a| {
-            if !search_query.is_empty() {
-                let matches = a.title.to_lowercase().contains(&search_query)
-                    || a.content.to_lowercase().contains(&search_query);
-                if !matches {
-                    return false;
-                }
-            }
-            if !type_filter.is_empty() && format!("{:?}", a.announcement_type) != type_filter {
-                return false;
-            }
-            if !status_filter.is_empty() {
-                let is_published = a.published_at.is_some();
-                match status_filter.as_str() {
-                    "published" => {
-                        if !is_published {
-                            return false;
-                        }
-                    }
-                    "draft" => {
-                        if is_published {
-                            return false;
-                        }
-                    }
-                    "featured" => {
-                        if !a.featured {
-                            return false;
-                        }
-                    }
-                    "public" => {
-                        if !a.is_public {
-                            return false;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            true
-        })
-        .collect();
-
-    match sort_field.as_str() {
-        "title" => {
-            filtered_announcements.sort_by(|a, b| {
-                if sort_order == "asc" {
-                    a.title.to_lowercase().cmp(&b.title.to_lowercase())
-                } else {
-                    b.title.to_lowercase().cmp(&a.title.to_lowercase())
-                }
-            });
-        }
-        "type" => {
-            filtered_announcements.sort_by(|a, b| {
-                let a_type = format!("{:?}", a.announcement_type);
-                let b_type = format!("{:?}", b.announcement_type);
-                if sort_order == "asc" {
-                    a_type.cmp(&b_type)
-                } else {
-                    b_type.cmp(&a_type)
-                }
-            });
-        }
-        "published_at" => {
-            filtered_announcements.sort_by(|a, b| {
-                if sort_order == "asc" {
-                    a.published_at.cmp(&b.published_at)
-                } else {
-                    b.published_at.cmp(&a.published_at)
-                }
-            });
-        }
-        _ => {
-            filtered_announcements.sort_by(|a, b| {
-                if sort_order == "asc" {
-                    a.created_at.cmp(&b.created_at)
-                } else {
-                    b.created_at.cmp(&a.created_at)
-                }
-            });
-        }
-    }
+    // Params the request leaves out fall back to the club's defaults.
+    let sort_field = match query.sort.clone() {
+        Some(sort) => sort,
+        None => settings_service
+            .list_default(&list_defaults::ANNOUNCEMENT_SORT)
+            .await
+            .to_string(),
+    };
+    let sort_order = match query.order.clone() {
+        Some(order) => order,
+        None => settings_service
+            .list_default(&list_defaults::ANNOUNCEMENT_ORDER)
+            .await
+            .to_string(),
+    };
 
-    let total_announcements = filtered_announcements.len() as i64;
+    // An unrecognized sort or order falls back to newest-created first.
+    let filter = AnnouncementFilter {
+        search: query.q.clone().filter(|s| !s.trim().is_empty()),
+        announcement_type: type_filter.parse().ok(),
+        status: status_filter.parse().ok(),
+        sort: sort_field.parse().unwrap_or_default(),
+        order: sort_order.parse().unwrap_or(SortOrder::Desc),
+    };
+    let total_announcements = announcement_repo.count(&filter).await.unwrap_or(0);
     let total_pages = (total_announcements + per_page - 1) / per_page;
-    let offset = ((page - 1) * per_page) as usize;
-    let paginated_announcements: Vec<AdminAnnouncementInfo> = filtered_announcements
+    let page_announcements = announcement_repo
+        .search(&filter, per_page, (page - 1) * per_page)
+        .await
+        .unwrap_or_default();
+    let now = chrono::Utc::now();
+
+    let paginated_announcements: Vec<AdminAnnouncementInfo> = page_announcements
         .into_iter()
-        .skip(offset)
-        .take(per_page as usize)
         .map(|a| {
             let content_preview = if a.content.len() > 100 {
                 format!("{}...", &a.content[..100])
             } else {
                 a.content.clone()
             };
+            let is_expired = a.is_expired_at(now);
             AdminAnnouncementInfo {
                 id: a.id.to_string(),
                 title: a.title,
-                announcement_type: format!("{:?}", a.announcement_type),
+                announcement_type: a.announcement_type.to_string(),
                 is_public: a.is_public,
                 featured: a.featured,
                 published_at: a
                     .published_at
-                    .map(|dt| dt.format("%b %d, %Y %H:%M").to_string()),
+                    .map(|dt| current_user.locale.date_time(&dt)),
                 is_published: a.published_at.is_some(),
-                created_at: a.created_at.format("%b %d, %Y").to_string(),
+                is_expired,
+                created_at: current_user.locale.short_date(&a.created_at),
                 content_preview,
                 image_url: a.image_url,
             }
@@ -253,7 +204,7 @@ pub async fn admin_announcements_page(
     let type_filter_val = query.announcement_type.unwrap_or_default();
     let status_filter_val = query.status.unwrap_or_default();
 
-    if is_htmx {
+    let body = if is_htmx {
         HtmlTemplate(AdminAnnouncementsTableTemplate {
             announcements: paginated_announcements,
             total_announcements,
@@ -282,15 +233,51 @@ pub async fn admin_announcements_page(
             sort_order,
         })
         .into_response()
-    }
+    };
+    (jar, body)
 }
 
 #[derive(Template)]
 #[template(path = "admin/announcement_detail.html")]
 pub struct AdminAnnouncementDetailTemplate {
     pub base: BaseContext,
+    pub announcement_id: String,
     pub announcement: AdminAnnouncementDetail,
     pub announcement_types: Vec<TypeOption>,
+    pub comments: Vec<AdminCommentInfo>,
+}
+
+/// Just the comments card, re-rendered after each moderation action.
+#[derive(Template)]
+#[template(path = "admin/_announcement_comments.html")]
+pub struct AdminAnnouncementCommentsTemplate {
+    pub announcement_id: String,
+    pub comments: Vec<AdminCommentInfo>,
+}
+
+pub struct AdminCommentInfo {
+    pub id: String,
+    pub author: String,
+    /// Sanitized render of the member's Markdown.
+    pub body_html: String,
+    pub created_at: String,
+    pub is_hidden: bool,
+}
+
+impl AdminCommentInfo {
+    fn from_comment(
+        comment: AnnouncementComment,
+        locale: &crate::domain::Locale,
+        policy: &HtmlPolicy,
+    ) -> Self {
+        Self {
+            id: comment.id.to_string(),
+            author: comment.author,
+            body_html: crate::web::markdown::render(&comment.body, policy),
+            created_at: locale.date_time(&comment.created_at),
+            is_hidden: comment.hidden_at.is_some(),
+        }
+    }
 }
 
 pub struct AdminAnnouncementDetail {
@@ -310,11 +297,46 @@ pub struct AdminAnnouncementDetail {
     pub scheduled_publish_at_input: String,
     /// Human-friendly display for the sidebar — None if not scheduled.
     pub scheduled_publish_at_display: Option<String>,
+    /// Form-input value for the expiry field, same format as
+    /// `scheduled_publish_at_input`.
+    pub expires_at_input: String,
+    /// Sidebar display — None if the announcement never expires.
+    pub expires_at_display: Option<String>,
+    pub is_expired: bool,
+    pub comments_enabled: bool,
+    /// The edit form's notify select: "", "true" or "false".
+    pub notify_members: &'static str,
+    /// Whether the publish form's "Notify members" box starts ticked:
+    /// the row's choice (or its type's default), unless members were
+    /// already notified on an earlier publish.
+    pub notify_on_publish: bool,
+    /// Sidebar display — None if members were never notified.
+    pub notified_at: Option<String>,
+}
+
+/// The notify select's value for a stored choice, and back. An empty
+/// value follows the announcement type's default.
+fn notify_choice_value(choice: Option<bool>) -> &'static str {
+    match choice {
+        Some(true) => "true",
+        Some(false) => "false",
+        None => "",
+    }
+}
+
+fn parse_notify_choice(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
 }
 
 pub async fn admin_announcement_detail_page(
     State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
     State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(settings_service): State<Arc<SettingsService>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
@@ -346,32 +368,61 @@ pub async fn admin_announcement_detail_page(
         .unwrap_or_default();
     let scheduled_publish_at_display = announcement
         .scheduled_publish_at
-        .map(|dt| dt.format("%b %d, %Y %H:%M UTC").to_string());
+        .map(|dt| format!("{} UTC", current_user.locale.date_time(&dt)));
+
+    let expires_at_input = announcement
+        .expires_at
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M").to_string())
+        .unwrap_or_default();
+    let expires_at_display = announcement
+        .expires_at
+        .map(|dt| format!("{} UTC", current_user.locale.date_time(&dt)));
+    let is_expired = announcement.is_expired_at(chrono::Utc::now());
+    let notify_by_default = match (announcement.notify_members, announcement.announcement_type_id) {
+        (Some(choice), _) => choice,
+        (None, Some(type_id)) => announcement_repo
+            .type_notifies_members(type_id)
+            .await
+            .unwrap_or(true),
+        (None, None) => true,
+    };
 
     let detail = AdminAnnouncementDetail {
         id: announcement.id.to_string(),
         title: announcement.title,
         content: announcement.content,
-        announcement_type: format!("{:?}", announcement.announcement_type),
+        announcement_type: announcement.announcement_type.to_string(),
         is_public: announcement.is_public,
         featured: announcement.featured,
         image_url: announcement.image_url,
         published_at: announcement
             .published_at
-            .map(|dt| dt.format("%b %d, %Y %H:%M").to_string()),
+            .map(|dt| current_user.locale.date_time(&dt)),
         is_published: announcement.published_at.is_some(),
-        created_at: announcement
-            .created_at
-            .format("%b %d, %Y %H:%M")
-            .to_string(),
-        updated_at: announcement
-            .updated_at
-            .format("%b %d, %Y %H:%M")
-            .to_string(),
+        created_at: current_user.locale.date_time(&announcement.created_at),
+        updated_at: current_user.locale.date_time(&announcement.updated_at),
         scheduled_publish_at_input,
         scheduled_publish_at_display,
+        expires_at_input,
+        expires_at_display,
+        is_expired,
+        comments_enabled: announcement.comments_enabled,
+        notify_members: notify_choice_value(announcement.notify_members),
+        notify_on_publish: notify_by_default && announcement.notified_at.is_none(),
+        notified_at: announcement
+            .notified_at
+            .map(|dt| current_user.locale.date_time(&dt)),
     };
 
+    let policy = settings_service.html_policy().await;
+    let comments = comment_service
+        .list_for_admin(id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| AdminCommentInfo::from_comment(c, &current_user.locale, &policy))
+        .collect();
+
     // Fetch active announcement types for the dropdown
     let announcement_types = announcement_type_service
         .0
@@ -383,35 +434,64 @@ pub async fn admin_announcement_detail_page(
             id: t.id.to_string(),
             name: t.name,
             slug: t.slug,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
             color: t.color,
         })
         .collect();
 
     HtmlTemplate(AdminAnnouncementDetailTemplate {
         base,
+        announcement_id: detail.id.clone(),
         announcement: detail,
         announcement_types,
+        comments,
     })
     .into_response()
 }
 
+/// What the admin typed into the new-announcement form, echoed back
+/// when validation fails. The image can't be echoed — browsers never
+/// pre-fill file inputs.
+#[derive(Debug, Clone, Default)]
+pub struct AnnouncementFormValues {
+    pub title: String,
+    pub content: String,
+    pub announcement_type: String,
+    pub is_public: bool,
+    pub featured: bool,
+    pub publish_now: bool,
+    pub comments_enabled: bool,
+    /// "", "true" or "false"; see `parse_notify_choice`.
+    pub notify_members: String,
+    pub scheduled_publish_at: String,
+    pub expires_at: String,
+}
+
 #[derive(Template)]
 #[template(path = "admin/announcement_new.html")]
 pub struct AdminNewAnnouncementTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
     pub announcement_types: Vec<TypeOption>,
+    pub values: AnnouncementFormValues,
+    pub errors: FormErrors,
 }
 
-pub async fn admin_new_announcement_page(
-    State(announcement_type_service): State<AnnouncementBasicTypeService>,
-    State(csrf_service): State<Arc<CsrfService>>,
-    Extension(current_user): Extension<CurrentUser>,
-    Extension(session_info): Extension<SessionInfo>,
-) -> impl IntoResponse {
-    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
+/// Just the `<form>`, for re-rendering after a failed submit.
+#[derive(Template)]
+#[template(path = "admin/_announcement_form.html")]
+pub struct AdminAnnouncementFormTemplate {
+    pub csrf_token: String,
+    pub announcement_types: Vec<TypeOption>,
+    pub values: AnnouncementFormValues,
+    pub errors: FormErrors,
+}
 
-    // Fetch active announcement types for the dropdown
-    let announcement_types = announcement_type_service
+/// Active announcement types for the form's dropdown.
+async fn announcement_type_options(
+    announcement_type_service: &AnnouncementBasicTypeService,
+) -> Vec<TypeOption> {
+    announcement_type_service
         .0
         .list(false)
         .await
@@ -421,83 +501,139 @@ pub async fn admin_new_announcement_page(
             id: t.id.to_string(),
             name: t.name,
             slug: t.slug,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
             color: t.color,
         })
-        .collect();
+        .collect()
+}
+
+pub async fn admin_new_announcement_page(
+    State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+) -> impl IntoResponse {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
 
     HtmlTemplate(AdminNewAnnouncementTemplate {
+        csrf_token: base.csrf_token.clone(),
         base,
-        announcement_types,
+        announcement_types: announcement_type_options(&announcement_type_service).await,
+        values: AnnouncementFormValues::default(),
+        errors: FormErrors::new(),
     })
     .into_response()
 }
 
+/// Check the submitted values and build the service input. The image
+/// is attached by the caller once everything else has passed.
+fn validate_announcement_form(
+    values: &AnnouncementFormValues,
+) -> Result<CreateAnnouncementInput, FormErrors> {
+    use crate::domain::AnnouncementType;
+
+    let mut errors = FormErrors::new();
+
+    if values.title.trim().is_empty() {
+        errors.add("title", "Title is required");
+    }
+    if values.content.trim().is_empty() {
+        errors.add("content", "Content is required");
+    }
+
+    let scheduled_publish_at = parse_datetime_local(&values.scheduled_publish_at);
+    if scheduled_publish_at.is_none() && !values.scheduled_publish_at.trim().is_empty() {
+        errors.add("scheduled_publish_at", "Invalid date and time");
+    }
+
+    let expires_at = parse_datetime_local(&values.expires_at);
+    if expires_at.is_none() && !values.expires_at.trim().is_empty() {
+        errors.add("expires_at", "Invalid date and time");
+    }
+    // Same rule the service enforces, checked here so the message
+    // lands on the field.
+    let publishes_at = if values.publish_now {
+        None
+    } else {
+        scheduled_publish_at
+    };
+    if check_expiry(expires_at, publishes_at, chrono::Utc::now()).is_err() {
+        errors.add("expires_at", EXPIRY_BEFORE_PUBLISH);
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let announcement_type = values
+        .announcement_type
+        .parse()
+        .unwrap_or(AnnouncementType::General);
+
+    Ok(CreateAnnouncementInput {
+        title: values.title.trim().to_string(),
+        content: values.content.clone(),
+        announcement_type,
+        announcement_type_id: None,
+        is_public: values.is_public,
+        featured: values.featured,
+        image_url: None,
+        publish_now: values.publish_now,
+        scheduled_publish_at,
+        expires_at,
+        comments_enabled: values.comments_enabled,
+        notify_members: parse_notify_choice(&values.notify_members),
+    })
+}
+
 pub async fn admin_create_announcement(
     State(settings): State<Arc<Settings>>,
     State(announcement_admin_service): State<Arc<AnnouncementAdminService>>,
+    State(announcement_type_service): State<AnnouncementBasicTypeService>,
     Extension(current_user): Extension<CurrentUser>,
     mut multipart: Multipart,
 ) -> impl IntoResponse {
-    use crate::domain::AnnouncementType;
-
     // Parse multipart form
-    let mut title = String::new();
-    let mut content = String::new();
-    let mut announcement_type_str = String::new();
-    let mut is_public = false;
-    let mut featured = false;
-    let mut publish_now = false;
-    let mut image_url: Option<String> = None;
-    let mut scheduled_publish_at_str = String::new();
+    let mut values = AnnouncementFormValues::default();
+    let mut csrf_token = String::new();
+    let mut image: Option<(String, axum::body::Bytes)> = None;
 
     while let Ok(Some(field)) = multipart.next_field().await {
         let name = field.name().unwrap_or("").to_string();
 
         match name.as_str() {
-            "csrf_token" => {
-                let _ = field.text().await;
+            "csrf_token" => csrf_token = field.text().await.unwrap_or_default(),
+            "title" => values.title = field.text().await.unwrap_or_default(),
+            "content" => values.content = field.text().await.unwrap_or_default(),
+            "announcement_type" => {
+                values.announcement_type = field.text().await.unwrap_or_default()
             }
-            "title" => title = field.text().await.unwrap_or_default(),
-            "content" => content = field.text().await.unwrap_or_default(),
-            "announcement_type" => announcement_type_str = field.text().await.unwrap_or_default(),
             "is_public" => {
-                is_public = true;
+                values.is_public = true;
                 let _ = field.text().await;
             }
             "featured" => {
-                featured = true;
+                values.featured = true;
                 let _ = field.text().await;
             }
             "publish_now" => {
-                publish_now = true;
+                values.publish_now = true;
                 let _ = field.text().await;
             }
+            "comments_enabled" => {
+                values.comments_enabled = true;
+                let _ = field.text().await;
+            }
+            "notify_members" => values.notify_members = field.text().await.unwrap_or_default(),
             "scheduled_publish_at" => {
-                scheduled_publish_at_str = field.text().await.unwrap_or_default();
+                values.scheduled_publish_at = field.text().await.unwrap_or_default();
             }
+            "expires_at" => values.expires_at = field.text().await.unwrap_or_default(),
             "image" => {
                 let filename = field.file_name().unwrap_or("").to_string();
-                if !filename.is_empty() {
-                    if let Ok(data) = field.bytes().await {
-                        if !data.is_empty() {
-                            match save_uploaded_file(
-                                &settings.server.uploads_path(),
-                                &filename,
-                                &data,
-                            )
-                            .await
-                            {
-                                Ok(path) => image_url = Some(path),
-                                Err(e) => {
-                                    return partials::admin_alert(
-                                        "error",
-                                        &format!("Error uploading image: {}", e),
-                                        false,
-                                    )
-                                    .into_response()
-                                }
-                            }
-                        }
+                if let Ok(data) = field.bytes().await {
+                    if !filename.is_empty() && !data.is_empty() {
+                        image = Some((filename, data));
                     }
                 }
             }
@@ -507,43 +643,57 @@ pub async fn admin_create_announcement(
         }
     }
 
-    let announcement_type = match announcement_type_str.as_str() {
-        "News" => AnnouncementType::News,
-        "Achievement" => AnnouncementType::Achievement,
-        "Meeting" => AnnouncementType::Meeting,
-        "CTFResult" => AnnouncementType::CTFResult,
-        "General" => AnnouncementType::General,
-        _ => AnnouncementType::General,
+    let invalid = |errors: FormErrors, announcement_types: Vec<TypeOption>| {
+        form_invalid(AdminAnnouncementFormTemplate {
+            csrf_token: csrf_token.clone(),
+            announcement_types,
+            values: values.clone(),
+            errors,
+        })
     };
 
-    let scheduled_publish_at = parse_scheduled_publish_at(&scheduled_publish_at_str);
-
-    let input = CreateAnnouncementInput {
-        title,
-        content,
-        announcement_type,
-        announcement_type_id: None,
-        is_public,
-        featured,
-        image_url,
-        publish_now,
-        scheduled_publish_at,
+    let announcement_types = announcement_type_options(&announcement_type_service).await;
+    let mut input = match validate_announcement_form(&values) {
+        Ok(input) => input,
+        Err(errors) => return invalid(errors, announcement_types),
     };
+    // The dropdown sends the type's name; keep its id too so the
+    // type's settings (like its notify default) apply.
+    input.announcement_type_id = announcement_types
+        .iter()
+        .find(|t| t.name == values.announcement_type)
+        .and_then(|t| t.id.parse().ok());
+
+    if let Some((filename, data)) = image {
+        match save_uploaded_file(
+            &settings.server.uploads_path(),
+            &settings.images,
+            &filename,
+            &data,
+        )
+        .await
+        {
+            Ok(path) => input.image_url = Some(path),
+            Err(e) => {
+                let mut errors = FormErrors::new();
+                errors.add("image", format!("Error uploading image: {}", e));
+                return invalid(errors, announcement_types);
+            }
+        }
+    }
 
     match announcement_admin_service
         .create(current_user.member.id, input)
         .await
     {
-        Ok(created) => {
-            axum::response::Redirect::to(&format!("/portal/admin/announcements/{}", created.id))
-                .into_response()
-        }
-        Err(e) => partials::admin_alert(
-            "error",
-            &format!("Error creating announcement: {}", e),
-            false,
-        )
-        .into_response(),
+        Ok(created) => form_saved(
+            &format!("/portal/admin/announcements/{}", created.id),
+            "Announcement created",
+        ),
+        Err(e) => invalid(
+            FormErrors::form_level(format!("Error creating announcement: {}", e)),
+            announcement_types,
+        ),
     }
 }
 
@@ -581,9 +731,12 @@ pub async fn admin_update_announcement(
     let mut announcement_type_str = String::new();
     let mut is_public = false;
     let mut featured = false;
+    let mut comments_enabled = false;
+    let mut notify_members = String::new();
     let mut new_image_url: Option<String> = None;
     let mut remove_image = false;
     let mut scheduled_publish_at_str = String::new();
+    let mut expires_at_str = String::new();
 
     while let Ok(Some(field)) = multipart.next_field().await {
         let name = field.name().unwrap_or("").to_string();
@@ -603,6 +756,11 @@ pub async fn admin_update_announcement(
                 featured = true;
                 let _ = field.text().await;
             }
+            "comments_enabled" => {
+                comments_enabled = true;
+                let _ = field.text().await;
+            }
+            "notify_members" => notify_members = field.text().await.unwrap_or_default(),
             "remove_image" => {
                 remove_image = true;
                 let _ = field.text().await;
@@ -610,6 +768,7 @@ pub async fn admin_update_announcement(
             "scheduled_publish_at" => {
                 scheduled_publish_at_str = field.text().await.unwrap_or_default();
             }
+            "expires_at" => expires_at_str = field.text().await.unwrap_or_default(),
             "image" => {
                 let filename = field.file_name().unwrap_or("").to_string();
                 if !filename.is_empty() {
@@ -617,6 +776,7 @@ pub async fn admin_update_announcement(
                         if !data.is_empty() {
                             match save_uploaded_file(
                                 &settings.server.uploads_path(),
+                                &settings.images,
                                 &filename,
                                 &data,
                             )
@@ -642,14 +802,9 @@ pub async fn admin_update_announcement(
         }
     }
 
-    let announcement_type = match announcement_type_str.as_str() {
-        "News" => AnnouncementType::News,
-        "Achievement" => AnnouncementType::Achievement,
-        "Meeting" => AnnouncementType::Meeting,
-        "CTFResult" => AnnouncementType::CTFResult,
-        "General" => AnnouncementType::General,
-        _ => AnnouncementType::General,
-    };
+    let announcement_type = announcement_type_str
+        .parse()
+        .unwrap_or(AnnouncementType::General);
 
     // Determine final image_url: new upload > remove > keep existing.
     // Capture the old URL so we can delete it from disk after save.
@@ -667,7 +822,8 @@ pub async fn admin_update_announcement(
         None
     };
 
-    let scheduled_publish_at = parse_scheduled_publish_at(&scheduled_publish_at_str);
+    let scheduled_publish_at = parse_datetime_local(&scheduled_publish_at_str);
+    let expires_at = parse_datetime_local(&expires_at_str);
 
     let input = UpdateAnnouncementInput {
         title,
@@ -678,6 +834,9 @@ pub async fn admin_update_announcement(
         featured,
         image_url,
         scheduled_publish_at,
+        expires_at,
+        comments_enabled,
+        notify_members: parse_notify_choice(&notify_members),
     };
 
     match announcement_admin_service
@@ -743,10 +902,18 @@ pub async fn admin_delete_announcement(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PublishAnnouncementForm {
+    /// HTML checkbox: present when checked, absent otherwise.
+    #[serde(default)]
+    pub notify_members: Option<String>,
+}
+
 pub async fn admin_publish_announcement(
     State(announcement_admin_service): State<Arc<AnnouncementAdminService>>,
     Extension(current_user): Extension<CurrentUser>,
     Path(announcement_id): Path<String>,
+    axum::Form(form): axum::Form<PublishAnnouncementForm>,
 ) -> impl IntoResponse {
     let id = match uuid::Uuid::parse_str(&announcement_id) {
         Ok(id) => id,
@@ -756,7 +923,7 @@ pub async fn admin_publish_announcement(
     };
 
     match announcement_admin_service
-        .publish(current_user.member.id, id)
+        .publish(current_user.member.id, id, Some(form.notify_members.is_some()))
         .await
     {
         Ok(_) => axum::response::Redirect::to(&format!("/portal/admin/announcements/{}", id))
@@ -796,3 +963,102 @@ pub async fn admin_unpublish_announcement(
         .into_response(),
     }
 }
+
+/// Hide, unhide or delete one comment, then re-render the comments
+/// card so the admin sees the result in place.
+async fn moderate_comment(
+    comment_service: &AnnouncementCommentService,
+    settings_service: &SettingsService,
+    current_user: &CurrentUser,
+    announcement_id: &str,
+    comment_id: &str,
+    action: CommentModeration,
+) -> axum::response::Response {
+    let (Ok(id), Ok(comment_id)) = (
+        uuid::Uuid::parse_str(announcement_id),
+        uuid::Uuid::parse_str(comment_id),
+    ) else {
+        return partials::admin_alert("error", "Invalid comment ID", false).into_response();
+    };
+
+    let actor = current_user.member.id;
+    let result = match action {
+        CommentModeration::Hide => comment_service.hide(actor, comment_id).await,
+        CommentModeration::Unhide => comment_service.unhide(actor, comment_id).await,
+        CommentModeration::Delete => comment_service.delete(actor, comment_id).await,
+    };
+    if let Err(e) = result {
+        return partials::admin_alert("error", &format!("Error moderating comment: {}", e), false)
+            .into_response();
+    }
+
+    let policy = settings_service.html_policy().await;
+    match comment_service.list_for_admin(id).await {
+        Ok(comments) => HtmlTemplate(AdminAnnouncementCommentsTemplate {
+            announcement_id: id.to_string(),
+            comments: comments
+                .into_iter()
+                .map(|c| AdminCommentInfo::from_comment(c, &current_user.locale, &policy))
+                .collect(),
+        })
+        .into_response(),
+        Err(_) => partials::admin_alert("error", "Error loading comments", false).into_response(),
+    }
+}
+
+enum CommentModeration {
+    Hide,
+    Unhide,
+    Delete,
+}
+
+pub async fn admin_hide_comment(
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((announcement_id, comment_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    moderate_comment(
+        &comment_service,
+        &settings_service,
+        &current_user,
+        &announcement_id,
+        &comment_id,
+        CommentModeration::Hide,
+    )
+    .await
+}
+
+pub async fn admin_unhide_comment(
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((announcement_id, comment_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    moderate_comment(
+        &comment_service,
+        &settings_service,
+        &current_user,
+        &announcement_id,
+        &comment_id,
+        CommentModeration::Unhide,
+    )
+    .await
+}
+
+pub async fn admin_delete_comment(
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((announcement_id, comment_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    moderate_comment(
+        &comment_service,
+        &settings_service,
+        &current_user,
+        &announcement_id,
+        &comment_id,
+        CommentModeration::Delete,
+    )
+    .await
+}
@@ -17,6 +17,7 @@ use serde::Deserialize;
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
+    domain::Locale,
     service::audit_service::AuditService,
     web::{
         portal::admin::csv::push_csv,
@@ -67,7 +68,7 @@ pub async fn audit_log_page(
     Query(query): Query<AuditLogQuery>,
 ) -> Response {
     let limit = query.limit.unwrap_or(100).clamp(10, 500);
-    let entries = filtered_entries(&audit_service, &query, limit).await;
+    let entries = filtered_entries(&audit_service, &query, limit, current_user.locale).await;
     let export_qs = build_export_qs(&query);
 
     HtmlTemplate(AuditLogTemplate {
@@ -88,6 +89,7 @@ async fn filtered_entries(
     audit_service: &AuditService,
     query: &AuditLogQuery,
     limit: i64,
+    locale: Locale,
 ) -> Vec<AuditEntryDisplay> {
     let raw = audit_service
         .recent(limit * 3) // over-fetch a bit to account for filtering
@@ -118,7 +120,7 @@ async fn filtered_entries(
             action: pretty_action(&e.action),
             entity: format!("{} {}", e.entity_type, short_id(&e.entity_id)),
             detail: format_detail(e.old_value.as_deref(), e.new_value.as_deref()),
-            when: e.created_at.format("%b %d, %Y at %H:%M UTC").to_string(),
+            when: format!("{} UTC", locale.date_time(&e.created_at)),
         })
         .collect()
 }
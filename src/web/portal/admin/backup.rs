@@ -0,0 +1,176 @@
+//! Admin database backups. `POST /portal/admin/backup` snapshots the
+//! live database via BackupService and streams the file straight back
+//! as a download; the page lists earlier snapshots (including ones the
+//! scheduled job took) for re-download. Admin-gated by the portal
+//! admin router like every other page here.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use tokio_util::io::ReaderStream;
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    service::{
+        audit_service::AuditService,
+        backup_service::{BackupFile, BackupService},
+        settings_service::SettingsService,
+    },
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "admin/backup.html")]
+pub struct BackupTemplate {
+    pub base: BaseContext,
+    pub backups: Vec<BackupDisplay>,
+    pub scheduled_enabled: bool,
+    pub interval_hours: i64,
+    pub keep: i64,
+    pub error: Option<String>,
+}
+
+pub struct BackupDisplay {
+    pub name: String,
+    pub size: String,
+    pub when: String,
+}
+
+pub async fn backup_page(
+    State(backup_service): State<Arc<BackupService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+) -> Response {
+    let (backups, error) = match backup_service.list_backups().await {
+        Ok(list) => (list, None),
+        Err(e) => (Vec::new(), Some(e.to_string())),
+    };
+    let backups = backups
+        .into_iter()
+        .map(|b| BackupDisplay {
+            size: human_size(b.size_bytes),
+            when: format!("{} UTC", current_user.locale.date_time(&b.created_at)),
+            name: b.name,
+        })
+        .collect();
+
+    HtmlTemplate(BackupTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        backups,
+        scheduled_enabled: settings_service
+            .get_bool("backup.scheduled_enabled")
+            .await
+            .unwrap_or(false),
+        interval_hours: settings_service
+            .get_number("backup.interval_hours")
+            .await
+            .unwrap_or(24),
+        keep: settings_service.get_number("backup.keep").await.unwrap_or(7),
+        error,
+    })
+    .into_response()
+}
+
+/// Take a fresh backup and return it as a download.
+pub async fn create_backup(
+    State(backup_service): State<Arc<BackupService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Response {
+    let backup = match backup_service.create_backup().await {
+        Ok(b) => b,
+        Err(e) => return e.into_response(),
+    };
+
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "create_backup",
+            "backup",
+            &backup.name,
+            None,
+            Some(&backup.size_bytes.to_string()),
+            None,
+        )
+        .await;
+
+    send_backup(&backup).await
+}
+
+/// Re-download an existing backup by file name.
+pub async fn download_backup(
+    State(backup_service): State<Arc<BackupService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(name): Path<String>,
+) -> Response {
+    let backup = match backup_service.find(&name).await {
+        Ok(b) => b,
+        Err(e) => return e.into_response(),
+    };
+
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "download_backup",
+            "backup",
+            &backup.name,
+            None,
+            None,
+            None,
+        )
+        .await;
+
+    send_backup(&backup).await
+}
+
+/// Stream the snapshot from disk rather than buffering it: a large
+/// club's database shouldn't have to fit in memory to be downloaded.
+/// The file stays put afterwards — it's the retained backup the page
+/// lists, pruned only by the scheduled job's `backup.keep`.
+async fn send_backup(backup: &BackupFile) -> Response {
+    let file = match tokio::fs::File::open(&backup.path).await {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!("Failed to read backup {}: {}", backup.name, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read backup file")
+                .into_response();
+        }
+    };
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/vnd.sqlite3".to_string()),
+            (header::CONTENT_LENGTH, backup.size_bytes.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", backup.name),
+            ),
+        ],
+        Body::from_stream(ReaderStream::new(file)),
+    )
+        .into_response()
+}
+
+fn human_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b < KB {
+        format!("{} B", bytes)
+    } else if b < KB * KB {
+        format!("{:.1} KB", b / KB)
+    } else {
+        format!("{:.1} MB", b / (KB * KB))
+    }
+}
@@ -11,12 +11,18 @@ use axum::{
     response::{IntoResponse, Response},
     Extension,
 };
+use chrono::{TimeZone, Utc};
 
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
-    payments::StripeClient,
-    repository::{MemberRepository, PaymentRepository, ScheduledPaymentRepository},
+    domain::Locale,
+    payments::{webhook_metrics::WebhookCounts, StripeClient, WebhookMetrics},
+    repository::{
+        processed_events_repository::{PRUNE_MARGIN, REPLAY_WINDOW},
+        MemberRepository, PaymentRepository, ProcessedEventsRepository,
+        ScheduledPaymentRepository,
+    },
     service::{audit_service::AuditService, billing_service::BillingService},
     web::templates::{BaseContext, HtmlTemplate},
 };
@@ -188,9 +194,9 @@ pub async fn bulk_migrate_stripe_subs(
 // =====================================================================
 // Billing dashboard — read-only operator overview
 //
-// Three sections: upcoming scheduled (next 30 days), recent failures
+// Four sections: upcoming scheduled (next 30 days), recent failures
 // (last 90 days), revenue by month split into dues vs donations
-// (last 12 months). Every row links to a per-member page where the
+// (last 12 months), and Stripe webhook dedupe counters. Every row links to a per-member page where the
 // actual remediation actions live; this page is observation, not
 // action.
 // =====================================================================
@@ -202,12 +208,19 @@ pub struct AdminBillingDashboardTemplate {
     pub upcoming: Vec<UpcomingScheduledRow>,
     pub failures: Vec<FailedScheduledRow>,
     pub months: Vec<MonthlyRevenueRow>,
+    pub projection: Vec<ProjectedMonthRow>,
     /// 30 / 90 / 12 — surfaced so the section copy stays in sync if
     /// we ever change the windows. (And so the template doesn't
     /// hardcode magic numbers separately.)
     pub upcoming_window_days: i64,
     pub failure_window_days: i64,
     pub revenue_window_months: u32,
+    pub projection_window_months: u32,
+    /// Stripe webhook dedupe / prune counters since the last restart.
+    pub webhooks: WebhookCounts,
+    /// Idempotency claims currently on file.
+    pub webhook_claims: i64,
+    pub webhook_retention_days: i64,
 }
 
 pub struct UpcomingScheduledRow {
@@ -240,15 +253,34 @@ pub struct MonthlyRevenueRow {
     pub total_dollars: String,
 }
 
+/// Expected renewal income for one upcoming month, with the
+/// per-membership-type lines that add up to it.
+pub struct ProjectedMonthRow {
+    pub month_label: String,
+    pub total_dollars: String,
+    pub member_count: i64,
+    pub types: Vec<ProjectedTypeLine>,
+}
+
+pub struct ProjectedTypeLine {
+    pub name: String,
+    pub dollars: String,
+    pub member_count: i64,
+}
+
 const UPCOMING_WINDOW_DAYS: i64 = 30;
 const FAILURE_WINDOW_DAYS: i64 = 90;
 const REVENUE_WINDOW_MONTHS: u32 = 12;
+const PROJECTION_WINDOW_MONTHS: u32 = 12;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn billing_dashboard_page(
     State(csrf_service): State<Arc<CsrfService>>,
     State(member_repo): State<Arc<dyn MemberRepository>>,
     State(payment_repo): State<Arc<dyn PaymentRepository>>,
     State(scheduled_payment_repo): State<Arc<dyn ScheduledPaymentRepository>>,
+    State(processed_events_repo): State<Arc<dyn ProcessedEventsRepository>>,
+    State(webhook_metrics): State<Arc<WebhookMetrics>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
 ) -> Response {
@@ -267,8 +299,10 @@ pub async fn billing_dashboard_page(
         upcoming.push(UpcomingScheduledRow {
             member_id: sp.member_id.to_string(),
             member_name: name,
-            due_date: sp.due_date.format("%b %d, %Y").to_string(),
-            amount_display: format!("${:.2}", sp.amount_cents as f64 / 100.0),
+            due_date: current_user
+                .locale
+                .short_date(&sp.due_date.and_time(chrono::NaiveTime::MIN).and_utc()),
+            amount_display: current_user.locale.currency(sp.amount_cents, &sp.currency),
             retry_count: sp.retry_count,
             status: match sp.status {
                 crate::domain::ScheduledPaymentStatus::Pending => "Pending",
@@ -294,9 +328,9 @@ pub async fn billing_dashboard_page(
             member_name: name,
             last_attempt_display: sp
                 .last_attempt_at
-                .map(|d| d.format("%b %d, %Y %H:%M UTC").to_string())
+                .map(|d| format!("{} UTC", current_user.locale.date_time(&d)))
                 .unwrap_or_else(|| "—".to_string()),
-            amount_display: format!("${:.2}", sp.amount_cents as f64 / 100.0),
+            amount_display: current_user.locale.currency(sp.amount_cents, &sp.currency),
             retry_count: sp.retry_count,
             failure_reason: sp.failure_reason.unwrap_or_else(|| "—".to_string()),
         });
@@ -307,7 +341,17 @@ pub async fn billing_dashboard_page(
         .revenue_by_month(REVENUE_WINDOW_MONTHS)
         .await
         .unwrap_or_default();
-    let months = fold_revenue_buckets(buckets);
+    let months = fold_revenue_buckets(buckets, current_user.locale);
+
+    // ---- Section 4: projected renewals (next 12 months) ----
+    let projection_until = now
+        .checked_add_months(chrono::Months::new(PROJECTION_WINDOW_MONTHS))
+        .unwrap_or(now);
+    let projected = member_repo
+        .dues_projection(now, projection_until)
+        .await
+        .unwrap_or_default();
+    let projection = fold_projection(projected, current_user.locale);
 
     HtmlTemplate(AdminBillingDashboardTemplate {
         base,
@@ -317,6 +361,11 @@ pub async fn billing_dashboard_page(
         upcoming_window_days: UPCOMING_WINDOW_DAYS,
         failure_window_days: FAILURE_WINDOW_DAYS,
         revenue_window_months: REVENUE_WINDOW_MONTHS,
+        projection,
+        projection_window_months: PROJECTION_WINDOW_MONTHS,
+        webhooks: webhook_metrics.snapshot(),
+        webhook_claims: processed_events_repo.count().await.unwrap_or_default(),
+        webhook_retention_days: (REPLAY_WINDOW + PRUNE_MARGIN).num_days(),
     })
     .into_response()
 }
@@ -341,7 +390,10 @@ async fn lookup_member_name(
 /// Fold the flat (year, month, type) buckets into one row per month
 /// with separate dues / donations totals. The flat list comes back
 /// already sorted newest-first, so the order survives.
-fn fold_revenue_buckets(buckets: Vec<crate::repository::MonthlyRevenue>) -> Vec<MonthlyRevenueRow> {
+fn fold_revenue_buckets(
+    buckets: Vec<crate::repository::MonthlyRevenue>,
+    locale: Locale,
+) -> Vec<MonthlyRevenueRow> {
     // Stable insertion-ordered map: BTreeMap keyed on (year, month)
     // sorted DESC; we'd rather not pull in indexmap for one place.
     let mut accum: std::collections::BTreeMap<(i32, u32), [i64; 4]> =
@@ -373,11 +425,15 @@ fn fold_revenue_buckets(buckets: Vec<crate::repository::MonthlyRevenue>) -> Vec<
         .into_iter()
         .rev()
         .map(|((year, month), [dc, dn, oc, on])| {
-            let dollars = |c: i64| format!("${:.2}", c as f64 / 100.0);
+            let dollars = |c: i64| locale.currency(c, "USD");
             let total = dc + oc;
             MonthlyRevenueRow {
                 month_key: format!("{:04}-{:02}", year, month),
-                month_label: format!("{} {}", month_name(month), year),
+                month_label: Utc
+                    .with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .single()
+                    .map(|d| locale.month_year(&d))
+                    .unwrap_or_else(|| format!("{:04}-{:02}", year, month)),
                 dues_dollars: dollars(dc),
                 dues_count: dn,
                 donations_dollars: dollars(oc),
@@ -388,20 +444,47 @@ fn fold_revenue_buckets(buckets: Vec<crate::repository::MonthlyRevenue>) -> Vec<
         .collect()
 }
 
-fn month_name(m: u32) -> &'static str {
-    match m {
-        1 => "January",
-        2 => "February",
-        3 => "March",
-        4 => "April",
-        5 => "May",
-        6 => "June",
-        7 => "July",
-        8 => "August",
-        9 => "September",
-        10 => "October",
-        11 => "November",
-        12 => "December",
-        _ => "—",
+/// Group the (month, type) projection buckets into one row per month.
+/// They arrive soonest-first with each month's types in display
+/// order, so consecutive buckets with the same month belong together.
+fn fold_projection(
+    buckets: Vec<crate::repository::ProjectedDues>,
+    locale: Locale,
+) -> Vec<ProjectedMonthRow> {
+    let dollars = |c: i64| locale.currency(c, "USD");
+    let mut rows: Vec<((i32, u32), i64, ProjectedMonthRow)> = Vec::new();
+    for b in buckets {
+        let line = ProjectedTypeLine {
+            name: b.membership_type,
+            dollars: dollars(b.fee_cents),
+            member_count: b.member_count,
+        };
+        match rows.last_mut() {
+            Some((key, total, row)) if *key == (b.year, b.month) => {
+                *total += b.fee_cents;
+                row.member_count += b.member_count;
+                row.types.push(line);
+            }
+            _ => rows.push((
+                (b.year, b.month),
+                b.fee_cents,
+                ProjectedMonthRow {
+                    month_label: Utc
+                        .with_ymd_and_hms(b.year, b.month, 1, 0, 0, 0)
+                        .single()
+                        .map(|d| locale.month_year(&d))
+                        .unwrap_or_else(|| format!("{:04}-{:02}", b.year, b.month)),
+                    total_dollars: String::new(),
+                    member_count: b.member_count,
+                    types: vec![line],
+                },
+            )),
+        }
     }
+    rows.into_iter()
+        .map(|(_, total, mut row)| {
+            row.total_dollars = dollars(total);
+            row
+        })
+        .collect()
 }
@@ -0,0 +1,95 @@
+//! Admin contact inbox: messages sent through the contact-the-admins
+//! form (`/contact` or `POST /public/contact`). Admins reply by email
+//! and mark each one handled here.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Extension,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    service::contact_service::ContactService,
+    web::portal::admin::partials,
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "admin/contact.html")]
+pub struct AdminContactTemplate {
+    pub base: BaseContext,
+    pub messages: Vec<AdminContactInfo>,
+    pub show_handled: bool,
+}
+
+pub struct AdminContactInfo {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub category: String,
+    pub message: String,
+    pub submitted_at: String,
+    pub handled_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminContactQuery {
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// The inbox, newest first. Handled messages only show with `?all=true`.
+pub async fn admin_contact_page(
+    State(contact_service): State<Arc<ContactService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+    Query(query): Query<AdminContactQuery>,
+) -> impl IntoResponse {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
+
+    let messages = contact_service
+        .list(query.all)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| AdminContactInfo {
+            id: m.id.to_string(),
+            name: m.name,
+            email: m.email,
+            category: m.category.label().to_string(),
+            message: m.message,
+            submitted_at: current_user.locale.date_time(&m.created_at),
+            handled_at: m.handled_at.map(|t| current_user.locale.date_time(&t)),
+        })
+        .collect();
+
+    HtmlTemplate(AdminContactTemplate {
+        base,
+        messages,
+        show_handled: query.all,
+    })
+}
+
+pub async fn admin_mark_contact_handled(
+    State(contact_service): State<Arc<ContactService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(id) = uuid::Uuid::parse_str(&id) else {
+        return partials::admin_alert("error", "Invalid message ID", false);
+    };
+    match contact_service
+        .mark_handled(id, current_user.member.id)
+        .await
+    {
+        Ok(()) => partials::admin_alert("success", "Marked as handled.", true),
+        Err(e) => partials::admin_alert("error", &format!("Could not update: {}", e), false),
+    }
+}
@@ -0,0 +1,36 @@
+//! Admin report of rows with enum/status values the app can't parse.
+//! List pages skip those rows rather than failing, so without this
+//! they'd go unnoticed. Backs onto `DataCheckService`.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{extract::State, response::IntoResponse, Extension};
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    error::AppError,
+    service::data_check_service::{DataCheckService, MalformedRow},
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "admin/data_check.html")]
+pub struct DataCheckTemplate {
+    pub base: BaseContext,
+    pub rows: Vec<MalformedRow>,
+}
+
+pub async fn data_check_page(
+    State(data_check_service): State<Arc<DataCheckService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+) -> Result<impl IntoResponse, AppError> {
+    let rows = data_check_service.malformed_rows().await?;
+    Ok(HtmlTemplate(DataCheckTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        rows,
+    }))
+}
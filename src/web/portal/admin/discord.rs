@@ -3,7 +3,7 @@
 //! button that hits Discord's API and shows the bot's identity (or
 //! the exact error) inline.
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use askama::Template;
 use axum::{
@@ -18,10 +18,12 @@ use crate::{
     auth::CsrfService,
     config::Settings,
     integrations::{discord::DiscordIntegration, discord_client::DiscordClient},
-    repository::MemberRepository,
+    repository::{MemberRepository, MembershipTypeRepository},
     service::{
         audit_service::AuditService,
-        settings_service::{SettingsService, UpdateDiscordConfig},
+        settings_service::{
+            format_type_role_ids, parse_type_role_ids, SettingsService, UpdateDiscordConfig,
+        },
     },
     web::{
         portal::admin::test_result::test_result_html,
@@ -41,6 +43,8 @@ pub struct DiscordSettingsTemplate {
     pub announcements_channel_id: String,
     pub admin_alerts_channel_id: String,
     pub invite_url: String,
+    /// `slug=role_id` lines, as stored.
+    pub type_role_ids: String,
     /// True if a token is on file (we never display the plaintext).
     pub bot_token_set: bool,
     /// True if the encrypted token can't decrypt (session_secret rotated).
@@ -119,6 +123,7 @@ async fn render_page(
         announcements_channel_id: cfg.announcements_channel_id,
         admin_alerts_channel_id: cfg.admin_alerts_channel_id,
         invite_url: cfg.invite_url,
+        type_role_ids: format_type_role_ids(&cfg.type_role_ids),
         bot_token_set: !cfg.bot_token.is_empty(),
         token_undecryptable,
         last_test_status,
@@ -143,6 +148,8 @@ pub struct UpdateDiscordForm {
     pub announcements_channel_id: String,
     pub admin_alerts_channel_id: String,
     pub invite_url: String,
+    #[serde(default)]
+    pub type_role_ids: String,
     /// Same convention as SMTP password: "" = leave alone,
     /// "__CLEAR__" = remove, anything else = update.
     pub bot_token: String,
@@ -193,6 +200,21 @@ pub async fn update_discord_settings(
         .await;
     }
 
+    let type_role_ids = match parse_type_role_ids_form(&form.type_role_ids) {
+        Ok(map) => map,
+        Err(err) => {
+            return render_page(
+                &settings_service,
+                &csrf_service,
+                &current_user,
+                &session_info,
+                None,
+                Some(err),
+            )
+            .await;
+        }
+    };
+
     // Invite URL: if non-empty, must look like https://discord.gg/... or .com.
     if !form.invite_url.is_empty()
         && !(form.invite_url.starts_with("https://discord.gg/")
@@ -227,6 +249,7 @@ pub async fn update_discord_settings(
         announcements_channel_id: form.announcements_channel_id,
         admin_alerts_channel_id: form.admin_alerts_channel_id,
         invite_url: form.invite_url,
+        type_role_ids,
         bot_token,
     };
 
@@ -287,6 +310,19 @@ fn first_invalid_snowflake(inputs: &[(&str, &str)]) -> Option<String> {
     None
 }
 
+/// Parse the membership-type roles textarea and check each role ID is
+/// a snowflake, with an error fit for the flash message.
+fn parse_type_role_ids_form(text: &str) -> Result<BTreeMap<String, String>, String> {
+    let map = parse_type_role_ids(text).map_err(|e| format!("Membership type roles: {}", e))?;
+    for (slug, role_id) in &map {
+        let label = format!("Role ID for {}", slug);
+        if let Some(err) = first_invalid_snowflake(&[(label.as_str(), role_id.as_str())]) {
+            return Err(err);
+        }
+    }
+    Ok(map)
+}
+
 /// Hit Discord's API with the current bot token and report what the
 /// connection looks like. Used by the "Test connection" button.
 pub async fn test_discord_connection(
@@ -350,6 +386,7 @@ pub async fn reconcile_roles(
     State(settings_service): State<Arc<SettingsService>>,
     State(audit_service): State<Arc<AuditService>>,
     State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(membership_type_repo): State<Arc<dyn MembershipTypeRepository>>,
     Extension(current_user): Extension<CurrentUser>,
 ) -> impl IntoResponse {
     let cfg = match settings_service.get_discord_config().await {
@@ -370,8 +407,11 @@ pub async fn reconcile_roles(
         );
     }
 
-    let integration =
-        DiscordIntegration::new(settings_service.clone(), settings.server.base_url.clone());
+    let integration = DiscordIntegration::new(
+        settings_service.clone(),
+        membership_type_repo,
+        settings.server.base_url.clone(),
+    );
     let summary = integration.reconcile_all(member_repo.clone()).await;
 
     audit_service
@@ -330,12 +330,16 @@ pub async fn send_test_email(
         org_name: &org_name,
         portal_url: &portal_url,
         discord_invite: None,
+        verify_url: None,
+        set_password_url: None,
     };
     let text = WelcomeText {
         full_name: &full_name,
         org_name: &org_name,
         portal_url: &portal_url,
         discord_invite: None,
+        verify_url: None,
+        set_password_url: None,
     };
     let message = match email::message_from_templates(
         admin_email.clone(),
@@ -1,11 +1,14 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use askama::Template;
 use axum::{
     extract::{Multipart, Path, Query, State},
-    response::IntoResponse,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Extension,
 };
+use axum_extra::extract::CookieJar;
 use serde::Deserialize;
 
 use crate::{
@@ -15,10 +18,24 @@ use crate::{
     },
     auth::CsrfService,
     config::Settings,
-    repository::EventRepository,
-    service::event_admin_service::{CreateEventInput, EventAdminService, UpdateEventInput},
-    web::portal::admin::partials,
-    web::templates::{BaseContext, HtmlTemplate},
+    domain::{contrasting_text_color, list_defaults},
+    repository::{EventAttendeeRow, EventRepository, MemberRepository},
+    service::{
+        audit_service::AuditService,
+        emergency_contact_service::{EmergencyContact, EmergencyContactService},
+        event_admin_service::{
+            CreateEventInput, EventAdminService, UpdateEventInput, RSVP_DEADLINE_AFTER_START,
+        },
+        event_host_service::{EventHost, EventHostService},
+        event_invite_service::EventInviteService,
+        event_proposal_service::EventProposalService,
+        settings_service::SettingsService,
+    },
+    web::portal::admin::{
+        forms::{form_invalid, form_saved, FormErrors},
+        pagination, partials,
+    },
+    web::templates::{filters, BaseContext, HtmlTemplate},
     web::uploads::save_uploaded_file,
 };
 
@@ -29,6 +46,8 @@ pub struct TypeOption {
     pub name: String,
     pub slug: String,
     pub color: Option<String>,
+    /// Badge text color for `color`; see `contrasting_text_color`.
+    pub text_color: Option<&'static str>,
 }
 
 #[derive(Template)]
@@ -44,6 +63,8 @@ pub struct AdminEventsTemplate {
     pub type_filter: String,
     pub visibility_filter: String,
     pub time_filter: String,
+    /// The club's default, so "Clear" only shows when the filter differs.
+    pub default_time_filter: String,
     pub sort_field: String,
     pub sort_order: String,
 }
@@ -89,6 +110,7 @@ pub struct AdminEventsQuery {
     pub visibility: Option<String>,
     pub time: Option<String>,
     pub page: Option<i64>,
+    pub per_page: Option<i64>,
     pub sort: Option<String>,
     pub order: Option<String>,
 }
@@ -96,9 +118,11 @@ pub struct AdminEventsQuery {
 pub async fn admin_events_page(
     State(event_repo): State<Arc<dyn EventRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
+    State(settings_service): State<Arc<SettingsService>>,
     headers: axum::http::HeaderMap,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
+    jar: CookieJar,
     Query(query): Query<AdminEventsQuery>,
 ) -> impl IntoResponse {
     let is_htmx = headers.get("HX-Request").is_some();
@@ -106,18 +130,32 @@ pub async fn admin_events_page(
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
 
     let page = query.page.unwrap_or(1).max(1);
-    let per_page: i64 = 20;
+    let (per_page, jar) = pagination::per_page(query.per_page, jar);
     let offset = (page - 1) * per_page;
 
     let search_query = query.q.clone().unwrap_or_default().to_lowercase();
     let type_filter = query.event_type.clone().unwrap_or_default();
     let visibility_filter = query.visibility.clone().unwrap_or_default();
-    let time_filter = query.time.clone().unwrap_or_else(|| "upcoming".to_string());
-    let sort_field = query
-        .sort
+    // Params the request leaves out fall back to the club's defaults.
+    let default_time_filter = settings_service.list_default(&list_defaults::EVENT_TIME).await;
+    let time_filter = query
+        .time
         .clone()
-        .unwrap_or_else(|| "start_time".to_string());
-    let sort_order = query.order.clone().unwrap_or_else(|| "asc".to_string());
+        .unwrap_or_else(|| default_time_filter.to_string());
+    let sort_field = match query.sort.clone() {
+        Some(sort) => sort,
+        None => settings_service
+            .list_default(&list_defaults::EVENT_SORT)
+            .await
+            .to_string(),
+    };
+    let sort_order = match query.order.clone() {
+        Some(order) => order,
+        None => settings_service
+            .list_default(&list_defaults::EVENT_ORDER)
+            .await
+            .to_string(),
+    };
 
     let all_events = event_repo.list(1000, 0).await.unwrap_or_default();
 
@@ -137,10 +175,10 @@ pub async fn admin_events_page(
                     return false;
                 }
             }
-            if !type_filter.is_empty() && format!("{:?}", e.event_type) != type_filter {
+            if !type_filter.is_empty() && e.event_type.as_str() != type_filter {
                 return false;
             }
-            if !visibility_filter.is_empty() && format!("{:?}", e.visibility) != visibility_filter {
+            if !visibility_filter.is_empty() && e.visibility.as_str() != visibility_filter {
                 return false;
             }
             match time_filter.as_str() {
@@ -154,8 +192,8 @@ pub async fn admin_events_page(
     filtered_events.sort_by(|a, b| {
         let cmp = match sort_field.as_str() {
             "title" => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
-            "type" => format!("{:?}", a.event_type).cmp(&format!("{:?}", b.event_type)),
-            "visibility" => format!("{:?}", a.visibility).cmp(&format!("{:?}", b.visibility)),
+            "type" => a.event_type.as_str().cmp(b.event_type.as_str()),
+            "visibility" => a.visibility.as_str().cmp(b.visibility.as_str()),
             "start_time" | _ => a.start_time.cmp(&b.start_time),
         };
         if sort_order == "desc" {
@@ -179,11 +217,11 @@ pub async fn admin_events_page(
         paginated_events.push(AdminEventInfo {
             id: e.id.to_string(),
             title: e.title,
-            event_type: format!("{:?}", e.event_type),
-            visibility: format!("{:?}", e.visibility),
-            start_time: e.start_time.format("%b %d, %Y %H:%M").to_string(),
+            event_type: e.event_type.to_string(),
+            visibility: e.visibility.to_string(),
+            start_time: current_user.locale.date_time(&e.start_time),
             start_time_raw: e.start_time,
-            end_time: e.end_time.map(|t| t.format("%H:%M").to_string()),
+            end_time: e.end_time.map(|t| current_user.locale.time(&t)),
             location: e.location,
             image_url: e.image_url,
             attendee_count,
@@ -197,7 +235,7 @@ pub async fn admin_events_page(
     let type_filter_val = query.event_type.unwrap_or_default();
     let visibility_filter_val = query.visibility.unwrap_or_default();
 
-    if is_htmx {
+    let body = if is_htmx {
         HtmlTemplate(AdminEventsTableTemplate {
             events: paginated_events,
             total_events,
@@ -224,11 +262,13 @@ pub async fn admin_events_page(
             type_filter: type_filter_val,
             visibility_filter: visibility_filter_val,
             time_filter,
+            default_time_filter: default_time_filter.to_string(),
             sort_field,
             sort_order,
         })
         .into_response()
-    }
+    };
+    (jar, body)
 }
 
 #[derive(Template)]
@@ -237,6 +277,9 @@ pub struct AdminEventDetailTemplate {
     pub base: BaseContext,
     pub event: AdminEventDetail,
     pub event_types: Vec<TypeOption>,
+    pub hosts: Vec<EventHost>,
+    /// Every RSVP, guests and cancellations included, in sign-up order.
+    pub attendees: Vec<EventAttendeeRow>,
 }
 
 pub struct AdminEventDetail {
@@ -252,6 +295,10 @@ pub struct AdminEventDetail {
     pub location: Option<String>,
     pub max_attendees: Option<i32>,
     pub rsvp_required: bool,
+    pub allow_guest_rsvp: bool,
+    pub registration_group: Option<String>,
+    pub rsvp_deadline: Option<String>,
+    pub rsvp_deadline_input: Option<String>,
     pub image_url: Option<String>,
     pub attendee_count: i64,
     pub is_past: bool,
@@ -267,6 +314,7 @@ pub struct AdminEventDetail {
 
 pub async fn admin_event_detail_page(
     State(event_repo): State<Arc<dyn EventRepository>>,
+    State(event_host_service): State<Arc<EventHostService>>,
     State(event_type_service): State<EventBasicTypeService>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
@@ -291,6 +339,8 @@ pub async fn admin_event_detail_page(
     };
 
     let attendee_count = event_repo.get_attendee_count(event.id).await.unwrap_or(0);
+    let attendees = event_repo.list_attendees(event.id).await.unwrap_or_default();
+    let hosts = event_host_service.hosts(&event).await.unwrap_or_default();
 
     let now = chrono::Utc::now();
 
@@ -298,24 +348,32 @@ pub async fn admin_event_detail_page(
         id: event.id.to_string(),
         title: event.title,
         description: event.description,
-        event_type: format!("{:?}", event.event_type),
-        visibility: format!("{:?}", event.visibility),
-        start_time: event.start_time.format("%b %d, %Y %H:%M").to_string(),
+        event_type: event.event_type.to_string(),
+        visibility: event.visibility.to_string(),
+        start_time: current_user.locale.date_time(&event.start_time),
         start_time_input: event.start_time.format("%Y-%m-%dT%H:%M").to_string(),
         end_time: event
             .end_time
-            .map(|t| t.format("%b %d, %Y %H:%M").to_string()),
+            .map(|t| current_user.locale.date_time(&t)),
         end_time_input: event
             .end_time
             .map(|t| t.format("%Y-%m-%dT%H:%M").to_string()),
         location: event.location,
         max_attendees: event.max_attendees,
         rsvp_required: event.rsvp_required,
+        allow_guest_rsvp: event.allow_guest_rsvp,
+        registration_group: event.registration_group,
+        rsvp_deadline: event
+            .rsvp_deadline
+            .map(|t| current_user.locale.date_time(&t)),
+        rsvp_deadline_input: event
+            .rsvp_deadline
+            .map(|t| t.format("%Y-%m-%dT%H:%M").to_string()),
         image_url: event.image_url,
         attendee_count,
         is_past: event.start_time <= now,
-        created_at: event.created_at.format("%b %d, %Y %H:%M").to_string(),
-        updated_at: event.updated_at.format("%b %d, %Y %H:%M").to_string(),
+        created_at: current_user.locale.date_time(&event.created_at),
+        updated_at: current_user.locale.date_time(&event.updated_at),
         is_series: event.series_id.is_some(),
         occurrence_index: event.occurrence_index,
     };
@@ -331,6 +389,7 @@ pub async fn admin_event_detail_page(
             id: t.id.to_string(),
             name: t.name,
             slug: t.slug,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
             color: t.color,
         })
         .collect();
@@ -339,27 +398,106 @@ pub async fn admin_event_detail_page(
         base,
         event: detail,
         event_types,
+        hosts,
+        attendees,
     })
     .into_response()
 }
 
+/// What the admin typed into the new-event form, echoed back when
+/// validation fails. Kept as raw strings so a value that didn't parse
+/// still comes back exactly as entered. The image can't be echoed —
+/// browsers never pre-fill file inputs.
+#[derive(Debug, Clone)]
+pub struct EventFormValues {
+    pub title: String,
+    pub description: String,
+    pub event_type: String,
+    pub visibility: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub location: String,
+    pub max_attendees: String,
+    pub rsvp_required: bool,
+    pub allow_guest_rsvp: bool,
+    pub registration_group: String,
+    pub rsvp_deadline: String,
+    pub repeat_kind: String,
+    pub repeat_interval: String,
+    pub repeat_weekdays: Vec<String>,
+    pub repeat_day: String,
+    pub repeat_weekday: String,
+    pub repeat_ordinal: String,
+    pub repeat_until: String,
+}
+
+impl Default for EventFormValues {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            description: String::new(),
+            event_type: String::new(),
+            visibility: "MembersOnly".to_string(),
+            start_time: String::new(),
+            end_time: String::new(),
+            location: String::new(),
+            max_attendees: String::new(),
+            rsvp_required: false,
+            allow_guest_rsvp: false,
+            registration_group: String::new(),
+            rsvp_deadline: String::new(),
+            repeat_kind: "none".to_string(),
+            repeat_interval: "1".to_string(),
+            repeat_weekdays: Vec::new(),
+            repeat_day: String::new(),
+            repeat_weekday: "mon".to_string(),
+            repeat_ordinal: "1".to_string(),
+            repeat_until: String::new(),
+        }
+    }
+}
+
+impl EventFormValues {
+    pub fn repeats_on(&self, day: &str) -> bool {
+        self.repeat_weekdays.iter().any(|d| d == day)
+    }
+
+    /// `repeat_kind` for the Alpine `x-data` literal. Only the known
+    /// kinds are passed through; anything else (a forged form) falls
+    /// back to "none" rather than landing inside a JS expression.
+    pub fn repeat_kind_js(&self) -> &'static str {
+        match self.repeat_kind.as_str() {
+            "weekly" => "weekly",
+            "monthly_dom" => "monthly_dom",
+            "monthly_weekday" => "monthly_weekday",
+            _ => "none",
+        }
+    }
+}
+
 #[derive(Template)]
 #[template(path = "admin/event_new.html")]
 pub struct AdminNewEventTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
     pub event_types: Vec<TypeOption>,
+    pub values: EventFormValues,
+    pub errors: FormErrors,
 }
 
-pub async fn admin_new_event_page(
-    State(event_type_service): State<EventBasicTypeService>,
-    State(csrf_service): State<Arc<CsrfService>>,
-    Extension(current_user): Extension<CurrentUser>,
-    Extension(session_info): Extension<SessionInfo>,
-) -> impl IntoResponse {
-    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
+/// Just the `<form>`, for re-rendering after a failed submit.
+#[derive(Template)]
+#[template(path = "admin/_event_form.html")]
+pub struct AdminEventFormTemplate {
+    pub csrf_token: String,
+    pub event_types: Vec<TypeOption>,
+    pub values: EventFormValues,
+    pub errors: FormErrors,
+}
 
-    // Fetch active event types for the dropdown
-    let event_types = event_type_service
+/// Active event types for the form's dropdown.
+async fn event_type_options(event_type_service: &EventBasicTypeService) -> Vec<TypeOption> {
+    event_type_service
         .0
         .list(false)
         .await
@@ -369,217 +507,277 @@ pub async fn admin_new_event_page(
             id: t.id.to_string(),
             name: t.name,
             slug: t.slug,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
             color: t.color,
         })
-        .collect();
-
-    HtmlTemplate(AdminNewEventTemplate { base, event_types }).into_response()
+        .collect()
 }
 
-pub async fn admin_create_event(
-    State(settings): State<Arc<Settings>>,
-    State(event_admin_service): State<Arc<EventAdminService>>,
+pub async fn admin_new_event_page(
+    State(event_type_service): State<EventBasicTypeService>,
+    State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
-    mut multipart: Multipart,
+    Extension(session_info): Extension<SessionInfo>,
 ) -> impl IntoResponse {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
+
+    HtmlTemplate(AdminNewEventTemplate {
+        csrf_token: base.csrf_token.clone(),
+        base,
+        event_types: event_type_options(&event_type_service).await,
+        values: EventFormValues::default(),
+        errors: FormErrors::new(),
+    })
+    .into_response()
+}
+
+/// Guests RSVP through the public API, which only ever sees `Public`
+/// events, so the flag means nothing anywhere else.
+const GUEST_RSVP_NOT_PUBLIC: &str = "Guest RSVPs are only available on public events";
+
+/// Registration-group keys are short labels an admin types by hand.
+const REGISTRATION_GROUP_MAX_LEN: usize = 64;
+const REGISTRATION_GROUP_TOO_LONG: &str = "Registration group must be 64 characters or fewer";
+
+/// Trimmed group key, or `None` when left blank. `Err` when too long.
+fn parse_registration_group(raw: &str) -> Result<Option<String>, &'static str> {
+    let key = raw.trim();
+    if key.chars().count() > REGISTRATION_GROUP_MAX_LEN {
+        return Err(REGISTRATION_GROUP_TOO_LONG);
+    }
+    Ok(Some(key.to_string()).filter(|k| !k.is_empty()))
+}
+
+/// Check the submitted values and build the service input. The image
+/// is attached by the caller once everything else has passed, so a
+/// rejected form never leaves an orphaned upload behind.
+fn validate_event_form(values: &EventFormValues) -> Result<CreateEventInput, FormErrors> {
     use crate::domain::{EventType, EventVisibility};
 
-    // Parse multipart form
-    let mut title = String::new();
-    let mut description = String::new();
-    let mut event_type_str = String::new();
-    let mut visibility_str = String::new();
-    let mut start_time_str = String::new();
-    let mut end_time_str = String::new();
-    let mut location_str = String::new();
-    let mut max_attendees: Option<i32> = None;
-    let mut rsvp_required = false;
-    let mut image_url: Option<String> = None;
-    // Recurrence form fields. `repeat_kind` defaults to "none" so an
-    // unchecked form behaves identically to the pre-recurrence flow.
-    let mut repeat_kind = String::from("none");
-    let mut repeat_interval: u32 = 1;
-    let mut repeat_weekdays: Vec<String> = Vec::new();
-    let mut repeat_day: Option<u32> = None;
-    let mut repeat_weekday = String::from("mon");
-    let mut repeat_ordinal: i32 = 1;
-    let mut repeat_until_str = String::new();
+    let mut errors = FormErrors::new();
 
-    while let Ok(Some(field)) = multipart.next_field().await {
-        let name = field.name().unwrap_or("").to_string();
+    let title = values.title.trim();
+    if title.is_empty() {
+        errors.add("title", "Title is required");
+    }
 
-        match name.as_str() {
-            "csrf_token" => {
-                let _ = field.text().await;
-            }
-            "title" => title = field.text().await.unwrap_or_default(),
-            "description" => description = field.text().await.unwrap_or_default(),
-            "event_type" => event_type_str = field.text().await.unwrap_or_default(),
-            "visibility" => visibility_str = field.text().await.unwrap_or_default(),
-            "start_time" => start_time_str = field.text().await.unwrap_or_default(),
-            "end_time" => end_time_str = field.text().await.unwrap_or_default(),
-            "location" => location_str = field.text().await.unwrap_or_default(),
-            "max_attendees" => {
-                if let Ok(text) = field.text().await {
-                    max_attendees = text.parse().ok();
-                }
-            }
-            "rsvp_required" => {
-                rsvp_required = true;
-                let _ = field.text().await;
-            }
-            "repeat_kind" => repeat_kind = field.text().await.unwrap_or_default(),
-            "repeat_interval" => {
-                if let Ok(text) = field.text().await {
-                    if let Ok(n) = text.parse() {
-                        repeat_interval = n;
-                    }
-                }
-            }
-            "repeat_weekdays" => {
-                // Multipart sends one field per checked box; collect them.
-                if let Ok(text) = field.text().await {
-                    repeat_weekdays.push(text);
-                }
-            }
-            "repeat_day" => {
-                if let Ok(text) = field.text().await {
-                    repeat_day = text.parse().ok();
-                }
-            }
-            "repeat_weekday" => repeat_weekday = field.text().await.unwrap_or_default(),
-            "repeat_ordinal" => {
-                if let Ok(text) = field.text().await {
-                    if let Ok(n) = text.parse() {
-                        repeat_ordinal = n;
-                    }
-                }
-            }
-            "repeat_until" => repeat_until_str = field.text().await.unwrap_or_default(),
-            "image" => {
-                let filename = field.file_name().unwrap_or("").to_string();
-                if !filename.is_empty() {
-                    if let Ok(data) = field.bytes().await {
-                        if !data.is_empty() {
-                            match save_uploaded_file(
-                                &settings.server.uploads_path(),
-                                &filename,
-                                &data,
-                            )
-                            .await
-                            {
-                                Ok(path) => image_url = Some(path),
-                                Err(e) => {
-                                    return partials::admin_alert(
-                                        "error",
-                                        &format!("Error uploading image: {}", e),
-                                        false,
-                                    )
-                                    .into_response()
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            _ => {
-                let _ = field.bytes().await;
-            }
-        }
+    let event_type = values.event_type.parse().unwrap_or(EventType::Meeting);
+    let visibility = values
+        .visibility
+        .parse()
+        .unwrap_or(EventVisibility::MembersOnly);
+
+    if values.allow_guest_rsvp && visibility != EventVisibility::Public {
+        errors.add("allow_guest_rsvp", GUEST_RSVP_NOT_PUBLIC);
     }
 
-    let event_type = match event_type_str.as_str() {
-        "Meeting" => EventType::Meeting,
-        "Workshop" => EventType::Workshop,
-        "CTF" => EventType::CTF,
-        "Social" => EventType::Social,
-        "Training" => EventType::Training,
-        _ => EventType::Meeting,
-    };
+    let registration_group =
+        parse_registration_group(&values.registration_group).unwrap_or_else(|msg| {
+            errors.add("registration_group", msg);
+            None
+        });
 
-    let visibility = match visibility_str.as_str() {
-        "Public" => EventVisibility::Public,
-        "MembersOnly" => EventVisibility::MembersOnly,
-        "AdminOnly" => EventVisibility::AdminOnly,
-        _ => EventVisibility::MembersOnly,
+    let start_time = if values.start_time.is_empty() {
+        errors.add("start_time", "Start time is required");
+        None
+    } else {
+        let parsed = parse_datetime_local(&values.start_time);
+        if parsed.is_none() {
+            errors.add("start_time", "Invalid start time");
+        }
+        parsed
     };
 
-    let start_time = match chrono::NaiveDateTime::parse_from_str(&start_time_str, "%Y-%m-%dT%H:%M")
-    {
-        Ok(dt) => chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc),
-        Err(_) => {
-            return partials::admin_alert("error", "Invalid start time", false).into_response()
+    let end_time = parse_datetime_local(&values.end_time);
+    if !values.end_time.is_empty() && end_time.is_none() {
+        errors.add("end_time", "Invalid end time");
+    }
+    if let (Some(start), Some(end)) = (start_time, end_time) {
+        if end <= start {
+            errors.add("end_time", "End time must be after the start time");
         }
-    };
+    }
 
-    let end_time = if end_time_str.is_empty() {
+    let rsvp_deadline = parse_datetime_local(&values.rsvp_deadline);
+    if !values.rsvp_deadline.is_empty() && rsvp_deadline.is_none() {
+        errors.add("rsvp_deadline", "Invalid RSVP deadline");
+    }
+    if let (Some(start), Some(deadline)) = (start_time, rsvp_deadline) {
+        if deadline > start {
+            errors.add("rsvp_deadline", RSVP_DEADLINE_AFTER_START);
+        }
+    }
+
+    let max_attendees = if values.max_attendees.trim().is_empty() {
         None
     } else {
-        chrono::NaiveDateTime::parse_from_str(&end_time_str, "%Y-%m-%dT%H:%M")
-            .ok()
-            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
+        match values.max_attendees.trim().parse::<i32>() {
+            Ok(n) if n >= 1 => Some(n),
+            _ => {
+                errors.add("max_attendees", "Enter a whole number of at least 1");
+                None
+            }
+        }
     };
 
     // Build the recurrence rule, if the admin asked for one. The
     // service decides series-vs-single by inspecting input.recurrence.
-    let recurrence = if repeat_kind != "none" && !repeat_kind.is_empty() {
+    let recurrence = if values.repeat_kind != "none" && !values.repeat_kind.is_empty() {
         match build_recurrence(
-            &repeat_kind,
-            repeat_interval,
-            &repeat_weekdays,
-            repeat_day,
-            &repeat_weekday,
-            repeat_ordinal,
+            &values.repeat_kind,
+            values.repeat_interval.parse().unwrap_or(1),
+            &values.repeat_weekdays,
+            values.repeat_day.parse().ok(),
+            &values.repeat_weekday,
+            values.repeat_ordinal.parse().unwrap_or(1),
         ) {
             Ok(r) => Some(r),
             Err(msg) => {
-                return partials::admin_alert(
-                    "error",
-                    &format!("Invalid recurrence: {}", msg),
-                    false,
-                )
-                .into_response()
+                errors.add("repeat_kind", format!("Invalid recurrence: {}", msg));
+                None
             }
         }
     } else {
         None
     };
     let recurrence_until = if recurrence.is_some() {
-        parse_until(&repeat_until_str)
+        parse_datetime_local(&values.repeat_until)
     } else {
         None
     };
 
-    let input = CreateEventInput {
-        title,
-        description,
+    let Some(start_time) = start_time.filter(|_| errors.is_empty()) else {
+        return Err(errors);
+    };
+
+    Ok(CreateEventInput {
+        title: title.to_string(),
+        description: values.description.clone(),
         event_type,
         event_type_id: None,
         visibility,
         start_time,
         end_time,
-        location: if location_str.is_empty() {
+        location: if values.location.is_empty() {
             None
         } else {
-            Some(location_str)
+            Some(values.location.clone())
         },
         max_attendees,
-        rsvp_required,
-        image_url,
+        rsvp_required: values.rsvp_required,
+        allow_guest_rsvp: values.allow_guest_rsvp,
+        registration_group,
+        rsvp_deadline,
+        image_url: None,
         recurrence,
         recurrence_until,
+    })
+}
+
+pub async fn admin_create_event(
+    State(settings): State<Arc<Settings>>,
+    State(event_admin_service): State<Arc<EventAdminService>>,
+    State(event_type_service): State<EventBasicTypeService>,
+    Extension(current_user): Extension<CurrentUser>,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    // Parse multipart form
+    let mut values = EventFormValues::default();
+    let mut csrf_token = String::new();
+    let mut image: Option<(String, axum::body::Bytes)> = None;
+
+    while let Ok(Some(field)) = multipart.next_field().await {
+        let name = field.name().unwrap_or("").to_string();
+
+        match name.as_str() {
+            "csrf_token" => csrf_token = field.text().await.unwrap_or_default(),
+            "title" => values.title = field.text().await.unwrap_or_default(),
+            "description" => values.description = field.text().await.unwrap_or_default(),
+            "event_type" => values.event_type = field.text().await.unwrap_or_default(),
+            "visibility" => values.visibility = field.text().await.unwrap_or_default(),
+            "start_time" => values.start_time = field.text().await.unwrap_or_default(),
+            "end_time" => values.end_time = field.text().await.unwrap_or_default(),
+            "location" => values.location = field.text().await.unwrap_or_default(),
+            "max_attendees" => values.max_attendees = field.text().await.unwrap_or_default(),
+            "rsvp_required" => {
+                values.rsvp_required = true;
+                let _ = field.text().await;
+            }
+            "allow_guest_rsvp" => {
+                values.allow_guest_rsvp = true;
+                let _ = field.text().await;
+            }
+            "registration_group" => {
+                values.registration_group = field.text().await.unwrap_or_default()
+            }
+            "rsvp_deadline" => values.rsvp_deadline = field.text().await.unwrap_or_default(),
+            "repeat_kind" => values.repeat_kind = field.text().await.unwrap_or_default(),
+            "repeat_interval" => values.repeat_interval = field.text().await.unwrap_or_default(),
+            "repeat_weekdays" => {
+                // Multipart sends one field per checked box; collect them.
+                if let Ok(text) = field.text().await {
+                    values.repeat_weekdays.push(text);
+                }
+            }
+            "repeat_day" => values.repeat_day = field.text().await.unwrap_or_default(),
+            "repeat_weekday" => values.repeat_weekday = field.text().await.unwrap_or_default(),
+            "repeat_ordinal" => values.repeat_ordinal = field.text().await.unwrap_or_default(),
+            "repeat_until" => values.repeat_until = field.text().await.unwrap_or_default(),
+            "image" => {
+                let filename = field.file_name().unwrap_or("").to_string();
+                if let Ok(data) = field.bytes().await {
+                    if !filename.is_empty() && !data.is_empty() {
+                        image = Some((filename, data));
+                    }
+                }
+            }
+            _ => {
+                let _ = field.bytes().await;
+            }
+        }
+    }
+
+    let invalid = |errors: FormErrors, event_types: Vec<TypeOption>| {
+        form_invalid(AdminEventFormTemplate {
+            csrf_token: csrf_token.clone(),
+            event_types,
+            values: values.clone(),
+            errors,
+        })
+    };
+
+    let mut input = match validate_event_form(&values) {
+        Ok(input) => input,
+        Err(errors) => return invalid(errors, event_type_options(&event_type_service).await),
     };
 
+    if let Some((filename, data)) = image {
+        match save_uploaded_file(
+            &settings.server.uploads_path(),
+            &settings.images,
+            &filename,
+            &data,
+        )
+        .await
+        {
+            Ok(path) => input.image_url = Some(path),
+            Err(e) => {
+                let mut errors = FormErrors::new();
+                errors.add("image", format!("Error uploading image: {}", e));
+                return invalid(errors, event_type_options(&event_type_service).await);
+            }
+        }
+    }
+
     match event_admin_service
         .create(current_user.member.id, input)
         .await
     {
-        Ok(created) => {
-            axum::response::Redirect::to(&format!("/portal/admin/events/{}", created.id))
-                .into_response()
-        }
-        Err(e) => partials::admin_alert("error", &format!("Error creating event: {}", e), false)
-            .into_response(),
+        Ok(created) => form_saved(&format!("/portal/admin/events/{}", created.id), "Event created"),
+        Err(e) => invalid(
+            FormErrors::form_level(format!("Error creating event: {}", e)),
+            event_type_options(&event_type_service).await,
+        ),
     }
 }
 
@@ -637,7 +835,9 @@ fn build_recurrence(
     Ok(rule)
 }
 
-fn parse_until(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+/// Parse a `datetime-local` input value (`YYYY-MM-DDTHH:MM`, taken as
+/// UTC). Empty or malformed input is `None`.
+fn parse_datetime_local(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     if s.is_empty() {
         return None;
     }
@@ -681,6 +881,9 @@ pub async fn admin_update_event(
     let mut location_str = String::new();
     let mut max_attendees: Option<i32> = None;
     let mut rsvp_required = false;
+    let mut allow_guest_rsvp = false;
+    let mut registration_group_str = String::new();
+    let mut rsvp_deadline_str = String::new();
     let mut new_image_url: Option<String> = None;
     let mut remove_image = false;
     // For series occurrences: "this" (default), "this_and_future".
@@ -710,6 +913,14 @@ pub async fn admin_update_event(
                 rsvp_required = true;
                 let _ = field.text().await;
             }
+            "allow_guest_rsvp" => {
+                allow_guest_rsvp = true;
+                let _ = field.text().await;
+            }
+            "registration_group" => {
+                registration_group_str = field.text().await.unwrap_or_default()
+            }
+            "rsvp_deadline" => rsvp_deadline_str = field.text().await.unwrap_or_default(),
             "edit_scope" => edit_scope = field.text().await.unwrap_or_default(),
             "remove_image" => {
                 remove_image = true;
@@ -722,6 +933,7 @@ pub async fn admin_update_event(
                         if !data.is_empty() {
                             match save_uploaded_file(
                                 &settings.server.uploads_path(),
+                                &settings.images,
                                 &filename,
                                 &data,
                             )
@@ -747,20 +959,16 @@ pub async fn admin_update_event(
         }
     }
 
-    let event_type = match event_type_str.as_str() {
-        "Meeting" => EventType::Meeting,
-        "Workshop" => EventType::Workshop,
-        "CTF" => EventType::CTF,
-        "Social" => EventType::Social,
-        "Training" => EventType::Training,
-        _ => EventType::Meeting,
-    };
-
-    let visibility = match visibility_str.as_str() {
-        "Public" => EventVisibility::Public,
-        "MembersOnly" => EventVisibility::MembersOnly,
-        "AdminOnly" => EventVisibility::AdminOnly,
-        _ => EventVisibility::MembersOnly,
+    let event_type = event_type_str.parse().unwrap_or(EventType::Meeting);
+    let visibility = visibility_str
+        .parse()
+        .unwrap_or(EventVisibility::MembersOnly);
+    if allow_guest_rsvp && visibility != EventVisibility::Public {
+        return partials::admin_alert("error", GUEST_RSVP_NOT_PUBLIC, false).into_response();
+    }
+    let registration_group = match parse_registration_group(&registration_group_str) {
+        Ok(group) => group,
+        Err(msg) => return partials::admin_alert("error", msg, false).into_response(),
     };
 
     let start_time = match chrono::NaiveDateTime::parse_from_str(&start_time_str, "%Y-%m-%dT%H:%M") {
@@ -776,6 +984,14 @@ pub async fn admin_update_event(
             .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
     };
 
+    let rsvp_deadline = parse_datetime_local(&rsvp_deadline_str);
+    if !rsvp_deadline_str.is_empty() && rsvp_deadline.is_none() {
+        return partials::admin_alert("error", "Invalid RSVP deadline", false).into_response();
+    }
+    if rsvp_deadline.is_some_and(|deadline| deadline > start_time) {
+        return partials::admin_alert("error", RSVP_DEADLINE_AFTER_START, false).into_response();
+    }
+
     // Determine final image_url: new upload > remove > keep existing.
     // Also capture what (if anything) we need to delete from disk.
     let old_image = existing.image_url.clone();
@@ -808,6 +1024,9 @@ pub async fn admin_update_event(
         },
         max_attendees,
         rsvp_required,
+        allow_guest_rsvp,
+        registration_group,
+        rsvp_deadline,
         image_url,
     };
 
@@ -856,6 +1075,214 @@ pub async fn admin_update_event(
     partials::admin_alert("success", &msg, false).into_response()
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AttendeeExportQuery {
+    /// Add members' emergency contacts and medical notes, for
+    /// check-in. Off unless asked for.
+    #[serde(default)]
+    pub emergency: bool,
+}
+
+/// CSV of everyone who RSVP'd — members and guests — for check-in
+/// sheets and follow-up mail. Audited like the member export, since it
+/// carries contact details.
+pub async fn admin_export_event_attendees(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(emergency_contact_service): State<Arc<EmergencyContactService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(event_id): Path<String>,
+    Query(query): Query<AttendeeExportQuery>,
+) -> Response {
+    let Ok(id) = uuid::Uuid::parse_str(&event_id) else {
+        return (StatusCode::BAD_REQUEST, "Invalid event ID").into_response();
+    };
+    let event = match event_repo.find_by_id(id).await {
+        Ok(Some(e)) => e,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Event not found").into_response(),
+        Err(e) => {
+            tracing::error!("attendee export: loading event {} failed: {}", id, e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Error loading event").into_response();
+        }
+    };
+    let rows = match event_repo.list_attendees(id).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            tracing::error!("attendee export for event {} failed: {}", id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to build export. Check server logs.",
+            )
+                .into_response();
+        }
+    };
+
+    let emergency = if query.emergency {
+        let member_ids: Vec<uuid::Uuid> = rows.iter().filter_map(|r| r.member_id).collect();
+        match emergency_contact_service.for_members(&member_ids).await {
+            Ok(contacts) => Some(contacts),
+            Err(e) => {
+                tracing::error!("attendee export for event {} failed: {}", id, e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to build export. Check server logs.",
+                )
+                    .into_response();
+            }
+        }
+    } else {
+        None
+    };
+
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "export_event_attendees",
+            "event",
+            &id.to_string(),
+            None,
+            Some(&if emergency.is_some() {
+                format!("{} rows, with emergency contacts", rows.len())
+            } else {
+                format!("{} rows", rows.len())
+            }),
+            None,
+        )
+        .await;
+
+    let filename = format!("attendees-{}.csv", event.start_time.format("%Y-%m-%d"));
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        build_attendees_csv(&rows, emergency.as_ref()),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterMemberForm {
+    /// The member's email or username.
+    pub member: String,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+/// An admin signing a member up for an event. Capacity and
+/// registration groups still apply; the RSVP deadline doesn't, so
+/// organizers can squeeze in a late registration by hand.
+pub async fn admin_register_member(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(event_invite_service): State<Arc<EventInviteService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(event_id): Path<String>,
+    axum::Form(form): axum::Form<RegisterMemberForm>,
+) -> Response {
+    let Ok(id) = uuid::Uuid::parse_str(&event_id) else {
+        return partials::admin_alert("error", "Invalid event ID", false).into_response();
+    };
+    let event = match event_repo.find_by_id(id).await {
+        Ok(Some(e)) => e,
+        _ => return partials::admin_alert("error", "Event not found", false).into_response(),
+    };
+
+    let needle = form.member.trim();
+    let member = if needle.contains('@') {
+        member_repo.find_by_email(needle).await
+    } else {
+        member_repo.find_by_username(needle).await
+    };
+    let member = match member {
+        Ok(Some(m)) => m,
+        Ok(None) => {
+            return partials::admin_alert("error", "No member with that email or username", false)
+                .into_response()
+        }
+        Err(e) => {
+            return partials::admin_alert("error", &format!("Error finding member: {}", e), false)
+                .into_response()
+        }
+    };
+
+    match event_repo.admin_register_attendance(id, member.id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return partials::admin_alert("error", "This event is full", false).into_response()
+        }
+        Err(e) => return partials::admin_alert("error", &e.to_string(), false).into_response(),
+    }
+
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "admin_register_attendance",
+            "event",
+            &id.to_string(),
+            None,
+            Some(&member.id.to_string()),
+            None,
+        )
+        .await;
+    event_invite_service.send_rsvp_confirmation(&event, &member).await;
+
+    partials::admin_alert(
+        "success",
+        &format!("{} is registered", member.full_name),
+        true,
+    )
+    .into_response()
+}
+
+/// With `emergency`, four more columns carry each member's emergency
+/// contact and medical notes (empty for guests and members who gave
+/// none).
+fn build_attendees_csv(
+    rows: &[EventAttendeeRow],
+    emergency: Option<&HashMap<uuid::Uuid, EmergencyContact>>,
+) -> String {
+    use crate::web::portal::admin::csv::push_csv;
+
+    let mut out = String::from("name,email,attendee_type,status,registered_at");
+    if emergency.is_some() {
+        out.push_str(
+            ",emergency_contact_name,emergency_contact_phone,emergency_contact_relationship,medical_notes",
+        );
+    }
+    out.push('\n');
+    for r in rows {
+        push_csv(&mut out, &r.name);
+        out.push(',');
+        push_csv(&mut out, &r.email);
+        out.push(',');
+        push_csv(&mut out, if r.is_guest() { "guest" } else { "member" });
+        out.push(',');
+        push_csv(&mut out, r.status.as_str());
+        out.push(',');
+        push_csv(&mut out, &r.registered_at.to_rfc3339());
+        if let Some(contacts) = emergency {
+            let contact = r.member_id.and_then(|id| contacts.get(&id));
+            for field in [
+                contact.map(|c| c.name.as_str()),
+                contact.map(|c| c.phone.as_str()),
+                contact.map(|c| c.relationship.as_str()),
+                contact.map(|c| c.medical_notes.as_str()),
+            ] {
+                out.push(',');
+                push_csv(&mut out, field.unwrap_or_default());
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
 pub async fn admin_delete_event(
     State(settings): State<Arc<Settings>>,
     State(event_repo): State<Arc<dyn EventRepository>>,
@@ -955,3 +1382,110 @@ pub struct DeleteEventForm {
     #[allow(dead_code)]
     pub csrf_token: String,
 }
+
+#[derive(Template)]
+#[template(path = "admin/event_proposals.html")]
+pub struct AdminEventProposalsTemplate {
+    pub base: BaseContext,
+    pub proposals: Vec<AdminProposalInfo>,
+}
+
+pub struct AdminProposalInfo {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub event_type: String,
+    pub visibility: String,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub location: Option<String>,
+    pub proposer_name: String,
+    pub proposer_email: String,
+    pub submitted_at: String,
+}
+
+/// Review queue: every `Proposed` event, oldest first.
+pub async fn admin_event_proposals_page(
+    State(proposal_service): State<Arc<EventProposalService>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+) -> impl IntoResponse {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
+
+    let mut proposals = Vec::new();
+    for e in proposal_service.pending().await.unwrap_or_default() {
+        let proposer = member_repo.find_by_id(e.created_by).await.ok().flatten();
+        proposals.push(AdminProposalInfo {
+            id: e.id.to_string(),
+            title: e.title,
+            description: e.description,
+            event_type: e.event_type.to_string(),
+            visibility: e.visibility.to_string(),
+            start_time: current_user.locale.date_time(&e.start_time),
+            end_time: e.end_time.map(|t| current_user.locale.date_time(&t)),
+            location: e.location,
+            proposer_name: proposer
+                .as_ref()
+                .map(|m| m.full_name.clone())
+                .unwrap_or_else(|| "Unknown member".to_string()),
+            proposer_email: proposer.map(|m| m.email).unwrap_or_default(),
+            submitted_at: current_user.locale.date_time(&e.created_at),
+        });
+    }
+
+    HtmlTemplate(AdminEventProposalsTemplate { base, proposals })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewProposalForm {
+    #[serde(default)]
+    pub feedback: String,
+    #[allow(dead_code)]
+    pub csrf_token: Option<String>,
+}
+
+pub async fn admin_approve_proposal(
+    State(proposal_service): State<Arc<EventProposalService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<String>,
+    axum::Form(form): axum::Form<ReviewProposalForm>,
+) -> impl IntoResponse {
+    let Ok(event_id) = uuid::Uuid::parse_str(&id) else {
+        return partials::admin_alert("error", "Invalid event ID", false);
+    };
+    match proposal_service
+        .approve(current_user.member.id, event_id, Some(form.feedback))
+        .await
+    {
+        Ok(event) => partials::admin_alert(
+            "success",
+            &format!("Approved \"{}\" — it is now published.", event.title),
+            true,
+        ),
+        Err(e) => partials::admin_alert("error", &format!("Could not approve: {}", e), false),
+    }
+}
+
+pub async fn admin_reject_proposal(
+    State(proposal_service): State<Arc<EventProposalService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<String>,
+    axum::Form(form): axum::Form<ReviewProposalForm>,
+) -> impl IntoResponse {
+    let Ok(event_id) = uuid::Uuid::parse_str(&id) else {
+        return partials::admin_alert("error", "Invalid event ID", false);
+    };
+    match proposal_service
+        .reject(current_user.member.id, event_id, form.feedback)
+        .await
+    {
+        Ok(event) => partials::admin_alert(
+            "success",
+            &format!("Rejected \"{}\". The proposer has been notified.", event.title),
+            true,
+        ),
+        Err(e) => partials::admin_alert("error", &format!("Could not reject: {}", e), false),
+    }
+}
@@ -0,0 +1,104 @@
+//! Shared result handling for admin create/edit forms.
+//!
+//! Each form lives in its own `_*_form.html` partial that the full page
+//! includes and that the POST handler can render on its own. The form
+//! posts over HTMX and swaps itself (`hx-target="this"`,
+//! `hx-swap="outerHTML"`), so every handler answers in one of two ways:
+//!
+//!   - validation failed → [`form_invalid`] with the partial rebuilt
+//!     from the submitted values plus a [`FormErrors`], so the admin
+//!     sees the message next to the offending field and nothing they
+//!     typed is lost;
+//!   - saved → [`form_saved`], which sends the browser on to the next
+//!     page with a toast.
+//!
+//! A service rejection that can't be pinned on one field goes under
+//! [`FORM`] and renders above the fields.
+
+use std::collections::BTreeMap;
+
+use askama::Template;
+use axum::{
+    body::Body,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::web::templates::HtmlTemplate;
+
+/// Key for errors that belong to the form as a whole rather than a
+/// single input.
+pub const FORM: &str = "form";
+
+/// Validation messages keyed by form field name. Templates look them
+/// up with `errors.get("field")`; the first message recorded for a
+/// field wins, so check the most basic problem (missing) first.
+#[derive(Debug, Default, Clone)]
+pub struct FormErrors(BTreeMap<&'static str, String>);
+
+impl FormErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) {
+        self.0.entry(field).or_insert_with(|| message.into());
+    }
+
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The form-level message, if any. See [`FORM`].
+    pub fn form(&self) -> Option<&str> {
+        self.get(FORM)
+    }
+
+    /// A single form-level error, for service failures after the
+    /// fields themselves validated.
+    pub fn form_level(message: impl Into<String>) -> Self {
+        let mut errors = Self::new();
+        errors.add(FORM, message);
+        errors
+    }
+}
+
+/// Re-render a form partial after a validation failure. Answers 200
+/// rather than 422: htmx only swaps 2xx responses under the default
+/// config in `layouts/base.html`, and the form must replace itself.
+pub fn form_invalid<T: Template>(partial: T) -> Response {
+    HtmlTemplate(partial).into_response()
+}
+
+/// Successful submit: `HX-Redirect` to `location` with a success
+/// toast, the same shape the portal profile and proposal forms use.
+pub fn form_saved(location: &str, toast: &str) -> Response {
+    let toast = serde_json::json!({ "message": toast, "type": "success" }).to_string();
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("HX-Redirect", location)
+        .header("X-Toast", toast)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_message_per_field_wins() {
+        let mut errors = FormErrors::new();
+        assert!(errors.is_empty());
+        errors.add("title", "Title is required");
+        errors.add("title", "Title is too long");
+        assert_eq!(errors.get("title"), Some("Title is required"));
+        assert_eq!(errors.get("location"), None);
+        assert_eq!(errors.form(), None);
+        assert_eq!(FormErrors::form_level("boom").form(), Some("boom"));
+    }
+}
@@ -0,0 +1,161 @@
+//! Admin page for the outbound integration log. Backs onto
+//! IntegrationLogService; filters by integration and outcome, and lets
+//! an admin replay a failed attempt once the underlying problem (bad
+//! role ID, Discord outage) is fixed. Also shows how many deliveries
+//! are still queued in the outbox and how many ran out of retries.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    integrations::IntegrationManager,
+    service::{
+        audit_service::AuditService,
+        integration_log_service::{IntegrationLogFilter, IntegrationLogService},
+        integration_outbox_service::{IntegrationOutboxService, OutboxCounts},
+    },
+    web::{
+        portal::admin::test_result::test_result_html,
+        templates::{BaseContext, HtmlTemplate},
+    },
+};
+
+#[derive(Template)]
+#[template(path = "admin/integration_log.html")]
+pub struct IntegrationLogTemplate {
+    pub base: BaseContext,
+    pub entries: Vec<IntegrationLogDisplay>,
+    pub outbox: OutboxCounts,
+    pub integrations: Vec<String>,
+    pub integration_filter: String,
+    pub status_filter: String,
+    pub limit: i64,
+}
+
+pub struct IntegrationLogDisplay {
+    pub id: String,
+    pub integration: String,
+    pub event_type: String,
+    pub target: String,
+    pub summary: String,
+    pub ok: bool,
+    pub error: String,
+    pub is_replay: bool,
+    /// Failed and not yet replayed — the only rows with a replay button.
+    pub can_replay: bool,
+    pub when: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IntegrationLogQuery {
+    #[serde(default)]
+    pub integration: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+pub async fn integration_log_page(
+    State(log_service): State<Arc<IntegrationLogService>>,
+    State(outbox_service): State<Arc<IntegrationOutboxService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+    Query(query): Query<IntegrationLogQuery>,
+) -> Response {
+    let limit = query.limit.unwrap_or(100).clamp(10, 500);
+    let status = match query.status.as_str() {
+        "ok" | "error" => query.status.clone(),
+        _ => String::new(),
+    };
+    let filter = IntegrationLogFilter {
+        integration: query.integration.clone(),
+        status: status.clone(),
+        limit,
+    };
+
+    let entries = log_service
+        .recent(&filter)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| IntegrationLogDisplay {
+            id: e.id.to_string(),
+            can_replay: !e.ok && e.replayed_at.is_none(),
+            integration: e.integration,
+            event_type: e.event_type.replace('_', " "),
+            target: short_id(&e.target),
+            summary: e.request_summary,
+            ok: e.ok,
+            error: e.error.unwrap_or_default(),
+            is_replay: e.replay_of.is_some(),
+            when: format!("{} UTC", current_user.locale.date_time(&e.created_at)),
+        })
+        .collect();
+    let integrations = log_service.integration_names().await.unwrap_or_default();
+    let outbox = outbox_service.counts().await.unwrap_or_default();
+
+    HtmlTemplate(IntegrationLogTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        entries,
+        outbox,
+        integrations,
+        integration_filter: query.integration,
+        status_filter: status,
+        limit,
+    })
+    .into_response()
+}
+
+/// Re-run a failed entry against the integration that logged it.
+/// HTMX target; swaps in a success/failure fragment in place of the
+/// button. `IntegrationManager::replay` refuses entries that succeeded
+/// or were already replayed, so a forged request can't re-send them.
+pub async fn replay_integration_log_entry(
+    State(integration_manager): State<Arc<IntegrationManager>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    let target = format!("replay-{}", id);
+    let result = integration_manager.replay(id).await;
+
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "replay_integration_event",
+            "integration_log",
+            &id.to_string(),
+            None,
+            Some(if result.is_ok() { "ok" } else { "error" }),
+            None,
+        )
+        .await;
+
+    match result {
+        Ok(()) => test_result_html(&target, true, "Replayed successfully."),
+        Err(e) => test_result_html(&target, false, &format!("Replay failed: {}", e)),
+    }
+}
+
+fn short_id(id: &str) -> String {
+    // UUIDs are 36 chars; show the first 8 to keep the table readable.
+    // `get` rather than slicing: a non-UUID target with a multi-byte
+    // character straddling byte 8 would panic on `&id[..8]`.
+    if id.len() > 8 {
+        format!("{}…", id.get(..8).unwrap_or(id))
+    } else {
+        id.to_string()
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use askama::Template;
@@ -11,8 +12,12 @@ use axum::{
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
+    domain::SignupField,
     repository::MemberRepository,
-    service::{member_service::MemberService, membership_type_service::MembershipTypeService},
+    service::{
+        member_service::MemberService, membership_type_service::MembershipTypeService,
+        signup_field_service::SignupFieldService,
+    },
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -23,6 +28,10 @@ use super::AdminMembersQuery;
 /// member, with all non-credential fields. Audit row is written
 /// through `MemberService::audit_export` so abuse is traceable.
 ///
+/// After the fixed columns comes one `custom:<key>` column per
+/// configured custom signup field, so the file imports back through
+/// `admin_members_import` with those answers intact.
+///
 /// Response is `text/csv; charset=utf-8` with
 /// `Content-Disposition: attachment` so browsers download rather
 /// than rendering. Filename includes the UTC date so re-downloads
@@ -31,45 +40,14 @@ pub async fn admin_members_export(
     State(member_repo): State<Arc<dyn MemberRepository>>,
     State(member_service): State<Arc<MemberService>>,
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(signup_field_service): State<Arc<SignupFieldService>>,
     Extension(current_user): Extension<CurrentUser>,
     Query(query): Query<AdminMembersQuery>,
 ) -> Response {
-    use crate::repository::{MemberQuery, MemberSortField, SortOrder};
-
     let all_types = membership_type_service.list(true).await.unwrap_or_default();
-    let type_filter_id = query
-        .member_type
-        .as_deref()
-        .and_then(|slug| all_types.iter().find(|t| t.slug == slug).map(|t| t.id));
-
-    let sort_field = query.sort.as_deref().unwrap_or("name");
-    let sort_order = query.order.as_deref().unwrap_or("asc");
-
-    let typed_query = MemberQuery {
-        search: query.q.clone().filter(|s| !s.is_empty()),
-        status: query
-            .status
-            .as_deref()
-            .and_then(crate::domain::MemberStatus::from_str),
-        membership_type_id: type_filter_id,
-        sort: match sort_field {
-            "status" => MemberSortField::Status,
-            "type" => MemberSortField::MembershipType,
-            "joined" => MemberSortField::Joined,
-            "dues" => MemberSortField::DuesPaidUntil,
-            _ => MemberSortField::Name,
-        },
-        order: if sort_order == "desc" {
-            SortOrder::Desc
-        } else {
-            SortOrder::Asc
-        },
-        // Ignored by `export_rows`, but the field is non-optional.
-        limit: 0,
-        offset: 0,
-    };
+    let filter = query.filter(&all_types);
 
-    let rows = match member_repo.export_rows(typed_query).await {
+    let rows = match member_repo.export_rows(&filter).await {
         Ok(rows) => rows,
         Err(e) => {
             tracing::error!("admin members export failed: {}", e);
@@ -81,7 +59,29 @@ pub async fn admin_members_export(
         }
     };
 
-    let body = build_members_csv(&rows);
+    let custom_fields: Vec<SignupField> = signup_field_service
+        .fields()
+        .await
+        .into_iter()
+        .filter(|f| !f.is_profile_field())
+        .collect();
+    let custom_values = if custom_fields.is_empty() {
+        HashMap::new()
+    } else {
+        match signup_field_service.custom_values().await {
+            Ok(values) => values,
+            Err(e) => {
+                tracing::error!("admin members export failed: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to build export. Check server logs.",
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    let body = build_members_csv(&rows, &custom_fields, &custom_values);
 
     let filter_summary = build_filter_summary(&query);
     if let Err(e) = member_service
@@ -114,15 +114,26 @@ pub async fn admin_members_export(
 
 /// Assemble the CSV body: a header row followed by one row per
 /// `MemberExportRow`. Column order matches the
-/// `bulk-member-csv-export` capability spec exactly.
-fn build_members_csv(rows: &[crate::repository::MemberExportRow]) -> String {
+/// `bulk-member-csv-export` capability spec exactly, with
+/// `membership_type_slug` and the `custom:<key>` columns appended.
+fn build_members_csv(
+    rows: &[crate::repository::MemberExportRow],
+    custom_fields: &[SignupField],
+    custom_values: &HashMap<uuid::Uuid, HashMap<String, String>>,
+) -> String {
     use crate::web::portal::admin::csv::push_csv;
 
     let mut out = String::with_capacity(1024 + rows.len() * 256);
     out.push_str(
         "id,email,username,full_name,status,membership_type,joined_at,\
-         dues_paid_until,is_admin,bypass_dues,discord_id,email_verified_at,notes\n",
+         dues_paid_until,is_admin,bypass_dues,discord_id,email_verified_at,notes,\
+         membership_type_slug",
     );
+    for field in custom_fields {
+        out.push(',');
+        push_csv(&mut out, &format!("{}{}", CUSTOM_FIELD_PREFIX, field.key));
+    }
+    out.push('\n');
 
     for r in rows {
         push_csv(&mut out, &r.id.to_string());
@@ -160,6 +171,19 @@ fn build_members_csv(rows: &[crate::repository::MemberExportRow]) -> String {
         );
         out.push(',');
         push_csv(&mut out, r.notes.as_deref().unwrap_or(""));
+        out.push(',');
+        push_csv(&mut out, &r.membership_type_slug);
+        let values = custom_values.get(&r.id);
+        for field in custom_fields {
+            out.push(',');
+            push_csv(
+                &mut out,
+                values
+                    .and_then(|v| v.get(&field.key))
+                    .map(String::as_str)
+                    .unwrap_or(""),
+            );
+        }
         out.push('\n');
     }
     out
@@ -201,12 +225,142 @@ fn build_filter_summary(q: &AdminMembersQuery) -> String {
 /// field level too rather than trusting the outer cap.
 const IMPORT_FILE_MAX_BYTES: usize = 5 * 1024 * 1024;
 
+/// A column the importer understands. [`IMPORT_COLUMNS`] drives header
+/// matching, the column-mapping step and the downloadable template, so
+/// the three can't drift apart.
+pub struct ImportColumn {
+    pub name: &'static str,
+    pub required: bool,
+    /// Cell for the template's example row.
+    pub example: &'static str,
+}
+
+pub const IMPORT_COLUMNS: &[ImportColumn] = &[
+    ImportColumn {
+        name: "email",
+        required: true,
+        example: "jane@example.com",
+    },
+    ImportColumn {
+        name: "username",
+        required: true,
+        example: "jdoe",
+    },
+    ImportColumn {
+        name: "full_name",
+        required: true,
+        example: "Jane Doe",
+    },
+    ImportColumn {
+        name: "membership_type_slug",
+        required: true,
+        example: "member",
+    },
+    ImportColumn {
+        name: "status",
+        required: false,
+        example: "Pending",
+    },
+    ImportColumn {
+        name: "notes",
+        required: false,
+        example: "Imported from the old roster",
+    },
+    ImportColumn {
+        name: "discord_id",
+        required: false,
+        example: "",
+    },
+    ImportColumn {
+        name: "dues_paid_until",
+        required: false,
+        example: "2026-12-31",
+    },
+    ImportColumn {
+        name: "joined_at",
+        required: false,
+        example: "2024-03-01",
+    },
+    ImportColumn {
+        name: "email_verified_at",
+        required: false,
+        example: "",
+    },
+    ImportColumn {
+        name: "stripe_customer_id",
+        required: false,
+        example: "",
+    },
+    ImportColumn {
+        name: "stripe_subscription_id",
+        required: false,
+        example: "",
+    },
+];
+
+/// Header prefix marking a custom signup field column, e.g.
+/// `custom:pronouns`. The export writes these and the importer reads
+/// them back by key.
+const CUSTOM_FIELD_PREFIX: &str = "custom:";
+
+/// Which CSV column (by position) feeds each known field. Fields
+/// absent from the map aren't in the file.
+type ColumnMap = HashMap<&'static str, usize>;
+
 #[derive(Template)]
 #[template(path = "admin/member_import.html")]
 pub struct AdminMemberImportPageTemplate {
     pub base: BaseContext,
 }
 
+/// GET — download a CSV with every accepted column and one example
+/// row, ready to fill in. The example's `membership_type_slug` is a
+/// real active type so the file imports as-is.
+pub async fn admin_members_import_template(
+    State(membership_type_service): State<Arc<MembershipTypeService>>,
+) -> Response {
+    let slug = membership_type_service
+        .list(false)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+        .map(|t| t.slug);
+    let body = build_import_template(slug.as_deref());
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"members-import-template.csv\"".to_string(),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn build_import_template(membership_type_slug: Option<&str>) -> String {
+    use crate::web::portal::admin::csv::push_csv;
+
+    let names: Vec<&str> = IMPORT_COLUMNS.iter().map(|c| c.name).collect();
+    let mut out = names.join(",");
+    out.push('\n');
+    for (i, column) in IMPORT_COLUMNS.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let example = match (column.name, membership_type_slug) {
+            ("membership_type_slug", Some(slug)) => slug,
+            _ => column.example,
+        };
+        push_csv(&mut out, example);
+    }
+    out.push('\n');
+    out
+}
+
 /// GET — show the upload form. Pure render; no service work.
 pub async fn admin_members_import_page(
     State(csrf_service): State<Arc<CsrfService>>,
@@ -224,6 +378,7 @@ pub struct AdminMemberImportResultTemplate {
     pub succeeded: u32,
     pub failed: u32,
     pub failures: Vec<ImportFailureView>,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Clone)]
@@ -239,24 +394,94 @@ pub struct AdminMemberImportErrorTemplate {
     pub message: String,
 }
 
+/// Column-mapping step, shown when the uploaded header doesn't cover
+/// every required field. The file rides along base64-encoded in a
+/// hidden field so the admin doesn't have to pick it again.
+#[derive(Template)]
+#[template(path = "admin/member_import_mapping.html")]
+pub struct AdminMemberImportMappingTemplate {
+    pub csrf_token: String,
+    pub file_name: String,
+    pub csv_data: String,
+    /// Comma-separated required fields still unmapped.
+    pub missing: String,
+    pub fields: Vec<MappingFieldView>,
+}
+
+pub struct MappingFieldView {
+    pub name: &'static str,
+    pub required: bool,
+    pub options: Vec<MappingOptionView>,
+}
+
+pub struct MappingOptionView {
+    pub index: usize,
+    pub header: String,
+    pub selected: bool,
+}
+
 /// POST — accept a multipart upload with a `file` field carrying a CSV.
 /// The handler parses the CSV (5 MB cap, header validation), then
 /// delegates each row to `MemberService::bulk_import`, then renders an
 /// HTMX result fragment. CSV parsing is the handler's job; service
 /// stays format-agnostic.
+///
+/// When the header doesn't name every required column, the response is
+/// the column-mapping form instead. It posts back here with the file in
+/// `csv_data`, `mapping=1`, and one `map_<field>` per known column
+/// holding the chosen column's position (empty = not in the file).
 pub async fn admin_members_import(
     State(member_service): State<Arc<MemberService>>,
     Extension(current_user): Extension<CurrentUser>,
     mut multipart: Multipart,
 ) -> Response {
+    use base64::{engine::general_purpose::STANDARD as B64, Engine};
+
     let mut file_bytes: Option<Vec<u8>> = None;
     let mut file_name = String::new();
+    let mut csrf_token = String::new();
+    let mut mapping_submitted = false;
+    let mut mapping = ColumnMap::new();
 
     while let Ok(Some(field)) = multipart.next_field().await {
-        match field.name().unwrap_or("") {
-            "csrf_token" => {
+        let name = field.name().unwrap_or("").to_string();
+        if let Some(column) = name.strip_prefix("map_") {
+            let value = field.text().await.unwrap_or_default();
+            if let (Some(column), Ok(index)) = (
+                IMPORT_COLUMNS.iter().find(|c| c.name == column),
+                value.trim().parse::<usize>(),
+            ) {
+                mapping.insert(column.name, index);
+            }
+            continue;
+        }
+        match name.as_str() {
+            "csrf_token" => csrf_token = field.text().await.unwrap_or_default(),
+            "mapping" => {
+                mapping_submitted = true;
                 let _ = field.text().await;
             }
+            "file_name" => file_name = field.text().await.unwrap_or_default(),
+            "csv_data" => {
+                let encoded = field.text().await.unwrap_or_default();
+                match B64.decode(encoded.trim()) {
+                    Ok(b) if b.len() > IMPORT_FILE_MAX_BYTES => {
+                        return import_error_fragment(&format!(
+                            "File too large ({} bytes). Maximum is {} MB.",
+                            b.len(),
+                            IMPORT_FILE_MAX_BYTES / (1024 * 1024),
+                        ))
+                        .into_response();
+                    }
+                    Ok(b) => file_bytes = Some(b),
+                    Err(_) => {
+                        return import_error_fragment(
+                            "The uploaded file was lost between steps. Please upload it again.",
+                        )
+                        .into_response();
+                    }
+                }
+            }
             "file" => {
                 file_name = field.file_name().unwrap_or("members.csv").to_string();
                 match field.bytes().await {
@@ -296,9 +521,40 @@ pub async fn admin_members_import(
         }
     };
 
-    let rows = match parse_import_csv(&bytes) {
+    let explicit = mapping_submitted.then_some(&mapping);
+    let rows = match parse_import_csv(&bytes, explicit) {
         Ok(rows) => rows,
-        Err(e) => return import_error_fragment(&e).into_response(),
+        Err(ImportParseError::Invalid(e)) => return import_error_fragment(&e).into_response(),
+        Err(ImportParseError::NeedsMapping {
+            headers,
+            columns,
+            missing,
+        }) => {
+            let fields = IMPORT_COLUMNS
+                .iter()
+                .map(|c| MappingFieldView {
+                    name: c.name,
+                    required: c.required,
+                    options: headers
+                        .iter()
+                        .enumerate()
+                        .map(|(index, header)| MappingOptionView {
+                            index,
+                            header: header.clone(),
+                            selected: columns.get(c.name) == Some(&index),
+                        })
+                        .collect(),
+                })
+                .collect();
+            return HtmlTemplate(AdminMemberImportMappingTemplate {
+                csrf_token,
+                file_name,
+                csv_data: B64.encode(&bytes),
+                missing: missing.join(", "),
+                fields,
+            })
+            .into_response();
+        }
     };
 
     let summary = match member_service
@@ -326,13 +582,44 @@ pub async fn admin_members_import(
         succeeded: summary.succeeded,
         failed: summary.failed,
         failures,
+        warnings: summary.warnings,
     })
     .into_response()
 }
 
-/// Parse the raw CSV bytes into `Vec<ImportRow>`. Returns Err with a
-/// user-facing message on header validation failures (missing required
-/// columns) or unreadable file structure.
+/// Why `parse_import_csv` couldn't produce rows.
+enum ImportParseError {
+    /// The file itself is unusable; abort with this message.
+    Invalid(String),
+    /// The header is readable but doesn't cover every required field.
+    /// Carries what the mapping form needs: the uploaded headers, the
+    /// columns matched so far, and the required fields still missing.
+    NeedsMapping {
+        headers: Vec<String>,
+        columns: ColumnMap,
+        missing: Vec<&'static str>,
+    },
+}
+
+/// Match uploaded headers to known fields by name, case-insensitively.
+/// Extra columns are ignored.
+fn guess_columns(headers: &csv::StringRecord) -> ColumnMap {
+    IMPORT_COLUMNS
+        .iter()
+        .filter_map(|c| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(c.name))
+                .map(|i| (c.name, i))
+        })
+        .collect()
+}
+
+/// Parse the raw CSV bytes into `Vec<ImportRow>`. Columns come from
+/// `mapping` when the admin went through the mapping step, otherwise
+/// from the header names. Either way every required field must end up
+/// mapped; if not, the error asks for (another round of) mapping.
+/// Unreadable file structure is `Invalid`.
 ///
 /// Row-level coercion failures (e.g., a bad `status` value, a malformed
 /// row) are converted into `ImportRow`s with empty fields so the
@@ -341,7 +628,8 @@ pub async fn admin_members_import(
 /// missing the `email` column) abort here.
 fn parse_import_csv(
     bytes: &[u8],
-) -> std::result::Result<Vec<crate::service::member_service::ImportRow>, String> {
+    mapping: Option<&ColumnMap>,
+) -> std::result::Result<Vec<crate::service::member_service::ImportRow>, ImportParseError> {
     use crate::service::member_service::ImportRow;
     use chrono::{DateTime, NaiveDate, Utc};
 
@@ -352,27 +640,42 @@ fn parse_import_csv(
 
     let headers = match reader.headers() {
         Ok(h) => h.clone(),
-        Err(e) => return Err(format!("Could not read CSV header: {}", e)),
+        Err(e) => {
+            return Err(ImportParseError::Invalid(format!(
+                "Could not read CSV header: {}",
+                e
+            )))
+        }
     };
 
-    // Build a case-insensitive column index. Extra columns are
-    // tolerated and silently ignored; required columns must all be
-    // present or the batch aborts.
-    let col = |name: &str| -> Option<usize> {
-        headers
+    // An explicit mapping replaces name matching entirely; positions
+    // past the end of the header count as unmapped.
+    let columns: ColumnMap = match mapping {
+        Some(m) => m
             .iter()
-            .position(|h| h.trim().eq_ignore_ascii_case(name))
+            .filter(|(_, &i)| i < headers.len())
+            .map(|(&k, &i)| (k, i))
+            .collect(),
+        None => guess_columns(&headers),
     };
+    let missing: Vec<&'static str> = IMPORT_COLUMNS
+        .iter()
+        .filter(|c| c.required && !columns.contains_key(c.name))
+        .map(|c| c.name)
+        .collect();
+    if !missing.is_empty() {
+        return Err(ImportParseError::NeedsMapping {
+            headers: headers.iter().map(|h| h.trim().to_string()).collect(),
+            columns,
+            missing,
+        });
+    }
 
-    let email_idx =
-        col("email").ok_or_else(|| "Missing required column 'email' in CSV header.".to_string())?;
-    let username_idx = col("username")
-        .ok_or_else(|| "Missing required column 'username' in CSV header.".to_string())?;
-    let full_name_idx = col("full_name")
-        .ok_or_else(|| "Missing required column 'full_name' in CSV header.".to_string())?;
-    let mtype_idx = col("membership_type_slug").ok_or_else(|| {
-        "Missing required column 'membership_type_slug' in CSV header.".to_string()
-    })?;
+    let col = |name: &str| -> Option<usize> { columns.get(name).copied() };
+    let email_idx = columns["email"];
+    let username_idx = columns["username"];
+    let full_name_idx = columns["full_name"];
+    let mtype_idx = columns["membership_type_slug"];
     let status_idx = col("status");
     let notes_idx = col("notes");
     let discord_idx = col("discord_id");
@@ -383,6 +686,19 @@ fn parse_import_csv(
     let stripe_subscription_idx = col("stripe_subscription_id");
     let joined_at_idx = col("joined_at");
     let email_verified_at_idx = col("email_verified_at");
+    // Custom-field columns are picked up by header name even after
+    // the mapping step; the service decides which keys it knows.
+    let custom_columns: Vec<(usize, String)> = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(i, h)| {
+            let h = h.trim();
+            h.get(..CUSTOM_FIELD_PREFIX.len())
+                .filter(|p| p.eq_ignore_ascii_case(CUSTOM_FIELD_PREFIX))
+                .map(|_| (i, h[CUSTOM_FIELD_PREFIX.len()..].trim().to_ascii_lowercase()))
+        })
+        .filter(|(_, key)| !key.is_empty())
+        .collect();
 
     // Parse a timestamp cell. RFC 3339 first; fall back to
     // `YYYY-MM-DD` (interpreted as midnight UTC). Empty cell → `None`.
@@ -410,7 +726,12 @@ fn parse_import_csv(
     for record in reader.records() {
         let rec = match record {
             Ok(r) => r,
-            Err(e) => return Err(format!("Malformed CSV row: {}", e)),
+            Err(e) => {
+                return Err(ImportParseError::Invalid(format!(
+                    "Malformed CSV row: {}",
+                    e
+                )))
+            }
         };
 
         let get = |i: usize| -> String { rec.get(i).unwrap_or("").to_string() };
@@ -428,7 +749,7 @@ fn parse_import_csv(
         };
 
         let status =
-            get_opt(status_idx).and_then(|s| crate::domain::MemberStatus::from_str(s.trim()));
+            get_opt(status_idx).and_then(|s| s.trim().parse::<crate::domain::MemberStatus>().ok());
 
         // Parse the three optional timestamps; the first failure wins
         // and stamps `parse_error` so the row fails downstream rather
@@ -479,6 +800,14 @@ fn parse_import_csv(
             stripe_subscription_id: get_trimmed_opt(stripe_subscription_idx),
             joined_at,
             email_verified_at,
+            custom_fields: custom_columns
+                .iter()
+                .filter_map(|(i, key)| {
+                    rec.get(*i)
+                        .filter(|v| !v.trim().is_empty())
+                        .map(|v| (key.clone(), v.to_string()))
+                })
+                .collect(),
             parse_error,
         });
     }
@@ -7,26 +7,67 @@ use serde::Deserialize;
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
+    domain::DuplicateMemberField,
+    error::AppError,
+    repository::MemberRepository,
     service::{member_service::MemberService, membership_type_service::MembershipTypeService},
-    web::templates::{BaseContext, HtmlTemplate},
+    web::{
+        portal::admin::forms::{form_invalid, form_saved, FormErrors},
+        templates::{BaseContext, HtmlTemplate},
+    },
 };
 
 use super::MembershipTypeOption;
 
+/// What the admin typed, echoed back into the form when validation
+/// fails. The password is deliberately not part of it.
+#[derive(Debug, Clone)]
+pub struct MemberFormValues {
+    pub email: String,
+    pub username: String,
+    pub full_name: String,
+    pub membership_type_id: String,
+    pub status: String,
+    pub notes: String,
+}
+
+impl Default for MemberFormValues {
+    fn default() -> Self {
+        Self {
+            email: String::new(),
+            username: String::new(),
+            full_name: String::new(),
+            membership_type_id: String::new(),
+            status: "Pending".to_string(),
+            notes: String::new(),
+        }
+    }
+}
+
 #[derive(Template)]
 #[template(path = "admin/member_new.html")]
 pub struct AdminNewMemberTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
     pub type_options: Vec<MembershipTypeOption>,
+    pub values: MemberFormValues,
+    pub errors: FormErrors,
 }
 
-pub async fn admin_new_member_page(
-    State(membership_type_service): State<Arc<MembershipTypeService>>,
-    State(csrf_service): State<Arc<CsrfService>>,
-    Extension(current_user): Extension<CurrentUser>,
-    Extension(session_info): Extension<SessionInfo>,
-) -> axum::response::Response {
-    let type_options: Vec<MembershipTypeOption> = membership_type_service
+/// Just the `<form>`, for re-rendering after a failed submit.
+#[derive(Template)]
+#[template(path = "admin/_member_form.html")]
+pub struct AdminMemberFormTemplate {
+    pub csrf_token: String,
+    pub type_options: Vec<MembershipTypeOption>,
+    pub values: MemberFormValues,
+    pub errors: FormErrors,
+}
+
+async fn membership_type_options(
+    membership_type_service: &MembershipTypeService,
+) -> Vec<MembershipTypeOption> {
+    membership_type_service
         .list(false)
         .await
         .unwrap_or_default()
@@ -36,11 +77,22 @@ pub async fn admin_new_member_page(
             slug: t.slug,
             name: t.name,
         })
-        .collect();
+        .collect()
+}
 
+pub async fn admin_new_member_page(
+    State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+) -> axum::response::Response {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
     let template = AdminNewMemberTemplate {
-        base: BaseContext::for_member(&csrf_service, &current_user, &session_info).await,
-        type_options,
+        csrf_token: base.csrf_token.clone(),
+        base,
+        type_options: membership_type_options(&membership_type_service).await,
+        values: MemberFormValues::default(),
+        errors: FormErrors::new(),
     };
 
     HtmlTemplate(template).into_response()
@@ -55,48 +107,87 @@ pub struct AdminCreateMemberForm {
     pub membership_type_id: String,
     pub status: String,
     pub notes: Option<String>,
-    #[allow(dead_code)]
     pub csrf_token: String,
 }
 
+/// Field checks the repo would otherwise report one at a time
+/// (duplicates) or not at all (password strength).
+async fn validate_member_form(
+    member_repo: &dyn MemberRepository,
+    form: &AdminCreateMemberForm,
+) -> FormErrors {
+    let mut errors = FormErrors::new();
+
+    let email = form.email.trim();
+    if email.is_empty() {
+        errors.add("email", "Email is required");
+    } else if !email.contains('@') || email.contains(char::is_whitespace) {
+        errors.add("email", "Enter a valid email address");
+    } else if let Ok(Some(_)) = member_repo.find_by_email(email).await {
+        errors.add("email", DuplicateMemberField::Email.message());
+    }
+
+    let username = form.username.trim();
+    if username.is_empty() {
+        errors.add("username", "Username is required");
+    } else if let Err(msg) = crate::domain::validate_username(username) {
+        errors.add("username", msg);
+    } else if let Ok(Some(_)) = member_repo.find_by_username(username).await {
+        errors.add("username", DuplicateMemberField::Username.message());
+    }
+
+    if form.full_name.trim().is_empty() {
+        errors.add("full_name", "Full name is required");
+    }
+
+    if let Err(msg) = crate::auth::validate_password(&form.password) {
+        errors.add("password", msg);
+    }
+
+    errors
+}
+
 pub async fn admin_create_member(
     State(member_service): State<Arc<MemberService>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(membership_type_service): State<Arc<MembershipTypeService>>,
     Extension(current_user): Extension<CurrentUser>,
     axum::Form(form): axum::Form<AdminCreateMemberForm>,
 ) -> axum::response::Response {
-    use crate::domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest};
-
-    fn render_error(message: &str) -> axum::response::Response {
-        axum::response::Html(format!(
-            r#"<!DOCTYPE html>
-            <html>
-            <head>
-                <title>Error - Coterie</title>
-                <link rel="stylesheet" href="/static/style.css">
-            </head>
-            <body class="bg-gray-100 min-h-screen flex items-center justify-center">
-                <div class="bg-white p-8 rounded-lg shadow-md max-w-md">
-                    <h1 class="text-xl font-bold text-red-600 mb-4">Error Creating Member</h1>
-                    <p class="text-gray-700 mb-4">{}</p>
-                    <a href="/portal/admin/members/new" class="text-blue-600 hover:underline">Go back and try again</a>
-                </div>
-            </body>
-            </html>"#,
-            crate::web::escape_html(message),
-        )).into_response()
-    }
+    use crate::domain::{CreateMemberRequest, UpdateMemberRequest};
 
-    let membership_type_id = match uuid::Uuid::parse_str(&form.membership_type_id) {
-        Ok(id) => id,
-        Err(_) => return render_error("Invalid membership type."),
-    };
-
-    let create_request = CreateMemberRequest {
+    let values = MemberFormValues {
         email: form.email.clone(),
         username: form.username.clone(),
         full_name: form.full_name.clone(),
-        password: form.password,
-        membership_type_id: Some(membership_type_id),
+        membership_type_id: form.membership_type_id.clone(),
+        status: form.status.clone(),
+        notes: form.notes.clone().unwrap_or_default(),
+    };
+    let invalid = |errors: FormErrors, type_options: Vec<MembershipTypeOption>| {
+        form_invalid(AdminMemberFormTemplate {
+            csrf_token: form.csrf_token.clone(),
+            type_options,
+            values: values.clone(),
+            errors,
+        })
+    };
+
+    let mut errors = validate_member_form(member_repo.as_ref(), &form).await;
+    let membership_type_id = uuid::Uuid::parse_str(&form.membership_type_id).ok();
+    if membership_type_id.is_none() {
+        errors.add("membership_type_id", "Choose a membership type");
+    }
+    if !errors.is_empty() {
+        return invalid(errors, membership_type_options(&membership_type_service).await);
+    }
+
+    let create_request = CreateMemberRequest {
+        email: form.email.trim().to_string(),
+        username: form.username.trim().to_string(),
+        full_name: form.full_name.trim().to_string(),
+        password: form.password.clone(),
+        membership_type_id,
         ..Default::default()
     };
 
@@ -112,13 +203,13 @@ pub async fn admin_create_member(
             // landing on a default.
             let status = match form.status.as_str() {
                 "" | "Pending" => None,
-                s => MemberStatus::from_str(s),
+                s => s.parse().ok(),
             };
 
             if status.is_some() || form.notes.is_some() {
                 let update = UpdateMemberRequest {
                     status,
-                    notes: form.notes,
+                    notes: form.notes.clone(),
                     ..Default::default()
                 };
                 if let Err(e) = member_service
@@ -138,9 +229,22 @@ pub async fn admin_create_member(
                 }
             }
 
-            axum::response::Redirect::to(&format!("/portal/admin/members/{}", member.id))
-                .into_response()
+            form_saved(&format!("/portal/admin/members/{}", member.id), "Member created")
+        }
+        Err(e) => {
+            let errors = match e {
+                // Taken between the form check above and the insert.
+                AppError::DuplicateMember(field) => {
+                    let mut errors = FormErrors::new();
+                    errors.add(field.field(), field.message());
+                    errors
+                }
+                e => {
+                    tracing::error!("Admin member create failed: {}", e);
+                    FormErrors::form_level("Could not create member. Please try again.")
+                }
+            };
+            invalid(errors, membership_type_options(&membership_type_service).await)
         }
-        Err(e) => render_error(&e.to_string()),
     }
 }
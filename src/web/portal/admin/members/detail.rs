@@ -11,8 +11,19 @@ use serde::Deserialize;
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
-    repository::{MemberRepository, SavedCardRepository},
-    service::{member_service::MemberService, membership_type_service::MembershipTypeService},
+    domain::{DuesExtensionBase, DuesStatus},
+    integrations::IntegrationManager,
+    repository::{
+        EventRepository, MemberAttendanceRow, MemberRepository, PaymentRepository,
+        SavedCardRepository,
+    },
+    service::{
+        emergency_contact_service::{EmergencyContact, EmergencyContactService},
+        member_service::MemberService,
+        membership_type_service::MembershipTypeService,
+        settings_service::SettingsService,
+        signup_field_service::{SignupAnswer, SignupFieldService},
+    },
     web::{
         portal::admin::partials,
         templates::{filters, BaseContext, HtmlTemplate},
@@ -27,6 +38,18 @@ pub struct AdminMemberDetailTemplate {
     pub base: BaseContext,
     pub member: AdminMemberDetailInfo,
     pub type_options: Vec<MembershipTypeOption>,
+    pub default_extension_base: DuesExtensionBase,
+    pub extension_bases: Vec<DuesExtensionBase>,
+    /// Answers to the org's extra signup fields.
+    pub signup_answers: Vec<SignupAnswer>,
+    /// What the member gave for emergencies; the card is hidden when
+    /// it's empty.
+    pub emergency: EmergencyContact,
+    /// Every RSVP the member has made, cancelled ones included, most
+    /// recent event first.
+    pub event_history: Vec<MemberAttendanceRow>,
+    /// Registered integrations, for the resync picker.
+    pub integration_names: Vec<String>,
 }
 
 pub struct AdminMemberDetailInfo {
@@ -40,7 +63,10 @@ pub struct AdminMemberDetailInfo {
     pub membership_type_name: String,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub dues_paid_until: Option<chrono::DateTime<chrono::Utc>>,
-    pub dues_expired: bool,
+    /// Dues combined with the grace period and the latest dues payment,
+    /// shown next to the membership status so "Active, payment failed"
+    /// is visible.
+    pub dues_status: DuesStatus,
     pub bypass_dues: bool,
     pub email_verified: bool,
     pub notes: String,
@@ -48,6 +74,7 @@ pub struct AdminMemberDetailInfo {
     pub stripe_customer_id: Option<String>,
     pub stripe_subscription_id: Option<String>,
     pub discord_id: String,
+    pub honorary_until: Option<chrono::DateTime<chrono::Utc>>,
     pub saved_cards: Vec<AdminSavedCardInfo>,
     pub created_at: String,
     pub updated_at: String,
@@ -59,11 +86,18 @@ pub struct AdminSavedCardInfo {
     pub is_default: bool,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn admin_member_detail_page(
     State(member_repo): State<Arc<dyn MemberRepository>>,
     State(saved_card_repo): State<Arc<dyn SavedCardRepository>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    State(event_repo): State<Arc<dyn EventRepository>>,
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(signup_field_service): State<Arc<SignupFieldService>>,
+    State(emergency_contact_service): State<Arc<EmergencyContactService>>,
     State(csrf_service): State<Arc<CsrfService>>,
+    State(integration_manager): State<Arc<IntegrationManager>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
     Path(member_id): Path<String>,
@@ -88,8 +122,13 @@ pub async fn admin_member_detail_page(
         .collect::<String>()
         .to_uppercase();
 
-    let now = chrono::Utc::now();
-    let dues_expired = member.dues_paid_until.map(|d| d < now).unwrap_or(true);
+    let dues_status = crate::web::portal::dues_status_for(
+        &member,
+        payment_repo.as_ref(),
+        chrono::Utc::now(),
+        settings_service.grace_period_days().await,
+    )
+    .await;
 
     // Fetch saved cards for this member
     let saved_cards = saved_card_repo
@@ -137,7 +176,7 @@ pub async fn admin_member_detail_page(
         membership_type_name: type_name,
         joined_at: member.joined_at,
         dues_paid_until: member.dues_paid_until,
-        dues_expired,
+        dues_status,
         bypass_dues: member.bypass_dues,
         email_verified,
         notes: member.notes.unwrap_or_default(),
@@ -145,18 +184,31 @@ pub async fn admin_member_detail_page(
         stripe_customer_id: member.stripe_customer_id,
         stripe_subscription_id: member.stripe_subscription_id,
         discord_id: member.discord_id.unwrap_or_default(),
+        honorary_until: member.honorary_until,
         saved_cards,
-        created_at: member.created_at.format("%B %d, %Y").to_string(),
-        updated_at: member
-            .updated_at
-            .format("%B %d, %Y at %l:%M %p")
-            .to_string(),
+        created_at: current_user.locale.long_date(&member.created_at),
+        updated_at: current_user.locale.long_date_time(&member.updated_at),
     };
 
     let template = AdminMemberDetailTemplate {
         base,
         member: member_info,
         type_options,
+        default_extension_base: settings_service.dues_extension_base().await,
+        extension_bases: DuesExtensionBase::ALL.to_vec(),
+        signup_answers: signup_field_service
+            .answers(member.id)
+            .await
+            .unwrap_or_default(),
+        emergency: emergency_contact_service
+            .get(member.id)
+            .await
+            .unwrap_or_default(),
+        event_history: event_repo
+            .list_member_attendance(member.id, true)
+            .await
+            .unwrap_or_default(),
+        integration_names: integration_manager.names().await,
     };
 
     HtmlTemplate(template).into_response()
@@ -1,20 +1,23 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
     Extension,
 };
 use serde::Deserialize;
 
 use crate::{
-    api::middleware::auth::CurrentUser, repository::PaymentRepository,
+    api::middleware::auth::CurrentUser, domain::DuesExtensionBase, repository::PaymentRepository,
     service::member_service::MemberService, web::portal::admin::partials,
 };
 
 #[derive(Debug, Deserialize)]
 pub struct ExtendDuesForm {
     pub months: i32,
+    /// One of `DuesExtensionBase::as_str()`; empty means the club default.
+    #[serde(default)]
+    pub base: String,
     #[allow(dead_code)]
     pub csrf_token: String,
 }
@@ -37,14 +40,22 @@ pub async fn admin_extend_dues(
         Err(_) => return partials::admin_alert("error", "Invalid member ID", false),
     };
 
+    let base = match form.base.trim() {
+        "" => None,
+        raw => match DuesExtensionBase::parse(raw) {
+            Some(b) => Some(b),
+            None => return partials::admin_alert("error", "Unknown extension base", false),
+        },
+    };
+
     match member_service
-        .extend_dues(current_user.member.id, id, form.months)
+        .extend_dues(current_user.member.id, id, form.months, base)
         .await
     {
         Ok(member) => {
             let new_dues = member
                 .dues_paid_until
-                .map(|d| d.format("%B %d, %Y").to_string())
+                .map(|d| current_user.locale.long_date(&d))
                 .unwrap_or_else(|| "—".to_string());
             partials::admin_alert(
                 "success",
@@ -81,7 +92,7 @@ pub async fn admin_set_dues(
         Ok(member) => {
             let dues = member
                 .dues_paid_until
-                .map(|d| d.format("%B %d, %Y").to_string())
+                .map(|d| current_user.locale.long_date(&d))
                 .unwrap_or_else(|| "—".to_string());
             partials::admin_alert("success", &format!("Dues date set to: {}", dues), true)
         }
@@ -89,21 +100,43 @@ pub async fn admin_set_dues(
     }
 }
 
+/// Payments per page of the member-detail payment history.
+const PAYMENTS_PAGE_SIZE: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct MemberPaymentsQuery {
+    pub page: Option<i64>,
+}
+
+/// One page of the member's payment history, newest first. Page 1
+/// leads with totals over all of their payments; later pages are just
+/// rows, appended by the previous page's "Load more" button.
 pub async fn admin_member_payments(
     State(payment_repo): State<Arc<dyn PaymentRepository>>,
-    Extension(_current_user): Extension<CurrentUser>,
+    Extension(current_user): Extension<CurrentUser>,
     Path(member_id): Path<String>,
+    Query(query): Query<MemberPaymentsQuery>,
 ) -> impl IntoResponse {
     let id = match uuid::Uuid::parse_str(&member_id) {
         Ok(id) => id,
         Err(_) => return partials::admin_alert("error", "Invalid member ID", false),
     };
+    let page = query.page.unwrap_or(1).max(1);
 
-    let payments = payment_repo.find_by_member(id).await.unwrap_or_default();
+    let totals = payment_repo.member_payment_totals(id).await.unwrap_or_default();
+    let payments = payment_repo
+        .find_by_member_paginated(id, PAYMENTS_PAGE_SIZE, (page - 1) * PAYMENTS_PAGE_SIZE)
+        .await
+        .unwrap_or_default();
 
     let rows = payments
         .iter()
-        .map(partials::admin_payment_row_from)
+        .map(|p| partials::admin_payment_row_from(p, current_user.locale))
         .collect();
-    partials::admin_payment_list(rows)
+    let summary = (page == 1 && totals.payment_count > 0).then(|| partials::AdminPaymentSummary {
+        payment_count: totals.payment_count,
+        completed_total: current_user.locale.currency(totals.completed_cents, "USD"),
+    });
+    let next_page = (page * PAYMENTS_PAGE_SIZE < totals.payment_count).then_some(page + 1);
+    partials::admin_payment_list(rows, id.to_string(), summary, next_page)
 }
@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    response::{Html, IntoResponse, Response},
+    Extension,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::middleware::auth::CurrentUser, integrations::ResyncOutcome,
+    service::member_service::MemberService, web::escape_html,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ResyncIntegrationsForm {
+    /// Integration name, or empty for all of them.
+    #[serde(default)]
+    pub integration: String,
+    #[allow(dead_code)]
+    pub csrf_token: Option<String>,
+}
+
+/// Admin re-pushes a member's current status to the integrations
+/// (all, or the one picked) without changing the member, e.g. after
+/// Discord was down or a role was removed by hand. Renders what each
+/// integration did.
+pub async fn admin_resync_member_integrations(
+    State(member_service): State<Arc<MemberService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(member_id): Path<String>,
+    axum::Form(form): axum::Form<ResyncIntegrationsForm>,
+) -> impl IntoResponse {
+    let Ok(id) = uuid::Uuid::parse_str(&member_id) else {
+        return resync_result(false, "Invalid member ID", &[]);
+    };
+    let only = Some(form.integration.trim()).filter(|s| !s.is_empty());
+
+    match member_service
+        .resync_integrations(current_user.member.id, id, only)
+        .await
+    {
+        Ok(results) if results.is_empty() => {
+            resync_result(false, "No enabled integration handles member status.", &[])
+        }
+        Ok(results) => {
+            let lines: Vec<(bool, String)> = results
+                .iter()
+                .map(|r| match &r.outcome {
+                    ResyncOutcome::Sent => (true, format!("{}: synced", r.integration)),
+                    ResyncOutcome::Failed(e) => {
+                        (false, format!("{}: failed — {}", r.integration, e))
+                    }
+                    ResyncOutcome::NotSubscribed => (
+                        false,
+                        format!("{}: not subscribed to member status events", r.integration),
+                    ),
+                })
+                .collect();
+            let ok = lines.iter().all(|(ok, _)| *ok);
+            resync_result(ok, "Resync finished.", &lines)
+        }
+        Err(e) => resync_result(false, &format!("Resync failed: {}", e), &[]),
+    }
+}
+
+fn resync_result(ok: bool, headline: &str, lines: &[(bool, String)]) -> Response {
+    let (bg, fg) = if ok {
+        ("bg-green-50", "text-green-900")
+    } else {
+        ("bg-red-50", "text-red-900")
+    };
+    let items: String = lines
+        .iter()
+        .map(|(ok, line)| {
+            format!(
+                r#"<li class="{}">{}</li>"#,
+                if *ok {
+                    "text-green-800"
+                } else {
+                    "text-red-800"
+                },
+                escape_html(line),
+            )
+        })
+        .collect();
+    Html(format!(
+        r#"<div id="resync-result" class="mt-2 p-2 {bg} {fg} rounded text-sm">{headline}<ul class="mt-1 list-disc list-inside">{items}</ul></div>"#,
+        bg = bg,
+        fg = fg,
+        headline = escape_html(headline),
+        items = items,
+    ))
+    .into_response()
+}
@@ -6,13 +6,18 @@ use axum::{
     response::IntoResponse,
     Extension,
 };
+use axum_extra::extract::CookieJar;
 
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
-    repository::MemberRepository,
-    service::membership_type_service::MembershipTypeService,
-    web::templates::{filters, BaseContext, HtmlTemplate},
+    domain::DuesStatus,
+    repository::{MemberRepository, PaymentRepository},
+    service::{membership_type_service::MembershipTypeService, settings_service::SettingsService},
+    web::{
+        portal::{admin::pagination, dues_status_for},
+        templates::{filters, BaseContext, HtmlTemplate},
+    },
 };
 
 use super::{AdminMembersQuery, MembershipTypeOption};
@@ -47,6 +52,7 @@ pub struct AdminMembersTableTemplate {
     pub type_filter: String,
     pub sort_field: String,
     pub sort_order: String,
+    pub locale: crate::domain::Locale,
 }
 
 #[derive(Clone)]
@@ -60,15 +66,20 @@ pub struct AdminMemberInfo {
     pub membership_type: String,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub dues_paid_until: Option<chrono::DateTime<chrono::Utc>>,
+    pub dues_status: DuesStatus,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn admin_members_page(
     State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(settings_service): State<Arc<SettingsService>>,
     State(csrf_service): State<Arc<CsrfService>>,
     headers: axum::http::HeaderMap,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
+    jar: CookieJar,
     Query(query): Query<AdminMembersQuery>,
 ) -> impl IntoResponse {
     let is_htmx = headers.get("HX-Request").is_some();
@@ -76,12 +87,9 @@ pub async fn admin_members_page(
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
 
     let page = query.page.unwrap_or(1).max(1);
-    let per_page: i64 = 20;
+    let (per_page, jar) = pagination::per_page(query.per_page, jar);
     let offset = (page - 1) * per_page;
 
-    let sort_field = query.sort.clone().unwrap_or_else(|| "name".to_string());
-    let sort_order = query.order.clone().unwrap_or_else(|| "asc".to_string());
-
     // Load all membership types up front: drives the filter dropdown,
     // resolves the URL `?type=<slug>` filter to its FK, and provides
     // the per-row display name.
@@ -92,10 +100,6 @@ pub async fn admin_members_page(
             tracing::error!("admin members: list membership types failed: {}", e);
             Vec::new()
         });
-    let type_filter_id = query
-        .member_type
-        .as_deref()
-        .and_then(|slug| all_types.iter().find(|t| t.slug == slug).map(|t| t.id));
     let type_name_by_id: std::collections::HashMap<uuid::Uuid, String> =
         all_types.iter().map(|t| (t.id, t.name.clone())).collect();
     let type_options: Vec<MembershipTypeOption> = all_types
@@ -108,79 +112,64 @@ pub async fn admin_members_page(
         })
         .collect();
 
-    // Parse the wire-shape query into the typed MemberQuery. Unknown
-    // sort/order values fall back to the defaults rather than failing
-    // the request (the dropdown only offers known values; keeping the
-    // page renderable on a stale URL is friendlier). Unknown
-    // status/type filter values resolve to None — matches the prior
-    // "no filter" behavior on a malformed value.
-    use crate::repository::{MemberQuery, MemberSortField, SortOrder};
-    let typed_query = MemberQuery {
-        search: query.q.clone().filter(|s| !s.is_empty()),
-        status: query
-            .status
-            .as_deref()
-            .and_then(crate::domain::MemberStatus::from_str),
-        membership_type_id: type_filter_id,
-        sort: match sort_field.as_str() {
-            "status" => MemberSortField::Status,
-            "type" => MemberSortField::MembershipType,
-            "joined" => MemberSortField::Joined,
-            "dues" => MemberSortField::DuesPaidUntil,
-            _ => MemberSortField::Name,
-        },
-        order: if sort_order == "desc" {
-            SortOrder::Desc
-        } else {
-            SortOrder::Asc
-        },
-        limit: per_page,
-        offset,
-    };
+    let filter = query.filter(&all_types);
+    let sort_field = filter.sort.as_str().to_string();
+    let sort_order = filter.order.as_str().to_string();
 
-    let (members, total_members) = member_repo.search(typed_query).await.unwrap_or_else(|e| {
-        tracing::error!("admin members search failed: {}", e);
-        (Vec::new(), 0)
+    let members = member_repo
+        .search(&filter, per_page, offset)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("admin members search failed: {}", e);
+            Vec::new()
+        });
+    let total_members = member_repo.count(&filter).await.unwrap_or_else(|e| {
+        tracing::error!("admin members count failed: {}", e);
+        0
     });
     let total_pages = (total_members + per_page - 1) / per_page;
 
-    let paginated_members: Vec<AdminMemberInfo> = members
-        .into_iter()
-        .map(|m| {
-            let initials: String = m
-                .full_name
-                .split_whitespace()
-                .filter_map(|word| word.chars().next())
-                .take(2)
-                .collect::<String>()
-                .to_uppercase();
-
-            AdminMemberInfo {
-                id: m.id,
-                email: m.email,
-                username: m.username,
-                full_name: m.full_name,
-                initials: if initials.is_empty() {
-                    "?".to_string()
-                } else {
-                    initials
-                },
-                status: m.status,
-                membership_type: type_name_by_id
-                    .get(&m.membership_type_id)
-                    .cloned()
-                    .unwrap_or_else(|| "(unknown)".to_string()),
-                joined_at: m.joined_at,
-                dues_paid_until: m.dues_paid_until,
-            }
-        })
-        .collect();
+    let now = chrono::Utc::now();
+    let grace_period_days = settings_service.grace_period_days().await;
+    let mut paginated_members: Vec<AdminMemberInfo> = Vec::with_capacity(members.len());
+    for m in members {
+        let dues_status =
+            dues_status_for(&m, payment_repo.as_ref(), now, grace_period_days).await;
+
+        let initials: String = m
+            .full_name
+            .split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .take(2)
+            .collect::<String>()
+            .to_uppercase();
+
+        paginated_members.push(AdminMemberInfo {
+            id: m.id,
+            email: m.email,
+            username: m.username,
+            full_name: m.full_name,
+            initials: if initials.is_empty() {
+                "?".to_string()
+            } else {
+                initials
+            },
+            status: m.status,
+            membership_type: type_name_by_id
+                .get(&m.membership_type_id)
+                .cloned()
+                .unwrap_or_else(|| "(unknown)".to_string()),
+            joined_at: m.joined_at,
+            dues_paid_until: m.dues_paid_until,
+            dues_status,
+        });
+    }
 
     let search_query_val = query.q.unwrap_or_default();
     let status_filter_val = query.status.unwrap_or_default();
     let type_filter_val = query.member_type.unwrap_or_default();
 
-    if is_htmx {
+    let body = if is_htmx {
         HtmlTemplate(AdminMembersTableTemplate {
             members: paginated_members,
             total_members,
@@ -192,6 +181,7 @@ pub async fn admin_members_page(
             type_filter: type_filter_val,
             sort_field,
             sort_order,
+            locale: current_user.locale,
         })
         .into_response()
     } else {
@@ -210,5 +200,6 @@ pub async fn admin_members_page(
             sort_order,
         })
         .into_response()
-    }
+    };
+    (jar, body)
 }
@@ -1,10 +1,13 @@
 use serde::Deserialize;
 
+use crate::{domain::MembershipTypeConfig, repository::MemberFilter};
+
 pub mod bulk;
 pub mod create;
 pub mod detail;
 pub mod discord;
 pub mod dues;
+pub mod integrations;
 pub mod list;
 pub mod payments;
 pub mod status;
@@ -26,6 +29,37 @@ pub struct AdminMembersQuery {
     #[serde(rename = "type")]
     pub member_type: Option<String>,
     pub page: Option<i64>,
+    pub per_page: Option<i64>,
     pub sort: Option<String>,
     pub order: Option<String>,
 }
+
+impl AdminMembersQuery {
+    /// The roster filter this query string asks for. The members page
+    /// and the CSV export both go through here so they agree on what a
+    /// URL means. `?type=` is a slug, resolved against `types`.
+    /// Unknown sort/order values fall back to the defaults rather than
+    /// failing the request (the dropdown only offers known values;
+    /// keeping the page renderable on a stale URL is friendlier).
+    /// Unknown status/type values mean no filter.
+    pub fn filter(&self, types: &[MembershipTypeConfig]) -> MemberFilter {
+        MemberFilter {
+            search: self.q.clone().filter(|s| !s.is_empty()),
+            status: self.status.as_deref().and_then(|s| s.parse().ok()),
+            membership_type_id: self
+                .member_type
+                .as_deref()
+                .and_then(|slug| types.iter().find(|t| t.slug == slug).map(|t| t.id)),
+            sort: self
+                .sort
+                .as_deref()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_default(),
+            order: self
+                .order
+                .as_deref()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or_default(),
+        }
+    }
+}
@@ -111,8 +111,10 @@ pub async fn admin_record_payment_submit(
     };
 
     // Parse dollars → cents. Accept "100" or "100.00" or "100.5".
-    let amount_cents = match parse_dollars_to_cents(&form.amount) {
-        Some(c) if c > 0 || form.payment_type == "membership" => c,
+    // Negative amounts are refunds, which go through the refund flow.
+    let amount = crate::domain::Money::parse_dollars(&form.amount, crate::domain::DEFAULT_CURRENCY);
+    let amount_cents = match amount.map(|m| m.cents()) {
+        Ok(c) if c > 0 || (c == 0 && form.payment_type == "membership") => c,
         _ => return err("Amount must be a positive dollar amount.".to_string()).await,
     };
     if amount_cents > crate::domain::MAX_PAYMENT_CENTS {
@@ -189,34 +191,6 @@ pub async fn admin_record_payment_submit(
     axum::response::Redirect::to(&format!("/portal/admin/members/{}", id)).into_response()
 }
 
-/// "100", "100.00", "100.5" → 10000, 10000, 10050. Returns None on
-/// junk input or negative values. Refuses more than 2 decimal places
-/// to prevent silent rounding.
-fn parse_dollars_to_cents(s: &str) -> Option<i64> {
-    let s = s.trim();
-    if s.is_empty() {
-        return None;
-    }
-    let (whole, frac) = match s.split_once('.') {
-        Some((w, f)) => (w, f),
-        None => (s, ""),
-    };
-    if frac.len() > 2 || !frac.chars().all(|c| c.is_ascii_digit()) {
-        return None;
-    }
-    let whole: i64 = whole.parse().ok()?;
-    if whole < 0 {
-        return None;
-    }
-    let frac_padded = format!("{:0<2}", frac);
-    let frac: i64 = if frac_padded.is_empty() {
-        0
-    } else {
-        frac_padded.parse().ok()?
-    };
-    whole.checked_mul(100)?.checked_add(frac)
-}
-
 /// Render the record-payment page for `member_id`, optionally with a
 /// flash error message. Shared between the GET page and POST validation
 /// failures (the latter re-renders the same form with the error shown).
@@ -248,9 +222,9 @@ async fn render_record_payment(
         .unwrap_or_default()
         .into_iter()
         .map(|mt| RecordPaymentMembershipType {
+            fee_display: mt.fee().to_decimal_string(),
             slug: mt.slug,
             name: mt.name,
-            fee_display: format!("{:.2}", mt.fee_cents as f64 / 100.0),
             billing_period: mt.billing_period,
         })
         .collect();
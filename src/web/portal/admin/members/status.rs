@@ -5,6 +5,7 @@ use axum::{
     response::IntoResponse,
     Extension,
 };
+use serde::Deserialize;
 
 use crate::{
     api::middleware::auth::CurrentUser, service::member_service::MemberService,
@@ -24,7 +25,7 @@ pub async fn admin_activate_member(
     match member_service.activate(current_user.member.id, id).await {
         Ok(member) => {
             let mt_name = member_service.membership_type_name(&member).await;
-            partials::member_row_flash(&member, mt_name, "active")
+            partials::member_row_flash(&member, mt_name, "active", current_user.locale)
         }
         Err(e) => partials::member_row_error(&format!("Error: {}", e)),
     }
@@ -43,7 +44,7 @@ pub async fn admin_suspend_member(
     match member_service.suspend(current_user.member.id, id).await {
         Ok(member) => {
             let mt_name = member_service.membership_type_name(&member).await;
-            partials::member_row_flash(&member, mt_name, "suspended")
+            partials::member_row_flash(&member, mt_name, "suspended", current_user.locale)
         }
         Err(e) => partials::member_row_error(&format!("Error: {}", e)),
     }
@@ -64,3 +65,53 @@ pub async fn admin_expire_now(
         Err(e) => partials::admin_alert("error", &format!("Error: {}", e), false),
     }
 }
+
+#[derive(Debug, Deserialize)]
+pub struct GrantHonoraryForm {
+    pub reason: String,
+    /// `YYYY-MM-DD` from `<input type="date">`; empty for a permanent grant.
+    #[serde(default)]
+    pub until: String,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+pub async fn admin_grant_honorary(
+    State(member_service): State<Arc<MemberService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(member_id): Path<String>,
+    axum::Form(form): axum::Form<GrantHonoraryForm>,
+) -> impl IntoResponse {
+    use chrono::NaiveDate;
+
+    let id = match uuid::Uuid::parse_str(&member_id) {
+        Ok(id) => id,
+        Err(_) => return partials::admin_alert("error", "Invalid member ID", false),
+    };
+
+    // The grant runs through the end of the chosen day.
+    let until = match form.until.trim() {
+        "" => None,
+        raw => match NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+            Ok(d) => d.and_hms_opt(23, 59, 59).map(|dt| dt.and_utc()),
+            Err(_) => return partials::admin_alert("error", "Invalid date format", false),
+        },
+    };
+
+    match member_service
+        .grant_honorary(current_user.member.id, id, &form.reason, until)
+        .await
+    {
+        Ok(member) => {
+            let msg = match member.honorary_until {
+                Some(t) => format!(
+                    "Honorary status granted until {}.",
+                    current_user.locale.long_date(&t)
+                ),
+                None => "Honorary status granted.".to_string(),
+            };
+            partials::admin_alert("success", &msg, true)
+        }
+        Err(e) => partials::admin_alert("error", &format!("Error: {}", e), false),
+    }
+}
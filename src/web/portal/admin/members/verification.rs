@@ -7,10 +7,13 @@ use axum::{
 };
 
 use crate::{
-    api::middleware::auth::CurrentUser, repository::MemberRepository,
+    api::middleware::auth::CurrentUser, error::AppError, repository::MemberRepository,
     service::member_service::MemberService,
 };
 
+const VERIFY_RESULT_ID: &str = "verify-resend-result";
+const WELCOME_RESULT_ID: &str = "welcome-resend-result";
+
 /// Admin-triggered: regenerate a verification token for an unverified
 /// member and email them the fresh link. Invalidates any previously
 /// outstanding tokens so the old email (if the member still has it)
@@ -23,7 +26,9 @@ pub async fn admin_resend_verification(
 ) -> axum::response::Response {
     let id = match uuid::Uuid::parse_str(&member_id) {
         Ok(id) => id,
-        Err(_) => return resend_result(false, "Invalid member ID").into_response(),
+        Err(_) => {
+            return resend_result(VERIFY_RESULT_ID, false, "Invalid member ID").into_response()
+        }
     };
 
     // The service refetches the member to render the success message;
@@ -32,22 +37,74 @@ pub async fn admin_resend_verification(
     // success of the email send.
     let email = match member_repo.find_by_id(id).await {
         Ok(Some(m)) => m.email,
-        Ok(None) => return resend_result(false, "Member not found").into_response(),
-        Err(e) => return resend_result(false, &format!("DB error: {}", e)).into_response(),
+        Ok(None) => {
+            return resend_result(VERIFY_RESULT_ID, false, "Member not found").into_response()
+        }
+        Err(e) => {
+            return resend_result(VERIFY_RESULT_ID, false, &format!("DB error: {}", e))
+                .into_response()
+        }
     };
 
     match member_service
         .resend_verification(current_user.member.id, id)
         .await
     {
-        Ok(()) => {
-            resend_result(true, &format!("Verification email resent to {}.", email)).into_response()
+        Ok(()) => resend_result(
+            VERIFY_RESULT_ID,
+            true,
+            &format!("Verification email resent to {}.", email),
+        )
+        .into_response(),
+        Err(e) => {
+            resend_result(VERIFY_RESULT_ID, false, &format!("Send failed: {}", e)).into_response()
         }
-        Err(e) => resend_result(false, &format!("Send failed: {}", e)).into_response(),
     }
 }
 
-fn resend_result(ok: bool, detail: &str) -> axum::response::Html<String> {
+/// Admin-triggered: send the welcome email again, with fresh
+/// verify/set-password links, for a member who never got going.
+/// Throttled per member by the service.
+pub async fn admin_resend_welcome(
+    State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(member_service): State<Arc<MemberService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(member_id): Path<String>,
+) -> axum::response::Response {
+    let id = match uuid::Uuid::parse_str(&member_id) {
+        Ok(id) => id,
+        Err(_) => {
+            return resend_result(WELCOME_RESULT_ID, false, "Invalid member ID").into_response()
+        }
+    };
+
+    let email = match member_repo.find_by_id(id).await {
+        Ok(Some(m)) => m.email,
+        Ok(None) => {
+            return resend_result(WELCOME_RESULT_ID, false, "Member not found").into_response()
+        }
+        Err(e) => {
+            return resend_result(WELCOME_RESULT_ID, false, &format!("DB error: {}", e))
+                .into_response()
+        }
+    };
+
+    let (ok, detail) = match member_service
+        .resend_welcome(current_user.member.id, id)
+        .await
+    {
+        Ok(()) => (true, format!("Welcome email resent to {}.", email)),
+        Err(AppError::TooManyRequests) => (
+            false,
+            "Welcome email already resent several times today. Try again tomorrow.".to_string(),
+        ),
+        Err(AppError::BadRequest(msg)) => (false, msg),
+        Err(e) => (false, format!("Send failed: {}", e)),
+    };
+    resend_result(WELCOME_RESULT_ID, ok, &detail).into_response()
+}
+
+fn resend_result(target_id: &str, ok: bool, detail: &str) -> axum::response::Html<String> {
     let escaped = crate::web::escape_html(detail);
     let (bg, fg) = if ok {
         ("bg-green-50", "text-green-900")
@@ -55,7 +112,8 @@ fn resend_result(ok: bool, detail: &str) -> axum::response::Html<String> {
         ("bg-red-50", "text-red-900")
     };
     axum::response::Html(format!(
-        r#"<div id="verify-resend-result" class="mt-2 p-2 {bg} {fg} rounded text-sm">{detail}</div>"#,
+        r#"<div id="{id}" class="mt-2 p-2 {bg} {fg} rounded text-sm">{detail}</div>"#,
+        id = target_id,
         bg = bg,
         fg = fg,
         detail = escaped,
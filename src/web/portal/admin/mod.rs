@@ -1,11 +1,17 @@
 pub mod announcements;
 pub mod audit;
+pub mod backup;
 pub mod billing;
+pub mod contact;
 pub mod csv;
+pub mod data_check;
 pub mod discord;
 pub mod email;
 pub mod events;
+pub mod forms;
+pub mod integration_log;
 pub mod members;
+pub mod pagination;
 pub mod partials;
 pub mod payments;
 pub mod settings;
@@ -0,0 +1,36 @@
+//! Page size for the admin list pages (members, events,
+//! announcements).
+//!
+//! `?per_page=` picks the size, clamped to `MAX_PER_PAGE`. The choice
+//! is remembered in a browser-session cookie, so filter submissions,
+//! sort links and later visits keep it without every URL having to
+//! carry the parameter.
+
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+
+pub const DEFAULT_PER_PAGE: i64 = 20;
+pub const MAX_PER_PAGE: i64 = 100;
+
+const COOKIE_NAME: &str = "admin_per_page";
+
+/// The page size for this request and the jar to send back: an
+/// explicit `requested` value wins and is remembered, otherwise the
+/// remembered value, otherwise `DEFAULT_PER_PAGE`.
+pub fn per_page(requested: Option<i64>, jar: CookieJar) -> (i64, CookieJar) {
+    if let Some(n) = requested {
+        let n = n.clamp(1, MAX_PER_PAGE);
+        // No max-age: the preference lasts for the browser session.
+        let cookie = Cookie::build((COOKIE_NAME, n.to_string()))
+            .path("/portal/admin")
+            .same_site(SameSite::Lax)
+            .http_only(true)
+            .build();
+        return (n, jar.add(cookie));
+    }
+    let remembered = jar
+        .get(COOKIE_NAME)
+        .and_then(|c| c.value().parse::<i64>().ok())
+        .map(|n| n.clamp(1, MAX_PER_PAGE))
+        .unwrap_or(DEFAULT_PER_PAGE);
+    (remembered, jar)
+}
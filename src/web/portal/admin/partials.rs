@@ -8,6 +8,8 @@
 use askama::Template;
 use axum::response::Html;
 
+use crate::domain::Locale;
+
 /// Result panel rendered after an admin HTMX action — the small
 /// green/red/yellow div that appears under a button. `kind` is one of
 /// `"success" | "error" | "warning"`. When `autoreload` is true, the
@@ -78,6 +80,7 @@ pub fn member_row_flash(
     member: &crate::domain::Member,
     membership_type_name: String,
     flash: &'static str,
+    locale: Locale,
 ) -> Html<String> {
     let initials: String = member
         .full_name
@@ -95,10 +98,10 @@ pub fn member_row_flash(
         username: member.username.clone(),
         status: member.status.as_str().to_string(),
         membership_type: membership_type_name,
-        joined_at: member.joined_at.format("%b %d, %Y").to_string(),
+        joined_at: locale.short_date(&member.joined_at),
         dues_paid_until: member
             .dues_paid_until
-            .map(|d| d.format("%b %d, %Y").to_string())
+            .map(|d| locale.short_date(&d))
             .unwrap_or_else(|| "—".to_string()),
     };
 
@@ -133,6 +136,8 @@ pub struct AdminPaymentRow {
     pub date: String,
     pub amount: String,
     pub status: &'static str,
+    /// Date the payment was refunded, for Refunded rows.
+    pub refunded_on: Option<String>,
     pub show_refund: bool,
     pub refund_confirm: String,
 }
@@ -141,12 +146,32 @@ pub struct AdminPaymentRow {
 #[template(path = "admin/_admin_payment_list.html")]
 pub struct AdminPaymentListTemplate {
     pub rows: Vec<AdminPaymentRow>,
+    pub member_id: String,
+    /// Totals over every payment, shown above the first page only.
+    pub summary: Option<AdminPaymentSummary>,
+    /// Page the "Load more" button fetches; `None` on the last page.
+    pub next_page: Option<i64>,
+}
+
+pub struct AdminPaymentSummary {
+    pub payment_count: i64,
+    pub completed_total: String,
 }
 
-/// Render the admin member-detail payments list. Returns the empty-
-/// state message when the member has no payments on file.
-pub fn admin_payment_list(rows: Vec<AdminPaymentRow>) -> Html<String> {
-    let tmpl = AdminPaymentListTemplate { rows };
+/// Render one page of the admin member-detail payments list. Returns
+/// the empty-state message when the member has no payments on file.
+pub fn admin_payment_list(
+    rows: Vec<AdminPaymentRow>,
+    member_id: String,
+    summary: Option<AdminPaymentSummary>,
+    next_page: Option<i64>,
+) -> Html<String> {
+    let tmpl = AdminPaymentListTemplate {
+        rows,
+        member_id,
+        summary,
+        next_page,
+    };
     Html(tmpl.render().unwrap_or_else(|e| {
         tracing::error!("admin_payment_list template render failed: {}", e);
         format!("<div class=\"p-6 text-center text-red-600\">Render error</div>")
@@ -157,28 +182,23 @@ pub fn admin_payment_list(rows: Vec<AdminPaymentRow>) -> Html<String> {
 /// Refund-button gating: only Completed Stripe / Manual rows. Waived
 /// rows are $0 — nothing to give back. Already-refunded rows
 /// obviously get no button.
-pub fn admin_payment_row_from(payment: &crate::domain::Payment) -> AdminPaymentRow {
+pub fn admin_payment_row_from(payment: &crate::domain::Payment, locale: Locale) -> AdminPaymentRow {
     use crate::domain::{PaymentMethod, PaymentStatus};
-    let status = match payment.status {
-        PaymentStatus::Completed => "Completed",
-        PaymentStatus::Pending => "Pending",
-        PaymentStatus::Failed => "Failed",
-        PaymentStatus::Refunded => "Refunded",
-    };
+    let status = payment.status.as_str();
 
     let show_refund = payment.status == PaymentStatus::Completed
         && payment.payment_method != PaymentMethod::Waived;
 
-    let amount_dollars = payment.amount_cents as f64 / 100.0;
+    let amount = payment.amount();
     let refund_confirm = if show_refund {
         match payment.payment_method {
             PaymentMethod::Stripe => format!(
-                "Issue a full Stripe refund of ${:.2}? This is irreversible.",
-                amount_dollars,
+                "Issue a full Stripe refund of ${}? This is irreversible.",
+                amount,
             ),
             _ => format!(
-                "Mark this ${:.2} payment as Refunded? (No external system will be touched — refund the cash/check yourself.)",
-                amount_dollars,
+                "Mark this ${} payment as Refunded? (No external system will be touched — refund the cash/check yourself.)",
+                amount,
             ),
         }
     } else {
@@ -194,9 +214,10 @@ pub fn admin_payment_row_from(payment: &crate::domain::Payment) -> AdminPaymentR
     AdminPaymentRow {
         id: payment.id.to_string(),
         description,
-        date: payment.created_at.format("%B %d, %Y").to_string(),
-        amount: format!("{:.2}", amount_dollars),
+        date: locale.long_date(&payment.created_at),
+        amount: amount.to_decimal_string(),
         status,
+        refunded_on: payment.refunded_at.as_ref().map(|at| locale.long_date(at)),
         show_refund,
         refund_confirm,
     }
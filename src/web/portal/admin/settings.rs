@@ -224,6 +224,16 @@ async fn fetch_settings_by_category(
             "Membership approval and duration settings",
         ),
         ("payment", "Payment", "Payment amounts and timing"),
+        (
+            "events",
+            "Events",
+            "Event reminders and the admin events list",
+        ),
+        (
+            "announcements",
+            "Announcements",
+            "Default order of the admin announcements list",
+        ),
         (
             "features",
             "Features",
@@ -235,7 +245,23 @@ async fn fetch_settings_by_category(
             "Third-party service connections",
         ),
         ("audit", "Audit", "Audit log retention"),
+        ("backup", "Backups", "Scheduled database backups"),
         ("auth", "Authentication", "Login policy and access controls"),
+        (
+            "notifications",
+            "Notifications",
+            "Which events notify admins directly, and when",
+        ),
+        (
+            "maintenance",
+            "Maintenance",
+            "Take the site offline for upgrades",
+        ),
+        (
+            "celebrations",
+            "Celebrations",
+            "Birthday and membership-anniversary shout-outs",
+        ),
     ];
 
     let mut result = Vec::new();
@@ -3,9 +3,9 @@
 //! Event and announcement types share one set of handlers parameterized by
 //! `BasicTypeKind`; the kind comes from the URL path (`/types/:kind/...`).
 //! Membership types keep their own handler set because membership has extra
-//! fields (fee, billing period) and extra validation.
+//! fields (fee, billing period, member cap) and extra validation.
 
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use askama::Template;
 use axum::{
@@ -13,7 +13,9 @@ use axum::{
     response::{IntoResponse, Response},
     Extension,
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use uuid::Uuid;
 
 use crate::{
     api::{
@@ -22,16 +24,22 @@ use crate::{
     },
     auth::CsrfService,
     domain::{
-        BasicTypeKind, CreateBasicTypeRequest, CreateMembershipTypeRequest, UpdateBasicTypeRequest,
-        UpdateMembershipTypeRequest,
+        contrasting_text_color, validate_hex_color, BasicTypeKind, BillingPeriod,
+        CreateBasicTypeRequest, CreateMembershipTypeRequest, Locale, Money, ParseMoneyError,
+        UpdateBasicTypeRequest, UpdateMembershipTypeRequest, DEFAULT_CURRENCY, MAX_PAYMENT_CENTS,
     },
+    error::AppError,
+    repository::{AnnouncementRepository, MemberRepository},
     service::{
         audit_service::AuditService, basic_type_service::BasicTypeService,
-        membership_type_service::MembershipTypeService,
+        configurable_types::SeedReport, membership_type_service::MembershipTypeService,
     },
     util::string::capitalize_first,
     web::{
-        portal::admin::partials,
+        portal::admin::{
+            forms::{form_invalid, form_saved, FormErrors},
+            partials,
+        },
         templates::{BaseContext, HtmlTemplate},
     },
 };
@@ -47,10 +55,18 @@ pub struct TypeInfo {
     pub slug: String,
     pub description: Option<String>,
     pub color: Option<String>,
+    /// Badge text color for `color`; see `contrasting_text_color`.
+    pub text_color: Option<&'static str>,
     pub icon: Option<String>,
     pub sort_order: i32,
     pub is_active: bool,
     pub usage_count: i64,
+    /// Announcement types only: whether publishing notifies members
+    /// when the announcement doesn't say.
+    pub notify_members: bool,
+    /// When and by whom the type was last saved; see `last_edited`.
+    /// `None` on a re-rendered form.
+    pub last_edited: Option<String>,
 }
 
 #[derive(Clone)]
@@ -60,13 +76,42 @@ pub struct MembershipTypeInfo {
     pub slug: String,
     pub description: Option<String>,
     pub color: Option<String>,
+    /// Badge text color for `color`; see `contrasting_text_color`.
+    pub text_color: Option<&'static str>,
     pub icon: Option<String>,
     pub sort_order: i32,
     pub is_active: bool,
-    pub fee_cents: i32,
+    pub fee_cents: i64,
     pub fee_dollars: String,
     pub billing_period: String,
+    /// The member cap as the form shows it; empty when uncapped.
+    pub max_members: String,
     pub usage_count: i64,
+    /// Set for a capped type on the overview page.
+    pub capacity: Option<Capacity>,
+    /// When and by whom the type was last saved; see `last_edited`.
+    /// `None` on a re-rendered form.
+    pub last_edited: Option<String>,
+}
+
+/// How full a capped membership type is, for the "X / Y used" column.
+#[derive(Clone, Copy)]
+pub struct Capacity {
+    pub used: i64,
+    pub max: i64,
+}
+
+impl Capacity {
+    /// Red once full, amber from 80%, grey below.
+    pub fn text_class(&self) -> &'static str {
+        if self.used >= self.max {
+            "text-red-600 font-semibold"
+        } else if self.used * 5 >= self.max * 4 {
+            "text-amber-600 font-semibold"
+        } else {
+            "text-gray-500"
+        }
+    }
 }
 
 // =============================================================================
@@ -86,14 +131,18 @@ pub async fn admin_types_page(
     State(event_type_service): State<EventBasicTypeService>,
     State(announcement_type_service): State<AnnouncementBasicTypeService>,
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
 ) -> impl IntoResponse {
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
-    let event_types = fetch_basic_types(&event_type_service.0, true).await;
-    let announcement_types = fetch_basic_types(&announcement_type_service.0, true).await;
-    let membership_types = fetch_membership_types(&membership_type_service, true).await;
+    let mut editors = EditorNames::new(member_repo.as_ref());
+    let event_types = fetch_basic_types(&event_type_service.0, &mut editors, true).await;
+    let announcement_types =
+        fetch_basic_types(&announcement_type_service.0, &mut editors, true).await;
+    let membership_types =
+        fetch_membership_types(&membership_type_service, &mut editors, true).await;
 
     HtmlTemplate(AdminTypesTemplate {
         base,
@@ -104,28 +153,122 @@ pub async fn admin_types_page(
     .into_response()
 }
 
+// =============================================================================
+// Seed Defaults (all three categories)
+// =============================================================================
+
+#[derive(Template)]
+#[template(path = "admin/types/seed_result.html")]
+pub struct SeedResultTemplate {
+    pub event_types: SeedReport,
+    pub announcement_types: SeedReport,
+    pub membership_types: SeedReport,
+}
+
+impl SeedResultTemplate {
+    pub fn total_created(&self) -> usize {
+        self.event_types.created + self.announcement_types.created + self.membership_types.created
+    }
+}
+
+/// POST /portal/admin/types/seed-all — restore any missing default
+/// event, announcement, and membership types in one go. Each seeder
+/// skips slugs that already exist, so this is safe to click again; the
+/// fragment reports created/skipped per category.
+pub async fn admin_seed_all_types(
+    State(event_type_service): State<EventBasicTypeService>,
+    State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Response {
+    let seeded = async {
+        Ok::<_, crate::error::AppError>(SeedResultTemplate {
+            event_types: event_type_service.0.seed_defaults().await?,
+            announcement_types: announcement_type_service.0.seed_defaults().await?,
+            membership_types: membership_type_service.seed_defaults().await?,
+        })
+    }
+    .await;
+
+    match seeded {
+        Ok(result) => {
+            if result.total_created() > 0 {
+                let summary = format!(
+                    "event types +{}, announcement types +{}, membership types +{}",
+                    result.event_types.created,
+                    result.announcement_types.created,
+                    result.membership_types.created,
+                );
+                audit_service
+                    .log(
+                        Some(current_user.member.id),
+                        "seed_default_types",
+                        "types",
+                        "defaults",
+                        None,
+                        Some(&summary),
+                        None,
+                    )
+                    .await;
+            }
+            HtmlTemplate(result).into_response()
+        }
+        Err(e) => partials::admin_alert(
+            "error",
+            &format!("Error seeding default types: {}", e),
+            false,
+        )
+        .into_response(),
+    }
+}
+
 // =============================================================================
 // Basic Types (Event + Announcement) Management
 // =============================================================================
 //
-// Two Askama template structs exist because the two form templates use
-// different field names (`event_type` vs `announcement_type`). The handler
-// code is shared and dispatches on `BasicTypeKind` at the very edge.
+// Two Askama template structs exist per kind because the two form
+// templates use different field names (`event_type` vs
+// `announcement_type`); each kind also has a `*Partial` that renders just
+// the `<form>` for a failed submit. The handler code is shared and
+// dispatches on `BasicTypeKind` at the very edge.
 
 #[derive(Template)]
 #[template(path = "admin/types/event_type_form.html")]
 pub struct EventTypeFormTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
     pub event_type: Option<TypeInfo>,
     pub is_edit: bool,
+    pub errors: FormErrors,
+}
+
+#[derive(Template)]
+#[template(path = "admin/types/_event_type_form.html")]
+pub struct EventTypeFormPartial {
+    pub csrf_token: String,
+    pub event_type: Option<TypeInfo>,
+    pub is_edit: bool,
+    pub errors: FormErrors,
 }
 
 #[derive(Template)]
 #[template(path = "admin/types/announcement_type_form.html")]
 pub struct AnnouncementTypeFormTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
     pub announcement_type: Option<TypeInfo>,
     pub is_edit: bool,
+    pub errors: FormErrors,
+}
+
+#[derive(Template)]
+#[template(path = "admin/types/_announcement_type_form.html")]
+pub struct AnnouncementTypeFormPartial {
+    pub csrf_token: String,
+    pub announcement_type: Option<TypeInfo>,
+    pub is_edit: bool,
+    pub errors: FormErrors,
 }
 
 fn parse_kind(kind_str: &str) -> Option<BasicTypeKind> {
@@ -146,22 +289,52 @@ fn render_basic_form(
     type_info: Option<TypeInfo>,
     is_edit: bool,
 ) -> Response {
+    let csrf_token = base.csrf_token.clone();
     match kind {
         BasicTypeKind::Event => HtmlTemplate(EventTypeFormTemplate {
             base,
+            csrf_token,
             event_type: type_info,
             is_edit,
+            errors: FormErrors::new(),
         })
         .into_response(),
         BasicTypeKind::Announcement => HtmlTemplate(AnnouncementTypeFormTemplate {
             base,
+            csrf_token,
             announcement_type: type_info,
             is_edit,
+            errors: FormErrors::new(),
         })
         .into_response(),
     }
 }
 
+/// Re-render just the form after a failed submit, with what the admin
+/// typed and why it was rejected.
+fn render_basic_form_invalid(
+    kind: BasicTypeKind,
+    csrf_token: String,
+    type_info: TypeInfo,
+    is_edit: bool,
+    errors: FormErrors,
+) -> Response {
+    match kind {
+        BasicTypeKind::Event => form_invalid(EventTypeFormPartial {
+            csrf_token,
+            event_type: Some(type_info),
+            is_edit,
+            errors,
+        }),
+        BasicTypeKind::Announcement => form_invalid(AnnouncementTypeFormPartial {
+            csrf_token,
+            announcement_type: Some(type_info),
+            is_edit,
+            errors,
+        }),
+    }
+}
+
 fn service_for<'a>(
     event_type_service: &'a Arc<BasicTypeService>,
     announcement_type_service: &'a Arc<BasicTypeService>,
@@ -197,6 +370,8 @@ pub async fn admin_new_basic_type_page(
 pub async fn admin_edit_basic_type_page(
     State(event_type_service): State<EventBasicTypeService>,
     State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
@@ -228,25 +403,39 @@ pub async fn admin_edit_basic_type_page(
         }
     };
 
+    let notify_members = match kind {
+        BasicTypeKind::Announcement => announcement_repo
+            .type_notifies_members(id)
+            .await
+            .unwrap_or(true),
+        BasicTypeKind::Event => true,
+    };
+
+    let last_edited = EditorNames::new(member_repo.as_ref())
+        .last_edited(basic_type.updated_at, basic_type.updated_by)
+        .await;
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
     let type_info = TypeInfo {
         id: basic_type.id.to_string(),
         name: basic_type.name,
         slug: basic_type.slug,
         description: basic_type.description,
+        text_color: basic_type.color.as_deref().and_then(contrasting_text_color),
         color: basic_type.color,
         icon: basic_type.icon,
         sort_order: basic_type.sort_order,
         is_active: basic_type.is_active,
         usage_count: 0,
+        notify_members,
+        last_edited: Some(last_edited),
     };
 
     render_basic_form(kind, base, Some(type_info), true)
 }
 
 // Form body for both event-type and announcement-type create/update.
-// Note: csrf_token is validated via X-CSRF-Token header in middleware,
-// not from form body, so it's not included here.
+// Note: csrf_token is validated in middleware; it's read here only so a
+// re-rendered form can carry it forward.
 #[derive(Debug, Deserialize)]
 pub struct BasicTypeForm {
     pub name: String,
@@ -255,11 +444,64 @@ pub struct BasicTypeForm {
     pub color: Option<String>,
     pub icon: Option<String>,
     pub is_active: Option<String>,
+    /// Announcement types only. HTML checkbox: present when checked.
+    pub notify_members: Option<String>,
+    #[serde(default)]
+    pub csrf_token: String,
+}
+
+impl BasicTypeForm {
+    /// The submitted values in the shape the form template reads.
+    fn to_type_info(&self, id: String) -> TypeInfo {
+        TypeInfo {
+            id,
+            name: self.name.clone(),
+            slug: self.slug.clone().unwrap_or_default(),
+            description: self.description.clone(),
+            color: self.color.clone(),
+            text_color: self.color.as_deref().and_then(contrasting_text_color),
+            icon: self.icon.clone(),
+            sort_order: 0,
+            is_active: self.is_active.is_some(),
+            usage_count: 0,
+            notify_members: self.notify_members.is_some(),
+            last_edited: None,
+        }
+    }
+}
+
+/// Field checks shared by every type form. The services repeat the
+/// color check; doing it here pins the message on the field.
+fn validate_type_fields(name: &str, color: Option<&str>) -> FormErrors {
+    let mut errors = FormErrors::new();
+    if name.trim().is_empty() {
+        errors.add("name", "Name is required");
+    }
+    if let Some(c) = color.filter(|c| !c.is_empty()) {
+        if !validate_hex_color(c) {
+            errors.add("color", "Use a hex color like #2196F3");
+        }
+    }
+    errors
+}
+
+/// Place a service rejection: a slug clash belongs to the slug field,
+/// anything else goes above the form.
+fn service_errors(action: &str, e: AppError) -> FormErrors {
+    match e {
+        AppError::Conflict(msg) => {
+            let mut errors = FormErrors::new();
+            errors.add("slug", msg);
+            errors
+        }
+        e => FormErrors::form_level(format!("Error {}: {}", action, e)),
+    }
 }
 
 pub async fn admin_create_basic_type(
     State(event_type_service): State<EventBasicTypeService>,
     State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
     State(audit_service): State<Arc<AuditService>>,
     Extension(current_user): Extension<CurrentUser>,
     Path(kind_str): Path<String>,
@@ -268,17 +510,40 @@ pub async fn admin_create_basic_type(
     let Some(kind) = parse_kind(&kind_str) else {
         return invalid_kind_response();
     };
+    let invalid = |errors: FormErrors| {
+        render_basic_form_invalid(
+            kind,
+            form.csrf_token.clone(),
+            form.to_type_info(String::new()),
+            false,
+            errors,
+        )
+    };
+
+    let errors = validate_type_fields(&form.name, form.color.as_deref());
+    if !errors.is_empty() {
+        return invalid(errors);
+    }
+
     let request = CreateBasicTypeRequest {
-        name: form.name,
-        slug: form.slug.filter(|s| !s.is_empty()),
-        description: form.description.filter(|s| !s.is_empty()),
-        color: form.color.filter(|s| !s.is_empty()),
-        icon: form.icon.filter(|s| !s.is_empty()),
+        name: form.name.trim().to_string(),
+        slug: form.slug.clone().filter(|s| !s.is_empty()),
+        description: form.description.clone().filter(|s| !s.is_empty()),
+        color: form.color.clone().filter(|s| !s.is_empty()),
+        icon: form.icon.clone().filter(|s| !s.is_empty()),
     };
 
     let svc = service_for(&event_type_service.0, &announcement_type_service.0, kind);
-    match svc.create(request).await {
+    match svc.create(Some(current_user.member.id), request).await {
         Ok(created) => {
+            if let BasicTypeKind::Announcement = kind {
+                if let Err(e) = announcement_repo
+                    .set_type_notifies_members(created.id, form.notify_members.is_some())
+                    .await
+                {
+                    tracing::error!("set announcement type notify default failed: {}", e);
+                }
+            }
             let (action, entity_type) = audit_strings_for_kind(kind, "create");
             audit_service
                 .log(
@@ -291,20 +556,19 @@ pub async fn admin_create_basic_type(
                     None,
                 )
                 .await;
-            axum::response::Redirect::to("/portal/admin/types").into_response()
+            form_saved(
+                "/portal/admin/types",
+                &format!("{} created", capitalize_first(kind.display_name())),
+            )
         }
-        Err(e) => partials::admin_alert(
-            "error",
-            &format!("Error creating {}: {}", kind.display_name(), e),
-            false,
-        )
-        .into_response(),
+        Err(e) => invalid(service_errors(&format!("creating {}", kind.display_name()), e)),
     }
 }
 
 pub async fn admin_update_basic_type(
     State(event_type_service): State<EventBasicTypeService>,
     State(announcement_type_service): State<AnnouncementBasicTypeService>,
+    State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
     State(audit_service): State<Arc<AuditService>>,
     Extension(current_user): Extension<CurrentUser>,
     Path((kind_str, type_id)): Path<(String, String)>,
@@ -318,6 +582,21 @@ pub async fn admin_update_basic_type(
         Err(_) => return partials::admin_alert("error", "Invalid type ID", false).into_response(),
     };
 
+    let invalid = |errors: FormErrors| {
+        render_basic_form_invalid(
+            kind,
+            form.csrf_token.clone(),
+            form.to_type_info(id.to_string()),
+            true,
+            errors,
+        )
+    };
+
+    let errors = validate_type_fields(&form.name, form.color.as_deref());
+    if !errors.is_empty() {
+        return invalid(errors);
+    }
+
     let svc = service_for(&event_type_service.0, &announcement_type_service.0, kind);
 
     // Capture the existing name BEFORE mutating so the audit row's
@@ -343,16 +622,24 @@ pub async fn admin_update_basic_type(
     };
 
     let request = UpdateBasicTypeRequest {
-        name: Some(form.name),
-        description: form.description,
-        color: form.color,
-        icon: form.icon,
+        name: Some(form.name.trim().to_string()),
+        description: form.description.clone(),
+        color: form.color.clone(),
+        icon: form.icon.clone(),
         sort_order: None,
         is_active: Some(form.is_active.is_some()),
     };
 
-    match svc.update(id, request).await {
+    match svc.update(Some(current_user.member.id), id, request).await {
         Ok(updated) => {
+            if let BasicTypeKind::Announcement = kind {
+                if let Err(e) = announcement_repo
+                    .set_type_notifies_members(id, form.notify_members.is_some())
+                    .await
+                {
+                    tracing::error!("set announcement type notify default failed: {}", e);
+                }
+            }
             let (action, entity_type) = audit_strings_for_kind(kind, "update");
             audit_service
                 .log(
@@ -365,14 +652,12 @@ pub async fn admin_update_basic_type(
                     None,
                 )
                 .await;
-            axum::response::Redirect::to("/portal/admin/types").into_response()
+            form_saved(
+                "/portal/admin/types",
+                &format!("{} updated", capitalize_first(kind.display_name())),
+            )
         }
-        Err(e) => partials::admin_alert(
-            "error",
-            &format!("Error updating {}: {}", kind.display_name(), e),
-            false,
-        )
-        .into_response(),
+        Err(e) => invalid(service_errors(&format!("updating {}", kind.display_name()), e)),
     }
 }
 
@@ -448,8 +733,19 @@ pub async fn admin_delete_basic_type(
 #[template(path = "admin/types/membership_type_form.html")]
 pub struct MembershipTypeFormTemplate {
     pub base: BaseContext,
+    pub csrf_token: String,
+    pub membership_type: Option<MembershipTypeInfo>,
+    pub is_edit: bool,
+    pub errors: FormErrors,
+}
+
+#[derive(Template)]
+#[template(path = "admin/types/_membership_type_form.html")]
+pub struct MembershipTypeFormPartial {
+    pub csrf_token: String,
     pub membership_type: Option<MembershipTypeInfo>,
     pub is_edit: bool,
+    pub errors: FormErrors,
 }
 
 pub async fn admin_new_membership_type_page(
@@ -457,16 +753,20 @@ pub async fn admin_new_membership_type_page(
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
 ) -> impl IntoResponse {
+    let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
     HtmlTemplate(MembershipTypeFormTemplate {
-        base: BaseContext::for_member(&csrf_service, &current_user, &session_info).await,
+        csrf_token: base.csrf_token.clone(),
+        base,
         membership_type: None,
         is_edit: false,
+        errors: FormErrors::new(),
     })
     .into_response()
 }
 
 pub async fn admin_edit_membership_type_page(
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
@@ -489,28 +789,43 @@ pub async fn admin_edit_membership_type_page(
         }
     };
 
+    let last_edited = EditorNames::new(member_repo.as_ref())
+        .last_edited(membership_type.updated_at, membership_type.updated_by)
+        .await;
     let base = BaseContext::for_member(&csrf_service, &current_user, &session_info).await;
 
-    let fee_dollars = membership_type.fee_dollars();
+    let fee_dollars = membership_type.fee().to_decimal_string();
     let type_info = MembershipTypeInfo {
         id: membership_type.id.to_string(),
         name: membership_type.name,
         slug: membership_type.slug,
         description: membership_type.description,
+        text_color: membership_type
+            .color
+            .as_deref()
+            .and_then(contrasting_text_color),
         color: membership_type.color,
         icon: membership_type.icon,
         sort_order: membership_type.sort_order,
         is_active: membership_type.is_active,
         fee_cents: membership_type.fee_cents,
-        fee_dollars: format!("{:.2}", fee_dollars),
+        fee_dollars,
         billing_period: membership_type.billing_period,
+        max_members: membership_type
+            .max_members
+            .map(|max| max.to_string())
+            .unwrap_or_default(),
         usage_count: 0,
+        capacity: None,
+        last_edited: Some(last_edited),
     };
 
     HtmlTemplate(MembershipTypeFormTemplate {
+        csrf_token: base.csrf_token.clone(),
         base,
         membership_type: Some(type_info),
         is_edit: true,
+        errors: FormErrors::new(),
     })
     .into_response()
 }
@@ -524,7 +839,92 @@ pub struct MembershipTypeForm {
     pub icon: Option<String>,
     pub fee_dollars: String,
     pub billing_period: String,
+    #[serde(default)]
+    pub max_members: String,
     pub is_active: Option<String>,
+    #[serde(default)]
+    pub csrf_token: String,
+}
+
+impl MembershipTypeForm {
+    /// The submitted values in the shape the form template reads. The
+    /// fee and cap go back exactly as typed, even if they didn't parse.
+    fn to_type_info(&self, id: String) -> MembershipTypeInfo {
+        MembershipTypeInfo {
+            id,
+            name: self.name.clone(),
+            slug: self.slug.clone().unwrap_or_default(),
+            description: self.description.clone(),
+            color: self.color.clone(),
+            text_color: self.color.as_deref().and_then(contrasting_text_color),
+            icon: self.icon.clone(),
+            sort_order: 0,
+            is_active: self.is_active.is_some(),
+            fee_cents: 0,
+            fee_dollars: self.fee_dollars.clone(),
+            billing_period: self.billing_period.clone(),
+            max_members: self.max_members.clone(),
+            usage_count: 0,
+            capacity: None,
+            last_edited: None,
+        }
+    }
+
+    /// Validate every field, returning the fee in cents and the member
+    /// cap when all pass.
+    fn validate(&self) -> Result<(i64, Option<i64>), FormErrors> {
+        let mut errors = validate_type_fields(&self.name, self.color.as_deref());
+        let fee_cents = match Money::parse_dollars(&self.fee_dollars, DEFAULT_CURRENCY) {
+            Ok(fee) if (0..=MAX_PAYMENT_CENTS).contains(&fee.cents()) => Some(fee.cents()),
+            Ok(_) | Err(ParseMoneyError::Overflow) => {
+                errors.add(
+                    "fee_dollars",
+                    format!(
+                        "Fee must be between $0.00 and {}",
+                        Locale::EnUs.money(&Money::usd(MAX_PAYMENT_CENTS))
+                    ),
+                );
+                None
+            }
+            Err(ParseMoneyError::Invalid) => {
+                errors.add("fee_dollars", "Invalid fee amount");
+                None
+            }
+        };
+        if BillingPeriod::from_str(&self.billing_period).is_none() {
+            errors.add("billing_period", "Choose monthly, yearly, or lifetime");
+        }
+        let max_members = match self.max_members.trim() {
+            "" => None,
+            raw => match raw.parse::<i64>() {
+                Ok(max) if max >= 1 => Some(max),
+                _ => {
+                    errors.add(
+                        "max_members",
+                        "Member cap must be a whole number of at least 1",
+                    );
+                    None
+                }
+            },
+        };
+        match fee_cents {
+            Some(cents) if errors.is_empty() => Ok((cents, max_members)),
+            _ => Err(errors),
+        }
+    }
+}
+
+fn render_membership_form_invalid(
+    form: &MembershipTypeForm,
+    id: Option<uuid::Uuid>,
+    errors: FormErrors,
+) -> Response {
+    form_invalid(MembershipTypeFormPartial {
+        csrf_token: form.csrf_token.clone(),
+        membership_type: Some(form.to_type_info(id.map(|id| id.to_string()).unwrap_or_default())),
+        is_edit: id.is_some(),
+        errors,
+    })
 }
 
 pub async fn admin_create_membership_type(
@@ -533,34 +933,26 @@ pub async fn admin_create_membership_type(
     Extension(current_user): Extension<CurrentUser>,
     axum::Form(form): axum::Form<MembershipTypeForm>,
 ) -> impl IntoResponse {
-    let fee_cents = match form.fee_dollars.parse::<f64>() {
-        Ok(dollars) if dollars.is_finite() && dollars >= 0.0 && dollars <= 999_999.99 => {
-            (dollars * 100.0).round() as i32
-        }
-        Ok(_) => {
-            return partials::admin_alert(
-                "error",
-                "Fee must be between $0.00 and $999,999.99",
-                false,
-            )
-            .into_response()
-        }
-        Err(_) => {
-            return partials::admin_alert("error", "Invalid fee amount", false).into_response()
-        }
+    let (fee_cents, max_members) = match form.validate() {
+        Ok(valid) => valid,
+        Err(errors) => return render_membership_form_invalid(&form, None, errors),
     };
 
     let request = CreateMembershipTypeRequest {
-        name: form.name,
-        slug: form.slug.filter(|s| !s.is_empty()),
-        description: form.description.filter(|s| !s.is_empty()),
-        color: form.color.filter(|s| !s.is_empty()),
-        icon: form.icon.filter(|s| !s.is_empty()),
+        name: form.name.trim().to_string(),
+        slug: form.slug.clone().filter(|s| !s.is_empty()),
+        description: form.description.clone().filter(|s| !s.is_empty()),
+        color: form.color.clone().filter(|s| !s.is_empty()),
+        icon: form.icon.clone().filter(|s| !s.is_empty()),
         fee_cents,
-        billing_period: form.billing_period,
+        billing_period: form.billing_period.clone(),
+        max_members,
     };
 
-    match membership_type_service.create(request).await {
+    match membership_type_service
+        .create(Some(current_user.member.id), request)
+        .await
+    {
         Ok(created) => {
             audit_service
                 .log(
@@ -573,14 +965,13 @@ pub async fn admin_create_membership_type(
                     None,
                 )
                 .await;
-            axum::response::Redirect::to("/portal/admin/types").into_response()
+            form_saved("/portal/admin/types", "Membership type created")
         }
-        Err(e) => partials::admin_alert(
-            "error",
-            &format!("Error creating membership type: {}", e),
-            false,
-        )
-        .into_response(),
+        Err(e) => render_membership_form_invalid(
+            &form,
+            None,
+            service_errors("creating membership type", e),
+        ),
     }
 }
 
@@ -596,21 +987,9 @@ pub async fn admin_update_membership_type(
         Err(_) => return partials::admin_alert("error", "Invalid type ID", false).into_response(),
     };
 
-    let fee_cents = match form.fee_dollars.parse::<f64>() {
-        Ok(dollars) if dollars.is_finite() && dollars >= 0.0 && dollars <= 999_999.99 => {
-            (dollars * 100.0).round() as i32
-        }
-        Ok(_) => {
-            return partials::admin_alert(
-                "error",
-                "Fee must be between $0.00 and $999,999.99",
-                false,
-            )
-            .into_response()
-        }
-        Err(_) => {
-            return partials::admin_alert("error", "Invalid fee amount", false).into_response()
-        }
+    let (fee_cents, max_members) = match form.validate() {
+        Ok(valid) => valid,
+        Err(errors) => return render_membership_form_invalid(&form, Some(id), errors),
     };
 
     // Capture pre-mutation name for the audit row's old_value.
@@ -627,17 +1006,21 @@ pub async fn admin_update_membership_type(
     };
 
     let request = UpdateMembershipTypeRequest {
-        name: Some(form.name),
-        description: form.description,
-        color: form.color,
-        icon: form.icon,
+        name: Some(form.name.trim().to_string()),
+        description: form.description.clone(),
+        color: form.color.clone(),
+        icon: form.icon.clone(),
         sort_order: None,
         is_active: Some(form.is_active.is_some()),
         fee_cents: Some(fee_cents),
-        billing_period: Some(form.billing_period),
+        billing_period: Some(form.billing_period.clone()),
+        max_members: Some(max_members),
     };
 
-    match membership_type_service.update(id, request).await {
+    match membership_type_service
+        .update(Some(current_user.member.id), id, request)
+        .await
+    {
         Ok(updated) => {
             audit_service
                 .log(
@@ -650,14 +1033,13 @@ pub async fn admin_update_membership_type(
                     None,
                 )
                 .await;
-            axum::response::Redirect::to("/portal/admin/types").into_response()
+            form_saved("/portal/admin/types", "Membership type updated")
         }
-        Err(e) => partials::admin_alert(
-            "error",
-            &format!("Error updating membership type: {}", e),
-            false,
-        )
-        .into_response(),
+        Err(e) => render_membership_form_invalid(
+            &form,
+            Some(id),
+            service_errors("updating membership type", e),
+        ),
     }
 }
 
@@ -713,51 +1095,106 @@ pub async fn admin_delete_membership_type(
 // Helper Functions
 // =============================================================================
 
-async fn fetch_basic_types(service: &BasicTypeService, include_inactive: bool) -> Vec<TypeInfo> {
-    service
-        .list(include_inactive)
-        .await
-        .unwrap_or_default()
-        .into_iter()
-        .map(|t| TypeInfo {
+/// Resolves the `updated_by` of each type to the admin's name, looking
+/// each admin up once per page.
+struct EditorNames<'a> {
+    member_repo: &'a dyn MemberRepository,
+    names: HashMap<Uuid, Option<String>>,
+}
+
+impl<'a> EditorNames<'a> {
+    fn new(member_repo: &'a dyn MemberRepository) -> Self {
+        Self {
+            member_repo,
+            names: HashMap::new(),
+        }
+    }
+
+    /// "2026-03-01 by Ada Lovelace", or just the date when there's no
+    /// editor on record (seeded types, or the admin has been deleted).
+    async fn last_edited(
+        &mut self,
+        updated_at: DateTime<Utc>,
+        updated_by: Option<Uuid>,
+    ) -> String {
+        let date = updated_at.format("%Y-%m-%d");
+        let Some(id) = updated_by else {
+            return date.to_string();
+        };
+        if !self.names.contains_key(&id) {
+            let name = match self.member_repo.find_by_id(id).await {
+                Ok(member) => member.map(|m| m.full_name),
+                Err(_) => None,
+            };
+            self.names.insert(id, name);
+        }
+        match &self.names[&id] {
+            Some(name) => format!("{} by {}", date, name),
+            None => date.to_string(),
+        }
+    }
+}
+
+async fn fetch_basic_types(
+    service: &BasicTypeService,
+    editors: &mut EditorNames<'_>,
+    include_inactive: bool,
+) -> Vec<TypeInfo> {
+    let types = service.list(include_inactive).await.unwrap_or_default();
+    let mut infos = Vec::with_capacity(types.len());
+    for t in types {
+        let last_edited = editors.last_edited(t.updated_at, t.updated_by).await;
+        infos.push(TypeInfo {
             id: t.id.to_string(),
             name: t.name,
             slug: t.slug,
             description: t.description,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
             color: t.color,
             icon: t.icon,
             sort_order: t.sort_order,
             is_active: t.is_active,
             usage_count: 0,
-        })
-        .collect()
+            notify_members: true,
+            last_edited: Some(last_edited),
+        });
+    }
+    infos
 }
 
 async fn fetch_membership_types(
     service: &MembershipTypeService,
+    editors: &mut EditorNames<'_>,
     include_inactive: bool,
 ) -> Vec<MembershipTypeInfo> {
-    service
-        .list(include_inactive)
-        .await
-        .unwrap_or_default()
-        .into_iter()
-        .map(|t| {
-            let fee_dollars = t.fee_dollars();
-            MembershipTypeInfo {
-                id: t.id.to_string(),
-                name: t.name,
-                slug: t.slug,
-                description: t.description,
-                color: t.color,
-                icon: t.icon,
-                sort_order: t.sort_order,
-                is_active: t.is_active,
-                fee_cents: t.fee_cents,
-                fee_dollars: format!("{:.2}", fee_dollars),
-                billing_period: t.billing_period,
-                usage_count: 0,
-            }
-        })
-        .collect()
+    let types = service.list(include_inactive).await.unwrap_or_default();
+    let counts = service.usage_counts().await.unwrap_or_default();
+    let mut infos = Vec::with_capacity(types.len());
+    for t in types {
+        let last_edited = editors.last_edited(t.updated_at, t.updated_by).await;
+        let fee_dollars = t.fee().to_decimal_string();
+        let usage_count = counts.get(&t.id).copied().unwrap_or(0);
+        infos.push(MembershipTypeInfo {
+            id: t.id.to_string(),
+            name: t.name,
+            slug: t.slug,
+            description: t.description,
+            text_color: t.color.as_deref().and_then(contrasting_text_color),
+            color: t.color,
+            icon: t.icon,
+            sort_order: t.sort_order,
+            is_active: t.is_active,
+            fee_cents: t.fee_cents,
+            fee_dollars,
+            billing_period: t.billing_period,
+            max_members: t.max_members.map(|max| max.to_string()).unwrap_or_default(),
+            usage_count,
+            capacity: t.max_members.map(|max| Capacity {
+                used: usage_count,
+                max,
+            }),
+            last_edited: Some(last_edited),
+        });
+    }
+    infos
 }
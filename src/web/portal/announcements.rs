@@ -2,16 +2,22 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
-    Extension,
+    Extension, Form,
 };
 use serde::Deserialize;
 
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
+    domain::{AnnouncementType, HtmlPolicy, Locale},
+    error::AppError,
     repository::AnnouncementRepository,
+    service::announcement_comment_service::{
+        AnnouncementComment, AnnouncementCommentService, CommentViewer, MAX_COMMENT_LEN,
+    },
+    service::settings_service::SettingsService,
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -41,7 +47,7 @@ pub struct AnnouncementsListQuery {
 
 pub async fn announcements_list_api(
     State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
-    Extension(_current_user): Extension<CurrentUser>,
+    Extension(current_user): Extension<CurrentUser>,
     Query(query): Query<AnnouncementsListQuery>,
 ) -> impl IntoResponse {
     // Get all published announcements (both public and private - members can see all)
@@ -61,7 +67,7 @@ pub async fn announcements_list_api(
         .filter(|a| {
             if let Some(ref announcement_type) = query.announcement_type {
                 if !announcement_type.is_empty()
-                    && format!("{:?}", a.announcement_type) != *announcement_type
+                    && a.announcement_type.as_str() != announcement_type
                 {
                     return false;
                 }
@@ -83,13 +89,12 @@ pub async fn announcements_list_api(
     html.push_str(r#"<div class="space-y-4">"#);
 
     for announcement in filtered_announcements {
-        let type_badge_color = match format!("{:?}", announcement.announcement_type).as_str() {
-            "News" => "bg-blue-100 text-blue-800",
-            "Achievement" => "bg-yellow-100 text-yellow-800",
-            "Meeting" => "bg-purple-100 text-purple-800",
-            "CTFResult" => "bg-red-100 text-red-800",
-            "General" => "bg-gray-100 text-gray-800",
-            _ => "bg-gray-100 text-gray-800",
+        let type_badge_color = match announcement.announcement_type {
+            AnnouncementType::News => "bg-blue-100 text-blue-800",
+            AnnouncementType::Achievement => "bg-yellow-100 text-yellow-800",
+            AnnouncementType::Meeting => "bg-purple-100 text-purple-800",
+            AnnouncementType::CTFResult => "bg-red-100 text-red-800",
+            AnnouncementType::General => "bg-gray-100 text-gray-800",
         };
 
         let visibility_badge = if announcement.is_public {
@@ -110,18 +115,18 @@ pub async fn announcements_list_api(
 
         let published_date = announcement
             .published_at
-            .map(|dt| dt.format("%B %d, %Y").to_string())
+            .map(|dt| current_user.locale.long_date(&dt))
             .unwrap_or_default();
 
         html.push_str(&format!(
             r#"<div class="bg-white rounded-lg shadow-sm p-6">
                 {}
                 <div class="flex items-center gap-2 mb-3">
-                    <span class="px-2 py-1 text-xs font-medium rounded {}">{:?}</span>
+                    <span class="px-2 py-1 text-xs font-medium rounded {}">{}</span>
                     {}
                     {}
                 </div>
-                <h3 class="text-lg font-semibold text-gray-900 mb-2">{}</h3>
+                <h3 class="text-lg font-semibold text-gray-900 mb-2"><a href="/portal/announcements/{}" class="hover:text-blue-600">{}</a></h3>
                 <p class="text-sm text-gray-600 whitespace-pre-wrap">{}</p>
                 <p class="text-xs text-gray-400 mt-4">{}</p>
             </div>"#,
@@ -130,6 +135,7 @@ pub async fn announcements_list_api(
             announcement.announcement_type,
             visibility_badge,
             featured_badge,
+            announcement.id,
             crate::web::escape_html(&announcement.title),
             crate::web::escape_html(&announcement.content),
             published_date,
@@ -139,3 +145,147 @@ pub async fn announcements_list_api(
     html.push_str("</div>");
     axum::response::Html(html)
 }
+
+pub struct PortalAnnouncementDetail {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub announcement_type: String,
+    pub is_public: bool,
+    pub image_url: Option<String>,
+    pub published_at: String,
+    pub comments_enabled: bool,
+}
+
+pub struct CommentView {
+    pub author: String,
+    /// Sanitized render of the member's Markdown.
+    pub body_html: String,
+    pub created_at: String,
+}
+
+impl CommentView {
+    fn from_comment(comment: AnnouncementComment, locale: &Locale, policy: &HtmlPolicy) -> Self {
+        Self {
+            author: comment.author,
+            body_html: crate::web::markdown::render(&comment.body, policy),
+            created_at: locale.date_time(&comment.created_at),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "portal/announcement_detail.html")]
+pub struct AnnouncementDetailTemplate {
+    pub base: BaseContext,
+    pub announcement: PortalAnnouncementDetail,
+    pub announcement_id: String,
+    pub comments: Vec<CommentView>,
+    pub error: Option<String>,
+    pub draft: String,
+    pub max_len: usize,
+}
+
+/// The comment thread alone, returned after a post.
+#[derive(Template)]
+#[template(path = "portal/_announcement_comments.html")]
+pub struct AnnouncementCommentsTemplate {
+    pub announcement_id: String,
+    pub comments: Vec<CommentView>,
+    pub error: Option<String>,
+    pub draft: String,
+    pub max_len: usize,
+}
+
+pub async fn announcement_detail_page(
+    State(csrf_service): State<Arc<CsrfService>>,
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+    Path(announcement_id): Path<uuid::Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let announcement = comment_service
+        .visible_announcement(announcement_id, CommentViewer::Member)
+        .await?;
+    let comments = if announcement.comments_enabled {
+        let policy = settings_service.html_policy().await;
+        comment_service
+            .list_visible(announcement_id, CommentViewer::Member)
+            .await?
+            .into_iter()
+            .map(|c| CommentView::from_comment(c, &current_user.locale, &policy))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let detail = PortalAnnouncementDetail {
+        id: announcement.id.to_string(),
+        title: announcement.title,
+        content: announcement.content,
+        announcement_type: announcement.announcement_type.to_string(),
+        is_public: announcement.is_public,
+        image_url: announcement.image_url,
+        published_at: announcement
+            .published_at
+            .map(|dt| current_user.locale.long_date(&dt))
+            .unwrap_or_default(),
+        comments_enabled: announcement.comments_enabled,
+    };
+
+    Ok(HtmlTemplate(AnnouncementDetailTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        announcement_id: detail.id.clone(),
+        announcement: detail,
+        comments,
+        error: None,
+        draft: String::new(),
+        max_len: MAX_COMMENT_LEN,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentForm {
+    pub body: String,
+}
+
+/// Post a comment and return the refreshed thread. A rejected comment
+/// (empty, too long, rate-limited) comes back in the textarea with the
+/// reason above it; like `form_invalid`, that's a 200 so htmx swaps it.
+pub async fn post_comment(
+    State(comment_service): State<Arc<AnnouncementCommentService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(announcement_id): Path<uuid::Uuid>,
+    Form(form): Form<CommentForm>,
+) -> Result<impl IntoResponse, AppError> {
+    let (error, draft) = match comment_service
+        .post(current_user.member.id, announcement_id, &form.body)
+        .await
+    {
+        Ok(_) => (None, String::new()),
+        Err(AppError::Validation(msg)) => (Some(msg), form.body),
+        Err(AppError::TooManyRequests) => (
+            Some("You're commenting too quickly. Please wait a few minutes.".to_string()),
+            form.body,
+        ),
+        Err(e) => return Err(e),
+    };
+
+    let policy = settings_service.html_policy().await;
+    let comments = comment_service
+        .list_visible(announcement_id, CommentViewer::Member)
+        .await?
+        .into_iter()
+        .map(|c| CommentView::from_comment(c, &current_user.locale, &policy))
+        .collect();
+
+    Ok(HtmlTemplate(AnnouncementCommentsTemplate {
+        announcement_id: announcement_id.to_string(),
+        comments,
+        error,
+        draft,
+        max_len: MAX_COMMENT_LEN,
+    }))
+}
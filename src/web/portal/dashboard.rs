@@ -4,13 +4,13 @@ use askama::Template;
 use axum::{extract::State, response::IntoResponse, Extension};
 use serde::Serialize;
 
-use super::MemberInfo;
+use super::{dues_status_for, MemberInfo};
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
-    domain::AttendanceStatus,
-    repository::{EventRepository, PaymentRepository},
-    service::membership_type_service::MembershipTypeService,
+    domain::{can_view_event, AttendanceStatus, DuesStatus, MemberStatus},
+    repository::{AnnouncementRepository, EventRepository, PaymentRepository},
+    service::{membership_type_service::MembershipTypeService, settings_service::SettingsService},
     web::templates::{filters, BaseContext, HtmlTemplate},
 };
 
@@ -19,34 +19,35 @@ use crate::{
 pub struct MemberDashboardTemplate {
     pub base: BaseContext,
     pub member: MemberInfo,
+    pub dues_status: DuesStatus,
+    /// Whether to show the featured-announcements card; off when the
+    /// club sets its count to 0.
+    pub show_featured: bool,
 }
 
 /// Async-loaded banner on every portal page. Shows a warning when dues
-/// are past due but the member is still within grace period (status is
-/// still Active). Returns empty HTML when dues are current or the
-/// member is already Expired (their dedicated /portal/restore page
-/// already tells them).
-pub async fn dues_warning(Extension(current_user): Extension<CurrentUser>) -> impl IntoResponse {
-    use crate::domain::MemberStatus;
-
+/// are past due but the member is still Active — normally because
+/// they're within the grace period, or because the daily sweep hasn't
+/// expired them yet. Returns empty HTML when dues are current or
+/// exempt, or the member is already Expired (their dedicated
+/// /portal/restore page already tells them).
+pub async fn dues_warning(
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> impl IntoResponse {
     let member = &current_user.member;
-
-    // Nothing to warn about for Honorary members or bypass-dues accounts.
-    if member.status != MemberStatus::Active || member.bypass_dues {
+    if member.status != MemberStatus::Active {
         return axum::response::Html(String::new());
     }
 
     let now = chrono::Utc::now();
-    let Some(due) = member.dues_paid_until else {
-        return axum::response::Html(String::new());
+    let status = DuesStatus::from_member(member, now, settings_service.grace_period_days().await);
+    let due = match (status, member.dues_paid_until) {
+        (DuesStatus::InGrace | DuesStatus::Expired, Some(due)) => due,
+        _ => return axum::response::Html(String::new()),
     };
 
-    if due > now {
-        // Dues are current.
-        return axum::response::Html(String::new());
-    }
-
-    // Past due but still Active — within grace period. Nudge them.
+    // Past due but still Active. Nudge them.
     let days_overdue = (now - due).num_days();
     let overdue_text = match days_overdue {
         0 => "today".to_string(),
@@ -74,6 +75,8 @@ pub async fn dues_warning(Extension(current_user): Extension<CurrentUser>) -> im
 
 pub async fn member_dashboard(
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session): Extension<SessionInfo>,
@@ -97,9 +100,19 @@ pub async fn member_dashboard(
         dues_paid_until: current_user.member.dues_paid_until,
     };
 
+    let dues_status = dues_status_for(
+        &current_user.member,
+        payment_repo.as_ref(),
+        chrono::Utc::now(),
+        settings_service.grace_period_days().await,
+    )
+    .await;
+
     let template = MemberDashboardTemplate {
         base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
         member: member_info,
+        dues_status,
+        show_featured: settings_service.dashboard_featured_count().await > 0,
     };
 
     HtmlTemplate(template)
@@ -121,10 +134,18 @@ pub async fn upcoming_events(
     State(event_repo): State<Arc<dyn EventRepository>>,
     Extension(current_user): Extension<CurrentUser>,
 ) -> impl IntoResponse {
-    // Authenticated members see both public and members-only events;
-    // visibility filtering is per-event inside the template, not at
-    // the repo layer.
-    let events = event_repo.list_upcoming(5).await.unwrap_or_default();
+    // The repo returns every published upcoming event; visibility is
+    // filtered here so AdminOnly events stay off non-admin dashboards.
+    // Over-fetch so a run of admin-only events doesn't leave the card
+    // short.
+    let events: Vec<_> = event_repo
+        .list_upcoming(50)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| can_view_event(Some(&current_user.member), e))
+        .take(5)
+        .collect();
 
     // Transform to our summary format, checking attendance for each event
     let member_id = current_user.member.id;
@@ -142,8 +163,8 @@ pub async fn upcoming_events(
         event_summaries.push(EventSummary {
             id: event.id.to_string(),
             title: event.title,
-            date: event.start_time.format("%B %d, %Y").to_string(),
-            time: event.start_time.format("%l:%M %p").to_string(),
+            date: current_user.locale.long_date(&event.start_time),
+            time: current_user.locale.time(&event.start_time),
             location: event.location,
             image_url: event.image_url,
             attending,
@@ -193,6 +214,56 @@ pub async fn upcoming_events(
     axum::response::Html(html)
 }
 
+// API endpoint for featured announcements
+pub async fn featured_announcements(
+    State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> impl IntoResponse {
+    let limit = settings_service.dashboard_featured_count().await;
+    if limit == 0 {
+        return axum::response::Html(String::new());
+    }
+
+    // Members-only announcements are for members in good standing,
+    // the same people the announcement pages admit.
+    let include_private = matches!(
+        current_user.member.status,
+        MemberStatus::Active | MemberStatus::Honorary
+    );
+    let announcements = announcement_repo
+        .list_featured(include_private, limit)
+        .await
+        .unwrap_or_default();
+
+    let html = if announcements.is_empty() {
+        r#"<p class="text-gray-500">No featured announcements</p>"#.to_string()
+    } else {
+        let mut html = String::from(r#"<div class="space-y-3">"#);
+        for announcement in announcements {
+            let published = announcement
+                .published_at
+                .map(|dt| current_user.locale.long_date(&dt))
+                .unwrap_or_default();
+            html.push_str(&format!(
+                r#"
+                <a href="/portal/announcements/{}" class="block border-l-4 border-yellow-400 pl-3 hover:bg-gray-50">
+                    <h3 class="font-medium">{}</h3>
+                    <p class="text-sm text-gray-600">{}</p>
+                </a>
+                "#,
+                announcement.id,
+                crate::web::escape_html(&announcement.title),
+                published,
+            ));
+        }
+        html.push_str("</div>");
+        html
+    };
+
+    axum::response::Html(html)
+}
+
 // API endpoint for recent payments
 #[derive(Serialize)]
 struct PaymentSummary {
@@ -219,9 +290,9 @@ pub async fn recent_payments(
         .into_iter()
         .map(|p| PaymentSummary {
             id: p.id.to_string(),
-            amount: format!("${:.2}", p.amount_cents as f64 / 100.0),
-            status: format!("{:?}", p.status),
-            date: p.created_at.format("%B %d, %Y").to_string(),
+            amount: current_user.locale.currency(p.amount_cents, &p.currency),
+            status: p.status.to_string(),
+            date: current_user.locale.long_date(&p.created_at),
             description: if p.description.is_empty() {
                 "Membership dues".to_string()
             } else {
@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Extension,
+};
+use uuid::Uuid;
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    error::AppError,
+    service::{
+        directory_service::{DirectoryEntry, DirectoryService},
+        settings_service::SettingsService,
+    },
+    web::templates::{filters, BaseContext, HtmlTemplate},
+};
+
+/// A directory entry ready to render. `contact_href` is a mailto for
+/// the member when they show their email, otherwise a mailto to the
+/// org's contact address naming them ("contact via admin"), or empty
+/// when the org hasn't set one.
+pub struct DirectoryMemberView {
+    pub entry: DirectoryEntry,
+    pub initials: String,
+    pub contact_href: String,
+}
+
+impl DirectoryMemberView {
+    fn new(entry: DirectoryEntry, org_contact_email: &str) -> Self {
+        let initials = entry
+            .full_name
+            .split_whitespace()
+            .filter_map(|w| w.chars().next())
+            .take(2)
+            .flat_map(char::to_uppercase)
+            .collect();
+        let contact_href = match (&entry.email, org_contact_email) {
+            (Some(email), _) => format!("mailto:{}", email),
+            (None, "") => String::new(),
+            (None, org) => format!(
+                "mailto:{}?subject={}",
+                org,
+                urlencoding::encode(&format!(
+                    "Message for {} (@{})",
+                    entry.full_name, entry.username
+                )),
+            ),
+        };
+        Self {
+            entry,
+            initials,
+            contact_href,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "portal/directory.html")]
+pub struct DirectoryTemplate {
+    pub base: BaseContext,
+    pub members: Vec<DirectoryMemberView>,
+    /// Whether the viewer is listed themselves, for the opt-in hint.
+    pub viewer_listed: bool,
+}
+
+#[derive(Template)]
+#[template(path = "portal/directory_member.html")]
+pub struct DirectoryMemberTemplate {
+    pub base: BaseContext,
+    pub member: DirectoryMemberView,
+}
+
+pub async fn directory_page(
+    State(directory_service): State<Arc<DirectoryService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+) -> Result<axum::response::Response, AppError> {
+    let org_contact_email = settings_service
+        .get_value("org.contact_email")
+        .await
+        .unwrap_or_default();
+    let entries = directory_service.list().await?;
+    let viewer_listed = entries
+        .iter()
+        .any(|e| e.member_id == current_user.member.id);
+    let members = entries
+        .into_iter()
+        .map(|e| DirectoryMemberView::new(e, &org_contact_email))
+        .collect();
+
+    let template = DirectoryTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        members,
+        viewer_listed,
+    };
+    Ok(HtmlTemplate(template).into_response())
+}
+
+/// One member's directory card. Members who aren't listed are a 404,
+/// same as ones that don't exist.
+pub async fn directory_member_page(
+    State(directory_service): State<Arc<DirectoryService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+    Path(member_id): Path<Uuid>,
+) -> Result<axum::response::Response, AppError> {
+    let entry = directory_service
+        .get(member_id)
+        .await?
+        .ok_or(AppError::NotFound("Member not found".to_string()))?;
+    let org_contact_email = settings_service
+        .get_value("org.contact_email")
+        .await
+        .unwrap_or_default();
+
+    let template = DirectoryMemberTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        member: DirectoryMemberView::new(entry, &org_contact_email),
+    };
+    Ok(HtmlTemplate(template).into_response())
+}
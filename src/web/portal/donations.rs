@@ -13,10 +13,11 @@ use crate::{
     },
     auth::CsrfService,
     config::Settings,
-    domain::{Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus},
+    domain::{Money, Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus},
     error::AppError,
     payments::StripeClient,
     repository::{DonationCampaignRepository, PaymentRepository, SavedCardRepository},
+    service::billing_service::BillingService,
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -85,7 +86,7 @@ pub async fn donate_page(
             } else {
                 0
             };
-            (Some(format!("{:.2}", goal as f64 / 100.0)), pct)
+            (Some(Money::usd(goal).to_decimal_string()), pct)
         } else {
             (None, 0)
         };
@@ -95,7 +96,7 @@ pub async fn donate_page(
             slug: c.slug,
             description: c.description,
             goal_display,
-            raised_display: format!("{:.2}", raised as f64 / 100.0),
+            raised_display: Money::usd(raised).to_decimal_string(),
             progress_pct,
         });
     }
@@ -128,6 +129,7 @@ pub async fn donate_api(
     State(payment_repo): State<Arc<dyn PaymentRepository>>,
     State(saved_card_repo): State<Arc<dyn SavedCardRepository>>,
     State(donation_campaign_repo): State<Arc<dyn DonationCampaignRepository>>,
+    State(billing_service): State<Arc<BillingService>>,
     Extension(current_user): Extension<CurrentUser>,
     headers: axum::http::HeaderMap,
     Json(request): Json<DonateRequest>,
@@ -216,6 +218,7 @@ pub async fn donate_api(
             description: description.clone(),
             kind: PaymentKind::Donation { campaign_id },
             paid_at: None,
+            refunded_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -229,12 +232,16 @@ pub async fn donate_api(
                 &description,
                 &idempotency_key,
                 payment_id,
+                None,
             )
             .await
         {
             Ok(id) => id,
             Err(e) => {
-                let _ = payment_repo.fail_pending_payment(payment_id).await;
+                let _ = billing_service
+                    .auto_renew
+                    .fail_pending_payment(payment_id)
+                    .await;
                 return Err(e);
             }
         };
@@ -242,7 +249,8 @@ pub async fn donate_api(
         // Donation post-work is just the row flip — no dues
         // extension, no rescheduling. So whether we or the webhook
         // wins the flip, the user-visible result is the same.
-        let _ = payment_repo
+        let _ = billing_service
+            .auto_renew
             .complete_pending_payment(payment_id, &stripe_payment_id)
             .await?;
 
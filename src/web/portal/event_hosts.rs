@@ -0,0 +1,177 @@
+//! Host editing from the portal: an event's creator and co-hosts edit
+//! its details at `/portal/events/:id/edit`, and the creator adds and
+//! removes co-hosts there. Admins can do both for any event.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    Extension, Form,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    error::AppError,
+    service::event_host_service::{EventHost, EventHostService, HostEventInput},
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "portal/event_edit.html")]
+pub struct EventEditTemplate {
+    pub base: BaseContext,
+    pub event_id: String,
+    pub title: String,
+    pub description: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub location: String,
+    pub hosts: Vec<EventHost>,
+    /// The creator or an admin: shows the add and remove controls.
+    pub can_manage_hosts: bool,
+}
+
+pub async fn event_edit_page(
+    State(csrf_service): State<Arc<CsrfService>>,
+    State(event_host_service): State<Arc<EventHostService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+    Path(event_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let event = event_host_service
+        .editable_event(&current_user.member, event_id)
+        .await?;
+    let hosts = event_host_service.hosts(&event).await?;
+
+    Ok(HtmlTemplate(EventEditTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        event_id: event.id.to_string(),
+        can_manage_hosts: EventHostService::can_manage_hosts(&current_user.member, &event),
+        title: event.title,
+        description: event.description,
+        start_time: event.start_time.format("%Y-%m-%dT%H:%M").to_string(),
+        end_time: event
+            .end_time
+            .map(|t| t.format("%Y-%m-%dT%H:%M").to_string())
+            .unwrap_or_default(),
+        location: event.location.unwrap_or_default(),
+        hosts,
+    }))
+}
+
+fn error_message(msg: &str) -> Response {
+    axum::response::Html(format!(
+        "<div class=\"p-4 bg-red-50 text-red-800 rounded-md\">{}</div>",
+        crate::web::escape_html(msg)
+    ))
+    .into_response()
+}
+
+/// Send the browser back to the edit page with a toast, so the host
+/// list and form reflect what was just saved.
+fn reload(event_id: Uuid, toast: &str) -> Response {
+    Response::builder()
+        .status(200)
+        .header("HX-Redirect", format!("/portal/events/{}/edit", event_id))
+        .header(
+            "X-Toast",
+            serde_json::json!({ "message": toast, "type": "success" }).to_string(),
+        )
+        .body(axum::body::Body::empty())
+        .unwrap()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventEditForm {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub start_time: String,
+    #[serde(default)]
+    pub end_time: String,
+    #[serde(default)]
+    pub location: String,
+    #[allow(dead_code)]
+    pub csrf_token: Option<String>,
+}
+
+/// HTMX submit for the edit form. Validation problems come back as a
+/// message above the form; a non-host gets a 403.
+pub async fn update_event(
+    State(event_host_service): State<Arc<EventHostService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(event_id): Path<Uuid>,
+    Form(form): Form<EventEditForm>,
+) -> Result<Response, AppError> {
+    let parse_time = |s: &str| {
+        chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M")
+            .ok()
+            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
+    };
+    let Some(start_time) = parse_time(&form.start_time) else {
+        return Ok(error_message("Please enter a valid start time."));
+    };
+    let end_time = if form.end_time.is_empty() {
+        None
+    } else {
+        match parse_time(&form.end_time) {
+            Some(t) => Some(t),
+            None => return Ok(error_message("Please enter a valid end time.")),
+        }
+    };
+
+    let input = HostEventInput {
+        title: form.title,
+        description: form.description,
+        start_time,
+        end_time,
+        location: Some(form.location),
+    };
+    match event_host_service
+        .update(&current_user.member, event_id, input)
+        .await
+    {
+        Ok(_) => Ok(reload(event_id, "Event updated")),
+        Err(AppError::BadRequest(msg)) => Ok(error_message(&msg)),
+        Err(e) => Err(e),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddCoHostForm {
+    pub username: String,
+    #[allow(dead_code)]
+    pub csrf_token: Option<String>,
+}
+
+pub async fn add_co_host(
+    State(event_host_service): State<Arc<EventHostService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(event_id): Path<Uuid>,
+    Form(form): Form<AddCoHostForm>,
+) -> Result<Response, AppError> {
+    match event_host_service
+        .add_co_host(&current_user.member, event_id, &form.username)
+        .await
+    {
+        Ok(()) => Ok(reload(event_id, "Co-host added")),
+        Err(AppError::BadRequest(msg)) => Ok(error_message(&msg)),
+        Err(e) => Err(e),
+    }
+}
+
+pub async fn remove_co_host(
+    State(event_host_service): State<Arc<EventHostService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path((event_id, member_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, AppError> {
+    event_host_service
+        .remove_co_host(&current_user.member, event_id, member_id)
+        .await?;
+    Ok(reload(event_id, "Co-host removed"))
+}
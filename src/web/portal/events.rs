@@ -10,10 +10,18 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
-    api::middleware::auth::{CurrentUser, SessionInfo},
+    api::{
+        middleware::auth::{CurrentUser, SessionInfo},
+        state::EventBasicTypeService,
+    },
     auth::CsrfService,
-    domain::AttendanceStatus,
-    repository::EventRepository,
+    domain::{can_view_event, AttendanceStatus, EventStatus, EventType, EventVisibility},
+    repository::{EventRepository, MemberRepository},
+    service::{
+        event_host_service::EventHostService,
+        event_invite_service::EventInviteService,
+        event_proposal_service::{EventProposalService, ProposeEventInput},
+    },
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -44,6 +52,7 @@ pub struct EventsListQuery {
 
 pub async fn events_list_api(
     State(event_repo): State<Arc<dyn EventRepository>>,
+    State(event_host_service): State<Arc<EventHostService>>,
     Extension(current_user): Extension<CurrentUser>,
     Query(query): Query<EventsListQuery>,
 ) -> impl IntoResponse {
@@ -54,13 +63,15 @@ pub async fn events_list_api(
 
     let now = chrono::Utc::now();
 
-    // Filter events by type (past events not currently supported by repository)
+    // Filter events by visibility and type (past events not currently
+    // supported by repository)
     let filtered_events: Vec<_> = events
         .into_iter()
+        .filter(|e| can_view_event(Some(&current_user.member), e))
         .filter(|e| {
             // Filter by type
             if let Some(ref event_type) = query.event_type {
-                if !event_type.is_empty() && format!("{:?}", e.event_type) != *event_type {
+                if !event_type.is_empty() && e.event_type.as_str() != event_type {
                     return false;
                 }
             }
@@ -77,18 +88,23 @@ pub async fn events_list_api(
         );
     }
 
+    let hosted = event_host_service
+        .hosted_event_ids(member_id)
+        .await
+        .unwrap_or_default();
+
     let mut html = String::new();
     html.push_str(r#"<div class="space-y-4">"#);
 
     for event in filtered_events {
         let is_past = event.start_time < now;
-        let type_badge_color = match format!("{:?}", event.event_type).as_str() {
-            "Meeting" => "bg-blue-100 text-blue-800",
-            "Workshop" => "bg-purple-100 text-purple-800",
-            "CTF" => "bg-red-100 text-red-800",
-            "Social" => "bg-green-100 text-green-800",
-            "Training" => "bg-yellow-100 text-yellow-800",
-            _ => "bg-gray-100 text-gray-800",
+        let type_badge_color = match event.event_type {
+            EventType::Meeting => "bg-blue-100 text-blue-800",
+            EventType::Workshop => "bg-purple-100 text-purple-800",
+            EventType::CTF => "bg-red-100 text-red-800",
+            EventType::Social => "bg-green-100 text-green-800",
+            EventType::Training => "bg-yellow-100 text-yellow-800",
+            EventType::Hackathon => "bg-gray-100 text-gray-800",
         };
 
         // Check member's RSVP status for this event
@@ -98,30 +114,82 @@ pub async fn events_list_api(
             .ok()
             .flatten();
 
+        let waitlist_position = match rsvp_status {
+            Some(AttendanceStatus::Waitlisted) => event_repo
+                .waitlist_position(event.id, member_id)
+                .await
+                .ok()
+                .flatten(),
+            _ => None,
+        };
+
         let rsvp_button = if is_past {
             String::new()
+        } else if event.rsvp_closed(now) && rsvp_status != Some(AttendanceStatus::Registered) {
+            r#"<span class="text-sm text-gray-500">RSVPs closed</span>"#.to_string()
         } else {
-            render_rsvp_button(&event.id.to_string(), rsvp_status.as_ref())
+            render_rsvp_button(
+                &event.id.to_string(),
+                rsvp_status.as_ref(),
+                waitlist_position,
+            )
         };
 
+        let deadline_html = event
+            .rsvp_deadline
+            .filter(|_| !is_past)
+            .map(|d| {
+                format!(
+                    r#"<p>RSVP by {} at {}</p>"#,
+                    current_user.locale.long_date(&d),
+                    current_user.locale.time(&d)
+                )
+            })
+            .unwrap_or_default();
+
         let image_html = event.image_url.as_ref().map(|url| {
             format!(r#"<div class="bg-gray-100 rounded-t-lg -mt-6 -mx-6 mb-4 overflow-hidden" style="width: calc(100% + 3rem);"><img src="/{}" alt="" class="w-full h-40 object-contain"></div>"#, crate::web::escape_html(url))
         }).unwrap_or_default();
 
+        let hosts_html = match event_host_service.hosts(&event).await {
+            Ok(hosts) if !hosts.is_empty() => format!(
+                r#"<p>Hosted by {}</p>"#,
+                hosts
+                    .iter()
+                    .map(|h| crate::web::escape_html(&h.full_name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => String::new(),
+        };
+
+        let edit_html = if hosted.contains(&event.id) || current_user.member.is_admin {
+            format!(
+                r#"<a href="/portal/events/{}/edit" class="text-xs text-blue-600 hover:text-blue-800">Edit</a>"#,
+                event.id
+            )
+        } else {
+            String::new()
+        };
+
         html.push_str(&format!(
             r#"<div class="bg-white rounded-lg shadow-sm p-6 {}">
                 {}
                 <div class="flex justify-between items-start">
                     <div>
                         <div class="flex items-center gap-2 mb-2">
-                            <span class="px-2 py-1 text-xs font-medium rounded {}">{:?}</span>
+                            <span class="px-2 py-1 text-xs font-medium rounded {}">{}</span>
                             {}
+                            {}
+                            <a href="/public/events/{}/calendar" class="text-xs text-blue-600 hover:text-blue-800">Add to calendar</a>
                         </div>
                         <h3 class="text-lg font-semibold text-gray-900">{}</h3>
                         <p class="text-sm text-gray-600 mt-1">{}</p>
                         <div class="mt-2 text-sm text-gray-500">
                             <p>{} at {}</p>
                             {}
+                            {}
+                            {}
                         </div>
 
                     </div>
@@ -139,14 +207,18 @@ pub async fn events_list_api(
             } else {
                 ""
             },
+            edit_html,
+            event.id,
             crate::web::escape_html(&event.title),
             crate::web::escape_html(&event.description),
-            event.start_time.format("%B %d, %Y"),
-            event.start_time.format("%l:%M %p"),
+            current_user.locale.long_date(&event.start_time),
+            current_user.locale.time(&event.start_time),
             event
                 .location
                 .map(|l| format!(r#"<p>Location: {}</p>"#, crate::web::escape_html(&l)))
                 .unwrap_or_default(),
+            deadline_html,
+            hosts_html,
             rsvp_button,
         ));
     }
@@ -155,8 +227,13 @@ pub async fn events_list_api(
     axum::response::Html(html)
 }
 
-/// Render the appropriate RSVP button based on current status
-fn render_rsvp_button(event_id: &str, status: Option<&AttendanceStatus>) -> String {
+/// Render the appropriate RSVP button based on current status.
+/// `waitlist_position` is shown next to a waitlisted status.
+fn render_rsvp_button(
+    event_id: &str,
+    status: Option<&AttendanceStatus>,
+    waitlist_position: Option<i64>,
+) -> String {
     match status {
         Some(AttendanceStatus::Registered) => {
             format!(
@@ -175,7 +252,7 @@ fn render_rsvp_button(event_id: &str, status: Option<&AttendanceStatus>) -> Stri
         Some(AttendanceStatus::Waitlisted) => {
             format!(
                 r#"<div class="flex flex-col items-end gap-2">
-                    <span class="text-sm text-yellow-600 font-medium">On waitlist</span>
+                    <span class="text-sm text-yellow-600 font-medium">On waitlist{}</span>
                     <button hx-post="/portal/api/events/{}/cancel"
                             hx-swap="outerHTML"
                             hx-target="closest div.text-right"
@@ -183,6 +260,9 @@ fn render_rsvp_button(event_id: &str, status: Option<&AttendanceStatus>) -> Stri
                         Leave waitlist
                     </button>
                 </div>"#,
+                waitlist_position
+                    .map(|p| format!(" (#{})", p))
+                    .unwrap_or_default(),
                 event_id
             )
         }
@@ -203,42 +283,260 @@ fn render_rsvp_button(event_id: &str, status: Option<&AttendanceStatus>) -> Stri
 /// Handle RSVP to an event
 pub async fn rsvp_event(
     State(event_repo): State<Arc<dyn EventRepository>>,
+    State(event_invite_service): State<Arc<EventInviteService>>,
     Extension(current_user): Extension<CurrentUser>,
     Path(event_id): Path<Uuid>,
 ) -> impl IntoResponse {
     let member_id = current_user.member.id;
 
-    // Register attendance
-    if let Err(e) = event_repo.register_attendance(event_id, member_id).await {
-        return axum::response::Html(format!(
-            r#"<div class="text-red-600 text-sm">Error: {}</div>"#,
-            crate::web::escape_html(&e.to_string())
-        ));
+    // Proposals aren't in any listing, but the id is guessable from the
+    // proposer's own page — don't let anyone RSVP before approval. Same
+    // for an AdminOnly event a member has no business seeing.
+    let event = match event_repo.find_by_id(event_id).await {
+        Ok(Some(event))
+            if event.status == EventStatus::Published
+                && can_view_event(Some(&current_user.member), &event) =>
+        {
+            event
+        }
+        _ => {
+            return axum::response::Html(
+                r#"<div class="text-red-600 text-sm">Error: Event not found</div>"#.to_string(),
+            );
+        }
+    };
+
+    // Register attendance. Capacity counts guests too, so a public
+    // workshop can fill up from the website before members get to it;
+    // past that, the member joins the waitlist.
+    match event_repo.register_attendance(event_id, member_id).await {
+        Ok(AttendanceStatus::Registered) => {}
+        Ok(status) => {
+            let position = event_repo
+                .waitlist_position(event_id, member_id)
+                .await
+                .ok()
+                .flatten();
+            return axum::response::Html(render_rsvp_button(
+                &event_id.to_string(),
+                Some(&status),
+                position,
+            ));
+        }
+        Err(crate::error::AppError::RsvpClosed(deadline)) => {
+            return axum::response::Html(format!(
+                r#"<div class="text-red-600 text-sm">RSVPs closed on {} at {}</div>"#,
+                current_user.locale.long_date(&deadline),
+                current_user.locale.time(&deadline)
+            ));
+        }
+        Err(crate::error::AppError::Conflict(msg) | crate::error::AppError::BadRequest(msg)) => {
+            return axum::response::Html(format!(
+                r#"<div class="text-red-600 text-sm">{}</div>"#,
+                crate::web::escape_html(&msg)
+            ));
+        }
+        Err(e) => {
+            return axum::response::Html(format!(
+                r#"<div class="text-red-600 text-sm">Error: {}</div>"#,
+                crate::web::escape_html(&e.to_string())
+            ));
+        }
     }
 
+    event_invite_service
+        .send_rsvp_confirmation(&event, &current_user.member)
+        .await;
+
     // Return updated button
     axum::response::Html(render_rsvp_button(
         &event_id.to_string(),
         Some(&AttendanceStatus::Registered),
+        None,
     ))
 }
 
 /// Handle cancel RSVP
 pub async fn cancel_rsvp_event(
     State(event_repo): State<Arc<dyn EventRepository>>,
+    State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(event_invite_service): State<Arc<EventInviteService>>,
     Extension(current_user): Extension<CurrentUser>,
     Path(event_id): Path<Uuid>,
 ) -> impl IntoResponse {
     let member_id = current_user.member.id;
 
-    // Cancel attendance
-    if let Err(e) = event_repo.cancel_attendance(event_id, member_id).await {
-        return axum::response::Html(format!(
-            r#"<div class="text-red-600 text-sm">Error: {}</div>"#,
-            crate::web::escape_html(&e.to_string())
-        ));
+    // Only a registered member was sent an invite to withdraw; leaving
+    // the waitlist needs no email.
+    let was_registered = matches!(
+        event_repo.get_member_attendance_status(event_id, member_id).await,
+        Ok(Some(AttendanceStatus::Registered))
+    );
+
+    // Cancel attendance. A freed seat goes to the head of the waitlist.
+    let promoted = match event_repo.cancel_attendance(event_id, member_id).await {
+        Ok(promoted) => promoted,
+        Err(e) => {
+            return axum::response::Html(format!(
+                r#"<div class="text-red-600 text-sm">Error: {}</div>"#,
+                crate::web::escape_html(&e.to_string())
+            ));
+        }
+    };
+
+    if let Ok(Some(event)) = event_repo.find_by_id(event_id).await {
+        if was_registered {
+            event_invite_service
+                .send_rsvp_cancellation(&event, &current_user.member)
+                .await;
+        }
+        if let Some(promoted) = promoted {
+            if let Ok(Some(member)) = member_repo.find_by_id(promoted).await {
+                event_invite_service
+                    .send_rsvp_confirmation(&event, &member)
+                    .await;
+            }
+        }
     }
 
     // Return updated button (shows RSVP button again)
-    axum::response::Html(render_rsvp_button(&event_id.to_string(), None))
+    axum::response::Html(render_rsvp_button(&event_id.to_string(), None, None))
+}
+
+#[derive(Template)]
+#[template(path = "portal/event_propose.html")]
+pub struct ProposeEventTemplate {
+    pub base: BaseContext,
+    pub event_types: Vec<String>,
+    pub proposals: Vec<ProposalInfo>,
+}
+
+pub struct ProposalInfo {
+    pub title: String,
+    pub start_time: String,
+    pub status: String,
+    pub feedback: Option<String>,
+}
+
+/// Proposal form plus the member's own submission history.
+pub async fn propose_event_page(
+    State(csrf_service): State<Arc<CsrfService>>,
+    State(event_type_service): State<EventBasicTypeService>,
+    State(proposal_service): State<Arc<EventProposalService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+) -> impl IntoResponse {
+    let event_types = event_type_service
+        .0
+        .list(false)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+
+    let proposals = proposal_service
+        .proposals_by(current_user.member.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| ProposalInfo {
+            title: e.title,
+            start_time: current_user.locale.long_date_time(&e.start_time),
+            status: match e.status {
+                EventStatus::Proposed => "Awaiting review",
+                EventStatus::Published => "Approved",
+                EventStatus::Rejected => "Rejected",
+            }
+            .to_string(),
+            feedback: e.review_feedback,
+        })
+        .collect();
+
+    HtmlTemplate(ProposeEventTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        event_types,
+        proposals,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProposeEventForm {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub event_type: String,
+    #[serde(default)]
+    pub visibility: String,
+    pub start_time: String,
+    #[serde(default)]
+    pub end_time: String,
+    #[serde(default)]
+    pub location: String,
+    #[allow(dead_code)]
+    pub csrf_token: Option<String>,
+}
+
+/// HTMX submit for the proposal form. Success redirects back to the
+/// page so the new proposal shows in the history list.
+pub async fn propose_event(
+    State(proposal_service): State<Arc<EventProposalService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<ProposeEventForm>,
+) -> axum::response::Response {
+    let error = |msg: &str| {
+        axum::response::Html(format!(
+            "<div class=\"p-4 bg-red-50 text-red-800 rounded-md\">{}</div>",
+            crate::web::escape_html(msg)
+        ))
+        .into_response()
+    };
+
+    let parse_time = |s: &str| {
+        chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M")
+            .ok()
+            .map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc))
+    };
+    let Some(start_time) = parse_time(&form.start_time) else {
+        return error("Please enter a valid start time.");
+    };
+    let end_time = if form.end_time.is_empty() {
+        None
+    } else {
+        match parse_time(&form.end_time) {
+            Some(t) => Some(t),
+            None => return error("Please enter a valid end time."),
+        }
+    };
+
+    let event_type = form.event_type.parse().unwrap_or(EventType::Meeting);
+    let visibility = match form.visibility.as_str() {
+        "Public" => EventVisibility::Public,
+        _ => EventVisibility::MembersOnly,
+    };
+
+    let input = ProposeEventInput {
+        title: form.title,
+        description: form.description,
+        event_type,
+        visibility,
+        start_time,
+        end_time,
+        location: Some(form.location),
+    };
+
+    match proposal_service.propose(&current_user.member, input).await {
+        Ok(_) => axum::response::Response::builder()
+            .status(200)
+            .header("HX-Redirect", "/portal/events/propose")
+            .header(
+                "X-Toast",
+                r#"{"message":"Thanks! Your event has been sent to the organizers for review.","type":"success"}"#,
+            )
+            .body(axum::body::Body::empty())
+            .unwrap(),
+        Err(crate::error::AppError::BadRequest(msg)) => error(&msg),
+        Err(e) => error(&format!("Could not submit your proposal: {}", e)),
+    }
 }
@@ -0,0 +1,139 @@
+//! `/portal/me`: one page with a member's own dues standing, the next
+//! event they're registered for, their recent payments and their
+//! notification preferences. It's where members land after signing in
+//! unless the club or the member picks another page.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{extract::State, response::IntoResponse, Extension};
+use chrono::Utc;
+
+use super::dues_status_for;
+use crate::{
+    api::middleware::auth::{CurrentUser, SessionInfo},
+    auth::CsrfService,
+    domain::{AttendanceStatus, DuesStatus},
+    repository::{EventRepository, PaymentRepository},
+    service::{celebration_service::CelebrationService, settings_service::SettingsService},
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+/// How many payments the summary lists; the full history is on
+/// `/portal/payments`.
+const RECENT_PAYMENTS: i64 = 5;
+
+pub struct NextEvent {
+    pub title: String,
+    pub when: String,
+    pub location: Option<String>,
+}
+
+pub struct PaymentLine {
+    pub description: String,
+    pub date: String,
+    pub amount: String,
+    pub status: String,
+}
+
+#[derive(Template)]
+#[template(path = "portal/me.html")]
+pub struct MeTemplate {
+    pub base: BaseContext,
+    pub full_name: String,
+    pub dues_status: DuesStatus,
+    /// "Paid through" date, empty if dues have never been paid.
+    pub dues_paid_until: String,
+    /// "N days remaining" until dues lapse; `None` once they have, or
+    /// if they've never been paid.
+    pub days_remaining: Option<String>,
+    pub next_event: Option<NextEvent>,
+    pub payments: Vec<PaymentLine>,
+    pub event_reminders: bool,
+    pub celebrate: bool,
+}
+
+pub async fn me_page(
+    State(settings_service): State<Arc<SettingsService>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(celebration_service): State<Arc<CelebrationService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+) -> impl IntoResponse {
+    let member = &current_user.member;
+    let locale = current_user.locale;
+    let now = Utc::now();
+
+    let dues_status = dues_status_for(
+        member,
+        payment_repo.as_ref(),
+        now,
+        settings_service.grace_period_days().await,
+    )
+    .await;
+    let days_remaining =
+        member
+            .dues_paid_until
+            .filter(|due| *due > now)
+            .map(|due| match (due - now).num_days() {
+                1 => "1 day remaining".to_string(),
+                n => format!("{} days remaining", n),
+            });
+
+    // Attendance comes back latest first; the next event is the
+    // soonest upcoming one still marked Registered.
+    let next_event = event_repo
+        .list_member_attendance(member.id, false)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|a| a.event_start > now && a.status == AttendanceStatus::Registered)
+        .min_by_key(|a| a.event_start)
+        .map(|a| NextEvent {
+            title: a.event_title,
+            when: locale.long_date_time(&a.event_start),
+            location: a.event_location,
+        });
+
+    let payments = payment_repo
+        .find_by_member_paginated(member.id, RECENT_PAYMENTS, 0)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| PaymentLine {
+            description: if p.description.is_empty() {
+                "Membership dues".to_string()
+            } else {
+                p.description
+            },
+            date: locale.long_date(&p.created_at),
+            amount: locale.currency(p.amount_cents, &p.currency),
+            status: p.status.to_string(),
+        })
+        .collect();
+
+    let celebrations = celebration_service
+        .preferences(member.id)
+        .await
+        .unwrap_or_default();
+
+    HtmlTemplate(MeTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        full_name: member.full_name.clone(),
+        dues_status,
+        dues_paid_until: member
+            .dues_paid_until
+            .map(|d| locale.long_date(&d))
+            .unwrap_or_default(),
+        days_remaining,
+        next_event,
+        payments,
+        event_reminders: event_repo
+            .reminders_enabled(member.id)
+            .await
+            .unwrap_or(true),
+        celebrate: celebrations.celebrate,
+    })
+}
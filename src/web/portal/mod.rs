@@ -1,8 +1,11 @@
 pub mod admin;
 mod announcements;
 pub mod dashboard;
+mod directory;
 mod donations;
+mod event_hosts;
 mod events;
+mod me;
 mod partials;
 mod payments;
 pub mod profile;
@@ -34,6 +37,10 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             "/members/import",
             post(admin::members::admin_members_import),
         )
+        .route(
+            "/members/import/template",
+            get(admin::members::admin_members_import_template),
+        )
         .route(
             "/members/new",
             get(admin::members::create::admin_new_member_page),
@@ -70,6 +77,10 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             "/members/:id/expire-now",
             post(admin::members::status::admin_expire_now),
         )
+        .route(
+            "/members/:id/honorary",
+            post(admin::members::status::admin_grant_honorary),
+        )
         .route(
             "/members/:id/payments",
             get(admin::members::dues::admin_member_payments),
@@ -90,14 +101,34 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             "/members/:id/resend-verification",
             post(admin::members::verification::admin_resend_verification),
         )
+        .route(
+            "/members/:id/resend-welcome",
+            post(admin::members::verification::admin_resend_welcome),
+        )
         .route(
             "/members/:id/discord-id",
             post(admin::members::discord::admin_update_discord_id),
         )
+        .route(
+            "/members/:id/resync-integrations",
+            post(admin::members::integrations::admin_resync_member_integrations),
+        )
         // Events
         .route("/events", get(admin::events::admin_events_page))
         .route("/events/new", get(admin::events::admin_new_event_page))
         .route("/events/new", post(admin::events::admin_create_event))
+        .route(
+            "/events/proposals",
+            get(admin::events::admin_event_proposals_page),
+        )
+        .route(
+            "/events/proposals/:id/approve",
+            post(admin::events::admin_approve_proposal),
+        )
+        .route(
+            "/events/proposals/:id/reject",
+            post(admin::events::admin_reject_proposal),
+        )
         .route("/events/:id", get(admin::events::admin_event_detail_page))
         .route(
             "/events/:id/update",
@@ -107,6 +138,14 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             "/events/:id/delete",
             post(admin::events::admin_delete_event),
         )
+        .route(
+            "/events/:id/attendees/export",
+            get(admin::events::admin_export_event_attendees),
+        )
+        .route(
+            "/events/:id/attendees",
+            post(admin::events::admin_register_member),
+        )
         // Announcements
         .route(
             "/announcements",
@@ -140,11 +179,24 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             "/announcements/:id/unpublish",
             post(admin::announcements::admin_unpublish_announcement),
         )
+        .route(
+            "/announcements/:id/comments/:comment_id/hide",
+            post(admin::announcements::admin_hide_comment),
+        )
+        .route(
+            "/announcements/:id/comments/:comment_id/unhide",
+            post(admin::announcements::admin_unhide_comment),
+        )
+        .route(
+            "/announcements/:id/comments/:comment_id/delete",
+            post(admin::announcements::admin_delete_comment),
+        )
         // Type management. Membership-type routes are registered first
         // with static `membership` segments so Axum's static-over-dynamic
         // matching prefers them; event/announcement types share a single
         // handler set parameterized by `:kind` ("event" | "announcement").
         .route("/types", get(admin::types::admin_types_page))
+        .route("/types/seed-all", post(admin::types::admin_seed_all_types))
         .route(
             "/types/membership/new",
             get(admin::types::admin_new_membership_type_page),
@@ -226,8 +278,29 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
             get(admin::billing::billing_dashboard_page),
         )
         // Audit log viewer + CSV export
+        .route("/contact", get(admin::contact::admin_contact_page))
+        .route(
+            "/contact/:id/handled",
+            post(admin::contact::admin_mark_contact_handled),
+        )
         .route("/audit", get(admin::audit::audit_log_page))
         .route("/audit/export", get(admin::audit::audit_log_export))
+        // Database backups: page, take-and-download, re-download
+        .route(
+            "/backup",
+            get(admin::backup::backup_page).post(admin::backup::create_backup),
+        )
+        .route("/backup/:name", get(admin::backup::download_backup))
+        // Outbound integration log + replay of failed attempts
+        .route(
+            "/integrations/log",
+            get(admin::integration_log::integration_log_page),
+        )
+        .route("/data-check", get(admin::data_check::data_check_page))
+        .route(
+            "/integrations/log/:id/replay",
+            post(admin::integration_log::replay_integration_log_entry),
+        )
         // CSRF is enforced at the top of the application router (see
         // `middleware::security::csrf_protect_unless_exempt`); only the
         // admin gate is layered here.
@@ -258,6 +331,14 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
         // historical receipts (for tax filing) even though they aren't
         // currently Active.
         .route("/payments/receipts", get(payments::receipts::receipts_page))
+        .route(
+            "/payments/summary/:year",
+            get(payments::receipts::year_summary_page),
+        )
+        .route(
+            "/payments/summary/:year/statement",
+            get(payments::receipts::year_statement),
+        )
         .route(
             "/payments/:payment_id/receipt",
             get(payments::receipts::receipt_page),
@@ -311,14 +392,59 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
     // these get bounced to /portal/restore by require_auth_redirect.
     let active_only_routes = Router::new()
         .route("/dashboard", get(dashboard::member_dashboard))
+        .route("/me", get(me::me_page))
         .route("/events", get(events::events_page))
+        .route(
+            "/events/propose",
+            get(events::propose_event_page).post(events::propose_event),
+        )
+        .route(
+            "/events/:id/edit",
+            get(event_hosts::event_edit_page).post(event_hosts::update_event),
+        )
+        .route("/events/:id/co-hosts", post(event_hosts::add_co_host))
+        .route(
+            "/events/:id/co-hosts/:member_id/remove",
+            post(event_hosts::remove_co_host),
+        )
         .route("/announcements", get(announcements::announcements_page))
+        .route(
+            "/announcements/:id",
+            get(announcements::announcement_detail_page),
+        )
+        .route(
+            "/announcements/:id/comments",
+            post(announcements::post_comment),
+        )
         .route("/payments", get(payments::views::payments_page))
         .route("/donate", get(donations::donate_page))
+        .route("/directory", get(directory::directory_page))
+        .route("/directory/:id", get(directory::directory_member_page))
         .route("/profile", get(profile::profile_page))
         .route("/profile", post(profile::update_profile))
+        .route("/profile/privacy", post(profile::update_privacy))
+        .route("/profile/celebrations", post(profile::update_celebrations))
+        .route(
+            "/profile/emergency-contact",
+            post(profile::update_emergency_contact),
+        )
+        .route("/profile/event-reminders", post(profile::update_event_reminders))
+        .route(
+            "/profile/admin-notifications",
+            post(profile::update_admin_notifications),
+        )
+        .route("/profile/landing-page", post(profile::update_landing_page))
         .route("/profile/password", post(profile::update_password))
         .route("/profile/security", get(security::security_page))
+        .route("/profile/sessions", get(security::sessions_page))
+        .route(
+            "/profile/sessions/:id/revoke",
+            post(security::revoke_session),
+        )
+        .route(
+            "/profile/sessions/revoke-all",
+            post(security::revoke_all_sessions),
+        )
         .route(
             "/profile/security/totp/enroll/start",
             post(security::enroll_start),
@@ -334,6 +460,10 @@ pub fn create_portal_routes(state: AppState) -> Router<AppState> {
         )
         // API endpoints (HTMX fragments) — for Active members only
         .route("/api/events/upcoming", get(dashboard::upcoming_events))
+        .route(
+            "/api/announcements/featured",
+            get(dashboard::featured_announcements),
+        )
         .route("/api/events/list", get(events::events_list_api))
         .route("/api/events/:id/rsvp", post(events::rsvp_event))
         .route("/api/events/:id/cancel", post(events::cancel_rsvp_event))
@@ -368,6 +498,28 @@ pub struct MemberInfo {
     pub dues_paid_until: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// The `DuesStatus` every portal view shows for `member`. The latest
+/// dues payment is only looked up once dues have lapsed, since it
+/// can't change the answer before then.
+pub async fn dues_status_for(
+    member: &crate::domain::Member,
+    payment_repo: &dyn crate::repository::PaymentRepository,
+    now: chrono::DateTime<chrono::Utc>,
+    grace_period_days: i64,
+) -> crate::domain::DuesStatus {
+    use crate::domain::DuesStatus;
+
+    let status = DuesStatus::from_member(member, now, grace_period_days);
+    if !status.is_lapsed() {
+        return status;
+    }
+    let latest = payment_repo
+        .latest_payment(member.id)
+        .await
+        .unwrap_or_default();
+    DuesStatus::compute(member, latest.as_ref(), now, grace_period_days)
+}
+
 pub fn is_admin(member: &crate::domain::Member) -> bool {
     member.is_admin
 }
@@ -7,6 +7,8 @@
 use askama::Template;
 use axum::response::Html;
 
+use crate::domain::{DuesStatus, Locale};
+
 // --------------------------------------------------------------------
 // Member's own payment history
 // --------------------------------------------------------------------
@@ -32,14 +34,8 @@ pub fn member_payment_list(rows: Vec<MemberPaymentRow>) -> Html<String> {
     }))
 }
 
-pub fn member_payment_row_from(payment: &crate::domain::Payment) -> MemberPaymentRow {
-    use crate::domain::PaymentStatus;
-    let status = match payment.status {
-        PaymentStatus::Completed => "Completed",
-        PaymentStatus::Pending => "Pending",
-        PaymentStatus::Failed => "Failed",
-        PaymentStatus::Refunded => "Refunded",
-    };
+pub fn member_payment_row_from(payment: &crate::domain::Payment, locale: Locale) -> MemberPaymentRow {
+    let status = payment.status.as_str();
 
     let description = if payment.description.is_empty() {
         "Membership dues".to_string()
@@ -49,8 +45,8 @@ pub fn member_payment_row_from(payment: &crate::domain::Payment) -> MemberPaymen
 
     MemberPaymentRow {
         description,
-        date: payment.created_at.format("%B %d, %Y").to_string(),
-        amount: format!("{:.2}", payment.amount_cents as f64 / 100.0),
+        date: locale.long_date(&payment.created_at),
+        amount: payment.amount().to_decimal_string(),
         status,
     }
 }
@@ -98,12 +94,11 @@ pub fn saved_card_row_from(card: &crate::domain::SavedCard) -> SavedCardRow {
 #[derive(Template)]
 #[template(path = "portal/_dues_status_pill.html")]
 pub struct DuesStatusPillTemplate {
-    /// `"current" | "expired" | "unpaid"`.
-    pub status: &'static str,
+    pub dues_status: DuesStatus,
 }
 
-pub fn dues_status_pill(status: &'static str) -> Html<String> {
-    let tmpl = DuesStatusPillTemplate { status };
+pub fn dues_status_pill(dues_status: DuesStatus) -> Html<String> {
+    let tmpl = DuesStatusPillTemplate { dues_status };
     Html(tmpl.render().unwrap_or_else(|e| {
         tracing::error!("dues_status_pill template render failed: {}", e);
         format!("<span class=\"text-yellow-600\">Unpaid</span>")
@@ -68,7 +68,7 @@ pub async fn checkout_api(
         )));
     }
 
-    let amount_cents = membership_type.fee_cents as i64;
+    let amount_cents = membership_type.fee_cents;
 
     let (checkout_url, payment_id) = stripe_client
         .create_membership_checkout_session(
@@ -151,7 +151,7 @@ pub async fn charge_saved_card_api(
         return Err(AppError::Forbidden);
     }
 
-    let amount_cents = membership_type.fee_cents as i64;
+    let amount_cents = membership_type.fee_cents;
     let description = format!("{} Membership Payment", membership_type.name);
 
     // Idempotency key: use the one from the form if present (stable across
@@ -180,6 +180,7 @@ pub async fn charge_saved_card_api(
         description: description.clone(),
         kind: crate::domain::PaymentKind::Membership,
         paid_at: None,
+        refunded_at: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
@@ -196,20 +197,26 @@ pub async fn charge_saved_card_api(
             &description,
             &idempotency_key,
             payment_id,
+            Some(&membership_type.slug),
         )
         .await
     {
         Ok(id) => id,
         Err(e) => {
-            let _ = payment_repo.fail_pending_payment(payment_id).await;
+            let _ = billing_service
+                .auto_renew
+                .fail_pending_payment(payment_id)
+                .await;
             return Err(e);
         }
     };
 
     // Race-free flip. If we win (won_flip=true), do the post-work.
     // If the webhook beat us, it already did the post-work and we
-    // return success without duplicating dues extension.
-    let won_flip = payment_repo
+    // return success without duplicating dues extension. Either way
+    // the winner has told integrations the payment completed.
+    let won_flip = billing_service
+        .auto_renew
         .complete_pending_payment(payment_id, &stripe_payment_id)
         .await?;
     if !won_flip {
@@ -222,10 +229,6 @@ pub async fn charge_saved_card_api(
         ));
     }
 
-    // Both the dues-extension and the auto-renew branch below run
-    // through the shared BillingService.
-    let billing_service = billing_service.as_ref();
-
     // Extend dues first so the new dues_paid_until is what auto-renew
     // schedules off of. This is the source of truth for "when does the
     // next renewal fire" — if scheduling reads the old date, the queued
@@ -100,11 +100,11 @@ pub async fn payment_new_page(
         .unwrap_or_default()
         .into_iter()
         .map(|mt| MembershipTypeDisplay {
+            fee_display: mt.fee().to_decimal_string(),
             name: mt.name,
             slug: mt.slug,
             description: mt.description,
             color: mt.color,
-            fee_display: format!("{:.2}", mt.fee_cents as f64 / 100.0),
             billing_period: mt.billing_period,
         })
         .collect();
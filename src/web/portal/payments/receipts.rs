@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
 use askama::Template;
-use axum::{extract::State, response::IntoResponse, Extension};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension,
+};
 
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
+    domain::{Money, Payment, PaymentKind, PaymentStatus},
     error::AppError,
     repository::{DonationCampaignRepository, PaymentRepository},
     service::settings_service::SettingsService,
@@ -16,9 +22,11 @@ use crate::{
 // Member-facing receipts
 // =============================================================================
 //
-// Two surfaces:
-//   /portal/payments/receipts          — yearly aggregation page
-//   /portal/payments/:id/receipt       — printable single-payment receipt
+// Four surfaces:
+//   /portal/payments/receipts                  — yearly aggregation page
+//   /portal/payments/summary/:year             — one year's giving summary
+//   /portal/payments/summary/:year/statement   — that year as a CSV download
+//   /portal/payments/:id/receipt               — printable single-payment receipt
 //
 // Both are member-self-service: a member can only see their own
 // payments. Admins see member receipts via the admin payment views.
@@ -52,6 +60,18 @@ pub struct ReceiptLineDisplay {
     pub amount_display: String,
 }
 
+#[derive(Template)]
+#[template(path = "portal/payment_summary.html")]
+pub struct YearSummaryTemplate {
+    pub base: BaseContext,
+    pub member_full_name: String,
+    pub year: i32,
+    pub total_display: String,
+    pub dues_total_display: String,
+    pub donations_total_display: String,
+    pub items: Vec<ReceiptLineDisplay>,
+}
+
 #[derive(Template)]
 #[template(path = "portal/receipt.html")]
 pub struct ReceiptTemplate {
@@ -87,7 +107,6 @@ pub async fn receipts_page(
     Extension(current_user): Extension<CurrentUser>,
     Extension(session): Extension<SessionInfo>,
 ) -> Result<axum::response::Response, AppError> {
-    use crate::domain::{PaymentKind, PaymentStatus};
     use std::collections::BTreeMap;
 
     let payments = payment_repo.find_by_member(current_user.member.id).await?;
@@ -102,9 +121,7 @@ pub async fn receipts_page(
         if p.status != PaymentStatus::Completed {
             continue;
         }
-        let when = p.paid_at.unwrap_or(p.created_at);
-        let year = when.format("%Y").to_string().parse::<i32>().unwrap_or(0);
-        by_year.entry(year).or_default().push(p);
+        by_year.entry(receipt_year(&p)).or_default().push(p);
     }
 
     let mut years: Vec<ReceiptYearDisplay> = by_year
@@ -115,27 +132,12 @@ pub async fn receipts_page(
             let mut lines: Vec<ReceiptLineDisplay> = items
                 .into_iter()
                 .map(|p| {
-                    let kind_label = match p.kind {
-                        PaymentKind::Membership => {
-                            dues_cents += p.amount_cents;
-                            "Dues"
-                        }
-                        PaymentKind::Donation { .. } => {
-                            donations_cents += p.amount_cents;
-                            "Donation"
-                        }
-                        PaymentKind::Other => "Other",
-                    }
-                    .to_string();
-
-                    let when = p.paid_at.unwrap_or(p.created_at);
-                    ReceiptLineDisplay {
-                        payment_id: p.id.to_string(),
-                        date: when.format("%Y-%m-%d").to_string(),
-                        description: p.description.clone(),
-                        kind_label,
-                        amount_display: format!("${:.2}", p.amount_cents as f64 / 100.0),
+                    match p.kind {
+                        PaymentKind::Membership => dues_cents += p.amount_cents,
+                        PaymentKind::Donation { .. } => donations_cents += p.amount_cents,
+                        PaymentKind::Other => {}
                     }
+                    receipt_line(&p, &current_user)
                 })
                 .collect();
 
@@ -144,8 +146,8 @@ pub async fn receipts_page(
 
             ReceiptYearDisplay {
                 year,
-                dues_total_display: format!("${:.2}", dues_cents as f64 / 100.0),
-                donations_total_display: format!("${:.2}", donations_cents as f64 / 100.0),
+                dues_total_display: current_user.locale.currency(dues_cents, "USD"),
+                donations_total_display: current_user.locale.currency(donations_cents, "USD"),
                 items: lines,
             }
         })
@@ -162,6 +164,157 @@ pub async fn receipts_page(
     Ok(HtmlTemplate(template).into_response())
 }
 
+/// One year's giving summary: the year's total (and its dues /
+/// donations split) over the member's Completed payments, each line
+/// linking to its printable receipt. A year with no payments renders
+/// an empty state rather than a 404 — "you paid nothing in 2019" is a
+/// valid answer.
+pub async fn year_summary_page(
+    State(csrf_service): State<Arc<CsrfService>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session): Extension<SessionInfo>,
+    Path(year): Path<i32>,
+) -> Result<axum::response::Response, AppError> {
+    let payments = completed_in_year(
+        payment_repo.find_by_member(current_user.member.id).await?,
+        year,
+    );
+    let totals = YearTotals::of(&payments);
+
+    let template = YearSummaryTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
+        member_full_name: current_user.member.full_name.clone(),
+        year,
+        total_display: current_user.locale.currency(totals.total_cents, "USD"),
+        dues_total_display: current_user.locale.currency(totals.dues_cents, "USD"),
+        donations_total_display: current_user.locale.currency(totals.donations_cents, "USD"),
+        items: payments
+            .iter()
+            .map(|p| receipt_line(p, &current_user))
+            .collect(),
+    };
+    Ok(HtmlTemplate(template).into_response())
+}
+
+/// The same year as a CSV statement: one row per Completed payment,
+/// oldest first, then a closing total row. Amounts are plain decimals
+/// (no symbol, no grouping) so spreadsheets read them as numbers. An
+/// empty year still downloads — header plus a zero total.
+pub async fn year_statement(
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Path(year): Path<i32>,
+) -> Result<axum::response::Response, AppError> {
+    use crate::web::portal::admin::csv::push_csv;
+
+    let payments = completed_in_year(
+        payment_repo.find_by_member(current_user.member.id).await?,
+        year,
+    );
+    let totals = YearTotals::of(&payments);
+
+    let mut out = String::from("date,description,type,amount,currency,receipt_id\n");
+    for p in &payments {
+        let line = receipt_line(p, &current_user);
+        push_csv(&mut out, &line.date);
+        out.push(',');
+        push_csv(&mut out, &line.description);
+        out.push(',');
+        push_csv(&mut out, &line.kind_label);
+        out.push(',');
+        push_csv(&mut out, &p.amount().to_decimal_string());
+        out.push(',');
+        push_csv(&mut out, &p.currency);
+        out.push(',');
+        push_csv(&mut out, &line.payment_id);
+        out.push('\n');
+    }
+    push_csv(&mut out, "");
+    out.push(',');
+    push_csv(&mut out, &format!("Total for {}", year));
+    out.push_str(",,");
+    push_csv(&mut out, &Money::usd(totals.total_cents).to_decimal_string());
+    out.push_str(",\"USD\",\n");
+
+    let filename = format!("payments-{}.csv", year);
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        out,
+    )
+        .into_response())
+}
+
+/// Calendar year a payment's receipt is dated in: `paid_at`, falling
+/// back to `created_at` for old manual records that lack it.
+fn receipt_year(p: &Payment) -> i32 {
+    use chrono::Datelike;
+    p.paid_at.unwrap_or(p.created_at).year()
+}
+
+/// The member's Completed payments dated in `year`, oldest first.
+/// Same exclusions as the receipts page: pending, failed and refunded
+/// payments have no receipt.
+fn completed_in_year(payments: Vec<Payment>, year: i32) -> Vec<Payment> {
+    let mut kept: Vec<Payment> = payments
+        .into_iter()
+        .filter(|p| p.status == PaymentStatus::Completed && receipt_year(p) == year)
+        .collect();
+    kept.sort_by_key(|p| p.paid_at.unwrap_or(p.created_at));
+    kept
+}
+
+struct YearTotals {
+    total_cents: i64,
+    dues_cents: i64,
+    donations_cents: i64,
+}
+
+impl YearTotals {
+    fn of(payments: &[Payment]) -> Self {
+        let mut totals = YearTotals {
+            total_cents: 0,
+            dues_cents: 0,
+            donations_cents: 0,
+        };
+        for p in payments {
+            totals.total_cents += p.amount_cents;
+            match p.kind {
+                PaymentKind::Membership => totals.dues_cents += p.amount_cents,
+                PaymentKind::Donation { .. } => totals.donations_cents += p.amount_cents,
+                PaymentKind::Other => {}
+            }
+        }
+        totals
+    }
+}
+
+fn receipt_line(p: &Payment, current_user: &CurrentUser) -> ReceiptLineDisplay {
+    let kind_label = match p.kind {
+        PaymentKind::Membership => "Dues",
+        PaymentKind::Donation { .. } => "Donation",
+        PaymentKind::Other => "Other",
+    };
+    ReceiptLineDisplay {
+        payment_id: p.id.to_string(),
+        date: p
+            .paid_at
+            .unwrap_or(p.created_at)
+            .format("%Y-%m-%d")
+            .to_string(),
+        description: p.description.clone(),
+        kind_label: kind_label.to_string(),
+        amount_display: current_user.locale.currency(p.amount_cents, &p.currency),
+    }
+}
+
 /// Printable single-payment receipt. Standalone HTML (no portal nav),
 /// styled for both screen and print. Member can only see their own
 /// receipts; refunded / pending / failed payments return 404 (no
@@ -173,7 +326,7 @@ pub async fn receipt_page(
     Extension(current_user): Extension<CurrentUser>,
     axum::extract::Path(payment_id): axum::extract::Path<uuid::Uuid>,
 ) -> Result<axum::response::Response, AppError> {
-    use crate::domain::{PaymentKind, PaymentMethod, PaymentStatus};
+    use crate::domain::PaymentMethod;
 
     let payment = payment_repo
         .find_by_id(payment_id)
@@ -256,13 +409,13 @@ pub async fn receipt_page(
         payment_id: payment.id.to_string(),
         recipient_name: current_user.member.full_name.clone(),
         recipient_email: current_user.member.email.clone(),
-        date: when.format("%B %-d, %Y").to_string(),
-        amount_display: format!("${:.2}", payment.amount_cents as f64 / 100.0),
+        date: current_user.locale.long_date(&when),
+        amount_display: current_user.locale.currency(payment.amount_cents, &payment.currency),
         kind_label,
         description: payment.description.clone(),
         campaign,
         payment_method_label,
-        generated_on: chrono::Utc::now().format("%B %-d, %Y").to_string(),
+        generated_on: current_user.locale.long_date(&chrono::Utc::now()),
     };
     Ok(HtmlTemplate(template).into_response())
 }
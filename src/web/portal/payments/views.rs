@@ -7,6 +7,7 @@ use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
     repository::PaymentRepository,
+    service::settings_service::SettingsService,
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -40,7 +41,7 @@ pub async fn payments_list_api(
 
     let rows = payments
         .iter()
-        .map(crate::web::portal::partials::member_payment_row_from)
+        .map(|p| crate::web::portal::partials::member_payment_row_from(p, current_user.locale))
         .collect();
     crate::web::portal::partials::member_payment_list(rows)
 }
@@ -63,27 +64,29 @@ pub async fn payments_summary_api(
         .map(|p| p.amount_cents)
         .sum();
 
-    axum::response::Html(format!("${:.2}", total as f64 / 100.0))
+    axum::response::Html(current_user.locale.currency(total, "USD"))
 }
 
 // API endpoint for dues status
-pub async fn dues_status_api(Extension(current_user): Extension<CurrentUser>) -> impl IntoResponse {
-    let status: &'static str = if let Some(dues_until) = current_user.member.dues_paid_until {
-        if dues_until > chrono::Utc::now() {
-            "current"
-        } else {
-            "expired"
-        }
-    } else {
-        "unpaid"
-    };
+pub async fn dues_status_api(
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> impl IntoResponse {
+    let status = crate::web::portal::dues_status_for(
+        &current_user.member,
+        payment_repo.as_ref(),
+        chrono::Utc::now(),
+        settings_service.grace_period_days().await,
+    )
+    .await;
     crate::web::portal::partials::dues_status_pill(status)
 }
 
 // API endpoint for next due date
 pub async fn next_due_api(Extension(current_user): Extension<CurrentUser>) -> impl IntoResponse {
     let next_due = if let Some(dues_until) = current_user.member.dues_paid_until {
-        dues_until.format("%B %d, %Y").to_string()
+        current_user.locale.long_date(&dues_until)
     } else {
         "—".to_string()
     };
@@ -1,16 +1,30 @@
 use std::sync::Arc;
 
 use askama::Template;
-use axum::{extract::State, response::IntoResponse, Extension};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension,
+};
 use serde::Deserialize;
 use sqlx::SqlitePool;
 
-use super::MemberInfo;
+use super::{dues_status_for, MemberInfo};
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
     auth::CsrfService,
-    repository::MemberRepository,
-    service::membership_type_service::MembershipTypeService,
+    domain::{AdminNotificationChannel, DuesStatus, LandingPage, Locale, MonthDay},
+    repository::{EventRepository, MemberAttendanceRow, MemberRepository, PaymentRepository},
+    service::{
+        admin_notification_service::AdminNotificationService,
+        celebration_service::{CelebrationPreferences, CelebrationService},
+        directory_service::{DirectoryPrivacy, DirectoryService},
+        emergency_contact_service::{EmergencyContact, EmergencyContactService},
+        landing_page_service::LandingPageService,
+        membership_type_service::MembershipTypeService,
+        settings_service::SettingsService,
+    },
     web::templates::{filters, BaseContext, HtmlTemplate},
 };
 
@@ -19,10 +33,87 @@ use crate::{
 pub struct ProfileTemplate {
     pub base: BaseContext,
     pub member: MemberInfo,
+    pub dues_status: DuesStatus,
+    pub locale_options: Vec<LocaleOption>,
+    /// The member's saved override tag, or "" for the org default.
+    pub member_locale: String,
+    pub org_locale_label: String,
+    pub privacy: DirectoryPrivacy,
+    pub birthday_months: Vec<MonthOption>,
+    /// Day of the saved birthday, or "" when none is set.
+    pub birthday_day: String,
+    pub celebrate: bool,
+    /// Emergency contact and medical notes; only the member and admins
+    /// see these.
+    pub emergency: EmergencyContact,
+    /// Whether the member gets emailed before events they RSVP'd to.
+    pub event_reminders: bool,
+    /// Admins only: how admin notifications reach them.
+    pub admin_channel: AdminNotificationChannel,
+    pub admin_channel_options: [AdminNotificationChannel; 3],
+    /// The member's saved start page, or "" for the club default.
+    pub landing_page: String,
+    pub default_landing_label: String,
+    /// Start pages this member may pick (admin pages for admins only).
+    pub landing_page_options: Vec<LandingPage>,
+    /// Events the member has RSVP'd to, most recent first, without
+    /// cancelled RSVPs.
+    pub event_history: Vec<MemberAttendanceRow>,
+}
+
+pub struct LocaleOption {
+    pub tag: String,
+    pub label: String,
+}
+
+impl LocaleOption {
+    pub fn all() -> Vec<Self> {
+        Locale::ALL
+            .iter()
+            .map(|l| LocaleOption {
+                tag: l.tag().to_string(),
+                label: l.label().to_string(),
+            })
+            .collect()
+    }
+}
+
+pub struct MonthOption {
+    pub value: u32,
+    pub label: &'static str,
+    pub selected: bool,
+}
+
+impl MonthOption {
+    const LABELS: [&'static str; 12] = [
+        "January", "February", "March", "April", "May", "June", "July", "August",
+        "September", "October", "November", "December",
+    ];
+
+    /// The twelve months, with `birthday`'s month selected.
+    pub fn all(birthday: Option<MonthDay>) -> Vec<Self> {
+        (1..=12)
+            .zip(Self::LABELS)
+            .map(|(value, label)| MonthOption {
+                value,
+                label,
+                selected: birthday.is_some_and(|b| b.month == value),
+            })
+            .collect()
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn profile_page(
     State(membership_type_service): State<Arc<MembershipTypeService>>,
+    State(payment_repo): State<Arc<dyn PaymentRepository>>,
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(directory_service): State<Arc<DirectoryService>>,
+    State(admin_notification_service): State<Arc<AdminNotificationService>>,
+    State(celebration_service): State<Arc<CelebrationService>>,
+    State(emergency_contact_service): State<Arc<EmergencyContactService>>,
+    State(landing_page_service): State<Arc<LandingPageService>>,
     State(csrf_service): State<Arc<CsrfService>>,
     Extension(current_user): Extension<CurrentUser>,
     Extension(session_info): Extension<SessionInfo>,
@@ -46,9 +137,76 @@ pub async fn profile_page(
         dues_paid_until: current_user.member.dues_paid_until,
     };
 
+    let member_locale = current_user
+        .member
+        .locale
+        .as_deref()
+        .and_then(Locale::from_tag)
+        .map(|l| l.tag().to_string())
+        .unwrap_or_default();
+
+    let celebrations = celebration_service
+        .preferences(current_user.member.id)
+        .await
+        .unwrap_or_default();
+
     let template = ProfileTemplate {
         base: BaseContext::for_member(&csrf_service, &current_user, &session_info).await,
         member: member_info,
+        dues_status: dues_status_for(
+            &current_user.member,
+            payment_repo.as_ref(),
+            chrono::Utc::now(),
+            settings_service.grace_period_days().await,
+        )
+        .await,
+        locale_options: LocaleOption::all(),
+        member_locale,
+        org_locale_label: settings_service.org_locale().await.label().to_string(),
+        privacy: directory_service
+            .privacy(current_user.member.id)
+            .await
+            .unwrap_or_default(),
+        birthday_months: MonthOption::all(celebrations.birthday),
+        birthday_day: celebrations
+            .birthday
+            .map(|b| b.day.to_string())
+            .unwrap_or_default(),
+        celebrate: celebrations.celebrate,
+        emergency: emergency_contact_service
+            .get(current_user.member.id)
+            .await
+            .unwrap_or_default(),
+        event_reminders: event_repo
+            .reminders_enabled(current_user.member.id)
+            .await
+            .unwrap_or(true),
+        admin_channel: admin_notification_service
+            .channel(current_user.member.id)
+            .await
+            .unwrap_or_default(),
+        admin_channel_options: AdminNotificationChannel::ALL,
+        landing_page: landing_page_service
+            .preference(current_user.member.id)
+            .await
+            .ok()
+            .flatten()
+            .map(|p| p.as_str().to_string())
+            .unwrap_or_default(),
+        default_landing_label: settings_service
+            .default_landing_page(current_user.member.is_admin)
+            .await
+            .permitted_for(current_user.member.is_admin)
+            .label()
+            .to_string(),
+        landing_page_options: LandingPage::ALL
+            .into_iter()
+            .filter(|p| current_user.member.is_admin || !p.admin_only())
+            .collect(),
+        event_history: event_repo
+            .list_member_attendance(current_user.member.id, false)
+            .await
+            .unwrap_or_default(),
     };
 
     HtmlTemplate(template)
@@ -57,6 +215,9 @@ pub async fn profile_page(
 #[derive(Debug, Deserialize)]
 pub struct UpdateProfileRequest {
     pub full_name: String,
+    /// Locale tag, or "" to follow the org default.
+    #[serde(default)]
+    pub locale: String,
     #[allow(dead_code)]
     pub csrf_token: String,
 }
@@ -68,12 +229,32 @@ pub async fn update_profile(
 ) -> axum::response::Response {
     use crate::domain::UpdateMemberRequest;
 
+    let locale = if form.locale.is_empty() {
+        None
+    } else {
+        match Locale::from_tag(&form.locale) {
+            Some(l) => Some(l.tag()),
+            None => {
+                return axum::response::Html(
+                    "<div class=\"p-4 bg-red-50 text-red-800 rounded-md\">Unknown date &amp; number format.</div>"
+                        .to_string(),
+                )
+                .into_response();
+            }
+        }
+    };
+
     let update = UpdateMemberRequest {
         full_name: Some(form.full_name.clone()),
         ..Default::default()
     };
 
-    match member_repo.update(current_user.member.id, update).await {
+    let result = match member_repo.update(current_user.member.id, update).await {
+        Ok(_) => member_repo.update_locale(current_user.member.id, locale).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
         Ok(_) => {
             // Redirect back to profile with success message
             axum::response::Response::builder()
@@ -96,6 +277,267 @@ pub async fn update_profile(
     }
 }
 
+/// Directory opt-in and contact visibility. Unchecked boxes are simply
+/// absent from the form.
+#[derive(Debug, Deserialize)]
+pub struct UpdatePrivacyRequest {
+    pub show_in_directory: Option<String>,
+    pub show_contact: Option<String>,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+pub async fn update_privacy(
+    State(directory_service): State<Arc<DirectoryService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdatePrivacyRequest>,
+) -> impl IntoResponse {
+    let privacy = DirectoryPrivacy {
+        show_in_directory: form.show_in_directory.is_some(),
+        show_contact: form.show_contact.is_some(),
+    };
+    match directory_service
+        .set_privacy(current_user.member.id, privacy)
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Directory settings saved
+            </div>"#
+                .to_string(),
+        ),
+        Err(e) => axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">Failed to save directory settings: {}</div>"#,
+            crate::web::escape_html(&e.to_string())
+        )),
+    }
+}
+
+/// Birthday (month and day; both empty clears it) and the shout-out
+/// opt-in. An unchecked box is absent from the form.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCelebrationsRequest {
+    #[serde(default)]
+    pub birthday_month: String,
+    #[serde(default)]
+    pub birthday_day: String,
+    pub celebrate: Option<String>,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+pub async fn update_celebrations(
+    State(celebration_service): State<Arc<CelebrationService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdateCelebrationsRequest>,
+) -> impl IntoResponse {
+    let error = |msg: &str| {
+        axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">{}</div>"#,
+            crate::web::escape_html(msg)
+        ))
+    };
+    let (month, day) = (form.birthday_month.trim(), form.birthday_day.trim());
+    let birthday = if month.is_empty() && day.is_empty() {
+        None
+    } else {
+        let parsed = month
+            .parse()
+            .ok()
+            .zip(day.parse().ok())
+            .and_then(|(m, d)| MonthDay::new(m, d));
+        match parsed {
+            Some(b) => Some(b),
+            None => return error("That isn't a valid birthday"),
+        }
+    };
+    let prefs = CelebrationPreferences {
+        birthday,
+        celebrate: form.celebrate.is_some(),
+    };
+    match celebration_service
+        .set_preferences(current_user.member.id, prefs)
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Celebration settings saved
+            </div>"#
+                .to_string(),
+        ),
+        Err(e) => error(&format!("Failed to save celebration settings: {}", e)),
+    }
+}
+
+/// Emergency contact and medical notes. All fields empty clears them.
+#[derive(Debug, Deserialize)]
+pub struct UpdateEmergencyContactRequest {
+    #[serde(default)]
+    pub contact_name: String,
+    #[serde(default)]
+    pub contact_phone: String,
+    #[serde(default)]
+    pub contact_relationship: String,
+    #[serde(default)]
+    pub medical_notes: String,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+pub async fn update_emergency_contact(
+    State(emergency_contact_service): State<Arc<EmergencyContactService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdateEmergencyContactRequest>,
+) -> impl IntoResponse {
+    let contact = EmergencyContact {
+        name: form.contact_name,
+        phone: form.contact_phone,
+        relationship: form.contact_relationship,
+        medical_notes: form.medical_notes,
+    };
+    match emergency_contact_service
+        .set(current_user.member.id, contact)
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Emergency details saved
+            </div>"#
+                .to_string(),
+        ),
+        Err(crate::error::AppError::BadRequest(msg)) => axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">{}</div>"#,
+            crate::web::escape_html(&msg)
+        )),
+        Err(e) => axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">Failed to save emergency details: {}</div>"#,
+            crate::web::escape_html(&e.to_string())
+        )),
+    }
+}
+
+/// The event-reminder opt-in. An unchecked box is absent from the form.
+#[derive(Debug, Deserialize)]
+pub struct UpdateEventRemindersRequest {
+    pub event_reminders: Option<String>,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+pub async fn update_event_reminders(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdateEventRemindersRequest>,
+) -> impl IntoResponse {
+    match event_repo
+        .set_reminders_enabled(current_user.member.id, form.event_reminders.is_some())
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Reminder settings saved
+            </div>"#
+                .to_string(),
+        ),
+        Err(e) => axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">Failed to save reminder settings: {}</div>"#,
+            crate::web::escape_html(&e.to_string())
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateAdminNotificationsRequest {
+    pub channel: String,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+/// An admin's own delivery channel for admin notifications.
+pub async fn update_admin_notifications(
+    State(admin_notification_service): State<Arc<AdminNotificationService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdateAdminNotificationsRequest>,
+) -> Response {
+    let error = |msg: &str| {
+        axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">{}</div>"#,
+            crate::web::escape_html(msg)
+        ))
+    };
+    if !current_user.member.is_admin {
+        return (
+            StatusCode::FORBIDDEN,
+            error("Only admins receive admin notifications"),
+        )
+            .into_response();
+    }
+    let Ok(channel) = form.channel.parse::<AdminNotificationChannel>() else {
+        return error("Unknown notification channel").into_response();
+    };
+    match admin_notification_service
+        .set_channel(current_user.member.id, channel)
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Notification settings saved
+            </div>"#
+                .to_string(),
+        )
+        .into_response(),
+        Err(e) => error(&format!("Failed to save notification settings: {}", e)).into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLandingPageRequest {
+    /// A landing page, or "" to follow the club default.
+    #[serde(default)]
+    pub landing_page: String,
+    #[allow(dead_code)]
+    pub csrf_token: String,
+}
+
+/// The member's own start page after signing in.
+pub async fn update_landing_page(
+    State(landing_page_service): State<Arc<LandingPageService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    axum::Form(form): axum::Form<UpdateLandingPageRequest>,
+) -> Response {
+    let error = |msg: &str| {
+        axum::response::Html(format!(
+            r#"<div class="p-3 bg-red-50 text-red-800 rounded-md text-sm">{}</div>"#,
+            crate::web::escape_html(msg)
+        ))
+    };
+    let page = if form.landing_page.is_empty() {
+        None
+    } else {
+        match form.landing_page.parse::<LandingPage>() {
+            Ok(page) if page.admin_only() && !current_user.member.is_admin => {
+                return (StatusCode::FORBIDDEN, error("That page is for admins only"))
+                    .into_response();
+            }
+            Ok(page) => Some(page),
+            Err(_) => return error("Unknown start page").into_response(),
+        }
+    };
+    match landing_page_service
+        .set_preference(current_user.member.id, page)
+        .await
+    {
+        Ok(()) => axum::response::Html(
+            r#"<div class="p-3 bg-green-50 text-green-800 rounded-md text-sm">
+                Start page saved
+            </div>"#
+                .to_string(),
+        )
+        .into_response(),
+        Err(e) => error(&format!("Failed to save start page: {}", e)).into_response(),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdatePasswordRequest {
     pub current_password: String,
@@ -38,7 +38,7 @@ pub async fn restore_page(
     let expired_on = current_user
         .member
         .dues_paid_until
-        .map(|d| d.format("%B %d, %Y").to_string());
+        .map(|d| current_user.locale.long_date(&d));
 
     let template = RestoreTemplate {
         base: BaseContext::for_member(&csrf_service, &current_user, &session).await,
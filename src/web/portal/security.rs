@@ -1,5 +1,7 @@
-//! Member-facing 2FA (TOTP) management page. Available to every
-//! logged-in member; admin promotion is independent of TOTP enrollment.
+//! Member-facing 2FA (TOTP) management page, login history and active
+//! sessions.
+//! Available to every logged-in member; admin promotion is independent
+//! of TOTP enrollment.
 //!
 //! The flow is deliberately split into HTMX fragments rather than full
 //! page reloads — the page renders the QR + recovery codes once, into
@@ -11,7 +13,8 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::{Html, IntoResponse, Response},
     Extension,
 };
@@ -21,9 +24,11 @@ use sqlx::SqlitePool;
 use super::{is_admin, MemberInfo};
 use crate::{
     api::middleware::auth::{CurrentUser, SessionInfo},
-    auth::{CsrfService, TotpService},
+    auth::{AuthService, CsrfService, TotpService},
     service::{
-        audit_service::AuditService, membership_type_service::MembershipTypeService,
+        audit_service::AuditService,
+        login_history_service::{describe_user_agent, LoginHistoryService},
+        membership_type_service::MembershipTypeService,
         settings_service::SettingsService,
     },
     web::templates::{BaseContext, HtmlTemplate},
@@ -135,6 +140,205 @@ pub async fn security_page(
     })
 }
 
+// --------------------------------------------------------------------
+// Sessions and login history: GET /portal/profile/sessions
+// The member's live sessions, each revocable, and a read-only list of
+// recent sign-ins so they can spot one that wasn't them. History rows
+// age out per `auth.login_history_retention_days`.
+// --------------------------------------------------------------------
+
+#[derive(Template)]
+#[template(path = "portal/sessions.html")]
+pub struct SessionsTemplate {
+    pub base: BaseContext,
+    pub active_sessions: Vec<ActiveSessionDisplay>,
+    pub logins: Vec<LoginDisplay>,
+    pub retention_days: i64,
+}
+
+pub struct ActiveSessionDisplay {
+    pub id: String,
+    /// First characters of the token hash, enough to tell sessions
+    /// apart without showing anything usable.
+    pub short_id: String,
+    pub started: String,
+    pub last_active: String,
+    pub expires: String,
+    /// The session making this request. Its revoke button is replaced
+    /// by a marker so the member doesn't sign themselves out mid-page.
+    pub current: bool,
+}
+
+pub struct LoginDisplay {
+    pub when: String,
+    pub device: String,
+    pub user_agent: String,
+    pub ip_address: String,
+    pub location: String,
+    pub method: String,
+    pub new_device: bool,
+}
+
+pub async fn sessions_page(
+    State(auth_service): State<Arc<AuthService>>,
+    State(login_history_service): State<Arc<LoginHistoryService>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(csrf_service): State<Arc<CsrfService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+) -> impl IntoResponse {
+    let locale = &current_user.locale;
+    let active_sessions = auth_service
+        .list_sessions(current_user.member.id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| ActiveSessionDisplay {
+            short_id: s.token_hash.chars().take(8).collect(),
+            started: format!("{} UTC", locale.date_time(&s.created_at)),
+            last_active: format!("{} UTC", locale.date_time(&s.last_used_at)),
+            expires: format!("{} UTC", locale.date_time(&s.expires_at)),
+            current: s.id == session_info.session_id,
+            id: s.id,
+        })
+        .collect();
+    let logins = login_history_service
+        .recent_for_member(current_user.member.id, 50)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| LoginDisplay {
+            when: format!("{} UTC", current_user.locale.date_time(&e.created_at)),
+            device: describe_user_agent(e.user_agent.as_deref()),
+            user_agent: e.user_agent.unwrap_or_default(),
+            ip_address: e.ip_address,
+            location: e.location.unwrap_or_default(),
+            method: e.method.label().to_string(),
+            new_device: e.new_device,
+        })
+        .collect();
+    let retention_days = settings_service
+        .get_number("auth.login_history_retention_days")
+        .await
+        .unwrap_or(90);
+
+    HtmlTemplate(SessionsTemplate {
+        base: BaseContext::for_member(&csrf_service, &current_user, &session_info).await,
+        active_sessions,
+        logins,
+        retention_days,
+    })
+}
+
+// --------------------------------------------------------------------
+// Revoke: POST /portal/profile/sessions/:id/revoke
+// Ends one of the member's other sessions. The current session isn't
+// revocable here; "Log out everywhere" or the normal logout covers it.
+// --------------------------------------------------------------------
+
+pub async fn revoke_session(
+    State(auth_service): State<Arc<AuthService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+    Extension(session_info): Extension<SessionInfo>,
+    Path(session_id): Path<String>,
+) -> Response {
+    if session_id == session_info.session_id {
+        return toast_reload(
+            "That's the session you're using now. Use Log out instead.",
+            "error",
+        );
+    }
+    match auth_service
+        .revoke_session(current_user.member.id, &session_id)
+        .await
+    {
+        Ok(true) => {
+            audit_service
+                .log(
+                    Some(current_user.member.id),
+                    "session_revoke",
+                    "session",
+                    &session_id,
+                    None,
+                    None,
+                    None,
+                )
+                .await;
+            toast_reload("Session signed out.", "success")
+        }
+        Ok(false) => toast_reload("That session has already ended.", "info"),
+        Err(e) => {
+            tracing::error!("revoke_session failed: {}", e);
+            toast_reload("Couldn't sign that session out. Please try again.", "error")
+        }
+    }
+}
+
+// --------------------------------------------------------------------
+// Log out everywhere: POST /portal/profile/sessions/revoke-all
+// Ends every session for the member, this one included, and sends the
+// browser to the login page.
+// --------------------------------------------------------------------
+
+pub async fn revoke_all_sessions(
+    State(auth_service): State<Arc<AuthService>>,
+    State(audit_service): State<Arc<AuditService>>,
+    Extension(current_user): Extension<CurrentUser>,
+) -> Response {
+    if let Err(e) = auth_service
+        .invalidate_all_sessions(current_user.member.id)
+        .await
+    {
+        tracing::error!("revoke_all_sessions failed: {}", e);
+        return toast_reload(
+            "Couldn't sign out your sessions. Please try again.",
+            "error",
+        );
+    }
+    audit_service
+        .log(
+            Some(current_user.member.id),
+            "session_revoke_all",
+            "member",
+            &current_user.member.id.to_string(),
+            None,
+            None,
+            None,
+        )
+        .await;
+
+    (
+        StatusCode::OK,
+        [
+            (
+                header::SET_COOKIE,
+                AuthService::create_logout_cookie().to_string(),
+            ),
+            (
+                header::HeaderName::from_static("hx-redirect"),
+                "/login".to_string(),
+            ),
+        ],
+    )
+        .into_response()
+}
+
+/// Reload the sessions page with a toast so the list reflects the change.
+fn toast_reload(message: &str, kind: &str) -> Response {
+    (
+        StatusCode::OK,
+        [
+            ("HX-Redirect", "/portal/profile/sessions".to_string()),
+            (
+                "X-Toast",
+                serde_json::json!({ "message": message, "type": kind }).to_string(),
+            ),
+        ],
+    )
+        .into_response()
+}
+
 // --------------------------------------------------------------------
 // Enroll: POST /portal/profile/security/totp/enroll/start
 // Returns the QR + a confirmation form. No persistence yet — the
@@ -142,6 +346,11 @@ pub async fn security_page(
 // valid code is entered.
 // --------------------------------------------------------------------
 
+/// The confirm form's CSRF token is bound to this path: the fragment
+/// carries the TOTP secret in a hidden field, so its token shouldn't
+/// double as a pass for any other endpoint.
+const ENROLL_CONFIRM_PATH: &str = "/portal/profile/security/totp/enroll/confirm";
+
 pub async fn enroll_start(
     State(totp_service): State<Arc<TotpService>>,
     State(csrf_service): State<Arc<CsrfService>>,
@@ -180,7 +389,7 @@ pub async fn enroll_start(
     };
 
     let csrf_token = csrf_service
-        .generate_token(&session_info.session_id)
+        .generate_token_for_action(&session_info.session_id, ENROLL_CONFIRM_PATH)
         .await
         .unwrap_or_else(|_| String::new());
 
@@ -233,7 +442,7 @@ pub async fn enroll_confirm(
     };
 
     let csrf_token = csrf_service
-        .generate_token(&session_info.session_id)
+        .generate_token_for_action(&session_info.session_id, ENROLL_CONFIRM_PATH)
         .await
         .unwrap_or_else(|_| String::new());
 
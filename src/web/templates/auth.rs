@@ -12,11 +12,16 @@ use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
 
 use crate::{
-    api::state::LoginLimiter,
+    api::state::{FailedLoginTracker, LoginLimiter},
     auth::{AuthService, CsrfService, PendingLoginService, TotpService},
     config::Settings,
     repository::MemberRepository,
-    service::audit_service::AuditService,
+    service::{
+        audit_service::AuditService,
+        contact_service::ContactService,
+        landing_page_service::LandingPageService,
+        login_history_service::{LoginHistoryService, LoginMethod},
+    },
     web::templates::{BaseContext, HtmlTemplate},
 };
 
@@ -25,6 +30,8 @@ use crate::{
 pub struct LoginTemplate {
     pub base: BaseContext,
     pub redirect_url: Option<String>,
+    /// Show the "Contact the admins" link under the form.
+    pub contact_enabled: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -51,6 +58,7 @@ pub struct LoginResponse {
 pub async fn login_page(
     State(auth_service): State<Arc<AuthService>>,
     State(member_repo): State<Arc<dyn MemberRepository>>,
+    State(contact_service): State<Arc<ContactService>>,
     jar: CookieJar,
     Query(query): Query<LoginQuery>,
 ) -> Response {
@@ -80,6 +88,7 @@ pub async fn login_page(
     let template = LoginTemplate {
         base: BaseContext::for_anon(),
         redirect_url: query.redirect,
+        contact_enabled: contact_service.enabled().await,
     };
     HtmlTemplate(template).into_response()
 }
@@ -94,11 +103,14 @@ pub async fn login_page(
 pub async fn login_handler(
     State(settings): State<Arc<Settings>>,
     State(login_limiter): State<LoginLimiter>,
+    State(login_failures): State<FailedLoginTracker>,
     State(member_repo): State<Arc<dyn MemberRepository>>,
     State(db_pool): State<SqlitePool>,
     State(auth_service): State<Arc<AuthService>>,
     State(totp_service): State<Arc<TotpService>>,
     State(pending_login_service): State<Arc<PendingLoginService>>,
+    State(login_history_service): State<Arc<LoginHistoryService>>,
+    State(landing_page_service): State<Arc<LandingPageService>>,
     headers: HeaderMap,
     Json(credentials): Json<LoginRequest>,
 ) -> Response {
@@ -132,6 +144,23 @@ pub async fn login_handler(
         member
     };
 
+    // Per-account lockout after repeated failures. Keyed on the member's
+    // email when the identifier resolves, so username and email share a
+    // count; unknown identifiers are counted as typed and lock out the
+    // same way. Checked before any password work, with a message that
+    // doesn't depend on whether the account exists.
+    let account_key = member
+        .as_ref()
+        .map(|m| m.email.clone())
+        .unwrap_or_else(|| credentials.username.clone());
+    if login_failures.is_locked(&account_key, ip) {
+        return (StatusCode::TOO_MANY_REQUESTS, Json(LoginResponse {
+            success: false,
+            redirect: None,
+            error: Some("Too many failed login attempts. Please try again later.".to_string()),
+        })).into_response();
+    }
+
     if let Some(member) = member {
         // Get password hash from database
         let password_hash = crate::auth::get_password_hash(
@@ -150,6 +179,8 @@ pub async fn login_handler(
         };
 
         if password_valid {
+            login_failures.reset(&account_key, ip);
+
             // Reject login for Pending/Suspended — they shouldn't have a
             // portal session at all. Expired members are allowed in so they
             // can reach the restoration flow and update payment.
@@ -249,16 +280,19 @@ pub async fn login_handler(
             let (_session, token) = auth_service
                 .create_session(
                     member.id,
-                    if credentials.remember_me.unwrap_or(false) { 24 * 30 } else { 24 }
+                    if credentials.remember_me.unwrap_or(false) { 24 * 30 } else { settings.auth.session_duration_hours }
                 )
                 .await
                 .unwrap();
+            login_history_service
+                .record_login(&member, ip, crate::api::state::user_agent(&headers), LoginMethod::Password)
+                .await;
             // Create session cookie. Secure flag is driven by server config
             // so local http dev still works while TLS deployments get it set.
             let max_age_secs = if credentials.remember_me.unwrap_or(false) {
                 60 * 60 * 24 * 30 // 30 days
             } else {
-                60 * 60 * 24 // 24 hours
+                60 * 60 * settings.auth.session_duration_hours
             };
             let secure_attr = if settings.server.cookies_are_secure() {
                 "; Secure"
@@ -272,12 +306,8 @@ pub async fn login_handler(
 
             // Expired members go straight to the restoration flow. Active/
             // Honorary go to the originally-requested URL (if validated) or
-            // the dashboard. Path validation guards against open-redirect.
-            let default_destination = if member.status == MemberStatus::Expired {
-                "/portal/restore".to_string()
-            } else {
-                "/portal/dashboard".to_string()
-            };
+            // their landing page. Path validation guards against open-redirect.
+            let default_destination = landing_page_service.destination(&member).await;
             let redirect_url = credentials.redirect_url
                 .filter(|url| url.starts_with("/portal/") && !url.contains(".."))
                 .unwrap_or(default_destination);
@@ -322,6 +352,7 @@ pub async fn login_handler(
     }
 
     // Invalid credentials
+    login_failures.record_failure(&account_key, ip);
     (StatusCode::UNAUTHORIZED, Json(LoginResponse {
         success: false,
         redirect: None,
@@ -472,6 +503,9 @@ pub async fn login_totp_handler(
     State(member_repo): State<Arc<dyn MemberRepository>>,
     State(totp_service): State<Arc<TotpService>>,
     State(pending_login_service): State<Arc<PendingLoginService>>,
+    State(login_history_service): State<Arc<LoginHistoryService>>,
+    State(landing_page_service): State<Arc<LandingPageService>>,
+    request_headers: HeaderMap,
     jar: CookieJar,
     Json(payload): Json<LoginTotpRequest>,
 ) -> Response {
@@ -572,7 +606,7 @@ pub async fn login_totp_handler(
     let (_session, token) = match auth_service
         .create_session(
             member.id,
-            if pending.remember_me { 24 * 30 } else { 24 },
+            if pending.remember_me { 24 * 30 } else { settings.auth.session_duration_hours },
         ).await
     {
         Ok(s) => s,
@@ -585,6 +619,15 @@ pub async fn login_totp_handler(
         }
     };
 
+    let ip = crate::api::state::client_ip(
+        &request_headers,
+        settings.server.trust_forwarded_for(),
+    );
+    let method = if used_recovery { LoginMethod::RecoveryCode } else { LoginMethod::Totp };
+    login_history_service
+        .record_login(&member, ip, crate::api::state::user_agent(&request_headers), method)
+        .await;
+
     // Heads-up notification when a recovery code was used: there's now
     // one fewer left, and if the user wasn't the one logging in, they
     // should know. We just log + dispatch an admin alert if recovery
@@ -600,7 +643,7 @@ pub async fn login_totp_handler(
         );
     }
 
-    let max_age_secs = if pending.remember_me { 60 * 60 * 24 * 30 } else { 60 * 60 * 24 };
+    let max_age_secs = if pending.remember_me { 60 * 60 * 24 * 30 } else { 60 * 60 * settings.auth.session_duration_hours };
     let secure_attr = if settings.server.cookies_are_secure() { "; Secure" } else { "" };
     let session_cookie_value = format!(
         "session={}; HttpOnly; SameSite=Lax; Path=/; Max-Age={}{}",
@@ -608,12 +651,7 @@ pub async fn login_totp_handler(
     );
     let clear_pending = crate::auth::pending_login::create_clear_cookie();
 
-    use crate::domain::MemberStatus;
-    let default_destination = if member.status == MemberStatus::Expired {
-        "/portal/restore".to_string()
-    } else {
-        "/portal/dashboard".to_string()
-    };
+    let default_destination = landing_page_service.destination(&member).await;
     let redirect_url = payload.redirect_url
         .filter(|u| u.starts_with("/portal/") && !u.contains(".."))
         .unwrap_or(default_destination);
@@ -634,4 +672,4 @@ pub async fn login_totp_handler(
         redirect: Some(redirect_url),
         error: None,
     })).into_response()
-}
\ No newline at end of file
+}
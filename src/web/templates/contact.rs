@@ -0,0 +1,145 @@
+//! Contact-the-admins form for visitors without an account:
+//!   GET /contact[?category=cant_log_in] -> the form
+//!   POST /contact                       -> store + notify admins, then
+//!                                          a thank-you page
+//!
+//! The login page links here with `category=cant_log_in` preselected.
+//! See `service::contact_service` for the honeypot and storage.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Form,
+};
+use serde::Deserialize;
+
+use crate::{
+    api::state::ContactLimiter,
+    config::Settings,
+    domain::ContactCategory,
+    error::AppError,
+    service::contact_service::{ContactInput, ContactService},
+    web::templates::{BaseContext, HtmlTemplate},
+};
+
+#[derive(Template)]
+#[template(path = "auth/contact.html")]
+pub struct ContactTemplate {
+    pub base: BaseContext,
+    pub enabled: bool,
+    pub submitted: bool,
+    pub error: Option<String>,
+    pub categories: Vec<ContactCategory>,
+    pub category: String,
+    pub name: String,
+    pub email: String,
+    pub message: String,
+}
+
+impl ContactTemplate {
+    fn new(enabled: bool) -> Self {
+        Self {
+            base: BaseContext::for_anon(),
+            enabled,
+            submitted: false,
+            error: None,
+            categories: ContactCategory::ALL.to_vec(),
+            category: ContactCategory::default().as_str().to_string(),
+            name: String::new(),
+            email: String::new(),
+            message: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContactQuery {
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContactForm {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub message: String,
+    /// Honeypot. Hidden from people; bots fill it in.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+pub async fn contact_page(
+    State(contact_service): State<Arc<ContactService>>,
+    Query(query): Query<ContactQuery>,
+) -> Response {
+    let mut template = ContactTemplate::new(contact_service.enabled().await);
+    if let Some(category) = query
+        .category
+        .as_deref()
+        .and_then(|c| c.parse::<ContactCategory>().ok())
+    {
+        template.category = category.as_str().to_string();
+    }
+    HtmlTemplate(template).into_response()
+}
+
+pub async fn contact_handler(
+    State(settings): State<Arc<Settings>>,
+    State(contact_limiter): State<ContactLimiter>,
+    State(contact_service): State<Arc<ContactService>>,
+    headers: HeaderMap,
+    Form(form): Form<ContactForm>,
+) -> Response {
+    let enabled = contact_service.enabled().await;
+    let mut template = ContactTemplate::new(enabled);
+    if !enabled {
+        return (StatusCode::NOT_FOUND, HtmlTemplate(template)).into_response();
+    }
+
+    let ip = crate::api::state::client_ip(&headers, settings.server.trust_forwarded_for());
+    let category = form.category.parse::<ContactCategory>().unwrap_or_default();
+    template.category = category.as_str().to_string();
+    template.name = form.name.clone();
+    template.email = form.email.clone();
+    template.message = form.message.clone();
+
+    if !contact_limiter.0.check_and_record(ip) {
+        template.error =
+            Some("Too many messages from your network. Please try again later.".to_string());
+        return (StatusCode::TOO_MANY_REQUESTS, HtmlTemplate(template)).into_response();
+    }
+
+    let result = contact_service
+        .submit(ContactInput {
+            name: form.name,
+            email: form.email,
+            category,
+            message: form.message,
+            honeypot: form.website,
+        })
+        .await;
+    match result {
+        Ok(_) => {
+            template.submitted = true;
+            HtmlTemplate(template).into_response()
+        }
+        Err(AppError::BadRequest(msg)) => {
+            template.error = Some(msg);
+            (StatusCode::BAD_REQUEST, HtmlTemplate(template)).into_response()
+        }
+        Err(e) => {
+            tracing::error!("Contact form submission failed: {}", e);
+            template.error =
+                Some("Your message couldn't be sent. Please try again later.".to_string());
+            (StatusCode::INTERNAL_SERVER_ERROR, HtmlTemplate(template)).into_response()
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! Embeddable widgets for the org's own website.
+//!
+//! `/public/embed/events` is a self-contained HTML fragment listing the
+//! next few public events, meant to be dropped into an `<iframe>`. It
+//! carries its own inline styles and none of the app chrome, and the
+//! security-headers middleware gives `/public/embed/` its own
+//! `frame-ancestors` (`security_headers.embed_frame_ancestors`) so
+//! other sites may frame it. Members-only events never appear.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::{
+    config::Settings, repository::EventRepository, service::settings_service::SettingsService,
+    web::templates::HtmlTemplate,
+};
+
+/// Events listed when the embed doesn't ask for a `count`.
+const DEFAULT_COUNT: usize = 5;
+/// Upper bound on `count`; the widget is a teaser, not a calendar.
+const MAX_COUNT: usize = 20;
+
+/// Same freshness as the share pages: cheap for busy sites, and edits
+/// show up within a few minutes.
+const EMBED_CACHE_CONTROL: &str = "public, max-age=300";
+
+#[derive(Debug, Deserialize)]
+pub struct EmbedEventsQuery {
+    /// How many events to show, 1–20. Default 5.
+    pub count: Option<usize>,
+    /// `light` (default) or `dark`.
+    pub theme: Option<String>,
+}
+
+pub struct EmbedEvent {
+    pub title: String,
+    pub when: String,
+    pub location: Option<String>,
+    pub url: String,
+}
+
+#[derive(Template)]
+#[template(path = "embed/events.html")]
+pub struct EmbedEventsTemplate {
+    pub org_name: String,
+    /// `light` or `dark`.
+    pub theme: &'static str,
+    pub events: Vec<EmbedEvent>,
+}
+
+pub async fn embed_events(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(settings): State<Arc<Settings>>,
+    Query(query): Query<EmbedEventsQuery>,
+) -> Response {
+    let count = query.count.unwrap_or(DEFAULT_COUNT).clamp(1, MAX_COUNT);
+    let theme = match query.theme.as_deref() {
+        Some("dark") => "dark",
+        _ => "light",
+    };
+
+    let events = match event_repo.list_public().await {
+        Ok(events) => events,
+        Err(e) => {
+            tracing::error!("events embed: listing public events failed: {}", e);
+            Vec::new()
+        }
+    };
+    let now = Utc::now();
+    let mut upcoming: Vec<_> = events.into_iter().filter(|e| e.start_time > now).collect();
+    upcoming.sort_by_key(|e| e.start_time);
+    upcoming.truncate(count);
+
+    let locale = settings_service.org_locale().await;
+    let base_url = settings.server.base_url.trim_end_matches('/');
+    let events = upcoming
+        .into_iter()
+        .map(|e| EmbedEvent {
+            when: locale.long_date_time(&e.start_time),
+            location: e.location.filter(|l| !l.is_empty()),
+            url: format!("{}/events/{}", base_url, e.id),
+            title: e.title,
+        })
+        .collect();
+
+    let org_name = settings_service
+        .get_value("org.name")
+        .await
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Coterie".to_string());
+
+    (
+        [(header::CACHE_CONTROL, EMBED_CACHE_CONTROL)],
+        HtmlTemplate(EmbedEventsTemplate {
+            org_name,
+            theme,
+            events,
+        }),
+    )
+        .into_response()
+}
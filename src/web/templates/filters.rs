@@ -1,21 +1,31 @@
+//! Askama filters for dates. Templates pass the page's locale
+//! explicitly (`{{ d|fmt_long_date(base.locale) }}`) — see
+//! `domain::Locale` for the per-locale patterns.
+
 use chrono::{DateTime, Utc};
 
-pub fn fmt_long_date(d: &DateTime<Utc>) -> ::askama::Result<String> {
-    Ok(d.format("%B %d, %Y").to_string())
+use crate::domain::Locale;
+
+pub fn fmt_long_date(d: &DateTime<Utc>, locale: &Locale) -> ::askama::Result<String> {
+    Ok(locale.long_date(d))
 }
 
-pub fn fmt_short_date(d: &DateTime<Utc>) -> ::askama::Result<String> {
-    Ok(d.format("%b %d, %Y").to_string())
+pub fn fmt_short_date(d: &DateTime<Utc>, locale: &Locale) -> ::askama::Result<String> {
+    Ok(locale.short_date(d))
+}
+
+pub fn fmt_date_time(d: &DateTime<Utc>, locale: &Locale) -> ::askama::Result<String> {
+    Ok(locale.date_time(d))
 }
 
 #[allow(dead_code)]
-pub fn fmt_long_date_opt(d: &Option<DateTime<Utc>>) -> ::askama::Result<String> {
-    Ok(d.map(|x| x.format("%B %d, %Y").to_string()).unwrap_or_default())
+pub fn fmt_long_date_opt(d: &Option<DateTime<Utc>>, locale: &Locale) -> ::askama::Result<String> {
+    Ok(d.map(|x| locale.long_date(&x)).unwrap_or_default())
 }
 
 #[allow(dead_code)]
-pub fn fmt_short_date_opt(d: &Option<DateTime<Utc>>) -> ::askama::Result<String> {
-    Ok(d.map(|x| x.format("%b %d, %Y").to_string()).unwrap_or_default())
+pub fn fmt_short_date_opt(d: &Option<DateTime<Utc>>, locale: &Locale) -> ::askama::Result<String> {
+    Ok(d.map(|x| locale.short_date(&x)).unwrap_or_default())
 }
 
 #[cfg(test)]
@@ -29,31 +39,48 @@ mod tests {
 
     #[test]
     fn fmt_long_date_renders_full_month_name() {
-        assert_eq!(fmt_long_date(&fixture()).unwrap(), "September 12, 2025");
+        assert_eq!(fmt_long_date(&fixture(), &Locale::EnUs).unwrap(), "September 12, 2025");
     }
 
     #[test]
     fn fmt_short_date_renders_abbreviated_month() {
-        assert_eq!(fmt_short_date(&fixture()).unwrap(), "Sep 12, 2025");
+        assert_eq!(fmt_short_date(&fixture(), &Locale::EnUs).unwrap(), "Sep 12, 2025");
+    }
+
+    #[test]
+    fn fmt_date_time_appends_the_time() {
+        assert_eq!(fmt_date_time(&fixture(), &Locale::EnUs).unwrap(), "Sep 12, 2025 14:30");
     }
 
     #[test]
     fn fmt_long_date_opt_renders_some() {
-        assert_eq!(fmt_long_date_opt(&Some(fixture())).unwrap(), "September 12, 2025");
+        assert_eq!(fmt_long_date_opt(&Some(fixture()), &Locale::EnUs).unwrap(), "September 12, 2025");
     }
 
     #[test]
     fn fmt_long_date_opt_returns_empty_for_none() {
-        assert_eq!(fmt_long_date_opt(&None).unwrap(), "");
+        assert_eq!(fmt_long_date_opt(&None, &Locale::EnUs).unwrap(), "");
     }
 
     #[test]
     fn fmt_short_date_opt_renders_some() {
-        assert_eq!(fmt_short_date_opt(&Some(fixture())).unwrap(), "Sep 12, 2025");
+        assert_eq!(fmt_short_date_opt(&Some(fixture()), &Locale::EnUs).unwrap(), "Sep 12, 2025");
     }
 
     #[test]
     fn fmt_short_date_opt_returns_empty_for_none() {
-        assert_eq!(fmt_short_date_opt(&None).unwrap(), "");
+        assert_eq!(fmt_short_date_opt(&None, &Locale::EnUs).unwrap(), "");
+    }
+
+    #[test]
+    fn switching_locale_changes_rendered_date() {
+        let d = fixture();
+        assert_eq!(fmt_long_date(&d, &Locale::EnGb).unwrap(), "12 September 2025");
+        assert_eq!(fmt_long_date(&d, &Locale::DeDe).unwrap(), "12. September 2025");
+        assert_eq!(fmt_short_date(&d, &Locale::FrFr).unwrap(), "12/09/2025");
+        assert_ne!(
+            fmt_short_date(&d, &Locale::EnUs).unwrap(),
+            fmt_short_date(&d, &Locale::DeDe).unwrap()
+        );
     }
 }
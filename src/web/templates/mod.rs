@@ -1,7 +1,11 @@
 pub mod auth;
+pub mod contact;
+pub mod embed;
 pub mod filters;
 pub mod reset;
 pub mod setup;
+pub mod share;
+pub mod signup;
 pub mod verify;
 
 use askama::Template;
@@ -12,6 +16,7 @@ use axum::{
 
 use crate::api::middleware::auth::{CurrentUser, SessionInfo};
 use crate::auth::CsrfService;
+use crate::domain::Locale;
 
 /// Context every page that extends `layouts/base.html` carries.
 ///
@@ -30,6 +35,9 @@ pub struct BaseContext {
     pub current_user: Option<UserInfo>,
     pub is_admin: bool,
     pub csrf_token: String,
+    /// Display locale for dates/amounts on this page. Pre-auth pages
+    /// get the default (en-US); they render almost no dates.
+    pub locale: Locale,
 }
 
 impl BaseContext {
@@ -55,6 +63,7 @@ impl BaseContext {
             }),
             is_admin: current_user.member.is_admin,
             csrf_token,
+            locale: current_user.locale,
         }
     }
 
@@ -33,6 +33,11 @@ pub struct SetupRequest {
     pub full_name: String,
     pub password: String,
     pub password_confirm: String,
+    /// HTML checkbox: present when checked, absent otherwise. Turns on
+    /// `auth.require_totp_for_admins`, so the new admin is sent to
+    /// enroll before their first admin page.
+    #[serde(default)]
+    pub require_totp: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -118,6 +123,14 @@ pub async fn setup_handler(
         })).into_response();
     }
 
+    if let Err(msg) = crate::domain::validate_username(request.username.trim()) {
+        return (StatusCode::BAD_REQUEST, Json(SetupResponse {
+            success: false,
+            redirect: None,
+            error: Some(msg.to_string()),
+        })).into_response();
+    }
+
     // Serialize first-admin creation. Without this, two concurrent setup
     // requests can both pass the "no admin exists" check and both create
     // admin accounts. The lock is held across check + create + promote.
@@ -201,6 +214,17 @@ pub async fn setup_handler(
             tracing::warn!("Couldn't persist org.name during setup ({}); admin can edit later", e);
         }
     }
+    if request.require_totp.is_some() {
+        let update = crate::domain::UpdateSettingRequest {
+            value: "true".to_string(),
+            reason: Some("Set during initial setup".to_string()),
+        };
+        if let Err(e) = settings_service
+            .update_setting("auth.require_totp_for_admins", update, member.id).await
+        {
+            tracing::warn!("Couldn't require admin two-factor during setup ({}); admin can enable it later", e);
+        }
+    }
     tracing::info!("Setup complete for organization: {}", request.org_name);
 
     let mut headers = HeaderMap::new();
@@ -0,0 +1,228 @@
+//! Public share pages for events and announcements.
+//!
+//! `/events/:id` and `/announcements/:id` are what a member pastes into
+//! Discord, Slack or social media: a minimal standalone page whose
+//! Open Graph and Twitter Card tags give the link a real preview — the
+//! item's title, a plain-text summary, and its image (or the org's
+//! `org.share_image_url` when it has none). Only what the public API
+//! would show is shared: members-only events get the same anonymized
+//! view as the public feeds, and anything unpublished, expired,
+//! members-only (announcements) or admin-only is a 404.
+
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api::handlers::public::anonymous_view,
+    config::Settings,
+    domain::EventStatus,
+    repository::{AnnouncementRepository, EventRepository},
+    service::settings_service::SettingsService,
+    web::{markdown, templates::HtmlTemplate},
+};
+
+/// Longest `og:description` we generate. Previews truncate well
+/// before this anyway.
+const SUMMARY_CHARS: usize = 200;
+
+/// Crawlers refetch rarely; a few minutes keeps edits visible soon.
+const SHARE_CACHE_CONTROL: &str = "public, max-age=300";
+
+#[derive(Template)]
+#[template(path = "share.html")]
+pub struct ShareTemplate {
+    pub org_name: String,
+    pub website_url: String,
+    /// `article` for announcements, `website` for events (Open Graph
+    /// has no event type).
+    pub og_type: &'static str,
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    pub image_url: Option<String>,
+    pub when: String,
+    pub location: Option<String>,
+    pub body_html: String,
+}
+
+pub async fn event_share_page(
+    State(event_repo): State<Arc<dyn EventRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(settings): State<Arc<Settings>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let event = match event_repo.find_by_id(id).await {
+        Ok(Some(e)) if e.status == EventStatus::Published => e,
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("share page: loading event {} failed: {}", id, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Some(event) = anonymous_view(event) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let locale = settings_service.org_locale().await;
+    let when = match event.end_time {
+        Some(end) => format!(
+            "{} – {}",
+            locale.long_date_time(&event.start_time),
+            locale.time(&end)
+        ),
+        None => locale.long_date_time(&event.start_time),
+    };
+    render(
+        &settings_service,
+        &settings,
+        ShareItem {
+            og_type: "website",
+            path: format!("/events/{}", id),
+            title: event.title,
+            body: event.description,
+            image_url: event.image_url,
+            when,
+            location: event.location,
+        },
+    )
+    .await
+}
+
+pub async fn announcement_share_page(
+    State(announcement_repo): State<Arc<dyn AnnouncementRepository>>,
+    State(settings_service): State<Arc<SettingsService>>,
+    State(settings): State<Arc<Settings>>,
+    Path(id): Path<Uuid>,
+) -> Response {
+    let now = Utc::now();
+    let announcement = match announcement_repo.find_by_id(id).await {
+        Ok(Some(a))
+            if a.is_public
+                && a.published_at.is_some_and(|at| at <= now)
+                && !a.is_expired_at(now) =>
+        {
+            a
+        }
+        Ok(_) => return StatusCode::NOT_FOUND.into_response(),
+        Err(e) => {
+            tracing::error!("share page: loading announcement {} failed: {}", id, e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let locale = settings_service.org_locale().await;
+    let when = announcement
+        .published_at
+        .map(|at| locale.long_date(&at))
+        .unwrap_or_default();
+    render(
+        &settings_service,
+        &settings,
+        ShareItem {
+            og_type: "article",
+            path: format!("/announcements/{}", id),
+            title: announcement.title,
+            body: announcement.content,
+            image_url: announcement.image_url,
+            when,
+            location: None,
+        },
+    )
+    .await
+}
+
+struct ShareItem {
+    og_type: &'static str,
+    path: String,
+    title: String,
+    /// Markdown.
+    body: String,
+    image_url: Option<String>,
+    when: String,
+    location: Option<String>,
+}
+
+async fn render(
+    settings_service: &SettingsService,
+    settings: &Settings,
+    item: ShareItem,
+) -> Response {
+    let setting = |key: &'static str| async move {
+        settings_service
+            .get_value(key)
+            .await
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let org_name = Some(setting("org.name").await)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Coterie".to_string());
+    let default_image = Some(setting("org.share_image_url").await).filter(|s| !s.is_empty());
+
+    // Crawlers need absolute URLs; uploads are stored as `/uploads/..`.
+    let base_url = &settings.server.base_url;
+    let image_url = item
+        .image_url
+        .filter(|s| !s.is_empty())
+        .or(default_image)
+        .map(|u| absolute_url(base_url, &u));
+
+    let page = ShareTemplate {
+        org_name,
+        website_url: setting("org.website_url").await,
+        og_type: item.og_type,
+        url: absolute_url(base_url, &item.path),
+        summary: summary(&item.body),
+        body_html: markdown::render(&item.body, &settings_service.html_policy().await),
+        title: item.title,
+        image_url,
+        when: item.when,
+        location: item.location.filter(|l| !l.is_empty()),
+    };
+    (
+        [(header::CACHE_CONTROL, SHARE_CACHE_CONTROL)],
+        HtmlTemplate(page),
+    )
+        .into_response()
+}
+
+fn absolute_url(base_url: &str, url: &str) -> String {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        return url.to_string();
+    }
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        url.trim_start_matches('/')
+    )
+}
+
+/// A one-line plain-text summary of markdown `text`: whitespace
+/// collapsed, the common markup characters dropped, and cut at a word
+/// boundary near `SUMMARY_CHARS`.
+pub(crate) fn summary(text: &str) -> String {
+    let plain = text
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c| matches!(c, '#' | '*' | '_' | '`' | '>')))
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if plain.chars().count() <= SUMMARY_CHARS {
+        return plain;
+    }
+    let cut: String = plain.chars().take(SUMMARY_CHARS - 1).collect();
+    let cut = match cut.rfind(' ') {
+        Some(i) if i > cut.len() / 2 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
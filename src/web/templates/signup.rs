@@ -0,0 +1,45 @@
+//! Landing page for Stripe Checkout after public signup. The new
+//! member is still Pending and can't log in yet, so the portal's
+//! payment success/cancel pages are out of reach; this page just says
+//! what happens next. Activation itself comes from the webhook.
+
+use askama::Template;
+use axum::{
+    extract::Query,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::web::templates::{BaseContext, HtmlTemplate};
+
+#[derive(Debug, Deserialize)]
+pub struct SignupPaymentQuery {
+    #[serde(default)]
+    pub result: String,
+}
+
+#[derive(Template)]
+#[template(path = "auth/signup_payment.html")]
+pub struct SignupPaymentTemplate {
+    pub base: BaseContext,
+    pub paid: bool,
+    pub message: String,
+}
+
+pub async fn signup_payment_page(Query(query): Query<SignupPaymentQuery>) -> Response {
+    let paid = query.result == "paid";
+    let message = if paid {
+        "Thanks! Your membership activates as soon as the payment clears, usually within a \
+         minute. We'll email you a welcome note when it does."
+    } else {
+        "Your account was created, but the payment wasn't completed, so your membership is \
+         still pending. Contact us to finish paying and we'll activate you."
+    };
+
+    HtmlTemplate(SignupPaymentTemplate {
+        base: BaseContext::for_anon(),
+        paid,
+        message: message.to_string(),
+    })
+    .into_response()
+}
@@ -1,3 +1,4 @@
+use std::io::Cursor;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -8,6 +9,8 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use axum_extra::extract::CookieJar;
+use image::{codecs::jpeg::JpegEncoder, codecs::webp::WebPEncoder, imageops::FilterType};
+use image::{DynamicImage, ImageFormat, ImageReader, Limits, RgbImage};
 use sqlx::SqlitePool;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
@@ -15,7 +18,7 @@ use tokio_util::io::ReaderStream;
 use uuid::Uuid;
 
 use crate::auth::AuthService;
-use crate::config::Settings;
+use crate::config::{ImageConfig, ImageOutputFormat, Settings};
 use crate::error::{AppError, Result};
 
 /// Allowed image extensions
@@ -24,34 +27,84 @@ const ALLOWED_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
 /// Maximum file size (10 MB)
 const MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
 
+/// Largest decoded edge we'll accept. Anything bigger is almost
+/// certainly a decompression bomb rather than a photo.
+const MAX_DECODED_DIMENSION: u32 = 12_000;
+
 /// Inspect the first bytes of an image and return its detected format
 /// as a canonical extension string ("jpg", "png", "gif", "webp"). Any
 /// other content returns `None`. The extension alone is a hint from the
 /// uploader — this is the authoritative check.
 fn detect_image_format(data: &[u8]) -> Option<&'static str> {
-    // JPEG: FF D8 FF (all three bytes)
-    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
-        return Some("jpg");
-    }
-    // PNG: 89 50 4E 47 0D 0A 1A 0A
-    if data.starts_with(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]) {
-        return Some("png");
+    match image::guess_format(data).ok()? {
+        ImageFormat::Jpeg => Some("jpg"),
+        ImageFormat::Png => Some("png"),
+        ImageFormat::Gif => Some("gif"),
+        ImageFormat::WebP => Some("webp"),
+        _ => None,
     }
-    // GIF: "GIF87a" or "GIF89a"
-    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
-        return Some("gif");
+}
+
+/// Decode, downscale to fit `config`'s bounds, and re-encode. Returns
+/// the new bytes and their extension. CPU-bound — run it on the
+/// blocking pool.
+fn process_image(data: &[u8], config: &ImageConfig) -> Result<(Vec<u8>, &'static str)> {
+    let undecodable = |e: image::ImageError| {
+        AppError::Validation(format!("Image could not be decoded: {}", e))
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DECODED_DIMENSION);
+    limits.max_image_height = Some(MAX_DECODED_DIMENSION);
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| AppError::Internal(format!("Failed to read image: {}", e)))?;
+    reader.limits(limits);
+    let mut img = reader.decode().map_err(undecodable)?;
+
+    // `resize` fits inside the box and keeps the aspect ratio.
+    if img.width() > config.max_width || img.height() > config.max_height {
+        img = img.resize(config.max_width, config.max_height, FilterType::Lanczos3);
     }
-    // WebP: RIFF....WEBP (4 bytes, then 4-byte size, then "WEBP")
-    if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
-        return Some("webp");
+
+    let mut out = Vec::new();
+    let encode_failed =
+        |e: image::ImageError| AppError::Internal(format!("Failed to encode image: {}", e));
+    match config.format {
+        ImageOutputFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(&mut out, config.jpeg_quality.clamp(1, 100));
+            DynamicImage::ImageRgb8(flatten_onto_white(&img))
+                .write_with_encoder(encoder)
+                .map_err(encode_failed)?;
+            Ok((out, "jpg"))
+        }
+        ImageOutputFormat::Webp => {
+            let encoder = WebPEncoder::new_lossless(&mut out);
+            DynamicImage::ImageRgba8(img.to_rgba8())
+                .write_with_encoder(encoder)
+                .map_err(encode_failed)?;
+            Ok((out, "webp"))
+        }
     }
-    None
 }
 
-/// Save an uploaded file to the uploads directory.
+/// JPEG has no alpha channel. Composite onto white so transparent PNG
+/// logos don't come out on black.
+fn flatten_onto_white(img: &DynamicImage) -> RgbImage {
+    let rgba = img.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+/// Save an uploaded image to the uploads directory, resized and
+/// re-encoded per `images` (GIFs are stored untouched).
 /// Returns the relative path to the file (e.g., "uploads/abc123.jpg")
 pub async fn save_uploaded_file(
     uploads_dir: &str,
+    images: &ImageConfig,
     filename: &str,
     data: &[u8],
 ) -> Result<String> {
@@ -97,6 +150,17 @@ pub async fn save_uploaded_file(
         )));
     }
 
+    let (processed, stored_extension) = if detected == "gif" {
+        (None, "gif")
+    } else {
+        let owned = data.to_vec();
+        let config = images.clone();
+        let (bytes, ext) = tokio::task::spawn_blocking(move || process_image(&owned, &config))
+            .await
+            .map_err(|e| AppError::Internal(format!("Image processing task failed: {}", e)))??;
+        (Some(bytes), ext)
+    };
+
     // Ensure uploads directory exists
     let uploads_path = PathBuf::from(uploads_dir);
     fs::create_dir_all(&uploads_path).await.map_err(|e| {
@@ -104,20 +168,32 @@ pub async fn save_uploaded_file(
     })?;
 
     // Generate unique filename
-    let new_filename = format!("{}.{}", Uuid::new_v4(), extension);
-    let file_path = uploads_path.join(&new_filename);
+    let id = Uuid::new_v4();
+    let new_filename = format!("{}.{}", id, stored_extension);
+    write_file(&uploads_path.join(&new_filename), processed.as_deref().unwrap_or(data)).await?;
+
+    if images.keep_original && processed.is_some() {
+        let original = format!("{}.orig.{}", id, detected);
+        write_file(&uploads_path.join(original), data).await?;
+    }
+
+    // Return relative path for storing in database
+    Ok(format!("uploads/{}", new_filename))
+}
 
-    // Write file
-    let mut file = fs::File::create(&file_path).await.map_err(|e| {
+async fn write_file(path: &std::path::Path, data: &[u8]) -> Result<()> {
+    let mut file = fs::File::create(path).await.map_err(|e| {
         AppError::Internal(format!("Failed to create file: {}", e))
     })?;
 
     file.write_all(data).await.map_err(|e| {
         AppError::Internal(format!("Failed to write file: {}", e))
     })?;
-
-    // Return relative path for storing in database
-    Ok(format!("uploads/{}", new_filename))
+    // tokio's File finishes writes in the background; flush so the
+    // bytes are on disk before the path is handed back.
+    file.flush().await.map_err(|e| {
+        AppError::Internal(format!("Failed to write file: {}", e))
+    })
 }
 
 /// Delete an uploaded file by its URL path (e.g., "uploads/abc123.jpg").
@@ -143,12 +219,22 @@ pub async fn delete_uploaded_file(uploads_dir: &str, url_path: &str) -> Result<(
         return Ok(());
     }
 
-    let path = PathBuf::from(uploads_dir).join(filename);
-    if path.exists() {
-        if let Err(e) = fs::remove_file(&path).await {
-            // Don't fail the caller — the DB-level delete already
-            // succeeded. Log so orphans don't accumulate silently.
-            tracing::warn!("Failed to delete upload {}: {}", path.display(), e);
+    // The kept original (if `images.keep_original` was on at upload
+    // time) goes with it.
+    let mut paths = vec![PathBuf::from(uploads_dir).join(filename)];
+    if let Some((stem, _)) = filename.split_once('.') {
+        for ext in ["jpg", "png", "webp"] {
+            paths.push(PathBuf::from(uploads_dir).join(format!("{}.orig.{}", stem, ext)));
+        }
+    }
+
+    for path in paths {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(&path).await {
+                // Don't fail the caller — the DB-level delete already
+                // succeeded. Log so orphans don't accumulate silently.
+                tracing::warn!("Failed to delete upload {}: {}", path.display(), e);
+            }
         }
     }
 
@@ -215,6 +301,12 @@ pub async fn serve_upload(
         return StatusCode::BAD_REQUEST.into_response();
     }
 
+    // Kept originals aren't referenced by any event or announcement,
+    // so the privacy check below can't see them. Never serve them.
+    if filename.contains(".orig.") {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
     // Check if this is a private image
     if is_private_image(&db_pool, &filename).await {
         // Require authentication
@@ -312,4 +404,83 @@ mod tests {
         riff_wav.extend_from_slice(b"WAVE");
         assert_eq!(detect_image_format(&riff_wav), None);
     }
+
+    fn temp_uploads_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("coterie-uploads-test-{}", Uuid::new_v4()))
+    }
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = image::RgbaImage::from_pixel(width, height, image::Rgba([200, 30, 30, 255]));
+        let mut out = Vec::new();
+        DynamicImage::ImageRgba8(img)
+            .write_to(&mut Cursor::new(&mut out), ImageFormat::Png)
+            .unwrap();
+        out
+    }
+
+    #[tokio::test]
+    async fn oversized_png_is_downscaled_and_reencoded() {
+        let dir = temp_uploads_dir();
+        let config = ImageConfig { keep_original: true, ..Default::default() };
+
+        let path = save_uploaded_file(dir.to_str().unwrap(), &config, "banner.png", &png_bytes(3200, 1000))
+            .await
+            .unwrap();
+        let filename = path.strip_prefix("uploads/").unwrap();
+        assert!(filename.ends_with(".jpg"), "{}", path);
+
+        let stored = std::fs::read(dir.join(filename)).unwrap();
+        assert_eq!(image::guess_format(&stored).unwrap(), ImageFormat::Jpeg);
+        let img = image::load_from_memory(&stored).unwrap();
+        assert_eq!((img.width(), img.height()), (1600, 500));
+
+        let original = dir.join(filename.replace(".jpg", ".orig.png"));
+        assert!(original.exists());
+
+        delete_uploaded_file(dir.to_str().unwrap(), &path).await.unwrap();
+        assert!(!dir.join(filename).exists());
+        assert!(!original.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn webp_output_keeps_small_images_at_size() {
+        let dir = temp_uploads_dir();
+        let config = ImageConfig { format: ImageOutputFormat::Webp, ..Default::default() };
+
+        let path = save_uploaded_file(dir.to_str().unwrap(), &config, "icon.png", &png_bytes(40, 30))
+            .await
+            .unwrap();
+        let stored = std::fs::read(dir.join(path.strip_prefix("uploads/").unwrap())).unwrap();
+        assert_eq!(image::guess_format(&stored).unwrap(), ImageFormat::WebP);
+        let img = image::load_from_memory(&stored).unwrap();
+        assert_eq!((img.width(), img.height()), (40, 30));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn non_image_is_rejected() {
+        let dir = temp_uploads_dir();
+        let err = save_uploaded_file(
+            dir.to_str().unwrap(),
+            &ImageConfig::default(),
+            "photo.png",
+            b"<!DOCTYPE html><html><script>alert(1)</script></html>",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)), "{:?}", err);
+        assert!(!dir.exists(), "nothing should be written for a rejected upload");
+    }
+
+    #[tokio::test]
+    async fn truncated_image_is_rejected() {
+        let dir = temp_uploads_dir();
+        let mut data = png_bytes(64, 64);
+        data.truncate(40);
+        let err = save_uploaded_file(dir.to_str().unwrap(), &ImageConfig::default(), "cut.png", &data)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)), "{:?}", err);
+    }
 }
@@ -0,0 +1,109 @@
+//! A member can see their live sessions on `/portal/profile/sessions`,
+//! sign out any other one, and log out everywhere. The session making
+//! the request is marked and can't be revoked from the list.
+//!
+//! Run with: cargo test --features test-utils --test active_sessions_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::auth::AuthService;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session, SESSION_SECRET};
+
+async fn send(app: &Router, method: Method, uri: &str, cookie: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn session_ids(pool: &SqlitePool, member: Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT id FROM sessions WHERE member_id = ? ORDER BY id")
+        .bind(member.to_string())
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn lists_and_revokes_other_sessions_but_not_the_current_one() {
+    let pool = fresh_pool().await;
+    let (member, current, cookie) = member_session(&pool, false).await;
+    let (other, _) = AuthService::new(pool.clone(), SESSION_SECRET.to_string())
+        .create_session(member, 24)
+        .await
+        .unwrap();
+    let (stranger, stranger_session, _) = member_session(&pool, false).await;
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+
+    let resp = send(&app, Method::GET, "/portal/profile/sessions", &cookie).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body =
+        String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(body.contains("This session"), "{}", body);
+    assert!(body.contains(&format!("/portal/profile/sessions/{}/revoke", other.id)));
+    assert!(!body.contains(&format!("/portal/profile/sessions/{}/revoke", current)));
+    assert!(body.contains(&other.token_hash[..8]));
+
+    // The current session and another member's session stay put.
+    for id in [&current, &stranger_session] {
+        let uri = format!("/portal/profile/sessions/{}/revoke", id);
+        assert_eq!(
+            send(&app, Method::POST, &uri, &cookie).await.status(),
+            StatusCode::OK
+        );
+    }
+    assert_eq!(session_ids(&pool, stranger).await, vec![stranger_session]);
+
+    let uri = format!("/portal/profile/sessions/{}/revoke", other.id);
+    let resp = send(&app, Method::POST, &uri, &cookie).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("HX-Redirect").unwrap(),
+        "/portal/profile/sessions"
+    );
+    assert_eq!(session_ids(&pool, member).await, vec![current]);
+}
+
+#[tokio::test]
+async fn log_out_everywhere_ends_every_session() {
+    let pool = fresh_pool().await;
+    let (member, _, cookie) = member_session(&pool, false).await;
+    AuthService::new(pool.clone(), SESSION_SECRET.to_string())
+        .create_session(member, 24)
+        .await
+        .unwrap();
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+
+    let resp = send(
+        &app,
+        Method::POST,
+        "/portal/profile/sessions/revoke-all",
+        &cookie,
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("HX-Redirect").unwrap(), "/login");
+    let set_cookie = resp
+        .headers()
+        .get(header::SET_COOKIE)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(set_cookie.starts_with("session=;"), "{}", set_cookie);
+    assert!(session_ids(&pool, member).await.is_empty());
+}
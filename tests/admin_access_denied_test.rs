@@ -0,0 +1,84 @@
+//! A non-admin reaching an admin route never gets a 200: a page
+//! navigation is redirected to the dashboard, while HTMX requests and
+//! form submissions get a 403 instead of a followed redirect.
+//!
+//! Run with: cargo test --test admin_access_denied_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    response::Response,
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    repository::{MembershipTypeRepository, SqliteMembershipTypeRepository},
+};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, fresh_pool, member_session, SESSION_SECRET};
+
+async fn send(app: &Router, request: Request<Body>) -> Response {
+    app.clone().oneshot(request).await.unwrap()
+}
+
+fn get(uri: &str, cookie: &str, htmx: bool) -> Request<Body> {
+    let mut builder = Request::builder().uri(uri).header(header::COOKIE, cookie);
+    if htmx {
+        builder = builder.header("HX-Request", "true");
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn non_admin_gets_403_on_type_management_requests() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (_, session_id, member) = member_session(&pool, false).await;
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+
+    let resp = send(&app, get("/portal/admin/types", &member, true)).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body = to_bytes(resp.into_body(), 1 << 16).await.unwrap();
+    assert_eq!(&body[..], b"Access denied");
+
+    let resp = send(
+        &app,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/portal/admin/types/membership/new")
+            .header(header::COOKIE, &member)
+            .header("X-CSRF-Token", token)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(
+                "name=Sneaky&slug=sneaky&color=%23123456&fee_dollars=1&billing_period=yearly",
+            ))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert!(SqliteMembershipTypeRepository::new(pool.clone())
+        .find_by_slug("sneaky")
+        .await
+        .unwrap()
+        .is_none());
+
+    // A plain page load still lands on the dashboard.
+    let resp = send(&app, get("/portal/admin/types", &member, false)).await;
+    assert!(resp.status().is_redirection(), "{}", resp.status());
+    assert_eq!(resp.headers()[header::LOCATION], "/portal/dashboard");
+}
+
+#[tokio::test]
+async fn admin_reaches_type_management() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (_, _, admin) = member_session(&pool, true).await;
+
+    let resp = send(&app, get("/portal/admin/types", &admin, true)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+}
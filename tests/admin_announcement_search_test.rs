@@ -0,0 +1,188 @@
+//! The admin announcements list filters, sorts and pages in SQL via
+//! `AnnouncementRepository::search`/`count`, so a search reaches every
+//! row rather than the newest thousand.
+//!
+//! Run with: cargo test --test admin_announcement_search_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{
+        AnnouncementFilter, AnnouncementRepository, AnnouncementSort, AnnouncementStatusFilter,
+        SortOrder, SqliteAnnouncementRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, make_member, member_session};
+
+fn announcement(author: Uuid, title: &str, content: &str) -> Announcement {
+    let now = Utc::now();
+    Announcement {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        content: content.to_string(),
+        announcement_type: AnnouncementType::General,
+        announcement_type_id: None,
+        is_public: false,
+        featured: false,
+        image_url: None,
+        published_at: Some(now - Duration::hours(1)),
+        scheduled_publish_at: None,
+        expires_at: None,
+        comments_enabled: false,
+        notify_members: None,
+        notified_at: None,
+        created_by: author,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+async fn titles(pool: &SqlitePool, filter: AnnouncementFilter) -> Vec<String> {
+    let repo = SqliteAnnouncementRepository::new(pool.clone());
+    let total = repo.count(&filter).await.unwrap();
+    let rows = repo.search(&filter, 50, 0).await.unwrap();
+    assert_eq!(rows.len() as i64, total, "count agrees with search");
+    rows.into_iter().map(|a| a.title).collect()
+}
+
+#[tokio::test]
+async fn search_filters_and_sorts_in_sql() {
+    let pool = fresh_pool().await;
+    let repo = SqliteAnnouncementRepository::new(pool.clone());
+    let author = make_member(&pool).await;
+
+    repo.create(Announcement {
+        announcement_type: AnnouncementType::Meeting,
+        is_public: true,
+        ..announcement(author, "Board meeting", "Agenda attached")
+    })
+    .await
+    .unwrap();
+    repo.create(Announcement {
+        published_at: None,
+        ..announcement(author, "Draft notice", "Lockpicking night is moving")
+    })
+    .await
+    .unwrap();
+    repo.create(Announcement {
+        featured: true,
+        expires_at: Some(Utc::now() - Duration::minutes(5)),
+        ..announcement(author, "CTF recap", "We placed third")
+    })
+    .await
+    .unwrap();
+
+    let search = |q: &str| AnnouncementFilter {
+        search: Some(q.to_string()),
+        sort: AnnouncementSort::Title,
+        ..Default::default()
+    };
+    // Title or content, case-insensitively.
+    assert_eq!(titles(&pool, search("LOCKPICKING")).await, ["Draft notice"]);
+    assert_eq!(titles(&pool, search("  ")).await.len(), 3);
+    // LIKE wildcards in the query match only themselves.
+    for wildcard in ["%", "_", "\\"] {
+        assert!(
+            titles(&pool, search(wildcard)).await.is_empty(),
+            "{wildcard}"
+        );
+    }
+
+    let status = |status| AnnouncementFilter {
+        status: Some(status),
+        sort: AnnouncementSort::Title,
+        ..Default::default()
+    };
+    assert_eq!(
+        titles(&pool, status(AnnouncementStatusFilter::Published)).await,
+        ["Board meeting", "CTF recap"]
+    );
+    assert_eq!(
+        titles(&pool, status(AnnouncementStatusFilter::Draft)).await,
+        ["Draft notice"]
+    );
+    assert_eq!(
+        titles(&pool, status(AnnouncementStatusFilter::Expired)).await,
+        ["CTF recap"]
+    );
+    assert_eq!(
+        titles(&pool, status(AnnouncementStatusFilter::Featured)).await,
+        ["CTF recap"]
+    );
+    assert_eq!(
+        titles(&pool, status(AnnouncementStatusFilter::Public)).await,
+        ["Board meeting"]
+    );
+
+    let of_type = AnnouncementFilter {
+        announcement_type: Some(AnnouncementType::Meeting),
+        ..Default::default()
+    };
+    assert_eq!(titles(&pool, of_type).await, ["Board meeting"]);
+
+    // Drafts have no publish date: last when newest-published first.
+    let by_published = AnnouncementFilter {
+        sort: AnnouncementSort::PublishedAt,
+        order: SortOrder::Desc,
+        ..Default::default()
+    };
+    assert_eq!(titles(&pool, by_published).await[2], "Draft notice");
+    let by_title = AnnouncementFilter {
+        sort: AnnouncementSort::Title,
+        order: SortOrder::Desc,
+        ..Default::default()
+    };
+    assert_eq!(
+        titles(&pool, by_title).await,
+        ["Draft notice", "CTF recap", "Board meeting"]
+    );
+}
+
+#[tokio::test]
+async fn admin_search_reaches_past_the_first_thousand() {
+    let pool = fresh_pool().await;
+    let repo = SqliteAnnouncementRepository::new(pool.clone());
+    let author = make_member(&pool).await;
+
+    // The oldest row is the one a 1000-row load would have dropped.
+    repo.create(announcement(author, "Founding charter", "Signed by all"))
+        .await
+        .unwrap();
+    for i in 0..1000 {
+        repo.create(announcement(author, &format!("Weekly notes {}", i), "Body"))
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        repo.count(&AnnouncementFilter::default()).await.unwrap(),
+        1001
+    );
+
+    let (_, _, admin) = member_session(&pool, true).await;
+    let app = build_app(&pool).await;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/portal/admin/announcements?q=charter")
+                .header("Cookie", &admin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let html =
+        String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(html.contains("Founding charter"), "{}", html);
+    assert!(html.contains("1 announcements"), "{}", html);
+    assert!(!html.contains("Weekly notes"), "{}", html);
+}
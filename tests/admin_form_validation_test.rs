@@ -0,0 +1,386 @@
+//! Admin create/edit forms re-render themselves on a validation
+//! failure: the response is the form partial with the message next to
+//! the offending field and everything the admin typed still filled in.
+//! A valid submit answers with `HX-Redirect` instead.
+//!
+//! Run with: cargo test --features test-utils --test admin_form_validation_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, fresh_pool, member_session, SESSION_SECRET};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    cookie: String,
+    csrf: String,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+
+    let (_, session_id, cookie) = member_session(&pool, true).await;
+    let csrf = CsrfService::new(SESSION_SECRET).generate_token(&session_id).await.unwrap();
+
+    H {
+        pool,
+        app,
+        cookie,
+        csrf,
+    }
+}
+
+struct Reply {
+    status: StatusCode,
+    redirect: Option<String>,
+    body: String,
+}
+
+async fn send(h: &H, uri: &str, content_type: &str, body: String) -> Reply {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("Cookie", &h.cookie)
+                .header("HX-Request", "true")
+                .header(header::CONTENT_TYPE, content_type)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let redirect = resp
+        .headers()
+        .get("HX-Redirect")
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    Reply { status, redirect, body }
+}
+
+async fn post_form(h: &H, uri: &str, fields: &[(&str, &str)]) -> Reply {
+    let mut fields = fields.to_vec();
+    fields.push(("csrf_token", &h.csrf));
+    let body = serde_urlencoded::to_string(&fields).unwrap();
+    send(h, uri, "application/x-www-form-urlencoded", body).await
+}
+
+async fn post_multipart(h: &H, uri: &str, fields: &[(&str, &str)]) -> Reply {
+    const BOUNDARY: &str = "coterie-form-test";
+    let mut body = String::new();
+    for (name, value) in fields.iter().chain([("csrf_token", h.csrf.as_str())].iter()) {
+        body.push_str(&format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            BOUNDARY, name, value
+        ));
+    }
+    body.push_str(&format!("--{}--\r\n", BOUNDARY));
+    send(
+        h,
+        uri,
+        &format!("multipart/form-data; boundary={}", BOUNDARY),
+        body,
+    )
+    .await
+}
+
+async fn count(pool: &SqlitePool, sql: &str) -> i64 {
+    sqlx::query_scalar(sql).fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn member_form_keeps_values_and_flags_the_bad_field() {
+    let h = harness().await;
+    let members_before = count(&h.pool, "SELECT COUNT(*) FROM members").await;
+    let type_id: String = sqlx::query_scalar("SELECT id FROM membership_types LIMIT 1")
+        .fetch_one(&h.pool)
+        .await
+        .unwrap();
+
+    let reply = post_form(
+        &h,
+        "/portal/admin/members/new",
+        &[
+            ("email", "not-an-email"),
+            ("username", "river_song"),
+            ("full_name", "River Song"),
+            ("password", "short"),
+            ("membership_type_id", &type_id),
+            ("status", "Active"),
+            ("notes", "Met at the spring open house"),
+        ],
+    )
+    .await;
+
+    assert_eq!(reply.status, StatusCode::OK, "{}", reply.body);
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Enter a valid email address"), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="not-an-email""#), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="river_song""#));
+    assert!(reply.body.contains(r#"value="River Song""#));
+    assert!(reply.body.contains("Met at the spring open house"));
+    assert!(!reply.body.contains(r#"value="short""#), "password is never echoed");
+    assert!(!reply.body.contains("<html"), "only the form partial comes back");
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM members").await, members_before);
+
+    let reply = post_form(
+        &h,
+        "/portal/admin/members/new",
+        &[
+            ("email", "river@example.com"),
+            ("username", "river_song"),
+            ("full_name", "River Song"),
+            ("password", "Long-enough-passw0rd"),
+            ("membership_type_id", &type_id),
+            ("status", "Active"),
+        ],
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK);
+    let location = reply.redirect.unwrap_or_else(|| panic!("no redirect: {}", reply.body));
+    assert!(location.starts_with("/portal/admin/members/"), "{}", location);
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM members").await, members_before + 1);
+}
+
+#[tokio::test]
+async fn event_form_reports_missing_start_time() {
+    let h = harness().await;
+
+    let reply = post_multipart(
+        &h,
+        "/portal/admin/events/new",
+        &[
+            ("title", "Soldering Night"),
+            ("description", "Bring your own iron"),
+            ("location", "Workshop"),
+            ("visibility", "MembersOnly"),
+            ("start_time", ""),
+            ("max_attendees", "12"),
+        ],
+    )
+    .await;
+
+    assert_eq!(reply.status, StatusCode::OK);
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Start time is required"), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="Soldering Night""#), "{}", reply.body);
+    assert!(reply.body.contains("Bring your own iron"));
+    assert!(reply.body.contains(r#"value="12""#));
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM events").await, 0);
+
+    let reply = post_multipart(
+        &h,
+        "/portal/admin/events/new",
+        &[
+            ("title", "Soldering Night"),
+            ("description", "Bring your own iron"),
+            ("visibility", "MembersOnly"),
+            ("start_time", "2030-03-14T19:00"),
+        ],
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK, "{}", reply.body);
+    assert!(reply.redirect.is_some(), "{}", reply.body);
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM events").await, 1);
+}
+
+#[tokio::test]
+async fn type_form_reports_bad_color_and_duplicate_slug() {
+    let h = harness().await;
+
+    let reply = post_form(
+        &h,
+        "/portal/admin/types/event/new",
+        &[("name", "Workshops"), ("slug", "workshops"), ("color", "teal")],
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK);
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Use a hex color like #2196F3"), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="Workshops""#), "{}", reply.body);
+
+    let first = post_form(
+        &h,
+        "/portal/admin/types/event/new",
+        &[("name", "Workshops"), ("slug", "workshops"), ("color", "#009688")],
+    )
+    .await;
+    assert_eq!(first.redirect.as_deref(), Some("/portal/admin/types"), "{}", first.body);
+
+    let dup = post_form(
+        &h,
+        "/portal/admin/types/event/new",
+        &[("name", "Workshops Again"), ("slug", "workshops"), ("color", "#009688")],
+    )
+    .await;
+    assert_eq!(dup.status, StatusCode::OK);
+    assert!(dup.redirect.is_none());
+    assert!(dup.body.contains(r#"value="Workshops Again""#), "{}", dup.body);
+    assert!(dup.body.contains("text-red-600"), "slug conflict shows on the field: {}", dup.body);
+}
+
+#[tokio::test]
+async fn type_badge_text_contrasts_with_its_color() {
+    let h = harness().await;
+
+    let reply = post_form(
+        &h,
+        "/portal/admin/types/event/new",
+        &[("name", "Open Hack"), ("slug", "open-hack"), ("color", "#FFEB3")],
+    )
+    .await;
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Use a hex color like #2196F3"), "{}", reply.body);
+
+    for (name, slug, color) in [
+        ("Open Hack", "open-hack", "#FFEB3B"),
+        ("Night Shift", "night-shift", "#1A237E"),
+    ] {
+        let reply = post_form(
+            &h,
+            "/portal/admin/types/event/new",
+            &[("name", name), ("slug", slug), ("color", color)],
+        )
+        .await;
+        assert!(reply.redirect.is_some(), "{}", reply.body);
+    }
+
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/portal/admin/types")
+                .header("Cookie", &h.cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let page = String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(page.contains("background-color: #FFEB3B; color: #000000;"), "{}", page);
+    assert!(page.contains("background-color: #1A237E; color: #FFFFFF;"), "{}", page);
+}
+
+#[tokio::test]
+async fn membership_type_form_reports_bad_fee() {
+    let h = harness().await;
+
+    let reply = post_form(
+        &h,
+        "/portal/admin/types/membership/new",
+        &[
+            ("name", "Student"),
+            ("slug", "student-test"),
+            ("fee_dollars", "ten"),
+            ("billing_period", "monthly"),
+        ],
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK);
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Invalid fee amount"), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="Student""#), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="ten""#), "{}", reply.body);
+}
+
+#[tokio::test]
+async fn announcement_form_rejects_expiry_before_publish() {
+    let h = harness().await;
+
+    let reply = post_multipart(
+        &h,
+        "/portal/admin/announcements/new",
+        &[
+            ("title", "Volunteers needed"),
+            ("content", "Setup crew for Saturday"),
+            ("announcement_type", "General"),
+            ("scheduled_publish_at", "2030-03-14T19:00"),
+            ("expires_at", "2030-03-10T19:00"),
+        ],
+    )
+    .await;
+    assert_eq!(reply.status, StatusCode::OK);
+    assert!(reply.redirect.is_none());
+    assert!(reply.body.contains("Expiry must be after the publish time"), "{}", reply.body);
+    assert!(reply.body.contains(r#"value="2030-03-10T19:00""#), "{}", reply.body);
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM announcements").await, 0);
+}
+
+async fn create_membership_type(h: &H, slug: &str, fee: &str) -> Reply {
+    post_form(
+        h,
+        "/portal/admin/types/membership/new",
+        &[("name", slug), ("slug", slug), ("fee_dollars", fee), ("billing_period", "monthly")],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn membership_type_fee_parses_exactly_and_rejects_out_of_range() {
+    let h = harness().await;
+
+    // 19.99 is 1998.9999… as an f64; the decimal parse keeps it exact.
+    for (slug, fee, cents) in [("fee-1999", "19.99", 1999), ("fee-free", "0", 0)] {
+        let reply = create_membership_type(&h, slug, fee).await;
+        assert!(reply.redirect.is_some(), "{}", reply.body);
+        let saved: i64 = sqlx::query_scalar("SELECT fee_cents FROM membership_types WHERE slug = ?")
+            .bind(slug)
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+        assert_eq!(saved, cents, "{}", fee);
+    }
+
+    for (slug, fee) in [("fee-negative", "-5.00"), ("fee-huge", "100000.01")] {
+        let reply = create_membership_type(&h, slug, fee).await;
+        assert_eq!(reply.status, StatusCode::OK);
+        assert!(reply.redirect.is_none(), "{} accepted", fee);
+        assert!(reply.body.contains("Fee must be between $0.00 and $100,000.00"), "{}", reply.body);
+        assert!(reply.body.contains(&format!(r#"value="{}""#, fee)), "{}", reply.body);
+    }
+    assert_eq!(
+        count(&h.pool, "SELECT COUNT(*) FROM membership_types WHERE slug IN ('fee-negative', 'fee-huge')").await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn manual_payment_amount_uses_the_same_parser() {
+    let h = harness().await;
+    let (member, _, _) = member_session(&h.pool, false).await;
+    let uri = format!("/portal/admin/members/{}/record-payment", member);
+    let form = |amount: &'static str| [("payment_type", "other"), ("amount", amount), ("description", "Workshop materials")];
+
+    for (amount, message) in [
+        ("-5.00", "Amount must be a positive dollar amount."),
+        ("19.999", "Amount must be a positive dollar amount."),
+        ("100000.01", "cap on a single payment"),
+    ] {
+        let reply = post_form(&h, &uri, &form(amount)).await;
+        assert!(reply.body.contains(message), "{}: {}", amount, reply.body);
+    }
+    assert_eq!(count(&h.pool, "SELECT COUNT(*) FROM payments").await, 0);
+
+    post_form(&h, &uri, &form("19.99")).await;
+    let cents: i64 = sqlx::query_scalar("SELECT amount_cents FROM payments WHERE member_id = ?")
+        .bind(member.to_string())
+        .fetch_one(&h.pool)
+        .await
+        .unwrap();
+    assert_eq!(cents, 1999);
+}
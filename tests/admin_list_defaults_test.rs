@@ -0,0 +1,143 @@
+//! Admin events/announcements lists take their default sort (and, for
+//! events, time filter) from settings when the URL doesn't say, and
+//! still honour explicit params. Unknown values can't be saved.
+//!
+//! Run with: cargo test --features test-utils --test admin_list_defaults_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType, UpdateSettingRequest},
+    error::AppError,
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+    service::settings_service::SettingsService,
+};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, build_router, fresh_pool, member_session};
+
+struct H {
+    app: Router,
+    cookie: String,
+    admin: Uuid,
+    settings: Arc<SettingsService>,
+}
+
+async fn harness() -> (H, SqliteAnnouncementRepository) {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let settings = state.service_context.settings_service.clone();
+    let app = build_router(state);
+    let (admin, _, cookie) = member_session(&pool, true).await;
+    (
+        H {
+            app,
+            cookie,
+            admin,
+            settings,
+        },
+        SqliteAnnouncementRepository::new(pool),
+    )
+}
+
+async fn seed(repo: &SqliteAnnouncementRepository, author: Uuid, title: &str, age_days: i64) {
+    let created = Utc::now() - Duration::days(age_days);
+    repo.create(Announcement {
+        id: Uuid::new_v4(),
+        title: title.to_string(),
+        content: "Body".to_string(),
+        announcement_type: AnnouncementType::General,
+        announcement_type_id: None,
+        is_public: true,
+        featured: false,
+        image_url: None,
+        published_at: Some(created),
+        scheduled_publish_at: None,
+        expires_at: None,
+        comments_enabled: false,
+        notify_members: None,
+        notified_at: None,
+        created_by: author,
+        created_at: created,
+        updated_at: created,
+    })
+    .await
+    .unwrap();
+}
+
+async fn get(h: &H, uri: &str) -> String {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, &h.cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+/// Whether `first` appears before `second` in `html`.
+fn before(html: &str, first: &str, second: &str) -> bool {
+    let a = html.find(first).unwrap_or_else(|| panic!("{} missing", first));
+    let b = html.find(second).unwrap_or_else(|| panic!("{} missing", second));
+    a < b
+}
+
+async fn set(h: &H, key: &str, value: &str) -> Result<(), AppError> {
+    h.settings
+        .update_setting(
+            key,
+            UpdateSettingRequest {
+                value: value.to_string(),
+                reason: None,
+            },
+            h.admin,
+        )
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn default_sort_setting_changes_unparameterized_order() {
+    let (h, repo) = harness().await;
+    // Newest first by default, which is the reverse of title order.
+    // `create` stamps `created_at` itself, so seed oldest first.
+    seed(&repo, h.admin, "Aardvark club", 5).await;
+    seed(&repo, h.admin, "Zebra crossing", 1).await;
+
+    let html = get(&h, "/portal/admin/announcements").await;
+    assert!(before(&html, "Zebra crossing", "Aardvark club"));
+
+    set(&h, "announcements.default_sort", "title").await.unwrap();
+    set(&h, "announcements.default_order", "asc").await.unwrap();
+    let html = get(&h, "/portal/admin/announcements").await;
+    assert!(before(&html, "Aardvark club", "Zebra crossing"));
+
+    // An explicit param still wins over the setting.
+    let html = get(&h, "/portal/admin/announcements?sort=created_at&order=desc").await;
+    assert!(before(&html, "Zebra crossing", "Aardvark club"));
+}
+
+#[tokio::test]
+async fn unknown_values_are_rejected() {
+    let (h, _) = harness().await;
+    let err = set(&h, "announcements.default_sort", "content").await;
+    assert!(matches!(err, Err(AppError::BadRequest(_))), "{:?}", err);
+    let err = set(&h, "events.default_time_filter", "tomorrow").await;
+    assert!(matches!(err, Err(AppError::BadRequest(_))), "{:?}", err);
+    set(&h, "events.default_time_filter", "all").await.unwrap();
+}
@@ -64,9 +64,11 @@ async fn build_harness() -> Harness {
             base_url: "http://127.0.0.1".to_string(),
             data_dir: "./data".to_string(),
             uploads_dir: None,
+            backups_dir: None,
             secure_cookies: Some(false),
             cors_origins: None,
             trust_forwarded_for: Some(false),
+            max_in_flight_requests: 0,
         },
         database: coterie::config::DatabaseConfig {
             url: "sqlite::memory:".to_string(),
@@ -75,12 +77,20 @@ async fn build_harness() -> Harness {
         auth: coterie::config::AuthConfig {
             session_secret: "test-session-secret-please-ignore".to_string(),
             session_duration_hours: 24,
+            session_idle_timeout_minutes: None,
             totp_issuer: "Coterie Test".to_string(),
+            csrf_token_ttl_hours: 12,
+            geoip_db_path: None,
+            session_store: Default::default(),
+            redis_url: None,
+            login_rate_limit: Default::default(),
         },
         stripe: Default::default(),
         integrations: Default::default(),
         seed: Default::default(),
         bot_challenge: Default::default(),
+        images: Default::default(),
+        security_headers: Default::default(),
     };
     let settings = Arc::new(settings);
 
@@ -347,7 +357,8 @@ async fn export_unfiltered_returns_csv_with_all_members() {
     assert_eq!(
         header,
         "id,email,username,full_name,status,membership_type,joined_at,\
-         dues_paid_until,is_admin,bypass_dues,discord_id,email_verified_at,notes",
+         dues_paid_until,is_admin,bypass_dues,discord_id,email_verified_at,notes,\
+         membership_type_slug",
     );
     // 3 seeded + 1 admin = 4 data rows.
     let data_rows: Vec<&str> = lines.filter(|l| !l.is_empty()).collect();
@@ -467,8 +478,8 @@ async fn export_escapes_special_characters_per_rfc_4180() {
     // Column order: id, email, username, full_name, status, ...
     assert_eq!(fields[1], "obrien@example.com");
     assert_eq!(fields[3], "O'Brien, Sean");
-    // notes is the last column.
-    assert_eq!(fields.last().unwrap(), "Has \"complications\"");
+    // notes is the thirteenth column.
+    assert_eq!(fields[12], "Has \"complications\"");
 }
 
 #[tokio::test]
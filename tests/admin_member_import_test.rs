@@ -8,7 +8,11 @@
 //!     without aborting the batch;
 //!   - the malformed-header path (missing required column) aborts the
 //!     batch with no members created;
-//!   - an unknown `membership_type_slug` is a per-row failure.
+//!   - an unknown `membership_type_slug` is a per-row failure;
+//!   - the downloadable template's header is exactly what the importer
+//!     accepts, and the template imports as-is;
+//!   - a file with differently-named headers goes through the
+//!     column-mapping step and imports with the chosen mapping.
 //!
 //! Run with: cargo test --features test-utils --test admin_member_import_test
 
@@ -92,9 +96,11 @@ async fn build_harness() -> Harness {
             base_url: "http://127.0.0.1".to_string(),
             data_dir: "./data".to_string(),
             uploads_dir: None,
+            backups_dir: None,
             secure_cookies: Some(false),
             cors_origins: None,
             trust_forwarded_for: Some(false),
+            max_in_flight_requests: 0,
         },
         database: coterie::config::DatabaseConfig {
             url: "sqlite::memory:".to_string(),
@@ -103,12 +109,20 @@ async fn build_harness() -> Harness {
         auth: coterie::config::AuthConfig {
             session_secret: "test-session-secret-please-ignore".to_string(),
             session_duration_hours: 24,
+            session_idle_timeout_minutes: None,
             totp_issuer: "Coterie Test".to_string(),
+            csrf_token_ttl_hours: 12,
+            geoip_db_path: None,
+            session_store: Default::default(),
+            redis_url: None,
+            login_rate_limit: Default::default(),
         },
         stripe: Default::default(),
         integrations: Default::default(),
         seed: Default::default(),
         bot_challenge: Default::default(),
+        images: Default::default(),
+        security_headers: Default::default(),
     };
     let settings = Arc::new(settings);
 
@@ -280,6 +294,23 @@ fn build_multipart(csrf_token: &str, file_name: &str, csv_bytes: &[u8]) -> (Stri
     (content_type, body)
 }
 
+/// Multipart body of plain text fields, in order. Used for the
+/// column-mapping step, which re-posts the file as base64 text.
+fn build_text_multipart(fields: &[(&str, &str)]) -> (String, Vec<u8>) {
+    let boundary = "----coterie-test-boundary-xyz";
+    let mut body: Vec<u8> = Vec::new();
+    for (name, value) in fields {
+        body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+        body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+        );
+        body.extend_from_slice(value.as_bytes());
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+    (format!("multipart/form-data; boundary={boundary}"), body)
+}
+
 fn import_request(h: &Harness, file_name: &str, csv: &[u8]) -> Request<Body> {
     let (ct, body) = build_multipart(&h.csrf_token, file_name, csv);
     Request::builder()
@@ -505,8 +536,8 @@ async fn import_missing_required_column_aborts_batch() {
     let body = to_bytes(resp.into_body(), 1024 * 1024).await.unwrap();
     let text = String::from_utf8(body.to_vec()).unwrap();
 
-    // The error fragment should call out the missing column. The
-    // batch must not create members or audit rows.
+    // The response (now the column-mapping step) should call out the
+    // missing column. The batch must not create members or audit rows.
     assert!(
         text.contains("email") && text.to_lowercase().contains("missing"),
         "expected missing-column error message; got:\n{}",
@@ -750,3 +781,142 @@ async fn import_email_verified_at_skips_verification_email() {
         "import_email_verified_at_skips_verification_email: no new emails should be queued",
     );
 }
+
+async fn body_text(resp: axum::response::Response) -> String {
+    let body = to_bytes(resp.into_body(), 1024 * 1024).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Pull `value="..."` for the hidden input named `name` out of the
+/// mapping fragment.
+fn hidden_value(html: &str, name: &str) -> String {
+    let marker = format!("name=\"{}\" value=\"", name);
+    let start = html.find(&marker).expect(name) + marker.len();
+    let end = html[start..].find('"').unwrap();
+    html[start..start + end].to_string()
+}
+
+#[tokio::test]
+async fn import_template_matches_importer_columns() {
+    use coterie::web::portal::admin::members::IMPORT_COLUMNS;
+
+    let h = build_harness().await;
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/portal/admin/members/import/template")
+                .header(header::COOKIE, &h.session_cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .contains("attachment"));
+    let template = body_text(resp).await;
+
+    let lines: Vec<&str> = template.lines().collect();
+    assert_eq!(lines.len(), 2, "header + one example row:\n{}", template);
+    let expected: Vec<&str> = IMPORT_COLUMNS.iter().map(|c| c.name).collect();
+    assert_eq!(lines[0], expected.join(","));
+
+    // The example row is importable without edits.
+    let before = member_count(&h.pool).await;
+    let resp = h
+        .app
+        .clone()
+        .oneshot(import_request(&h, "template.csv", template.as_bytes()))
+        .await
+        .unwrap();
+    let text = body_text(resp).await;
+    assert_eq!(member_count(&h.pool).await, before + 1, "{}", text);
+    assert!(member_exists(&h.pool, "jane@example.com").await);
+}
+
+#[tokio::test]
+async fn import_remapped_column_imports() {
+    let h = build_harness().await;
+    let before = member_count(&h.pool).await;
+
+    // `E-mail Address` and `Name` don't match; the rest do.
+    let csv = "E-mail Address,username,Name,membership_type_slug\n\
+               alice@example.com,alice,Alice A.,regular\n";
+    let resp = h
+        .app
+        .clone()
+        .oneshot(import_request(&h, "roster.csv", csv.as_bytes()))
+        .await
+        .unwrap();
+    let form = body_text(resp).await;
+    assert!(form.contains("Match your columns"), "{}", form);
+    assert!(form.contains("email, full_name"), "{}", form);
+    assert_eq!(member_count(&h.pool).await, before);
+    let csv_data = hidden_value(&form, "csv_data");
+
+    let submit = |fields: Vec<(&str, &str)>| {
+        let mut all = vec![
+            ("csrf_token", h.csrf_token.as_str()),
+            ("mapping", "1"),
+            ("file_name", "roster.csv"),
+            ("csv_data", csv_data.as_str()),
+        ];
+        all.extend(fields);
+        let (ct, body) = build_text_multipart(&all);
+        Request::builder()
+            .method("POST")
+            .uri("/portal/admin/members/import")
+            .header(header::COOKIE, &h.session_cookie)
+            .header(header::CONTENT_TYPE, ct)
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    // A mapping that still leaves a required field unset asks again.
+    let resp = h
+        .app
+        .clone()
+        .oneshot(submit(vec![
+            ("map_email", "0"),
+            ("map_username", "1"),
+            ("map_full_name", ""),
+            ("map_membership_type_slug", "3"),
+        ]))
+        .await
+        .unwrap();
+    let text = body_text(resp).await;
+    assert!(
+        text.contains("Missing required columns: full_name"),
+        "{}",
+        text
+    );
+    assert_eq!(member_count(&h.pool).await, before);
+
+    let resp = h
+        .app
+        .clone()
+        .oneshot(submit(vec![
+            ("map_email", "0"),
+            ("map_username", "1"),
+            ("map_full_name", "2"),
+            ("map_membership_type_slug", "3"),
+            ("map_notes", ""),
+        ]))
+        .await
+        .unwrap();
+    let text = body_text(resp).await;
+    assert_eq!(member_count(&h.pool).await, before + 1, "{}", text);
+    let full_name: (String,) =
+        sqlx::query_as("SELECT full_name FROM members WHERE email = 'alice@example.com'")
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert_eq!(full_name.0, "Alice A.");
+}
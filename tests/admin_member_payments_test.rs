@@ -0,0 +1,173 @@
+//! Admin member-detail payment history is paginated: pages don't
+//! overlap, a "Load more" button chains to the next page until the
+//! last, and the summary totals cover every payment on file rather
+//! than the page on screen. A refund from the list records and shows
+//! when it happened.
+//!
+//! Run with: cargo test --features test-utils --test admin_member_payments_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    auth::CsrfService,
+    domain::{Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus},
+    repository::{
+        payment_repository::MemberPaymentTotals, PaymentRepository, SqlitePaymentRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, make_member, member_session, SESSION_SECRET};
+
+/// 25 payments of $10 for `member`, one a day, the oldest first; every
+/// fifth one is Refunded, the rest Completed. Returns ids newest first.
+async fn seed_payments(pool: &SqlitePool, member: Uuid) -> Vec<Uuid> {
+    let repo = SqlitePaymentRepository::new(pool.clone());
+    let start = Utc::now() - Duration::days(30);
+    let mut ids = Vec::new();
+    for i in 0..25 {
+        let created = start + Duration::days(i);
+        let status = if i % 5 == 0 {
+            PaymentStatus::Refunded
+        } else {
+            PaymentStatus::Completed
+        };
+        let payment = repo
+            .create(Payment {
+                id: Uuid::new_v4(),
+                payer: Payer::Member(member),
+                amount_cents: 10_00,
+                currency: "USD".to_string(),
+                status,
+                payment_method: PaymentMethod::Manual,
+                external_id: None,
+                description: format!("Payment {i}"),
+                kind: PaymentKind::Membership,
+                paid_at: Some(created),
+                refunded_at: None,
+                created_at: created,
+                updated_at: created,
+            })
+            .await
+            .unwrap();
+        ids.push(payment.id);
+    }
+    ids.reverse();
+    ids
+}
+
+#[tokio::test]
+async fn pages_cover_every_payment_once_and_totals_cover_all() {
+    let pool = fresh_pool().await;
+    let repo = SqlitePaymentRepository::new(pool.clone());
+    let member = make_member(&pool).await;
+    let newest_first = seed_payments(&pool, member).await;
+    // Someone else's payments stay out of both.
+    seed_payments(&pool, make_member(&pool).await).await;
+
+    let first = repo.find_by_member_paginated(member, 20, 0).await.unwrap();
+    let second = repo.find_by_member_paginated(member, 20, 20).await.unwrap();
+    assert_eq!(first.len(), 20);
+    assert_eq!(second.len(), 5);
+    let paged: Vec<Uuid> = first.iter().chain(&second).map(|p| p.id).collect();
+    assert_eq!(paged, newest_first);
+
+    // 20 Completed at $10; the 5 Refunded aren't collected money.
+    assert_eq!(
+        repo.member_payment_totals(member).await.unwrap(),
+        MemberPaymentTotals {
+            payment_count: 25,
+            completed_cents: 20_000,
+        }
+    );
+    assert_eq!(
+        repo.member_payment_totals(Uuid::new_v4()).await.unwrap(),
+        MemberPaymentTotals::default()
+    );
+}
+
+async fn get(app: &Router, uri: &str, cookie: &str) -> String {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "GET {}", uri);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn payment_history_loads_more_and_totals_every_payment() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (_, _, admin) = member_session(&pool, true).await;
+    let member = make_member(&pool).await;
+    seed_payments(&pool, member).await;
+
+    let uri = format!("/portal/admin/members/{}/payments", member);
+    let first = get(&app, &uri, &admin).await;
+    assert!(first.contains("25 payments"), "{first}");
+    assert!(first.contains("$200.00 collected"), "{first}");
+    assert_eq!(first.matches("Payment ").count(), 20);
+    assert!(first.contains("Payment 24"), "newest first");
+    assert!(first.contains(&format!("{uri}?page=2")), "{first}");
+
+    let second = get(&app, &format!("{uri}?page=2"), &admin).await;
+    assert_eq!(second.matches("Payment ").count(), 5);
+    assert!(second.contains("Payment 0"));
+    assert!(!second.contains("collected"), "summary only above page 1");
+    assert!(!second.contains("Load more"), "page 2 is the last");
+}
+
+#[tokio::test]
+async fn refund_from_the_history_stamps_and_shows_the_refund_date() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (_, session_id, admin) = member_session(&pool, true).await;
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+    let member = make_member(&pool).await;
+    let newest = seed_payments(&pool, member).await[0];
+    let repo = SqlitePaymentRepository::new(pool.clone());
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/portal/admin/payments/{newest}/refund"))
+                .header("Cookie", &admin)
+                .header("X-CSRF-Token", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let refunded = repo.find_by_id(newest).await.unwrap().unwrap();
+    assert_eq!(refunded.status, PaymentStatus::Refunded);
+    let refunded_at = refunded.refunded_at.expect("refund is stamped");
+    assert!(Utc::now() - refunded_at < Duration::minutes(1));
+
+    let uri = format!("/portal/admin/members/{member}/payments");
+    let history = get(&app, &uri, &admin).await;
+    let on = format!("Refunded {}", refunded_at.format("%B %d, %Y"));
+    assert_eq!(history.matches(&on).count(), 1, "{history}");
+}
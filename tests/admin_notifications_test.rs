@@ -0,0 +1,284 @@
+//! Admin notification routing: a public signup notifies each admin on
+//! their own channel, the recipients setting and per-admin opt-out
+//! narrow who hears about it, repeats of the same event are deduped,
+//! and digest mode queues notifications until the daily sweep.
+//!
+//! Run with: cargo test --features test-utils --test admin_notifications_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{AdminNotificationChannel, AdminNotificationKind, MemberStatus, UpdateMemberRequest},
+    email::{EmailMessage, EmailSender},
+    error::Result as CoterieResult,
+    integrations::{
+        admin_notifications::AdminNotificationIntegration, IntegrationEvent, IntegrationManager,
+    },
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::admin_notification_service::AdminNotificationService,
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member_with_email};
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+impl FakeEmailSender {
+    async fn recipients(&self) -> Vec<String> {
+        let mut to: Vec<String> = self
+            .sent
+            .lock()
+            .await
+            .iter()
+            .map(|m| m.to.clone())
+            .collect();
+        to.sort();
+        to
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    integrations: Arc<IntegrationManager>,
+    service: Arc<AdminNotificationService>,
+    email: Arc<FakeEmailSender>,
+    /// (id, email) of two admins, sorted by email.
+    admins: Vec<(Uuid, String)>,
+}
+
+async fn active_member(pool: &SqlitePool, is_admin: bool) -> (Uuid, String) {
+    let (id, email) = make_member_with_email(pool).await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    repo.update(
+        id,
+        UpdateMemberRequest {
+            status: Some(MemberStatus::Active),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    if is_admin {
+        repo.set_admin(id, true).await.unwrap();
+    }
+    (id, email)
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let email = Arc::new(FakeEmailSender::default());
+    let service = Arc::new(AdminNotificationService::new(
+        pool.clone(),
+        state.service_context.settings_service.clone(),
+        email.clone(),
+        "http://localhost:8080".to_string(),
+    ));
+    let integrations = state.service_context.integration_manager.clone();
+    integrations
+        .register(Arc::new(AdminNotificationIntegration::new(service.clone())))
+        .await;
+
+    let mut admins = vec![
+        active_member(&pool, true).await,
+        active_member(&pool, true).await,
+    ];
+    admins.sort_by(|a, b| a.1.cmp(&b.1));
+    // A regular member never hears about admin events.
+    active_member(&pool, false).await;
+
+    H {
+        pool,
+        app: coterie::api::create_app(state),
+        integrations,
+        service,
+        email,
+        admins,
+    }
+}
+
+async fn set_setting(pool: &SqlitePool, key: &str, value: &str) {
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = ?")
+        .bind(value)
+        .bind(key)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn signup(h: &H, username: &str, full_name: &str) -> StatusCode {
+    let body = serde_json::json!({
+        "email": format!("{}@example.com", username),
+        "username": username,
+        "full_name": full_name,
+        "password": "Correct-horse-battery-9",
+    });
+    h.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/public/signup")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+fn proposal(key: &str) -> IntegrationEvent {
+    IntegrationEvent::AdminNotification {
+        kind: AdminNotificationKind::ProposalSubmitted,
+        key: key.to_string(),
+        subject: "Event proposed: Lockpicking night".to_string(),
+        body: "Someone proposed an event.".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn new_signup_notifies_every_admin_once() {
+    let h = harness().await;
+
+    assert_eq!(signup(&h, "ada", "Ada Newcomer").await, StatusCode::CREATED);
+
+    let expected: Vec<String> = h.admins.iter().map(|a| a.1.clone()).collect();
+    assert_eq!(h.email.recipients().await, expected);
+    let sent = h.email.sent.lock().await.clone();
+    assert!(
+        sent[0].subject.contains("New signup: Ada Newcomer"),
+        "{}",
+        sent[0].subject
+    );
+    assert!(sent[0].text_body.contains("@ada"), "{}", sent[0].text_body);
+
+    // The same occurrence dispatched again (a replay, a retried job)
+    // doesn't notify anyone a second time.
+    let member_id: String = sqlx::query_scalar("SELECT id FROM members WHERE username = 'ada'")
+        .fetch_one(&h.pool)
+        .await
+        .unwrap();
+    h.integrations
+        .handle_event(IntegrationEvent::AdminNotification {
+            kind: AdminNotificationKind::NewSignup,
+            key: format!("signup:{}", member_id),
+            subject: "New signup: Ada Newcomer".to_string(),
+            body: "again".to_string(),
+        })
+        .await;
+    assert_eq!(h.email.sent.lock().await.len(), 2);
+}
+
+#[tokio::test]
+async fn recipients_setting_and_opt_out_narrow_delivery() {
+    let h = harness().await;
+    let (first_id, _) = h.admins[0].clone();
+    let (second_id, second_email) = h.admins[1].clone();
+
+    // Only the second admin is a configured recipient.
+    set_setting(
+        &h.pool,
+        "notifications.admin_recipients",
+        &second_email.to_uppercase(),
+    )
+    .await;
+    h.integrations.handle_event(proposal("proposal:1")).await;
+    assert_eq!(h.email.recipients().await, vec![second_email.clone()]);
+
+    // Back to everyone, but the first admin has opted out.
+    set_setting(&h.pool, "notifications.admin_recipients", "").await;
+    h.service
+        .set_channel(first_id, AdminNotificationChannel::None)
+        .await
+        .unwrap();
+    assert_eq!(
+        h.service.channel(first_id).await.unwrap(),
+        AdminNotificationChannel::None
+    );
+    h.integrations.handle_event(proposal("proposal:2")).await;
+    assert_eq!(
+        h.email.recipients().await,
+        [second_email.clone(), second_email]
+    );
+    assert_eq!(
+        h.service.channel(second_id).await.unwrap(),
+        AdminNotificationChannel::Email,
+        "email is the default"
+    );
+
+    // Kinds missing from the events setting don't notify at all.
+    set_setting(&h.pool, "notifications.admin_events", "new_signup").await;
+    h.integrations.handle_event(proposal("proposal:3")).await;
+    assert_eq!(h.email.sent.lock().await.len(), 2);
+}
+
+#[tokio::test]
+async fn digest_mode_batches_instead_of_sending() {
+    let h = harness().await;
+    set_setting(&h.pool, "notifications.admin_digest", "true").await;
+
+    assert_eq!(
+        signup(&h, "grace", "Grace Newcomer").await,
+        StatusCode::CREATED
+    );
+    h.integrations.handle_event(proposal("proposal:1")).await;
+    assert!(
+        h.email.sent.lock().await.is_empty(),
+        "digest mode queues instead of sending"
+    );
+
+    // Nothing has waited a day yet.
+    assert_eq!(h.service.send_due_digests(Utc::now()).await.unwrap(), 0);
+    assert!(h.email.sent.lock().await.is_empty());
+
+    let tomorrow = Utc::now() + Duration::hours(25);
+    assert_eq!(h.service.send_due_digests(tomorrow).await.unwrap(), 2);
+    let expected: Vec<String> = h.admins.iter().map(|a| a.1.clone()).collect();
+    assert_eq!(h.email.recipients().await, expected);
+    let sent = h.email.sent.lock().await.clone();
+    for message in &sent {
+        assert!(
+            message.subject.contains("Daily digest: 2 notifications"),
+            "{}",
+            message.subject
+        );
+        assert!(
+            message.text_body.contains("New signups"),
+            "{}",
+            message.text_body
+        );
+        assert!(message.text_body.contains("New signup: Grace Newcomer"));
+        assert!(message.text_body.contains("Event proposals"));
+        assert!(message
+            .text_body
+            .contains("Event proposed: Lockpicking night"));
+    }
+
+    // Everything went out; the next sweep has nothing to send.
+    assert_eq!(h.service.send_due_digests(tomorrow).await.unwrap(), 0);
+    assert_eq!(h.email.sent.lock().await.len(), 2);
+}
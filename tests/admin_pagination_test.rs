@@ -0,0 +1,137 @@
+//! Admin list page size: `?per_page=` is honored, clamped to the
+//! maximum, and remembered for later requests via a browser-session
+//! cookie.
+//!
+//! Run with: cargo test --test admin_pagination_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, fresh_pool, make_member, member_session};
+
+/// Router plus an admin's session cookie, with `extra` more members
+/// on the list.
+async fn setup(extra: usize) -> (Router, String) {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    for _ in 0..extra {
+        make_member(&pool).await;
+    }
+    (app, member_session(&pool, true).await.2)
+}
+
+/// GET with the given cookies; returns (Set-Cookie, body).
+async fn get(app: &Router, uri: &str, cookies: &str) -> (Option<String>, String) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, cookies)
+                .header("HX-Request", "true")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+    let set_cookie = resp
+        .headers()
+        .get(header::SET_COOKIE)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (set_cookie, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn showing_up_to(n: usize) -> String {
+    format!("to <span class=\"font-medium\">{}</span>", n)
+}
+
+#[tokio::test]
+async fn requested_page_size_is_honored_and_remembered() {
+    let (app, session) = setup(7).await;
+
+    let (set_cookie, body) = get(&app, "/portal/admin/members?per_page=5", &session).await;
+    assert!(body.contains(&showing_up_to(5)), "{}", body);
+    assert!(body.contains("per_page=5"), "paging links keep the size");
+    let set_cookie = set_cookie.expect("choice is remembered");
+    assert!(set_cookie.starts_with("admin_per_page=5"), "{}", set_cookie);
+    assert!(
+        !set_cookie.contains("Max-Age"),
+        "lasts for the browser session"
+    );
+
+    // Without the parameter, the remembered size applies.
+    let remembered = format!("{}; admin_per_page=5", session);
+    let (_, body) = get(&app, "/portal/admin/members?page=2", &remembered).await;
+    assert!(body.contains(&showing_up_to(8)), "{}", body);
+    assert!(body.contains("of <span class=\"font-medium\">8</span>"));
+
+    // With neither, the default (20) fits everyone on one page.
+    let (set_cookie, body) = get(&app, "/portal/admin/members", &session).await;
+    assert!(set_cookie.is_none());
+    assert!(!body.contains("Showing"), "{}", body);
+}
+
+#[tokio::test]
+async fn page_size_is_clamped_to_the_maximum() {
+    let (app, session) = setup(0).await;
+
+    for uri in [
+        "/portal/admin/members?per_page=5000",
+        "/portal/admin/events?per_page=5000",
+        "/portal/admin/announcements?per_page=5000",
+    ] {
+        let (set_cookie, _) = get(&app, uri, &session).await;
+        let set_cookie = set_cookie.unwrap();
+        assert!(
+            set_cookie.starts_with("admin_per_page=100;"),
+            "{}: {}",
+            uri,
+            set_cookie
+        );
+    }
+
+    let (set_cookie, _) = get(&app, "/portal/admin/events?per_page=0", &session).await;
+    assert!(set_cookie.unwrap().starts_with("admin_per_page=1;"));
+}
+
+#[tokio::test]
+async fn size_outside_the_menu_stays_selected() {
+    let (app, session) = setup(0).await;
+
+    for uri in [
+        "/portal/admin/members?per_page=30",
+        "/portal/admin/events?per_page=30",
+        "/portal/admin/announcements?per_page=30",
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header(header::COOKIE, &session)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+        let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        // Otherwise the menu shows 20 and the next filter submit
+        // quietly resets the size.
+        assert!(
+            body.contains(r#"<option value="30" selected>30</option>"#),
+            "{}: {}",
+            uri,
+            body
+        );
+        assert!(!body.contains(r#"<option value="20" selected>"#), "{}", uri);
+    }
+}
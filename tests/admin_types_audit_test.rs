@@ -1,7 +1,8 @@
 //! Integration tests for the admin type-mutation handlers. Each of the
 //! six mutations (create/update/delete × {event-type, announcement-type,
 //! membership-type}) must write exactly one row to `audit_logs` with the
-//! expected action / entity_type / old_value / new_value. A final test
+//! expected action / entity_type / old_value / new_value, and updates
+//! must stamp the acting admin on the type's `updated_by`. A final test
 //! confirms the fire-and-forget contract: when the `audit_logs` table
 //! is unavailable (simulating a transient DB failure), the underlying
 //! type mutation still commits.
@@ -24,8 +25,8 @@ use coterie::{
     },
     domain::{BasicTypeKind, CreateBasicTypeRequest, CreateMembershipTypeRequest, Member},
     repository::{
-        BasicTypeRepository, MembershipTypeRepository, SqliteBasicTypeRepository,
-        SqliteMembershipTypeRepository,
+        AnnouncementRepository, BasicTypeRepository, MembershipTypeRepository,
+        SqliteAnnouncementRepository, SqliteBasicTypeRepository, SqliteMembershipTypeRepository,
     },
     service::{
         audit_service::AuditService, basic_type_service::BasicTypeService,
@@ -52,6 +53,7 @@ struct H {
     event_svc: Arc<BasicTypeService>,
     announcement_svc: Arc<BasicTypeService>,
     membership_svc: Arc<MembershipTypeService>,
+    announcements: Arc<dyn AnnouncementRepository>,
     audit: Arc<AuditService>,
     current_user: CurrentUser,
 }
@@ -71,17 +73,20 @@ async fn build_harness() -> H {
     let membership_repo: Arc<dyn MembershipTypeRepository> =
         Arc::new(SqliteMembershipTypeRepository::new(pool.clone()));
     let membership_svc = Arc::new(MembershipTypeService::new(membership_repo));
+    let announcements: Arc<dyn AnnouncementRepository> =
+        Arc::new(SqliteAnnouncementRepository::new(pool.clone()));
     let audit = Arc::new(AuditService::new(pool.clone()));
 
     let member_id = common::make_member(&pool).await;
     let member = fetch_member(&pool, member_id).await;
-    let current_user = CurrentUser { member };
+    let current_user = CurrentUser { member, locale: Default::default() };
 
     H {
         pool,
         event_svc,
         announcement_svc,
         membership_svc,
+        announcements,
         audit,
         current_user,
     }
@@ -148,6 +153,8 @@ fn basic_form(name: &str) -> BasicTypeForm {
         color: None,
         icon: None,
         is_active: Some("on".to_string()),
+        notify_members: Some("true".to_string()),
+        csrf_token: String::new(),
     }
 }
 
@@ -160,7 +167,9 @@ fn membership_form(name: &str) -> MembershipTypeForm {
         icon: None,
         fee_dollars: "10.00".to_string(),
         billing_period: "monthly".to_string(),
+        max_members: None,
         is_active: Some("on".to_string()),
+        csrf_token: String::new(),
     }
 }
 
@@ -195,6 +204,7 @@ async fn create_event_type_writes_audit_row() {
     let _ = admin_create_basic_type(
         State(EventBasicTypeService(h.event_svc.clone())),
         State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.announcements.clone()),
         State(h.audit.clone()),
         Extension(h.current_user.clone()),
         Path("event".to_string()),
@@ -219,6 +229,10 @@ async fn create_event_type_writes_audit_row() {
         row.actor_id.as_deref(),
         Some(h.current_user.member.id.to_string().as_str())
     );
+
+    let created = h.event_svc.get(id).await.unwrap().unwrap();
+    assert_eq!(created.created_by, Some(h.current_user.member.id));
+    assert_eq!(created.updated_by, Some(h.current_user.member.id));
 }
 
 #[tokio::test]
@@ -227,7 +241,7 @@ async fn update_event_type_writes_audit_row_with_old_and_new_names() {
 
     let created = h
         .event_svc
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Workshop".to_string(),
             slug: Some("workshop".to_string()),
             description: None,
@@ -242,6 +256,7 @@ async fn update_event_type_writes_audit_row_with_old_and_new_names() {
     let _ = admin_update_basic_type(
         State(EventBasicTypeService(h.event_svc.clone())),
         State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.announcements.clone()),
         State(h.audit.clone()),
         Extension(h.current_user.clone()),
         Path(("event".to_string(), created.id.to_string())),
@@ -255,6 +270,11 @@ async fn update_event_type_writes_audit_row_with_old_and_new_names() {
     assert_eq!(row.action, "update_event_type");
     assert_eq!(row.old_value.as_deref(), Some("Workshop"));
     assert_eq!(row.new_value.as_deref(), Some("Tournament"));
+
+    // The type itself records who edited it; it was seeded with no actor.
+    let updated = h.event_svc.get(created.id).await.unwrap().unwrap();
+    assert_eq!(updated.created_by, None);
+    assert_eq!(updated.updated_by, Some(h.current_user.member.id));
 }
 
 #[tokio::test]
@@ -263,7 +283,7 @@ async fn delete_event_type_writes_audit_row_with_old_name() {
 
     let created = h
         .event_svc
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Workshop".to_string(),
             slug: Some("workshop".to_string()),
             description: None,
@@ -301,6 +321,7 @@ async fn create_announcement_type_writes_audit_row() {
     let _ = admin_create_basic_type(
         State(EventBasicTypeService(h.event_svc.clone())),
         State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.announcements.clone()),
         State(h.audit.clone()),
         Extension(h.current_user.clone()),
         Path("announcement".to_string()),
@@ -324,7 +345,7 @@ async fn update_announcement_type_writes_audit_row() {
 
     let created = h
         .announcement_svc
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Newsletter".to_string(),
             slug: Some("newsletter".to_string()),
             description: None,
@@ -337,6 +358,7 @@ async fn update_announcement_type_writes_audit_row() {
     let _ = admin_update_basic_type(
         State(EventBasicTypeService(h.event_svc.clone())),
         State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.announcements.clone()),
         State(h.audit.clone()),
         Extension(h.current_user.clone()),
         Path(("announcement".to_string(), created.id.to_string())),
@@ -358,7 +380,7 @@ async fn delete_announcement_type_writes_audit_row() {
 
     let created = h
         .announcement_svc
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Newsletter".to_string(),
             slug: Some("newsletter".to_string()),
             description: None,
@@ -417,7 +439,7 @@ async fn update_membership_type_writes_audit_row() {
 
     let created = h
         .membership_svc
-        .create(CreateMembershipTypeRequest {
+        .create(None, CreateMembershipTypeRequest {
             name: "Annual".to_string(),
             slug: Some("annual".to_string()),
             description: None,
@@ -425,6 +447,7 @@ async fn update_membership_type_writes_audit_row() {
             icon: None,
             fee_cents: 1000,
             billing_period: "monthly".to_string(),
+            max_members: None,
         })
         .await
         .expect("seed membership type");
@@ -444,6 +467,10 @@ async fn update_membership_type_writes_audit_row() {
     assert_eq!(row.action, "update_membership_type");
     assert_eq!(row.old_value.as_deref(), Some("Annual"));
     assert_eq!(row.new_value.as_deref(), Some("Premium"));
+
+    let updated = h.membership_svc.get(created.id).await.unwrap().unwrap();
+    assert_eq!(updated.created_by, None);
+    assert_eq!(updated.updated_by, Some(h.current_user.member.id));
 }
 
 #[tokio::test]
@@ -452,7 +479,7 @@ async fn delete_membership_type_writes_audit_row() {
 
     let created = h
         .membership_svc
-        .create(CreateMembershipTypeRequest {
+        .create(None, CreateMembershipTypeRequest {
             name: "Annual".to_string(),
             slug: Some("annual".to_string()),
             description: None,
@@ -460,6 +487,7 @@ async fn delete_membership_type_writes_audit_row() {
             icon: None,
             fee_cents: 1000,
             billing_period: "monthly".to_string(),
+            max_members: None,
         })
         .await
         .expect("seed membership type");
@@ -499,6 +527,7 @@ async fn type_mutation_commits_when_audit_insert_fails() {
     let _ = admin_create_basic_type(
         State(EventBasicTypeService(h.event_svc.clone())),
         State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.announcements.clone()),
         State(h.audit.clone()),
         Extension(h.current_user.clone()),
         Path("event".to_string()),
@@ -0,0 +1,167 @@
+//! `POST /portal/admin/types/seed-all` restores the default event,
+//! announcement, and membership types in one call. Each seeder skips
+//! slugs that already exist, so a second run creates nothing and
+//! reports every default as skipped.
+//!
+//! Like `admin_types_audit_test`, these call the handler directly via
+//! its extractors rather than spinning up the router.
+
+use std::sync::Arc;
+
+use axum::{body::to_bytes, extract::State, Extension};
+use coterie::{
+    api::{
+        middleware::auth::CurrentUser,
+        state::{AnnouncementBasicTypeService, EventBasicTypeService},
+    },
+    domain::{BasicTypeKind, UpdateBasicTypeRequest},
+    repository::{
+        BasicTypeRepository, MemberRepository, SqliteBasicTypeRepository,
+        SqliteMembershipTypeRepository, SqliteMemberRepository,
+    },
+    service::{
+        audit_service::AuditService, basic_type_service::BasicTypeService,
+        configurable_types::SeedReport, membership_type_service::MembershipTypeService,
+    },
+    web::portal::admin::types::admin_seed_all_types,
+};
+use sqlx::SqlitePool;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+struct H {
+    pool: SqlitePool,
+    event_svc: Arc<BasicTypeService>,
+    announcement_svc: Arc<BasicTypeService>,
+    membership_svc: Arc<MembershipTypeService>,
+    audit: Arc<AuditService>,
+    current_user: CurrentUser,
+}
+
+/// Migrated pool with no default types left, plus an admin to act as.
+/// The admin's own membership type can't be deleted (members need
+/// one), so it's renamed off the default slug instead.
+async fn build_harness() -> H {
+    let pool = fresh_pool().await;
+    let member_id = make_member(&pool).await;
+    for table in ["event_types", "announcement_types"] {
+        sqlx::query(&format!("DELETE FROM {}", table))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+    sqlx::query(
+        "DELETE FROM membership_types WHERE id NOT IN (SELECT membership_type_id FROM members)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE membership_types SET name = 'Staff', slug = 'staff'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let basic_repo: Arc<dyn BasicTypeRepository> =
+        Arc::new(SqliteBasicTypeRepository::new(pool.clone()));
+    let event_svc = Arc::new(BasicTypeService::new(basic_repo.clone(), BasicTypeKind::Event));
+    let announcement_svc = Arc::new(BasicTypeService::new(basic_repo, BasicTypeKind::Announcement));
+    let membership_svc = Arc::new(MembershipTypeService::new(Arc::new(
+        SqliteMembershipTypeRepository::new(pool.clone()),
+    )));
+
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let member = repo.set_admin(member_id, true).await.unwrap();
+
+    H {
+        audit: Arc::new(AuditService::new(pool.clone())),
+        pool,
+        event_svc,
+        announcement_svc,
+        membership_svc,
+        current_user: CurrentUser { member, locale: Default::default() },
+    }
+}
+
+async fn seed_all(h: &H) -> String {
+    let resp = admin_seed_all_types(
+        State(EventBasicTypeService(h.event_svc.clone())),
+        State(AnnouncementBasicTypeService(h.announcement_svc.clone())),
+        State(h.membership_svc.clone()),
+        State(h.audit.clone()),
+        Extension(h.current_user.clone()),
+    )
+    .await;
+    String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap()
+}
+
+async fn count(pool: &SqlitePool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn seeding_twice_creates_defaults_once() {
+    let h = build_harness().await;
+
+    let first = seed_all(&h).await;
+    assert!(first.contains("Event types: 2 created, 0 skipped"), "{}", first);
+    assert!(first.contains("Announcement types: 2 created, 0 skipped"), "{}", first);
+    assert!(first.contains("Membership types: 3 created, 0 skipped"), "{}", first);
+    assert_eq!(count(&h.pool, "event_types").await, 2);
+    assert_eq!(count(&h.pool, "announcement_types").await, 2);
+    assert_eq!(count(&h.pool, "membership_types").await, 4);
+
+    let second = seed_all(&h).await;
+    assert!(second.contains("Event types: 0 created, 2 skipped"), "{}", second);
+    assert!(second.contains("Announcement types: 0 created, 2 skipped"), "{}", second);
+    assert!(second.contains("Membership types: 0 created, 3 skipped"), "{}", second);
+    assert!(second.contains("nothing to do"));
+    assert_eq!(count(&h.pool, "event_types").await, 2);
+    assert_eq!(count(&h.pool, "announcement_types").await, 2);
+    assert_eq!(count(&h.pool, "membership_types").await, 4);
+
+    // Only the run that changed something is audited.
+    let audited: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM audit_logs WHERE action = 'seed_default_types'")
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert_eq!(audited, 1);
+}
+
+#[tokio::test]
+async fn seeding_fills_gaps_and_leaves_edited_defaults_alone() {
+    let h = build_harness().await;
+    seed_all(&h).await;
+
+    // An admin renamed one default and deleted another.
+    let social = h.event_svc.get_by_slug("social").await.unwrap().unwrap();
+    h.event_svc
+        .update(
+            None,
+            social.id,
+            UpdateBasicTypeRequest {
+                name: Some("Hangouts".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    let news = h.announcement_svc.get_by_slug("news").await.unwrap().unwrap();
+    h.announcement_svc.delete(news.id).await.unwrap();
+
+    assert_eq!(
+        h.event_svc.seed_defaults().await.unwrap(),
+        SeedReport { created: 0, skipped: 2 }
+    );
+    assert_eq!(
+        h.announcement_svc.seed_defaults().await.unwrap(),
+        SeedReport { created: 1, skipped: 1 }
+    );
+    let social = h.event_svc.get_by_slug("social").await.unwrap().unwrap();
+    assert_eq!(social.name, "Hangouts");
+    assert!(h.announcement_svc.get_by_slug("news").await.unwrap().is_some());
+}
@@ -0,0 +1,382 @@
+//! Announcement comment threads: members post on
+//! `/portal/announcements/:id`, the thread shows on the public API only
+//! for public announcements, admins hide and delete comments from the
+//! admin announcement page, and posting is rate-limited per member.
+//!
+//! Run with: cargo test --features test-utils --test announcement_comments_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    auth::CsrfService,
+    domain::{Announcement, AnnouncementType},
+    error::AppError,
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+    service::announcement_comment_service::{AnnouncementCommentService, COMMENT_RATE_LIMIT},
+};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, build_router, fresh_pool, member_session, SESSION_SECRET};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    comments: Arc<AnnouncementCommentService>,
+    admin: Uuid,
+    admin_login: Login,
+    member: Uuid,
+    member_login: Login,
+}
+
+/// A session cookie and a CSRF token bound to it.
+struct Login {
+    cookie: String,
+    csrf: String,
+}
+
+async fn login(pool: &SqlitePool, is_admin: bool) -> (Uuid, Login) {
+    let (id, session_id, cookie) = member_session(pool, is_admin).await;
+    let csrf = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+    (id, Login { cookie, csrf })
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let comments = state.service_context.announcement_comment_service.clone();
+    let app = build_router(state);
+    let (admin, admin_login) = login(&pool, true).await;
+    let (member, member_login) = login(&pool, false).await;
+    H {
+        pool,
+        app,
+        comments,
+        admin,
+        admin_login,
+        member,
+        member_login,
+    }
+}
+
+async fn seed(h: &H, is_public: bool, comments_enabled: bool) -> Announcement {
+    let now = Utc::now();
+    SqliteAnnouncementRepository::new(h.pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: "Workshop recap".to_string(),
+            content: "Thanks for coming".to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public,
+            featured: false,
+            image_url: None,
+            published_at: Some(now - Duration::days(1)),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled,
+            notify_members: None,
+            notified_at: None,
+            created_by: h.admin,
+            created_at: now - Duration::days(1),
+            updated_at: now - Duration::days(1),
+        })
+        .await
+        .unwrap()
+}
+
+async fn call(
+    h: &H,
+    method: Method,
+    uri: &str,
+    login: Option<&Login>,
+    form: Option<&str>,
+) -> (StatusCode, String) {
+    let mut req = Request::builder().method(method).uri(uri);
+    if let Some(login) = login {
+        req = req
+            .header(header::COOKIE, &login.cookie)
+            .header("X-CSRF-Token", &login.csrf);
+    }
+    let body = match form {
+        Some(form) => {
+            req = req.header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+            Body::from(form.to_string())
+        }
+        None => Body::empty(),
+    };
+    let resp = h
+        .app
+        .clone()
+        .oneshot(req.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+async fn public_comments(h: &H, announcement_id: Uuid) -> (StatusCode, Value) {
+    let (status, body) = call(
+        h,
+        Method::GET,
+        &format!("/public/announcements/{}/comments", announcement_id),
+        None,
+        None,
+    )
+    .await;
+    (status, serde_json::from_str(&body).unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn member_posts_comment_and_it_renders_sanitized() {
+    let h = harness().await;
+    let announcement = seed(&h, true, true).await;
+
+    let (status, thread) = call(
+        &h,
+        Method::POST,
+        &format!("/portal/announcements/{}/comments", announcement.id),
+        Some(&h.member_login),
+        Some("body=**Great**+session+%3Cscript%3Ealert(1)%3C%2Fscript%3E"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        thread.contains("<strong>Great</strong> session"),
+        "{}",
+        thread
+    );
+    assert!(thread.contains("&lt;script&gt;"), "{}", thread);
+    assert!(!thread.contains("<script>alert"), "{}", thread);
+
+    let (status, page) = call(
+        &h,
+        Method::GET,
+        &format!("/portal/announcements/{}", announcement.id),
+        Some(&h.member_login),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("<strong>Great</strong> session"), "{}", page);
+
+    let (status, json) = public_comments(&h, announcement.id).await;
+    assert_eq!(status, StatusCode::OK);
+    let list = json.as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(
+        list[0]["body"],
+        "**Great** session <script>alert(1)</script>"
+    );
+    assert!(list[0]["body_html"]
+        .as_str()
+        .unwrap()
+        .contains("<strong>Great</strong>"));
+    assert!(list[0].get("member_id").is_none());
+
+    let (status, thread) = call(
+        &h,
+        Method::POST,
+        &format!("/portal/announcements/{}/comments", announcement.id),
+        Some(&h.member_login),
+        Some("body=+++"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(thread.contains("Comment can&#x27;t be empty"), "{}", thread);
+}
+
+#[tokio::test]
+async fn hidden_and_deleted_comments_drop_out_of_member_views() {
+    let h = harness().await;
+    let announcement = seed(&h, true, true).await;
+    let comment = h
+        .comments
+        .post(h.member, announcement.id, "Off-topic rant")
+        .await
+        .unwrap();
+    let moderate = |action: &str| {
+        format!(
+            "/portal/admin/announcements/{}/comments/{}/{}",
+            announcement.id, comment.id, action
+        )
+    };
+
+    let (status, card) = call(
+        &h,
+        Method::POST,
+        &moderate("hide"),
+        Some(&h.admin_login),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        card.contains("Off-topic rant") && card.contains(">Hidden</span>"),
+        "{}",
+        card
+    );
+
+    let (_, json) = public_comments(&h, announcement.id).await;
+    assert_eq!(json.as_array().unwrap().len(), 0);
+    let (_, page) = call(
+        &h,
+        Method::GET,
+        &format!("/portal/announcements/{}", announcement.id),
+        Some(&h.member_login),
+        None,
+    )
+    .await;
+    assert!(!page.contains("Off-topic rant"), "{}", page);
+
+    call(
+        &h,
+        Method::POST,
+        &moderate("unhide"),
+        Some(&h.admin_login),
+        None,
+    )
+    .await;
+    let (_, json) = public_comments(&h, announcement.id).await;
+    assert_eq!(json.as_array().unwrap().len(), 1);
+
+    let (_, card) = call(
+        &h,
+        Method::POST,
+        &moderate("delete"),
+        Some(&h.admin_login),
+        None,
+    )
+    .await;
+    assert!(!card.contains("Off-topic rant"), "{}", card);
+    let (_, json) = public_comments(&h, announcement.id).await;
+    assert_eq!(json.as_array().unwrap().len(), 0);
+    let still_stored: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM comments WHERE id = ? AND deleted_at IS NOT NULL")
+            .bind(comment.id.to_string())
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert_eq!(still_stored, 1, "soft delete");
+
+    let audited: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM audit_logs WHERE entity_id = ? ORDER BY created_at, rowid",
+    )
+    .bind(comment.id.to_string())
+    .fetch_all(&h.pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        audited,
+        ["hide_comment", "unhide_comment", "delete_comment"]
+    );
+
+    let (status, _) = call(
+        &h,
+        Method::POST,
+        &moderate("hide"),
+        Some(&h.member_login),
+        None,
+    )
+    .await;
+    assert_ne!(status, StatusCode::OK, "members can't moderate");
+}
+
+#[tokio::test]
+async fn members_only_threads_stay_private() {
+    let h = harness().await;
+    let private = seed(&h, false, true).await;
+    h.comments
+        .post(h.member, private.id, "Door code changed")
+        .await
+        .unwrap();
+
+    let (status, json) = public_comments(&h, private.id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(!json.to_string().contains("Door code"), "{}", json);
+
+    let (status, page) = call(
+        &h,
+        Method::GET,
+        &format!("/portal/announcements/{}", private.id),
+        Some(&h.member_login),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("Door code changed"));
+
+    let (status, _) = call(
+        &h,
+        Method::GET,
+        &format!("/portal/announcements/{}", private.id),
+        None,
+        None,
+    )
+    .await;
+    assert_ne!(
+        status,
+        StatusCode::OK,
+        "signed-out visitors get no portal page"
+    );
+
+    // Comments off: no thread anywhere, and posting is refused.
+    let closed = seed(&h, true, false).await;
+    let (status, _) = public_comments(&h, closed.id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(matches!(
+        h.comments.post(h.member, closed.id, "hello").await,
+        Err(AppError::Forbidden)
+    ));
+}
+
+#[tokio::test]
+async fn posting_is_rate_limited_per_member() {
+    let h = harness().await;
+    let announcement = seed(&h, true, true).await;
+
+    for i in 0..COMMENT_RATE_LIMIT {
+        h.comments
+            .post(h.member, announcement.id, &format!("comment {}", i))
+            .await
+            .unwrap();
+    }
+    assert!(matches!(
+        h.comments.post(h.member, announcement.id, "one more").await,
+        Err(AppError::TooManyRequests)
+    ));
+
+    let (status, thread) = call(
+        &h,
+        Method::POST,
+        &format!("/portal/announcements/{}/comments", announcement.id),
+        Some(&h.member_login),
+        Some("body=one+more"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(thread.contains("commenting too quickly"), "{}", thread);
+    assert!(
+        thread.contains(">one more</textarea>"),
+        "draft kept: {}",
+        thread
+    );
+
+    // Someone else isn't affected.
+    h.comments
+        .post(h.admin, announcement.id, "admin reply")
+        .await
+        .unwrap();
+}
@@ -0,0 +1,163 @@
+//! Announcement expiry: once `expires_at` passes, an announcement drops
+//! out of the public list, the RSS feed, the members-only count and the
+//! member portal's recent list, but admins still see it (badged
+//! "Expired") and can edit it.
+//!
+//! Run with: cargo test --features test-utils --test announcement_expiry_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    admin: Uuid,
+    admin_cookie: String,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+
+    let (admin, _, admin_cookie) = member_session(&pool, true).await;
+
+    H {
+        pool,
+        app,
+        admin,
+        admin_cookie,
+    }
+}
+
+async fn seed(
+    h: &H,
+    title: &str,
+    is_public: bool,
+    expires_at: Option<DateTime<Utc>>,
+) -> Announcement {
+    let now = Utc::now();
+    SqliteAnnouncementRepository::new(h.pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            content: "Body".to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public,
+            featured: false,
+            image_url: None,
+            published_at: Some(now - Duration::days(2)),
+            scheduled_publish_at: None,
+            expires_at,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: h.admin,
+            created_at: now - Duration::days(2),
+            updated_at: now - Duration::days(2),
+        })
+        .await
+        .unwrap()
+}
+
+async fn get(h: &H, uri: &str, cookie: Option<&str>) -> (StatusCode, String) {
+    let mut req = Request::builder().uri(uri);
+    if let Some(cookie) = cookie {
+        req = req.header("Cookie", cookie);
+    }
+    let resp = h
+        .app
+        .clone()
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn expired_announcements_leave_public_listings() {
+    let h = harness().await;
+    let past = Utc::now() - Duration::hours(1);
+    let future = Utc::now() + Duration::days(3);
+    seed(&h, "Volunteers needed Saturday", true, Some(past)).await;
+    seed(&h, "Workshop signups open", true, Some(future)).await;
+    seed(&h, "Welcome to the portal", true, None).await;
+    seed(&h, "Members-only stale note", false, Some(past)).await;
+    seed(&h, "Members-only fresh note", false, None).await;
+
+    let (status, body) = get(&h, "/public/announcements", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains("Volunteers needed Saturday"), "{}", body);
+    assert!(body.contains("Workshop signups open"));
+    assert!(body.contains("Welcome to the portal"));
+
+    let (_, rss) = get(&h, "/public/feed/rss", None).await;
+    assert!(!rss.contains("Volunteers needed Saturday"), "{}", rss);
+    assert!(rss.contains("Workshop signups open"));
+
+    let (_, count) = get(&h, "/public/announcements/private-count", None).await;
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&count).unwrap()["count"],
+        1
+    );
+
+    let recent = SqliteAnnouncementRepository::new(h.pool.clone())
+        .list_recent(50)
+        .await
+        .unwrap();
+    let titles: Vec<&str> = recent.iter().map(|a| a.title.as_str()).collect();
+    assert!(!titles.contains(&"Volunteers needed Saturday"), "{:?}", titles);
+    assert!(!titles.contains(&"Members-only stale note"), "{:?}", titles);
+    assert_eq!(titles.len(), 3, "{:?}", titles);
+}
+
+#[tokio::test]
+async fn admins_still_see_expired_announcements() {
+    let h = harness().await;
+    let expired = seed(
+        &h,
+        "Volunteers needed Saturday",
+        true,
+        Some(Utc::now() - Duration::hours(1)),
+    )
+    .await;
+
+    let (status, list) = get(&h, "/portal/admin/announcements", Some(&h.admin_cookie)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(list.contains("Volunteers needed Saturday"), "{}", list);
+    assert!(list.contains(">Expired</span>"), "{}", list);
+
+    let (_, filtered) = get(
+        &h,
+        "/portal/admin/announcements?status=expired",
+        Some(&h.admin_cookie),
+    )
+    .await;
+    assert!(filtered.contains("Volunteers needed Saturday"));
+
+    let (status, detail) = get(
+        &h,
+        &format!("/portal/admin/announcements/{}", expired.id),
+        Some(&h.admin_cookie),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(detail.contains(r#"name="expires_at""#), "{}", detail);
+    assert!(detail.contains("Expired"));
+}
@@ -0,0 +1,213 @@
+//! The per-announcement "notify members" choice: whether publishing
+//! dispatches `AnnouncementPublished`, defaulting to the announcement
+//! type's setting, and at most once unless the admin asks again.
+//!
+//! Run: cargo test --features test-utils --test announcement_notify_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{AnnouncementType, CreateMemberRequest},
+    error::Result as CoterieResult,
+    integrations::{Integration, IntegrationEvent, IntegrationManager},
+    repository::{
+        AnnouncementRepository, MemberRepository, SqliteAnnouncementRepository,
+        SqliteMemberRepository,
+    },
+    service::{
+        announcement_admin_service::{AnnouncementAdminService, CreateAnnouncementInput},
+        audit_service::AuditService,
+    },
+};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+mod common;
+use common::fresh_pool;
+
+struct FakeIntegration {
+    events: Mutex<Vec<IntegrationEvent>>,
+}
+
+impl FakeIntegration {
+    async fn published(&self) -> usize {
+        self.events
+            .lock()
+            .await
+            .iter()
+            .filter(|e| matches!(e, IntegrationEvent::AnnouncementPublished(_)))
+            .count()
+    }
+}
+
+#[async_trait]
+impl Integration for FakeIntegration {
+    fn name(&self) -> &str {
+        "fake"
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+    async fn handle_event(&self, event: &IntegrationEvent) -> CoterieResult<()> {
+        self.events.lock().await.push(event.clone());
+        Ok(())
+    }
+}
+
+struct H {
+    pool: sqlx::SqlitePool,
+    repo: Arc<dyn AnnouncementRepository>,
+    service: AnnouncementAdminService,
+    fake: Arc<FakeIntegration>,
+    actor: Uuid,
+}
+
+async fn build() -> H {
+    let pool = fresh_pool().await;
+    let repo: Arc<dyn AnnouncementRepository> =
+        Arc::new(SqliteAnnouncementRepository::new(pool.clone()));
+    let manager = Arc::new(IntegrationManager::new());
+    let fake = Arc::new(FakeIntegration {
+        events: Mutex::new(Vec::new()),
+    });
+    manager.register(fake.clone() as Arc<dyn Integration>).await;
+    let service = AnnouncementAdminService::new(
+        repo.clone(),
+        Arc::new(AuditService::new(pool.clone())),
+        manager,
+    );
+    let actor = SqliteMemberRepository::new(pool.clone())
+        .create(CreateMemberRequest {
+            email: "author@example.com".to_string(),
+            username: "author".to_string(),
+            full_name: "Author".to_string(),
+            password: "p4ssword_long_enough".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .id;
+    H {
+        pool,
+        repo,
+        service,
+        fake,
+        actor,
+    }
+}
+
+fn input(publish_now: bool, notify_members: Option<bool>) -> CreateAnnouncementInput {
+    CreateAnnouncementInput {
+        title: "Club night moved".to_string(),
+        content: "Now on Thursday.".to_string(),
+        announcement_type: AnnouncementType::News,
+        announcement_type_id: None,
+        is_public: false,
+        featured: false,
+        image_url: None,
+        publish_now,
+        scheduled_publish_at: None,
+        expires_at: None,
+        comments_enabled: false,
+        notify_members,
+    }
+}
+
+#[tokio::test]
+async fn quiet_publish_sends_nothing() {
+    let h = build().await;
+
+    let created = h
+        .service
+        .create(h.actor, input(true, Some(false)))
+        .await
+        .unwrap();
+    assert!(created.published_at.is_some());
+
+    let draft = h.service.create(h.actor, input(false, None)).await.unwrap();
+    h.service
+        .publish(h.actor, draft.id, Some(false))
+        .await
+        .unwrap();
+
+    assert_eq!(h.fake.published().await, 0);
+    for id in [created.id, draft.id] {
+        let row = h.repo.find_by_id(id).await.unwrap().unwrap();
+        assert!(row.notified_at.is_none(), "nothing was sent");
+    }
+}
+
+#[tokio::test]
+async fn notify_broadcasts_once_unless_asked_again() {
+    let h = build().await;
+
+    let draft = h.service.create(h.actor, input(false, None)).await.unwrap();
+    h.service
+        .publish(h.actor, draft.id, Some(true))
+        .await
+        .unwrap();
+    assert_eq!(h.fake.published().await, 1);
+    let row = h.repo.find_by_id(draft.id).await.unwrap().unwrap();
+    assert!(row.notified_at.is_some());
+
+    // Publishing an already-published row is a no-op for integrations.
+    h.service
+        .publish(h.actor, draft.id, Some(true))
+        .await
+        .unwrap();
+    assert_eq!(h.fake.published().await, 1);
+
+    // Unpublish and publish again with no explicit choice: the row
+    // already notified, so it stays quiet.
+    h.service.unpublish(h.actor, draft.id).await.unwrap();
+    h.service.publish(h.actor, draft.id, None).await.unwrap();
+    assert_eq!(h.fake.published().await, 1);
+
+    // Asking again does send.
+    h.service.unpublish(h.actor, draft.id).await.unwrap();
+    h.service
+        .publish(h.actor, draft.id, Some(true))
+        .await
+        .unwrap();
+    assert_eq!(h.fake.published().await, 2);
+}
+
+#[tokio::test]
+async fn unset_choice_follows_the_type_default() {
+    let h = build().await;
+    let type_id: String =
+        sqlx::query_scalar("SELECT id FROM announcement_types WHERE slug = 'news'")
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    let type_id = Uuid::parse_str(&type_id).unwrap();
+    assert!(h.repo.type_notifies_members(type_id).await.unwrap());
+    h.repo
+        .set_type_notifies_members(type_id, false)
+        .await
+        .unwrap();
+
+    let mut quiet = input(false, None);
+    quiet.announcement_type_id = Some(type_id);
+    quiet.scheduled_publish_at = Some(Utc::now() + Duration::hours(1));
+    let scheduled = h.service.create(h.actor, quiet).await.unwrap();
+    sqlx::query("UPDATE announcements SET scheduled_publish_at = ? WHERE id = ?")
+        .bind((Utc::now() - Duration::minutes(5)).naive_utc())
+        .bind(scheduled.id.to_string())
+        .execute(&h.pool)
+        .await
+        .unwrap();
+    assert_eq!(h.service.publish_scheduled().await.unwrap(), 1);
+    assert_eq!(h.fake.published().await, 0);
+
+    // An explicit choice on the announcement beats the type.
+    let mut loud = input(true, Some(true));
+    loud.announcement_type_id = Some(type_id);
+    h.service.create(h.actor, loud).await.unwrap();
+    assert_eq!(h.fake.published().await, 1);
+}
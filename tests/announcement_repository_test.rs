@@ -0,0 +1,82 @@
+//! `SqliteAnnouncementRepository` round-trips an announcement's image
+//! and its configurable announcement type through create and update.
+//!
+//! Run with: cargo test --features test-utils --test announcement_repository_test
+
+use chrono::Utc;
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+async fn type_id(pool: &SqlitePool, slug: &str) -> Uuid {
+    let id: String = sqlx::query_scalar("SELECT id FROM announcement_types WHERE slug = ?")
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    Uuid::parse_str(&id).unwrap()
+}
+
+#[tokio::test]
+async fn image_url_and_announcement_type_id_survive_create_and_update() {
+    let pool = fresh_pool().await;
+    let repo = SqliteAnnouncementRepository::new(pool.clone());
+    let admin = make_member(&pool).await;
+    let news = type_id(&pool, "news").await;
+    let awards = type_id(&pool, "awards").await;
+
+    let now = Utc::now();
+    let created = repo
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: "New workshop".to_string(),
+            content: "Body".to_string(),
+            announcement_type: AnnouncementType::News,
+            announcement_type_id: Some(news),
+            is_public: true,
+            featured: false,
+            image_url: Some("/uploads/announcements/workshop.png".to_string()),
+            published_at: Some(now),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: admin,
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+
+    let found = repo.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(
+        found.image_url.as_deref(),
+        Some("/uploads/announcements/workshop.png")
+    );
+    assert_eq!(found.announcement_type_id, Some(news));
+
+    let mut edited = found;
+    edited.image_url = Some("/uploads/announcements/workshop-2.png".to_string());
+    edited.announcement_type_id = Some(awards);
+    repo.update(edited.id, edited).await.unwrap();
+
+    let found = repo.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(
+        found.image_url.as_deref(),
+        Some("/uploads/announcements/workshop-2.png")
+    );
+    assert_eq!(found.announcement_type_id, Some(awards));
+    let public = repo.list_public().await.unwrap();
+    let listed = public.iter().find(|a| a.id == created.id).unwrap();
+    assert_eq!(
+        listed.image_url.as_deref(),
+        Some("/uploads/announcements/workshop-2.png")
+    );
+}
@@ -1,10 +1,14 @@
 //! Integration tests for `AutoRenew::process_scheduled_payment`'s
-//! terminal-failure AdminAlert dispatch (a22).
+//! terminal-failure admin notification (a22). It goes out as a single
+//! `PaymentFailed` `AdminNotification`, not also as an `AdminAlert`.
+//! Also covers the payment lifecycle events the runner owes
+//! integrations: `PaymentCompleted` on a successful renewal and
+//! `PaymentFailed` once retries run out.
 //!
 //! Hits a real in-memory SQLite + migrations + `BillingService` so the
 //! actual production path executes end-to-end. The test
 //! `RecordingIntegration` captures every `IntegrationEvent` so the
-//! tests can assert which AdminAlerts fired (or didn't).
+//! tests can assert which notifications fired (or didn't).
 //!
 //! Run with: cargo test --features test-utils --test auto_renew_alert_test
 
@@ -13,7 +17,7 @@ use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use coterie::{
-    auth::SecretCrypto,
+    auth::{AuthService, SecretCrypto},
     domain::{CreateMemberRequest, SavedCard, ScheduledPayment, ScheduledPaymentStatus},
     email::{EmailMessage, EmailSender},
     error::{AppError, Result as CoterieResult},
@@ -63,18 +67,45 @@ impl Integration for RecordingIntegration {
     }
 }
 
-fn admin_alerts(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<(String, String)> {
+fn admin_notifications(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<(String, String)> {
     events
         .lock()
         .unwrap()
         .iter()
         .filter_map(|e| match e {
-            IntegrationEvent::AdminAlert { subject, body } => Some((subject.clone(), body.clone())),
+            IntegrationEvent::AdminNotification { subject, body, .. } => {
+                Some((subject.clone(), body.clone()))
+            }
             _ => None,
         })
         .collect()
 }
 
+fn admin_alert_count(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> usize {
+    events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| matches!(e, IntegrationEvent::AdminAlert { .. }))
+        .count()
+}
+
+/// Payment lifecycle events recorded so far, in dispatch order.
+fn payment_events(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<IntegrationEvent> {
+    events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| {
+            matches!(
+                e,
+                IntegrationEvent::PaymentCompleted(_) | IntegrationEvent::PaymentFailed(_)
+            )
+        })
+        .cloned()
+        .collect()
+}
+
 struct NoopEmailSender;
 
 #[async_trait]
@@ -141,6 +172,10 @@ async fn build_harness() -> Harness {
         settings,
         email,
         integrations,
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
         Some(stripe_client),
         "http://localhost:3000".to_string(),
         pool.clone(),
@@ -265,7 +300,7 @@ async fn scheduled_status(pool: &SqlitePool, id: Uuid) -> String {
 // ---------------------------------------------------------------------
 
 #[tokio::test]
-async fn terminal_failure_dispatches_admin_alert() {
+async fn terminal_failure_notifies_admins() {
     let h = build_harness().await;
     let (member_id, mt_id) = seed_coterie_managed_member(&h.pool).await;
     seed_default_card(&h.saved_card_repo, member_id).await;
@@ -291,15 +326,22 @@ async fn terminal_failure_dispatches_admin_alert() {
         "row must transition to Failed on terminal retry"
     );
 
-    let alerts = admin_alerts(&h.recorded_events);
-    assert!(
-        !alerts.is_empty(),
-        "expected at least one AdminAlert on terminal failure, got none"
+    let notifications = admin_notifications(&h.recorded_events);
+    assert_eq!(
+        notifications.len(),
+        1,
+        "expected one admin notification on terminal failure, got {:?}",
+        notifications,
     );
-    let terminal = alerts
+    assert_eq!(
+        admin_alert_count(&h.recorded_events),
+        0,
+        "the notification replaces the org-wide AdminAlert, not joins it"
+    );
+    let terminal = notifications
         .iter()
         .find(|(s, _)| s.contains("Coterie-managed renewal failed (final)"))
-        .expect("alert subject must contain 'Coterie-managed renewal failed (final)'");
+        .expect("subject must contain 'Coterie-managed renewal failed (final)'");
     assert!(
         terminal.0.contains("Jane Smith"),
         "subject must include member name, got: {:?}",
@@ -335,7 +377,7 @@ async fn terminal_failure_dispatches_admin_alert() {
 }
 
 #[tokio::test]
-async fn transient_failure_does_not_dispatch_admin_alert() {
+async fn transient_failure_does_not_notify_admins() {
     let h = build_harness().await;
     let (member_id, mt_id) = seed_coterie_managed_member(&h.pool).await;
     seed_default_card(&h.saved_card_repo, member_id).await;
@@ -359,10 +401,80 @@ async fn transient_failure_does_not_dispatch_admin_alert() {
         "row must return to Pending for retry on a non-terminal failure"
     );
 
-    let alerts = admin_alerts(&h.recorded_events);
+    let notifications = admin_notifications(&h.recorded_events);
+    assert!(
+        notifications.is_empty(),
+        "no admin notification should be sent on a transient retry; got {:?}",
+        notifications,
+    );
+    assert_eq!(admin_alert_count(&h.recorded_events), 0);
+}
+
+#[tokio::test]
+async fn successful_renewal_announces_completed_payment() {
+    let h = build_harness().await;
+    let (member_id, mt_id) = seed_coterie_managed_member(&h.pool).await;
+    seed_default_card(&h.saved_card_repo, member_id).await;
+    let scheduled_id = seed_scheduled_payment(&h.scheduled_repo, member_id, mt_id, 0).await;
+
+    h.billing
+        .auto_renew
+        .process_scheduled_payment(scheduled_id)
+        .await
+        .expect("process_scheduled_payment");
+
+    assert_eq!(scheduled_status(&h.pool, scheduled_id).await, "completed");
+    let events = payment_events(&h.recorded_events);
+    assert_eq!(events.len(), 1, "expected one payment event, got {:?}", events);
+    match &events[0] {
+        IntegrationEvent::PaymentCompleted(p) => {
+            assert_eq!(p.amount_cents, 50_00);
+            assert_eq!(p.member_id(), Some(member_id));
+        }
+        other => panic!("expected PaymentCompleted, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn terminal_failure_announces_failed_payment() {
+    let h = build_harness().await;
+    let (member_id, mt_id) = seed_coterie_managed_member(&h.pool).await;
+    seed_default_card(&h.saved_card_repo, member_id).await;
+    let scheduled_id = seed_scheduled_payment(&h.scheduled_repo, member_id, mt_id, 2).await;
+    h.fake
+        .next_payment_intent_err(AppError::External("card_declined".to_string()));
+
+    h.billing
+        .auto_renew
+        .process_scheduled_payment(scheduled_id)
+        .await
+        .expect("process_scheduled_payment returns Ok even on terminal failure");
+
+    let events = payment_events(&h.recorded_events);
+    assert_eq!(events.len(), 1, "expected one payment event, got {:?}", events);
+    match &events[0] {
+        IntegrationEvent::PaymentFailed(p) => assert_eq!(p.member_id(), Some(member_id)),
+        other => panic!("expected PaymentFailed, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn transient_failure_announces_nothing() {
+    let h = build_harness().await;
+    let (member_id, mt_id) = seed_coterie_managed_member(&h.pool).await;
+    seed_default_card(&h.saved_card_repo, member_id).await;
+    let scheduled_id = seed_scheduled_payment(&h.scheduled_repo, member_id, mt_id, 0).await;
+    h.fake
+        .next_payment_intent_err(AppError::External("card_declined".to_string()));
+
+    h.billing
+        .auto_renew
+        .process_scheduled_payment(scheduled_id)
+        .await
+        .expect("process_scheduled_payment returns Ok on transient failure");
+
     assert!(
-        alerts.is_empty(),
-        "no AdminAlert should be dispatched on a transient retry; got {:?}",
-        alerts,
+        payment_events(&h.recorded_events).is_empty(),
+        "a retry still pending isn't a failed payment yet"
     );
 }
@@ -0,0 +1,153 @@
+//! Admin database backups: `POST /portal/admin/backup` returns a real
+//! SQLite file with the data that was live at the time, downloads are
+//! admin-only, and rotation keeps only the newest N snapshots.
+//!
+//! Run with: cargo test --features test-utils --test backup_test
+
+use std::{path::PathBuf, sync::Arc};
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    service::backup_service::BackupService,
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, build_router, make_member, member_session, SESSION_SECRET};
+
+/// A migrated, file-backed database plus the directory backups go to.
+/// `VACUUM INTO` writes through the source database's VFS, so the
+/// shared `:memory:` pool would produce an in-memory "file" — the real
+/// deployment is always on disk, and so is this.
+async fn file_pool() -> (SqlitePool, PathBuf) {
+    let root = std::env::temp_dir().join(format!("coterie-backup-test-{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&root).unwrap();
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&format!("sqlite://{}?mode=rwc", root.join("live.db").display()))
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    (pool, root)
+}
+
+async fn backup_app(pool: SqlitePool, dir: PathBuf) -> Router {
+    let mut state = build_app_state(pool.clone()).await;
+    state.backup_service = Arc::new(BackupService::new(pool, dir));
+
+    build_router(state)
+}
+
+#[tokio::test]
+async fn backup_endpoint_returns_an_openable_copy_of_current_data() {
+    let (pool, root) = file_pool().await;
+    let dir = root.join("backups");
+    let app = backup_app(pool.clone(), dir.clone()).await;
+    let (_, session_id, cookie) = member_session(&pool, true).await;
+    let marker_id = make_member(&pool).await;
+
+    let token = CsrfService::new(SESSION_SECRET).generate_token(&session_id).await.unwrap();
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/portal/admin/backup")
+                .header("Cookie", &cookie)
+                .header("X-CSRF-Token", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let disposition = resp.headers()[header::CONTENT_DISPOSITION].to_str().unwrap().to_string();
+    assert!(disposition.starts_with("attachment; filename=\"coterie-"), "{}", disposition);
+    let bytes = to_bytes(resp.into_body(), 64 * 1024 * 1024).await.unwrap();
+    assert!(bytes.starts_with(b"SQLite format 3\0"));
+
+    // Open the downloaded bytes as a database in their own right.
+    let copy = root.join("downloaded.sqlite");
+    std::fs::write(&copy, &bytes).unwrap();
+    let restored = SqlitePool::connect(&format!("sqlite://{}?mode=ro", copy.display()))
+        .await
+        .expect("backup opens as SQLite");
+    let (found,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM members WHERE id = ?")
+        .bind(marker_id.to_string())
+        .fetch_one(&restored)
+        .await
+        .unwrap();
+    assert_eq!(found, 1, "backup reflects the member created before it");
+    restored.close().await;
+
+    let (audited,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM audit_logs WHERE action = 'create_backup'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(audited, 1);
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn backups_are_not_downloadable_without_admin() {
+    let (pool, root) = file_pool().await;
+    let dir = root.join("backups");
+    let backup = BackupService::new(pool.clone(), dir.clone())
+        .create_backup()
+        .await
+        .unwrap();
+    let app = backup_app(pool.clone(), dir.clone()).await;
+    let uri = format!("/portal/admin/backup/{}", backup.name);
+
+    let anonymous = app
+        .clone()
+        .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_ne!(anonymous.status(), StatusCode::OK);
+
+    let (_, _, cookie) = member_session(&pool, false).await;
+    let member = app
+        .oneshot(
+            Request::builder()
+                .uri(&uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_ne!(member.status(), StatusCode::OK);
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[tokio::test]
+async fn rotation_keeps_the_newest_backups() {
+    let (pool, root) = file_pool().await;
+    let dir = root.join("backups");
+    let service = BackupService::new(pool, dir.clone());
+    for _ in 0..4 {
+        service.create_backup().await.unwrap();
+    }
+    let newest = service.list_backups().await.unwrap()[0].name.clone();
+
+    assert_eq!(service.rotate(2).await.unwrap(), 2);
+    let left = service.list_backups().await.unwrap();
+    assert_eq!(left.len(), 2);
+    assert_eq!(left[0].name, newest);
+
+    // Not due yet: the newest backup is seconds old.
+    assert!(service.run_scheduled(24, 2).await.unwrap().is_none());
+
+    std::fs::remove_dir_all(&root).ok();
+}
@@ -24,7 +24,7 @@ async fn delete_in_use_event_type_returns_event_type_display_name() -> anyhow::R
     let service = BasicTypeService::new(repo.clone(), BasicTypeKind::Event);
 
     let created = service
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Workshop".to_string(),
             slug: Some("workshop".to_string()),
             description: None,
@@ -90,7 +90,7 @@ async fn delete_in_use_announcement_type_returns_announcement_type_display_name(
     let service = BasicTypeService::new(repo.clone(), BasicTypeKind::Announcement);
 
     let created = service
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "News".to_string(),
             slug: Some("news".to_string()),
             description: None,
@@ -149,7 +149,7 @@ async fn list_event_and_announcement_kinds_query_disjoint_tables() -> anyhow::Re
     let announcement_service = BasicTypeService::new(repo.clone(), BasicTypeKind::Announcement);
 
     let event_a = event_service
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Workshop".to_string(),
             slug: Some("workshop".to_string()),
             description: None,
@@ -158,7 +158,7 @@ async fn list_event_and_announcement_kinds_query_disjoint_tables() -> anyhow::Re
         })
         .await?;
     let event_b = event_service
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "Social".to_string(),
             slug: Some("social".to_string()),
             description: None,
@@ -167,7 +167,7 @@ async fn list_event_and_announcement_kinds_query_disjoint_tables() -> anyhow::Re
         })
         .await?;
     let ann_a = announcement_service
-        .create(CreateBasicTypeRequest {
+        .create(None, CreateBasicTypeRequest {
             name: "News".to_string(),
             slug: Some("news".to_string()),
             description: None,
@@ -1,4 +1,4 @@
-//! Integration tests for the two repo methods that power the admin
+//! Integration tests for the repo methods that power the admin
 //! billing dashboard:
 //!
 //!   - `PaymentRepository::revenue_by_month` — sums Completed payment
@@ -6,20 +6,22 @@
 //!     months. Refunded / Pending / Failed are excluded.
 //!   - `ScheduledPaymentRepository::list_failures_since` — Failed
 //!     scheduled payments whose last attempt landed in [since, now].
+//!   - `MemberRepository::dues_projection` — renewal fees of Active,
+//!     non-exempt members bucketed by the month their dues run out.
 //!
 //! Run: cargo test --test billing_dashboard_test
 
 use std::sync::Arc;
 
-use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
 use coterie::{
     domain::{
         Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus, ScheduledPayment,
         ScheduledPaymentStatus, StripeRef,
     },
     repository::{
-        PaymentRepository, ScheduledPaymentRepository, SqlitePaymentRepository,
-        SqliteScheduledPaymentRepository,
+        MemberRepository, PaymentRepository, ScheduledPaymentRepository, SqliteMemberRepository,
+        SqlitePaymentRepository, SqliteScheduledPaymentRepository,
     },
 };
 use sqlx::SqlitePool;
@@ -51,6 +53,7 @@ async fn insert_completed_payment(
         description: "test".to_string(),
         kind,
         paid_at: Some(paid_at),
+        refunded_at: None,
         created_at: paid_at,
         updated_at: paid_at,
     };
@@ -380,3 +383,63 @@ async fn list_failures_since_orders_newest_first() {
         last = cur;
     }
 }
+
+// --------------------------------------------------------------------
+// dues_projection
+// --------------------------------------------------------------------
+
+/// Make `member` Active with dues running out at `until`, and set
+/// their membership type's fee to `fee_cents`.
+async fn set_dues(pool: &SqlitePool, member: Uuid, until: DateTime<Utc>, fee_cents: i64) {
+    sqlx::query("UPDATE members SET status = 'Active', dues_paid_until = ? WHERE id = ?")
+        .bind(until.naive_utc())
+        .bind(member.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE membership_types SET fee_cents = ?, billing_period = 'yearly'
+         WHERE id = (SELECT membership_type_id FROM members WHERE id = ?)",
+    )
+    .bind(fee_cents)
+    .bind(member.to_string())
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn dues_projection_counts_next_month_expiry_and_skips_exempt() {
+    let pool = fresh_pool().await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let now = Utc::now();
+    // Mid-month, so the bucket doesn't depend on today's date.
+    let next_month = (now + Months::new(1)).with_day(15).unwrap();
+
+    let renewing = make_member(&pool).await;
+    set_dues(&pool, renewing, next_month, 42_00).await;
+    let exempt = make_member(&pool).await;
+    set_dues(&pool, exempt, next_month, 42_00).await;
+    sqlx::query("UPDATE members SET bypass_dues = 1 WHERE id = ?")
+        .bind(exempt.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let honorary = make_member(&pool).await;
+    set_dues(&pool, honorary, next_month, 42_00).await;
+    sqlx::query("UPDATE members SET status = 'Honorary' WHERE id = ?")
+        .bind(honorary.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    // Outside the window.
+    let later = make_member(&pool).await;
+    set_dues(&pool, later, now + Months::new(13), 42_00).await;
+
+    let buckets = repo.dues_projection(now, now + Months::new(12)).await.unwrap();
+    assert_eq!(buckets.len(), 1, "{:?}", buckets);
+    let b = &buckets[0];
+    assert_eq!((b.year, b.month), (next_month.year(), next_month.month()));
+    assert_eq!(b.fee_cents, 42_00);
+    assert_eq!(b.member_count, 1);
+}
@@ -0,0 +1,177 @@
+//! `GET /public/feed/calendar` is an RFC 5545 calendar: content lines
+//! are CRLF-terminated and folded at 75 octets, each VEVENT carries its
+//! event id as the UID, times are UTC with a `Z`, and text values are
+//! escaped. Only `Public` events are included. The single-event
+//! download at `/public/events/:id/calendar` uses the same format and
+//! lets a logged-in member fetch the members-only events they can see.
+//!
+//! Run with: cargo test --test calendar_feed_test
+
+use std::collections::HashMap;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, TimeZone, Utc};
+use coterie::{
+    domain::{Event, EventVisibility},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, event, fresh_pool, make_member, member_session};
+
+async fn create_event(pool: &SqlitePool, title: &str, visibility: EventVisibility) -> Event {
+    let start = Utc::now() + Duration::days(10);
+    let start = Utc.timestamp_opt(start.timestamp(), 0).unwrap();
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: title.to_string(),
+            description: format!(
+                "Bring a laptop; we'll supply the locks.\nDoors open at 6, talk at 7. {}",
+                "Parking is behind the building. ".repeat(4)
+            ),
+            visibility,
+            start_time: start,
+            end_time: Some(start + Duration::hours(2)),
+            location: Some("Room 4, Main St".to_string()),
+            rsvp_required: false,
+            ..event(make_member(pool).await)
+        })
+        .await
+        .unwrap()
+}
+
+/// Unfold the calendar into its VEVENTs, each a map of property name
+/// to raw (still escaped) value.
+fn parse_vevents(ics: &str) -> Vec<HashMap<String, String>> {
+    let unfolded = ics.replace("\r\n ", "");
+    let mut events = Vec::new();
+    let mut current: Option<HashMap<String, String>> = None;
+    for line in unfolded.split("\r\n").filter(|l| !l.is_empty()) {
+        match line {
+            "BEGIN:VEVENT" => current = Some(HashMap::new()),
+            "END:VEVENT" => events.push(current.take().expect("END without BEGIN")),
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    let (name, value) = line.split_once(':').expect("NAME:value");
+                    props.insert(name.to_string(), value.to_string());
+                }
+            }
+        }
+    }
+    events
+}
+
+#[tokio::test]
+async fn calendar_feed_is_valid_ical_with_only_public_events() {
+    let pool = fresh_pool().await;
+    let event = create_event(&pool, "Lockpicking 101, part 2", EventVisibility::Public).await;
+    create_event(&pool, "Members Lockpicking", EventVisibility::MembersOnly).await;
+    create_event(&pool, "Board Budget", EventVisibility::AdminOnly).await;
+    let app = coterie::api::create_app(build_app_state(pool.clone()).await);
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/public/feed/calendar")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        "text/calendar; charset=utf-8"
+    );
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let ics = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"), "{}", ics);
+    assert!(ics.ends_with("END:VCALENDAR\r\n"), "{}", ics);
+    for line in ics.trim_end_matches("\r\n").split("\r\n") {
+        assert!(line.len() <= 75, "{} octets: {:?}", line.len(), line);
+    }
+    assert!(ics.contains("\r\n "), "the long description folds: {}", ics);
+
+    let vevents = parse_vevents(&ics);
+    assert_eq!(vevents.len(), 1, "{}", ics);
+    let vevent = &vevents[0];
+    assert_eq!(vevent["UID"], event.id.to_string());
+    assert_eq!(
+        vevent["DTSTART"],
+        event.start_time.format("%Y%m%dT%H%M%SZ").to_string()
+    );
+    assert_eq!(
+        vevent["DTEND"],
+        event.end_time.unwrap().format("%Y%m%dT%H%M%SZ").to_string()
+    );
+    assert!(vevent["DTSTAMP"].ends_with('Z'), "{}", vevent["DTSTAMP"]);
+    assert_eq!(vevent["SUMMARY"], "Lockpicking 101\\, part 2");
+    assert!(
+        vevent["DESCRIPTION"].starts_with(
+            "Bring a laptop\\; we'll supply the locks.\\nDoors open at 6\\, talk at 7."
+        ),
+        "{}",
+        vevent["DESCRIPTION"]
+    );
+    assert_eq!(vevent["LOCATION"], "Room 4\\, Main St");
+}
+
+async fn download(app: &axum::Router, id: Uuid, cookie: Option<&str>) -> axum::response::Response {
+    let mut req = Request::builder().uri(format!("/public/events/{}/calendar", id));
+    if let Some(cookie) = cookie {
+        req = req.header(header::COOKIE, cookie);
+    }
+    app.clone()
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn single_event_download_respects_visibility() {
+    let pool = fresh_pool().await;
+    let public = create_event(&pool, "Lockpicking 101, part 2", EventVisibility::Public).await;
+    let members = create_event(&pool, "Members Lockpicking", EventVisibility::MembersOnly).await;
+    let admins = create_event(&pool, "Board Budget", EventVisibility::AdminOnly).await;
+    let (_, _, cookie) = member_session(&pool, false).await;
+    let app = coterie::api::create_app(build_app_state(pool.clone()).await);
+
+    let resp = download(&app, public.id, None).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+        "attachment; filename=\"lockpicking-101-part-2.ics\""
+    );
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let vevents = parse_vevents(&String::from_utf8(body.to_vec()).unwrap());
+    assert_eq!(vevents.len(), 1);
+    assert_eq!(vevents[0]["UID"], public.id.to_string());
+
+    assert_eq!(
+        download(&app, members.id, None).await.status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        download(&app, members.id, Some(&cookie)).await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        download(&app, admins.id, None).await.status(),
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        download(&app, admins.id, Some(&cookie)).await.status(),
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(
+        download(&app, Uuid::new_v4(), None).await.status(),
+        StatusCode::NOT_FOUND
+    );
+}
@@ -0,0 +1,126 @@
+//! Birthday / anniversary shout-outs: who is due on a given day, and
+//! that the hourly sweep celebrates each occasion once.
+//!
+//! Run with: cargo test --test celebration_test
+
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use coterie::{
+    domain::{CelebrationKind, MemberStatus, MonthDay, UpdateMemberRequest},
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::celebration_service::CelebrationPreferences,
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+fn at(y: i32, m: u32, d: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_opt(10, 0, 0)
+        .unwrap()
+}
+
+/// An Active member who joined at `joined_at`.
+async fn member_joined(pool: &SqlitePool, joined_at: NaiveDateTime) -> Uuid {
+    let id = make_member(pool).await;
+    SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    sqlx::query("UPDATE members SET joined_at = ? WHERE id = ?")
+        .bind(joined_at)
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+#[tokio::test]
+async fn anniversary_today_is_selected_and_other_days_are_skipped() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let celebrations = &state.service_context.celebration_service;
+    let today = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+
+    let three_years = member_joined(&pool, at(2023, 10, 15)).await;
+    let other_day = member_joined(&pool, at(2023, 10, 18)).await;
+    let joined_today = member_joined(&pool, at(2026, 10, 15)).await;
+    let opted_out = member_joined(&pool, at(2024, 10, 15)).await;
+    celebrations
+        .set_preferences(
+            opted_out,
+            CelebrationPreferences {
+                birthday: None,
+                celebrate: false,
+            },
+        )
+        .await
+        .unwrap();
+    // Still Pending: not celebrated.
+    let pending = make_member(&pool).await;
+    sqlx::query("UPDATE members SET joined_at = ? WHERE id = ?")
+        .bind(at(2023, 10, 15))
+        .bind(pending.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    // Birthday today, anniversary another day.
+    celebrations
+        .set_preferences(
+            other_day,
+            CelebrationPreferences {
+                birthday: MonthDay::new(10, 15),
+                celebrate: true,
+            },
+        )
+        .await
+        .unwrap();
+
+    let due = celebrations.due_on(today).await.unwrap();
+    let found: Vec<(Uuid, CelebrationKind, Option<i32>)> =
+        due.iter().map(|c| (c.member_id, c.kind, c.years)).collect();
+
+    assert!(found.contains(&(three_years, CelebrationKind::Anniversary, Some(3))));
+    assert!(found.contains(&(other_day, CelebrationKind::Birthday, None)));
+    assert_eq!(found.len(), 2, "{:?}", found);
+    for skipped in [joined_today, opted_out, pending] {
+        assert!(!found.iter().any(|(id, _, _)| *id == skipped));
+    }
+}
+
+#[tokio::test]
+async fn sweep_celebrates_once_and_only_when_a_channel_is_on() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let celebrations = &state.service_context.celebration_service;
+    let now = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+    member_joined(&pool, at(2025, 10, 15)).await;
+
+    // Both channels off by default: nothing sent, nothing recorded.
+    assert_eq!(celebrations.send_due(now).await.unwrap(), 0);
+    assert_eq!(
+        celebrations.due_on(now.date_naive()).await.unwrap().len(),
+        1
+    );
+
+    sqlx::query("UPDATE app_settings SET value = 'true' WHERE key = 'celebrations.email_enabled'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(celebrations.send_due(now).await.unwrap(), 1);
+    assert_eq!(celebrations.send_due(now).await.unwrap(), 0, "once a year");
+    assert!(celebrations
+        .due_on(now.date_naive())
+        .await
+        .unwrap()
+        .is_empty());
+}
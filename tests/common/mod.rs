@@ -17,6 +17,8 @@
 
 use std::sync::Arc;
 
+use axum::Router;
+use chrono::{Duration, Utc};
 use coterie::{
     api::{
         middleware::bot_challenge::DisabledVerifier,
@@ -24,7 +26,10 @@ use coterie::{
     },
     auth::{AuthService, CsrfService, PendingLoginService, SecretCrypto, TotpService},
     config::Settings,
-    domain::CreateMemberRequest,
+    domain::{
+        CreateMemberRequest, Event, EventStatus, EventType, EventVisibility, MemberStatus,
+        UpdateMemberRequest,
+    },
     email::LogSender,
     integrations::IntegrationManager,
     repository::{
@@ -37,6 +42,10 @@ use coterie::{
 use sqlx::{Executor, SqlitePool};
 use uuid::Uuid;
 
+/// `auth.session_secret` in [`build_app_state`]. Tests that mint CSRF
+/// tokens or sessions by hand need the same secret.
+pub const SESSION_SECRET: &str = "test-session-secret-please-ignore";
+
 /// Fresh in-memory SQLite pool with all migrations applied and
 /// `PRAGMA foreign_keys = ON` enforced on every connection. Pool is
 /// pinned to a single connection because `sqlite::memory:` databases
@@ -90,23 +99,33 @@ pub async fn build_app_state(pool: SqlitePool) -> AppState {
             base_url: "http://127.0.0.1".to_string(),
             data_dir: "./data".to_string(),
             uploads_dir: None,
+            backups_dir: None,
             secure_cookies: Some(false),
             cors_origins: None,
             trust_forwarded_for: Some(false),
+            max_in_flight_requests: 0,
         },
         database: coterie::config::DatabaseConfig {
             url: "sqlite::memory:".to_string(),
             max_connections: 1,
         },
         auth: coterie::config::AuthConfig {
-            session_secret: "test-session-secret-please-ignore".to_string(),
+            session_secret: SESSION_SECRET.to_string(),
             session_duration_hours: 24,
+            session_idle_timeout_minutes: None,
             totp_issuer: "Coterie Test".to_string(),
+            csrf_token_ttl_hours: 12,
+            geoip_db_path: None,
+            session_store: Default::default(),
+            redis_url: None,
+            login_rate_limit: Default::default(),
         },
         stripe: Default::default(),
         integrations: Default::default(),
         seed: Default::default(),
         bot_challenge: Default::default(),
+        images: Default::default(),
+        security_headers: Default::default(),
     };
     let settings = Arc::new(settings);
 
@@ -172,6 +191,29 @@ pub async fn build_app_state(pool: SqlitePool) -> AppState {
     )
 }
 
+/// `create_app` and `create_web_routes` merged, without the outer
+/// layers `main` wraps them in. For tests that stack a layer of their
+/// own or that call a handler with no session to bind a CSRF token to;
+/// everything else wants [`build_router`].
+pub fn merged_routes(state: AppState) -> Router {
+    coterie::api::create_app(state.clone()).merge(coterie::web::create_web_routes(state))
+}
+
+/// [`merged_routes`] wrapped in the CSRF layer, as `main` serves them.
+/// Tests that swap something in the state first build it with
+/// [`build_app_state`] and hand it here; the rest call [`build_app`].
+pub fn build_router(state: AppState) -> Router {
+    merged_routes(state.clone()).layer(axum::middleware::from_fn_with_state(
+        state,
+        coterie::api::middleware::security::csrf_protect_unless_exempt,
+    ))
+}
+
+/// [`build_router`] over a default [`build_app_state`].
+pub async fn build_app(pool: &SqlitePool) -> Router {
+    build_router(build_app_state(pool.clone()).await)
+}
+
 /// Insert a fresh test member through `SqliteMemberRepository::create`
 /// and return its id. Email / username are randomized so successive
 /// calls in the same pool don't trip the uniqueness constraints.
@@ -210,3 +252,60 @@ pub async fn make_member_with_email(pool: &SqlitePool) -> (Uuid, String) {
     let email = member.email.clone();
     (member.id, email)
 }
+
+/// An Active member (and an admin when `is_admin`) with a live
+/// session. Returns `(member_id, session_id, cookie)`, the cookie
+/// ready for a `Cookie:` header.
+pub async fn member_session(pool: &SqlitePool, is_admin: bool) -> (Uuid, String, String) {
+    let id = make_member(pool).await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    repo.update(
+        id,
+        UpdateMemberRequest {
+            status: Some(MemberStatus::Active),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("activate member");
+    if is_admin {
+        repo.set_admin(id, true).await.expect("make admin");
+    }
+    let (session, token) = AuthService::new(pool.clone(), SESSION_SECRET.to_string())
+        .create_session(id, 24)
+        .await
+        .expect("create session");
+    (id, session.id, format!("session={}", token))
+}
+
+/// A published, members-only workshop by `created_by`, a week out and
+/// two hours long, RSVP required, with no capacity, deadline or
+/// registration group. Tests override what they care about with struct
+/// update syntax before handing it to `EventRepository::create`.
+pub fn event(created_by: Uuid) -> Event {
+    let start = Utc::now() + Duration::days(7);
+    Event {
+        id: Uuid::new_v4(),
+        title: "Test Event".to_string(),
+        description: String::new(),
+        event_type: EventType::Workshop,
+        event_type_id: None,
+        visibility: EventVisibility::MembersOnly,
+        start_time: start,
+        end_time: Some(start + Duration::hours(2)),
+        location: None,
+        max_attendees: None,
+        rsvp_required: true,
+        allow_guest_rsvp: false,
+        registration_group: None,
+        rsvp_deadline: None,
+        image_url: None,
+        created_by,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        series_id: None,
+        occurrence_index: None,
+        status: EventStatus::Published,
+        review_feedback: None,
+    }
+}
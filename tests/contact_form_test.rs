@@ -0,0 +1,223 @@
+//! Contact-the-admins form: a message sent from `/contact` (or the
+//! public API) lands in the admin inbox and notifies the admins, a
+//! filled-in honeypot is silently dropped, each IP gets five messages
+//! an hour, and the login page links to the form while it's enabled.
+//!
+//! Run with: cargo test --features test-utils --test contact_form_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    email::{EmailMessage, EmailSender},
+    error::Result as CoterieResult,
+    integrations::admin_notifications::AdminNotificationIntegration,
+    service::admin_notification_service::AdminNotificationService,
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, build_router, fresh_pool, member_session, SESSION_SECRET};
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    email: Arc<FakeEmailSender>,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let email = Arc::new(FakeEmailSender::default());
+    let service = Arc::new(AdminNotificationService::new(
+        pool.clone(),
+        state.service_context.settings_service.clone(),
+        email.clone(),
+        "http://localhost:8080".to_string(),
+    ));
+    state
+        .service_context
+        .integration_manager
+        .register(Arc::new(AdminNotificationIntegration::new(service)))
+        .await;
+    let app = build_router(state);
+    H { pool, app, email }
+}
+
+async fn send(h: &H, request: Request<Body>) -> (StatusCode, String) {
+    let resp = h.app.clone().oneshot(request).await.unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn contact_form(body: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::POST)
+        .uri("/contact")
+        .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn get(uri: &str, cookie: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().uri(uri);
+    if let Some(cookie) = cookie {
+        builder = builder.header(header::COOKIE, cookie);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn stored(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM contact_submissions")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+const LOCKED_OUT: &str = "name=Ada+Lovelace&email=ada%40example.com&category=cant_log_in\
+     &message=My+password+reset+email+never+arrives.&website=";
+
+#[tokio::test]
+async fn message_reaches_admin_inbox_and_notifies_admins() {
+    let h = harness().await;
+    let (_, session_id, cookie) = member_session(&h.pool, true).await;
+
+    let (status, body) = send(&h, contact_form(LOCKED_OUT)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Message sent"), "{}", body);
+
+    let (id, category): (String, String) =
+        sqlx::query_as("SELECT id, category FROM contact_submissions")
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert_eq!(category, "cant_log_in");
+
+    let sent = h.email.sent.lock().await.clone();
+    assert_eq!(sent.len(), 1, "the one admin is notified");
+    assert!(
+        sent[0].subject.contains("I can't log in"),
+        "{}",
+        sent[0].subject
+    );
+    assert!(
+        sent[0].text_body.contains("ada@example.com"),
+        "{}",
+        sent[0].text_body
+    );
+
+    let (status, page) = send(&h, get("/portal/admin/contact", Some(&cookie))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page.contains("My password reset email never arrives."));
+
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+    let (status, _) = send(
+        &h,
+        Request::builder()
+            .method(Method::POST)
+            .uri(format!("/portal/admin/contact/{}/handled", id))
+            .header(header::COOKIE, &cookie)
+            .header("X-CSRF-Token", token)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, page) = send(&h, get("/portal/admin/contact", Some(&cookie))).await;
+    assert!(!page.contains("My password reset email never arrives."));
+    assert!(page.contains("No messages waiting."));
+
+    let handled_by: Option<String> =
+        sqlx::query_scalar("SELECT handled_by FROM contact_submissions WHERE id = ?")
+            .bind(&id)
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert!(handled_by.is_some_and(|by| Uuid::parse_str(&by).is_ok()));
+}
+
+#[tokio::test]
+async fn honeypot_submission_looks_accepted_but_is_dropped() {
+    let h = harness().await;
+    member_session(&h.pool, true).await;
+
+    let body = serde_json::json!({
+        "name": "Totally Human",
+        "email": "bot@example.com",
+        "message": "Cheap watches",
+        "website": "http://spam.example",
+    });
+    let (status, _) = send(
+        &h,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/public/contact")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(stored(&h.pool).await, 0);
+    assert!(h.email.sent.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn sixth_message_in_an_hour_is_rate_limited() {
+    let h = harness().await;
+    for _ in 0..5 {
+        assert_eq!(send(&h, contact_form(LOCKED_OUT)).await.0, StatusCode::OK);
+    }
+    assert_eq!(
+        send(&h, contact_form(LOCKED_OUT)).await.0,
+        StatusCode::TOO_MANY_REQUESTS
+    );
+    assert_eq!(stored(&h.pool).await, 5);
+}
+
+#[tokio::test]
+async fn login_page_links_to_form_only_while_enabled() {
+    let h = harness().await;
+    let (_, page) = send(&h, get("/login", None)).await;
+    assert!(page.contains("/contact?category=cant_log_in"), "{}", page);
+
+    sqlx::query(
+        "UPDATE app_settings SET value = 'false' WHERE key = 'features.contact_form_enabled'",
+    )
+    .execute(&h.pool)
+    .await
+    .unwrap();
+    let (_, page) = send(&h, get("/login", None)).await;
+    assert!(!page.contains("/contact?category=cant_log_in"));
+    assert_eq!(
+        send(&h, contact_form(LOCKED_OUT)).await.0,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(stored(&h.pool).await, 0);
+}
@@ -36,13 +36,23 @@ use coterie::{
 use tower::ServiceExt;
 
 mod common;
-use common::fresh_pool;
+use common::{fresh_pool, make_member};
+use sqlx::SqlitePool;
 
 /// Build the full merged app the way `main.rs` does. The whole point
 /// of F9 is that a unit test of the middleware in isolation would pass
 /// even with the original bug in place — the regression only shows up
 /// at the routing layer where `.merge()` strips the layer.
 async fn build_app() -> Router {
+    build_app_with_csrf(CsrfService::new).await.0
+}
+
+/// Same app, but the caller picks how the CSRF service is configured
+/// (e.g. a short TTL) and gets the pool back to create members and
+/// sessions against.
+async fn build_app_with_csrf(
+    make_csrf: impl FnOnce(&str) -> CsrfService,
+) -> (Router, SqlitePool) {
     let pool = fresh_pool().await;
 
     // --- Settings (minimal hand-built; the on-disk Settings::new()
@@ -54,9 +64,11 @@ async fn build_app() -> Router {
             base_url: "http://127.0.0.1".to_string(),
             data_dir: "./data".to_string(),
             uploads_dir: None,
+            backups_dir: None,
             secure_cookies: Some(false),
             cors_origins: None,
             trust_forwarded_for: Some(false),
+            max_in_flight_requests: 0,
         },
         database: coterie::config::DatabaseConfig {
             url: "sqlite::memory:".to_string(),
@@ -65,12 +77,20 @@ async fn build_app() -> Router {
         auth: coterie::config::AuthConfig {
             session_secret: "test-session-secret-please-ignore".to_string(),
             session_duration_hours: 24,
+            session_idle_timeout_minutes: None,
             totp_issuer: "Coterie Test".to_string(),
+            csrf_token_ttl_hours: 12,
+            geoip_db_path: None,
+            session_store: Default::default(),
+            redis_url: None,
+            login_rate_limit: Default::default(),
         },
         stripe: Default::default(),
         integrations: Default::default(),
         seed: Default::default(),
         bot_challenge: Default::default(),
+        images: Default::default(),
+        security_headers: Default::default(),
     };
     let settings = Arc::new(settings);
 
@@ -89,7 +109,7 @@ async fn build_app() -> Router {
         pool.clone(),
         settings.auth.session_secret.clone(),
     ));
-    let csrf_service = Arc::new(CsrfService::new(&settings.auth.session_secret));
+    let csrf_service = Arc::new(make_csrf(&settings.auth.session_secret));
     let totp_service = Arc::new(TotpService::new(
         pool.clone(),
         crypto.clone(),
@@ -144,7 +164,7 @@ async fn build_app() -> Router {
     // (outermost). If F9 ever regresses (CSRF layered before merge),
     // the assertion in `portal_admin_post_without_csrf_returns_403`
     // will fail.
-    api_app
+    let app = api_app
         .merge(web_app)
         .layer(axum::middleware::from_fn_with_state(
             app_state.clone(),
@@ -153,7 +173,8 @@ async fn build_app() -> Router {
         .layer(axum::middleware::from_fn_with_state(
             app_state,
             coterie::api::middleware::security::csrf_protect_unless_exempt,
-        ))
+        ));
+    (app, pool)
 }
 
 #[tokio::test]
@@ -232,3 +253,79 @@ async fn api_payments_post_without_csrf_returns_403() {
         resp.status()
     );
 }
+
+/// Log a fresh member in and return `(session_id, cookie_header)`.
+async fn login(pool: &SqlitePool) -> (String, String) {
+    let member_id = make_member(pool).await;
+    let auth = AuthService::new(pool.clone(), "test-session-secret-please-ignore".to_string());
+    let (session, token) = auth.create_session(member_id, 24).await.unwrap();
+    (session.id, format!("session={}", token))
+}
+
+async fn post_with_token(app: Router, cookie: &str, token: &str) -> axum::response::Response {
+    let req = Request::builder()
+        .method("POST")
+        .uri("/portal/profile")
+        .header("Cookie", cookie)
+        .header("X-CSRF-Token", token)
+        .body(Body::empty())
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+async fn json_body(resp: axum::response::Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(resp.into_body(), 64 * 1024).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn expired_token_is_rejected_with_a_fresh_one() {
+    let (app, pool) = build_app_with_csrf(|secret| {
+        CsrfService::new(secret).with_ttl(chrono::Duration::seconds(1))
+    })
+    .await;
+    let (session_id, cookie) = login(&pool).await;
+    let csrf = CsrfService::new("test-session-secret-please-ignore")
+        .with_ttl(chrono::Duration::seconds(1));
+    let token = csrf.generate_token(&session_id).await.unwrap();
+
+    tokio::time::sleep(std::time::Duration::from_millis(2100)).await;
+
+    let resp = post_with_token(app, &cookie, &token).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let fresh = resp
+        .headers()
+        .get("X-CSRF-Token")
+        .expect("rejection carries a replacement token")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(fresh, token);
+    assert!(csrf.validate_token(&session_id, &fresh).await.unwrap());
+
+    let body = json_body(resp).await;
+    assert!(
+        body["error"].as_str().unwrap().contains("expired"),
+        "error names the reason: {}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn token_from_another_session_is_rejected() {
+    let (app, pool) = build_app_with_csrf(CsrfService::new).await;
+    let (session_a, _cookie_a) = login(&pool).await;
+    let (_session_b, cookie_b) = login(&pool).await;
+
+    let csrf = CsrfService::new("test-session-secret-please-ignore");
+    let token_a = csrf.generate_token(&session_a).await.unwrap();
+
+    let resp = post_with_token(app, &cookie_b, &token_a).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let body = json_body(resp).await;
+    assert!(
+        body["error"].as_str().unwrap().contains("does not match"),
+        "error names the reason: {}",
+        body
+    );
+}
@@ -0,0 +1,152 @@
+//! Member dashboard's featured-announcements card: only published,
+//! unexpired, featured announcements appear, members-only ones only
+//! for members in good standing, each linking to its detail page, up
+//! to the configured count.
+//!
+//! Run with: cargo test --features test-utils --test dashboard_featured_announcements_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    member: Uuid,
+    cookie: String,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (member, _, cookie) = member_session(&pool, false).await;
+    H {
+        pool,
+        app,
+        member,
+        cookie,
+    }
+}
+
+async fn seed(
+    h: &H,
+    title: &str,
+    is_public: bool,
+    published_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+) -> Announcement {
+    let now = Utc::now();
+    SqliteAnnouncementRepository::new(h.pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            content: "Body".to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public,
+            featured: true,
+            image_url: None,
+            published_at,
+            scheduled_publish_at: None,
+            expires_at,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: h.member,
+            created_at: now - Duration::days(3),
+            updated_at: now - Duration::days(3),
+        })
+        .await
+        .unwrap()
+}
+
+async fn get(h: &H, uri: &str) -> String {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, &h.cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+async fn set_count(pool: &SqlitePool, count: i64) {
+    sqlx::query(
+        "UPDATE app_settings SET value = ? WHERE key = 'features.dashboard_featured_announcements'",
+    )
+    .bind(count.to_string())
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn published_featured_appears_and_unpublished_does_not() {
+    let h = harness().await;
+    let an_hour_ago = Some(Utc::now() - Duration::hours(1));
+    let live = seed(&h, "Annual meetup", true, an_hour_ago, None).await;
+    seed(&h, "Draft plans", true, None, None).await;
+    seed(
+        &h,
+        "Last season",
+        true,
+        an_hour_ago,
+        Some(Utc::now() - Duration::minutes(5)),
+    )
+    .await;
+
+    let html = get(&h, "/portal/api/announcements/featured").await;
+    assert!(html.contains("Annual meetup"), "{}", html);
+    assert!(html.contains(&format!("/portal/announcements/{}", live.id)));
+    assert!(!html.contains("Draft plans"), "unpublished leaked: {}", html);
+    assert!(!html.contains("Last season"), "expired leaked: {}", html);
+}
+
+#[tokio::test]
+async fn members_only_featured_needs_an_eligible_member() {
+    let h = harness().await;
+    seed(&h, "Members-only", false, Some(Utc::now() - Duration::hours(1)), None).await;
+
+    let html = get(&h, "/portal/api/announcements/featured").await;
+    assert!(html.contains("Members-only"), "{}", html);
+
+    let repo = SqliteAnnouncementRepository::new(h.pool.clone());
+    assert!(repo.list_featured(false, 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn count_setting_limits_and_hides_the_card() {
+    let h = harness().await;
+    seed(&h, "Older", true, Some(Utc::now() - Duration::hours(2)), None).await;
+    seed(&h, "Newer", true, Some(Utc::now() - Duration::hours(1)), None).await;
+
+    set_count(&h.pool, 1).await;
+    let html = get(&h, "/portal/api/announcements/featured").await;
+    assert!(html.contains("Newer") && !html.contains("Older"), "{}", html);
+
+    set_count(&h.pool, 0).await;
+    assert_eq!(get(&h, "/portal/api/announcements/featured").await, "");
+    let dashboard = get(&h, "/portal/dashboard").await;
+    assert!(!dashboard.contains("featured-announcements"));
+}
@@ -0,0 +1,215 @@
+//! Dues reminders escalate through `membership.reminder_sequence`:
+//! each step goes out once as the member's dues run out, and paying
+//! stops the rest of the sequence.
+//!
+//! Run: cargo test --features test-utils --test dues_reminder_escalation_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    auth::{AuthService, SecretCrypto},
+    domain::CreateMemberRequest,
+    email::{EmailMessage, EmailSender},
+    error::Result as CoterieResult,
+    integrations::IntegrationManager,
+    repository::{
+        EventRepository, MemberRepository, SqliteEventRepository, SqliteMemberRepository,
+        SqliteMembershipTypeRepository, SqlitePaymentRepository, SqliteSavedCardRepository,
+        SqliteScheduledPaymentRepository,
+    },
+    service::{
+        billing_service::BillingService, membership_type_service::MembershipTypeService,
+        settings_service::SettingsService,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+mod common;
+use common::fresh_pool;
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+impl FakeEmailSender {
+    /// Subjects sent since the last call.
+    async fn take_subjects(&self) -> Vec<String> {
+        self.sent
+            .lock()
+            .await
+            .drain(..)
+            .map(|m| m.subject)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    billing: BillingService,
+    member_repo: Arc<dyn MemberRepository>,
+    email: Arc<FakeEmailSender>,
+    member: Uuid,
+    due: DateTime<Utc>,
+}
+
+/// A manually billed Active member whose dues run out in 30 days,
+/// under a three-step sequence.
+async fn build() -> H {
+    let pool = fresh_pool().await;
+
+    let member_repo: Arc<dyn MemberRepository> =
+        Arc::new(SqliteMemberRepository::new(pool.clone()));
+    let event_repo: Arc<dyn EventRepository> = Arc::new(SqliteEventRepository::new(pool.clone()));
+    let mt_service = Arc::new(MembershipTypeService::new(Arc::new(
+        SqliteMembershipTypeRepository::new(pool.clone()),
+    )));
+    let crypto = Arc::new(SecretCrypto::new("test-secret-please-ignore"));
+    let settings = Arc::new(SettingsService::new(pool.clone(), crypto));
+    let email = Arc::new(FakeEmailSender::default());
+    let email_for_billing: Arc<dyn EmailSender> = email.clone();
+
+    let billing = BillingService::new(
+        Arc::new(SqliteScheduledPaymentRepository::new(pool.clone())),
+        Arc::new(SqlitePaymentRepository::new(pool.clone())),
+        Arc::new(SqliteSavedCardRepository::new(pool.clone())),
+        member_repo.clone(),
+        event_repo,
+        mt_service,
+        settings,
+        email_for_billing,
+        Arc::new(IntegrationManager::new()),
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
+        None,
+        "http://localhost:3000".to_string(),
+        pool.clone(),
+    );
+
+    sqlx::query(
+        "UPDATE app_settings SET value = '14:friendly,3:firm,-2:lapsed' \
+         WHERE key = 'membership.reminder_sequence'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let member = member_repo
+        .create(CreateMemberRequest {
+            email: "dues@example.com".to_string(),
+            username: format!("u_{}", Uuid::new_v4().simple()),
+            full_name: "Dues Member".to_string(),
+            password: "p4ssword_long_enough".to_string(),
+            membership_type_id: None,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let due = Utc::now() + Duration::days(30);
+    sqlx::query("UPDATE members SET status = 'Active', dues_paid_until = ? WHERE id = ?")
+        .bind(due)
+        .bind(member.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    H {
+        pool,
+        billing,
+        member_repo,
+        email,
+        member: member.id,
+        due,
+    }
+}
+
+impl H {
+    /// Run the reminder job `days` from the dues date and return the
+    /// subjects it sent.
+    async fn run_at(&self, days: i64) -> Vec<String> {
+        self.billing
+            .notifications
+            .send_dues_reminders_at(self.due + Duration::days(days))
+            .await
+            .unwrap();
+        self.email.take_subjects().await
+    }
+}
+
+#[tokio::test]
+async fn member_moves_through_each_step_once() {
+    let h = build().await;
+
+    assert!(h.run_at(-20).await.is_empty());
+
+    let friendly = h.run_at(-10).await;
+    assert_eq!(friendly.len(), 1);
+    assert!(friendly[0].contains("dues are due soon"), "{:?}", friendly);
+    assert!(h.run_at(-9).await.is_empty(), "friendly step sent twice");
+
+    let firm = h.run_at(-2).await;
+    assert_eq!(firm.len(), 1);
+    assert!(firm[0].starts_with("Reminder:"), "{:?}", firm);
+    assert!(h.run_at(-1).await.is_empty(), "firm step sent twice");
+
+    // Nothing between expiry and the lapsed step.
+    assert!(h.run_at(1).await.is_empty());
+
+    let lapsed = h.run_at(3).await;
+    assert_eq!(lapsed.len(), 1);
+    assert!(lapsed[0].contains("has lapsed"), "{:?}", lapsed);
+    assert!(h.run_at(4).await.is_empty(), "lapsed step sent twice");
+
+    let steps: Vec<i64> = sqlx::query_scalar(
+        "SELECT step_days FROM dues_reminders_sent WHERE member_id = ? ORDER BY step_days DESC",
+    )
+    .bind(h.member.to_string())
+    .fetch_all(&h.pool)
+    .await
+    .unwrap();
+    assert_eq!(steps, vec![14, 3, -2]);
+}
+
+#[tokio::test]
+async fn paying_stops_the_escalation() {
+    let h = build().await;
+
+    assert_eq!(h.run_at(-10).await.len(), 1);
+
+    h.member_repo
+        .set_dues_paid_until_with_revival(h.member, h.due + Duration::days(365))
+        .await
+        .unwrap();
+
+    for days in [-2, -1, 3, 4] {
+        assert!(
+            h.run_at(days).await.is_empty(),
+            "reminder sent {} days from the old dues date after paying",
+            days
+        );
+    }
+}
+
+#[tokio::test]
+async fn late_arrivals_get_only_the_current_step() {
+    let h = build().await;
+
+    let firm = h.run_at(-2).await;
+    assert_eq!(firm.len(), 1);
+    assert!(firm[0].starts_with("Reminder:"), "{:?}", firm);
+    assert!(h.run_at(-1).await.is_empty());
+}
@@ -0,0 +1,127 @@
+//! Integration tests for `PaymentRepository::latest_payment` and the
+//! `DuesStatus` it feeds: an Active member whose dues have lapsed
+//! reads as "payment pending" / "payment failed" depending on their
+//! latest dues payment, not just "expired".
+//!
+//! Run: cargo test --test dues_status_test
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{
+        DuesStatus, MemberStatus, Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus,
+    },
+    repository::{
+        MemberRepository, PaymentRepository, SqliteMemberRepository, SqlitePaymentRepository,
+    },
+};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+/// Insert a payment and backdate `created_at` so ordering is explicit.
+async fn insert_payment(
+    pool: &SqlitePool,
+    repo: &Arc<dyn PaymentRepository>,
+    member_id: Uuid,
+    status: PaymentStatus,
+    kind: PaymentKind,
+    created_at: DateTime<Utc>,
+) -> Uuid {
+    let id = Uuid::new_v4();
+    let payment = Payment {
+        id,
+        payer: Payer::Member(member_id),
+        amount_cents: 50_00,
+        currency: "USD".to_string(),
+        status,
+        payment_method: PaymentMethod::Stripe,
+        external_id: None,
+        description: "test".to_string(),
+        kind,
+        paid_at: None,
+        refunded_at: None,
+        created_at,
+        updated_at: created_at,
+    };
+    repo.create(payment).await.unwrap();
+    sqlx::query("UPDATE payments SET created_at = ? WHERE id = ?")
+        .bind(created_at.naive_utc())
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+/// Make an Active member whose dues lapsed a few days ago.
+async fn lapsed_active_member(pool: &SqlitePool) -> Uuid {
+    let id = make_member(pool).await;
+    sqlx::query("UPDATE members SET status = 'Active', dues_paid_until = ? WHERE id = ?")
+        .bind((Utc::now() - Duration::days(3)).naive_utc())
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+async fn dues_status(pool: &SqlitePool, member_id: Uuid) -> DuesStatus {
+    let members = SqliteMemberRepository::new(pool.clone());
+    let payments = SqlitePaymentRepository::new(pool.clone());
+    let member = members.find_by_id(member_id).await.unwrap().unwrap();
+    assert_eq!(member.status, MemberStatus::Active);
+    let latest = payments.latest_payment(member_id).await.unwrap();
+    DuesStatus::compute(&member, latest.as_ref(), Utc::now(), 0)
+}
+
+#[tokio::test]
+async fn latest_payment_is_newest_dues_payment_and_skips_donations() {
+    let pool = fresh_pool().await;
+    let repo: Arc<dyn PaymentRepository> = Arc::new(SqlitePaymentRepository::new(pool.clone()));
+    let m = make_member(&pool).await;
+
+    assert!(repo.latest_payment(m).await.unwrap().is_none());
+
+    let now = Utc::now();
+    insert_payment(&pool, &repo, m, PaymentStatus::Completed, PaymentKind::Membership, now - Duration::days(30)).await;
+    let newest = insert_payment(&pool, &repo, m, PaymentStatus::Failed, PaymentKind::Membership, now - Duration::days(2)).await;
+    insert_payment(
+        &pool,
+        &repo,
+        m,
+        PaymentStatus::Completed,
+        PaymentKind::Donation { campaign_id: None },
+        now - Duration::days(1),
+    )
+    .await;
+
+    let latest = repo.latest_payment(m).await.unwrap().unwrap();
+    assert_eq!(latest.id, newest);
+    assert_eq!(latest.status, PaymentStatus::Failed);
+}
+
+#[tokio::test]
+async fn active_member_with_pending_payment_reads_payment_pending() {
+    let pool = fresh_pool().await;
+    let repo: Arc<dyn PaymentRepository> = Arc::new(SqlitePaymentRepository::new(pool.clone()));
+    let m = lapsed_active_member(&pool).await;
+
+    assert_eq!(dues_status(&pool, m).await, DuesStatus::Expired);
+
+    insert_payment(&pool, &repo, m, PaymentStatus::Pending, PaymentKind::Membership, Utc::now()).await;
+    assert_eq!(dues_status(&pool, m).await, DuesStatus::PaymentPending);
+}
+
+#[tokio::test]
+async fn active_member_with_failed_payment_reads_payment_failed() {
+    let pool = fresh_pool().await;
+    let repo: Arc<dyn PaymentRepository> = Arc::new(SqlitePaymentRepository::new(pool.clone()));
+    let m = lapsed_active_member(&pool).await;
+
+    insert_payment(&pool, &repo, m, PaymentStatus::Failed, PaymentKind::Membership, Utc::now()).await;
+    assert_eq!(dues_status(&pool, m).await, DuesStatus::PaymentFailed);
+}
@@ -0,0 +1,174 @@
+//! Dues badges come from one `DuesStatus` computation, so the admin
+//! member list, the admin member page and the member's own dashboard
+//! show the same badge for the same member — including inside the
+//! grace period and past it, before the expiration sweep runs.
+//!
+//! Run with: cargo test --features test-utils --test dues_status_views_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::UpdateMemberRequest,
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session};
+
+/// Every `DuesStatus::label()`.
+const DUES_LABELS: [&str; 7] = [
+    "Current",
+    "In grace period",
+    "Exempt",
+    "Payment pending",
+    "Payment failed",
+    "Expired",
+    "Unpaid",
+];
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    admin_cookie: String,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (admin, admin_cookie) = paid_member(&pool, "Ada Admin", None).await;
+    SqliteMemberRepository::new(pool.clone())
+        .set_admin(admin, true)
+        .await
+        .unwrap();
+    H {
+        pool,
+        app,
+        admin_cookie,
+    }
+}
+
+/// An Active member called `full_name`, paid through
+/// `dues_paid_until`, with a live session; returns (id, cookie).
+async fn paid_member(
+    pool: &SqlitePool,
+    full_name: &str,
+    dues_paid_until: Option<chrono::DateTime<Utc>>,
+) -> (Uuid, String) {
+    let (id, _, cookie) = member_session(pool, false).await;
+    SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                full_name: Some(full_name.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    sqlx::query("UPDATE members SET dues_paid_until = ? WHERE id = ?")
+        .bind(dues_paid_until.map(|d| d.naive_utc()))
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+    (id, cookie)
+}
+
+async fn get(h: &H, uri: &str, cookie: &str) -> String {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "GET {}", uri);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Labels of the dues badges on a page, in order.
+fn dues_badges(html: &str) -> Vec<&'static str> {
+    html.split("rounded-full")
+        .skip(1)
+        .filter_map(|badge| {
+            let text = badge.split_once('>')?.1.split_once("</span>")?.0;
+            DUES_LABELS.iter().copied().find(|l| *l == text.trim())
+        })
+        .collect()
+}
+
+/// The badge the admin list, the admin member page and the member's
+/// dashboard each show for one member.
+async fn badges_across_views(h: &H, id: Uuid, name: &str, cookie: &str) -> [Vec<&'static str>; 3] {
+    let search = name.split_whitespace().next().unwrap();
+    [
+        dues_badges(
+            &get(
+                h,
+                &format!("/portal/admin/members?q={}", search),
+                &h.admin_cookie,
+            )
+            .await,
+        ),
+        dues_badges(&get(h, &format!("/portal/admin/members/{}", id), &h.admin_cookie).await),
+        dues_badges(&get(h, "/portal/dashboard", cookie).await),
+    ]
+}
+
+#[tokio::test]
+async fn views_agree_for_member_in_grace() {
+    let h = harness().await;
+    let name = "Greta Lapsed";
+    let (id, cookie) = paid_member(&h.pool, name, Some(Utc::now() - Duration::days(5))).await;
+
+    for badges in badges_across_views(&h, id, name, &cookie).await {
+        assert_eq!(badges, ["In grace period"]);
+    }
+}
+
+#[tokio::test]
+async fn views_agree_once_grace_runs_out() {
+    let h = harness().await;
+    sqlx::query("UPDATE app_settings SET value = '10' WHERE key = 'membership.grace_period_days'")
+        .execute(&h.pool)
+        .await
+        .unwrap();
+    let name = "Elmo Overdue";
+    let (id, cookie) = paid_member(&h.pool, name, Some(Utc::now() - Duration::days(12))).await;
+
+    // Still Active — the sweep hasn't run — but every view calls it
+    // expired.
+    for badges in badges_across_views(&h, id, name, &cookie).await {
+        assert_eq!(badges, ["Expired"]);
+    }
+}
+
+#[tokio::test]
+async fn views_agree_for_current_and_unpaid_members() {
+    let h = harness().await;
+    let current = "Cora Current";
+    let (current_id, current_cookie) =
+        paid_member(&h.pool, current, Some(Utc::now() + Duration::days(30))).await;
+    let unpaid = "Uri Unpaid";
+    let (unpaid_id, unpaid_cookie) = paid_member(&h.pool, unpaid, None).await;
+
+    for badges in badges_across_views(&h, current_id, current, &current_cookie).await {
+        assert_eq!(badges, ["Current"]);
+    }
+    for badges in badges_across_views(&h, unpaid_id, unpaid, &unpaid_cookie).await {
+        assert_eq!(badges, ["Unpaid"]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Creating a member with a taken email or username: the repo reports
+//! which field collided (from its pre-check, and from the UNIQUE
+//! constraint when the pre-check is bypassed), and public signup only
+//! names the field when the org has opted in.
+//!
+//! Run with: cargo test --features test-utils --test duplicate_member_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::password::PasswordCost,
+    domain::{CreateMemberRequest, DuplicateMemberField},
+    error::AppError,
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+fn request(email: &str, username: &str) -> CreateMemberRequest {
+    CreateMemberRequest {
+        email: email.to_string(),
+        username: username.to_string(),
+        full_name: "Dee Duplicate".to_string(),
+        password: "Correct-horse-battery-9".to_string(),
+        ..Default::default()
+    }
+}
+
+fn duplicate_field(err: AppError) -> DuplicateMemberField {
+    match err {
+        AppError::DuplicateMember(field) => field,
+        other => panic!("expected DuplicateMember, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn duplicate_email_and_username_name_the_field() {
+    let pool = fresh_pool().await;
+    let repo = SqliteMemberRepository::new(pool);
+    repo.create(request("dee@example.com", "dee"))
+        .await
+        .unwrap();
+
+    let err = repo
+        .create(request("dee@example.com", "someone_else"))
+        .await
+        .unwrap_err();
+    assert_eq!(duplicate_field(err), DuplicateMemberField::Email);
+
+    let err = repo
+        .create(request("other@example.com", "dee"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Username taken", "no SQL in the message");
+    assert_eq!(duplicate_field(err), DuplicateMemberField::Username);
+}
+
+#[tokio::test]
+async fn constraint_violation_is_mapped_when_the_pre_check_is_skipped() {
+    let pool = fresh_pool().await;
+    let repo = SqliteMemberRepository::new(pool);
+
+    // The batch path has no pre-check, so the second row of each pair
+    // reaches the UNIQUE constraint.
+    let results = repo
+        .create_batch(
+            vec![
+                request("pat@example.com", "pat"),
+                request("pat@example.com", "pat_two"),
+                request("sam@example.com", "pat"),
+            ],
+            PasswordCost::TestData,
+        )
+        .await
+        .unwrap();
+    let mut results = results.into_iter();
+    assert!(results.next().unwrap().is_ok());
+    assert_eq!(
+        duplicate_field(results.next().unwrap().unwrap_err()),
+        DuplicateMemberField::Email
+    );
+    assert_eq!(
+        duplicate_field(results.next().unwrap().unwrap_err()),
+        DuplicateMemberField::Username
+    );
+}
+
+async fn signup(app: &Router, email: &str, username: &str) -> (StatusCode, Value) {
+    let body = json!({
+        "email": email,
+        "username": username,
+        "full_name": "New Member",
+        "password": "Correct-horse-battery-9",
+    });
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/public/signup")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+async fn name_duplicate_fields(pool: &SqlitePool, on: bool) {
+    sqlx::query(
+        "UPDATE app_settings SET value = ? \
+         WHERE key = 'membership.signup_duplicate_field_errors'",
+    )
+    .bind(if on { "true" } else { "false" })
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn signup_names_the_field_only_when_enabled() {
+    let pool = fresh_pool().await;
+    let app = coterie::api::create_app(build_app_state(pool.clone()).await);
+    let (status, _) = signup(&app, "first@example.com", "first").await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Default: generic, so signup can't be used to probe for accounts.
+    let (status, body) = signup(&app, "first@example.com", "second").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body.get("fields").is_none(), "{}", body);
+    assert!(!body["error"].as_str().unwrap().contains("UNIQUE"));
+
+    name_duplicate_fields(&pool, true).await;
+    let (status, body) = signup(&app, "first@example.com", "second").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["fields"]["email"], "Email already in use");
+
+    let (status, body) = signup(&app, "second@example.com", "first").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["fields"]["username"], "Username taken");
+}
@@ -0,0 +1,202 @@
+//! Emails are stored trimmed and lowercased and looked up the same
+//! way, so case variants are one account: a mixed-case signup collides
+//! with the existing lowercase account and login by email ignores
+//! case. Usernames must follow the charset/length policy, and
+//! migration 051 normalizes legacy rows without breaking UNIQUE(email)
+//! when two accounts differ only by case.
+//!
+//! Run with: cargo test --features test-utils --test email_normalization_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, merged_routes};
+
+const PASSWORD: &str = "Correct-horse-battery-9";
+
+async fn create(pool: &SqlitePool, email: &str, username: &str) -> Uuid {
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let member = repo
+        .create(CreateMemberRequest {
+            email: email.to_string(),
+            username: username.to_string(),
+            full_name: "Alice Example".to_string(),
+            password: PASSWORD.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    repo.update(
+        member.id,
+        UpdateMemberRequest {
+            status: Some(MemberStatus::Active),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    member.id
+}
+
+async fn post_json(app: &Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn mixed_case_signup_collides_with_lowercase_account() {
+    let pool = fresh_pool().await;
+    create(&pool, "alice@example.com", "alice").await;
+    sqlx::query(
+        "UPDATE app_settings SET value = 'true' \
+         WHERE key = 'membership.signup_duplicate_field_errors'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let app = merged_routes(build_app_state(pool.clone()).await);
+
+    let (status, body) = post_json(
+        &app,
+        "/public/signup",
+        json!({
+            "email": "  Alice@Example.COM ",
+            "username": "alice_two",
+            "full_name": "Alice Again",
+            "password": PASSWORD,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+    assert_eq!(body["fields"]["email"], "Email already in use");
+
+    // A new address is stored in its normalized form.
+    let (status, body) = post_json(
+        &app,
+        "/public/signup",
+        json!({
+            "email": "Bob@Example.com",
+            "username": "bob",
+            "full_name": "Bob Example",
+            "password": PASSWORD,
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let stored: String = sqlx::query_scalar("SELECT email FROM members WHERE username = 'bob'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, "bob@example.com");
+}
+
+#[tokio::test]
+async fn login_by_email_ignores_case() {
+    let pool = fresh_pool().await;
+    create(&pool, "alice@example.com", "alice").await;
+    let app = merged_routes(build_app_state(pool.clone()).await);
+
+    let (status, body) = post_json(
+        &app,
+        "/login",
+        json!({"username": "ALICE@example.com", "password": PASSWORD}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["success"], true, "{body}");
+}
+
+#[tokio::test]
+async fn signup_rejects_usernames_outside_the_policy() {
+    let pool = fresh_pool().await;
+    let app = merged_routes(build_app_state(pool.clone()).await);
+
+    for username in ["a", "has space", "-dash-first"] {
+        let (status, body) = post_json(
+            &app,
+            "/public/signup",
+            json!({
+                "email": format!("{}@example.com", Uuid::new_v4()),
+                "username": username,
+                "full_name": "New Member",
+                "password": PASSWORD,
+            }),
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "{username}: {body}"
+        );
+        assert!(body["fields"]["username"].is_string(), "{body}");
+    }
+}
+
+#[tokio::test]
+async fn migration_normalizes_and_leaves_case_collisions_for_an_admin() {
+    let pool = fresh_pool().await;
+    let lone = create(&pool, "carol@example.com", "carol").await;
+    let older = create(&pool, "dave@example.com", "dave").await;
+    let newer = create(&pool, "dave2@example.com", "dave2").await;
+
+    // Rows as they were stored before normalization.
+    for (id, email, created_at) in [
+        (lone, " Carol@Example.com", "2020-01-01 00:00:00"),
+        (older, "Dave@Example.com", "2020-01-01 00:00:00"),
+        (newer, "DAVE@example.com", "2021-01-01 00:00:00"),
+    ] {
+        sqlx::query("UPDATE members SET email = ?, created_at = ? WHERE id = ?")
+            .bind(email)
+            .bind(created_at)
+            .bind(id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    sqlx::query(include_str!(
+        "../migrations/051_normalize_member_emails.sql"
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let email = |id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, String>("SELECT email FROM members WHERE id = ?")
+                .bind(id.to_string())
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(email(lone).await, "carol@example.com");
+    assert_eq!(email(older).await, "dave@example.com");
+    assert_eq!(email(newer).await, "DAVE@example.com");
+}
@@ -0,0 +1,115 @@
+//! The embeddable events widget at `/public/embed/events`: it lists
+//! only published public events that haven't started yet, honours
+//! `count`, and is cacheable and frameable by other sites while the
+//! rest of the app stays unframeable.
+//!
+//! Run with: cargo test --features test-utils --test embed_events_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, HeaderMap, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Event, EventType, EventVisibility},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, event, fresh_pool, member_session, merged_routes};
+
+async fn create_event(pool: &SqlitePool, title: &str, visibility: EventVisibility, in_days: i64) {
+    let (creator, _, _) = member_session(pool, true).await;
+    let start = Utc::now() + Duration::days(in_days);
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: title.to_string(),
+            event_type: EventType::Meeting,
+            visibility,
+            start_time: start,
+            end_time: None,
+            location: Some("Main Hall".to_string()),
+            rsvp_required: false,
+            ..event(creator)
+        })
+        .await
+        .unwrap();
+}
+
+/// The app as `main.rs` layers it, minus CSRF and the setup redirect.
+async fn app(pool: SqlitePool) -> Router {
+    let state = build_app_state(pool).await;
+    merged_routes(state.clone()).layer(axum::middleware::from_fn_with_state(
+        state,
+        coterie::api::middleware::security_headers::security_headers,
+    ))
+}
+
+async fn get(app: &Router, uri: &str) -> (HeaderMap, String) {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "{uri}");
+    let headers = resp.headers().clone();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn lists_only_upcoming_public_events() {
+    let pool = fresh_pool().await;
+    create_event(&pool, "Public Soon", EventVisibility::Public, 3).await;
+    create_event(&pool, "Public Later", EventVisibility::Public, 10).await;
+    create_event(&pool, "Public Past", EventVisibility::Public, -3).await;
+    create_event(&pool, "Members Night", EventVisibility::MembersOnly, 5).await;
+    let app = app(pool).await;
+
+    let (headers, body) = get(&app, "/public/embed/events?theme=dark").await;
+    assert!(body.contains("Public Soon"), "{body}");
+    assert!(body.contains("Public Later"), "{body}");
+    assert!(!body.contains("Public Past"), "{body}");
+    assert!(!body.contains("Members Night"), "{body}");
+    assert!(
+        body.find("Public Soon").unwrap() < body.find("Public Later").unwrap(),
+        "soonest first"
+    );
+    assert!(body.contains(r#"<body class="dark">"#), "{body}");
+
+    assert_eq!(
+        headers.get(header::CACHE_CONTROL).unwrap(),
+        "public, max-age=300"
+    );
+    let csp = headers
+        .get(header::CONTENT_SECURITY_POLICY)
+        .unwrap()
+        .to_str()
+        .unwrap();
+    assert!(csp.contains("frame-ancestors *"), "{csp}");
+    assert!(!headers.contains_key(header::X_FRAME_OPTIONS));
+
+    // Everything else keeps the default deny.
+    let (headers, _) = get(&app, "/login").await;
+    assert_eq!(headers.get(header::X_FRAME_OPTIONS).unwrap(), "DENY");
+}
+
+#[tokio::test]
+async fn count_limits_the_list() {
+    let pool = fresh_pool().await;
+    create_event(&pool, "First Up", EventVisibility::Public, 1).await;
+    create_event(&pool, "Second Up", EventVisibility::Public, 2).await;
+    create_event(&pool, "Third Up", EventVisibility::Public, 3).await;
+    let app = app(pool).await;
+
+    let (_, body) = get(&app, "/public/embed/events?count=1").await;
+    assert!(body.contains("First Up"), "{body}");
+    assert!(!body.contains("Second Up"), "{body}");
+
+    let (_, body) = get(&app, "/public/embed/events?count=2").await;
+    assert!(body.contains("Second Up"), "{body}");
+    assert!(!body.contains("Third Up"), "{body}");
+}
@@ -0,0 +1,185 @@
+//! Emergency contact and medical notes: the member saves them from
+//! their profile, admins see them, and they stay out of the member
+//! directory and the member export. An event's attendee export only
+//! carries them when asked.
+//!
+//! Run with: cargo test --features test-utils --test emergency_contact_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Event, EventType},
+    repository::{EventRepository, SqliteEventRepository},
+    service::{
+        directory_service::{DirectoryPrivacy, DirectoryService},
+        emergency_contact_service::EmergencyContact,
+    },
+};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, event, fresh_pool, member_session};
+
+const PHONE: &str = "+1 555 0100";
+const NOTES: &str = "Severe peanut allergy; EpiPen in backpack";
+
+async fn send(app: &Router, req: Request<Body>) -> (StatusCode, String) {
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn get(app: &Router, uri: &str, cookie: &str) -> String {
+    let (status, body) = send(
+        app,
+        Request::builder()
+            .uri(uri)
+            .header(header::COOKIE, cookie)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "GET {}", uri);
+    body
+}
+
+#[tokio::test]
+async fn saved_from_profile_and_kept_out_of_directory_and_exports() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let ctx = state.service_context.clone();
+    let (member, _, cookie) = member_session(&pool, false).await;
+    let (_, _, viewer) = member_session(&pool, false).await;
+    let (_, _, admin) = member_session(&pool, true).await;
+    let app = coterie::web::create_web_routes(state);
+
+    let form = serde_urlencoded::to_string([
+        ("contact_name", "  Sam Rivera "),
+        ("contact_phone", PHONE),
+        ("contact_relationship", "Parent"),
+        ("medical_notes", NOTES),
+        ("csrf_token", "unused"),
+    ])
+    .unwrap();
+    let (status, body) = send(
+        &app,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/portal/profile/emergency-contact")
+            .header(header::COOKIE, &cookie)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Emergency details saved"), "{}", body);
+
+    let saved = ctx.emergency_contact_service.get(member).await.unwrap();
+    assert_eq!(
+        saved,
+        EmergencyContact {
+            name: "Sam Rivera".to_string(),
+            phone: PHONE.to_string(),
+            relationship: "Parent".to_string(),
+            medical_notes: NOTES.to_string(),
+        }
+    );
+    assert!(get(&app, "/portal/profile", &cookie).await.contains(PHONE));
+    let detail = get(&app, &format!("/portal/admin/members/{}", member), &admin).await;
+    assert!(detail.contains(PHONE), "{}", detail);
+    assert!(detail.contains("Severe peanut allergy"));
+
+    // Listed with contact details shown, and still nothing leaks.
+    DirectoryService::new(pool.clone())
+        .set_privacy(
+            member,
+            DirectoryPrivacy {
+                show_in_directory: true,
+                show_contact: true,
+            },
+        )
+        .await
+        .unwrap();
+    for uri in [
+        "/portal/directory".to_string(),
+        format!("/portal/directory/{}", member),
+    ] {
+        let page = get(&app, &uri, &viewer).await;
+        assert!(!page.contains(PHONE), "{}: {}", uri, page);
+        assert!(!page.contains("Severe peanut allergy"), "{}", uri);
+    }
+    let export = get(&app, "/portal/admin/members/export", &admin).await;
+    assert!(!export.contains(PHONE), "{}", export);
+
+    let event = SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Climbing trip".to_string(),
+            event_type: EventType::Social,
+            start_time: Utc::now() + Duration::days(5),
+            end_time: None,
+            ..event(member)
+        })
+        .await
+        .unwrap();
+    ctx.event_repo
+        .register_attendance(event.id, member)
+        .await
+        .unwrap();
+
+    let uri = format!("/portal/admin/events/{}/attendees/export", event.id);
+    let plain = get(&app, &uri, &admin).await;
+    assert!(!plain.contains("emergency_contact_name"), "{}", plain);
+    assert!(!plain.contains(PHONE));
+    let check_in = get(&app, &format!("{}?emergency=true", uri), &admin).await;
+    assert!(check_in.contains("emergency_contact_name"), "{}", check_in);
+    assert!(check_in.contains(PHONE), "{}", check_in);
+}
+
+#[tokio::test]
+async fn clearing_every_field_removes_the_details() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let service = state.service_context.emergency_contact_service.clone();
+    let (member, _, _) = member_session(&pool, false).await;
+
+    service
+        .set(
+            member,
+            EmergencyContact {
+                phone: PHONE.to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(service.get(member).await.unwrap().phone, PHONE);
+
+    service
+        .set(member, EmergencyContact::default())
+        .await
+        .unwrap();
+    assert!(service.get(member).await.unwrap().is_empty());
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM member_emergency_contacts")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, 0);
+
+    let err = service
+        .set(
+            member,
+            EmergencyContact {
+                name: "x".repeat(201),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, coterie::error::AppError::BadRequest(_)));
+}
@@ -0,0 +1,124 @@
+//! Event co-hosts: the creator adds a co-host from
+//! `/portal/events/:id/edit`, the co-host can then edit the event, and
+//! a member who doesn't host it is refused.
+//!
+//! Run with: cargo test --features test-utils --test event_co_host_test
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{EventType, EventVisibility},
+    repository::{EventRepository, MemberRepository},
+    service::event_proposal_service::ProposeEventInput,
+};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session};
+
+async fn post_form(app: &Router, uri: &str, cookie: &str, body: &str) -> StatusCode {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn co_host_can_edit_and_non_host_cannot() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let ctx = state.service_context.clone();
+    let (creator_id, _, creator) = member_session(&pool, false).await;
+    let (co_host_id, _, co_host) = member_session(&pool, false).await;
+    let (_, _, outsider) = member_session(&pool, false).await;
+
+    let creator_member = ctx
+        .member_repo
+        .find_by_id(creator_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let co_host_username = ctx
+        .member_repo
+        .find_by_id(co_host_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .username;
+    let event = ctx
+        .event_proposal_service
+        .propose(
+            &creator_member,
+            ProposeEventInput {
+                title: "Lockpicking night".to_string(),
+                description: "Bring your own picks".to_string(),
+                event_type: EventType::Workshop,
+                visibility: EventVisibility::MembersOnly,
+                start_time: Utc::now() + Duration::days(7),
+                end_time: None,
+                location: Some("Back room".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    let event_id = event.id;
+
+    let app = coterie::web::create_web_routes(state);
+    let edit = format!("/portal/events/{}/edit", event_id);
+    let form = |title: &str| {
+        format!(
+            "title={}&description=&start_time=2030-06-01T18%3A00&end_time=&location=Hackspace",
+            title
+        )
+    };
+
+    // Only the creator manages co-hosts.
+    assert_eq!(
+        post_form(
+            &app,
+            &format!("/portal/events/{}/co-hosts", event_id),
+            &outsider,
+            &format!("username={}", co_host_username),
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        post_form(
+            &app,
+            &format!("/portal/events/{}/co-hosts", event_id),
+            &creator,
+            &format!("username={}", co_host_username),
+        )
+        .await,
+        StatusCode::OK
+    );
+
+    assert_eq!(
+        post_form(&app, &edit, &co_host, &form("Lockpicking+and+pizza")).await,
+        StatusCode::OK
+    );
+    let saved = ctx.event_repo.find_by_id(event_id).await.unwrap().unwrap();
+    assert_eq!(saved.title, "Lockpicking and pizza");
+    assert_eq!(saved.location.as_deref(), Some("Hackspace"));
+
+    assert_eq!(
+        post_form(&app, &edit, &outsider, &form("Hijacked")).await,
+        StatusCode::FORBIDDEN
+    );
+    let saved = ctx.event_repo.find_by_id(event_id).await.unwrap().unwrap();
+    assert_eq!(saved.title, "Lockpicking and pizza");
+}
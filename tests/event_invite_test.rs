@@ -0,0 +1,239 @@
+//! Calendar invites for member RSVPs: an RSVP emails a METHOD:REQUEST
+//! `.ics`, an event edit re-sends it, and cancelling emails a
+//! METHOD:CANCEL — all for the same UID with a rising SEQUENCE so the
+//! member's calendar app replaces its entry each time.
+//!
+//! Run with: cargo test --features test-utils --test event_invite_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Duration;
+use coterie::{
+    auth::SecretCrypto,
+    domain::{AttendanceStatus, Event, Member, MemberStatus, UpdateMemberRequest},
+    email::{EmailMessage, EmailSender},
+    error::Result as CoterieResult,
+    repository::{
+        EventRepository, MemberRepository, SqliteEventRepository, SqliteMemberRepository,
+    },
+    service::{event_invite_service::EventInviteService, settings_service::SettingsService},
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+mod common;
+use common::{event, fresh_pool, make_member};
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+fn service(pool: &SqlitePool, email: Arc<FakeEmailSender>) -> EventInviteService {
+    EventInviteService::new(
+        pool.clone(),
+        email,
+        Arc::new(SettingsService::new(
+            pool.clone(),
+            Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+        )),
+        "http://127.0.0.1".to_string(),
+    )
+}
+
+async fn active_member(pool: &SqlitePool) -> Member {
+    let id = make_member(pool).await;
+    SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+}
+
+async fn create_event(pool: &SqlitePool, created_by: Uuid) -> Event {
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Lockpicking, Intro".to_string(),
+            description: "Bring picks; we have spares".to_string(),
+            location: Some("Back room".to_string()),
+            ..event(created_by)
+        })
+        .await
+        .unwrap()
+}
+
+/// The single `.ics` attachment on `message`, unfolded so properties
+/// can be matched whole.
+fn ics(message: &EmailMessage) -> String {
+    assert_eq!(message.attachments.len(), 1, "{:?}", message.attachments);
+    let attachment = &message.attachments[0];
+    assert!(
+        attachment.content_type.starts_with("text/calendar"),
+        "{}",
+        attachment.content_type
+    );
+    String::from_utf8(attachment.content.clone())
+        .unwrap()
+        .replace("\r\n ", "")
+}
+
+fn property<'a>(ics: &'a str, name: &str) -> Option<&'a str> {
+    ics.split("\r\n")
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+}
+
+#[tokio::test]
+async fn rsvp_sends_request_invite_and_cancel_sends_cancel_with_higher_sequence() {
+    let pool = fresh_pool().await;
+    let email = Arc::new(FakeEmailSender::default());
+    let invites = service(&pool, email.clone());
+    let event_repo = SqliteEventRepository::new(pool.clone());
+    let member = active_member(&pool).await;
+    let event = create_event(&pool, member.id).await;
+
+    assert_eq!(
+        event_repo
+            .register_attendance(event.id, member.id)
+            .await
+            .unwrap(),
+        AttendanceStatus::Registered
+    );
+    invites.send_rsvp_confirmation(&event, &member).await;
+
+    let request = {
+        let sent = email.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, member.email);
+        ics(&sent[0])
+    };
+    assert!(request.starts_with("BEGIN:VCALENDAR\r\n"), "{}", request);
+    assert!(request.ends_with("END:VCALENDAR\r\n"), "{}", request);
+    assert_eq!(property(&request, "METHOD"), Some("REQUEST"));
+    assert_eq!(
+        property(&request, "UID"),
+        Some(event.id.to_string().as_str())
+    );
+    assert_eq!(property(&request, "SEQUENCE"), Some("0"));
+    assert_eq!(property(&request, "SUMMARY"), Some("Lockpicking\\, Intro"));
+    assert_eq!(property(&request, "STATUS"), Some("CONFIRMED"));
+    assert_eq!(
+        property(&request, "DTSTART"),
+        Some(
+            event
+                .start_time
+                .format("%Y%m%dT%H%M%SZ")
+                .to_string()
+                .as_str()
+        )
+    );
+    assert!(
+        request.contains(&format!(":mailto:{}\r\n", member.email)),
+        "{}",
+        request
+    );
+    assert_eq!(request.matches("BEGIN:VEVENT").count(), 1);
+
+    event_repo
+        .cancel_attendance(event.id, member.id)
+        .await
+        .unwrap();
+    invites.send_rsvp_cancellation(&event, &member).await;
+
+    let sent = email.sent.lock().await;
+    assert_eq!(sent.len(), 2);
+    let cancel = ics(&sent[1]);
+    assert_eq!(property(&cancel, "METHOD"), Some("CANCEL"));
+    assert_eq!(
+        property(&cancel, "UID"),
+        Some(event.id.to_string().as_str())
+    );
+    assert_eq!(property(&cancel, "SEQUENCE"), Some("1"));
+    assert_eq!(property(&cancel, "STATUS"), Some("CANCELLED"));
+}
+
+#[tokio::test]
+async fn event_update_resends_to_registered_members_only() {
+    let pool = fresh_pool().await;
+    let email = Arc::new(FakeEmailSender::default());
+    let invites = service(&pool, email.clone());
+    let event_repo = SqliteEventRepository::new(pool.clone());
+    let going = active_member(&pool).await;
+    let dropped = active_member(&pool).await;
+    let mut event = create_event(&pool, going.id).await;
+
+    for member in [&going, &dropped] {
+        event_repo
+            .register_attendance(event.id, member.id)
+            .await
+            .unwrap();
+        invites.send_rsvp_confirmation(&event, member).await;
+    }
+    event_repo
+        .cancel_attendance(event.id, dropped.id)
+        .await
+        .unwrap();
+    invites.send_rsvp_cancellation(&event, &dropped).await;
+    email.sent.lock().await.clear();
+
+    event.start_time += Duration::hours(1);
+    let event = event_repo.update(event.id, event).await.unwrap();
+    invites.send_event_update(&event).await;
+
+    let sent = email.sent.lock().await;
+    assert_eq!(sent.len(), 1, "only the registered member hears about it");
+    assert_eq!(sent[0].to, going.email);
+    let update = ics(&sent[0]);
+    assert_eq!(property(&update, "METHOD"), Some("REQUEST"));
+    assert_eq!(property(&update, "SEQUENCE"), Some("1"));
+    assert_eq!(
+        property(&update, "DTSTART"),
+        Some(
+            event
+                .start_time
+                .format("%Y%m%dT%H%M%SZ")
+                .to_string()
+                .as_str()
+        )
+    );
+}
+
+#[tokio::test]
+async fn deleting_an_event_cancels_registered_members_invites() {
+    let pool = fresh_pool().await;
+    let email = Arc::new(FakeEmailSender::default());
+    let invites = service(&pool, email.clone());
+    let event_repo = SqliteEventRepository::new(pool.clone());
+    let member = active_member(&pool).await;
+    let event = create_event(&pool, member.id).await;
+
+    event_repo
+        .register_attendance(event.id, member.id)
+        .await
+        .unwrap();
+    invites.send_rsvp_confirmation(&event, &member).await;
+
+    let recipients = invites.registered_recipients(event.id).await;
+    event_repo.delete(event.id).await.unwrap();
+    invites.send_event_cancellation(&event, &recipients).await;
+
+    let sent = email.sent.lock().await;
+    assert_eq!(sent.len(), 2);
+    let cancel = ics(&sent[1]);
+    assert_eq!(property(&cancel, "METHOD"), Some("CANCEL"));
+    assert_eq!(property(&cancel, "SEQUENCE"), Some("1"));
+}
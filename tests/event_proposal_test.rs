@@ -0,0 +1,242 @@
+//! Member event proposals: a submission from `/portal/events/propose`
+//! stays out of every listing while `Proposed`, and approval publishes
+//! it and emails the proposer.
+//!
+//! Run with: cargo test --features test-utils --test event_proposal_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    auth::{CsrfService, SecretCrypto},
+    domain::{EventStatus, EventType, EventVisibility},
+    email::{EmailMessage, EmailSender},
+    error::{AppError, Result as CoterieResult},
+    integrations::IntegrationManager,
+    repository::{
+        EventRepository, MemberRepository, SqliteEventRepository, SqliteMemberRepository,
+    },
+    service::{
+        audit_service::AuditService,
+        event_proposal_service::{EventProposalService, ProposeEventInput},
+        settings_service::SettingsService,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, fresh_pool, member_session, SESSION_SECRET};
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    service: EventProposalService,
+    events: Arc<dyn EventRepository>,
+    email: Arc<FakeEmailSender>,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+
+    let events: Arc<dyn EventRepository> = Arc::new(SqliteEventRepository::new(pool.clone()));
+    let email = Arc::new(FakeEmailSender::default());
+    let service = EventProposalService::new(
+        events.clone(),
+        Arc::new(SqliteMemberRepository::new(pool.clone())),
+        Arc::new(AuditService::new(pool.clone())),
+        Arc::new(IntegrationManager::new()),
+        email.clone(),
+        Arc::new(SettingsService::new(
+            pool.clone(),
+            Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+        )),
+        "http://127.0.0.1".to_string(),
+    );
+
+    H {
+        pool,
+        app,
+        service,
+        events,
+        email,
+    }
+}
+
+fn proposal(title: &str) -> ProposeEventInput {
+    ProposeEventInput {
+        title: title.to_string(),
+        description: "Bring your own lockpicks".to_string(),
+        event_type: EventType::Workshop,
+        visibility: EventVisibility::Public,
+        start_time: Utc::now() + Duration::days(7),
+        end_time: None,
+        location: Some("Back room".to_string()),
+    }
+}
+
+/// GET as the member and return the body.
+async fn member_get(app: &Router, uri: &str, cookie: &str) -> String {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+    String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap()
+}
+
+async fn public_events(app: &Router) -> String {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri("/public/events").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn proposal_submitted_through_the_portal_is_hidden_from_listings() {
+    let h = harness().await;
+    let (member_id, session_id, cookie) = member_session(&h.pool, false).await;
+
+    let token = CsrfService::new(SESSION_SECRET).generate_token(&session_id).await.unwrap();
+    let start = (Utc::now() + Duration::days(3)).format("%Y-%m-%dT%H:%M").to_string();
+    let form = format!(
+        "title=Secret+Soldering+Night&description=&event_type=Workshop&visibility=Public&start_time={}&end_time=&location=",
+        start.replace(':', "%3A"),
+    );
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/portal/events/propose")
+                .header("Cookie", &cookie)
+                .header("X-CSRF-Token", token)
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(Body::from(form))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["HX-Redirect"], "/portal/events/propose");
+
+    let mine = h.events.list_by_creator(member_id).await.unwrap();
+    assert_eq!(mine.len(), 1);
+    assert_eq!(mine[0].status, EventStatus::Proposed);
+
+    assert!(h.events.list_upcoming(50).await.unwrap().is_empty());
+    assert!(h.events.list_public().await.unwrap().is_empty());
+    assert!(h.events.list(1000, 0).await.unwrap().is_empty());
+    assert!(!member_get(&h.app, "/portal/api/events/list", &cookie)
+        .await
+        .contains("Secret Soldering Night"));
+    assert!(!public_events(&h.app).await.contains("Secret Soldering Night"));
+
+    // The proposer still sees it on their own page.
+    let page = member_get(&h.app, "/portal/events/propose", &cookie).await;
+    assert!(page.contains("Secret Soldering Night"));
+    assert!(page.contains("Awaiting review"));
+}
+
+#[tokio::test]
+async fn approval_publishes_the_event_and_notifies_the_proposer() {
+    let h = harness().await;
+    let (member_id, _, cookie) = member_session(&h.pool, false).await;
+    let (admin_id, _, _) = member_session(&h.pool, false).await;
+    let member = SqliteMemberRepository::new(h.pool.clone())
+        .find_by_id(member_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let proposed = h.service.propose(&member, proposal("Lockpicking 101")).await.unwrap();
+    assert!(h.email.sent.lock().await.is_empty());
+
+    let approved = h
+        .service
+        .approve(admin_id, proposed.id, Some("Looks great".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(approved.status, EventStatus::Published);
+
+    let listed = h.events.list_upcoming(50).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, proposed.id);
+    assert!(member_get(&h.app, "/portal/api/events/list", &cookie)
+        .await
+        .contains("Lockpicking 101"));
+    assert!(public_events(&h.app).await.contains("Lockpicking 101"));
+
+    let sent = h.email.sent.lock().await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, member.email);
+    assert!(sent[0].subject.contains("approved"), "{}", sent[0].subject);
+    assert!(sent[0].text_body.contains("Looks great"));
+    drop(sent);
+
+    // A second decision on the same proposal loses.
+    let again = h.service.reject(admin_id, proposed.id, "Changed my mind".to_string()).await;
+    assert!(matches!(again, Err(AppError::Conflict(_))));
+}
+
+#[tokio::test]
+async fn rejection_needs_feedback_and_keeps_the_event_hidden() {
+    let h = harness().await;
+    let (member_id, _, _) = member_session(&h.pool, false).await;
+    let (admin_id, _, _) = member_session(&h.pool, false).await;
+    let member = SqliteMemberRepository::new(h.pool.clone())
+        .find_by_id(member_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let proposed = h.service.propose(&member, proposal("Rooftop BBQ")).await.unwrap();
+
+    let err = h.service.reject(admin_id, proposed.id, "  ".to_string()).await;
+    assert!(matches!(err, Err(AppError::BadRequest(_))));
+
+    let rejected = h
+        .service
+        .reject(admin_id, proposed.id, "The roof is off limits".to_string())
+        .await
+        .unwrap();
+    assert_eq!(rejected.status, EventStatus::Rejected);
+    assert_eq!(rejected.review_feedback.as_deref(), Some("The roof is off limits"));
+    assert!(h.events.list_upcoming(50).await.unwrap().is_empty());
+    assert!(h.service.pending().await.unwrap().is_empty());
+
+    let sent = h.email.sent.lock().await;
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].text_body.contains("The roof is off limits"));
+}
@@ -0,0 +1,188 @@
+//! Registration groups: events sharing a group key allow a member one
+//! active RSVP across the group. A second RSVP in the same group is
+//! refused with a message naming the event already held, while events
+//! in another group (or in none) are unaffected, and cancelling frees
+//! the slot. Only a held seat counts: a waitlist place doesn't block
+//! the rest of the group.
+//!
+//! Run with: cargo test --features test-utils --test event_registration_group_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use coterie::{
+    auth::CsrfService,
+    domain::{AttendanceStatus, Event, MemberStatus, UpdateMemberRequest},
+    error::AppError,
+    repository::{
+        EventRepository, MemberRepository, SqliteEventRepository, SqliteMemberRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, event, fresh_pool, make_member, member_session, SESSION_SECRET};
+
+async fn active_member(pool: &SqlitePool) -> Uuid {
+    let id = make_member(pool).await;
+    SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    id
+}
+
+async fn create_event(pool: &SqlitePool, title: &str, group: Option<&str>) -> Event {
+    let creator = active_member(pool).await;
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: title.to_string(),
+            registration_group: group.map(str::to_string),
+            ..event(creator)
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn second_event_in_a_group_is_rejected_other_groups_allowed() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let member = active_member(&pool).await;
+    let red = create_event(&pool, "CTF: Red team", Some("ctf-2026")).await;
+    let blue = create_event(&pool, "CTF: Blue team", Some("ctf-2026")).await;
+    let lockpicking = create_event(&pool, "Lockpicking 101", Some("workshops")).await;
+    let social = create_event(&pool, "Social night", None).await;
+
+    assert_eq!(
+        repo.register_attendance(red.id, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+
+    let err = repo.register_attendance(blue.id, member).await.unwrap_err();
+    match err {
+        AppError::Conflict(msg) => assert!(msg.contains("CTF: Red team"), "{}", msg),
+        other => panic!("expected Conflict, got {:?}", other),
+    }
+    assert_eq!(repo.get_attendee_count(blue.id).await.unwrap(), 0);
+
+    // A different group, and no group at all, are independent.
+    assert_eq!(
+        repo.register_attendance(lockpicking.id, member)
+            .await
+            .unwrap(),
+        AttendanceStatus::Registered
+    );
+    assert_eq!(
+        repo.register_attendance(social.id, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+
+    // Re-confirming the RSVP already held isn't a second registration.
+    assert_eq!(
+        repo.register_attendance(red.id, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+
+    // Someone else can still take a CTF slot.
+    let other = active_member(&pool).await;
+    assert_eq!(
+        repo.register_attendance(blue.id, other).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+
+    // Cancelling frees the member to switch.
+    repo.cancel_attendance(red.id, member).await.unwrap();
+    assert_eq!(
+        repo.register_attendance(blue.id, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+    assert!(repo.register_attendance(red.id, member).await.is_err());
+}
+
+#[tokio::test]
+async fn a_waitlist_place_does_not_hold_the_group() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let red = create_event(&pool, "CTF: Red team", Some("ctf-2026")).await;
+    let blue = create_event(&pool, "CTF: Blue team", Some("ctf-2026")).await;
+    sqlx::query("UPDATE events SET max_attendees = 1 WHERE id = ?")
+        .bind(red.id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let (seated, member) = (active_member(&pool).await, active_member(&pool).await);
+
+    repo.register_attendance(red.id, seated).await.unwrap();
+    assert_eq!(
+        repo.register_attendance(red.id, member).await.unwrap(),
+        AttendanceStatus::Waitlisted
+    );
+    assert_eq!(
+        repo.register_attendance(blue.id, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+
+    // The seat that frees up passes over a member now seated at blue,
+    // who keeps their place in line.
+    assert_eq!(repo.cancel_attendance(red.id, seated).await.unwrap(), None);
+    assert_eq!(
+        repo.get_member_attendance_status(red.id, member)
+            .await
+            .unwrap(),
+        Some(AttendanceStatus::Waitlisted)
+    );
+
+    // Giving up blue makes them eligible again.
+    repo.cancel_attendance(blue.id, member).await.unwrap();
+    assert_eq!(
+        repo.promote_from_waitlist(red.id).await.unwrap(),
+        Some(member)
+    );
+}
+
+#[tokio::test]
+async fn portal_rsvp_shows_why_it_was_blocked() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (member, session_id, cookie) = member_session(&pool, false).await;
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+    let red = create_event(&pool, "CTF: Red team", Some("ctf-2026")).await;
+    let blue = create_event(&pool, "CTF: Blue team", Some("ctf-2026")).await;
+    SqliteEventRepository::new(pool.clone())
+        .register_attendance(red.id, member)
+        .await
+        .unwrap();
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/portal/api/events/{}/rsvp", blue.id))
+                .header("Cookie", cookie)
+                .header("X-CSRF-Token", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body =
+        String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(
+        body.contains("already registered for &quot;CTF: Red team&quot;"),
+        "{}",
+        body
+    );
+}
@@ -2,7 +2,8 @@
 //! the runner method that emails members who RSVP'd to an upcoming
 //! event. Hits a real in-memory SQLite + migrations and asserts on
 //! both the side-effect (one email queued via a fake sender) and the
-//! claim semantics (`event_attendance.reminder_sent_at` stamped).
+//! claim semantics (one `event_reminders_sent` row per lead time),
+//! including several lead times and the member's opt-out.
 //!
 //! Run: cargo test --features test-utils --test event_reminder_test
 
@@ -11,8 +12,8 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use coterie::{
-    auth::SecretCrypto,
-    domain::{CreateMemberRequest, Event, EventType, EventVisibility},
+    auth::{AuthService, SecretCrypto},
+    domain::{CreateMemberRequest, Event, EventType},
     email::{EmailMessage, EmailSender, LogSender},
     error::{AppError, Result as CoterieResult},
     integrations::IntegrationManager,
@@ -28,7 +29,7 @@ use coterie::{
 use sqlx::SqlitePool;
 
 mod common;
-use common::fresh_pool;
+use common::{event, fresh_pool};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
@@ -119,6 +120,10 @@ async fn build_with(email: Arc<FakeEmailSender>, event_start: DateTime<Utc>, sta
         settings,
         email_for_billing,
         integrations,
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
         None,
         "http://localhost:3000".to_string(),
         pool.clone(),
@@ -139,23 +144,13 @@ async fn build_with(email: Arc<FakeEmailSender>, event_start: DateTime<Utc>, sta
 
     // Seed an event.
     let event = Event {
-        id: Uuid::new_v4(),
         title: "Quarterly Meetup".to_string(),
         description: "It's happening.".to_string(),
         event_type: EventType::Social,
-        event_type_id: None,
-        visibility: EventVisibility::MembersOnly,
         start_time: event_start,
         end_time: Some(event_start + Duration::hours(2)),
         location: Some("HQ".to_string()),
-        max_attendees: None,
-        rsvp_required: true,
-        image_url: None,
-        created_by: member.id,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-        series_id: None,
-        occurrence_index: None,
+        ..event(member.id)
     };
     let event = event_repo.create(event).await.expect("create event");
 
@@ -183,16 +178,47 @@ async fn build_with(email: Arc<FakeEmailSender>, event_start: DateTime<Utc>, sta
     }
 }
 
+/// When the default 24-hour reminder was claimed, if it was.
 async fn reminder_sent_at(pool: &SqlitePool, event_id: Uuid, member_id: Uuid) -> Option<String> {
-    let row: Option<(Option<String>,)> = sqlx::query_as(
-        "SELECT reminder_sent_at FROM event_attendance WHERE event_id = ? AND member_id = ?",
+    sqlx::query_scalar(
+        "SELECT sent_at FROM event_reminders_sent \
+         WHERE event_id = ? AND member_id = ? AND lead_hours = 24",
     )
     .bind(event_id.to_string())
     .bind(member_id.to_string())
     .fetch_optional(pool)
     .await
-    .expect("query");
-    row.and_then(|(v,)| v)
+    .expect("query")
+}
+
+/// Lead times whose reminder has been claimed, shortest first.
+async fn windows_sent(pool: &SqlitePool, event_id: Uuid, member_id: Uuid) -> Vec<i64> {
+    sqlx::query_scalar(
+        "SELECT lead_hours FROM event_reminders_sent \
+         WHERE event_id = ? AND member_id = ? ORDER BY lead_hours",
+    )
+    .bind(event_id.to_string())
+    .bind(member_id.to_string())
+    .fetch_all(pool)
+    .await
+    .expect("query")
+}
+
+async fn set_lead_hours(pool: &SqlitePool, value: &str) {
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'events.reminder_lead_hours'")
+        .bind(value)
+        .execute(pool)
+        .await
+        .expect("set lead hours");
+}
+
+async fn move_event(pool: &SqlitePool, event_id: Uuid, start: DateTime<Utc>) {
+    sqlx::query("UPDATE events SET start_time = ? WHERE id = ?")
+        .bind(start.naive_utc())
+        .bind(event_id.to_string())
+        .execute(pool)
+        .await
+        .expect("move event");
 }
 
 // ---------------------------------------------------------------------
@@ -256,10 +282,9 @@ async fn already_stamped_row_skipped() {
     let email = FakeEmailSender::ok();
     let h = build_with(email.clone(), Utc::now() + Duration::hours(6), "Registered").await;
 
-    // Pre-stamp the row.
+    // Pre-claim the reminder.
     sqlx::query(
-        "UPDATE event_attendance SET reminder_sent_at = CURRENT_TIMESTAMP \
-         WHERE event_id = ? AND member_id = ?",
+        "INSERT INTO event_reminders_sent (event_id, member_id, lead_hours) VALUES (?, ?, 24)",
     )
     .bind(h.event_id.to_string())
     .bind(h.member.to_string())
@@ -313,12 +338,71 @@ async fn send_failure_keeps_row_stamped() {
     // (already claimed), proving the claim was persisted.
     let claim_again = h
         .event_repo
-        .mark_reminder_sent(h.event_id, h.member)
+        .mark_reminder_sent(h.event_id, h.member, 24)
         .await
         .expect("mark");
     assert!(!claim_again);
 }
 
+#[tokio::test]
+async fn each_lead_time_sends_its_own_reminder_once() {
+    let email = FakeEmailSender::ok();
+    let h = build_with(
+        email.clone(),
+        Utc::now() + Duration::hours(72),
+        "Registered",
+    )
+    .await;
+    set_lead_hours(&h.pool, "168, 24").await;
+    let notifications = &h.billing.notifications;
+
+    // Three days out: inside the week-before window only.
+    assert_eq!(notifications.send_event_reminders().await.unwrap(), 1);
+    assert_eq!(notifications.send_event_reminders().await.unwrap(), 0);
+    assert_eq!(windows_sent(&h.pool, h.event_id, h.member).await, vec![168]);
+
+    // Six hours out: the day-before reminder goes out, once.
+    move_event(&h.pool, h.event_id, Utc::now() + Duration::hours(6)).await;
+    assert_eq!(notifications.send_event_reminders().await.unwrap(), 1);
+    assert_eq!(notifications.send_event_reminders().await.unwrap(), 0);
+    assert_eq!(
+        windows_sent(&h.pool, h.event_id, h.member).await,
+        vec![24, 168]
+    );
+    assert_eq!(email.count().await, 2);
+}
+
+#[tokio::test]
+async fn late_rsvp_gets_only_the_nearest_reminder() {
+    let email = FakeEmailSender::ok();
+    let h = build_with(email.clone(), Utc::now() + Duration::hours(6), "Registered").await;
+    set_lead_hours(&h.pool, "168,24").await;
+
+    assert_eq!(
+        h.billing.notifications.send_event_reminders().await.unwrap(),
+        1
+    );
+    assert_eq!(windows_sent(&h.pool, h.event_id, h.member).await, vec![24]);
+}
+
+#[tokio::test]
+async fn opted_out_member_gets_no_reminders() {
+    let email = FakeEmailSender::ok();
+    let h = build_with(email.clone(), Utc::now() + Duration::hours(6), "Registered").await;
+    h.event_repo
+        .set_reminders_enabled(h.member, false)
+        .await
+        .expect("opt out");
+    assert!(!h.event_repo.reminders_enabled(h.member).await.unwrap());
+
+    assert_eq!(
+        h.billing.notifications.send_event_reminders().await.unwrap(),
+        0
+    );
+    assert_eq!(email.count().await, 0);
+    assert!(windows_sent(&h.pool, h.event_id, h.member).await.is_empty());
+}
+
 // LogSender import kept for parity with neighbour test files; suppresses
 // the dead-code lint when nothing in this module wires the real sender.
 #[allow(dead_code)]
@@ -0,0 +1,110 @@
+//! `SqliteEventRepository` round-trips an event's image and its
+//! configurable event type through create and update, and deleting an
+//! event takes its RSVPs with it.
+//!
+//! Run with: cargo test --features test-utils --test event_repository_test
+
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Event, EventType, EventVisibility},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use uuid::Uuid;
+
+mod common;
+use common::{event, fresh_pool, make_member};
+
+#[tokio::test]
+async fn image_url_and_event_type_id_survive_create_and_update() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let creator = make_member(&pool).await;
+    let type_id = |slug: &'static str| {
+        let pool = pool.clone();
+        async move {
+            let id: String = sqlx::query_scalar("SELECT id FROM event_types WHERE slug = ?")
+                .bind(slug)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            Uuid::parse_str(&id).unwrap()
+        }
+    };
+    let social = type_id("social").await;
+    let meeting = type_id("member-meeting").await;
+
+    let created = repo
+        .create(Event {
+            title: "Photo walk".to_string(),
+            event_type: EventType::Social,
+            event_type_id: Some(social),
+            visibility: EventVisibility::Public,
+            start_time: Utc::now() + Duration::days(3),
+            end_time: None,
+            rsvp_required: false,
+            image_url: Some("/uploads/events/photo-walk.jpg".to_string()),
+            ..event(creator)
+        })
+        .await
+        .unwrap();
+
+    let found = repo.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(
+        found.image_url.as_deref(),
+        Some("/uploads/events/photo-walk.jpg")
+    );
+    assert_eq!(found.event_type_id, Some(social));
+
+    let mut edited = found;
+    edited.image_url = Some("/uploads/events/photo-walk-2.jpg".to_string());
+    edited.event_type_id = Some(meeting);
+    repo.update(edited.id, edited).await.unwrap();
+
+    let found = repo.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(
+        found.image_url.as_deref(),
+        Some("/uploads/events/photo-walk-2.jpg")
+    );
+    assert_eq!(found.event_type_id, Some(meeting));
+    let listed = repo.list_upcoming(10).await.unwrap();
+    let listed = listed.iter().find(|e| e.id == created.id).unwrap();
+    assert_eq!(
+        listed.image_url.as_deref(),
+        Some("/uploads/events/photo-walk-2.jpg")
+    );
+}
+
+#[tokio::test]
+async fn deleting_an_event_removes_its_attendance() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let member = make_member(&pool).await;
+    let event = repo
+        .create(Event {
+            title: "Open night".to_string(),
+            event_type: EventType::Social,
+            visibility: EventVisibility::Public,
+            start_time: Utc::now() + Duration::days(3),
+            end_time: None,
+            allow_guest_rsvp: true,
+            ..event(member)
+        })
+        .await
+        .unwrap();
+    repo.register_attendance(event.id, member).await.unwrap();
+    assert!(repo
+        .register_guest(event.id, "Ada Guest", "ada@example.com")
+        .await
+        .unwrap());
+    assert_eq!(repo.list_attendees(event.id).await.unwrap().len(), 2);
+
+    repo.delete(event.id).await.unwrap();
+
+    let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM event_attendance WHERE event_id = ?")
+        .bind(event.id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(left, 0);
+    assert_eq!(repo.get_attendee_count(event.id).await.unwrap(), 0);
+}
@@ -0,0 +1,243 @@
+//! RSVP deadlines: members and guests can't RSVP once an event's
+//! deadline has passed, admins can still register a member by hand,
+//! and a deadline after the start is refused. The refusal names the
+//! deadline in the viewer's locale.
+//!
+//! Run with: cargo test --features test-utils --test event_rsvp_deadline_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    auth::CsrfService,
+    domain::{AttendanceStatus, Event, EventType, EventVisibility, Locale, UpdateSettingRequest},
+    error::AppError,
+    repository::{
+        EventRepository, MemberRepository, SqliteEventRepository, SqliteMemberRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{
+    build_app, build_app_state, build_router, event, fresh_pool, make_member, member_session,
+    SESSION_SECRET,
+};
+
+async fn create_event(pool: &SqlitePool, rsvp_deadline: Option<DateTime<Utc>>) -> Uuid {
+    let creator = make_member(pool).await;
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Workshop".to_string(),
+            visibility: EventVisibility::Public,
+            start_time: Utc::now() + Duration::days(2),
+            end_time: None,
+            allow_guest_rsvp: true,
+            rsvp_deadline,
+            ..event(creator)
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn rsvp_after_deadline_rejected_but_admin_can_register() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let closed = create_event(&pool, Some(Utc::now() - Duration::hours(1))).await;
+    let open = create_event(&pool, Some(Utc::now() + Duration::days(1))).await;
+    let member = make_member(&pool).await;
+
+    let err = repo.register_attendance(closed, member).await.unwrap_err();
+    assert!(matches!(&err, AppError::RsvpClosed(_)), "{err:?}");
+    assert_eq!(
+        repo.get_member_attendance_status(closed, member)
+            .await
+            .unwrap(),
+        None
+    );
+    assert!(matches!(
+        repo.register_guest(closed, "Guest", "guest@example.com")
+            .await
+            .unwrap_err(),
+        AppError::RsvpClosed(_)
+    ));
+
+    assert!(repo
+        .admin_register_attendance(closed, member)
+        .await
+        .unwrap());
+    assert_eq!(
+        repo.get_member_attendance_status(closed, member)
+            .await
+            .unwrap(),
+        Some(AttendanceStatus::Registered)
+    );
+
+    // Before the deadline nothing changes.
+    assert_eq!(
+        repo.register_attendance(open, member).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+}
+
+/// POST a form over the session `(session_id, cookie)`.
+async fn post(app: &Router, uri: &str, (session_id, cookie): (&str, &str), body: String) -> String {
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(session_id)
+        .await
+        .unwrap();
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .header("X-CSRF-Token", token)
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "POST {uri}");
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn portal_rejects_late_member_rsvp_and_allows_admin_override() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let event = create_event(&pool, Some(Utc::now() - Duration::hours(1))).await;
+    let (member, member_session_id, member_cookie) = member_session(&pool, false).await;
+    let (_, admin_session_id, admin_cookie) = member_session(&pool, true).await;
+    let repo = SqliteEventRepository::new(pool.clone());
+
+    let html = post(
+        &app,
+        &format!("/portal/api/events/{event}/rsvp"),
+        (&member_session_id, &member_cookie),
+        String::new(),
+    )
+    .await;
+    assert!(html.contains("RSVPs closed"), "{html}");
+    assert_eq!(
+        repo.get_member_attendance_status(event, member)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let username = SqliteMemberRepository::new(pool.clone())
+        .find_by_id(member)
+        .await
+        .unwrap()
+        .unwrap()
+        .username;
+    let html = post(
+        &app,
+        &format!("/portal/admin/events/{event}/attendees"),
+        (&admin_session_id, &admin_cookie),
+        format!("member={username}&csrf_token=x"),
+    )
+    .await;
+    assert!(html.contains("is registered"), "{html}");
+    assert_eq!(
+        repo.get_member_attendance_status(event, member)
+            .await
+            .unwrap(),
+        Some(AttendanceStatus::Registered)
+    );
+}
+
+#[tokio::test]
+async fn late_guest_rsvp_names_the_deadline_in_the_org_locale() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let admin = make_member(&pool).await;
+    state
+        .service_context
+        .settings_service
+        .update_setting(
+            "org.locale",
+            UpdateSettingRequest {
+                value: "de-DE".to_string(),
+                reason: None,
+            },
+            admin,
+        )
+        .await
+        .unwrap();
+    let deadline = Utc::now() - Duration::hours(1);
+    let event = create_event(&pool, Some(deadline)).await;
+
+    let resp = build_router(state)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/public/events/rsvp")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "event_id": event,
+                        "name": "Guest",
+                        "email": "guest@example.com",
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(
+        body.contains(&Locale::DeDe.long_date_time(&deadline)),
+        "{body}"
+    );
+}
+
+#[tokio::test]
+async fn deadline_after_start_is_refused() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let admin = make_member(&pool).await;
+    let start = Utc::now() + Duration::days(2);
+
+    let err = state
+        .service_context
+        .event_admin_service
+        .create(
+            admin,
+            coterie::service::event_admin_service::CreateEventInput {
+                title: "Late".to_string(),
+                description: String::new(),
+                event_type: EventType::Meeting,
+                event_type_id: None,
+                visibility: EventVisibility::MembersOnly,
+                start_time: start,
+                end_time: None,
+                location: None,
+                max_attendees: None,
+                rsvp_required: true,
+                allow_guest_rsvp: false,
+                registration_group: None,
+                rsvp_deadline: Some(start + Duration::hours(1)),
+                image_url: None,
+                recurrence: None,
+                recurrence_until: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+}
@@ -0,0 +1,161 @@
+//! Event visibility is enforced everywhere an event is listed: an
+//! `AdminOnly` event never reaches a regular member's portal or the
+//! public feeds, `MembersOnly` events go out to the public only as a
+//! sanitized time slot in the JSON listing (and not at all in either
+//! calendar feed), and `Public` events show up as-is.
+//!
+//! Run with: cargo test --features test-utils --test event_visibility_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    domain::{Event, EventType, EventVisibility},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, event, fresh_pool, member_session, SESSION_SECRET};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    members_only: Event,
+    admin_only: Event,
+}
+
+/// One event of each visibility, a week out, created by an admin.
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+
+    let (creator, _, _) = member_session(&pool, true).await;
+    let events = SqliteEventRepository::new(pool.clone());
+    let (mut members_only, mut admin_only) = (None, None);
+    for (title, visibility) in [
+        ("Open House", EventVisibility::Public),
+        ("Members Lockpicking", EventVisibility::MembersOnly),
+        ("Board Budget Session", EventVisibility::AdminOnly),
+    ] {
+        let event = events
+            .create(Event {
+                title: title.to_string(),
+                description: format!("{} details", title),
+                event_type: EventType::Meeting,
+                visibility,
+                location: Some("Back room".to_string()),
+                ..event(creator)
+            })
+            .await
+            .unwrap();
+        match event.visibility {
+            EventVisibility::MembersOnly => members_only = Some(event),
+            EventVisibility::AdminOnly => admin_only = Some(event),
+            _ => {}
+        }
+    }
+
+    H {
+        pool,
+        app,
+        members_only: members_only.unwrap(),
+        admin_only: admin_only.unwrap(),
+    }
+}
+
+async fn get(app: &Router, uri: &str, cookie: Option<&str>) -> String {
+    let mut req = Request::builder().uri(uri);
+    if let Some(cookie) = cookie {
+        req = req.header("Cookie", cookie);
+    }
+    let resp = app
+        .clone()
+        .oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "GET {}", uri);
+    String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn portal_listings_hide_admin_only_events_from_members() {
+    let h = harness().await;
+    let (_, _, member) = member_session(&h.pool, false).await;
+    let (_, _, admin) = member_session(&h.pool, true).await;
+
+    for uri in ["/portal/api/events/list", "/portal/api/events/upcoming"] {
+        let seen = get(&h.app, uri, Some(&member)).await;
+        assert!(seen.contains("Open House"), "{}: {}", uri, seen);
+        assert!(seen.contains("Members Lockpicking"), "{}: {}", uri, seen);
+        assert!(!seen.contains("Board Budget Session"), "{}: {}", uri, seen);
+
+        let seen = get(&h.app, uri, Some(&admin)).await;
+        assert!(seen.contains("Board Budget Session"), "{}: {}", uri, seen);
+    }
+}
+
+#[tokio::test]
+async fn member_cannot_rsvp_to_admin_only_event() {
+    let h = harness().await;
+    let (_, session_id, cookie) = member_session(&h.pool, false).await;
+    let token = CsrfService::new(SESSION_SECRET).generate_token(&session_id).await.unwrap();
+
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/portal/api/events/{}/rsvp", h.admin_only.id))
+                .header("Cookie", &cookie)
+                .header("X-CSRF-Token", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(body.contains("Event not found"), "{}", body);
+
+    let registered: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM event_attendance WHERE event_id = ?")
+            .bind(h.admin_only.id.to_string())
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    assert_eq!(registered, 0);
+}
+
+#[tokio::test]
+async fn public_feeds_never_include_admin_only_events() {
+    let h = harness().await;
+
+    for uri in ["/public/events", "/public/events?format=ical", "/public/feed/calendar"] {
+        let seen = get(&h.app, uri, None).await;
+        assert!(seen.contains("Open House"), "{}: {}", uri, seen);
+        // Only the JSON listing shows members-only slots; neither
+        // calendar feed carries them.
+        assert_eq!(
+            seen.contains("Members-Only Event"),
+            uri == "/public/events",
+            "{}: {}",
+            uri,
+            seen
+        );
+        assert_eq!(
+            seen.contains(&h.members_only.id.to_string()),
+            uri == "/public/events",
+            "{}: {}",
+            uri,
+            seen
+        );
+        assert!(!seen.contains("Members Lockpicking"), "{}: {}", uri, seen);
+        assert!(!seen.contains("Board Budget Session"), "{}: {}", uri, seen);
+        assert!(!seen.contains(&h.admin_only.id.to_string()), "{}: {}", uri, seen);
+    }
+}
@@ -0,0 +1,165 @@
+//! RSVP waitlist: once an event reaches `max_attendees`, a member's
+//! RSVP puts them on the waitlist instead of failing. A cancellation
+//! that frees a seat promotes the longest-waiting member, and the
+//! others move up the line. The admin event page lists every RSVP
+//! with waitlisted members marked as such.
+//!
+//! Run with: cargo test --test event_waitlist_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use coterie::{
+    domain::{AttendanceStatus, Event},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, event, fresh_pool, make_member, make_member_with_email, member_session};
+
+async fn create_event(pool: &SqlitePool, max_attendees: i32) -> Uuid {
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Lockpicking 101".to_string(),
+            max_attendees: Some(max_attendees),
+            ..event(make_member(pool).await)
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn full_event_waitlists_and_cancellation_promotes_in_order() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let event = create_event(&pool, 1).await;
+    let (seated, first, second) = (
+        make_member(&pool).await,
+        make_member(&pool).await,
+        make_member(&pool).await,
+    );
+
+    assert_eq!(
+        repo.register_attendance(event, seated).await.unwrap(),
+        AttendanceStatus::Registered
+    );
+    for member in [first, second] {
+        assert_eq!(
+            repo.register_attendance(event, member).await.unwrap(),
+            AttendanceStatus::Waitlisted
+        );
+    }
+    assert_eq!(repo.get_attendee_count(event).await.unwrap(), 1);
+    assert_eq!(repo.waitlist_position(event, seated).await.unwrap(), None);
+    assert_eq!(
+        repo.waitlist_position(event, second).await.unwrap(),
+        Some(2)
+    );
+
+    // RSVPing again while waitlisted keeps the member's place.
+    repo.register_attendance(event, first).await.unwrap();
+    assert_eq!(repo.waitlist_position(event, first).await.unwrap(), Some(1));
+
+    // No free seat, nobody moves.
+    assert_eq!(repo.promote_from_waitlist(event).await.unwrap(), None);
+
+    assert_eq!(
+        repo.cancel_attendance(event, seated).await.unwrap(),
+        Some(first)
+    );
+    assert_eq!(
+        repo.get_member_attendance_status(event, first)
+            .await
+            .unwrap(),
+        Some(AttendanceStatus::Registered)
+    );
+    assert_eq!(
+        repo.waitlist_position(event, second).await.unwrap(),
+        Some(1)
+    );
+    assert_eq!(repo.get_attendee_count(event).await.unwrap(), 1);
+
+    // Leaving the waitlist frees no seat.
+    assert_eq!(repo.cancel_attendance(event, second).await.unwrap(), None);
+    assert_eq!(repo.waitlist_position(event, second).await.unwrap(), None);
+    assert_eq!(repo.get_attendee_count(event).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn lowered_capacity_keeps_registered_members_seated() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let event = create_event(&pool, 2).await;
+    let (first, second) = (make_member(&pool).await, make_member(&pool).await);
+    for member in [first, second] {
+        assert_eq!(
+            repo.register_attendance(event, member).await.unwrap(),
+            AttendanceStatus::Registered
+        );
+    }
+
+    sqlx::query("UPDATE events SET max_attendees = 1 WHERE id = ?")
+        .bind(event.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Two seated against a cap of one: RSVPing again mustn't trade
+    // either seat for a waitlist place.
+    for member in [first, second] {
+        assert_eq!(
+            repo.register_attendance(event, member).await.unwrap(),
+            AttendanceStatus::Registered
+        );
+        assert_eq!(
+            repo.get_member_attendance_status(event, member)
+                .await
+                .unwrap(),
+            Some(AttendanceStatus::Registered)
+        );
+    }
+    assert_eq!(repo.get_attendee_count(event).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn admin_event_page_lists_attendees_by_status() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let event = create_event(&pool, 1).await;
+    let (seated, seated_email) = make_member_with_email(&pool).await;
+    let (waiting, waiting_email) = make_member_with_email(&pool).await;
+    repo.register_attendance(event, seated).await.unwrap();
+    repo.register_attendance(event, waiting).await.unwrap();
+
+    let (_, _, admin) = member_session(&pool, true).await;
+    let app = build_app(&pool).await;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/portal/admin/events/{}", event))
+                .header("Cookie", &admin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let html =
+        String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+
+    let seated_at = html.find(&seated_email).expect("registered member listed");
+    let waiting_at = html.find(&waiting_email).expect("waitlisted member listed");
+    assert!(seated_at < waiting_at, "listed in sign-up order");
+    assert!(
+        html[seated_at..waiting_at].contains(">Registered<"),
+        "{}",
+        html
+    );
+    assert!(html[waiting_at..].contains(">Waitlisted<"), "{}", html);
+    assert!(html.contains(&format!("/portal/admin/events/{}/attendees/export", event)));
+}
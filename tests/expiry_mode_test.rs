@@ -0,0 +1,273 @@
+//! Derived expiry mode: with `membership.expiry_mode = derived` the
+//! sweep leaves a lapsed member `Active` in the database, but their
+//! effective status is `Expired` for access, the portal and the
+//! integration events; the default mode still writes `Expired`.
+//!
+//! Run with: cargo test --features test-utils --test expiry_mode_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    api::state::AppState,
+    domain::{MemberStatus, Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus},
+    error::Result as CoterieResult,
+    integrations::{Integration, IntegrationEvent},
+    repository::{
+        MemberRepository, PaymentRepository, SqliteMemberRepository, SqlitePaymentRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session};
+
+/// Records `(event kind, member status)` for every member event.
+#[derive(Default)]
+struct Recorder {
+    seen: Mutex<Vec<(String, MemberStatus)>>,
+}
+
+#[async_trait]
+impl Integration for Recorder {
+    fn name(&self) -> &str {
+        "Recorder"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: &IntegrationEvent) -> CoterieResult<()> {
+        let status = match event {
+            IntegrationEvent::MemberActivated(m) | IntegrationEvent::MemberExpired(m) => m.status,
+            IntegrationEvent::MemberUpdated { old, new } => {
+                self.seen
+                    .lock()
+                    .await
+                    .push(("member_updated_old".to_string(), old.status));
+                new.status
+            }
+            _ => return Ok(()),
+        };
+        self.seen
+            .lock()
+            .await
+            .push((event.kind().as_str().to_string(), status));
+        Ok(())
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    state: AppState,
+    recorder: Arc<Recorder>,
+    member_id: Uuid,
+    cookie: String,
+}
+
+/// An `Active` member whose dues ran out 60 days ago (well past the
+/// default 30-day grace), with a live session.
+async fn harness(mode: &str) -> H {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let recorder = Arc::new(Recorder::default());
+    state
+        .service_context
+        .integration_manager
+        .register(recorder.clone())
+        .await;
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'membership.expiry_mode'")
+        .bind(mode)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (member_id, _, cookie) = member_session(&pool, false).await;
+    sqlx::query("UPDATE members SET dues_paid_until = ? WHERE id = ?")
+        .bind(Utc::now() - Duration::days(60))
+        .bind(member_id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+    H {
+        pool,
+        state,
+        recorder,
+        member_id,
+        cookie,
+    }
+}
+
+async fn stored_status(h: &H) -> MemberStatus {
+    SqliteMemberRepository::new(h.pool.clone())
+        .find_by_id(h.member_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .status
+}
+
+async fn effective_status(h: &H) -> MemberStatus {
+    let member = SqliteMemberRepository::new(h.pool.clone())
+        .find_by_id(h.member_id)
+        .await
+        .unwrap()
+        .unwrap();
+    h.state
+        .service_context
+        .settings_service
+        .effective_status(&member)
+        .await
+}
+
+async fn sweep(h: &H) -> u32 {
+    h.state
+        .billing_service
+        .expiration
+        .check_expired_members()
+        .await
+        .unwrap()
+}
+
+async fn dashboard_location(h: &H) -> Option<String> {
+    let app = coterie::web::create_web_routes(h.state.clone());
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/portal/dashboard")
+                .header(header::COOKIE, &h.cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    if resp.status() == StatusCode::OK {
+        return None;
+    }
+    resp.headers()
+        .get(header::LOCATION)
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn derived_mode_keeps_stored_status_but_treats_member_as_expired() {
+    let h = harness("derived").await;
+
+    // Lapsed before any sweep: already Expired in effect.
+    assert_eq!(stored_status(&h).await, MemberStatus::Active);
+    assert_eq!(effective_status(&h).await, MemberStatus::Expired);
+
+    assert_eq!(sweep(&h).await, 1);
+    assert_eq!(stored_status(&h).await, MemberStatus::Active, "no write");
+    assert_eq!(effective_status(&h).await, MemberStatus::Expired);
+    assert_eq!(
+        *h.recorder.seen.lock().await,
+        vec![("member_expired".to_string(), MemberStatus::Expired)],
+        "integrations still hear about the lapse, with the effective status"
+    );
+
+    // One dispatch per lapse, not one per day.
+    assert_eq!(sweep(&h).await, 0);
+    assert_eq!(h.recorder.seen.lock().await.len(), 1);
+
+    // The session survives, but access follows the effective status.
+    assert_eq!(
+        dashboard_location(&h).await.as_deref(),
+        Some("/portal/restore")
+    );
+}
+
+#[tokio::test]
+async fn renewing_a_derived_lapse_restores_effective_status() {
+    let h = harness("derived").await;
+    sweep(&h).await;
+    h.recorder.seen.lock().await.clear();
+
+    let (admin_id, _, _) = member_session(&h.pool, true).await;
+    h.state
+        .service_context
+        .member_service
+        .extend_dues(admin_id, h.member_id, 1, None)
+        .await
+        .unwrap();
+
+    assert_eq!(effective_status(&h).await, MemberStatus::Active);
+    assert_eq!(
+        *h.recorder.seen.lock().await,
+        vec![
+            ("member_updated_old".to_string(), MemberStatus::Expired),
+            ("member_updated".to_string(), MemberStatus::Active),
+        ],
+        "integrations see the effective Expired -> Active change"
+    );
+    assert_eq!(dashboard_location(&h).await, None);
+}
+
+#[tokio::test]
+async fn a_payment_ending_a_derived_lapse_activates_the_member() {
+    let h = harness("derived").await;
+    let member = SqliteMemberRepository::new(h.pool.clone())
+        .find_by_id(h.member_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let payment = SqlitePaymentRepository::new(h.pool.clone())
+        .create(Payment {
+            id: Uuid::new_v4(),
+            payer: Payer::Member(h.member_id),
+            amount_cents: 50_00,
+            currency: "USD".to_string(),
+            status: PaymentStatus::Completed,
+            payment_method: PaymentMethod::Manual,
+            external_id: None,
+            description: "Membership renewal".to_string(),
+            kind: PaymentKind::Membership,
+            paid_at: Some(Utc::now()),
+            refunded_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+
+    h.state
+        .billing_service
+        .auto_renew
+        .extend_member_dues(payment.id, h.member_id, member.membership_type_id)
+        .await
+        .unwrap();
+
+    assert_eq!(stored_status(&h).await, MemberStatus::Active);
+    assert_eq!(effective_status(&h).await, MemberStatus::Active);
+    assert_eq!(
+        *h.recorder.seen.lock().await,
+        vec![("member_activated".to_string(), MemberStatus::Active)],
+        "the stored status never changed, but the effective one did"
+    );
+}
+
+#[tokio::test]
+async fn stored_mode_writes_expired() {
+    let h = harness("stored").await;
+
+    assert_eq!(sweep(&h).await, 1);
+    assert_eq!(stored_status(&h).await, MemberStatus::Expired);
+    assert_eq!(effective_status(&h).await, MemberStatus::Expired);
+    assert_eq!(
+        *h.recorder.seen.lock().await,
+        vec![("member_expired".to_string(), MemberStatus::Expired)]
+    );
+}
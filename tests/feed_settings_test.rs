@@ -0,0 +1,120 @@
+//! The RSS feed follows `announcements.feed_item_limit` unless the
+//! request passes `?limit=`, and `announcements.feed_full_content`
+//! switches items between the rendered announcement and a short
+//! plain-text summary.
+//!
+//! Run with: cargo test --test feed_settings_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType, UpdateSettingRequest},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+    service::settings_service::SettingsService,
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+async fn rss(app: &Router, uri: &str) -> String {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+fn descriptions(rss: &str) -> Vec<&str> {
+    rss.split("<description><![CDATA[")
+        .skip(1)
+        .map(|d| d.split("]]></description>").next().unwrap())
+        .collect()
+}
+
+async fn set(settings: &SettingsService, key: &str, value: &str, admin: Uuid) {
+    settings
+        .update_setting(
+            key,
+            UpdateSettingRequest {
+                value: value.to_string(),
+                reason: None,
+            },
+            admin,
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn limit_and_content_settings_shape_the_feed() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let settings = state.service_context.settings_service.clone();
+    let admin = make_member(&pool).await;
+
+    let repo = SqliteAnnouncementRepository::new(pool.clone());
+    let content = format!(
+        "**Big news.** {}",
+        "The space is open late this week. ".repeat(20)
+    );
+    for i in 0..5 {
+        let published = Utc::now() - Duration::days(i);
+        repo.create(Announcement {
+            id: Uuid::new_v4(),
+            title: format!("Update {}", i),
+            content: content.clone(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public: true,
+            featured: false,
+            image_url: None,
+            published_at: Some(published),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: admin,
+            created_at: published,
+            updated_at: published,
+        })
+        .await
+        .unwrap();
+    }
+    let app = coterie::api::create_app(state);
+
+    set(&settings, "announcements.feed_item_limit", "3", admin).await;
+    let feed = rss(&app, "/public/feed/rss").await;
+    assert_eq!(feed.matches("<item>").count(), 3, "{}", feed);
+    assert!(feed.find("Update 0").unwrap() < feed.find("Update 1").unwrap());
+
+    let feed = rss(&app, "/public/feed/rss?limit=2").await;
+    assert_eq!(feed.matches("<item>").count(), 2);
+    let feed = rss(&app, "/public/feed/rss?limit=1000").await;
+    assert_eq!(feed.matches("<item>").count(), 5);
+
+    let full = rss(&app, "/public/feed/rss").await;
+    let full = descriptions(&full);
+    assert!(
+        full[0].contains("<strong>Big news.</strong>"),
+        "{}",
+        full[0]
+    );
+
+    set(&settings, "announcements.feed_full_content", "false", admin).await;
+    let summary = rss(&app, "/public/feed/rss").await;
+    let summary = descriptions(&summary);
+    assert_eq!(summary.len(), 3);
+    assert!(summary[0].starts_with("Big news."), "{}", summary[0]);
+    assert!(summary[0].ends_with('…'), "{}", summary[0]);
+    assert!(summary[0].len() < full[0].len() / 2);
+}
@@ -0,0 +1,101 @@
+//! `GET /public/feed/rss?type=` narrows the feed to announcements of
+//! the given configured type slugs (one, or comma-separated). Without
+//! the parameter the feed is unchanged, and an unknown slug is a 400.
+//!
+//! Run with: cargo test --test feed_type_filter_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn type_id(pool: &SqlitePool, slug: &str) -> Uuid {
+    let id: String = sqlx::query_scalar("SELECT id FROM announcement_types WHERE slug = ?")
+        .bind(slug)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    Uuid::parse_str(&id).unwrap()
+}
+
+async fn publish(pool: &SqlitePool, title: &str, type_id: Option<Uuid>, is_public: bool) {
+    let author = make_member(pool).await;
+    let published = Utc::now() - Duration::hours(1);
+    SqliteAnnouncementRepository::new(pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: title.to_string(),
+            content: "Body".to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: type_id,
+            is_public,
+            featured: false,
+            image_url: None,
+            published_at: Some(published),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: author,
+            created_at: published,
+            updated_at: published,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn type_parameter_filters_the_feed_by_slug() {
+    let pool = fresh_pool().await;
+    let news = type_id(&pool, "news").await;
+    let awards = type_id(&pool, "awards").await;
+    publish(&pool, "Open night", Some(news), true).await;
+    publish(&pool, "Board minutes", Some(news), false).await;
+    publish(&pool, "Member of the month", Some(awards), true).await;
+    publish(&pool, "Untyped notice", None, true).await;
+    let app = coterie::api::create_app(build_app_state(pool.clone()).await);
+
+    let (status, feed) = get(&app, "/public/feed/rss").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(feed.matches("<item>").count(), 3, "{}", feed);
+
+    let (status, feed) = get(&app, "/public/feed/rss?type=news").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(feed.contains("Open night"), "{}", feed);
+    assert_eq!(feed.matches("<item>").count(), 1, "members-only stays out");
+
+    let (_, feed) = get(&app, "/public/feed/rss?type=news,%20awards").await;
+    assert!(feed.contains("Open night") && feed.contains("Member of the month"));
+    assert!(!feed.contains("Untyped notice"), "{}", feed);
+
+    let (status, _) = get(&app, "/public/feed/rss?type=news,meetups").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // An empty parameter is the same as none.
+    let (_, feed) = get(&app, "/public/feed/rss?type=").await;
+    assert_eq!(feed.matches("<item>").count(), 3);
+}
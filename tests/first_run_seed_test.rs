@@ -0,0 +1,117 @@
+//! `first_run::seed_if_empty`: an empty database gets the configured
+//! admin and membership types; a database with members is untouched;
+//! a weak admin password refuses to seed at all.
+//!
+//! Run with: cargo test --test first_run_seed_test
+
+use coterie::{
+    config::{AdminSeedConfig, MembershipTypeSeedConfig, SeedConfig},
+    domain::MemberStatus,
+    first_run::{seed_if_empty, FirstRunOutcome},
+    repository::{
+        MemberRepository, MembershipTypeRepository, SqliteMemberRepository,
+        SqliteMembershipTypeRepository,
+    },
+};
+
+mod common;
+use common::{fresh_pool, make_member};
+
+fn config() -> SeedConfig {
+    SeedConfig {
+        on_first_run: true,
+        admin: AdminSeedConfig {
+            email: "root@example.org".to_string(),
+            username: "root".to_string(),
+            full_name: "Root Admin".to_string(),
+            password: "Correct-horse-battery-9".to_string(),
+        },
+        test_users: Vec::new(),
+        membership_types: vec![MembershipTypeSeedConfig {
+            name: "Sustaining".to_string(),
+            slug: "sustaining".to_string(),
+            color: "#123456".to_string(),
+            fee_cents: 12_000,
+            billing_frequency: "yearly".to_string(),
+        }],
+    }
+}
+
+async fn member_count(pool: &sqlx::SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM members")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn empty_database_gets_admin_and_types() {
+    let pool = fresh_pool().await;
+
+    let outcome = seed_if_empty(&pool, &config()).await.unwrap();
+    let FirstRunOutcome::Seeded {
+        admin_id,
+        membership_types,
+    } = outcome
+    else {
+        panic!("expected Seeded, got {:?}", outcome);
+    };
+    assert_eq!(membership_types, 1);
+
+    let admin = SqliteMemberRepository::new(pool.clone())
+        .find_by_id(admin_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(admin.username, "root");
+    assert_eq!(admin.email, "root@example.org");
+    assert!(admin.is_admin);
+    assert!(admin.bypass_dues);
+    assert_eq!(admin.status, MemberStatus::Active);
+    assert!(admin.email_verified_at.is_some());
+
+    let sustaining = SqliteMembershipTypeRepository::new(pool.clone())
+        .find_by_slug("sustaining")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sustaining.fee_cents, 12_000);
+    assert_eq!(admin.membership_type_id, sustaining.id);
+
+    // Running again (every later boot) changes nothing.
+    assert_eq!(
+        seed_if_empty(&pool, &config()).await.unwrap(),
+        FirstRunOutcome::AlreadyPopulated
+    );
+    assert_eq!(member_count(&pool).await, 1);
+}
+
+#[tokio::test]
+async fn populated_database_is_left_alone() {
+    let pool = fresh_pool().await;
+    make_member(&pool).await;
+
+    assert_eq!(
+        seed_if_empty(&pool, &config()).await.unwrap(),
+        FirstRunOutcome::AlreadyPopulated
+    );
+    assert_eq!(member_count(&pool).await, 1);
+    assert!(SqliteMembershipTypeRepository::new(pool.clone())
+        .find_by_slug("sustaining")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn weak_admin_password_refuses_to_seed() {
+    let pool = fresh_pool().await;
+
+    // The built-in default admin password is deliberately too weak.
+    let cfg = SeedConfig {
+        on_first_run: true,
+        ..SeedConfig::default()
+    };
+    assert!(seed_if_empty(&pool, &cfg).await.is_err());
+    assert_eq!(member_count(&pool).await, 0);
+}
@@ -0,0 +1,219 @@
+//! Guest RSVPs: a non-member can RSVP to a public event that allows
+//! it with just a name and email. Guests take a seat like anyone else
+//! (capacity counts them) and show up in the admin attendee export.
+//!
+//! Run with: cargo test --features test-utils --test guest_rsvp_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    domain::{Event, EventVisibility},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, event, fresh_pool, member_session, SESSION_SECRET};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    H { pool, app }
+}
+
+async fn create_event(
+    pool: &SqlitePool,
+    visibility: EventVisibility,
+    allow_guest_rsvp: bool,
+    max_attendees: Option<i32>,
+) -> Event {
+    let (creator, _, _) = member_session(pool, true).await;
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Open Soldering Workshop".to_string(),
+            description: "Bring a friend".to_string(),
+            visibility,
+            location: Some("Workshop".to_string()),
+            max_attendees,
+            allow_guest_rsvp,
+            ..event(creator)
+        })
+        .await
+        .unwrap()
+}
+
+async fn guest_rsvp(h: &H, event_id: Uuid, name: &str, email: &str) -> StatusCode {
+    h.app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/public/events/rsvp")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "event_id": event_id, "name": name, "email": email })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn member_rsvp(h: &H, event_id: Uuid) -> String {
+    let (_, session_id, cookie) = member_session(&h.pool, false).await;
+    let token = CsrfService::new(SESSION_SECRET).generate_token(&session_id).await.unwrap();
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/portal/api/events/{}/rsvp", event_id))
+                .header("Cookie", &cookie)
+                .header("X-CSRF-Token", token)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap()
+}
+
+async fn attendee_count(pool: &SqlitePool, event_id: Uuid) -> i64 {
+    SqliteEventRepository::new(pool.clone())
+        .get_attendee_count(event_id)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn guest_rsvp_counts_toward_capacity() {
+    let h = harness().await;
+    let event = create_event(&h.pool, EventVisibility::Public, true, Some(2)).await;
+
+    let body = member_rsvp(&h, event.id).await;
+    assert!(body.contains("Cancel"), "member took a seat: {}", body);
+    assert_eq!(
+        guest_rsvp(&h, event.id, "Ada Guest", "Ada@Example.com").await,
+        StatusCode::CREATED
+    );
+    assert_eq!(attendee_count(&h.pool, event.id).await, 2);
+
+    // Full: neither another guest nor another member gets in; the
+    // member lands on the waitlist instead.
+    assert_eq!(
+        guest_rsvp(&h, event.id, "Late Guest", "late@example.com").await,
+        StatusCode::CONFLICT
+    );
+    let body = member_rsvp(&h, event.id).await;
+    assert!(body.contains("On waitlist (#1)"), "{}", body);
+    assert_eq!(attendee_count(&h.pool, event.id).await, 2);
+
+    // The same guest again (any casing) keeps their seat, not a new one.
+    assert_eq!(
+        guest_rsvp(&h, event.id, "Ada Lovelace", "ada@example.com").await,
+        StatusCode::CREATED
+    );
+    assert_eq!(attendee_count(&h.pool, event.id).await, 2);
+}
+
+#[tokio::test]
+async fn guest_rsvp_requires_public_event_with_flag() {
+    let h = harness().await;
+    let closed = create_event(&h.pool, EventVisibility::Public, false, None).await;
+    let members_only = create_event(&h.pool, EventVisibility::MembersOnly, true, None).await;
+
+    for event in [&closed, &members_only] {
+        assert_eq!(
+            guest_rsvp(&h, event.id, "Ada Guest", "ada@example.com").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(attendee_count(&h.pool, event.id).await, 0);
+    }
+    assert_eq!(
+        guest_rsvp(&h, Uuid::new_v4(), "Ada Guest", "ada@example.com").await,
+        StatusCode::NOT_FOUND
+    );
+
+    let open = create_event(&h.pool, EventVisibility::Public, true, None).await;
+    assert_eq!(
+        guest_rsvp(&h, open.id, "  ", "ada@example.com").await,
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        guest_rsvp(&h, open.id, "Ada Guest", "not-an-email").await,
+        StatusCode::BAD_REQUEST
+    );
+}
+
+#[tokio::test]
+async fn attendee_export_lists_members_and_guests() {
+    let h = harness().await;
+    let event = create_event(&h.pool, EventVisibility::Public, true, None).await;
+    member_rsvp(&h, event.id).await;
+    guest_rsvp(&h, event.id, "Ada \"Countess\" Guest", "ada@example.com").await;
+
+    let (_, _, admin) = member_session(&h.pool, true).await;
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/portal/admin/events/{}/attendees/export", event.id))
+                .header("Cookie", &admin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with("text/csv"));
+    let csv = String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "name,email,attendee_type,status,registered_at");
+    assert_eq!(lines.len(), 3, "{}", csv);
+    assert!(lines.iter().any(|l| l.contains(r#""member","Registered""#)), "{}", csv);
+    assert!(
+        lines.iter().any(|l| l.starts_with(
+            r#""Ada ""Countess"" Guest","ada@example.com","guest","Registered""#
+        )),
+        "{}",
+        csv
+    );
+
+    // Non-admins don't get the export.
+    let (_, _, member) = member_session(&h.pool, false).await;
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/portal/admin/events/{}/attendees/export", event.id))
+                .header("Cookie", &member)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_ne!(resp.status(), StatusCode::OK);
+}
@@ -0,0 +1,107 @@
+//! The `announcements.html_*` settings decide which raw HTML survives
+//! in rendered announcement Markdown: an iframe from an allowlisted
+//! host renders once a club allows it, while `script` is stripped
+//! whatever the settings say and can't be allowed at all.
+//!
+//! Run with: cargo test --test html_allowlist_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType, UpdateSettingRequest},
+    error::AppError,
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+const CONTENT: &str = concat!(
+    r#"<iframe src="https://www.youtube-nocookie.com/embed/abc"></iframe>"#,
+    "\n\n",
+    r#"<iframe src="https://tracker.example/embed"></iframe>"#,
+    "\n\n",
+    "<script>alert(1)</script>",
+);
+
+#[tokio::test]
+async fn allowlisted_iframe_survives_and_script_is_stripped() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let settings = state.service_context.settings_service.clone();
+    let admin = make_member(&pool).await;
+
+    let published = Utc::now() - Duration::days(1);
+    let announcement = SqliteAnnouncementRepository::new(pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: "Talk recording".to_string(),
+            content: CONTENT.to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public: true,
+            featured: false,
+            image_url: None,
+            published_at: Some(published),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: admin,
+            created_at: published,
+            updated_at: published,
+        })
+        .await
+        .unwrap();
+
+    let set = |key: &'static str, value: &'static str| {
+        let settings = settings.clone();
+        async move {
+            settings
+                .update_setting(
+                    key,
+                    UpdateSettingRequest {
+                        value: value.to_string(),
+                        reason: None,
+                    },
+                    admin,
+                )
+                .await
+        }
+    };
+    set("announcements.html_allowed_tags", "b, iframe").await.unwrap();
+    set("announcements.html_allowed_attributes", "iframe.src").await.unwrap();
+    set("announcements.html_allowed_iframe_hosts", "youtube-nocookie.com")
+        .await
+        .unwrap();
+    let refused = set("announcements.html_allowed_tags", "iframe, script").await;
+    assert!(matches!(refused, Err(AppError::BadRequest(_))), "{:?}", refused);
+
+    let app = coterie::web::create_web_routes(state);
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/announcements/{}", announcement.id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let html = String::from_utf8_lossy(&body);
+
+    assert!(
+        html.contains(r#"<iframe src="https://www.youtube-nocookie.com/embed/abc"></iframe>"#),
+        "{}",
+        html
+    );
+    assert!(!html.contains(r#"<iframe src="https://tracker.example"#), "{}", html);
+    assert!(!html.contains("<script>alert"), "{}", html);
+}
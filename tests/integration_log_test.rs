@@ -0,0 +1,223 @@
+//! Integration tests for the outbound integration log: every
+//! `IntegrationManager::handle_event` attempt writes a row, failures
+//! carry the (redacted) error, and `replay` re-invokes only the
+//! integration that failed.
+//!
+//! The "Discord" integration here is a stand-in that fails its first
+//! call — the real DiscordIntegration talks to discord.com, which the
+//! test environment can't reach deterministically.
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use coterie::{
+    error::{AppError, Result as CoterieResult},
+    integrations::{Integration, IntegrationEvent, IntegrationManager},
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::integration_log_service::{IntegrationLogFilter, IntegrationLogService},
+};
+
+mod common;
+use common::{fresh_pool, make_member};
+
+/// Fails the first `fail_times` calls, succeeds after that.
+struct FlakyIntegration {
+    name: &'static str,
+    calls: Arc<AtomicUsize>,
+    fail_times: usize,
+}
+
+#[async_trait]
+impl Integration for FlakyIntegration {
+    fn name(&self) -> &str {
+        self.name
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+    async fn handle_event(&self, _event: &IntegrationEvent) -> CoterieResult<()> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst);
+        if n < self.fail_times {
+            return Err(AppError::External(
+                "Discord add_role: HTTP 403 (Authorization: Bot secret.token.value)".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn failed_discord_dispatch_is_logged_and_replay_reinvokes_it() {
+    let pool = fresh_pool().await;
+    let member_id = make_member(&pool).await;
+    let member = SqliteMemberRepository::new(pool.clone())
+        .find_by_id(member_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let log = Arc::new(IntegrationLogService::new(pool.clone()));
+    let manager = IntegrationManager::new();
+    manager.attach_log(log.clone());
+
+    let discord_calls = Arc::new(AtomicUsize::new(0));
+    let other_calls = Arc::new(AtomicUsize::new(0));
+    manager
+        .register(Arc::new(FlakyIntegration {
+            name: "Discord",
+            calls: discord_calls.clone(),
+            fail_times: 1,
+        }))
+        .await;
+    manager
+        .register(Arc::new(FlakyIntegration {
+            name: "Other",
+            calls: other_calls.clone(),
+            fail_times: 0,
+        }))
+        .await;
+
+    manager
+        .handle_event(IntegrationEvent::MemberActivated(member.clone()))
+        .await;
+
+    let failed = log
+        .recent(&IntegrationLogFilter {
+            integration: "Discord".to_string(),
+            status: "error".to_string(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(failed.len(), 1);
+    let entry = &failed[0];
+    assert_eq!(entry.event_type, "member_activated");
+    assert_eq!(entry.target, member.id.to_string());
+    let error = entry.error.as_deref().unwrap();
+    assert!(error.contains("HTTP 403"), "error kept: {}", error);
+    assert!(!error.contains("secret.token.value"), "token redacted: {}", error);
+
+    // The healthy integration logged its own success row.
+    let ok = log
+        .recent(&IntegrationLogFilter {
+            integration: "Other".to_string(),
+            status: "ok".to_string(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(ok.len(), 1);
+
+    manager.replay(entry.id).await.expect("replay succeeds");
+    assert_eq!(discord_calls.load(Ordering::SeqCst), 2, "Discord re-invoked");
+    assert_eq!(other_calls.load(Ordering::SeqCst), 1, "other integrations untouched");
+
+    let original = log.get(entry.id).await.unwrap();
+    assert!(original.replayed_at.is_some());
+
+    let replays: Vec<_> = log
+        .recent(&IntegrationLogFilter {
+            integration: "Discord".to_string(),
+            status: "ok".to_string(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(replays.len(), 1);
+    assert_eq!(replays[0].replay_of, Some(entry.id));
+}
+
+#[tokio::test]
+async fn replay_refuses_successful_and_already_replayed_entries() {
+    let pool = fresh_pool().await;
+    let member_id = make_member(&pool).await;
+    let member = SqliteMemberRepository::new(pool.clone())
+        .find_by_id(member_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let log = Arc::new(IntegrationLogService::new(pool.clone()));
+    let manager = IntegrationManager::new();
+    manager.attach_log(log.clone());
+    let calls = Arc::new(AtomicUsize::new(0));
+    manager
+        .register(Arc::new(FlakyIntegration {
+            name: "Discord",
+            calls: calls.clone(),
+            fail_times: 1,
+        }))
+        .await;
+
+    // First delivery fails, the replay lands.
+    manager
+        .handle_event(IntegrationEvent::MemberActivated(member))
+        .await;
+    let failed = log
+        .recent(&IntegrationLogFilter {
+            integration: "Discord".to_string(),
+            status: "error".to_string(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    manager.replay(failed[0].id).await.expect("replay succeeds");
+    let succeeded = log
+        .recent(&IntegrationLogFilter {
+            integration: "Discord".to_string(),
+            status: "ok".to_string(),
+            limit: 10,
+        })
+        .await
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    let err = manager.replay(succeeded[0].id).await.unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+    let err = manager.replay(failed[0].id).await.unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)), "{err:?}");
+    assert_eq!(calls.load(Ordering::SeqCst), 2, "nothing re-sent");
+}
+
+#[tokio::test]
+async fn replay_of_unknown_entry_is_not_found() {
+    let pool = fresh_pool().await;
+    let manager = IntegrationManager::new();
+    manager.attach_log(Arc::new(IntegrationLogService::new(pool)));
+
+    let err = manager.replay(uuid::Uuid::new_v4()).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn prune_removes_old_entries() {
+    let pool = fresh_pool().await;
+    let log = IntegrationLogService::new(pool.clone());
+    let manager = IntegrationManager::new();
+    manager.attach_log(Arc::new(IntegrationLogService::new(pool.clone())));
+    manager
+        .register(Arc::new(FlakyIntegration {
+            name: "Discord",
+            calls: Arc::new(AtomicUsize::new(0)),
+            fail_times: 0,
+        }))
+        .await;
+    manager
+        .handle_event(IntegrationEvent::AdminAlert {
+            subject: "s".to_string(),
+            body: "b".to_string(),
+        })
+        .await;
+
+    sqlx::query("UPDATE integration_log SET created_at = datetime('now', '-40 days')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(log.prune_older_than(30).await.unwrap(), 1);
+}
@@ -0,0 +1,159 @@
+//! With an outbox attached, `IntegrationManager::handle_event` queues
+//! each delivery before attempting it. A success leaves nothing
+//! behind; a failure is retried by `deliver_due` with backoff until the
+//! attempt limit, and rows left by an earlier process are picked up.
+//!
+//! Run with: cargo test --features test-utils --test integration_outbox_test
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use coterie::{
+    error::{AppError, Result as CoterieResult},
+    integrations::{Integration, IntegrationEvent, IntegrationManager},
+    service::integration_outbox_service::{IntegrationOutboxService, OutboxCounts},
+};
+use sqlx::SqlitePool;
+
+mod common;
+use common::fresh_pool;
+
+/// Fails the first `fail_times` calls, succeeds after that.
+struct FlakyIntegration {
+    calls: Arc<AtomicUsize>,
+    fail_times: usize,
+}
+
+#[async_trait]
+impl Integration for FlakyIntegration {
+    fn name(&self) -> &str {
+        "Flaky"
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+    async fn handle_event(&self, _event: &IntegrationEvent) -> CoterieResult<()> {
+        let n = self.calls.fetch_add(1, Ordering::SeqCst);
+        if n < self.fail_times {
+            return Err(AppError::External("Discord is down".to_string()));
+        }
+        Ok(())
+    }
+}
+
+async fn manager(pool: &SqlitePool, fail_times: usize) -> (IntegrationManager, Arc<AtomicUsize>) {
+    let manager = IntegrationManager::new();
+    manager.attach_outbox(Arc::new(IntegrationOutboxService::new(pool.clone())));
+    let calls = Arc::new(AtomicUsize::new(0));
+    manager
+        .register(Arc::new(FlakyIntegration {
+            calls: calls.clone(),
+            fail_times,
+        }))
+        .await;
+    (manager, calls)
+}
+
+fn alert() -> IntegrationEvent {
+    IntegrationEvent::AdminAlert {
+        subject: "Test".to_string(),
+        body: "Body".to_string(),
+    }
+}
+
+async fn counts(pool: &SqlitePool) -> OutboxCounts {
+    IntegrationOutboxService::new(pool.clone())
+        .counts()
+        .await
+        .unwrap()
+}
+
+/// Skip the backoff: make every pending row due now.
+async fn make_due(pool: &SqlitePool) {
+    sqlx::query("UPDATE integration_outbox SET next_attempt_at = datetime('now', '-1 second')")
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn successful_delivery_leaves_nothing_queued() {
+    let pool = fresh_pool().await;
+    let (manager, calls) = manager(&pool, 0).await;
+
+    manager.handle_event(alert()).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 1, "first attempt is inline");
+    assert_eq!(counts(&pool).await, OutboxCounts::default());
+    make_due(&pool).await;
+    assert_eq!(manager.deliver_due().await, 0);
+}
+
+#[tokio::test]
+async fn failures_back_off_and_then_succeed() {
+    let pool = fresh_pool().await;
+    let (manager, calls) = manager(&pool, 2).await;
+    manager.handle_event(alert()).await;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(counts(&pool).await.pending, 1);
+
+    // Rescheduled into the future, so not due yet.
+    assert_eq!(manager.deliver_due().await, 0);
+    let (attempts, error): (i64, String) =
+        sqlx::query_as("SELECT attempts, last_error FROM integration_outbox")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(attempts, 1);
+    assert_eq!(error, "External service error: Discord is down");
+
+    make_due(&pool).await;
+    assert_eq!(manager.deliver_due().await, 1);
+    assert_eq!(counts(&pool).await.pending, 1);
+    make_due(&pool).await;
+    assert_eq!(manager.deliver_due().await, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(counts(&pool).await, OutboxCounts::default());
+}
+
+#[tokio::test]
+async fn delivery_gives_up_after_the_attempt_limit() {
+    let pool = fresh_pool().await;
+    let (manager, calls) = manager(&pool, usize::MAX).await;
+    manager.handle_event(alert()).await;
+
+    // Default `integrations.outbox_max_attempts` is 8.
+    for _ in 0..10 {
+        make_due(&pool).await;
+        manager.deliver_due().await;
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 8);
+    let left = counts(&pool).await;
+    assert_eq!((left.pending, left.failed), (0, 1));
+}
+
+#[tokio::test]
+async fn deliveries_left_by_an_earlier_process_are_picked_up() {
+    let pool = fresh_pool().await;
+    // Queued, then the process went away before settling the attempt.
+    IntegrationOutboxService::new(pool.clone())
+        .enqueue(
+            "Flaky",
+            "admin_alert",
+            &serde_json::to_string(&alert()).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let (manager, calls) = manager(&pool, 0).await;
+    assert_eq!(manager.deliver_due().await, 0, "claim still held");
+    make_due(&pool).await;
+    assert_eq!(manager.deliver_due().await, 1);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(counts(&pool).await, OutboxCounts::default());
+}
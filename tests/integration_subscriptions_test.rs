@@ -0,0 +1,160 @@
+//! Per-integration event subscriptions: the manager only sends an
+//! integration the event kinds it subscribes to, and an admin can
+//! narrow that per integration with `integrations.<name>.events`.
+//!
+//! Run with: cargo test --features test-utils --test integration_subscriptions_test
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use coterie::{
+    domain::Member,
+    error::Result as CoterieResult,
+    integrations::{
+        subscription_setting_key, Integration, IntegrationEvent, IntegrationEventKind,
+        IntegrationManager,
+    },
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use sqlx::SqlitePool;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+/// Counts the events it's sent. `subscribed` of `None` keeps the
+/// trait default (all kinds).
+struct Counter {
+    name: &'static str,
+    subscribed: Option<&'static [IntegrationEventKind]>,
+    calls: AtomicUsize,
+}
+
+impl Counter {
+    fn new(name: &'static str, subscribed: Option<&'static [IntegrationEventKind]>) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            subscribed,
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Integration for Counter {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+
+    async fn handle_event(&self, _event: &IntegrationEvent) -> CoterieResult<()> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn subscribed_events(&self) -> &[IntegrationEventKind] {
+        self.subscribed.unwrap_or(IntegrationEventKind::ALL)
+    }
+}
+
+async fn member(pool: &SqlitePool) -> Member {
+    let id = make_member(pool).await;
+    SqliteMemberRepository::new(pool.clone())
+        .find_by_id(id)
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+async fn harness() -> (SqlitePool, Arc<IntegrationManager>) {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    (pool, state.service_context.integration_manager.clone())
+}
+
+#[tokio::test]
+async fn integration_subscribed_to_expired_only_is_skipped_for_activated() {
+    let (pool, manager) = harness().await;
+    let expired_only = Counter::new("ExpiredOnly", Some(&[IntegrationEventKind::MemberExpired]));
+    let everything = Counter::new("Everything", None);
+    manager.register(expired_only.clone()).await;
+    manager.register(everything.clone()).await;
+
+    let m = member(&pool).await;
+    manager
+        .handle_event(IntegrationEvent::MemberActivated(m.clone()))
+        .await;
+    assert_eq!(
+        expired_only.calls(),
+        0,
+        "not subscribed to member_activated"
+    );
+    assert_eq!(everything.calls(), 1);
+
+    manager
+        .handle_event(IntegrationEvent::MemberExpired(m))
+        .await;
+    assert_eq!(expired_only.calls(), 1);
+    assert_eq!(everything.calls(), 2);
+}
+
+#[tokio::test]
+async fn setting_narrows_an_integration_without_a_restart() {
+    let (pool, manager) = harness().await;
+    let discord_like = Counter::new("DiscordLike", None);
+    manager.register(discord_like.clone()).await;
+
+    let key = subscription_setting_key("DiscordLike");
+    assert_eq!(key, "integrations.discord_like.events");
+    sqlx::query(
+        "INSERT INTO app_settings (key, value, value_type, category, description, is_sensitive) \
+         VALUES (?, 'member_expired, not_a_kind', 'string', 'integrations', '', 0)",
+    )
+    .bind(&key)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let m = member(&pool).await;
+    manager
+        .handle_event(IntegrationEvent::MemberActivated(m.clone()))
+        .await;
+    assert_eq!(discord_like.calls(), 0, "narrowed to member_expired");
+    manager
+        .handle_event(IntegrationEvent::MemberExpired(m.clone()))
+        .await;
+    assert_eq!(discord_like.calls(), 1);
+
+    // Clearing the setting goes back to the integration's own list.
+    sqlx::query("UPDATE app_settings SET value = '' WHERE key = ?")
+        .bind(&key)
+        .execute(&pool)
+        .await
+        .unwrap();
+    manager
+        .handle_event(IntegrationEvent::MemberActivated(m))
+        .await;
+    assert_eq!(discord_like.calls(), 2);
+}
+
+#[test]
+fn event_kind_tags_round_trip() {
+    for kind in IntegrationEventKind::ALL {
+        assert_eq!(
+            kind.as_str().parse::<IntegrationEventKind>().unwrap(),
+            *kind
+        );
+    }
+    assert!("member_created".parse::<IntegrationEventKind>().is_err());
+}
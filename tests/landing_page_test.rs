@@ -0,0 +1,207 @@
+//! Post-login landing page: the club default for the member's role, a
+//! member's own override on top of it, a safe `redirect_url` beating
+//! both, and admin pages never offered to (or honored for) members.
+//!
+//! Run with: cargo test --features test-utils --test landing_page_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::SecretCrypto,
+    domain::{settings::UpdateSettingRequest, LandingPage},
+    error::AppError,
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::{landing_page_service::LandingPageService, settings_service::SettingsService},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member, member_session, merged_routes};
+
+fn settings(pool: &SqlitePool) -> Arc<SettingsService> {
+    Arc::new(SettingsService::new(
+        pool.clone(),
+        Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+    ))
+}
+
+async fn set(pool: &SqlitePool, key: &str, value: &str) -> Result<(), AppError> {
+    settings(pool)
+        .update_setting(
+            key,
+            UpdateSettingRequest {
+                value: value.to_string(),
+                reason: None,
+            },
+            make_member(pool).await,
+        )
+        .await
+        .map(|_| ())
+}
+
+/// Log `member` in through `POST /login` and return where the JSON
+/// response sends them.
+async fn login_redirect(
+    app: &Router,
+    pool: &SqlitePool,
+    member: Uuid,
+    next: Option<&str>,
+) -> String {
+    let username = SqliteMemberRepository::new(pool.clone())
+        .find_by_id(member)
+        .await
+        .unwrap()
+        .unwrap()
+        .username;
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    serde_json::json!({
+                        "username": username,
+                        "password": "p4ssword_long_enough",
+                        "redirect_url": next,
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    json["redirect"].as_str().expect("redirect").to_string()
+}
+
+#[tokio::test]
+async fn admin_lands_on_admin_default_and_member_on_summary() {
+    let pool = fresh_pool().await;
+    set(&pool, "auth.admin_landing_page", "admin_members")
+        .await
+        .unwrap();
+    let app = merged_routes(build_app_state(pool.clone()).await);
+    let (admin, _, _) = member_session(&pool, true).await;
+    let (member, _, _) = member_session(&pool, false).await;
+
+    assert_eq!(
+        login_redirect(&app, &pool, admin, None).await,
+        "/portal/admin/members"
+    );
+    assert_eq!(
+        login_redirect(&app, &pool, member, None).await,
+        "/portal/me"
+    );
+    // A validated `next` still wins over the landing page.
+    assert_eq!(
+        login_redirect(&app, &pool, admin, Some("/portal/events")).await,
+        "/portal/events"
+    );
+    assert_eq!(
+        login_redirect(&app, &pool, admin, Some("https://evil.example/")).await,
+        "/portal/admin/members"
+    );
+}
+
+#[tokio::test]
+async fn member_override_wins_and_admin_pages_fall_back() {
+    let pool = fresh_pool().await;
+    set(&pool, "auth.member_landing_page", "events")
+        .await
+        .unwrap();
+    let service = LandingPageService::new(pool.clone(), settings(&pool));
+    let app = merged_routes(build_app_state(pool.clone()).await);
+    let (member, _, _) = member_session(&pool, false).await;
+
+    assert_eq!(
+        login_redirect(&app, &pool, member, None).await,
+        "/portal/events"
+    );
+
+    service
+        .set_preference(member, Some(LandingPage::Directory))
+        .await
+        .unwrap();
+    assert_eq!(
+        service.preference(member).await.unwrap(),
+        Some(LandingPage::Directory)
+    );
+    assert_eq!(
+        login_redirect(&app, &pool, member, None).await,
+        "/portal/directory"
+    );
+
+    // An admin page saved for someone who isn't (or is no longer) an
+    // admin sends them to the dashboard.
+    service
+        .set_preference(member, Some(LandingPage::AdminBilling))
+        .await
+        .unwrap();
+    assert_eq!(
+        login_redirect(&app, &pool, member, None).await,
+        "/portal/dashboard"
+    );
+
+    service.set_preference(member, None).await.unwrap();
+    assert_eq!(service.preference(member).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn settings_reject_unknown_and_admin_pages_for_members() {
+    let pool = fresh_pool().await;
+
+    let err = set(&pool, "auth.member_landing_page", "admin_members")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+    let err = set(&pool, "auth.admin_landing_page", "nowhere")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+
+    let settings = settings(&pool);
+    assert_eq!(settings.default_landing_page(false).await, LandingPage::Me);
+    assert_eq!(
+        settings.default_landing_page(true).await,
+        LandingPage::Dashboard
+    );
+}
+
+#[tokio::test]
+async fn profile_rejects_admin_start_page_for_members() {
+    let pool = fresh_pool().await;
+    let app = merged_routes(build_app_state(pool.clone()).await);
+    let (member, _, cookie) = member_session(&pool, false).await;
+
+    let post = |page: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri("/portal/profile/landing-page")
+            .header(header::COOKIE, cookie.clone())
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(format!("landing_page={page}&csrf_token=x")))
+            .unwrap()
+    };
+
+    let resp = app.clone().oneshot(post("admin_members")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = app.clone().oneshot(post("announcements")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let service = LandingPageService::new(pool.clone(), settings(&pool));
+    assert_eq!(
+        service.preference(member).await.unwrap(),
+        Some(LandingPage::Announcements)
+    );
+}
@@ -0,0 +1,73 @@
+//! Load shedding: once `server.max_in_flight_requests` requests are in
+//! flight, further ones get an immediate 503 with `Retry-After`, while
+//! health probes keep answering.
+//!
+//! Run with: cargo test --features test-utils --test load_shed_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::api::state::AppState;
+use sqlx::SqlitePool;
+use tokio::sync::Semaphore;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool, merged_routes};
+
+const LIMIT: usize = 2;
+
+/// The app with only the load-shed layer on top, plus the semaphore it
+/// draws from so tests can occupy slots as in-flight requests would.
+async fn app(pool: &SqlitePool) -> (Router, Arc<Semaphore>) {
+    let mut state: AppState = build_app_state(pool.clone()).await;
+    let slots = Arc::new(Semaphore::new(LIMIT));
+    state.request_slots = Some(slots.clone());
+
+    let router = merged_routes(state.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            state,
+            coterie::api::middleware::load_shed::shed_load,
+        ));
+    (router, slots)
+}
+
+async fn get(app: &Router, uri: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn requests_past_the_limit_are_shed_but_health_answers() {
+    let pool = fresh_pool().await;
+    let (app, slots) = app(&pool).await;
+
+    assert_eq!(get(&app, "/login").await.status(), StatusCode::OK);
+    assert_eq!(slots.available_permits(), LIMIT, "slot released after the response");
+
+    // Occupy every slot, as LIMIT slow requests would.
+    let held = slots.clone().acquire_many_owned(LIMIT as u32).await.unwrap();
+
+    let resp = get(&app, "/login").await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert!(resp.headers().contains_key(header::RETRY_AFTER));
+
+    assert_eq!(get(&app, "/health").await.status(), StatusCode::OK);
+
+    drop(held);
+    assert_eq!(get(&app, "/login").await.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn zero_disables_the_limit() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    assert_eq!(state.settings.server.max_in_flight_requests, 0);
+    assert!(state.request_slots.is_none());
+}
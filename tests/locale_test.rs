@@ -0,0 +1,88 @@
+//! Display-locale resolution: the org default from `org.locale`, a
+//! member's own override on top of it, and the formatted output both
+//! produce for the same instant.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use coterie::{
+    auth::SecretCrypto,
+    domain::{settings::UpdateSettingRequest, Locale},
+    error::AppError,
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::settings_service::SettingsService,
+};
+
+mod common;
+use common::{fresh_pool, make_member};
+
+#[tokio::test]
+async fn member_override_wins_over_org_default() {
+    let pool = fresh_pool().await;
+    let member_id = make_member(&pool).await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let settings = SettingsService::new(
+        pool.clone(),
+        Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+    );
+    let instant = Utc.with_ymd_and_hms(2025, 9, 3, 14, 30, 0).unwrap();
+
+    let member = repo.find_by_id(member_id).await.unwrap().unwrap();
+    assert_eq!(settings.locale_for(&member).await, Locale::EnUs);
+    assert_eq!(
+        settings.locale_for(&member).await.long_date(&instant),
+        "September 03, 2025"
+    );
+
+    settings
+        .update_setting(
+            "org.locale",
+            UpdateSettingRequest {
+                value: "de-DE".to_string(),
+                reason: None,
+            },
+            member_id,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        settings.locale_for(&member).await.long_date(&instant),
+        "3. September 2025"
+    );
+
+    repo.update_locale(member_id, Some("fr-FR")).await.unwrap();
+    let member = repo.find_by_id(member_id).await.unwrap().unwrap();
+    assert_eq!(member.locale.as_deref(), Some("fr-FR"));
+    assert_eq!(
+        settings.locale_for(&member).await.long_date(&instant),
+        "3 septembre 2025"
+    );
+
+    repo.update_locale(member_id, None).await.unwrap();
+    let member = repo.find_by_id(member_id).await.unwrap().unwrap();
+    assert_eq!(settings.locale_for(&member).await, Locale::DeDe);
+}
+
+#[tokio::test]
+async fn unknown_org_locale_is_rejected() {
+    let pool = fresh_pool().await;
+    let member_id = make_member(&pool).await;
+    let settings = SettingsService::new(
+        pool,
+        Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+    );
+
+    let err = settings
+        .update_setting(
+            "org.locale",
+            UpdateSettingRequest {
+                value: "klingon".to_string(),
+                reason: None,
+            },
+            member_id,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+    assert_eq!(settings.org_locale().await, Locale::EnUs);
+}
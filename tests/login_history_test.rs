@@ -0,0 +1,234 @@
+//! Member login history: every successful login writes a row the
+//! member can see on `/portal/profile/sessions`, and a login from a
+//! device fingerprint they've never used sends exactly one alert.
+//!
+//! Run with: cargo test --features test-utils --test login_history_test
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use coterie::{
+    auth::SecretCrypto,
+    domain::{Member, MemberStatus, UpdateMemberRequest},
+    email::{EmailMessage, EmailSender},
+    error::Result as CoterieResult,
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::{
+        login_history_service::{LoginHistoryService, LoginMethod},
+        settings_service::SettingsService,
+    },
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app, fresh_pool, make_member};
+
+const FIREFOX_LINUX: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
+const SAFARI_IPHONE: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) \
+     AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1";
+
+#[derive(Default)]
+struct FakeEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for FakeEmailSender {
+    async fn send(&self, message: &EmailMessage) -> CoterieResult<()> {
+        self.sent.lock().await.push(message.clone());
+        Ok(())
+    }
+}
+
+fn service(pool: &SqlitePool, email: Arc<FakeEmailSender>) -> LoginHistoryService {
+    LoginHistoryService::new(
+        pool.clone(),
+        email,
+        Arc::new(SettingsService::new(
+            pool.clone(),
+            Arc::new(SecretCrypto::new("test-secret-please-ignore")),
+        )),
+        "http://127.0.0.1".to_string(),
+    )
+}
+
+async fn active_member(pool: &SqlitePool) -> Member {
+    let id = make_member(pool).await;
+    SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                status: Some(MemberStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+}
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+async fn history_count(pool: &SqlitePool, member: &Member) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM login_history WHERE member_id = ?")
+        .bind(member.id.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn login_writes_history_row_shown_on_sessions_page() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let member = active_member(&pool).await;
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/auth/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::USER_AGENT, FIREFOX_LINUX)
+                .body(Body::from(
+                    serde_json::json!({
+                        "email": member.email,
+                        "password": "p4ssword_long_enough",
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let cookie = resp
+        .headers()
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find(|v| v.starts_with("session="))
+        .and_then(|v| v.split(';').next())
+        .expect("session cookie")
+        .to_string();
+
+    let (method, user_agent, new_device): (String, Option<String>, bool) = sqlx::query_as(
+        "SELECT method, user_agent, new_device FROM login_history WHERE member_id = ?",
+    )
+    .bind(member.id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(method, "api");
+    assert_eq!(user_agent.as_deref(), Some(FIREFOX_LINUX));
+    assert!(!new_device, "the first login on record is the baseline, not a new device");
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/portal/profile/sessions")
+                .header("Cookie", &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = String::from_utf8(to_bytes(resp.into_body(), 1 << 20).await.unwrap().to_vec()).unwrap();
+    assert!(body.contains("Firefox on Linux"), "sessions page lists the login");
+    assert!(body.contains("127.0.0.1"));
+}
+
+#[tokio::test]
+async fn first_seen_device_triggers_exactly_one_alert() {
+    let pool = fresh_pool().await;
+    let email = Arc::new(FakeEmailSender::default());
+    let svc = service(&pool, email.clone());
+    let member = active_member(&pool).await;
+
+    // Baseline: first login ever, then the same laptop again on a new
+    // DHCP lease. Neither is news to the member.
+    svc.record_login(&member, ip("203.0.113.10"), Some(FIREFOX_LINUX), LoginMethod::Password)
+        .await
+        .expect("row written");
+    svc.record_login(&member, ip("203.0.113.77"), Some(FIREFOX_LINUX), LoginMethod::Totp)
+        .await
+        .expect("row written");
+    assert!(email.sent.lock().await.is_empty());
+
+    // A phone on a different network: one alert.
+    svc.record_login(&member, ip("198.51.100.5"), Some(SAFARI_IPHONE), LoginMethod::Password)
+        .await
+        .expect("row written");
+    {
+        let sent = email.sent.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, member.email);
+        assert!(sent[0].text_body.contains("Safari on iOS"));
+        assert!(sent[0].text_body.contains("198.51.100.5"));
+        assert!(sent[0].text_body.contains("/portal/profile/sessions"));
+    }
+
+    // Same phone, same network: deduplicated by fingerprint.
+    svc.record_login(&member, ip("198.51.100.6"), Some(SAFARI_IPHONE), LoginMethod::Password)
+        .await
+        .expect("row written");
+    assert_eq!(email.sent.lock().await.len(), 1);
+
+    assert_eq!(history_count(&pool, &member).await, 4);
+    let entries = svc.recent_for_member(member.id, 10).await.unwrap();
+    assert_eq!(entries.iter().filter(|e| e.new_device).count(), 1);
+}
+
+#[tokio::test]
+async fn new_device_alerts_can_be_turned_off() {
+    let pool = fresh_pool().await;
+    sqlx::query("UPDATE app_settings SET value = 'false' WHERE key = 'auth.new_device_alerts'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let email = Arc::new(FakeEmailSender::default());
+    let svc = service(&pool, email.clone());
+    let member = active_member(&pool).await;
+
+    svc.record_login(&member, ip("203.0.113.10"), Some(FIREFOX_LINUX), LoginMethod::Password)
+        .await;
+    svc.record_login(&member, ip("198.51.100.5"), Some(SAFARI_IPHONE), LoginMethod::Password)
+        .await;
+
+    assert!(email.sent.lock().await.is_empty());
+    let entries = svc.recent_for_member(member.id, 10).await.unwrap();
+    assert!(entries[0].new_device, "history still flags the new device");
+}
+
+#[tokio::test]
+async fn prune_removes_old_rows_only() {
+    let pool = fresh_pool().await;
+    let svc = service(&pool, Arc::new(FakeEmailSender::default()));
+    let member = active_member(&pool).await;
+
+    svc.record_login(&member, ip("203.0.113.10"), Some(FIREFOX_LINUX), LoginMethod::Password)
+        .await;
+    svc.record_login(&member, ip("203.0.113.10"), Some(FIREFOX_LINUX), LoginMethod::Password)
+        .await;
+    sqlx::query(
+        "UPDATE login_history SET created_at = datetime('now', '-120 days') \
+         WHERE rowid = (SELECT MIN(rowid) FROM login_history)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(svc.prune_older_than(90).await.unwrap(), 1);
+    assert_eq!(history_count(&pool, &member).await, 1);
+}
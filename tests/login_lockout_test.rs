@@ -0,0 +1,130 @@
+//! Repeated failed logins lock out one account from one IP: further
+//! attempts get a 429 without the password being checked, the message
+//! is the same whether or not the account exists, and a successful
+//! login clears the count.
+//!
+//! Run with: cargo test --features test-utils --test login_lockout_test
+
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    api::state::{FailedLoginTracker, RateLimiter},
+    domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+const PASSWORD: &str = "Correct-horse-battery-9";
+const MAX_FAILURES: usize = 3;
+
+/// App with one Active member, "locky", and a lockout after
+/// `MAX_FAILURES` failures. The per-IP limiter is opened up so only the
+/// per-account lockout is in play.
+async fn app() -> Router {
+    let pool = fresh_pool().await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let member = repo
+        .create(CreateMemberRequest {
+            email: "locky@example.com".to_string(),
+            username: "locky".to_string(),
+            full_name: "Locky Member".to_string(),
+            password: PASSWORD.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    repo.update(
+        member.id,
+        UpdateMemberRequest {
+            status: Some(MemberStatus::Active),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut state = build_app_state(pool).await;
+    state.login_limiter = RateLimiter::new(100, Duration::from_secs(60));
+    state.login_failures = FailedLoginTracker::new(
+        MAX_FAILURES,
+        Duration::from_secs(15 * 60),
+        Duration::from_secs(15 * 60),
+    );
+    coterie::web::create_web_routes(state)
+}
+
+async fn login(app: &Router, username: &str, password: &str) -> (StatusCode, String) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({"username": username, "password": password}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn lockout_blocks_even_the_right_password() {
+    let app = app().await;
+    for _ in 0..MAX_FAILURES {
+        let (status, _) = login(&app, "locky", "wrong-password").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    // The email and the username share one count.
+    let (status, body) = login(&app, "LOCKY@example.com", PASSWORD).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS, "{}", body);
+    let (status, _) = login(&app, "locky", PASSWORD).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn unknown_accounts_lock_out_with_the_same_message() {
+    let app = app().await;
+    for _ in 0..MAX_FAILURES {
+        login(&app, "locky", "wrong-password").await;
+        login(&app, "nobody@example.com", "wrong-password").await;
+    }
+
+    let (known_status, known) = login(&app, "locky", "wrong-password").await;
+    let (unknown_status, unknown) = login(&app, "nobody@example.com", "wrong-password").await;
+    assert_eq!(known_status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(unknown_status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(known, unknown);
+}
+
+#[tokio::test]
+async fn successful_login_resets_the_count() {
+    let app = app().await;
+    for _ in 0..MAX_FAILURES - 1 {
+        login(&app, "locky", "wrong-password").await;
+    }
+    let (status, _) = login(&app, "locky", PASSWORD).await;
+    assert_eq!(status, StatusCode::OK);
+
+    for _ in 0..MAX_FAILURES - 1 {
+        let (status, _) = login(&app, "locky", "wrong-password").await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+    let (status, _) = login(&app, "locky", PASSWORD).await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,178 @@
+//! Maintenance mode: with `maintenance.enabled` on, anonymous visitors
+//! and regular members get the 503 maintenance page, while login,
+//! health checks and the Stripe webhook keep working, and admins —
+//! by session or by allowlisted IP — pass straight through.
+//!
+//! Run with: cargo test --features test-utils --test maintenance_mode_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    api::state::AppState,
+    payments::{fake_gateway::FakeStripeGateway, WebhookDispatcher},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session, merged_routes};
+
+/// The app as `main.rs` assembles it, minus CSRF (not under test), with
+/// forwarded headers trusted so tests can pick the client IP and a
+/// webhook dispatcher so the Stripe endpoint answers for itself.
+async fn app(pool: &SqlitePool) -> Router {
+    let mut state: AppState = build_app_state(pool.clone()).await;
+    let mut settings = (*state.settings).clone();
+    settings.server.trust_forwarded_for = Some(true);
+    state.settings = Arc::new(settings);
+    let ctx = state.service_context.clone();
+    state.webhook_dispatcher = Some(Arc::new(WebhookDispatcher::new(
+        Arc::new(FakeStripeGateway::new()),
+        "whsec_test_dummy".to_string(),
+        ctx.payment_repo.clone(),
+        ctx.member_repo.clone(),
+        ctx.processed_events_repo.clone(),
+        ctx.membership_type_service.clone(),
+        ctx.integration_manager.clone(),
+        ctx.member_service.clone(),
+        ctx.webhook_metrics.clone(),
+    )));
+
+    merged_routes(state.clone()).layer(axum::middleware::from_fn_with_state(
+        state,
+        coterie::api::middleware::maintenance::maintenance_mode,
+    ))
+}
+
+async fn set_setting(pool: &SqlitePool, key: &str, value: &str) {
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = ?")
+        .bind(value)
+        .bind(key)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn send(app: &Router, request: Request<Body>) -> (StatusCode, String) {
+    let resp = app.clone().oneshot(request).await.unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+fn get(uri: &str) -> axum::http::request::Builder {
+    Request::builder().uri(uri)
+}
+
+#[tokio::test]
+async fn visitors_and_members_get_the_maintenance_page() {
+    let pool = fresh_pool().await;
+    let app = app(&pool).await;
+    let (_, _, member) = member_session(&pool, false).await;
+
+    // Off by default.
+    let (status, _) = send(&app, get("/health").body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send(
+        &app,
+        get("/portal/dashboard")
+            .header(header::COOKIE, &member)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    set_setting(&pool, "maintenance.enabled", "true").await;
+    set_setting(&pool, "maintenance.message", "Upgrading the database.").await;
+
+    for (uri, cookie) in [
+        ("/", None),
+        ("/portal/dashboard", None),
+        ("/portal/dashboard", Some(&member)),
+        ("/public/events", None),
+    ] {
+        let mut req = get(uri);
+        if let Some(cookie) = cookie {
+            req = req.header(header::COOKIE, cookie);
+        }
+        let (status, body) = send(&app, req.body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE, "GET {}", uri);
+        assert!(
+            body.contains("down for maintenance"),
+            "GET {}: {}",
+            uri,
+            body
+        );
+        assert!(body.contains("Upgrading the database."), "GET {}", uri);
+    }
+
+    // Login, probes and the webhook are exempt. The webhook fails its
+    // signature check, but it reaches the handler to do so: a 400
+    // rather than the maintenance 503.
+    for uri in ["/login", "/health"] {
+        let (status, _) = send(&app, get(uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK, "GET {}", uri);
+    }
+    let (status, _) = send(
+        &app,
+        Request::builder()
+            .method(Method::POST)
+            .uri("/api/payments/webhook/stripe")
+            .header("stripe-signature", "t=0,v1=bogus")
+            .body(Body::from("{}"))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn admins_and_allowlisted_ips_pass_through() {
+    let pool = fresh_pool().await;
+    let app = app(&pool).await;
+    let (_, _, admin) = member_session(&pool, true).await;
+    set_setting(&pool, "maintenance.enabled", "true").await;
+    set_setting(&pool, "maintenance.allowed_ips", "203.0.113.7, 2001:db8::1").await;
+
+    // An admin session gets the real site.
+    let (status, body) = send(
+        &app,
+        get("/portal/dashboard")
+            .header(header::COOKIE, &admin)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!body.contains("down for maintenance"));
+
+    // So does an allowlisted address, logged in or not.
+    for ip in ["203.0.113.7", "2001:db8::1"] {
+        let (status, _) = send(
+            &app,
+            get("/")
+                .header("X-Forwarded-For", ip)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_ne!(status, StatusCode::SERVICE_UNAVAILABLE, "from {}", ip);
+    }
+
+    // Anyone else doesn't.
+    let (status, _) = send(
+        &app,
+        get("/")
+            .header("X-Forwarded-For", "198.51.100.2")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
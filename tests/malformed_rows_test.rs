@@ -0,0 +1,106 @@
+//! A row with an unparseable enum value is skipped by list queries
+//! (logged, not fatal) and reported on the admin data-check page.
+//!
+//! Run with: cargo test --features test-utils --test malformed_rows_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use coterie::{
+    repository::{MemberFilter, MemberRepository, SqliteMemberRepository},
+    service::data_check_service::DataCheckService,
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, make_member, member_session};
+
+/// Writes a status the CHECK constraint would reject, the way a
+/// hand-edited or half-migrated database might hold one.
+async fn corrupt_status(pool: &SqlitePool, id: Uuid) {
+    let mut conn = pool.acquire().await.unwrap();
+    sqlx::query("PRAGMA ignore_check_constraints = ON")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE members SET status = 'Lapsed' WHERE id = ?")
+        .bind(id.to_string())
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+    sqlx::query("PRAGMA ignore_check_constraints = OFF")
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+}
+
+async fn get(app: &axum::Router, uri: &str, cookie: &str) -> (StatusCode, String) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8_lossy(&body).into_owned())
+}
+
+#[tokio::test]
+async fn one_bad_status_does_not_blank_the_member_list() {
+    let pool = fresh_pool().await;
+    let good = make_member(&pool).await;
+    let bad = make_member(&pool).await;
+    corrupt_status(&pool, bad).await;
+
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let members = repo.search(&MemberFilter::default(), 50, 0).await.unwrap();
+    let ids: Vec<Uuid> = members.iter().map(|m| m.id).collect();
+    assert!(ids.contains(&good), "good member missing: {:?}", ids);
+    assert!(!ids.contains(&bad), "bad member should be skipped");
+
+    let app = build_app(&pool).await;
+    let (_, _, cookie) = member_session(&pool, true).await;
+    let (status, _) = get(&app, "/portal/admin/members", &cookie).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn data_check_reports_the_bad_row() {
+    let pool = fresh_pool().await;
+    make_member(&pool).await;
+    let bad = make_member(&pool).await;
+    corrupt_status(&pool, bad).await;
+
+    let rows = DataCheckService::new(pool.clone())
+        .malformed_rows()
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1, "{:?}", rows);
+    assert_eq!(rows[0].table, "members");
+    assert_eq!(rows[0].column, "status");
+    assert_eq!(rows[0].id, bad.to_string());
+    assert_eq!(rows[0].value, "Lapsed");
+
+    let app = build_app(&pool).await;
+    let (_, _, cookie) = member_session(&pool, true).await;
+    let (status, html) = get(&app, "/portal/admin/data-check", &cookie).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains(&bad.to_string()), "{}", html);
+}
+
+#[tokio::test]
+async fn data_check_is_empty_on_a_clean_database() {
+    let pool = fresh_pool().await;
+    make_member(&pool).await;
+    let rows = DataCheckService::new(pool).malformed_rows().await.unwrap();
+    assert!(rows.is_empty(), "{:?}", rows);
+}
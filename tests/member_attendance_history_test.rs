@@ -0,0 +1,130 @@
+//! A member's event history: every RSVP joined to its event, most
+//! recent first, with the checked-in flag, optionally leaving out
+//! cancelled RSVPs; and the member's own profile listing it.
+//!
+//! Run with: cargo test --features test-utils --test member_attendance_history_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{AttendanceStatus, Event, EventType},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, event, fresh_pool, make_member, member_session};
+
+async fn create_event(pool: &SqlitePool, title: &str, start: DateTime<Utc>) -> Uuid {
+    let creator = make_member(pool).await;
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: title.to_string(),
+            event_type: EventType::Meeting,
+            start_time: start,
+            end_time: None,
+            ..event(creator)
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn check_in(pool: &SqlitePool, event_id: Uuid, member_id: Uuid) {
+    sqlx::query("UPDATE event_attendance SET attended = 1 WHERE event_id = ? AND member_id = ?")
+        .bind(event_id.to_string())
+        .bind(member_id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Three RSVPs for `member`: a past meetup they checked in to, an
+/// upcoming workshop they're registered for, and a cancelled social.
+async fn history(pool: &SqlitePool, member: Uuid) -> (Uuid, Uuid, Uuid) {
+    let repo = SqliteEventRepository::new(pool.clone());
+    let now = Utc::now();
+    let past = create_event(pool, "Past meetup", now - Duration::days(30)).await;
+    let upcoming = create_event(pool, "Upcoming workshop", now + Duration::days(7)).await;
+    let cancelled = create_event(pool, "Cancelled social", now - Duration::days(3)).await;
+    for event in [past, upcoming, cancelled] {
+        assert_eq!(
+            repo.register_attendance(event, member).await.unwrap(),
+            AttendanceStatus::Registered
+        );
+    }
+    check_in(pool, past, member).await;
+    repo.cancel_attendance(cancelled, member).await.unwrap();
+    (past, upcoming, cancelled)
+}
+
+#[tokio::test]
+async fn lists_registered_and_checked_in_events_most_recent_first() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let member = make_member(&pool).await;
+    let (past, upcoming, cancelled) = history(&pool, member).await;
+
+    // Someone else's RSVP doesn't show up.
+    let other = make_member(&pool).await;
+    repo.register_attendance(past, other).await.unwrap();
+
+    let rows = repo.list_member_attendance(member, true).await.unwrap();
+    let ids: Vec<Uuid> = rows.iter().map(|r| r.event_id).collect();
+    assert_eq!(ids, vec![upcoming, cancelled, past]);
+
+    assert_eq!(rows[0].event_title, "Upcoming workshop");
+    assert_eq!(rows[0].status, AttendanceStatus::Registered);
+    assert!(!rows[0].attended);
+    assert_eq!(rows[1].status, AttendanceStatus::Cancelled);
+    assert_eq!(rows[2].status, AttendanceStatus::Registered);
+    assert!(rows[2].attended, "checked-in flag carried through");
+}
+
+#[tokio::test]
+async fn cancelled_rsvps_excluded_when_requested() {
+    let pool = fresh_pool().await;
+    let repo = SqliteEventRepository::new(pool.clone());
+    let member = make_member(&pool).await;
+    let (past, upcoming, _) = history(&pool, member).await;
+
+    let rows = repo.list_member_attendance(member, false).await.unwrap();
+    let ids: Vec<Uuid> = rows.iter().map(|r| r.event_id).collect();
+    assert_eq!(ids, vec![upcoming, past]);
+}
+
+#[tokio::test]
+async fn profile_page_shows_the_members_history() {
+    let pool = fresh_pool().await;
+    let (member, _, cookie) = member_session(&pool, false).await;
+    history(&pool, member).await;
+    let app = build_app(&pool).await;
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/portal/profile")
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let html = String::from_utf8_lossy(&body);
+
+    assert!(html.contains("Event History"));
+    assert!(html.contains("Past meetup"));
+    assert!(html.contains("Attended"));
+    assert!(html.contains("Upcoming workshop"));
+    assert!(
+        !html.contains("Cancelled social"),
+        "own history hides cancelled RSVPs"
+    );
+}
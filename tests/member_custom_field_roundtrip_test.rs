@@ -0,0 +1,195 @@
+//! Custom signup-field answers survive a roster export → import round
+//! trip: the export writes one `custom:<key>` column per configured
+//! field, and the importer maps them back by key into a fresh
+//! database with the same definitions. Columns with no matching
+//! definition, and values a field rejects, are dropped with a warning.
+//!
+//! Run with: cargo test --features test-utils --test member_custom_field_roundtrip_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    domain::{CreateMemberRequest, SignupField, SignupFieldKind},
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session, SESSION_SECRET};
+
+const FIELDS: &str = r#"[
+    {"key": "pronouns", "label": "Pronouns"},
+    {"key": "homepage", "label": "Homepage", "kind": "url"}
+]"#;
+
+async fn set_signup_fields(pool: &SqlitePool, json: &str) {
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'membership.signup_fields'")
+        .bind(json)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn custom_values(pool: &SqlitePool, email: &str) -> Vec<(String, String)> {
+    sqlx::query_as(
+        "SELECT f.field_key, f.value FROM member_custom_fields f \
+         JOIN members m ON m.id = f.member_id \
+         WHERE m.email = ? ORDER BY f.field_key",
+    )
+    .bind(email)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+async fn body_text(resp: axum::response::Response) -> String {
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+/// Import `csv` as the admin whose session is `(session_id, cookie)`.
+async fn import(app: &Router, (session_id, cookie): (&str, &str), csv: &str) -> String {
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(session_id)
+        .await
+        .unwrap();
+    let boundary = "----coterie-roundtrip";
+    let body = format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"members.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n\
+         {csv}\r\n\
+         --{boundary}--\r\n"
+    );
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/portal/admin/members/import")
+                .header(header::COOKIE, cookie)
+                .header("X-CSRF-Token", token)
+                .header(
+                    header::CONTENT_TYPE,
+                    format!("multipart/form-data; boundary={boundary}"),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_text(resp).await
+}
+
+#[tokio::test]
+async fn export_then_import_reproduces_custom_field_values() {
+    // Source database: one member with both custom fields answered,
+    // plus a value for a field that the destination won't define.
+    let source = fresh_pool().await;
+    set_signup_fields(
+        &source,
+        r#"[{"key": "pronouns", "label": "Pronouns"},
+            {"key": "homepage", "label": "Homepage", "kind": "url"},
+            {"key": "shoe_size", "label": "Shoe size"}]"#,
+    )
+    .await;
+    let type_id: String = sqlx::query_scalar("SELECT id FROM membership_types WHERE slug = 'member'")
+        .fetch_one(&source)
+        .await
+        .unwrap();
+    let member = SqliteMemberRepository::new(source.clone())
+        .create(CreateMemberRequest {
+            email: "ada@example.com".to_string(),
+            username: "ada".to_string(),
+            full_name: "Ada Lovelace".to_string(),
+            password: "p4ssword_long_enough".to_string(),
+            membership_type_id: Some(Uuid::parse_str(&type_id).unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let state = build_app_state(source.clone()).await;
+    let field = |key: &str, kind| SignupField {
+        key: key.to_string(),
+        label: key.to_string(),
+        required: false,
+        kind,
+    };
+    state
+        .service_context
+        .signup_field_service
+        .save(
+            member.id,
+            &[
+                (field("pronouns", SignupFieldKind::Text), "she/her, they/them".to_string()),
+                (field("homepage", SignupFieldKind::Url), "https://ada.example".to_string()),
+                (field("shoe_size", SignupFieldKind::Text), "38".to_string()),
+            ],
+        )
+        .await
+        .unwrap();
+
+    let (_, _, source_admin) = member_session(&source, true).await;
+    let resp = build_app(&source)
+        .await
+        .oneshot(
+            Request::builder()
+                .uri("/portal/admin/members/export")
+                .header(header::COOKIE, &source_admin)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let csv = body_text(resp).await;
+    let header_row = csv.lines().next().unwrap();
+    assert!(
+        header_row.ends_with(",membership_type_slug,custom:pronouns,custom:homepage,custom:shoe_size"),
+        "{}",
+        header_row
+    );
+
+    // Destination: same definitions minus shoe_size.
+    let dest = fresh_pool().await;
+    set_signup_fields(&dest, FIELDS).await;
+    let (_, session_id, dest_admin) = member_session(&dest, true).await;
+    let result = import(&build_app(&dest).await, (&session_id, &dest_admin), &csv).await;
+
+    assert_eq!(
+        custom_values(&dest, "ada@example.com").await,
+        vec![
+            ("homepage".to_string(), "https://ada.example".to_string()),
+            ("pronouns".to_string(), "she/her, they/them".to_string()),
+        ]
+    );
+    assert!(
+        result.contains("custom:shoe_size skipped"),
+        "unknown column should be reported: {}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn rejected_values_are_skipped_with_a_warning() {
+    let pool = fresh_pool().await;
+    set_signup_fields(&pool, FIELDS).await;
+    let (_, session_id, admin) = member_session(&pool, true).await;
+
+    let csv = "email,username,full_name,membership_type_slug,Custom:Homepage,custom:pronouns\n\
+               grace@example.com,grace,Grace Hopper,member,not a link,she/her\n";
+    let result = import(&build_app(&pool).await, (&session_id, &admin), csv).await;
+
+    assert!(result.contains("Row 1: Homepage must be a link"), "{}", result);
+    assert_eq!(
+        custom_values(&pool, "grace@example.com").await,
+        vec![("pronouns".to_string(), "she/her".to_string())]
+    );
+}
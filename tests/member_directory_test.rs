@@ -0,0 +1,188 @@
+//! Member directory privacy. Being listed and showing contact details
+//! are separate choices: a member who is listed with contact hidden
+//! shows up to other members by name, but their email never appears in
+//! the directory list or on their directory card; others get a
+//! "contact via admin" link instead.
+//!
+//! Run with: cargo test --features test-utils --test member_directory_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    auth::CsrfService,
+    domain::UpdateMemberRequest,
+    repository::{MemberRepository, SqliteMemberRepository},
+    service::directory_service::{DirectoryPrivacy, DirectoryService},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session, SESSION_SECRET};
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    H { pool, app }
+}
+
+/// An Active member called `full_name` with a live session; returns
+/// (id, email, session id, cookie).
+async fn named_member(pool: &SqlitePool, full_name: &str) -> (Uuid, String, String, String) {
+    let (id, session_id, cookie) = member_session(pool, false).await;
+    let member = SqliteMemberRepository::new(pool.clone())
+        .update(
+            id,
+            UpdateMemberRequest {
+                full_name: Some(full_name.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    (id, member.email, session_id, cookie)
+}
+
+async fn get(h: &H, uri: &str, cookie: &str) -> (StatusCode, String) {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn contact_hidden_email_never_rendered_to_other_members() {
+    let h = harness().await;
+    let directory = DirectoryService::new(h.pool.clone());
+
+    let (private, private_email, _, _) = named_member(&h.pool, "Priya Private").await;
+    directory
+        .set_privacy(
+            private,
+            DirectoryPrivacy {
+                show_in_directory: true,
+                show_contact: false,
+            },
+        )
+        .await
+        .unwrap();
+    let (open, open_email, _, _) = named_member(&h.pool, "Olu Open").await;
+    directory
+        .set_privacy(
+            open,
+            DirectoryPrivacy {
+                show_in_directory: true,
+                show_contact: true,
+            },
+        )
+        .await
+        .unwrap();
+    let (_, unlisted_email, _, _) = named_member(&h.pool, "Uma Unlisted").await;
+    let (_, _, _, viewer) = named_member(&h.pool, "Vic Viewer").await;
+
+    let (status, list) = get(&h, "/portal/directory", &viewer).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(list.contains("Priya Private"), "{}", list);
+    assert!(!list.contains(&private_email), "{}", list);
+    assert!(list.contains("Contact via admin"));
+    assert!(list.contains(&open_email));
+    assert!(!list.contains("Uma Unlisted"));
+    assert!(!list.contains(&unlisted_email));
+
+    let (status, card) = get(&h, &format!("/portal/directory/{}", private), &viewer).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(card.contains("Priya Private"), "{}", card);
+    assert!(!card.contains(&private_email), "{}", card);
+    assert!(card.contains("Contact via admin"));
+
+    let (_, card) = get(&h, &format!("/portal/directory/{}", open), &viewer).await;
+    assert!(card.contains(&format!("mailto:{}", open_email)), "{}", card);
+}
+
+#[tokio::test]
+async fn unlisted_member_card_is_not_found() {
+    let h = harness().await;
+    let (unlisted, _, _, _) = named_member(&h.pool, "Uma Unlisted").await;
+    let (_, _, _, viewer) = named_member(&h.pool, "Vic Viewer").await;
+
+    let (status, _) = get(&h, &format!("/portal/directory/{}", unlisted), &viewer).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Hiding contact doesn't unlist, and unlisting wins over contact.
+    DirectoryService::new(h.pool.clone())
+        .set_privacy(
+            unlisted,
+            DirectoryPrivacy {
+                show_in_directory: false,
+                show_contact: true,
+            },
+        )
+        .await
+        .unwrap();
+    let (status, _) = get(&h, &format!("/portal/directory/{}", unlisted), &viewer).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn profile_form_saves_directory_choices() {
+    let h = harness().await;
+    let (id, _, session_id, cookie) = named_member(&h.pool, "Priya Private").await;
+    let token = CsrfService::new(SESSION_SECRET)
+        .generate_token(&session_id)
+        .await
+        .unwrap();
+
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/portal/profile/privacy")
+                .header("Cookie", &cookie)
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "csrf_token={}&show_in_directory=1",
+                    token
+                )))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let privacy = DirectoryService::new(h.pool.clone())
+        .privacy(id)
+        .await
+        .unwrap();
+    assert_eq!(
+        privacy,
+        DirectoryPrivacy {
+            show_in_directory: true,
+            show_contact: false,
+        }
+    );
+
+    let (_, profile) = get(&h, "/portal/profile", &cookie).await;
+    assert!(profile.contains(r#"name="show_in_directory" value="1""#));
+}
@@ -0,0 +1,186 @@
+//! One `MemberFilter` behind every admin roster view: the members page
+//! and the CSV export build it from the same query string, so a URL
+//! lists the same members in the same order whichever one renders it.
+//!
+//! Run with: cargo test --features test-utils --test member_filter_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+    repository::{MemberFilter, MemberRepository, MemberSort, SortOrder, SqliteMemberRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session};
+
+async fn seed(pool: &SqlitePool, name: &str, status: MemberStatus, type_slug: &str) {
+    let type_id: String = sqlx::query_scalar("SELECT id FROM membership_types WHERE slug = ?")
+        .bind(type_slug)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let username = name.to_lowercase().replace(' ', "_");
+    let member = repo
+        .create(CreateMemberRequest {
+            email: format!("{}@example.com", username),
+            username,
+            full_name: name.to_string(),
+            password: "p4ssword_long_enough".to_string(),
+            membership_type_id: Some(Uuid::parse_str(&type_id).unwrap()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    repo.update(
+        member.id,
+        UpdateMemberRequest {
+            status: Some(status),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+}
+
+async fn get(app: &Router, cookie: &str, uri: &str) -> String {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "{uri}");
+    let body = to_bytes(resp.into_body(), 1 << 22).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Member ids in the order the members table lists them.
+fn page_ids(html: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for chunk in html.split("href=\"/portal/admin/members/").skip(1) {
+        let Some(id) = chunk.get(..36) else { continue };
+        if Uuid::parse_str(id).is_ok() && ids.last().map(String::as_str) != Some(id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+/// Member ids in the order the export's rows list them.
+fn export_ids(csv: &str) -> Vec<String> {
+    csv.lines()
+        .skip(1)
+        .map(|line| line[..36].to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn page_and_export_agree_for_the_same_query() {
+    let pool = fresh_pool().await;
+    seed(&pool, "Ada Lovelace", MemberStatus::Active, "member").await;
+    seed(&pool, "Grace Hopper", MemberStatus::Active, "associate").await;
+    seed(&pool, "Alan Turing", MemberStatus::Expired, "member").await;
+    seed(
+        &pool,
+        "Edsger Dijkstra",
+        MemberStatus::Pending,
+        "life-member",
+    )
+    .await;
+    seed(&pool, "Barbara Liskov", MemberStatus::Active, "life-member").await;
+    let (_, _, cookie) = member_session(&pool, true).await;
+    let state = build_app_state(pool.clone()).await;
+    let app = coterie::web::create_web_routes(state);
+
+    for query in [
+        "",
+        "status=Active",
+        "type=member",
+        "q=a&sort=type&order=desc",
+        "sort=status",
+        "sort=joined&order=desc",
+        "sort=dues",
+        "status=Active&type=life-member",
+        "sort=bogus&order=sideways",
+    ] {
+        let page = get(
+            &app,
+            &cookie,
+            &format!("/portal/admin/members?per_page=100&{query}"),
+        )
+        .await;
+        let export = get(
+            &app,
+            &cookie,
+            &format!("/portal/admin/members/export?{query}"),
+        )
+        .await;
+        let (page, export) = (page_ids(&page), export_ids(&export));
+        assert!(!page.is_empty(), "{query}: nothing listed");
+        assert_eq!(page, export, "{query}");
+    }
+}
+
+#[tokio::test]
+async fn count_matches_search_and_pages_are_disjoint() {
+    let pool = fresh_pool().await;
+    for i in 0..7 {
+        seed(
+            &pool,
+            &format!("Same Name {i}"),
+            MemberStatus::Active,
+            "member",
+        )
+        .await;
+    }
+    seed(&pool, "Someone Else", MemberStatus::Expired, "member").await;
+    let repo = SqliteMemberRepository::new(pool);
+
+    let filter = MemberFilter {
+        search: Some("same".to_string()),
+        status: Some(MemberStatus::Active),
+        sort: "name".parse().unwrap(),
+        order: "desc".parse().unwrap(),
+        ..Default::default()
+    };
+    assert_eq!(filter.sort, MemberSort::Name);
+    assert_eq!(filter.order, SortOrder::Desc);
+    assert!("bogus".parse::<MemberSort>().is_err());
+
+    assert_eq!(repo.count(&filter).await.unwrap(), 7);
+    assert_eq!(repo.count(&MemberFilter::default()).await.unwrap(), 8);
+    for (status, expected) in [
+        (MemberStatus::Active, 7),
+        (MemberStatus::Expired, 1),
+        (MemberStatus::Pending, 0),
+    ] {
+        assert_eq!(repo.count_by_status(status).await.unwrap(), expected);
+    }
+    let all = repo.search(&filter, 100, 0).await.unwrap();
+    let first = repo.search(&filter, 4, 0).await.unwrap();
+    let second = repo.search(&filter, 4, 4).await.unwrap();
+    let paged: Vec<Uuid> = first.iter().chain(&second).map(|m| m.id).collect();
+    assert_eq!(paged, all.iter().map(|m| m.id).collect::<Vec<_>>());
+
+    let exported: Vec<Uuid> = repo
+        .export_rows(&filter)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+    assert_eq!(exported, paged);
+}
@@ -67,4 +67,58 @@ async fn test_password_hashing() -> anyhow::Result<()> {
     assert!(!auth::AuthService::verify_password("wrong_password", &hash).await?);
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_create_batch_creates_every_member() -> anyhow::Result<()> {
+    use coterie::auth::{password::PasswordCost, AuthService};
+
+    let pool = SqlitePool::connect(":memory:").await?;
+    sqlx::migrate!("./migrations").run(&pool).await?;
+    let repo = SqliteMemberRepository::new(pool.clone());
+
+    // More than one insert batch, with an in-batch duplicate email
+    // that should fail on its own without sinking its neighbours.
+    const N: usize = 250;
+    let mut requests: Vec<CreateMemberRequest> = (0..N)
+        .map(|i| CreateMemberRequest {
+            email: format!("bulk{}@example.com", i),
+            username: format!("bulk{}", i),
+            full_name: format!("Bulk Member {}", i),
+            password: format!("password-{}", i),
+            ..Default::default()
+        })
+        .collect();
+    requests.push(CreateMemberRequest {
+        email: "bulk7@example.com".to_string(),
+        username: "bulk7-dupe".to_string(),
+        full_name: "Duplicate".to_string(),
+        password: "password-dupe".to_string(),
+        ..Default::default()
+    });
+
+    let results = repo.create_batch(requests, PasswordCost::TestData).await?;
+
+    assert_eq!(results.len(), N + 1);
+    assert!(results[N].is_err(), "duplicate email must fail");
+    for (i, result) in results[..N].iter().enumerate() {
+        let member = result.as_ref().expect("member created");
+        assert_eq!(member.email, format!("bulk{}@example.com", i));
+        assert_eq!(member.status, MemberStatus::Pending);
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM members")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, N as i64);
+
+    // Hashes are stored against the right rows.
+    let hash: String = sqlx::query_scalar("SELECT password_hash FROM members WHERE email = ?")
+        .bind("bulk42@example.com")
+        .fetch_one(&pool)
+        .await?;
+    assert!(AuthService::verify_password("password-42", &hash).await?);
+    assert!(!AuthService::verify_password("password-43", &hash).await?);
+
+    Ok(())
+}
@@ -0,0 +1,215 @@
+//! Admin integration resync for one member: re-pushes the member's
+//! current state to every enabled integration (or just the one picked)
+//! without changing the member, reports per-integration results and
+//! writes each attempt to the integration log.
+//!
+//! Run with: cargo test --features test-utils --test member_resync_test
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    domain::{Member, MemberStatus},
+    error::{AppError, Result as CoterieResult},
+    integrations::{Integration, IntegrationEvent},
+};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session};
+
+/// Records the member from every member event it's sent; `fail`
+/// makes it report an error after recording.
+struct Recorder {
+    name: &'static str,
+    fail: bool,
+    seen: Mutex<Vec<(&'static str, Member)>>,
+}
+
+impl Recorder {
+    fn new(name: &'static str, fail: bool) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            fail,
+            seen: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait]
+impl Integration for Recorder {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: &IntegrationEvent) -> CoterieResult<()> {
+        let (kind, member) = match event {
+            IntegrationEvent::MemberActivated(m) => ("activated", m.clone()),
+            IntegrationEvent::MemberExpired(m) => ("expired", m.clone()),
+            _ => return Ok(()),
+        };
+        self.seen.lock().await.push((kind, member));
+        if self.fail {
+            return Err(AppError::Integration("door controller offline".to_string()));
+        }
+        Ok(())
+    }
+}
+
+struct H {
+    pool: SqlitePool,
+    app: Router,
+    roles: Arc<Recorder>,
+    doors: Arc<Recorder>,
+    admin_cookie: String,
+    member_id: Uuid,
+}
+
+async fn harness() -> H {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let roles = Recorder::new("Roles", false);
+    let doors = Recorder::new("Doors", true);
+    let manager = state.service_context.integration_manager.clone();
+    manager.register(roles.clone()).await;
+    manager.register(doors.clone()).await;
+
+    let (_, _, admin_cookie) = member_session(&pool, true).await;
+    let (member_id, _, _) = member_session(&pool, false).await;
+    sqlx::query("UPDATE members SET discord_id = '123456789012345678' WHERE id = ?")
+        .bind(member_id.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    H {
+        pool,
+        app: coterie::web::create_web_routes(state),
+        roles,
+        doors,
+        admin_cookie,
+        member_id,
+    }
+}
+
+async fn resync(h: &H, integration: &str) -> (StatusCode, String) {
+    let resp = h
+        .app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(format!(
+                    "/portal/admin/members/{}/resync-integrations",
+                    h.member_id
+                ))
+                .header(header::COOKIE, &h.admin_cookie)
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "integration={}&csrf_token=x",
+                    integration
+                )))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn log_rows(pool: &SqlitePool) -> Vec<(String, String, String)> {
+    sqlx::query_as(
+        "SELECT integration, event_type, response_status FROM integration_log \
+         ORDER BY integration",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn resync_sends_current_member_state_to_every_integration() {
+    let h = harness().await;
+    let updated_before: String = sqlx::query_scalar("SELECT updated_at FROM members WHERE id = ?")
+        .bind(h.member_id.to_string())
+        .fetch_one(&h.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = resync(&h, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Roles: synced"), "{}", body);
+    assert!(body.contains("Doors: failed"), "{}", body);
+    assert!(body.contains("door controller offline"), "{}", body);
+
+    for recorder in [&h.roles, &h.doors] {
+        let seen = recorder.seen.lock().await;
+        assert_eq!(seen.len(), 1, "{} called once", recorder.name);
+        let (kind, member) = &seen[0];
+        assert_eq!(*kind, "activated");
+        assert_eq!(member.id, h.member_id);
+        assert_eq!(member.status, MemberStatus::Active);
+        assert_eq!(member.discord_id.as_deref(), Some("123456789012345678"));
+    }
+
+    assert_eq!(
+        log_rows(&h.pool).await,
+        vec![
+            (
+                "Doors".to_string(),
+                "member_activated".to_string(),
+                "error".to_string()
+            ),
+            (
+                "Roles".to_string(),
+                "member_activated".to_string(),
+                "ok".to_string()
+            ),
+        ]
+    );
+
+    let updated_after: String = sqlx::query_scalar("SELECT updated_at FROM members WHERE id = ?")
+        .bind(h.member_id.to_string())
+        .fetch_one(&h.pool)
+        .await
+        .unwrap();
+    assert_eq!(updated_before, updated_after, "the member is not modified");
+}
+
+#[tokio::test]
+async fn resync_can_target_one_integration_and_follows_status() {
+    let h = harness().await;
+    sqlx::query("UPDATE members SET status = 'Suspended' WHERE id = ?")
+        .bind(h.member_id.to_string())
+        .execute(&h.pool)
+        .await
+        .unwrap();
+
+    let (status, body) = resync(&h, "Roles").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("Roles: synced"), "{}", body);
+    assert!(!body.contains("Doors"), "{}", body);
+
+    let seen = h.roles.seen.lock().await;
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].0, "expired");
+    assert_eq!(seen[0].1.status, MemberStatus::Suspended);
+    assert!(h.doors.seen.lock().await.is_empty());
+}
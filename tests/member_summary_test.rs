@@ -0,0 +1,113 @@
+//! `/portal/me`: the member's own summary shows their dues standing
+//! with days remaining, the soonest upcoming event they're registered
+//! for, and an empty state for each widget with nothing to show.
+//!
+//! Run with: cargo test --features test-utils --test member_summary_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{AttendanceStatus, Event, EventType},
+    repository::{EventRepository, SqliteEventRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, event, fresh_pool, make_member, member_session};
+
+async fn create_event(pool: &SqlitePool, title: &str, start: DateTime<Utc>) -> Uuid {
+    let creator = make_member(pool).await;
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: title.to_string(),
+            event_type: EventType::Meeting,
+            start_time: start,
+            end_time: None,
+            location: Some("Main hall".to_string()),
+            ..event(creator)
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn get(app: &Router, uri: &str, cookie: &str) -> String {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::COOKIE, cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "GET {}", uri);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn shows_dues_status_and_next_registered_event() {
+    let pool = fresh_pool().await;
+    let (member, _, cookie) = member_session(&pool, false).await;
+    let now = Utc::now();
+    sqlx::query("UPDATE members SET dues_paid_until = ? WHERE id = ?")
+        .bind((now + Duration::days(10) + Duration::hours(1)).naive_utc())
+        .bind(member.to_string())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let repo = SqliteEventRepository::new(pool.clone());
+    let past = create_event(&pool, "Past meetup", now - Duration::days(30)).await;
+    let later = create_event(&pool, "Annual general meeting", now + Duration::days(20)).await;
+    let next = create_event(&pool, "Soldering workshop", now + Duration::days(3)).await;
+    let cancelled = create_event(&pool, "Board games", now + Duration::days(1)).await;
+    create_event(&pool, "Not registered", now + Duration::hours(12)).await;
+    for event in [past, later, next, cancelled] {
+        assert_eq!(
+            repo.register_attendance(event, member).await.unwrap(),
+            AttendanceStatus::Registered
+        );
+    }
+    repo.cancel_attendance(cancelled, member).await.unwrap();
+
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+    let html = get(&app, "/portal/me", &cookie).await;
+
+    assert!(html.contains(">Current</span>"), "{}", html);
+    assert!(html.contains("10 days remaining"), "{}", html);
+    assert!(html.contains("href=\"/portal/payments/new\""));
+    assert!(html.contains("Soldering workshop"), "{}", html);
+    for other in [
+        "Past meetup",
+        "Annual general meeting",
+        "Board games",
+        "Not registered",
+    ] {
+        assert!(!html.contains(other), "{} shown", other);
+    }
+    assert!(html.contains("No payment history"));
+}
+
+#[tokio::test]
+async fn widgets_fall_back_to_empty_states() {
+    let pool = fresh_pool().await;
+    let (_, _, cookie) = member_session(&pool, false).await;
+
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+    let html = get(&app, "/portal/me", &cookie).await;
+
+    assert!(html.contains(">Unpaid</span>"), "{}", html);
+    assert!(html.contains("No dues on record yet."));
+    assert!(html.contains("You haven't registered for any upcoming events."));
+    assert!(html.contains("No payment history"));
+}
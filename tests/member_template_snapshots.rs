@@ -14,18 +14,19 @@
 use askama::Template;
 use chrono::TimeZone;
 use coterie::{
-    domain::MemberStatus,
+    domain::{AdminNotificationChannel, DuesExtensionBase, DuesStatus, LandingPage, MemberStatus},
     web::{
         portal::{
             MemberInfo,
             admin::members::{
-                create::AdminNewMemberTemplate,
+                create::{AdminNewMemberTemplate, MemberFormValues},
                 detail::{AdminMemberDetailInfo, AdminMemberDetailTemplate, AdminSavedCardInfo},
                 list::{AdminMemberInfo, AdminMembersTableTemplate, AdminMembersTemplate},
                 MembershipTypeOption,
             },
+            admin::forms::FormErrors,
             dashboard::MemberDashboardTemplate,
-            profile::ProfileTemplate,
+            profile::{LocaleOption, MonthOption, ProfileTemplate},
             security::SecurityTemplate,
         },
         templates::BaseContext,
@@ -48,6 +49,14 @@ fn fixture_dues() -> chrono::DateTime<chrono::Utc> {
     chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
 }
 
+fn fixture_dues_status(status: MemberStatus) -> DuesStatus {
+    if status.is_honorary() {
+        DuesStatus::Exempt
+    } else {
+        DuesStatus::Current
+    }
+}
+
 fn fixture_base() -> BaseContext {
     BaseContext::default()
 }
@@ -76,6 +85,7 @@ fn admin_member_info(status: MemberStatus) -> AdminMemberInfo {
         membership_type: "Regular".to_string(),
         joined_at: fixture_joined(),
         dues_paid_until: Some(fixture_dues()),
+        dues_status: fixture_dues_status(status),
     }
 }
 
@@ -91,7 +101,7 @@ fn admin_member_detail_info(status: MemberStatus) -> AdminMemberDetailInfo {
         membership_type_name: "Regular".to_string(),
         joined_at: fixture_joined(),
         dues_paid_until: Some(fixture_dues()),
-        dues_expired: false,
+        dues_status: fixture_dues_status(status),
         bypass_dues: false,
         email_verified: true,
         notes: String::new(),
@@ -99,6 +109,7 @@ fn admin_member_detail_info(status: MemberStatus) -> AdminMemberDetailInfo {
         stripe_customer_id: None,
         stripe_subscription_id: None,
         discord_id: String::new(),
+        honorary_until: None,
         saved_cards: Vec::<AdminSavedCardInfo>::new(),
         created_at: "September 12, 2025".to_string(),
         updated_at: "September 12, 2025 at  2:30 PM".to_string(),
@@ -136,6 +147,7 @@ fn render_dashboard(status: MemberStatus) -> String {
     let tmpl = MemberDashboardTemplate {
         base: fixture_base(),
         member: member_info(status),
+        dues_status: fixture_dues_status(status),
     };
     tmpl.render().expect("render dashboard")
 }
@@ -144,6 +156,25 @@ fn render_profile(status: MemberStatus) -> String {
     let tmpl = ProfileTemplate {
         base: fixture_base(),
         member: member_info(status),
+        dues_status: fixture_dues_status(status),
+        locale_options: LocaleOption::all(),
+        member_locale: String::new(),
+        org_locale_label: "English (United States)".to_string(),
+        privacy: Default::default(),
+        birthday_months: MonthOption::all(None),
+        birthday_day: String::new(),
+        celebrate: true,
+        emergency: Default::default(),
+        event_reminders: true,
+        admin_channel: AdminNotificationChannel::default(),
+        admin_channel_options: AdminNotificationChannel::ALL,
+        landing_page: String::new(),
+        default_landing_label: "Dashboard".to_string(),
+        landing_page_options: LandingPage::ALL
+            .into_iter()
+            .filter(|p| !p.admin_only())
+            .collect(),
+        event_history: Vec::new(),
     };
     tmpl.render().expect("render profile")
 }
@@ -189,6 +220,7 @@ fn render_admin_members_table(status: MemberStatus) -> String {
         type_filter: String::new(),
         sort_field: "name".to_string(),
         sort_order: "asc".to_string(),
+        locale: Default::default(),
     };
     tmpl.render().expect("render admin members table")
 }
@@ -198,14 +230,24 @@ fn render_admin_member_detail(status: MemberStatus) -> String {
         base: fixture_base(),
         member: admin_member_detail_info(status),
         type_options: type_options(),
+        default_extension_base: DuesExtensionBase::default(),
+        extension_bases: DuesExtensionBase::ALL.to_vec(),
+        signup_answers: Vec::new(),
+        emergency: Default::default(),
+        event_history: Vec::new(),
+        integration_names: Vec::new(),
     };
     tmpl.render().expect("render admin member detail")
 }
 
 fn render_admin_member_new() -> String {
+    let base = fixture_base();
     let tmpl = AdminNewMemberTemplate {
-        base: fixture_base(),
+        csrf_token: base.csrf_token.clone(),
+        base,
         type_options: type_options(),
+        values: MemberFormValues::default(),
+        errors: FormErrors::new(),
     };
     tmpl.render().expect("render admin member new")
 }
@@ -0,0 +1,127 @@
+//! Membership type caps: the admin types page shows "X / Y used" for a
+//! capped type, in amber once it's nearly full, and public signup into
+//! a type that has reached its cap is refused.
+//!
+//! Run with: cargo test --features test-utils --test membership_type_capacity_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+};
+use coterie::domain::{CreateMembershipTypeRequest, MembershipTypeConfig};
+use serde_json::json;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member, member_session};
+
+async fn capped_type(
+    state: &coterie::api::state::AppState,
+    slug: &str,
+    max_members: i64,
+) -> MembershipTypeConfig {
+    state
+        .service_context
+        .membership_type_service
+        .create(None, CreateMembershipTypeRequest {
+            name: format!("Capped {}", slug),
+            slug: Some(slug.to_string()),
+            description: None,
+            color: None,
+            icon: None,
+            fee_cents: 0,
+            billing_period: "yearly".to_string(),
+            max_members: Some(max_members),
+        })
+        .await
+        .unwrap()
+}
+
+/// Add `n` members holding `membership_type`.
+async fn fill(pool: &SqlitePool, membership_type: &MembershipTypeConfig, n: usize) {
+    for _ in 0..n {
+        let id = make_member(pool).await;
+        sqlx::query("UPDATE members SET membership_type_id = ? WHERE id = ?")
+            .bind(membership_type.id.to_string())
+            .bind(id.to_string())
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn near_capacity_type_renders_warning() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let nearly = capped_type(&state, "nearly", 5).await;
+    fill(&pool, &nearly, 4).await;
+    let roomy = capped_type(&state, "roomy", 10).await;
+    fill(&pool, &roomy, 1).await;
+    let app = coterie::web::create_web_routes(state);
+    let (_, _, cookie) = member_session(&pool, true).await;
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/portal/admin/types")
+                .header(header::COOKIE, &cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    let html = String::from_utf8_lossy(&body);
+
+    assert!(
+        html.contains(r#"<span class="text-amber-600 font-semibold">4 / 5 used</span>"#),
+        "{}",
+        html
+    );
+    assert!(
+        html.contains(r#"<span class="text-gray-500">1 / 10 used</span>"#),
+        "{}",
+        html
+    );
+}
+
+#[tokio::test]
+async fn signup_into_full_type_is_refused() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let full = capped_type(&state, "full", 2).await;
+    fill(&pool, &full, 2).await;
+    let app = coterie::api::create_app(state);
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/public/signup")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({
+                        "email": "late@example.com",
+                        "username": "latecomer",
+                        "full_name": "Late Comer",
+                        "password": "Correct-horse-battery-9",
+                        "membership_type_slug": "full",
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let created: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM members WHERE email = ?")
+        .bind("late@example.com")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(created, 0);
+}
@@ -0,0 +1,126 @@
+//! Amounts survive storage and display intact: cents are i64 end to
+//! end, so a value past i32::MAX reads back exactly, and fees beyond
+//! what a single payment may charge are refused rather than stored.
+//!
+//! Run with: cargo test --features test-utils --test money_test
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use coterie::{
+    domain::{
+        CreateMembershipTypeRequest, Locale, Money, Payer, Payment, PaymentKind, PaymentMethod,
+        PaymentStatus, UpdateMembershipTypeRequest, MAX_PAYMENT_CENTS,
+    },
+    error::AppError,
+    repository::{
+        MembershipTypeRepository, PaymentRepository, SqliteMembershipTypeRepository,
+        SqlitePaymentRepository,
+    },
+    service::membership_type_service::MembershipTypeService,
+};
+use uuid::Uuid;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+/// Past i32::MAX, where the old `as i32` casts wrapped.
+const LARGE_CENTS: i64 = 3_000_000_007;
+
+fn membership_type(slug: &str, fee_cents: i64) -> CreateMembershipTypeRequest {
+    CreateMembershipTypeRequest {
+        name: slug.to_string(),
+        slug: Some(slug.to_string()),
+        description: None,
+        color: None,
+        icon: None,
+        fee_cents,
+        billing_period: "yearly".to_string(),
+        max_members: None,
+    }
+}
+
+#[tokio::test]
+async fn large_amounts_round_trip_without_truncation() {
+    let pool = fresh_pool().await;
+    let member = make_member(&pool).await;
+
+    let payments = SqlitePaymentRepository::new(pool.clone());
+    let id = Uuid::new_v4();
+    payments
+        .create(Payment {
+            id,
+            payer: Payer::Member(member),
+            amount_cents: LARGE_CENTS,
+            currency: "USD".to_string(),
+            status: PaymentStatus::Pending,
+            payment_method: PaymentMethod::Manual,
+            external_id: None,
+            description: "Large".to_string(),
+            kind: PaymentKind::Other,
+            paid_at: None,
+            refunded_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    let stored = payments.find_by_id(id).await.unwrap().unwrap();
+    assert_eq!(stored.amount(), Money::usd(LARGE_CENTS));
+    assert_eq!(stored.amount().to_decimal_string(), "30000000.07");
+    assert_eq!(Locale::EnUs.money(&stored.amount()), "$30,000,000.07");
+
+    // The repository stores whatever it's given; the cap lives in the
+    // service. The column must still hold it without wrapping.
+    let types = SqliteMembershipTypeRepository::new(pool.clone());
+    let created = types
+        .create(None, membership_type("patron", LARGE_CENTS))
+        .await
+        .unwrap();
+    let stored = types.find_by_id(created.id).await.unwrap().unwrap();
+    assert_eq!(stored.fee_cents, LARGE_CENTS);
+    assert_eq!(stored.fee(), Money::usd(LARGE_CENTS));
+}
+
+#[tokio::test]
+async fn membership_fee_past_the_payment_cap_is_refused() {
+    let pool = fresh_pool().await;
+    let service =
+        MembershipTypeService::new(Arc::new(SqliteMembershipTypeRepository::new(pool.clone())));
+
+    let err = service
+        .create(None, membership_type("too-much", MAX_PAYMENT_CENTS + 1))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+
+    let at_cap = service
+        .create(None, membership_type("at-cap", MAX_PAYMENT_CENTS))
+        .await
+        .unwrap();
+    let err = service
+        .update(
+            None,
+            at_cap.id,
+            UpdateMembershipTypeRequest {
+                fee_cents: Some(LARGE_CENTS),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)), "{err:?}");
+}
+
+#[test]
+fn dollars_and_cents_convert_losslessly() {
+    for cents in [0, 1, 99, 100, 12_345, LARGE_CENTS, i64::MAX] {
+        let money = Money::usd(cents);
+        let back = Money::parse_dollars(&money.to_decimal_string(), "USD").unwrap();
+        assert_eq!(back.cents(), cents);
+    }
+    // Values an f64 can't hold exactly still come back to the cent.
+    let exact = Money::parse_dollars("90071992547409.93", "USD").unwrap();
+    assert_eq!(exact.cents(), 9_007_199_254_740_993);
+    assert_eq!(exact.to_decimal_string(), "90071992547409.93");
+}
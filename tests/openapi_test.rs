@@ -31,10 +31,13 @@ fn openapi_spec_documents_all_public_endpoints() {
     let expected: &[(&str, &str)] = &[
         ("/", "get"),
         ("/health", "get"),
+        ("/ready", "get"),
         ("/api", "get"),
         ("/public/signup", "post"),
         ("/public/events", "get"),
         ("/public/events/private-count", "get"),
+        ("/public/events/rsvp", "post"),
+        ("/public/events/{id}/calendar", "get"),
         ("/public/announcements", "get"),
         ("/public/announcements/private-count", "get"),
         ("/public/feed/rss", "get"),
@@ -67,12 +70,17 @@ fn openapi_spec_registers_all_dto_schemas() {
     let expected_schemas: &[&str] = &[
         "ApiInfo",
         "HealthStatus",
+        "ReadinessStatus",
+        "DependencyStatus",
         "SignupRequest",
         "SignupResponse",
         "PrivateEventCount",
+        "GuestRsvpRequest",
+        "GuestRsvpResponse",
         "PublicDonateRequest",
         "PublicDonateResponse",
         "PrivateAnnouncementCount",
+        "AnnouncementPage",
         "Event",
         "EventType",
         "EventVisibility",
@@ -0,0 +1,93 @@
+//! The `/reset-password` form end to end: a valid link sets the new
+//! password and signs the member out everywhere, and a used, expired
+//! or unknown link fails without touching the password.
+//!
+//! Run with: cargo test --features test-utils --test password_reset_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use chrono::Duration;
+use coterie::auth::{email_tokens::create_password_reset_token, AuthService};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, member_session};
+
+const NEW_PASSWORD: &str = "a-brand-new-passphrase-42";
+
+async fn reset(app: &Router, token: &str) -> String {
+    let form = serde_urlencoded::to_string([
+        ("token", token),
+        ("new_password", NEW_PASSWORD),
+        ("confirm_password", NEW_PASSWORD),
+    ])
+    .unwrap();
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/reset-password")
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .body(Body::from(form))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+async fn password_is(pool: &SqlitePool, member: Uuid, password: &str) -> bool {
+    let hash: String = sqlx::query_scalar("SELECT password_hash FROM members WHERE id = ?")
+        .bind(member.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    AuthService::verify_password(password, &hash).await.unwrap()
+}
+
+#[tokio::test]
+async fn link_works_once_and_signs_out_everywhere() {
+    let pool = fresh_pool().await;
+    let (member, _, _) = member_session(&pool, false).await;
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+    let created = create_password_reset_token(&pool, member, Duration::hours(1))
+        .await
+        .unwrap();
+
+    let body = reset(&app, &created.token).await;
+    assert!(body.contains("Password updated"), "{}", body);
+    assert!(password_is(&pool, member, NEW_PASSWORD).await);
+    let sessions: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sessions WHERE member_id = ?")
+        .bind(member.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(sessions, 0);
+
+    let again = reset(&app, &created.token).await;
+    assert!(again.contains("Reset failed"), "{}", again);
+}
+
+#[tokio::test]
+async fn expired_or_unknown_link_leaves_the_password_alone() {
+    let pool = fresh_pool().await;
+    let (member, _, _) = member_session(&pool, false).await;
+    let app = coterie::web::create_web_routes(build_app_state(pool.clone()).await);
+    let expired = create_password_reset_token(&pool, member, Duration::hours(-1))
+        .await
+        .unwrap();
+
+    for token in [expired.token.as_str(), "not-a-real-token"] {
+        let body = reset(&app, token).await;
+        assert!(body.contains("invalid or has expired"), "{}", body);
+    }
+    assert!(!password_is(&pool, member, NEW_PASSWORD).await);
+}
@@ -11,7 +11,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use coterie::{
-    auth::SecretCrypto,
+    auth::{AuthService, SecretCrypto},
     domain::{CreateMemberRequest, PaymentKind, PaymentMethod, MAX_PAYMENT_CENTS},
     email::{EmailMessage, EmailSender},
     error::{AppError, Result as CoterieResult},
@@ -70,6 +70,7 @@ async fn build_harness() -> H {
         member_repo.clone(),
         campaign_repo,
         audit_service,
+        Arc::new(IntegrationManager::new()),
     );
 
     // BillingService isn't dereferenced by any of these tests (validation
@@ -98,6 +99,10 @@ async fn build_harness() -> H {
         settings,
         email,
         integrations,
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
         None,
         "http://localhost:3000".to_string(),
         pool.clone(),
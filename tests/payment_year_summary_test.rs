@@ -0,0 +1,208 @@
+//! Member-facing yearly payment summary: the year's total counts only
+//! that member's Completed payments dated in that year — not other
+//! years, not pending / failed / refunded ones, not someone else's —
+//! and a year with nothing in it renders an empty summary.
+//!
+//! Run with: cargo test --features test-utils --test payment_year_summary_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use coterie::{
+    domain::{Payer, Payment, PaymentKind, PaymentMethod, PaymentStatus},
+    repository::{PaymentRepository, SqlitePaymentRepository},
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, fresh_pool, member_session};
+
+/// Insert a payment, then stamp status and `paid_at` directly so it
+/// lands in the year under test.
+async fn insert_payment(
+    pool: &SqlitePool,
+    member_id: Uuid,
+    amount_cents: i64,
+    kind: PaymentKind,
+    status: PaymentStatus,
+    paid_at: DateTime<Utc>,
+) {
+    let repo: Arc<dyn PaymentRepository> = Arc::new(SqlitePaymentRepository::new(pool.clone()));
+    let id = Uuid::new_v4();
+    repo.create(Payment {
+        id,
+        payer: Payer::Member(member_id),
+        amount_cents,
+        currency: "USD".to_string(),
+        status: PaymentStatus::Pending,
+        payment_method: PaymentMethod::Manual,
+        external_id: None,
+        description: format!("Payment of {}", amount_cents),
+        kind,
+        paid_at: None,
+        refunded_at: None,
+        created_at: paid_at,
+        updated_at: paid_at,
+    })
+    .await
+    .unwrap();
+    sqlx::query("UPDATE payments SET status = ?, paid_at = ?, created_at = ? WHERE id = ?")
+        .bind(status.as_str())
+        .bind(paid_at.naive_utc())
+        .bind(paid_at.naive_utc())
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn get(app: &Router, uri: &str, cookie: &str) -> (String, Option<String>) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header("Cookie", cookie)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "GET {}", uri);
+    let disposition = resp
+        .headers()
+        .get(header::CONTENT_DISPOSITION)
+        .map(|v| v.to_str().unwrap().to_string());
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (String::from_utf8(body.to_vec()).unwrap(), disposition)
+}
+
+fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+}
+
+#[tokio::test]
+async fn yearly_summary_totals_only_that_years_completed_payments() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (member, _, cookie) = member_session(&pool, false).await;
+    let (other, _, _) = member_session(&pool, false).await;
+
+    // Counted: 2024, Completed, this member.
+    insert_payment(
+        &pool,
+        member,
+        50_00,
+        PaymentKind::Membership,
+        PaymentStatus::Completed,
+        at(2024, 1, 1),
+    )
+    .await;
+    insert_payment(
+        &pool,
+        member,
+        25_50,
+        PaymentKind::Donation { campaign_id: None },
+        PaymentStatus::Completed,
+        at(2024, 12, 31),
+    )
+    .await;
+    // Not counted: other years, other statuses, other members.
+    insert_payment(
+        &pool,
+        member,
+        100_000,
+        PaymentKind::Membership,
+        PaymentStatus::Completed,
+        at(2023, 12, 31),
+    )
+    .await;
+    insert_payment(
+        &pool,
+        member,
+        200_000,
+        PaymentKind::Membership,
+        PaymentStatus::Completed,
+        at(2025, 1, 1),
+    )
+    .await;
+    for status in [
+        PaymentStatus::Pending,
+        PaymentStatus::Failed,
+        PaymentStatus::Refunded,
+    ] {
+        insert_payment(
+            &pool,
+            member,
+            30_000,
+            PaymentKind::Membership,
+            status,
+            at(2024, 6, 1),
+        )
+        .await;
+    }
+    insert_payment(
+        &pool,
+        other,
+        40_000,
+        PaymentKind::Membership,
+        PaymentStatus::Completed,
+        at(2024, 6, 1),
+    )
+    .await;
+
+    let (page, _) = get(&app, "/portal/payments/summary/2024", &cookie).await;
+    assert!(
+        page.contains(r#"<p class="text-2xl font-bold text-gray-900" id="year-total">$75.50</p>"#),
+        "{}",
+        page
+    );
+    assert!(page.contains("$50.00") && page.contains("$25.50"));
+    assert!(page.contains("Payment of 5000") && page.contains("Payment of 2550"));
+    for excluded in [
+        "Payment of 100000",
+        "Payment of 200000",
+        "Payment of 30000",
+        "Payment of 40000",
+    ] {
+        assert!(!page.contains(excluded), "{} listed", excluded);
+    }
+    assert_eq!(
+        page.matches("/receipt\"").count(),
+        2,
+        "one receipt link each"
+    );
+
+    let (csv, disposition) = get(&app, "/portal/payments/summary/2024/statement", &cookie).await;
+    assert_eq!(
+        disposition.as_deref(),
+        Some("attachment; filename=\"payments-2024.csv\"")
+    );
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4, "header, two payments, total:\n{}", csv);
+    assert!(lines[1].starts_with("\"2024-01-01\",\"Payment of 5000\",\"Dues\",\"50.00\""));
+    assert!(lines[2].starts_with("\"2024-12-31\",\"Payment of 2550\",\"Donation\",\"25.50\""));
+    assert_eq!(lines[3], "\"\",\"Total for 2024\",,\"75.50\",\"USD\",");
+}
+
+#[tokio::test]
+async fn year_without_payments_renders_an_empty_summary() {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    let (_, _, cookie) = member_session(&pool, false).await;
+
+    let (page, _) = get(&app, "/portal/payments/summary/2019", &cookie).await;
+    assert!(page.contains("No completed payments in 2019."));
+    assert!(page.contains(r#"id="year-total">$0.00</p>"#));
+
+    let (csv, _) = get(&app, "/portal/payments/summary/2019/statement", &cookie).await;
+    assert_eq!(csv.lines().count(), 2, "header and total only:\n{}", csv);
+    assert!(csv.ends_with("\"Total for 2019\",,\"0.00\",\"USD\",\n"));
+}
@@ -0,0 +1,82 @@
+//! Stripe webhook idempotency claims are pruned once they're past the
+//! replay window plus margin, and never while Stripe could still
+//! redeliver the event.
+//!
+//! Run with: cargo test --features test-utils --test processed_events_retention_test
+
+use chrono::{Duration, Utc};
+use coterie::{
+    payments::WebhookMetrics,
+    repository::{
+        processed_events_repository::{PRUNE_MARGIN, REPLAY_WINDOW},
+        ProcessedEventsRepository, SqliteProcessedEventsRepository,
+    },
+};
+use sqlx::SqlitePool;
+
+mod common;
+use common::fresh_pool;
+
+/// Claim `event_id` and backdate the claim by `age`.
+async fn claim_aged(
+    repo: &SqliteProcessedEventsRepository,
+    pool: &SqlitePool,
+    event_id: &str,
+    age: Duration,
+) {
+    assert!(repo
+        .claim(event_id, "CheckoutSessionCompleted")
+        .await
+        .unwrap());
+    sqlx::query("UPDATE processed_stripe_events SET processed_at = ? WHERE event_id = ?")
+        .bind((Utc::now() - age).format("%Y-%m-%d %H:%M:%S").to_string())
+        .bind(event_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn old_claims_pruned_recent_ones_kept() {
+    let pool = fresh_pool().await;
+    let repo = SqliteProcessedEventsRepository::new(pool.clone());
+    let retention = REPLAY_WINDOW + PRUNE_MARGIN;
+
+    claim_aged(&repo, &pool, "evt_old", retention + Duration::days(1)).await;
+    claim_aged(
+        &repo,
+        &pool,
+        "evt_in_margin",
+        retention - Duration::hours(1),
+    )
+    .await;
+    claim_aged(
+        &repo,
+        &pool,
+        "evt_in_window",
+        REPLAY_WINDOW - Duration::hours(1),
+    )
+    .await;
+    claim_aged(&repo, &pool, "evt_new", Duration::zero()).await;
+    assert_eq!(repo.count().await.unwrap(), 4);
+
+    let metrics = WebhookMetrics::default();
+    let pruned = repo.prune_expired(Utc::now()).await.unwrap();
+    metrics.record_pruned(pruned);
+    assert_eq!(pruned, 1);
+    assert_eq!(metrics.snapshot().pruned, 1);
+    assert_eq!(repo.count().await.unwrap(), 3);
+
+    // The old claim is gone, so a redelivery would be processed again;
+    // the recent ones still block it.
+    assert!(repo
+        .claim("evt_old", "CheckoutSessionCompleted")
+        .await
+        .unwrap());
+    for kept in ["evt_in_margin", "evt_in_window", "evt_new"] {
+        assert!(
+            !repo.claim(kept, "CheckoutSessionCompleted").await.unwrap(),
+            "{kept} should have been retained"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+//! `GET /public/announcements` paging and type filtering: `limit`,
+//! `offset` and `type` narrow the list, the response carries the total
+//! match count, and only published, unexpired public announcements are
+//! ever counted.
+//!
+//! Run with: cargo test --features test-utils --test public_announcements_paging_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use chrono::{DateTime, Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType},
+    repository::{AnnouncementRepository, SqliteAnnouncementRepository},
+};
+use serde_json::Value;
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app_state, fresh_pool, make_member};
+
+async fn harness() -> (SqlitePool, Router, Uuid) {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let author = make_member(&pool).await;
+    (pool, coterie::api::create_app(state), author)
+}
+
+struct Seed {
+    title: &'static str,
+    announcement_type: AnnouncementType,
+    is_public: bool,
+    published_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+async fn seed(pool: &SqlitePool, author: Uuid, s: Seed) {
+    let now = Utc::now();
+    SqliteAnnouncementRepository::new(pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: s.title.to_string(),
+            content: "Body".to_string(),
+            announcement_type: s.announcement_type,
+            announcement_type_id: None,
+            is_public: s.is_public,
+            featured: false,
+            image_url: None,
+            published_at: s.published_at,
+            scheduled_publish_at: None,
+            expires_at: s.expires_at,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: author,
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+}
+
+/// Five public announcements, newest first "P1".."P5" (P1/P3/P5 are
+/// News), plus a members-only one, a draft and an expired one that
+/// must never appear.
+async fn seed_all(pool: &SqlitePool, author: Uuid) {
+    let now = Utc::now();
+    for (i, title) in ["P1", "P2", "P3", "P4", "P5"].into_iter().enumerate() {
+        seed(
+            pool,
+            author,
+            Seed {
+                title,
+                announcement_type: if i % 2 == 0 {
+                    AnnouncementType::News
+                } else {
+                    AnnouncementType::Meeting
+                },
+                is_public: true,
+                published_at: Some(now - Duration::hours(i as i64 + 1)),
+                expires_at: None,
+            },
+        )
+        .await;
+    }
+    for (title, is_public, published_at, expires_at) in [
+        ("Members only", false, Some(now), None),
+        ("Draft", true, None, None),
+        ("Expired", true, Some(now), Some(now - Duration::minutes(1))),
+    ] {
+        seed(
+            pool,
+            author,
+            Seed {
+                title,
+                announcement_type: AnnouncementType::News,
+                is_public,
+                published_at,
+                expires_at,
+            },
+        )
+        .await;
+    }
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, Option<String>, Value) {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let cache = resp
+        .headers()
+        .get(header::CACHE_CONTROL)
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (
+        status,
+        cache,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+fn titles(page: &Value) -> Vec<&str> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["title"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn pages_through_public_announcements() {
+    let (pool, app, author) = harness().await;
+    seed_all(&pool, author).await;
+
+    let (status, cache, page) = get(&app, "/public/announcements").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cache.as_deref(), Some("public, max-age=60"));
+    assert_eq!(titles(&page), ["P1", "P2", "P3", "P4", "P5"]);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["limit"], 20);
+    assert_eq!(page["offset"], 0);
+
+    let (_, _, page) = get(&app, "/public/announcements?limit=2").await;
+    assert_eq!(titles(&page), ["P1", "P2"]);
+    assert_eq!(page["total"], 5);
+
+    let (_, _, page) = get(&app, "/public/announcements?limit=2&offset=4").await;
+    assert_eq!(titles(&page), ["P5"], "last partial page");
+
+    let (status, _, page) = get(&app, "/public/announcements?limit=2&offset=5").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(titles(&page).is_empty(), "past the end");
+    assert_eq!(page["total"], 5);
+
+    let (_, _, page) = get(&app, "/public/announcements?limit=0").await;
+    assert_eq!(page["limit"], 1);
+    assert_eq!(titles(&page), ["P1"]);
+    let (_, _, page) = get(&app, "/public/announcements?limit=1000").await;
+    assert_eq!(page["limit"], 100);
+
+    let (status, _, _) = get(&app, "/public/announcements?offset=-1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn filters_by_type() {
+    let (pool, app, author) = harness().await;
+    seed_all(&pool, author).await;
+
+    let (status, _, page) = get(&app, "/public/announcements?type=News").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&page), ["P1", "P3", "P5"]);
+    assert_eq!(page["total"], 3);
+
+    let (_, _, page) = get(&app, "/public/announcements?type=News&limit=1&offset=1").await;
+    assert_eq!(titles(&page), ["P3"]);
+    assert_eq!(page["total"], 3);
+
+    let (_, _, page) = get(&app, "/public/announcements?type=CTFResult").await;
+    assert!(titles(&page).is_empty());
+    assert_eq!(page["total"], 0);
+
+    let (_, _, page) = get(&app, "/public/announcements?type=").await;
+    assert_eq!(page["total"], 5, "empty type means no filter");
+
+    let (status, _, body) = get(&app, "/public/announcements?type=Gossip").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Invalid announcement type: Gossip");
+}
@@ -0,0 +1,137 @@
+//! `GET /public/types`: active event, announcement and membership
+//! types for website styling, without auth, pricing, or inactive rows.
+//!
+//! Run with: cargo test --test public_types_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use coterie::domain::{
+    CreateBasicTypeRequest, CreateMembershipTypeRequest, UpdateBasicTypeRequest,
+    UpdateMembershipTypeRequest,
+};
+use serde_json::Value;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+fn basic(name: &str, slug: &str) -> CreateBasicTypeRequest {
+    CreateBasicTypeRequest {
+        name: name.to_string(),
+        slug: Some(slug.to_string()),
+        description: None,
+        color: Some("#ff6600".to_string()),
+        icon: Some("flag".to_string()),
+    }
+}
+
+fn slugs(list: &Value) -> Vec<&str> {
+    list.as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["slug"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn lists_active_types_and_omits_inactive_ones() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool).await;
+    let ctx = &state.service_context;
+
+    ctx.event_type_service
+        .create(None, basic("Hack Night", "hack-night"))
+        .await
+        .unwrap();
+    let retired = ctx
+        .event_type_service
+        .create(None, basic("Retired Event", "retired-event"))
+        .await
+        .unwrap();
+    ctx.event_type_service
+        .update(
+            None,
+            retired.id,
+            UpdateBasicTypeRequest {
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    ctx.announcement_type_service
+        .create(None, basic("Press", "press"))
+        .await
+        .unwrap();
+    let hidden = ctx
+        .membership_type_service
+        .create(None, CreateMembershipTypeRequest {
+            name: "Founders".to_string(),
+            slug: Some("founders".to_string()),
+            description: None,
+            color: Some("#000000".to_string()),
+            icon: None,
+            fee_cents: 99_900,
+            billing_period: "yearly".to_string(),
+            max_members: None,
+        })
+        .await
+        .unwrap();
+    ctx.membership_type_service
+        .update(
+            None,
+            hidden.id,
+            UpdateMembershipTypeRequest {
+                is_active: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    let app = coterie::api::create_app(state.clone());
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/public/types")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[header::CACHE_CONTROL], "public, max-age=300");
+    let body: Value =
+        serde_json::from_slice(&to_bytes(resp.into_body(), 1 << 20).await.unwrap()).unwrap();
+
+    let events = slugs(&body["event_types"]);
+    assert!(events.contains(&"hack-night"), "{:?}", events);
+    assert!(!events.contains(&"retired-event"), "{:?}", events);
+    assert!(slugs(&body["announcement_types"]).contains(&"press"));
+    let memberships = slugs(&body["membership_types"]);
+    assert!(!memberships.is_empty());
+    assert!(!memberships.contains(&"founders"), "{:?}", memberships);
+
+    let hack_night = body["event_types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|t| t["slug"] == "hack-night")
+        .unwrap();
+    assert_eq!(hack_night["name"], "Hack Night");
+    assert_eq!(hack_night["color"], "#ff6600");
+    assert_eq!(hack_night["icon"], "flag");
+    assert!(
+        hack_night.get("id").is_none(),
+        "no internals: {}",
+        hack_night
+    );
+    assert!(hack_night.get("is_active").is_none());
+
+    for t in body["membership_types"].as_array().unwrap() {
+        assert!(t.get("fee_cents").is_none(), "no pricing: {}", t);
+        assert!(t.get("billing_period").is_none());
+    }
+}
@@ -0,0 +1,89 @@
+//! Query-plan checks for the hot lookups. A missing or shadowed index
+//! doesn't fail anything functionally — the query just degrades to a
+//! full table scan — so these pin the plans SQLite picks against the
+//! migrated schema. The SQL mirrors the WHERE/ORDER BY shape of the
+//! repository queries (`find_by_email`, `list_upcoming`,
+//! `list_public`); update both together.
+//!
+//! Run with: cargo test --features test-utils --test query_plan_test
+
+use sqlx::SqlitePool;
+
+mod common;
+use common::fresh_pool;
+
+/// The `detail` column of `EXPLAIN QUERY PLAN`, one entry per step.
+async fn plan(pool: &SqlitePool, sql: &str, binds: usize) -> Vec<String> {
+    let explain = format!("EXPLAIN QUERY PLAN {}", sql);
+    let mut query = sqlx::query_as::<_, (i64, i64, i64, String)>(&explain);
+    for _ in 0..binds {
+        query = query.bind(Option::<String>::None);
+    }
+    query
+        .fetch_all(pool)
+        .await
+        .expect("explain query plan")
+        .into_iter()
+        .map(|(_, _, _, detail)| detail)
+        .collect()
+}
+
+fn assert_indexed(plan: &[String], table: &str, index: &str) {
+    assert!(
+        plan.iter()
+            .any(|d| d.starts_with(&format!("SEARCH {} USING", table)) && d.contains(index)),
+        "expected {} to be searched via {}, got {:?}",
+        table,
+        index,
+        plan,
+    );
+    assert!(
+        !plan
+            .iter()
+            .any(|d| d.starts_with(&format!("SCAN {}", table))),
+        "full scan of {}: {:?}",
+        table,
+        plan,
+    );
+}
+
+#[tokio::test]
+async fn member_by_email_uses_an_index() {
+    let pool = fresh_pool().await;
+    let plan = plan(&pool, "SELECT id FROM members WHERE email = ?", 1).await;
+    // The UNIQUE constraint's autoindex; any index on email will do.
+    assert_indexed(&plan, "members", "(email=?)");
+}
+
+#[tokio::test]
+async fn upcoming_events_use_an_index_without_sorting() {
+    let pool = fresh_pool().await;
+    let plan = plan(
+        &pool,
+        "SELECT id FROM events \
+         WHERE start_time > ? AND status = 'Published' \
+         ORDER BY start_time ASC LIMIT ?",
+        2,
+    )
+    .await;
+    assert_indexed(&plan, "events", "idx_events_status_start");
+    assert!(
+        !plan.iter().any(|d| d.contains("TEMP B-TREE")),
+        "upcoming events shouldn't need a sort: {:?}",
+        plan,
+    );
+}
+
+#[tokio::test]
+async fn public_upcoming_events_use_an_index() {
+    let pool = fresh_pool().await;
+    let plan = plan(
+        &pool,
+        "SELECT id FROM events \
+         WHERE visibility = ? AND start_time > ? AND status = 'Published' \
+         ORDER BY start_time ASC",
+        2,
+    )
+    .await;
+    assert_indexed(&plan, "events", "idx_events_status_visibility_start");
+}
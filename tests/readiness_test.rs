@@ -0,0 +1,138 @@
+//! Liveness vs readiness. `/health` is 200 whenever the process is up;
+//! `/ready` is 503 until the startup dependency check passes. Under
+//! `integrations.startup_policy = "fail"` a failing enabled
+//! integration keeps the instance not-ready; under `"warn"` it's
+//! reported but the instance goes ready anyway.
+//!
+//! The integration here is a stand-in whose health check fails until
+//! told otherwise.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use coterie::{
+    api::state::AppState,
+    config::IntegrationStartupPolicy,
+    error::{AppError, Result as CoterieResult},
+    integrations::{Integration, IntegrationEvent},
+    service::readiness_service::ReadinessService,
+};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+struct DoorController {
+    healthy: Arc<AtomicBool>,
+}
+
+#[async_trait]
+impl Integration for DoorController {
+    fn name(&self) -> &str {
+        "Unifi"
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    async fn health_check(&self) -> CoterieResult<()> {
+        if self.healthy.load(Ordering::SeqCst) {
+            Ok(())
+        } else {
+            Err(AppError::External("controller unreachable".to_string()))
+        }
+    }
+    async fn handle_event(&self, _event: &IntegrationEvent) -> CoterieResult<()> {
+        Ok(())
+    }
+}
+
+/// App state under `policy` with a registered integration that fails
+/// its health check until the returned flag is set.
+async fn state_with_failing_integration(
+    policy: IntegrationStartupPolicy,
+) -> (AppState, Arc<AtomicBool>) {
+    let mut state = build_app_state(fresh_pool().await).await;
+    let healthy = Arc::new(AtomicBool::new(false));
+    let ctx = state.service_context.clone();
+    ctx.integration_manager
+        .register(Arc::new(DoorController {
+            healthy: healthy.clone(),
+        }))
+        .await;
+    state.readiness_service = Arc::new(ReadinessService::new(
+        ctx.db_pool.clone(),
+        ctx.integration_manager.clone(),
+        policy,
+    ));
+    (state, healthy)
+}
+
+async fn get(state: &AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+    let resp = coterie::api::create_app(state.clone())
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn fail_policy_holds_readiness_until_integration_passes() {
+    let (state, healthy) = state_with_failing_integration(IntegrationStartupPolicy::Fail).await;
+
+    // Before any check has run: not ready, but alive.
+    assert_eq!(get(&state, "/ready").await.0, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(get(&state, "/health").await.0, StatusCode::OK);
+
+    assert!(!state.readiness_service.check().await.ready);
+    let (status, body) = get(&state, "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "not_ready");
+    let unifi = body["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == "Unifi")
+        .unwrap();
+    assert_eq!(unifi["critical"], true);
+    assert_eq!(unifi["healthy"], false);
+    assert!(unifi["error"].as_str().unwrap().contains("controller unreachable"));
+    assert_eq!(get(&state, "/health").await.0, StatusCode::OK);
+
+    // The retry that follows a fix flips it to ready.
+    healthy.store(true, Ordering::SeqCst);
+    assert!(state.readiness_service.check().await.ready);
+    let (status, body) = get(&state, "/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ready");
+
+    // Ready latches: a later failure is reported but doesn't un-ready.
+    healthy.store(false, Ordering::SeqCst);
+    assert!(state.readiness_service.check().await.ready);
+    assert_eq!(get(&state, "/ready").await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn warn_policy_goes_ready_despite_failing_integration() {
+    let (state, _healthy) = state_with_failing_integration(IntegrationStartupPolicy::Warn).await;
+    assert_eq!(get(&state, "/ready").await.0, StatusCode::SERVICE_UNAVAILABLE);
+
+    assert!(state.readiness_service.check().await.ready);
+    let (status, body) = get(&state, "/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    let checks = body["checks"].as_array().unwrap();
+    let db = checks.iter().find(|c| c["name"] == "database").unwrap();
+    assert_eq!(db["critical"], true);
+    assert_eq!(db["healthy"], true);
+    let unifi = checks.iter().find(|c| c["name"] == "Unifi").unwrap();
+    assert_eq!(unifi["critical"], false);
+    assert_eq!(unifi["healthy"], false);
+}
@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
 use coterie::{
-    domain::{CreateMemberRequest, Event, EventType, EventVisibility, Recurrence, WeekdayCode},
+    domain::{CreateMemberRequest, Event, EventType, Recurrence, WeekdayCode},
     repository::{
         EventRepository, EventSeriesRepository, MemberRepository, SqliteEventRepository,
         SqliteEventSeriesRepository, SqliteMemberRepository,
@@ -27,7 +27,7 @@ use sqlx::SqlitePool;
 use uuid::Uuid;
 
 mod common;
-use common::fresh_pool;
+use common::{event, fresh_pool};
 
 struct H {
     pool: SqlitePool,
@@ -135,23 +135,14 @@ async fn build() -> H {
 
 fn template(creator: Uuid, start: DateTime<Utc>) -> Event {
     Event {
-        id: Uuid::new_v4(),
         title: "Tuesday Coffee".to_string(),
         description: "Weekly hangout".to_string(),
         event_type: EventType::Social,
-        event_type_id: None,
-        visibility: EventVisibility::MembersOnly,
         start_time: start,
         end_time: Some(start + Duration::hours(2)),
         location: Some("HQ".to_string()),
         max_attendees: Some(20),
-        rsvp_required: true,
-        image_url: None,
-        created_by: creator,
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-        series_id: None,
-        occurrence_index: None,
+        ..event(creator)
     }
 }
 
@@ -15,10 +15,14 @@
 //! deleted ones now 404 — the regression net that says "yes, the
 //! route really got unregistered."
 //!
+//! The charge-saved endpoint is covered here too, for the payment
+//! lifecycle events it owes integrations when it settles a row.
+//!
 //! Run with: cargo test --features test-utils --test saved_card_routes_test
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use async_trait::async_trait;
 use axum::{
     body::{to_bytes, Body},
     http::{header, Request, StatusCode},
@@ -31,7 +35,8 @@ use coterie::{
     config::Settings,
     domain::{CreateMemberRequest, SavedCard},
     email::LogSender,
-    integrations::IntegrationManager,
+    error::{AppError, Result as CoterieResult},
+    integrations::{Integration, IntegrationEvent, IntegrationManager},
     payments::{fake_gateway::FakeStripeGateway, gateway::StripeGateway, StripeClient},
     repository::{
         AnnouncementRepository, EventRepository, MemberRepository, PaymentRepository,
@@ -47,6 +52,32 @@ use uuid::Uuid;
 mod common;
 use common::fresh_pool;
 
+// ---------------------------------------------------------------------
+// RecordingIntegration — captures every dispatched IntegrationEvent so
+// the charge tests can assert which payment events fired.
+// ---------------------------------------------------------------------
+
+struct RecordingIntegration {
+    events: Arc<Mutex<Vec<IntegrationEvent>>>,
+}
+
+#[async_trait]
+impl Integration for RecordingIntegration {
+    fn name(&self) -> &str {
+        "test-recording"
+    }
+    fn is_enabled(&self) -> bool {
+        true
+    }
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+    async fn handle_event(&self, event: &IntegrationEvent) -> CoterieResult<()> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------
 // Harness
 // ---------------------------------------------------------------------
@@ -64,6 +95,7 @@ struct Harness {
     member_id: Uuid,
     session_cookie: String,
     csrf_token: String,
+    recorded_events: Arc<Mutex<Vec<IntegrationEvent>>>,
 }
 
 /// Build the merged app exactly the way `main.rs` does — both the
@@ -79,9 +111,11 @@ async fn build_harness() -> Harness {
             base_url: "http://127.0.0.1".to_string(),
             data_dir: "./data".to_string(),
             uploads_dir: None,
+            backups_dir: None,
             secure_cookies: Some(false),
             cors_origins: None,
             trust_forwarded_for: Some(false),
+            max_in_flight_requests: 0,
         },
         database: coterie::config::DatabaseConfig {
             url: "sqlite::memory:".to_string(),
@@ -90,12 +124,20 @@ async fn build_harness() -> Harness {
         auth: coterie::config::AuthConfig {
             session_secret: "test-session-secret-please-ignore".to_string(),
             session_duration_hours: 24,
+            session_idle_timeout_minutes: None,
             totp_issuer: "Coterie Test".to_string(),
+            csrf_token_ttl_hours: 12,
+            geoip_db_path: None,
+            session_store: Default::default(),
+            redis_url: None,
+            login_rate_limit: Default::default(),
         },
         stripe: Default::default(),
         integrations: Default::default(),
         seed: Default::default(),
         bot_challenge: Default::default(),
+        images: Default::default(),
+        security_headers: Default::default(),
     };
     let settings = Arc::new(settings);
 
@@ -128,6 +170,12 @@ async fn build_harness() -> Harness {
         "Test".to_string(),
     ));
     let integration_manager = Arc::new(IntegrationManager::new());
+    let recorded_events: Arc<Mutex<Vec<IntegrationEvent>>> = Arc::new(Mutex::new(Vec::new()));
+    integration_manager
+        .register(Arc::new(RecordingIntegration {
+            events: recorded_events.clone(),
+        }))
+        .await;
 
     let money_limiter = MoneyLimiter(RateLimiter::new(10, std::time::Duration::from_secs(60)));
 
@@ -235,6 +283,7 @@ async fn build_harness() -> Harness {
         member_id: created.id,
         session_cookie,
         csrf_token,
+        recorded_events,
     }
 }
 
@@ -535,10 +584,80 @@ async fn html_set_default_endpoint_works() {
     );
 }
 
+#[tokio::test]
+async fn charge_saved_card_announces_completed_payment() {
+    // The sync path usually wins the Pending → Completed flip, and the
+    // payment_intent.succeeded webhook then backs off without a word —
+    // so the charge handler itself must tell integrations.
+    let h = build_harness().await;
+    let card = seed_card(&h, "pm_charge", true).await;
+
+    let resp = charge_saved(&h, card.id).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let completed = payment_events(&h)
+        .into_iter()
+        .filter(|e| matches!(e, IntegrationEvent::PaymentCompleted(_)))
+        .count();
+    assert_eq!(completed, 1, "the winning flip announces the payment once");
+}
+
+#[tokio::test]
+async fn declined_saved_card_announces_failed_payment() {
+    let h = build_harness().await;
+    let card = seed_card(&h, "pm_declined", true).await;
+    h.fake
+        .next_payment_intent_err(AppError::External("card_declined".to_string()));
+
+    let resp = charge_saved(&h, card.id).await;
+    assert!(!resp.status().is_success());
+
+    let events = payment_events(&h);
+    assert_eq!(events.len(), 1, "expected only PaymentFailed, got {:?}", events);
+    match &events[0] {
+        IntegrationEvent::PaymentFailed(p) => {
+            assert_eq!(p.status, coterie::domain::PaymentStatus::Failed);
+        }
+        other => panic!("expected PaymentFailed, got {:?}", other),
+    }
+}
+
 // ---------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------
 
+async fn charge_saved(h: &Harness, card_id: Uuid) -> axum::response::Response {
+    let body = serde_json::json!({
+        "membership_type_slug": "member",
+        "saved_card_id": card_id.to_string(),
+    })
+    .to_string();
+    let req = auth_request(
+        h,
+        "POST",
+        "/portal/api/payments/charge-saved",
+        Body::from(body),
+        Some("application/json"),
+    );
+    h.app.clone().oneshot(req).await.unwrap()
+}
+
+/// Payment lifecycle events recorded so far, in dispatch order.
+fn payment_events(h: &Harness) -> Vec<IntegrationEvent> {
+    h.recorded_events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| {
+            matches!(
+                e,
+                IntegrationEvent::PaymentCompleted(_) | IntegrationEvent::PaymentFailed(_)
+            )
+        })
+        .cloned()
+        .collect()
+}
+
 async fn seed_card(h: &Harness, pm_id: &str, is_default: bool) -> SavedCard {
     let now = Utc::now();
     h.saved_card_repo
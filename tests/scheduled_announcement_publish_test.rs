@@ -137,6 +137,10 @@ async fn seed_announcement(
         image_url: None,
         published_at,
         scheduled_publish_at,
+        expires_at: None,
+        comments_enabled: false,
+        notify_members: None,
+        notified_at: None,
         created_by: h.actor,
         created_at: now,
         updated_at: now,
@@ -0,0 +1,127 @@
+//! Security headers on rendered pages: the CSP carries the same nonce
+//! the page's scripts were stamped with, the baseline headers are
+//! present, and `[security_headers]` switches the policy to
+//! report-only, drops the CDN allowance and controls HSTS.
+//!
+//! Run with: cargo test --features test-utils --test security_headers_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, HeaderMap, Request, StatusCode},
+};
+use coterie::{api::state::AppState, config::SecurityHeadersConfig};
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool, merged_routes};
+
+/// The app as `main.rs` layers it, minus CSRF and the setup redirect.
+async fn get_login(
+    security_headers: SecurityHeadersConfig,
+    secure_cookies: bool,
+) -> (HeaderMap, String) {
+    let mut state: AppState = build_app_state(fresh_pool().await).await;
+    let mut settings = (*state.settings).clone();
+    settings.security_headers = security_headers;
+    settings.server.secure_cookies = Some(secure_cookies);
+    state.settings = Arc::new(settings);
+
+    let app = merged_routes(state.clone()).layer(axum::middleware::from_fn_with_state(
+        state,
+        coterie::api::middleware::security_headers::security_headers,
+    ));
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/login")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let headers = resp.headers().clone();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (headers, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers
+        .get(name)
+        .unwrap_or_else(|| panic!("missing {name}"))
+        .to_str()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn rendered_page_carries_the_security_headers() {
+    let (headers, html) = get_login(SecurityHeadersConfig::default(), false).await;
+
+    let csp = header_str(&headers, "content-security-policy");
+    assert!(csp.contains("frame-ancestors 'none'"), "{csp}");
+    assert!(
+        csp.contains("https://unpkg.com"),
+        "default CDN allowance: {csp}"
+    );
+    assert!(!headers.contains_key("content-security-policy-report-only"));
+
+    // The nonce in the header is the one stamped into the page.
+    let nonce = csp
+        .split("'nonce-")
+        .nth(1)
+        .and_then(|rest| rest.split('\'').next())
+        .unwrap();
+    assert!(html.contains(&format!("nonce=\"{nonce}\"")));
+    assert!(!html.contains("__CSP_NONCE__"));
+
+    assert_eq!(header_str(&headers, "x-content-type-options"), "nosniff");
+    assert_eq!(header_str(&headers, "x-frame-options"), "DENY");
+    assert_eq!(
+        header_str(&headers, "referrer-policy"),
+        "strict-origin-when-cross-origin"
+    );
+    assert!(
+        !headers.contains_key(header::STRICT_TRANSPORT_SECURITY),
+        "no HSTS over plain http"
+    );
+}
+
+#[tokio::test]
+async fn report_only_mode_and_self_hosted_assets() {
+    let config = SecurityHeadersConfig {
+        csp_report_only: true,
+        csp_report_uri: Some("https://reports.example.org/csp".to_string()),
+        cdn_sources: String::new(),
+        frame_ancestors: "'self'".to_string(),
+        ..Default::default()
+    };
+    let (headers, _) = get_login(config, false).await;
+
+    assert!(!headers.contains_key("content-security-policy"));
+    let csp = header_str(&headers, "content-security-policy-report-only");
+    assert!(
+        csp.ends_with("; report-uri https://reports.example.org/csp"),
+        "{csp}"
+    );
+    assert!(!csp.contains("unpkg"), "{csp}");
+    assert!(csp.contains("frame-ancestors 'self'"), "{csp}");
+    assert_eq!(header_str(&headers, "x-frame-options"), "SAMEORIGIN");
+}
+
+#[tokio::test]
+async fn hsts_sent_only_when_secure_and_enabled() {
+    let (headers, _) = get_login(SecurityHeadersConfig::default(), true).await;
+    assert_eq!(
+        header_str(&headers, "strict-transport-security"),
+        "max-age=31536000; includeSubDomains"
+    );
+
+    let config = SecurityHeadersConfig {
+        hsts_max_age_secs: 0,
+        ..Default::default()
+    };
+    let (headers, _) = get_login(config, true).await;
+    assert!(!headers.contains_key(header::STRICT_TRANSPORT_SECURITY));
+}
@@ -0,0 +1,108 @@
+//! The session cookie lives as long as the server-side session:
+//! `auth.session_duration_hours` sets both, so a long configured
+//! session doesn't lose its cookie after a day.
+//!
+//! Run with: cargo test --features test-utils --test session_cookie_test
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request, StatusCode},
+};
+use coterie::{
+    auth::AuthService,
+    domain::{CreateMemberRequest, MemberStatus, UpdateMemberRequest},
+    repository::{MemberRepository, SqliteMemberRepository},
+};
+use serde_json::json;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+const PASSWORD: &str = "Correct-horse-battery-9";
+const WEEK_HOURS: i64 = 168;
+
+#[tokio::test]
+async fn cookie_max_age_is_the_duration_given() {
+    let pool = fresh_pool().await;
+    let auth = AuthService::new(pool, "unused".to_string());
+    let cookie = auth.create_session_cookie("token", WEEK_HOURS, false);
+    assert_eq!(
+        cookie.max_age(),
+        Some(cookie::time::Duration::hours(WEEK_HOURS))
+    );
+}
+
+#[tokio::test]
+async fn login_cookie_and_session_follow_the_configured_duration() {
+    let pool = fresh_pool().await;
+    let repo = SqliteMemberRepository::new(pool.clone());
+    let member = repo
+        .create(CreateMemberRequest {
+            email: "week@example.com".to_string(),
+            username: "week".to_string(),
+            full_name: "Week Long".to_string(),
+            password: PASSWORD.to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    repo.update(
+        member.id,
+        UpdateMemberRequest {
+            status: Some(MemberStatus::Active),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut state = build_app_state(pool.clone()).await;
+    let mut settings = (*state.settings).clone();
+    settings.auth.session_duration_hours = WEEK_HOURS;
+    state.settings = Arc::new(settings);
+    let app = coterie::web::create_web_routes(state);
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/login")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({"username": "week", "password": PASSWORD}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let set_cookie = resp
+        .headers()
+        .get(header::SET_COOKIE)
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(
+        set_cookie.contains(&format!("Max-Age={}", WEEK_HOURS * 3600)),
+        "{}",
+        set_cookie
+    );
+
+    let expires_in_hours: f64 = sqlx::query_scalar(
+        "SELECT (julianday(expires_at) - julianday('now')) * 24 \
+         FROM sessions WHERE member_id = ?",
+    )
+    .bind(member.id.to_string())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(
+        (expires_in_hours - WEEK_HOURS as f64).abs() < 1.0,
+        "session expires in {} hours",
+        expires_in_hours
+    );
+}
@@ -0,0 +1,120 @@
+//! Session idle timeout (`auth.session_idle_timeout_minutes`): a
+//! session with no requests for longer than the timeout stops
+//! validating even though `expires_at` is still in the future, while
+//! one that keeps being used stays logged in. Without a timeout only
+//! the absolute expiry applies.
+//!
+//! Run with: cargo test --test session_idle_timeout_test
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use coterie::auth::{session::SqliteSessionStore, AuthService};
+use sqlx::SqlitePool;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+async fn set_last_used(pool: &SqlitePool, session_id: &str, ago: Duration) {
+    sqlx::query("UPDATE sessions SET last_used_at = ? WHERE id = ?")
+        .bind((Utc::now() - ago).naive_utc())
+        .bind(session_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+fn idle_auth(pool: &SqlitePool) -> AuthService {
+    AuthService::new(pool.clone(), "unused".to_string()).with_session_store(Arc::new(
+        SqliteSessionStore::new(pool.clone()).with_idle_timeout(Duration::minutes(30)),
+    ))
+}
+
+#[tokio::test]
+async fn idle_session_is_rejected_and_active_one_is_kept() {
+    let pool = fresh_pool().await;
+    let auth = idle_auth(&pool);
+
+    let idle_member = make_member(&pool).await;
+    let (idle, idle_token) = auth.create_session(idle_member, 24).await.unwrap();
+    set_last_used(&pool, &idle.id, Duration::minutes(31)).await;
+
+    let active_member = make_member(&pool).await;
+    let (active, active_token) = auth.create_session(active_member, 24).await.unwrap();
+    set_last_used(&pool, &active.id, Duration::minutes(29)).await;
+
+    assert!(
+        auth.validate_session(&idle_token).await.unwrap().is_none(),
+        "idle past the timeout"
+    );
+    let session = auth
+        .validate_session(&active_token)
+        .await
+        .unwrap()
+        .expect("recently used session is still valid");
+    assert_eq!(session.member_id, active_member);
+
+    // That request counted as activity, so the clock restarts.
+    set_last_used(&pool, &active.id, Duration::minutes(29)).await;
+    assert!(auth
+        .validate_session(&active_token)
+        .await
+        .unwrap()
+        .is_some());
+    let last_used: chrono::NaiveDateTime =
+        sqlx::query_scalar("SELECT last_used_at FROM sessions WHERE id = ?")
+            .bind(&active.id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert!(Utc::now().naive_utc() - last_used < Duration::minutes(1));
+
+    assert_eq!(auth.cleanup_expired_sessions().await.unwrap(), 1);
+    let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM sessions")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec![active.id]);
+}
+
+#[tokio::test]
+async fn without_idle_timeout_only_absolute_expiry_applies() {
+    let pool = fresh_pool().await;
+    let auth = AuthService::new(pool.clone(), "unused".to_string());
+    let member_id = make_member(&pool).await;
+
+    let (session, token) = auth.create_session(member_id, 24).await.unwrap();
+    set_last_used(&pool, &session.id, Duration::hours(20)).await;
+    assert!(auth.validate_session(&token).await.unwrap().is_some());
+
+    let (_, expired_token) = auth.create_session(member_id, -1).await.unwrap();
+    assert!(auth
+        .validate_session(&expired_token)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn recent_activity_is_not_rewritten_on_every_request() {
+    let pool = fresh_pool().await;
+    let auth = idle_auth(&pool);
+    let member_id = make_member(&pool).await;
+
+    let (session, token) = auth.create_session(member_id, 24).await.unwrap();
+    set_last_used(&pool, &session.id, Duration::seconds(10)).await;
+    let before: String = sqlx::query_scalar("SELECT last_used_at FROM sessions WHERE id = ?")
+        .bind(&session.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    assert!(auth.validate_session(&token).await.unwrap().is_some());
+
+    let after: String = sqlx::query_scalar("SELECT last_used_at FROM sessions WHERE id = ?")
+        .bind(&session.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(before, after, "write throttled within the minute");
+}
@@ -0,0 +1,119 @@
+//! `SessionStore` contract: create → find → delete, absolute expiry,
+//! idle timeout and "log out everywhere", run against each backend.
+//! SQLite always runs; Redis runs when `COTERIE_TEST_REDIS_URL` points
+//! at a disposable server (e.g. `redis://127.0.0.1:6379/15`) and is
+//! skipped otherwise.
+//!
+//! Run with: cargo test --test session_store_test
+
+use chrono::{Duration, Utc};
+use coterie::auth::{
+    redis_session::RedisSessionStore,
+    session::{SessionStore, SqliteSessionStore},
+};
+use uuid::Uuid;
+
+mod common;
+use common::{fresh_pool, make_member};
+
+fn token() -> String {
+    format!("test-token-{}", Uuid::new_v4())
+}
+
+async fn create_find_delete(store: &dyn SessionStore, member_id: Uuid) {
+    let t = token();
+    let created = store
+        .create(member_id, &t, Utc::now() + Duration::hours(1))
+        .await
+        .unwrap();
+    assert_ne!(created.token_hash, t, "only the hash is kept");
+
+    let found = store.find_by_token(&t).await.unwrap().expect("live session");
+    assert_eq!(found.id, created.id);
+    assert_eq!(found.member_id, member_id);
+    assert!(store.find_by_token("not-a-token").await.unwrap().is_none());
+
+    store.delete_by_token(&t).await.unwrap();
+    assert!(store.find_by_token(&t).await.unwrap().is_none());
+}
+
+async fn expired_is_not_found(store: &dyn SessionStore, member_id: Uuid) {
+    let t = token();
+    store
+        .create(member_id, &t, Utc::now() - Duration::minutes(1))
+        .await
+        .unwrap();
+    assert!(store.find_by_token(&t).await.unwrap().is_none());
+}
+
+async fn delete_by_member_ends_every_session(
+    store: &dyn SessionStore,
+    member_id: Uuid,
+    other: Uuid,
+) {
+    let (a, b, kept) = (token(), token(), token());
+    let expires = Utc::now() + Duration::hours(1);
+    store.create(member_id, &a, expires).await.unwrap();
+    store
+        .create(member_id, &b, Utc::now() + Duration::days(30))
+        .await
+        .unwrap();
+    store.create(other, &kept, expires).await.unwrap();
+
+    store.delete_by_member(member_id).await.unwrap();
+    assert!(store.find_by_token(&a).await.unwrap().is_none());
+    assert!(store.find_by_token(&b).await.unwrap().is_none());
+    assert!(store.find_by_token(&kept).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn sqlite_store_contract() {
+    let pool = fresh_pool().await;
+    let store = SqliteSessionStore::new(pool.clone());
+    let member = make_member(&pool).await;
+    let other = make_member(&pool).await;
+
+    create_find_delete(&store, member).await;
+    expired_is_not_found(&store, member).await;
+    delete_by_member_ends_every_session(&store, member, other).await;
+
+    // The expired row from above is still on disk until cleanup.
+    assert_eq!(store.cleanup_expired().await.unwrap(), 1);
+}
+
+async fn redis_store() -> Option<RedisSessionStore> {
+    let url = std::env::var("COTERIE_TEST_REDIS_URL").ok()?;
+    Some(RedisSessionStore::connect(&url).await.expect("connect to test Redis"))
+}
+
+#[tokio::test]
+async fn redis_store_contract() {
+    let Some(store) = redis_store().await else {
+        eprintln!("COTERIE_TEST_REDIS_URL unset; skipping Redis session store test");
+        return;
+    };
+    // Redis has no foreign keys, so members needn't exist.
+    let (member, other) = (Uuid::new_v4(), Uuid::new_v4());
+
+    create_find_delete(&store, member).await;
+    expired_is_not_found(&store, member).await;
+    delete_by_member_ends_every_session(&store, member, other).await;
+    store.delete_by_member(other).await.unwrap();
+}
+
+#[tokio::test]
+async fn redis_session_expires_with_its_ttl() {
+    let Some(store) = redis_store().await else {
+        return;
+    };
+    let t = token();
+    store
+        .create(Uuid::new_v4(), &t, Utc::now() + Duration::milliseconds(300))
+        .await
+        .unwrap();
+    assert!(store.find_by_token(&t).await.unwrap().is_some());
+
+    tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+    assert!(store.find_by_token(&t).await.unwrap().is_none());
+    assert!(store.cleanup_expired().await.unwrap() >= 1, "index entry pruned");
+}
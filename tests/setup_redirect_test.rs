@@ -297,3 +297,112 @@ async fn wizard_post_arms_cache_for_next_request() {
         location
     );
 }
+
+// ---------------------------------------------------------------------
+// Section 6: the wizard is one-shot.
+// ---------------------------------------------------------------------
+
+fn setup_post(body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/setup")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+fn setup_get() -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri("/setup")
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// On an empty database `/setup` shows the form and creates an admin
+/// (opting into required two-factor when asked); once that admin
+/// exists the page redirects to `/login` and a second POST is refused.
+#[tokio::test]
+async fn wizard_creates_first_admin_then_disables_itself() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let app = router_full(state.clone());
+
+    let resp = app.clone().oneshot(setup_get()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK, "empty DB should show the wizard");
+
+    let resp = app
+        .clone()
+        .oneshot(setup_post(serde_json::json!({
+            "org_name": "Test Org",
+            "email": "first@example.com",
+            "username": "first",
+            "full_name": "First Admin",
+            "password": "WizardPass1",
+            "password_confirm": "WizardPass1",
+            "require_totp": "on",
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let admins: Vec<(String, i64)> =
+        sqlx::query_as("SELECT email, is_admin FROM members")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(admins, vec![("first@example.com".to_string(), 1)]);
+    let require_totp = state
+        .service_context
+        .settings_service
+        .get_setting("auth.require_totp_for_admins")
+        .await
+        .unwrap();
+    assert_eq!(require_totp.value, "true");
+
+    let resp = app.clone().oneshot(setup_get()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        resp.headers()
+            .get(header::LOCATION)
+            .and_then(|v| v.to_str().ok()),
+        Some("/login")
+    );
+
+    let resp = app
+        .oneshot(setup_post(serde_json::json!({
+            "org_name": "Hijack",
+            "email": "second@example.com",
+            "username": "second",
+            "full_name": "Second Admin",
+            "password": "WizardPass2",
+            "password_confirm": "WizardPass2",
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM members")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1, "a second setup must not create another member");
+}
+
+/// An instance brought up with an admin already in place never shows
+/// the wizard, even before any request has armed the cache.
+#[tokio::test]
+async fn wizard_redirects_when_admin_already_exists() {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool).await;
+    seed_admin(&state).await;
+    let app = router_full(state);
+
+    let resp = app.oneshot(setup_get()).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::SEE_OTHER);
+    assert_eq!(
+        resp.headers()
+            .get(header::LOCATION)
+            .and_then(|v| v.to_str().ok()),
+        Some("/login")
+    );
+}
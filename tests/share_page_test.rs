@@ -0,0 +1,150 @@
+//! Public share pages (`/events/:id`, `/announcements/:id`): Open Graph
+//! and Twitter Card tags for link previews, the org default image when
+//! the item has none, and 404 for anything not public.
+//!
+//! Run with: cargo test --test share_page_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use chrono::{Duration, Utc};
+use coterie::{
+    domain::{Announcement, AnnouncementType, Event, EventVisibility},
+    repository::{
+        AnnouncementRepository, EventRepository, SqliteAnnouncementRepository,
+        SqliteEventRepository,
+    },
+};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+mod common;
+use common::{build_app, event, fresh_pool, make_member};
+
+async fn setup() -> (SqlitePool, Router) {
+    let pool = fresh_pool().await;
+    let app = build_app(&pool).await;
+    (pool, app)
+}
+
+async fn create_event(
+    pool: &SqlitePool,
+    visibility: EventVisibility,
+    image_url: Option<&str>,
+) -> Event {
+    SqliteEventRepository::new(pool.clone())
+        .create(Event {
+            title: "Lockpicking Night".to_string(),
+            description: "Bring your **own** picks.\n\nBeginners welcome.".to_string(),
+            visibility,
+            location: Some("The Hackspace".to_string()),
+            rsvp_required: false,
+            image_url: image_url.map(str::to_string),
+            ..event(make_member(pool).await)
+        })
+        .await
+        .unwrap()
+}
+
+async fn create_announcement(pool: &SqlitePool, is_public: bool) -> Announcement {
+    let published = Utc::now() - Duration::days(1);
+    SqliteAnnouncementRepository::new(pool.clone())
+        .create(Announcement {
+            id: Uuid::new_v4(),
+            title: "New Space Open".to_string(),
+            content: "We moved!".to_string(),
+            announcement_type: AnnouncementType::General,
+            announcement_type_id: None,
+            is_public,
+            featured: false,
+            image_url: None,
+            published_at: Some(published),
+            scheduled_publish_at: None,
+            expires_at: None,
+            comments_enabled: false,
+            notify_members: None,
+            notified_at: None,
+            created_by: make_member(pool).await,
+            created_at: published,
+            updated_at: published,
+        })
+        .await
+        .unwrap()
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+    let resp = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn public_event_page_has_preview_tags() {
+    let (pool, app) = setup().await;
+    let event = create_event(&pool, EventVisibility::Public, Some("/uploads/picks.png")).await;
+
+    let (status, body) = get(&app, &format!("/events/{}", event.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        body.contains(r#"<meta property="og:title" content="Lockpicking Night">"#),
+        "{}",
+        body
+    );
+    assert!(
+        body.contains(r#"<meta property="og:image" content="http://127.0.0.1/uploads/picks.png">"#),
+        "image made absolute: {}",
+        body
+    );
+    assert!(body.contains(r#"<meta name="twitter:card" content="summary_large_image">"#));
+    assert!(
+        body.contains(
+            r#"<meta property="og:description" content="Bring your own picks. Beginners welcome.">"#
+        ),
+        "plain-text summary: {}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn announcement_without_image_uses_org_default() {
+    let (pool, app) = setup().await;
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'org.share_image_url'")
+        .bind("https://cdn.example.com/card.png")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let announcement = create_announcement(&pool, true).await;
+
+    let (status, body) = get(&app, &format!("/announcements/{}", announcement.id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains(r#"<meta property="og:title" content="New Space Open">"#));
+    assert!(
+        body.contains(r#"<meta property="og:image" content="https://cdn.example.com/card.png">"#),
+        "{}",
+        body
+    );
+}
+
+#[tokio::test]
+async fn non_public_items_are_not_found() {
+    let (pool, app) = setup().await;
+    let members_only = create_announcement(&pool, false).await;
+    let admin_only = create_event(&pool, EventVisibility::AdminOnly, None).await;
+
+    for uri in [
+        format!("/announcements/{}", members_only.id),
+        format!("/events/{}", admin_only.id),
+        format!("/events/{}", Uuid::new_v4()),
+    ] {
+        let (status, _) = get(&app, &uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+    }
+}
@@ -0,0 +1,154 @@
+//! Admin-configured signup fields on `/public/signup`. Fields listed in
+//! `membership.signup_fields` are offered on `GET /public/signup/fields`;
+//! leaving a required one blank gets a 422 naming the field, and a
+//! complete signup stores profile answers on `member_profiles` and the
+//! rest in `member_custom_fields`.
+//!
+//! Run with: cargo test --features test-utils --test signup_fields_test
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Method, Request, StatusCode},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+const FIELDS: &str = r#"[
+    {"key": "emergency_contact", "label": "Emergency contact", "required": true},
+    {"key": "github_username", "label": "GitHub username"},
+    {"key": "blog_url", "label": "Website", "kind": "url"}
+]"#;
+
+async fn harness() -> (SqlitePool, Router) {
+    let pool = fresh_pool().await;
+    sqlx::query("UPDATE app_settings SET value = ? WHERE key = 'membership.signup_fields'")
+        .bind(FIELDS)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let state = build_app_state(pool.clone()).await;
+    (pool, coterie::api::create_app(state))
+}
+
+async fn call(app: &Router, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+    let mut req = Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(json) => {
+            req = req.header(header::CONTENT_TYPE, "application/json");
+            Body::from(json.to_string())
+        }
+        None => Body::empty(),
+    };
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+fn signup_body(username: &str, fields: Value) -> Value {
+    json!({
+        "email": format!("{}@example.com", username),
+        "username": username,
+        "full_name": "New Member",
+        "password": "Correct-horse-battery-9",
+        "fields": fields,
+    })
+}
+
+async fn member_count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM members")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn form_lists_configured_fields() {
+    let (_pool, app) = harness().await;
+
+    let (status, body) = call(&app, Method::GET, "/public/signup/fields", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let fields = body["fields"].as_array().unwrap();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[0]["key"], "emergency_contact");
+    assert_eq!(fields[0]["required"], true);
+    assert_eq!(fields[1]["required"], false);
+    assert_eq!(fields[2]["kind"], "url");
+}
+
+#[tokio::test]
+async fn signup_missing_required_custom_field_is_rejected() {
+    let (pool, app) = harness().await;
+    let before = member_count(&pool).await;
+
+    let (status, body) = call(
+        &app,
+        Method::POST,
+        "/public/signup",
+        Some(signup_body(
+            "no_contact",
+            json!({ "emergency_contact": "   ", "blog_url": "example.com" }),
+        )),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{}", body);
+    assert_eq!(
+        body["fields"]["emergency_contact"],
+        "Emergency contact is required"
+    );
+    assert!(body["fields"]["blog_url"].is_string(), "{}", body);
+    assert!(
+        body["fields"]["github_username"].is_null(),
+        "optional: {}",
+        body
+    );
+    assert_eq!(member_count(&pool).await, before, "no member created");
+}
+
+#[tokio::test]
+async fn signup_with_required_field_succeeds_and_stores_answers() {
+    let (pool, app) = harness().await;
+
+    let (status, body) = call(
+        &app,
+        Method::POST,
+        "/public/signup",
+        Some(signup_body(
+            "with_contact",
+            json!({
+                "emergency_contact": " Sam, 555-0100 ",
+                "github_username": "withcontact",
+                "not_configured": "ignored",
+            }),
+        )),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    let member_id = body["member_id"].as_str().unwrap().to_string();
+
+    let custom: Vec<(String, String)> = sqlx::query_as(
+        "SELECT field_key, value FROM member_custom_fields WHERE member_id = ? ORDER BY field_key",
+    )
+    .bind(&member_id)
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        custom,
+        vec![("emergency_contact".to_string(), "Sam, 555-0100".to_string())]
+    );
+
+    let github: Option<String> =
+        sqlx::query_scalar("SELECT github_username FROM member_profiles WHERE member_id = ?")
+            .bind(&member_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(github.as_deref(), Some("withcontact"));
+}
@@ -0,0 +1,154 @@
+//! Post-signup payment handoff on `/public/signup`. With Stripe
+//! unconfigured (the test AppState has no client), a signup for a paid
+//! membership type gets the org's offline-payment instructions back and
+//! stays Pending; turning off `membership.require_payment_for_activation`
+//! restores the plain signup response. The admins hear about an
+//! offline payer once, in the signup notification. The Stripe path —
+//! Checkout completion activating the member — is covered in
+//! stripe_webhook_test.
+//!
+//! Run with: cargo test --features test-utils --test signup_payment_handoff_test
+
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use coterie::{
+    error::Result as CoterieResult,
+    integrations::{Integration, IntegrationEvent},
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+
+mod common;
+use common::{build_app_state, fresh_pool};
+
+/// Keeps every admin-facing event dispatched during the test.
+#[derive(Default)]
+struct Recorder {
+    events: Mutex<Vec<IntegrationEvent>>,
+}
+
+#[async_trait]
+impl Integration for Recorder {
+    fn name(&self) -> &str {
+        "Recorder"
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    async fn health_check(&self) -> CoterieResult<()> {
+        Ok(())
+    }
+
+    async fn handle_event(&self, event: &IntegrationEvent) -> CoterieResult<()> {
+        if matches!(
+            event,
+            IntegrationEvent::AdminAlert { .. } | IntegrationEvent::AdminNotification { .. }
+        ) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+        Ok(())
+    }
+}
+
+async fn harness() -> (SqlitePool, Router) {
+    let (pool, app, _) = recorded_harness().await;
+    (pool, app)
+}
+
+async fn recorded_harness() -> (SqlitePool, Router, Arc<Recorder>) {
+    let pool = fresh_pool().await;
+    let state = build_app_state(pool.clone()).await;
+    let recorder = Arc::new(Recorder::default());
+    state
+        .service_context
+        .integration_manager
+        .register(recorder.clone())
+        .await;
+    (pool, coterie::api::create_app(state), recorder)
+}
+
+async fn signup(app: &Router, username: &str) -> (StatusCode, Value) {
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/public/signup")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(
+                    json!({
+                        "email": format!("{}@example.com", username),
+                        "username": username,
+                        "full_name": "New Member",
+                        "password": "Correct-horse-battery-9",
+                        "membership_type_slug": "member",
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let body = to_bytes(resp.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn signup_without_stripe_returns_offline_instructions() {
+    let (pool, app, recorder) = recorded_harness().await;
+    sqlx::query(
+        "UPDATE app_settings SET value = 'Bring cash to the next meeting.' \
+         WHERE key = 'membership.offline_payment_instructions'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) = signup(&app, "offline_payer").await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert_eq!(body["status"], "Pending");
+    assert!(body["checkout_url"].is_null(), "{}", body);
+    assert_eq!(
+        body["payment_instructions"],
+        "Bring cash to the next meeting."
+    );
+
+    // One notification, routed per admin, flags the offline payment;
+    // no separate org-wide alert.
+    let events = recorder.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1, "{:?}", events);
+    match &events[0] {
+        IntegrationEvent::AdminNotification { subject, body, .. } => {
+            assert_eq!(subject, "New signup: New Member");
+            assert!(body.contains("offline payment instructions"), "{}", body);
+        }
+        other => panic!("expected AdminNotification, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn signup_skips_handoff_when_payment_not_required() {
+    let (pool, app) = harness().await;
+    sqlx::query(
+        "UPDATE app_settings SET value = 'false' \
+         WHERE key = 'membership.require_payment_for_activation'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) = signup(&app, "no_payment_needed").await;
+    assert_eq!(status, StatusCode::CREATED, "{}", body);
+    assert!(body["checkout_url"].is_null(), "{}", body);
+    assert!(body["payment_instructions"].is_null(), "{}", body);
+}
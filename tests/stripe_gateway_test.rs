@@ -109,6 +109,7 @@ async fn charge_saved_card_happy_path() {
             "Annual dues",
             "idem-key-1",
             payment_id,
+            Some("member"),
         )
         .await;
 
@@ -124,7 +125,8 @@ async fn charge_saved_card_happy_path() {
             assert_eq!(input.idempotency_key, "idem-key-1");
             assert_eq!(input.description, "Annual dues");
             // Metadata carries the member_id + payment_id so the
-            // PI.succeeded webhook can resolve the local row.
+            // PI.succeeded webhook can resolve the local row, and the
+            // slug so it extends dues by the type that was paid for.
             assert_eq!(
                 input.metadata.get("member_id").unwrap(),
                 &member_id.to_string()
@@ -133,6 +135,13 @@ async fn charge_saved_card_happy_path() {
                 input.metadata.get("payment_id").unwrap(),
                 &payment_id.to_string()
             );
+            assert_eq!(
+                input
+                    .metadata
+                    .get("membership_type_slug")
+                    .map(String::as_str),
+                Some("member")
+            );
         }
         other => panic!("expected CreatePaymentIntent, got {:?}", other),
     }
@@ -156,6 +165,7 @@ async fn charge_saved_card_requires_action_returns_external_error() {
             "Annual dues",
             "idem-key-2",
             Uuid::new_v4(),
+            None,
         )
         .await
         .expect_err("must surface RequiresAction as error");
@@ -187,7 +197,15 @@ async fn charge_saved_card_member_without_customer_bails_before_stripe() {
 
     let (client, fake) = build_client_with_fake(pool);
     let err = client
-        .charge_saved_card(member.id, "pm_card_visa", 100, "x", "ikey", Uuid::new_v4())
+        .charge_saved_card(
+            member.id,
+            "pm_card_visa",
+            100,
+            "x",
+            "ikey",
+            Uuid::new_v4(),
+            None,
+        )
         .await
         .expect_err("must refuse");
 
@@ -0,0 +1,199 @@
+//! `RealStripeGateway` timeout and retry policy, exercised against a
+//! local mock of the Stripe API: a slow response counts as a transient
+//! failure and is retried, a 500 is retried the configured number of
+//! times before surfacing as `ServiceUnavailable`, a 400 is not retried
+//! at all, and a retried POST resends the same idempotency key.
+//!
+//! Run with: cargo test --test stripe_retry_test
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use coterie::{
+    error::AppError,
+    payments::gateway::{CreateRefundInput, RealStripeGateway, StripeGateway, StripeRetryPolicy},
+};
+use serde_json::json;
+
+/// What the mock answers with on each request, in order; the last
+/// entry repeats once the script runs out.
+#[derive(Clone, Copy)]
+enum Reply {
+    Ok,
+    ServerError,
+    BadRequest,
+    Hang,
+}
+
+#[derive(Clone)]
+struct Mock {
+    script: Arc<Vec<Reply>>,
+    hits: Arc<AtomicU32>,
+    idempotency_keys: Arc<Mutex<Vec<Option<String>>>>,
+}
+
+impl Mock {
+    fn next(&self, headers: &HeaderMap) -> Reply {
+        let n = self.hits.fetch_add(1, Ordering::SeqCst) as usize;
+        self.idempotency_keys.lock().unwrap().push(
+            headers
+                .get("Idempotency-Key")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        );
+        self.script[n.min(self.script.len() - 1)]
+    }
+}
+
+async fn reply(reply: Reply, ok_body: serde_json::Value) -> Response {
+    let error = |status: StatusCode, kind: &str| {
+        (
+            status,
+            Json(json!({ "error": { "type": kind, "message": "mock failure" } })),
+        )
+            .into_response()
+    };
+    match reply {
+        Reply::Ok => Json(ok_body).into_response(),
+        Reply::ServerError => error(StatusCode::INTERNAL_SERVER_ERROR, "api_error"),
+        Reply::BadRequest => error(StatusCode::BAD_REQUEST, "invalid_request_error"),
+        Reply::Hang => {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Json(ok_body).into_response()
+        }
+    }
+}
+
+async fn list_sessions(State(mock): State<Mock>, headers: HeaderMap) -> Response {
+    let r = mock.next(&headers);
+    reply(
+        r,
+        json!({ "object": "list", "data": [], "has_more": false, "url": "/v1/checkout/sessions" }),
+    )
+    .await
+}
+
+async fn create_refund(State(mock): State<Mock>, headers: HeaderMap) -> Response {
+    let r = mock.next(&headers);
+    reply(
+        r,
+        json!({
+            "id": "re_mock",
+            "object": "refund",
+            "amount": 500,
+            "created": 1_700_000_000,
+            "currency": "usd",
+            "status": "succeeded",
+        }),
+    )
+    .await
+}
+
+/// Serve the mock on an ephemeral port and point a gateway at it.
+async fn gateway(script: Vec<Reply>, max_retries: u32) -> (RealStripeGateway, Mock) {
+    let mock = Mock {
+        script: Arc::new(script),
+        hits: Arc::new(AtomicU32::new(0)),
+        idempotency_keys: Arc::new(Mutex::new(Vec::new())),
+    };
+    let app = Router::new()
+        .route("/v1/checkout/sessions", get(list_sessions))
+        .route("/v1/refunds", post(create_refund))
+        .with_state(mock.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let client = stripe::Client::from_url(format!("http://{}/", addr).as_str(), "sk_test_mock");
+    let policy = StripeRetryPolicy {
+        timeout: Duration::from_millis(200),
+        max_retries,
+        backoff: Duration::from_millis(1),
+    };
+    (RealStripeGateway::with_client(client, policy), mock)
+}
+
+async fn list(gw: &RealStripeGateway) -> Result<Vec<String>, AppError> {
+    gw.list_checkout_sessions_by_intent("pi_mock").await
+}
+
+#[tokio::test]
+async fn server_error_is_retried_the_configured_number_of_times() {
+    let (gw, mock) = gateway(vec![Reply::ServerError], 3).await;
+
+    let err = list(&gw).await.unwrap_err();
+    assert!(matches!(err, AppError::ServiceUnavailable(_)), "{:?}", err);
+    assert_eq!(
+        mock.hits.load(Ordering::SeqCst),
+        4,
+        "one try plus three retries"
+    );
+}
+
+#[tokio::test]
+async fn timeout_is_retryable() {
+    let (gw, mock) = gateway(vec![Reply::Hang, Reply::Ok], 1).await;
+
+    assert_eq!(list(&gw).await.unwrap(), Vec::<String>::new());
+    assert_eq!(mock.hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn timeouts_exhaust_into_service_unavailable() {
+    let (gw, mock) = gateway(vec![Reply::Hang], 1).await;
+
+    let err = list(&gw).await.unwrap_err();
+    assert!(matches!(err, AppError::ServiceUnavailable(_)), "{:?}", err);
+    assert_eq!(mock.hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn client_error_is_not_retried() {
+    let (gw, mock) = gateway(vec![Reply::BadRequest], 3).await;
+
+    let err = list(&gw).await.unwrap_err();
+    assert!(matches!(err, AppError::External(_)), "{:?}", err);
+    assert_eq!(mock.hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn zero_retries_tries_once() {
+    let (gw, mock) = gateway(vec![Reply::ServerError], 0).await;
+
+    assert!(list(&gw).await.is_err());
+    assert_eq!(mock.hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn retried_post_reuses_its_idempotency_key() {
+    let (gw, mock) = gateway(vec![Reply::ServerError, Reply::Hang, Reply::Ok], 2).await;
+
+    let refund = gw
+        .create_refund(CreateRefundInput {
+            payment_intent_id: "pi_mock".to_string(),
+            idempotency_key: "refund-abc".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(refund.id, "re_mock");
+    let keys = mock.idempotency_keys.lock().unwrap().clone();
+    assert_eq!(keys, vec![Some("refund-abc".to_string()); 3]);
+}
+
+#[tokio::test]
+async fn gets_carry_no_idempotency_key() {
+    let (gw, mock) = gateway(vec![Reply::ServerError, Reply::Ok], 1).await;
+
+    list(&gw).await.unwrap();
+    assert_eq!(*mock.idempotency_keys.lock().unwrap(), vec![None, None]);
+}
@@ -16,7 +16,7 @@ use std::sync::{Arc, Mutex};
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use coterie::{
-    auth::SecretCrypto,
+    auth::{AuthService, SecretCrypto},
     domain::{
         BillingMode, CreateMemberRequest, Payer, Payment, PaymentKind, PaymentMethod,
         PaymentStatus, StripeRef,
@@ -25,7 +25,9 @@ use coterie::{
     error::Result as CoterieResult,
     integrations::{Integration, IntegrationEvent, IntegrationManager},
     payments::{
-        fake_gateway::FakeStripeGateway, gateway::StripeGateway, StripeClient, WebhookDispatcher,
+        fake_gateway::{FakeCall, FakeStripeGateway},
+        gateway::StripeGateway,
+        StripeClient, WebhookDispatcher,
     },
     repository::{
         EventRepository, MemberRepository, PaymentRepository, SqliteEventRepository,
@@ -33,7 +35,8 @@ use coterie::{
         SqliteScheduledPaymentRepository,
     },
     service::{
-        billing_service::BillingService, membership_type_service::MembershipTypeService,
+        audit_service::AuditService, billing_service::BillingService,
+        member_service::MemberService, membership_type_service::MembershipTypeService,
         settings_service::SettingsService,
     },
 };
@@ -47,7 +50,7 @@ use common::fresh_pool;
 // ---------------------------------------------------------------------
 // RecordingIntegration — test-only Integration impl that captures every
 // dispatched IntegrationEvent into a shared Vec the test can inspect.
-// Lets the invoice.payment_failed tests verify that AdminAlerts make it
+// Lets the invoice.payment_failed tests verify that admin notifications make it
 // through the IntegrationManager without modifying production code.
 // ---------------------------------------------------------------------
 
@@ -72,24 +75,41 @@ impl Integration for RecordingIntegration {
     }
 }
 
-fn admin_alert_subjects(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<String> {
+fn admin_notification_subjects(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<String> {
     events
         .lock()
         .unwrap()
         .iter()
         .filter_map(|e| match e {
-            IntegrationEvent::AdminAlert { subject, .. } => Some(subject.clone()),
+            IntegrationEvent::AdminNotification { subject, .. } => Some(subject.clone()),
             _ => None,
         })
         .collect()
 }
 
+/// `event_type` tags of the payment lifecycle events recorded so far.
+fn payment_event_types(events: &Arc<Mutex<Vec<IntegrationEvent>>>) -> Vec<&'static str> {
+    events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| {
+            matches!(
+                e,
+                IntegrationEvent::PaymentCompleted(_)
+                    | IntegrationEvent::PaymentFailed(_)
+                    | IntegrationEvent::PaymentRefunded(_)
+            )
+        })
+        .map(|e| e.kind().as_str())
+        .collect()
+}
+
 // ---------------------------------------------------------------------
 // Setup helpers
 // ---------------------------------------------------------------------
 
 struct Harness {
-    #[allow(dead_code)]
     client: StripeClient,
     dispatcher: WebhookDispatcher,
     fake: Arc<FakeStripeGateway>,
@@ -132,6 +152,20 @@ async fn build_harness() -> Harness {
     let processed_events_repo: Arc<dyn coterie::repository::ProcessedEventsRepository> = Arc::new(
         coterie::repository::SqliteProcessedEventsRepository::new(pool.clone()),
     );
+    let member_service = Arc::new(MemberService::new(
+        member_repo.clone(),
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
+        Arc::new(AuditService::new(pool.clone())),
+        integrations.clone(),
+        email_sender.clone(),
+        mt_service.clone(),
+        settings.clone(),
+        pool.clone(),
+        "http://localhost:3000".to_string(),
+    ));
     let dispatcher = WebhookDispatcher::new(
         gw,
         "whsec_test_dummy".to_string(),
@@ -140,6 +174,8 @@ async fn build_harness() -> Harness {
         processed_events_repo,
         mt_service.clone(),
         integrations.clone(),
+        member_service,
+        Arc::new(coterie::payments::WebhookMetrics::default()),
     );
 
     let billing = BillingService::new(
@@ -152,6 +188,10 @@ async fn build_harness() -> Harness {
         settings,
         email_sender,
         integrations,
+        Arc::new(AuthService::new(
+            pool.clone(),
+            "test-secret-please-ignore".to_string(),
+        )),
         None, // stripe_client — none of our tests invoke billing paths that need it
         "http://localhost:3000".to_string(),
         pool.clone(),
@@ -412,6 +452,7 @@ async fn pi_succeeded_retry_does_not_double_extend_dues() {
             description: "Dues".to_string(),
             kind: PaymentKind::Membership,
             paid_at: None,
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         },
@@ -494,6 +535,7 @@ async fn charge_refunded_echo_for_already_refunded_row_is_noop() {
             description: "Dues".to_string(),
             kind: PaymentKind::Membership,
             paid_at: Some(Utc::now()),
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         },
@@ -530,6 +572,10 @@ async fn charge_refunded_echo_for_already_refunded_row_is_noop() {
         updated_at_before, updated_at_after,
         "no UPDATE must run when echo finds row already Refunded"
     );
+    assert!(
+        payment_event_types(&h.recorded_events).is_empty(),
+        "the admin refund already announced it"
+    );
 }
 
 #[tokio::test]
@@ -555,6 +601,7 @@ async fn charge_refunded_for_completed_row_flips_to_refunded() {
             description: "Dues".to_string(),
             kind: PaymentKind::Membership,
             paid_at: Some(Utc::now()),
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         },
@@ -568,6 +615,25 @@ async fn charge_refunded_for_completed_row_flips_to_refunded() {
         .expect("dispatch ok");
 
     assert_eq!(payment_status(&h.pool, payment_id).await, "Refunded");
+    let refunded_at: Option<chrono::NaiveDateTime> =
+        sqlx::query_scalar("SELECT refunded_at FROM payments WHERE id = ?")
+            .bind(payment_id.to_string())
+            .fetch_one(&h.pool)
+            .await
+            .expect("refunded_at");
+    assert!(refunded_at.is_some(), "webhook refund stamps refunded_at");
+
+    let events = h.recorded_events.lock().unwrap();
+    let refunded = events
+        .iter()
+        .find_map(|e| match e {
+            IntegrationEvent::PaymentRefunded(p) => Some(p),
+            _ => None,
+        })
+        .expect("PaymentRefunded dispatched");
+    assert_eq!(refunded.id, payment_id);
+    assert_eq!(refunded.status, PaymentStatus::Refunded);
+    assert!(refunded.refunded_at.is_some());
 }
 
 // ---------------------------------------------------------------------
@@ -645,6 +711,7 @@ async fn public_donation_checkout_completion_marks_payment_completed() {
             description: "Donation — Anonymous".to_string(),
             kind: PaymentKind::Donation { campaign_id: None },
             paid_at: None,
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         },
@@ -689,6 +756,306 @@ async fn public_donation_checkout_completion_marks_payment_completed() {
     assert_eq!(stripe_id.as_deref(), Some("pi_public_donation"));
 }
 
+// ---------------------------------------------------------------------
+// 5. Signup handoff: the first membership Checkout after public signup
+//    activates the still-Pending member; an abandoned one doesn't.
+// ---------------------------------------------------------------------
+
+async fn member_status(pool: &SqlitePool, member_id: Uuid) -> String {
+    sqlx::query_scalar::<_, String>("SELECT status FROM members WHERE id = ?")
+        .bind(member_id.to_string())
+        .fetch_one(pool)
+        .await
+        .expect("query member status")
+}
+
+fn signup_membership_payment(member_id: Uuid, session_id: &str) -> Payment {
+    Payment {
+        id: Uuid::new_v4(),
+        payer: Payer::Member(member_id),
+        amount_cents: 50_00,
+        currency: "USD".to_string(),
+        status: PaymentStatus::Pending,
+        payment_method: PaymentMethod::Stripe,
+        external_id: Some(StripeRef::CheckoutSession(session_id.to_string())),
+        description: "Membership: Member".to_string(),
+        kind: PaymentKind::Membership,
+        paid_at: None,
+        refunded_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn signup_checkout_completion_activates_member_and_sets_dues() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, None, BillingMode::Manual).await;
+    assert_eq!(member_status(&h.pool, member_id).await, "Pending");
+
+    let session_id = "cs_signup_paid";
+    let payment = signup_membership_payment(member_id, session_id);
+    let payment_id = payment.id;
+    insert_pending_payment(&h.pool, payment).await;
+
+    let session = build_checkout_session(
+        session_id,
+        Some("pi_signup_paid"),
+        json!({
+            "payment_type": "membership",
+            "membership_type_slug": "member",
+        }),
+    );
+    h.dispatcher
+        .dispatch_checkout_session_completed(session, &h.billing)
+        .await
+        .expect("dispatch ok");
+
+    assert_eq!(payment_status(&h.pool, payment_id).await, "Completed");
+    assert_eq!(member_status(&h.pool, member_id).await, "Active");
+    let dues = member_dues_paid_until(&h.pool, member_id)
+        .await
+        .expect("dues_paid_until set");
+    assert!(
+        dues > Utc::now(),
+        "dues should run into the future: {}",
+        dues
+    );
+
+    let activated = h
+        .recorded_events
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|e| matches!(e, IntegrationEvent::MemberActivated(m) if m.id == member_id));
+    assert!(activated, "activation should reach integrations");
+}
+
+#[tokio::test]
+async fn abandoned_signup_checkout_leaves_member_pending() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, None, BillingMode::Manual).await;
+
+    let session_id = "cs_signup_abandoned";
+    let payment = signup_membership_payment(member_id, session_id);
+    let payment_id = payment.id;
+    insert_pending_payment(&h.pool, payment).await;
+
+    let session = build_checkout_session(
+        session_id,
+        None,
+        json!({
+            "payment_type": "membership",
+            "membership_type_slug": "member",
+        }),
+    );
+    h.dispatcher
+        .dispatch_checkout_session_expired(session)
+        .await
+        .expect("dispatch ok");
+
+    assert_eq!(payment_status(&h.pool, payment_id).await, "Failed");
+    assert_eq!(member_status(&h.pool, member_id).await, "Pending");
+    assert!(member_dues_paid_until(&h.pool, member_id).await.is_none());
+}
+
+#[tokio::test]
+async fn pi_succeeded_activates_pending_member_and_extends_dues_once() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, Some("cus_first_dues"), BillingMode::Manual).await;
+    assert_eq!(member_status(&h.pool, member_id).await, "Pending");
+
+    let payment = Payment {
+        external_id: None,
+        ..signup_membership_payment(member_id, "unused")
+    };
+    let payment_id = payment.id;
+    insert_pending_payment(&h.pool, payment).await;
+
+    let pi = build_payment_intent(
+        "pi_first_dues",
+        50_00,
+        json!({
+            "payment_id": payment_id.to_string(),
+            "member_id": member_id.to_string(),
+            "membership_type_slug": "member",
+        }),
+    );
+    h.dispatcher
+        .dispatch_payment_intent_succeeded(pi.clone(), &h.billing)
+        .await
+        .expect("first dispatch ok");
+
+    assert_eq!(member_status(&h.pool, member_id).await, "Active");
+    let dues_after_first = member_dues_paid_until(&h.pool, member_id)
+        .await
+        .expect("dues_paid_until set");
+    assert!(dues_after_first > Utc::now());
+
+    // Stripe retry of the same event: nothing moves, nothing re-fires.
+    h.dispatcher
+        .dispatch_payment_intent_succeeded(pi, &h.billing)
+        .await
+        .expect("retry dispatch ok");
+    assert_eq!(
+        member_dues_paid_until(&h.pool, member_id).await,
+        Some(dues_after_first)
+    );
+
+    let activations = h
+        .recorded_events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| matches!(e, IntegrationEvent::MemberActivated(m) if m.id == member_id))
+        .count();
+    assert_eq!(activations, 1, "MemberActivated should fire exactly once");
+}
+
+#[tokio::test]
+async fn checkout_completion_does_not_lift_suspension() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, None, BillingMode::Manual).await;
+    sqlx::query("UPDATE members SET status = 'Suspended' WHERE id = ?")
+        .bind(member_id.to_string())
+        .execute(&h.pool)
+        .await
+        .unwrap();
+
+    let session_id = "cs_suspended_renewal";
+    insert_pending_payment(&h.pool, signup_membership_payment(member_id, session_id)).await;
+    let session = build_checkout_session(
+        session_id,
+        Some("pi_suspended_renewal"),
+        json!({
+            "payment_type": "membership",
+            "membership_type_slug": "member",
+        }),
+    );
+    h.dispatcher
+        .dispatch_checkout_session_completed(session, &h.billing)
+        .await
+        .expect("dispatch ok");
+
+    assert_eq!(member_status(&h.pool, member_id).await, "Suspended");
+}
+
+#[tokio::test]
+async fn checkout_completion_revives_lapsed_member_once() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, None, BillingMode::Manual).await;
+    sqlx::query(
+        "UPDATE members SET status = 'Expired', dues_paid_until = datetime('now', '-10 days') \
+         WHERE id = ?",
+    )
+    .bind(member_id.to_string())
+    .execute(&h.pool)
+    .await
+    .unwrap();
+
+    let session_id = "cs_lapsed_renewal";
+    insert_pending_payment(&h.pool, signup_membership_payment(member_id, session_id)).await;
+    let session = build_checkout_session(
+        session_id,
+        Some("pi_lapsed_renewal"),
+        json!({
+            "payment_type": "membership",
+            "membership_type_slug": "member",
+        }),
+    );
+    h.dispatcher
+        .dispatch_checkout_session_completed(session.clone(), &h.billing)
+        .await
+        .expect("dispatch ok");
+
+    assert_eq!(member_status(&h.pool, member_id).await, "Active");
+    let dues = member_dues_paid_until(&h.pool, member_id)
+        .await
+        .expect("dues_paid_until set");
+    assert!(
+        dues > Utc::now(),
+        "a lapsed member's term starts today: {}",
+        dues
+    );
+
+    // Stripe retry: dues stay put and integrations hear it only once.
+    h.dispatcher
+        .dispatch_checkout_session_completed(session, &h.billing)
+        .await
+        .expect("retry dispatch ok");
+    assert_eq!(member_dues_paid_until(&h.pool, member_id).await, Some(dues));
+
+    let activations = h
+        .recorded_events
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|e| matches!(e, IntegrationEvent::MemberActivated(m) if m.id == member_id))
+        .count();
+    assert_eq!(activations, 1, "revival should reach integrations once");
+    assert_eq!(
+        payment_event_types(&h.recorded_events),
+        vec!["payment_completed"]
+    );
+}
+
+#[tokio::test]
+async fn member_checkout_session_round_trips_through_the_webhook() {
+    let h = build_harness().await;
+    let member_id = insert_member(&h.pool, None, BillingMode::Manual).await;
+
+    let (url, payment_id) = h
+        .client
+        .create_membership_checkout_session(
+            member_id,
+            "Member",
+            "member",
+            50_00,
+            "http://localhost:3000/portal/payments/success".to_string(),
+            "http://localhost:3000/portal/payments/cancel".to_string(),
+        )
+        .await
+        .expect("checkout session");
+    assert!(url.starts_with("https://checkout.stripe.test/"), "{}", url);
+    assert_eq!(payment_status(&h.pool, payment_id).await, "Pending");
+
+    // The session carries enough metadata for the webhook to reconcile
+    // without looking anything up.
+    let input = h
+        .fake
+        .calls()
+        .into_iter()
+        .find_map(|c| match c {
+            FakeCall::CreateCheckoutSession(input) => Some(input),
+            _ => None,
+        })
+        .expect("checkout session created");
+    assert_eq!(input.metadata["member_id"], member_id.to_string());
+    assert_eq!(input.metadata["membership_type_slug"], "member");
+    assert_eq!(input.line_items[0].amount_cents, 50_00);
+
+    let session_id: String =
+        sqlx::query_scalar("SELECT stripe_payment_id FROM payments WHERE id = ?")
+            .bind(payment_id.to_string())
+            .fetch_one(&h.pool)
+            .await
+            .unwrap();
+    let session = build_checkout_session(
+        &session_id,
+        Some("pi_self_service"),
+        serde_json::to_value(&input.metadata).unwrap(),
+    );
+    h.dispatcher
+        .dispatch_checkout_session_completed(session, &h.billing)
+        .await
+        .expect("dispatch ok");
+
+    assert_eq!(payment_status(&h.pool, payment_id).await, "Completed");
+    assert!(member_dues_paid_until(&h.pool, member_id)
+        .await
+        .is_some_and(|d| d > Utc::now()));
+}
+
 // ---------------------------------------------------------------------
 // Sanity assertion that none of the above quietly drove gateway calls
 // ---------------------------------------------------------------------
@@ -720,6 +1087,7 @@ async fn webhook_handlers_do_not_call_gateway_unnecessarily() {
             description: "Donation".to_string(),
             kind: PaymentKind::Donation { campaign_id: None },
             paid_at: Some(Utc::now()),
+            refunded_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         },
@@ -974,12 +1342,12 @@ async fn invoice_paid_for_unknown_subscription_is_noop() {
 }
 
 // ---------------------------------------------------------------------
-// 6. invoice.payment_failed: dispatches AdminAlert on first attempt,
+// 6. invoice.payment_failed: notifies admins on first attempt,
 //    softens copy on final attempt, no-op for unknown subscription.
 // ---------------------------------------------------------------------
 
 #[tokio::test]
-async fn invoice_payment_failed_dispatches_admin_alert() {
+async fn invoice_payment_failed_notifies_admins() {
     let h = build_harness().await;
     let customer_id = "cus_sub_failed";
     let member_id =
@@ -1004,12 +1372,12 @@ async fn invoice_payment_failed_dispatches_admin_alert() {
         .await
         .expect("dispatch_invoice_payment_failed");
 
-    let subjects = admin_alert_subjects(&h.recorded_events);
+    let subjects = admin_notification_subjects(&h.recorded_events);
     assert!(
         subjects
             .iter()
             .any(|s| s.contains("Stripe subscription charge failed")),
-        "expected AdminAlert subject containing 'Stripe subscription charge failed'; got {:?}",
+        "expected a notification subject containing 'Stripe subscription charge failed'; got {:?}",
         subjects,
     );
     assert!(
@@ -1045,7 +1413,7 @@ async fn invoice_payment_failed_final_attempt_softens_copy() {
     // Final attempt: next_payment_attempt = None signals Stripe is
     // done retrying. handle_invoice_payment_failed should pass
     // is_final = true to notify_subscription_payment_failed, which
-    // formats the AdminAlert subject with the "(final)" suffix.
+    // formats the notification subject with the "(final)" suffix.
     let invoice = build_invoice(
         "in_test_failed_final",
         customer_id,
@@ -1061,10 +1429,10 @@ async fn invoice_payment_failed_final_attempt_softens_copy() {
         .await
         .expect("dispatch_invoice_payment_failed (final)");
 
-    let subjects = admin_alert_subjects(&h.recorded_events);
+    let subjects = admin_notification_subjects(&h.recorded_events);
     assert!(
         subjects.iter().any(|s| s.contains("(final)")),
-        "final-attempt AdminAlert subject must contain '(final)'; got {:?}",
+        "final-attempt notification subject must contain '(final)'; got {:?}",
         subjects,
     );
 }
@@ -1094,10 +1462,10 @@ async fn invoice_payment_failed_for_unknown_subscription_is_noop() {
         .await
         .expect("dispatch should succeed quietly");
 
-    let subjects = admin_alert_subjects(&h.recorded_events);
+    let subjects = admin_notification_subjects(&h.recorded_events);
     assert!(
         subjects.is_empty(),
-        "no AdminAlert should be dispatched for an unknown subscription; got {:?}",
+        "no admin notification should be sent for an unknown subscription; got {:?}",
         subjects,
     );
     let dues_after = member_dues_paid_until(&h.pool, member_id)